@@ -1,28 +1,51 @@
+use self::events::EventBus;
+use self::server::cast::{CastCommand, CastDevice};
+use self::server::downloader::DownloadSettings;
+use self::server::export::{ExportSettings, SongTags};
+use self::server::structures::SearchFilter;
 use self::structures::{ListSong, ListSongID};
-use self::taskmanager::{AppRequest, TaskManager};
-use self::ui::WindowContext;
+use self::taskmanager::{AppRequest, TaskID, TaskManager};
+use self::ui::{ConfirmAction, WindowContext};
 use super::appevent::{AppEvent, EventHandler};
 use super::Result;
+use crate::config::{Config, LiveSettings};
+use crate::drawutils;
 use crate::error::Error;
-use crate::RuntimeInfo;
+use crate::i18n;
+use crate::remote::RemoteRequest;
+use crate::{get_data_dir, load_api_key, setup, RuntimeInfo};
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::widgets::{ListState, TableState};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::borrow::Cow;
-use std::{io, sync::Arc};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
-use tracing::info;
+use tracing::{error, info, warn};
+use tracing_subscriber::filter::Targets;
 use tracing_subscriber::prelude::*;
+use ui::albumart::AlbumArtState;
+use ui::logger::{LogFilter, NewLogRecordsLayer};
+use ui::songdetails::SongDetails;
+use ui::toast::{NotifyLevel, UIMessage};
 use ui::YoutuiWindow;
+use ytmapi_rs::common::{AlbumID, LikeStatus, PlaylistID, PodcastID};
 use ytmapi_rs::{ChannelID, VideoID};
 
+pub use ui::get_default_help_entries;
+
 mod component;
-mod keycommand;
-mod musiccache;
+mod crashreport;
+mod datafile;
+mod events;
+pub mod headless;
+pub mod keycommand;
+mod logfile;
+pub mod platform;
 mod server;
 mod structures;
 mod taskmanager;
@@ -31,16 +54,28 @@ mod view;
 
 const CALLBACK_CHANNEL_SIZE: usize = 64;
 const EVENT_CHANNEL_SIZE: usize = 256;
-const _LOG_FILE_NAME: &str = "debug.log";
+const LOG_FILE_NAME: &str = "debug.log";
+/// Size at which the log file is rotated.
+const LOG_FILE_MAX_SIZE: u64 = 5 * 1024 * 1024;
+/// Number of rotated log files kept, in addition to the current one.
+const LOG_FILE_BACKUPS: usize = 3;
 
 pub struct Youtui {
     status: AppStatus,
+    /// Set when something drawn may have changed, so the next frame needs to be drawn.
+    redraw: bool,
+    /// The last frame drawn contained an animation, so should be drawn again on the next tick.
+    animated: bool,
     event_handler: EventHandler,
     window_state: YoutuiWindow,
     window_mutable_state: YoutuiMutableState,
     task_manager: TaskManager,
+    set_log_filter: Box<dyn Fn(Targets)>,
     callback_rx: mpsc::Receiver<AppCallback>,
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    /// Settings from the config file that apply without restarting. None if the config file
+    /// couldn't be reloaded after it was last edited.
+    live_settings: Option<LiveSettings>,
 }
 
 // Mutable state for scrollable widgets.
@@ -50,8 +85,21 @@ pub struct YoutuiMutableState {
     pub filter_state: ListState,
     pub help_state: TableState,
     pub browser_album_songs_state: TableState,
+    pub browser_search_results_state: TableState,
     pub browser_artists_state: ListState,
     pub playlist_state: TableState,
+    pub library_playlists_state: TableState,
+    pub library_albums_state: TableState,
+    pub library_artists_state: TableState,
+    pub charts_state: TableState,
+    pub artist_details_state: TableState,
+    pub album_art_state: AlbumArtState,
+    pub podcasts_list_state: TableState,
+    pub podcast_episodes_state: TableState,
+    pub recently_played_state: TableState,
+    pub favorites_state: TableState,
+    /// Rows visible in the main pane at the last draw.
+    pub page_lines: usize,
 }
 
 #[derive(PartialEq)]
@@ -65,68 +113,231 @@ pub enum AppStatus {
 #[derive(Debug)]
 pub enum AppCallback {
     DownloadSong(VideoID<'static>, ListSongID),
+    /// Get the album art at the url for the song.
+    GetAlbumArt(String, ListSongID),
     GetVolume,
     GetProgress(ListSongID),
     Quit,
     ChangeContext(WindowContext),
     // Perhaps shiould not be here.
     HandleApiError(Error),
+    /// Load the credentials from the config directory again, and sign in using them.
+    RetrySignIn,
+    /// Run the setup wizard to sign in again, e.g once the credentials have expired.
+    RerunSetup,
     IncreaseVolume(i8),
     SearchArtist(String),
+    /// Get the next page of an artist search, using the continuation from the previous page.
+    SearchArtistsContinuation(String),
+    Search(String, SearchFilter),
     GetSearchSuggestions(String),
     GetArtistSongs(ChannelID<'static>),
+    /// Display the songs for an artist in the browser.
+    ViewArtist(ChannelID<'static>),
+    GetArtistDetails(ChannelID<'static>),
+    /// Display the description, top releases and related artists for an artist.
+    ViewArtistDetails(ChannelID<'static>),
+    GetPlaylistSongs(PlaylistID<'static>),
+    /// Display the songs from a YouTube Music playlist in the browser.
+    ViewPlaylistSongs(PlaylistID<'static>),
+    /// Add all the songs from a YouTube Music playlist to the queue.
+    QueuePlaylistSongs(PlaylistID<'static>),
+    ListAlbumSongs(AlbumID<'static>),
+    /// Display the songs from an album in the browser.
+    ViewAlbum(AlbumID<'static>),
+    GetLibrary,
+    /// Get the charts for a country, or the global charts if None.
+    GetCharts(Option<String>),
+    GetLibraryPodcasts,
+    GetPodcastEpisodes(PodcastID<'static>),
+    /// Add all the episodes from a podcast to the queue.
+    QueuePodcastEpisodes(PodcastID<'static>),
+    GetLyrics(VideoID<'static>),
+    GetLibraryPlaylists,
+    /// Open a popup to choose a YouTube Music playlist to add a song to.
+    ShowAddToLibraryPlaylist(VideoID<'static>, String),
+    /// Open a popup with the full metadata for a song.
+    ShowSongDetails(SongDetails),
+    /// Star a song in the local favorites, or unstar it if already starred.
+    ToggleStarSong(Box<ListSong>),
+    /// Save songs to the export directory as tagged files.
+    ExportSongs(Vec<ListSong>),
+    AddSongToLibraryPlaylist(PlaylistID<'static>, VideoID<'static>),
+    RateSong(VideoID<'static>, LikeStatus, ListSongID),
+    GetLibrarySubscriptions,
+    /// Subscribe to the artist if true, otherwise unsubscribe.
+    SetArtistSubscribed(ChannelID<'static>, bool),
+    GetAlbumSongs {
+        album_id: AlbumID<'static>,
+        artist: String,
+        play: bool,
+    },
     AddSongsToPlaylist(Vec<ListSong>),
     AddSongsToPlaylistAndPlay(Vec<ListSong>),
     PlaySong(Arc<Vec<u8>>, ListSongID),
     PausePlay(ListSongID),
     Stop(ListSongID),
+    /// Kill a single in-flight task.
+    KillTask(TaskID),
+    SetLogFilter(LogFilter),
+    /// Ask the user to confirm an action before running it.
+    Confirm(String, ConfirmAction),
+    CopyToClipboard(String),
+    /// Open a url in the system's default web browser.
+    OpenInBrowser(String),
+    /// Find Google Cast devices on the local network.
+    FindCastDevices,
+    /// Play the songs on the cast device, starting from the song at the index.
+    CastQueue(CastDevice, Vec<VideoID<'static>>, usize),
+    CastCommand(CastCommand),
+    StopCasting,
 }
 
 impl Youtui {
     pub fn new(rt: RuntimeInfo) -> Result<Youtui> {
-        let RuntimeInfo { api_key, .. } = rt;
-        // TODO: Handle errors
+        let RuntimeInfo {
+            api_key,
+            config,
+            log_level,
+            ..
+        } = rt;
         // Setup tracing and link to tui_logger.
         let tui_logger_layer = tui_logger::tracing_subscriber_layer();
-        // Hold off implementing log file until dirs improved.
-        // let log_file = std::fs::File::create(get_data_dir()?.join(LOG_FILE_NAME))?;
-        // let log_file_layer = tracing_subscriber::fmt::layer().with_writer(Arc::new(log_file));
-        // TODO: Confirm if this filter is correct.
-        let context_layer =
-            tracing_subscriber::filter::Targets::new().with_target("youtui", tracing::Level::DEBUG);
+        // The Logs pane filter can be changed at runtime, and doesn't affect the log file.
+        let (log_pane_filter, log_filter_handle) =
+            tracing_subscriber::reload::Layer::new(LogFilter::default().to_targets());
+        let set_log_filter = Box::new(move |targets: Targets| {
+            log_filter_handle
+                .reload(targets)
+                .unwrap_or_else(|e| error!("Error setting log filter: {e}"))
+        });
+        let log_file_path = get_data_dir()?.join(LOG_FILE_NAME);
+        let log_file_layer = match log_level.to_level() {
+            Some(level) => {
+                let log_file = logfile::RotatingLogFile::open(
+                    &log_file_path,
+                    LOG_FILE_MAX_SIZE,
+                    LOG_FILE_BACKUPS,
+                )?;
+                let targets = Targets::new()
+                    .with_target("youtui", level)
+                    .with_target("ytmapi_rs", level);
+                Some(
+                    tracing_subscriber::fmt::layer()
+                        .with_writer(Mutex::new(log_file))
+                        .with_ansi(false)
+                        .with_filter(targets),
+                )
+            }
+            None => None,
+        };
         tracing_subscriber::registry()
             .with(
-                tui_logger_layer, // Hold off from implementing log file until dirs support improved.
-                                  // .and_then(log_file_layer)
+                tui_logger_layer
+                    .and_then(NewLogRecordsLayer)
+                    .with_filter(log_pane_filter),
             )
-            .with(context_layer)
+            .with(log_file_layer)
             .init();
         info!("Starting");
+        let auth_type = api_key.get_auth_type();
+        match auth_type {
+            Some(auth_type) => info!("Using {auth_type:?} credentials"),
+            None => info!("Using demo responses"),
+        }
         // Setup terminal
         enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        let crash_reporter = crashreport::CrashReporter::new(
+            get_data_dir()?,
+            log_level.to_level().map(|_| log_file_path),
+            config.get_sanitized_summary()?,
+        );
         // Ensure clean return to shell if panic.
-        std::panic::set_hook(Box::new(|panic_info| {
+        std::panic::set_hook(Box::new(move |panic_info| {
             // If we fail to destruct terminal, ignore the error as panicking anyway.
             let _ = destruct_terminal();
             println!("{}", panic_info);
+            match crash_reporter.write_report(&panic_info.to_string()) {
+                Ok(path) => println!(
+                    "Crash report written to {}. Please include it when reporting this bug.",
+                    path.display()
+                ),
+                Err(e) => println!("Unable to write crash report: {e}"),
+            }
         }));
+        let live_settings = config.get_live_settings()?;
+        drawutils::set_ascii_icons(live_settings.ascii_icons);
+        drawutils::set_high_contrast(live_settings.high_contrast);
+        i18n::set_locale(config.get_locale());
+        // Keybinds are named by their Action's description, which needs the locale.
+        keycommand::set_keybind_overrides(&live_settings.keybinds);
+        let auth_fallback = auth_type
+            .filter(|auth_type| *auth_type != config.get_auth_type())
+            .map(|auth_type| {
+                format!(
+                    "Configured {:?} credentials unavailable, using {auth_type:?} credentials \
+                     instead",
+                    config.get_auth_type()
+                )
+            });
         // Setup components
         let (callback_tx, callback_rx) = mpsc::channel(CALLBACK_CHANNEL_SIZE);
-        let task_manager = taskmanager::TaskManager::new(api_key);
+        let task_manager = taskmanager::TaskManager::new(
+            api_key,
+            get_download_settings(&config)?,
+            config.get_output_settings(),
+            config.get_task_timeout(),
+        );
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
-        let event_handler = EventHandler::new(EVENT_CHANNEL_SIZE)?;
-        let window_state = YoutuiWindow::new(callback_tx);
+        let mut event_handler = EventHandler::new(EVENT_CHANNEL_SIZE, config.get_tick_rate())?;
+        event_handler.start_remote_watcher();
+        event_handler.start_config_watcher();
+        if let Some(settings) = config.get_web_remote_settings() {
+            event_handler.start_web_remote(settings);
+        }
+        let events = EventBus::default();
+        if let Some(command) = config.get_event_hook_command() {
+            events.start_hook_command(command.to_string());
+        }
+        let mut window_state = YoutuiWindow::new(
+            callback_tx,
+            live_settings.album_art_position,
+            config.get_pane_sizes(),
+            live_settings.status_format.clone(),
+            events,
+        );
+        window_state.set_cast_enabled(config.get_cast_enabled());
+        window_state.restore_session();
+        for warning in config.get_warnings() {
+            window_state.handle_ui_message(UIMessage::Notify(
+                NotifyLevel::Warning,
+                format!("Config file: {warning}"),
+            ));
+        }
+        if let Some(message) = auth_fallback {
+            warn!("{message}");
+            window_state.handle_ui_message(UIMessage::Notify(NotifyLevel::Warning, message));
+        }
+        // Terminal graphics support is queried once the alternate screen has been entered.
+        let window_mutable_state = YoutuiMutableState {
+            album_art_state: AlbumArtState::new(),
+            ..Default::default()
+        };
         Ok(Youtui {
             status: AppStatus::Running,
+            redraw: true,
+            animated: false,
             terminal,
             event_handler,
             window_state,
-            window_mutable_state: Default::default(),
+            window_mutable_state,
             task_manager,
             callback_rx,
+            set_log_filter,
+            live_settings: Some(live_settings),
         })
     }
     pub async fn run(&mut self) -> Result<()> {
@@ -141,24 +352,50 @@ impl Youtui {
                     self.synchronize_state().await;
                     // Write to terminal, using UI state as the input
                     // We draw after handling the event, as the event could be a keypress we want to instantly react to.
-                    self.terminal.draw(|f| {
-                        ui::draw::draw_app(f, &self.window_state, &mut self.window_mutable_state);
-                    })?;
+                    // Drawing is skipped if nothing has changed, to save CPU.
+                    if self.redraw {
+                        self.draw()?;
+                    }
                 }
                 AppStatus::Exiting(s) => {
+                    self.window_state.save_session();
                     // Once we're done running, destruct the terminal and print the exit message.
-                    destruct_terminal()?;
-                    println!("{s}");
+                    // The terminal may already be gone, e.g after SIGHUP, so failing to write to
+                    // it doesn't stop the exit.
+                    if let Err(e) = destruct_terminal() {
+                        warn!("Error <{e}> restoring terminal");
+                    }
+                    let _ = writeln!(io::stdout(), "{s}");
                     break;
                 }
             }
         }
         Ok(())
     }
+    fn draw(&mut self) -> Result<()> {
+        self.terminal.draw(|f| {
+            ui::draw::draw_app(f, &self.window_state, &mut self.window_mutable_state);
+        })?;
+        self.window_state
+            .set_page_lines(self.window_mutable_state.page_lines);
+        self.animated = drawutils::take_animated();
+        self.redraw = false;
+        Ok(())
+    }
     async fn synchronize_state(&mut self) {
-        self.task_manager
+        if self
+            .task_manager
             .action_messages(&mut self.window_state)
-            .await;
+            .await
+        {
+            self.redraw = true;
+        }
+        // Task ages change even if no messages are received.
+        if self.window_state.is_tasks_shown() {
+            self.window_state
+                .set_running_tasks(self.task_manager.get_running_tasks());
+            self.redraw = true;
+        }
     }
     async fn handle_next_event(&mut self) {
         let msg = self.event_handler.next().await;
@@ -167,24 +404,122 @@ impl Youtui {
             Some(AppEvent::QuitSignal) => {
                 self.status = AppStatus::Exiting("Quit signal received".into())
             }
-            Some(AppEvent::Crossterm(e)) => self.window_state.handle_event(e).await,
+            // Includes terminal resizes, which always need a redraw.
+            Some(AppEvent::Crossterm(e)) => {
+                self.window_state.handle_event(e).await;
+                self.redraw = true;
+            }
             // XXX: Should be try_poll or similar? Poll the Future but don't await it?
-            Some(AppEvent::Tick) => self.window_state.handle_tick().await,
+            Some(AppEvent::Tick) => {
+                if self.window_state.handle_tick().await || self.animated {
+                    self.redraw = true;
+                }
+            }
+            Some(AppEvent::Remote(RemoteRequest { command, reply_tx })) => {
+                let reply = self.window_state.handle_remote_command(command).await;
+                // The remote may have disconnected without waiting for a reply.
+                let _ = reply_tx.send(reply);
+                self.redraw = true;
+            }
+            Some(AppEvent::ConfigChanged) => {
+                self.reload_config().await;
+                self.redraw = true;
+            }
+            Some(AppEvent::SuspendSignal) => {
+                if let Err(e) = self.suspend() {
+                    error!("Error <{e}> suspending");
+                }
+                self.redraw = true;
+            }
+            // The process may have been stopped without warning, e.g by SIGSTOP, leaving the
+            // terminal to something else in the meantime.
+            Some(AppEvent::ContinueSignal) => {
+                if let Err(e) = self.take_back_terminal() {
+                    error!("Error <{e}> taking back terminal");
+                }
+                self.redraw = true;
+            }
             None => panic!("Channel closed"),
         }
     }
+    /// Restore the terminal and stop the process, as SIGTSTP would have if it wasn't caught.
+    /// Returns once the process is continued, e.g by `fg`, with the terminal taken back.
+    fn suspend(&mut self) -> Result<()> {
+        info!("Suspending");
+        destruct_terminal()?;
+        platform::stop_process()?;
+        info!("Continuing");
+        self.take_back_terminal()
+    }
+    fn take_back_terminal(&mut self) -> Result<()> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        // The terminal's contents can't be relied on, so the next draw is in full.
+        self.terminal.clear()?;
+        Ok(())
+    }
+    /// Reload the config file after it was edited, applying the settings that can change
+    /// without restarting.
+    async fn reload_config(&mut self) {
+        let reloaded = Config::new().and_then(|config| Ok((config.get_live_settings()?, config)));
+        let (live_settings, config) = match reloaded {
+            Ok(reloaded) => reloaded,
+            Err(e) => {
+                self.live_settings = None;
+                self.window_state.handle_ui_message(UIMessage::Notify(
+                    NotifyLevel::Error,
+                    format!("Config file not reloaded: {e}"),
+                ));
+                return;
+            }
+        };
+        // The app also writes to the config file, e.g when panes are resized.
+        if self.live_settings.as_ref() == Some(&live_settings) {
+            return;
+        }
+        drawutils::set_ascii_icons(live_settings.ascii_icons);
+        drawutils::set_high_contrast(live_settings.high_contrast);
+        keycommand::set_keybind_overrides(&live_settings.keybinds);
+        let output_changed = self.live_settings.as_ref().map(|s| &s.output_settings)
+            != Some(&live_settings.output_settings);
+        if output_changed {
+            self.task_manager
+                .set_output(live_settings.output_settings)
+                .await;
+        }
+        self.window_state.apply_live_settings(&live_settings);
+        self.live_settings = Some(live_settings);
+        self.window_state.handle_ui_message(UIMessage::Notify(
+            NotifyLevel::Info,
+            "Config file reloaded".into(),
+        ));
+        for warning in config.get_warnings() {
+            self.window_state.handle_ui_message(UIMessage::Notify(
+                NotifyLevel::Warning,
+                format!("Config file: {warning}"),
+            ));
+        }
+    }
     pub async fn process_callbacks(&mut self) {
         while let Ok(msg) = self.callback_rx.try_recv() {
+            self.redraw = true;
             match msg {
                 AppCallback::DownloadSong(video_id, playlist_id) => {
                     self.task_manager
                         .send_request(AppRequest::Download(video_id, playlist_id))
                         .await;
                 }
+                AppCallback::GetAlbumArt(url, song_id) => {
+                    self.task_manager
+                        .send_request(AppRequest::GetAlbumArt(url, song_id))
+                        .await;
+                }
                 AppCallback::Quit => self.status = AppStatus::Exiting("Quitting".into()),
                 AppCallback::HandleApiError(e) => {
                     self.status = AppStatus::Exiting(format!("{e}").into())
                 }
+                AppCallback::RetrySignIn => self.retry_sign_in().await,
+                AppCallback::RerunSetup => self.rerun_setup().await,
 
                 AppCallback::ChangeContext(context) => {
                     self.window_state.handle_change_context(context)
@@ -204,6 +539,129 @@ impl Youtui {
                         .send_request(AppRequest::SearchArtists(artist))
                         .await;
                 }
+                AppCallback::SearchArtistsContinuation(continuation) => {
+                    self.task_manager
+                        .send_request(AppRequest::SearchArtistsContinuation(continuation))
+                        .await;
+                }
+                AppCallback::Search(query, filter) => {
+                    let request = match filter {
+                        SearchFilter::Artists => AppRequest::SearchArtists(query),
+                        SearchFilter::Songs => AppRequest::SearchSongs(query),
+                        SearchFilter::Albums => AppRequest::SearchAlbums(query),
+                        SearchFilter::Playlists => AppRequest::SearchPlaylists(query),
+                        SearchFilter::Videos => AppRequest::SearchVideos(query),
+                        SearchFilter::Podcasts => AppRequest::SearchPodcasts(query),
+                        SearchFilter::All => AppRequest::SearchAll(query),
+                    };
+                    self.task_manager.send_request(request).await;
+                }
+                AppCallback::GetAlbumSongs {
+                    album_id,
+                    artist,
+                    play,
+                } => {
+                    self.task_manager
+                        .send_request(AppRequest::GetAlbumSongs {
+                            album_id,
+                            artist,
+                            play,
+                        })
+                        .await;
+                }
+                AppCallback::ViewArtist(id) => self.window_state.handle_view_artist(id).await,
+                AppCallback::GetArtistDetails(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::GetArtistDetails(id))
+                        .await;
+                }
+                AppCallback::ViewArtistDetails(id) => {
+                    self.window_state.handle_view_artist_details(id).await
+                }
+                AppCallback::ViewPlaylistSongs(id) => {
+                    self.window_state.handle_view_playlist_songs(id).await
+                }
+                AppCallback::GetPlaylistSongs(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::GetPlaylistSongs(id))
+                        .await;
+                }
+                AppCallback::QueuePlaylistSongs(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::QueuePlaylistSongs(id))
+                        .await;
+                }
+                AppCallback::ListAlbumSongs(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::ListAlbumSongs(id))
+                        .await;
+                }
+                AppCallback::ViewAlbum(id) => self.window_state.handle_view_album(id).await,
+                AppCallback::GetLibrary => {
+                    self.task_manager.send_request(AppRequest::GetLibrary).await;
+                }
+                AppCallback::GetCharts(country) => {
+                    self.task_manager
+                        .send_request(AppRequest::GetCharts(country))
+                        .await;
+                }
+                AppCallback::GetLibraryPodcasts => {
+                    self.task_manager
+                        .send_request(AppRequest::GetLibraryPodcasts)
+                        .await;
+                }
+                AppCallback::GetPodcastEpisodes(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::GetPodcastEpisodes(id))
+                        .await;
+                }
+                AppCallback::QueuePodcastEpisodes(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::QueuePodcastEpisodes(id))
+                        .await;
+                }
+                AppCallback::GetLyrics(id) => {
+                    self.task_manager
+                        .send_request(AppRequest::GetLyrics(id))
+                        .await;
+                }
+                AppCallback::GetLibraryPlaylists => {
+                    self.task_manager
+                        .send_request(AppRequest::GetLibraryPlaylists)
+                        .await;
+                }
+                AppCallback::ShowAddToLibraryPlaylist(video_id, title) => {
+                    self.window_state
+                        .handle_show_add_to_library_playlist(video_id, title)
+                        .await
+                }
+                AppCallback::ShowSongDetails(details) => {
+                    self.window_state.handle_show_song_details(details)
+                }
+                AppCallback::ToggleStarSong(song) => {
+                    self.window_state.handle_toggle_star_song(*song)
+                }
+                AppCallback::ExportSongs(songs) => self.export_songs(songs).await,
+                AppCallback::AddSongToLibraryPlaylist(playlist_id, video_id) => {
+                    self.task_manager
+                        .send_request(AppRequest::AddSongToLibraryPlaylist(playlist_id, video_id))
+                        .await;
+                }
+                AppCallback::GetLibrarySubscriptions => {
+                    self.task_manager
+                        .send_request(AppRequest::GetLibrarySubscriptions)
+                        .await;
+                }
+                AppCallback::SetArtistSubscribed(channel_id, subscribed) => {
+                    self.task_manager
+                        .send_request(AppRequest::SetArtistSubscribed(channel_id, subscribed))
+                        .await;
+                }
+                AppCallback::RateSong(video_id, rating, song_id) => {
+                    self.task_manager
+                        .send_request(AppRequest::RateSong(video_id, rating, song_id))
+                        .await;
+                }
                 AppCallback::GetArtistSongs(id) => {
                     self.task_manager
                         .send_request(AppRequest::GetArtistSongs(id))
@@ -218,6 +676,7 @@ impl Youtui {
                         .await
                 }
                 AppCallback::PlaySong(song, id) => {
+                    self.window_state.handle_song_played(id);
                     self.task_manager
                         .send_request(AppRequest::PlaySong(song, id))
                         .await;
@@ -231,6 +690,13 @@ impl Youtui {
                 AppCallback::Stop(id) => {
                     self.task_manager.send_request(AppRequest::Stop(id)).await;
                 }
+                AppCallback::KillTask(id) => self.task_manager.kill_task(id),
+                AppCallback::Confirm(message, action) => {
+                    self.window_state.handle_show_confirm(message, action)
+                }
+                AppCallback::CopyToClipboard(text) => self.window_state.copy_to_clipboard(text),
+                AppCallback::OpenInBrowser(url) => self.window_state.open_in_browser(url),
+                AppCallback::SetLogFilter(filter) => (self.set_log_filter)(filter.to_targets()),
                 AppCallback::GetVolume => {
                     self.task_manager.send_request(AppRequest::GetVolume).await;
                 }
@@ -239,9 +705,87 @@ impl Youtui {
                         .send_request(AppRequest::GetPlayProgress(id))
                         .await;
                 }
+                AppCallback::FindCastDevices => {
+                    self.task_manager
+                        .send_request(AppRequest::FindCastDevices)
+                        .await;
+                }
+                AppCallback::CastQueue(device, video_ids, index) => {
+                    self.task_manager
+                        .send_request(AppRequest::CastQueue(device, video_ids, index))
+                        .await;
+                }
+                AppCallback::CastCommand(command) => {
+                    self.task_manager
+                        .send_request(AppRequest::CastCommand(command))
+                        .await;
+                }
+                AppCallback::StopCasting => {
+                    self.task_manager
+                        .send_request(AppRequest::StopCasting)
+                        .await;
+                }
+            }
+        }
+    }
+    async fn export_songs(&mut self, songs: Vec<ListSong>) {
+        if songs.is_empty() {
+            return;
+        }
+        self.window_state.handle_ui_message(UIMessage::Notify(
+            NotifyLevel::Info,
+            i18n::tr_format("Exporting {} songs", &[&songs.len()]),
+        ));
+        for song in songs {
+            let video_id = song.raw.get_video_id().clone();
+            self.task_manager
+                .send_request(AppRequest::ExportSong(video_id, SongTags::from_song(&song)))
+                .await;
+        }
+    }
+    async fn retry_sign_in(&mut self) {
+        // The auth type may have been changed in the config since the app started.
+        let api_key = match Config::new() {
+            Ok(config) => load_api_key(&config).await,
+            Err(e) => Err(e),
+        };
+        match api_key {
+            Ok(api_key) => {
+                if let Some(auth_type) = api_key.get_auth_type() {
+                    info!("Using {auth_type:?} credentials");
+                }
+                self.task_manager.reinitialise_api(api_key).await
             }
+            Err(e) => self.window_state.handle_api_error(e).await,
+        }
+    }
+    async fn rerun_setup(&mut self) {
+        let result = self.run_setup().await;
+        // The app will be redrawn in full once setup is finished.
+        if let Err(e) = self.terminal.clear() {
+            error!("Error <{e}> clearing terminal");
+        }
+        match result {
+            Ok(true) => self.retry_sign_in().await,
+            Ok(false) => (),
+            Err(e) => self.window_state.handle_ui_message(UIMessage::Notify(
+                NotifyLevel::Error,
+                format!("Setup failed: {e}"),
+            )),
         }
     }
+    /// Run the setup wizard, saving the auth type that was set up.
+    /// Returns false if the user quit setup.
+    async fn run_setup(&mut self) -> Result<bool> {
+        execute!(io::stdout(), EnableBracketedPaste)?;
+        let result = setup::run(&mut self.terminal, &mut self.event_handler).await;
+        execute!(io::stdout(), DisableBracketedPaste)?;
+        let Some(auth_type) = result? else {
+            return Ok(false);
+        };
+        Config::new()?.save_auth_type(auth_type)?;
+        Ok(true)
+    }
 }
 
 /// Cleanly exit the tui
@@ -251,3 +795,16 @@ fn destruct_terminal() -> Result<()> {
     execute!(io::stdout(), crossterm::cursor::Show)?;
     Ok(())
 }
+
+fn get_download_settings(config: &Config) -> Result<DownloadSettings> {
+    Ok(DownloadSettings {
+        music_cache_size: config.get_music_cache_size(),
+        max_concurrent_downloads: config.get_max_concurrent_downloads(),
+        rate_limit: config.get_download_rate_limit(),
+        audio_format: config.get_audio_format_preference(),
+        export: ExportSettings {
+            dir: config.get_export_dir()?,
+            template: config.get_export_template(),
+        },
+    })
+}
@@ -1,2 +1,4 @@
 /// Traits related to application components
 pub mod actionhandler;
+/// Reusable modal confirmation dialog
+pub mod confirm;
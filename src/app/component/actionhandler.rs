@@ -22,13 +22,6 @@ pub trait KeyRouter<A: Action> {
     fn get_routed_keybinds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a KeyCommand<A>> + 'a>;
     /// Get the list of keybinds that the component and any child items can contain, regardless of current route.
     fn get_all_keybinds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a KeyCommand<A>> + 'a>;
-    // e.g - for use in help menu.
-    fn get_all_visible_keybinds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a KeyCommand<A>> + 'a> {
-        Box::new(
-            self.get_all_keybinds()
-                .filter(|kb| kb.visibility != CommandVisibility::Hidden),
-        )
-    }
     // e.g - for use in header.
     fn get_routed_global_keybinds<'a>(
         &'a self,
@@ -52,10 +45,6 @@ pub trait DominantKeyRouter {
 // Could possibly be a part of EventHandler instead.
 pub trait KeyDisplayer {
     // XXX: Can these all just be derived from KeyRouter?
-    /// Get the list of all keybinds that the KeyHandler and any child items can contain, regardless of context.
-    fn get_all_visible_keybinds_as_readable_iter<'a>(
-        &'a self,
-    ) -> Box<dyn Iterator<Item = DisplayableCommand<'a>> + 'a>;
     /// Get the list of all non-hidden keybinds that the KeyHandler and any child items can contain,
     /// regardless of context.
     fn get_all_keybinds_as_readable_iter<'a>(
@@ -66,11 +55,95 @@ pub trait KeyDisplayer {
     fn get_context_global_keybinds_as_readable_iter<'a>(
         &'a self,
     ) -> Box<dyn Iterator<Item = DisplayableCommand<'a>> + 'a>;
+    /// Get a context-specific list of the visible keybinds that aren't marked global, or the
+    /// keybinds for the current mode if a key is pending.
+    fn get_context_hint_keybinds_as_readable_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = DisplayableCommand<'a>> + 'a>;
+}
+/// Text editing other than pushing and popping characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextEdit {
+    CursorLeft,
+    CursorRight,
+    CursorStart,
+    CursorEnd,
+    DeleteForward,
+    /// Delete the word before the cursor.
+    DeleteWord,
+    /// Delete everything before the cursor.
+    DeleteLine,
+}
+impl TextEdit {
+    pub fn changes_text(&self) -> bool {
+        matches!(
+            self,
+            TextEdit::DeleteForward | TextEdit::DeleteWord | TextEdit::DeleteLine
+        )
+    }
+    /// Apply the edit to text, where cursor is the position in chars.
+    pub fn apply(self, text: &mut String, cursor: &mut usize) {
+        let len = text.chars().count();
+        *cursor = (*cursor).min(len);
+        match self {
+            TextEdit::CursorLeft => *cursor = cursor.saturating_sub(1),
+            TextEdit::CursorRight => *cursor = (*cursor + 1).min(len),
+            TextEdit::CursorStart => *cursor = 0,
+            TextEdit::CursorEnd => *cursor = len,
+            TextEdit::DeleteForward => {
+                if *cursor < len {
+                    let idx = byte_index(text, *cursor);
+                    text.remove(idx);
+                }
+            }
+            TextEdit::DeleteWord => {
+                let before: Vec<char> = text.chars().take(*cursor).collect();
+                let spaces = before
+                    .iter()
+                    .rev()
+                    .take_while(|c| c.is_whitespace())
+                    .count();
+                let word = before
+                    .iter()
+                    .rev()
+                    .skip(spaces)
+                    .take_while(|c| !c.is_whitespace())
+                    .count();
+                let start = *cursor - spaces - word;
+                text.replace_range(byte_index(text, start)..byte_index(text, *cursor), "");
+                *cursor = start;
+            }
+            TextEdit::DeleteLine => {
+                text.replace_range(..byte_index(text, *cursor), "");
+                *cursor = 0;
+            }
+        }
+    }
+}
+/// Insert c into text at cursor, where cursor is the position in chars, and advance the cursor.
+pub fn insert_at_cursor(text: &mut String, cursor: &mut usize, c: char) {
+    *cursor = (*cursor).min(text.chars().count());
+    text.insert(byte_index(text, *cursor), c);
+    *cursor += 1;
+}
+/// Remove the char before cursor, where cursor is the position in chars.
+pub fn remove_before_cursor(text: &mut String, cursor: &mut usize) {
+    *cursor = (*cursor).min(text.chars().count());
+    if *cursor == 0 {
+        return;
+    }
+    *cursor -= 1;
+    text.remove(byte_index(text, *cursor));
+}
+fn byte_index(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len())
 }
+
 /// A component of the application that handles text entry.
-// TODO: Cursor position and movement.
 pub trait TextHandler {
-    // TODO: cursor manipulation
     fn push_text(&mut self, c: char);
     fn pop_text(&mut self);
     // Assume internal representation is a String.
@@ -79,10 +152,22 @@ pub trait TextHandler {
     // Into<String> may also work.
     fn replace_text(&mut self, text: String);
     fn is_text_handling(&self) -> bool;
+    /// Apply a cursor based edit, returning false if not handled.
+    /// Handlers without a cursor don't need to implement this.
+    fn edit_text(&mut self, _edit: TextEdit) -> bool {
+        false
+    }
     fn handle_text_entry(&mut self, key_event: KeyEvent) -> bool {
         if !self.is_text_handling() {
             return false;
         }
+        if key_event.modifiers == KeyModifiers::CONTROL {
+            return match key_event.code {
+                KeyCode::Char('w') => self.edit_text(TextEdit::DeleteWord),
+                KeyCode::Char('u') => self.edit_text(TextEdit::DeleteLine),
+                _ => false,
+            };
+        }
         // The only accepted modifier is shift - if pressing another set of modifiers, we won't handle it.
         // Somewhere else should instead.
         if !key_event.modifiers.is_empty() {
@@ -99,6 +184,11 @@ pub trait TextHandler {
                 self.pop_text();
                 true
             }
+            KeyCode::Left => self.edit_text(TextEdit::CursorLeft),
+            KeyCode::Right => self.edit_text(TextEdit::CursorRight),
+            KeyCode::Home => self.edit_text(TextEdit::CursorStart),
+            KeyCode::End => self.edit_text(TextEdit::CursorEnd),
+            KeyCode::Delete => self.edit_text(TextEdit::DeleteForward),
             _ => false,
         }
     }
@@ -214,6 +304,47 @@ pub fn index_keymap<'a, A: Action>(
             Keymap::Mode(m) => index_keybinds(Box::new(m.commands.iter()), i),
         })
 }
+/// Find the first Action in a list of Keybinds with a description matching description (ignoring
+/// case), including Actions inside Modes.
+pub fn find_action_by_description<'a, A: Action + Clone + 'a>(
+    binds: Box<dyn Iterator<Item = &'a KeyCommand<A>> + 'a>,
+    description: &str,
+) -> Option<A> {
+    fn find_in_keymap<A: Action + Clone>(map: &Keymap<A>, description: &str) -> Option<A> {
+        match map {
+            Keymap::Action(a) => a
+                .describe()
+                .eq_ignore_ascii_case(description)
+                .then(|| a.clone()),
+            Keymap::Mode(m) => m
+                .commands
+                .iter()
+                .find_map(|kb| find_in_keymap(&kb.key_map, description)),
+        }
+    }
+    let mut binds = binds;
+    binds.find_map(|kb| find_in_keymap(&kb.key_map, description))
+}
+/// Get the descriptions of every non-hidden Action in a list of Keybinds, including Actions inside
+/// Modes.
+pub fn get_action_descriptions<'a, A: Action + 'a>(
+    binds: Box<dyn Iterator<Item = &'a KeyCommand<A>> + 'a>,
+) -> Vec<Cow<'a, str>> {
+    fn push_descriptions<'a, A: Action>(map: &'a Keymap<A>, descriptions: &mut Vec<Cow<'a, str>>) {
+        match map {
+            Keymap::Action(a) => descriptions.push(a.describe()),
+            Keymap::Mode(m) => m
+                .commands
+                .iter()
+                .for_each(|kb| push_descriptions(&kb.key_map, descriptions)),
+        }
+    }
+    let mut descriptions = Vec::new();
+    binds
+        .filter(|kb| kb.visibility != CommandVisibility::Hidden)
+        .for_each(|kb| push_descriptions(&kb.key_map, &mut descriptions));
+    descriptions
+}
 #[cfg(test)]
 mod tests {
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -362,4 +493,28 @@ mod tests {
         .key_map;
         assert_eq!(idx, Some(&eq));
     }
+    #[test]
+    fn test_text_edit() {
+        use crate::app::component::actionhandler::{
+            insert_at_cursor, remove_before_cursor, TextEdit,
+        };
+        let mut text = String::from("hello wörld");
+        let mut cur = 11;
+        TextEdit::CursorLeft.apply(&mut text, &mut cur);
+        insert_at_cursor(&mut text, &mut cur, 'x');
+        assert_eq!((text.as_str(), cur), ("hello wörlxd", 11));
+        TextEdit::DeleteForward.apply(&mut text, &mut cur);
+        remove_before_cursor(&mut text, &mut cur);
+        assert_eq!((text.as_str(), cur), ("hello wörl", 10));
+        TextEdit::DeleteWord.apply(&mut text, &mut cur);
+        assert_eq!((text.as_str(), cur), ("hello ", 6));
+        TextEdit::DeleteWord.apply(&mut text, &mut cur);
+        assert_eq!((text.as_str(), cur), ("", 0));
+        let mut text = String::from("search query");
+        let mut cur = 7;
+        TextEdit::DeleteLine.apply(&mut text, &mut cur);
+        assert_eq!((text.as_str(), cur), ("query", 0));
+        TextEdit::CursorEnd.apply(&mut text, &mut cur);
+        assert_eq!(cur, 5);
+    }
 }
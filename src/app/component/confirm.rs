@@ -0,0 +1,50 @@
+/// A modal yes/no dialog, used to confirm destructive actions.
+/// Holds the action to be run if the user confirms.
+pub struct ConfirmDialog<A> {
+    pending: Option<(String, A)>,
+}
+
+impl<A> Default for ConfirmDialog<A> {
+    fn default() -> Self {
+        Self { pending: None }
+    }
+}
+
+impl<A> ConfirmDialog<A> {
+    /// Show the dialog, replacing any pending confirmation.
+    pub fn open(&mut self, message: impl Into<String>, on_confirm: A) {
+        self.pending = Some((message.into(), on_confirm));
+    }
+    pub fn is_shown(&self) -> bool {
+        self.pending.is_some()
+    }
+    pub fn get_message(&self) -> Option<&str> {
+        self.pending.as_ref().map(|(message, _)| message.as_str())
+    }
+    /// Close the dialog, returning the action to run.
+    pub fn confirm(&mut self) -> Option<A> {
+        self.pending.take().map(|(_, action)| action)
+    }
+    /// Close the dialog without running the action.
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfirmDialog;
+
+    #[test]
+    fn test_confirm_dialog() {
+        let mut dialog = ConfirmDialog::default();
+        assert!(!dialog.is_shown());
+        dialog.open("Delete?", 1);
+        assert_eq!(dialog.get_message(), Some("Delete?"));
+        assert_eq!(dialog.confirm(), Some(1));
+        assert!(!dialog.is_shown());
+        dialog.open("Delete?", 2);
+        dialog.cancel();
+        assert_eq!(dialog.confirm(), None);
+    }
+}
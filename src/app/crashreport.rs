@@ -0,0 +1,105 @@
+//! Crash reports, written when the app panics so that bug reports can say what went wrong.
+use std::backtrace::Backtrace;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of lines from the end of the log file included in a crash report.
+const LOG_TAIL_LINES: usize = 50;
+
+/// Everything needed for a crash report is gathered up front, so that little can go wrong
+/// while panicking.
+pub struct CrashReporter {
+    dir: PathBuf,
+    log_file: Option<PathBuf>,
+    config_summary: String,
+}
+
+impl CrashReporter {
+    pub fn new(dir: PathBuf, log_file: Option<PathBuf>, config_summary: String) -> Self {
+        Self {
+            dir,
+            log_file,
+            config_summary,
+        }
+    }
+    /// Write a crash report for the panic to the report directory, returning its path.
+    pub fn write_report(&self, panic_message: &str) -> io::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = self.dir.join(format!("crash-{timestamp}.txt"));
+        let log_tail = match &self.log_file {
+            Some(log_file) => match std::fs::read_to_string(log_file) {
+                Ok(log) => tail(&log, LOG_TAIL_LINES).to_string(),
+                Err(e) => format!("Unable to read log file: {e}"),
+            },
+            None => "Log file is off".to_string(),
+        };
+        let report = format_report(
+            panic_message,
+            &Backtrace::force_capture().to_string(),
+            &log_tail,
+            &self.config_summary,
+        );
+        std::fs::write(&path, report)?;
+        Ok(path)
+    }
+}
+
+fn format_report(
+    panic_message: &str,
+    backtrace: &str,
+    log_tail: &str,
+    config_summary: &str,
+) -> String {
+    format!(
+        "youtui {} crash report ({} {})\n\n\
+         Panic:\n{panic_message}\n\n\
+         Backtrace:\n{backtrace}\n\n\
+         Recent log:\n{log_tail}\n\n\
+         Config:\n{config_summary}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+}
+
+/// The last lines of the text.
+fn tail(text: &str, lines: usize) -> &str {
+    let text_end = text.strip_suffix('\n').unwrap_or(text);
+    match text_end.rmatch_indices('\n').nth(lines.saturating_sub(1)) {
+        Some((i, _)) => &text[i + 1..],
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_report, tail};
+
+    #[test]
+    fn test_tail() {
+        assert_eq!(tail("a\nb\nc\n", 2), "b\nc\n");
+        assert_eq!(tail("a\nb\nc", 2), "b\nc");
+        assert_eq!(tail("a\nb\n", 5), "a\nb\n");
+        assert_eq!(tail("", 5), "");
+    }
+    #[test]
+    fn test_format_report() {
+        let report = format_report(
+            "panicked at 'oops'",
+            "0: main",
+            "INFO Starting",
+            "locale = \"English\"",
+        );
+        assert!(report.starts_with(&format!(
+            "youtui {} crash report",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(report.contains("Panic:\npanicked at 'oops'\n"));
+        assert!(report.contains("Recent log:\nINFO Starting\n"));
+        assert!(report.contains("Config:\nlocale = \"English\"\n"));
+    }
+}
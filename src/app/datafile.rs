@@ -0,0 +1,94 @@
+//! State that youtui keeps in the data directory, such as favorites and sessions. Files are JSON,
+//! and are saved so that a crash or a file that can't be read never loses the user's data.
+use crate::get_data_dir;
+use crate::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const PARTIAL_FILE_EXTENSION: &str = "part";
+const UNREADABLE_FILE_EXTENSION: &str = "bad";
+
+/// Load a file from the data directory. Returns None if the file doesn't exist or can't be
+/// read. A file that can't be parsed is moved aside to e.g favorites.json.bad, so that saving
+/// doesn't overwrite it.
+pub fn load_data_file<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    load_file(&get_data_dir().ok()?.join(file_name))
+}
+
+/// Save a file to the data directory, replacing it only once it has been completely written.
+pub fn save_data_file<T: Serialize>(file_name: &str, value: &T) -> Result<()> {
+    let dir = get_data_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    save_file(&dir.join(file_name), value)
+}
+
+fn load_file<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            warn!("Error <{e}> reading {}", path.display());
+            return None;
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            let unreadable = with_added_extension(path, UNREADABLE_FILE_EXTENSION);
+            warn!(
+                "Error <{e}> parsing {}, moving it to {}",
+                path.display(),
+                unreadable.display()
+            );
+            if let Err(e) = std::fs::rename(path, &unreadable) {
+                warn!("Error <{e}> moving {}", path.display());
+            }
+            None
+        }
+    }
+}
+
+fn save_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let partial = with_added_extension(path, PARTIAL_FILE_EXTENSION);
+    std::fs::write(&partial, serde_json::to_string_pretty(value)?)?;
+    std::fs::rename(partial, path)?;
+    Ok(())
+}
+
+/// e.g favorites.json becomes favorites.json.bad, keeping the original extension.
+fn with_added_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = path.to_path_buf().into_os_string();
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_file, save_file};
+    use crate::core::TestDir;
+
+    #[test]
+    fn test_save_and_load_file() {
+        let dir = TestDir::new("data_file_round_trip");
+        let path = dir.join("list.json");
+        assert_eq!(load_file::<Vec<String>>(&path), None);
+        save_file(&path, &vec!["a".to_string()]).unwrap();
+        assert_eq!(load_file(&path), Some(vec!["a".to_string()]));
+        assert!(!dir.join("list.json.part").exists());
+    }
+    #[test]
+    fn test_unparseable_file_kept_aside() {
+        let dir = TestDir::new("data_file_unparseable");
+        let path = dir.join("list.json");
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(load_file::<Vec<String>>(&path), None);
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("list.json.bad")).unwrap(),
+            "not json"
+        );
+    }
+}
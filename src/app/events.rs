@@ -0,0 +1,155 @@
+//! Events announcing what the player is doing, sent to any number of consumers so that
+//! integrations such as user scripts can follow playback without hooking into the playlist.
+use super::platform::shell_command;
+use super::structures::SavedSong;
+use std::process::Stdio;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+/// Events that aren't yet received by a slow consumer are dropped after this many.
+const EVENT_BUS_SIZE: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlayerEvent {
+    TrackStarted(SavedSong),
+    /// The track played to its end.
+    TrackFinished(SavedSong),
+    DownloadCompleted(SavedSong),
+    /// Songs were added to, removed from or reordered in the queue.
+    QueueChanged {
+        len: usize,
+    },
+}
+
+impl PlayerEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayerEvent::TrackStarted(_) => "track_started",
+            PlayerEvent::TrackFinished(_) => "track_finished",
+            PlayerEvent::DownloadCompleted(_) => "download_completed",
+            PlayerEvent::QueueChanged { .. } => "queue_changed",
+        }
+    }
+    /// The event as environment variables, as given to the event hook command.
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![("YOUTUI_EVENT", self.name().to_string())];
+        match self {
+            PlayerEvent::TrackStarted(song)
+            | PlayerEvent::TrackFinished(song)
+            | PlayerEvent::DownloadCompleted(song) => {
+                vars.push(("YOUTUI_VIDEO_ID", song.video_id.clone()));
+                vars.push(("YOUTUI_TITLE", song.title.clone()));
+                vars.push(("YOUTUI_ARTISTS", song.artists.join(", ")));
+                vars.push(("YOUTUI_ALBUM", song.album.clone()));
+                vars.push(("YOUTUI_DURATION", song.duration.clone().unwrap_or_default()));
+            }
+            PlayerEvent::QueueChanged { len } => vars.push(("YOUTUI_QUEUE_LEN", len.to_string())),
+        }
+        vars
+    }
+}
+
+/// Sends player events to every subscribed consumer. Cloning the bus gives another handle to
+/// the same consumers.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<PlayerEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (tx, _) = broadcast::channel(EVENT_BUS_SIZE);
+        Self { tx }
+    }
+}
+
+impl EventBus {
+    pub fn emit(&self, event: PlayerEvent) {
+        // An error only means that there are no consumers.
+        let _ = self.tx.send(event);
+    }
+    pub fn subscribe(&self) -> broadcast::Receiver<PlayerEvent> {
+        self.tx.subscribe()
+    }
+    /// Run the user's command through the shell for each event, one at a time, on its own
+    /// thread so that a slow script doesn't hold up the app. The command has no input, and its
+    /// output goes to the log.
+    pub fn start_hook_command(&self, command: String) {
+        let mut events = self.subscribe();
+        info!("Running event hook command <{command}>");
+        std::thread::spawn(move || loop {
+            let event = match events.blocking_recv() {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!("Event hook command is behind, skipped {n} events");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            };
+            // The terminal belongs to the app, so the command's output is logged instead.
+            let output = shell_command(&command)
+                .envs(event.env_vars())
+                .stdin(Stdio::null())
+                .output();
+            match output {
+                Ok(output) => {
+                    for line in String::from_utf8_lossy(&output.stdout).lines() {
+                        info!("Event hook command: {line}");
+                    }
+                    for line in String::from_utf8_lossy(&output.stderr).lines() {
+                        warn!("Event hook command: {line}");
+                    }
+                    if !output.status.success() {
+                        warn!(
+                            "Event hook command exited with {} for {}",
+                            output.status,
+                            event.name()
+                        )
+                    }
+                }
+                Err(e) => error!("Error <{e}> running event hook command"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventBus, PlayerEvent};
+    use crate::app::structures::{test_saved_song, SavedSong};
+
+    fn song() -> SavedSong {
+        SavedSong {
+            artists: vec!["Artist 1".into(), "Artist 2".into()],
+            ..test_saved_song("abc")
+        }
+    }
+
+    #[test]
+    fn test_env_vars() {
+        let vars = PlayerEvent::TrackStarted(song()).env_vars();
+        assert!(vars.contains(&("YOUTUI_EVENT", "track_started".into())));
+        assert!(vars.contains(&("YOUTUI_ARTISTS", "Artist 1, Artist 2".into())));
+        assert!(vars.contains(&("YOUTUI_DURATION", "3:25".into())));
+        let vars = PlayerEvent::QueueChanged { len: 3 }.env_vars();
+        assert_eq!(
+            vars,
+            vec![
+                ("YOUTUI_EVENT", "queue_changed".into()),
+                ("YOUTUI_QUEUE_LEN", "3".into())
+            ]
+        );
+    }
+    #[test]
+    fn test_event_bus() {
+        let bus = EventBus::default();
+        // Emitting without consumers is fine.
+        bus.emit(PlayerEvent::QueueChanged { len: 0 });
+        let mut first = bus.subscribe();
+        let mut second = bus.clone().subscribe();
+        bus.emit(PlayerEvent::TrackFinished(song()));
+        assert_eq!(first.try_recv(), Ok(PlayerEvent::TrackFinished(song())));
+        assert_eq!(second.try_recv(), Ok(PlayerEvent::TrackFinished(song())));
+        assert!(first.try_recv().is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use super::get_download_settings;
+use super::server::downloader::{self, DownloadProgressUpdateType};
+use super::server::{self, player, KillRequest, KillableTask, Server};
+use super::structures::ListSongID;
+use super::taskmanager::TaskID;
+use crate::config::{ApiKey, Config};
+use crate::core::send_or_error;
+use crate::error::Error;
+use crate::get_api;
+use crate::Result;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use ytmapi_rs::common::youtuberesult::YoutubeResult;
+use ytmapi_rs::common::{AlbumID, PlaylistID, YoutubeID};
+use ytmapi_rs::parse::SongResult;
+use ytmapi_rs::query::{GetAlbumQuery, GetPlaylistContinuationQuery, GetPlaylistQuery};
+use ytmapi_rs::VideoID;
+
+const MESSAGE_QUEUE_LENGTH: usize = 256;
+const VIDEO_ID_LENGTH: usize = 11;
+
+/// What to play, from its id or a link to it.
+#[derive(Clone, Debug, PartialEq)]
+enum PlayTarget {
+    Song(String),
+    Album(String),
+    Playlist(String),
+}
+
+impl PlayTarget {
+    fn parse(id_or_link: &str) -> Self {
+        let param = |name: &str| {
+            id_or_link
+                .split(['?', '&'])
+                .find_map(|p| p.strip_prefix(name))
+                .map(|id| id.to_string())
+        };
+        // A link to a song in a playlist plays the playlist.
+        if let Some(id) = param("list=") {
+            return PlayTarget::Playlist(id);
+        }
+        if let Some(id) = param("v=") {
+            return PlayTarget::Song(id);
+        }
+        // E.g an album link, "https://music.youtube.com/browse/<id>".
+        let id = id_or_link
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if id.starts_with("MPREb") {
+            PlayTarget::Album(id)
+        } else if id.len() == VIDEO_ID_LENGTH {
+            PlayTarget::Song(id)
+        } else {
+            PlayTarget::Playlist(id)
+        }
+    }
+}
+
+struct Song {
+    video_id: VideoID<'static>,
+    title: String,
+}
+
+impl From<SongResult> for Song {
+    fn from(song: SongResult) -> Self {
+        Self {
+            video_id: song.get_video_id().clone(),
+            title: song.get_title().clone(),
+        }
+    }
+}
+
+/// Play a song, album or playlist without the tui, printing what is playing. Stops at the end
+/// of the songs, or on ctrl-c.
+pub async fn play(config: &Config, api_key: ApiKey, id_or_link: String) -> Result<()> {
+    if let ApiKey::OAuthToken(_) = api_key {
+        return Err(Error::OAuthNotYetSupportedByApp);
+    }
+    let songs = get_songs(&api_key, PlayTarget::parse(&id_or_link)).await?;
+    if songs.is_empty() {
+        println!("No songs found");
+        return Ok(());
+    }
+    let (request_tx, request_rx) = mpsc::channel(MESSAGE_QUEUE_LENGTH);
+    let (response_tx, mut response_rx) = mpsc::channel(MESSAGE_QUEUE_LENGTH);
+    let mut server = Server::new(
+        api_key,
+        get_download_settings(config)?,
+        config.get_output_settings(),
+        config.get_task_timeout(),
+        response_tx,
+        request_rx,
+    )?;
+    let _server_handle = tokio::spawn(async move { server.run().await });
+    println!("Press ctrl-c to stop");
+    let mut playback = Playback {
+        songs,
+        request_tx,
+        playing: None,
+        downloading: None,
+        ready: None,
+    };
+    playback.download(0).await;
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("Stopped");
+                break;
+            }
+            response = response_rx.recv() => {
+                let Some(response) = response else {
+                    break;
+                };
+                if !playback.handle_response(response).await {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn get_songs(api_key: &ApiKey, target: PlayTarget) -> Result<Vec<Song>> {
+    let api = get_api(api_key).await?;
+    let songs = match target {
+        PlayTarget::Song(id) => vec![Song {
+            title: id.clone(),
+            video_id: VideoID::from_raw(id),
+        }],
+        PlayTarget::Album(id) => api
+            .get_album(GetAlbumQuery::new(AlbumID::from_raw(id)))
+            .await?
+            .tracks
+            .into_iter()
+            .map(Song::from)
+            .collect(),
+        PlayTarget::Playlist(id) => {
+            let playlist = api
+                .get_playlist(GetPlaylistQuery::new(PlaylistID::from_raw(id)))
+                .await?;
+            let mut tracks = playlist.tracks;
+            let mut continuation = playlist.continuation;
+            while let Some(c) = continuation.take() {
+                let page = api
+                    .get_playlist_continuation(GetPlaylistContinuationQuery::new(c))
+                    .await?;
+                tracks.extend(page.tracks);
+                continuation = page.continuation;
+            }
+            tracks.into_iter().map(|t| Song::from(t.song)).collect()
+        }
+    };
+    Ok(songs)
+}
+
+/// Plays the songs in order, downloading the next song while one plays.
+struct Playback {
+    songs: Vec<Song>,
+    request_tx: mpsc::Sender<server::Request>,
+    playing: Option<usize>,
+    /// The kill sender is kept, as dropping it kills the download.
+    downloading: Option<(usize, oneshot::Sender<KillRequest>)>,
+    /// A downloaded song waiting for the current song to finish.
+    ready: Option<(usize, Vec<u8>)>,
+}
+
+impl Playback {
+    async fn download(&mut self, idx: usize) {
+        let Some(song) = self.songs.get(idx) else {
+            self.downloading = None;
+            return;
+        };
+        let (kill_tx, kill_rx) = oneshot::channel();
+        let request = downloader::Request::DownloadSong(
+            song.video_id.clone(),
+            ListSongID::default(),
+            KillableTask::new(TaskID::default(), kill_rx),
+        );
+        self.downloading = Some((idx, kill_tx));
+        send_or_error(&self.request_tx, server::Request::Downloader(request)).await;
+    }
+    async fn play(&mut self, idx: usize, song: Vec<u8>) {
+        println!(
+            "Playing {}/{}: {}",
+            idx + 1,
+            self.songs.len(),
+            self.songs[idx].title
+        );
+        self.playing = Some(idx);
+        let request =
+            player::Request::PlaySong(Arc::new(song), ListSongID::default(), TaskID::default());
+        send_or_error(&self.request_tx, server::Request::Player(request)).await;
+        self.download(idx + 1).await;
+    }
+    /// Returns false once every song has been played.
+    async fn handle_response(&mut self, response: server::Response) -> bool {
+        match response {
+            server::Response::Downloader(downloader::Response::DownloadProgressUpdate(
+                update,
+                ..,
+            )) => self.handle_download_progress(update).await,
+            // Only downloads can time out, as the player doesn't time its tasks.
+            server::Response::TaskTimedOut(_) => {
+                let update = DownloadProgressUpdateType::Error("Timed out".into());
+                self.handle_download_progress(update).await
+            }
+            server::Response::Player(player::Response::DonePlaying(_)) => {
+                self.playing = None;
+                if let Some((idx, song)) = self.ready.take() {
+                    self.play(idx, song).await;
+                }
+            }
+            _ => (),
+        }
+        self.playing.is_some() || self.downloading.is_some() || self.ready.is_some()
+    }
+    async fn handle_download_progress(&mut self, update: DownloadProgressUpdateType) {
+        let Some(idx) = self.downloading.as_ref().map(|(idx, _)| *idx) else {
+            return;
+        };
+        match update {
+            DownloadProgressUpdateType::Started if self.playing.is_none() => {
+                println!("Downloading {}", self.songs[idx].title)
+            }
+            DownloadProgressUpdateType::Completed(song, _) => {
+                self.downloading = None;
+                if self.playing.is_some() {
+                    self.ready = Some((idx, song));
+                } else {
+                    self.play(idx, song).await;
+                }
+            }
+            DownloadProgressUpdateType::Error(e) => {
+                println!("Skipping {}, error downloading: {e}", self.songs[idx].title);
+                self.download(idx + 1).await;
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlayTarget;
+
+    #[test]
+    fn test_parse_play_target() {
+        assert_eq!(
+            PlayTarget::parse("https://music.youtube.com/watch?v=lYBUbBu4W08&list=RDAMVM"),
+            PlayTarget::Playlist("RDAMVM".into())
+        );
+        assert_eq!(
+            PlayTarget::parse("https://music.youtube.com/watch?v=lYBUbBu4W08"),
+            PlayTarget::Song("lYBUbBu4W08".into())
+        );
+        assert_eq!(
+            PlayTarget::parse("https://music.youtube.com/browse/MPREb_abc"),
+            PlayTarget::Album("MPREb_abc".into())
+        );
+        assert_eq!(
+            PlayTarget::parse("lYBUbBu4W08"),
+            PlayTarget::Song("lYBUbBu4W08".into())
+        );
+        assert_eq!(
+            PlayTarget::parse("PLabcdefghijklmnop"),
+            PlayTarget::Playlist("PLabcdefghijklmnop".into())
+        );
+    }
+}
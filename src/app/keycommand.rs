@@ -1,4 +1,4 @@
-use std::{borrow::Cow, fmt::Display};
+use std::{borrow::Cow, fmt::Display, str::FromStr, sync::RwLock};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 /// KeyCommand and Keybind model.
@@ -7,6 +7,28 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use itertools::Itertools;
 
 use super::component::actionhandler::Action;
+use crate::i18n::{tr, tr_runtime};
+
+/// Keys set in the config file, by the description of the Actions they replace the keys of.
+static KEYBIND_OVERRIDES: RwLock<Vec<(String, Keybind)>> = RwLock::new(Vec::new());
+
+/// Replace the keys of every Action with a matching description, where descriptions are in
+/// English and matched in the current locale.
+pub fn set_keybind_overrides(overrides: &[(String, Keybind)]) {
+    let overrides = overrides
+        .iter()
+        .map(|(description, keybind)| (tr_runtime(description), keybind.clone()))
+        .collect();
+    *KEYBIND_OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = overrides;
+}
+fn get_keybind_override(description: &str) -> Option<Keybind> {
+    KEYBIND_OVERRIDES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(d, _)| d.eq_ignore_ascii_case(description))
+        .map(|(_, keybind)| keybind.clone())
+}
 
 // Should another type be GlobalHidden?
 #[derive(PartialEq, Debug, Clone)]
@@ -82,6 +104,9 @@ impl Keybind {
 
 impl<A: Action> Display for KeyCommand<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(keybind) = self.keybind_override() {
+            return write!(f, "{keybind}");
+        }
         let w: String = self
             .keybinds
             .iter()
@@ -103,7 +128,11 @@ impl Display for Keybind {
             KeyCode::Down => "Down".into(),
             KeyCode::PageUp => "PageUp".into(),
             KeyCode::PageDown => "PageDown".into(),
+            KeyCode::Home => "Home".into(),
+            KeyCode::End => "End".into(),
             KeyCode::Esc => "Esc".into(),
+            KeyCode::Tab => "Tab".into(),
+            KeyCode::BackTab => "BackTab".into(),
             KeyCode::Char(c) => match c {
                 ' ' => "Space".into(),
                 c => c.to_string().into(),
@@ -120,6 +149,45 @@ impl Display for Keybind {
     }
 }
 
+/// Parse a key written the way it's displayed, e.g "C-n", "Space" or "F5".
+impl FromStr for Keybind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifiers, code) = match s.split_once('-') {
+            Some(("C", code)) if !code.is_empty() => (KeyModifiers::CONTROL, code),
+            Some(("A", code)) if !code.is_empty() => (KeyModifiers::ALT, code),
+            Some(("S", code)) if !code.is_empty() => (KeyModifiers::SHIFT, code),
+            _ => (KeyModifiers::empty(), s),
+        };
+        let code = match code {
+            "Enter" => KeyCode::Enter,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "Esc" => KeyCode::Esc,
+            "Tab" => KeyCode::Tab,
+            "BackTab" => KeyCode::BackTab,
+            "Space" => KeyCode::Char(' '),
+            code => {
+                let mut chars = code.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match code.strip_prefix('F').and_then(|n| n.parse().ok()) {
+                        Some(n) => KeyCode::F(n),
+                        None => return Err(format!("Unknown key <{s}>")),
+                    },
+                }
+            }
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
 // Is this an implementation of Action?
 impl<A: Action> Mode<A> {
     pub fn context(&self) -> Cow<str> {
@@ -129,7 +197,7 @@ impl<A: Action> Mode<A> {
             .unwrap_or_default()
     }
     pub fn describe(&self) -> Cow<str> {
-        self.name.into()
+        tr(self.name).into()
     }
     pub fn as_displayable_iter<'a>(
         &'a self,
@@ -155,7 +223,25 @@ impl<A: Action> KeyCommand<A> {
     pub fn as_displayable(&self) -> DisplayableCommand<'_> {
         self.into()
     }
+    /// The key set in the config file for this command's Action, which replaces its keybinds.
+    fn keybind_override(&self) -> Option<Keybind> {
+        // Avoids describing every Action on each keypress when nothing is overridden.
+        if KEYBIND_OVERRIDES
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+        {
+            return None;
+        }
+        match &self.key_map {
+            Keymap::Action(a) => get_keybind_override(&a.describe()),
+            Keymap::Mode(_) => None,
+        }
+    }
     pub fn contains_keyevent(&self, keyevent: &KeyEvent) -> bool {
+        if let Some(keybind) = self.keybind_override() {
+            return keybind.contains_keyevent(keyevent);
+        }
         for kb in self.keybinds.iter() {
             if kb.contains_keyevent(keyevent) {
                 return true;
@@ -223,6 +309,17 @@ impl<A: Action> KeyCommand<A> {
             visibility: CommandVisibility::Hidden,
         }
     }
+    pub fn new_hidden_modified_from_code(
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        action: A,
+    ) -> KeyCommand<A> {
+        KeyCommand {
+            keybinds: vec![Keybind::new(code, modifiers)],
+            key_map: Keymap::Action(action),
+            visibility: CommandVisibility::Hidden,
+        }
+    }
     pub fn new_action_only_mode(
         actions: Vec<(KeyCode, A)>,
         code: KeyCode,
@@ -249,3 +346,24 @@ impl<A: Action> KeyCommand<A> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Keybind;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_parse_keybind() {
+        for key in ["x", "C-n", "A-Enter", "S-Tab", "Space", "F5", "-", "C--"] {
+            let keybind: Keybind = key.parse().unwrap();
+            assert_eq!(keybind.to_string(), key);
+        }
+        assert_eq!(
+            "C-n".parse::<Keybind>().unwrap(),
+            Keybind::new(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        );
+        assert!("Nothing".parse::<Keybind>().is_err());
+        assert!("X-n".parse::<Keybind>().is_err());
+        assert!("".parse::<Keybind>().is_err());
+    }
+}
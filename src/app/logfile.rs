@@ -0,0 +1,91 @@
+//! A log file that is rotated once it reaches a maximum size, so that it doesn't grow without
+//! limit. Older logs are kept as e.g debug.log.1, debug.log.2, with higher numbers being older.
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+pub struct RotatingLogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_backups: usize,
+}
+
+impl RotatingLogFile {
+    /// Open the log file, appending to it if it already exists.
+    pub fn open(path: impl AsRef<Path>, max_size: u64, max_backups: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+            max_backups,
+        })
+    }
+    fn backup_path(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{n}"));
+        path.into()
+    }
+    /// Move each log up one backup, dropping the oldest, and start a new log file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for n in (1..self.max_backups).rev() {
+            let backup = self.backup_path(n);
+            if backup.exists() {
+                std::fs::rename(backup, self.backup_path(n + 1))?;
+            }
+        }
+        if self.max_backups > 0 {
+            std::fs::rename(&self.path, self.backup_path(1))?;
+        }
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // A message larger than the maximum size is still written whole, to an empty file.
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RotatingLogFile;
+    use crate::core::TestDir;
+    use std::io::Write;
+
+    #[test]
+    fn test_rotating_log_file() {
+        let dir = TestDir::new("rotating_log_file");
+        let path = dir.join("debug.log");
+        let mut log = RotatingLogFile::open(&path, 10, 2).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            log.write_all(line.as_bytes()).unwrap();
+        }
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("debug.log"), "fourth\n");
+        assert_eq!(read("debug.log.1"), "third\n");
+        assert_eq!(read("debug.log.2"), "second\n");
+        assert!(!dir.join("debug.log.3").exists());
+        // Reopening appends to the existing log.
+        let mut log = RotatingLogFile::open(&path, 100, 2).unwrap();
+        log.write_all(b"fifth\n").unwrap();
+        assert_eq!(read("debug.log"), "fourth\nfifth\n");
+    }
+}
@@ -0,0 +1,173 @@
+//! Behaviour that differs between operating systems, kept in one place so that the rest of the
+//! app doesn't need to know which one it's running on. Functions that only build values, rather
+//! than act on the system, are tested on every platform that CI runs on.
+use std::borrow::Cow;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Whether file names that differ only by case refer to the same file, as on Windows.
+pub const CASE_INSENSITIVE_FILE_NAMES: bool = cfg!(windows);
+
+/// Names that Windows reserves for devices, which can't be used as file names even with an
+/// extension.
+const WINDOWS_RESERVED_FILE_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+// tokio has no SignalKind for the job control signals, and their numbers differ by platform.
+#[cfg(unix)]
+pub use libc::{SIGCONT, SIGTSTP};
+
+/// A command that runs the command line through the system's shell.
+pub fn shell_command(command_line: &str) -> Command {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    command.arg(command_line);
+    command
+}
+
+/// A command that opens the url in the system's default web browser.
+pub fn open_url_command(url: &str) -> Command {
+    if cfg!(windows) {
+        // Not run through cmd, which would split the url at characters such as &.
+        let mut command = Command::new("rundll32");
+        command.args(["url.dll,FileProtocolHandler", url]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    }
+}
+
+/// Stop the process, as ctrl-z would in a shell, returning once it's continued.
+/// Windows has no job control, so there this is an error.
+#[cfg(unix)]
+pub fn stop_process() -> io::Result<()> {
+    // SIGTSTP is caught by the app, but SIGSTOP can't be, so the process stops here until it's
+    // continued.
+    // SAFETY: raise has no preconditions, and SIGSTOP is a valid signal.
+    if unsafe { libc::raise(libc::SIGSTOP) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+#[cfg(not(unix))]
+pub fn stop_process() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Suspending isn't supported on this platform",
+    ))
+}
+
+/// A relative path from one that separates directories with '/', such as a template in the
+/// config file, using the platform's own separator.
+pub fn relative_path(path: &str) -> PathBuf {
+    path.split('/').filter(|part| !part.is_empty()).collect()
+}
+
+/// The name of a file named after an id, such as a video id. Where file names are case
+/// insensitive, ids that differ only by case would otherwise share a file.
+pub fn id_file_name(id: &str) -> Cow<str> {
+    if CASE_INSENSITIVE_FILE_NAMES {
+        encode_case(id).into()
+    } else {
+        id.into()
+    }
+}
+
+/// Lower case each upper case letter and mark it with '^', which ids don't contain, so that
+/// the result is unique regardless of case.
+fn encode_case(id: &str) -> String {
+    let mut encoded = String::with_capacity(id.len());
+    for c in id.chars() {
+        if c.is_ascii_uppercase() {
+            encoded.push('^');
+            encoded.push(c.to_ascii_lowercase());
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
+
+/// True if Windows can't use the name for a file, e.g "con" or "nul.m4a". Files are often
+/// copied between platforms, so this is checked on every platform.
+pub fn is_reserved_file_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or_default().trim_end();
+    WINDOWS_RESERVED_FILE_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        encode_case, is_reserved_file_name, open_url_command, relative_path, shell_command,
+    };
+    use std::ffi::OsStr;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_relative_path() {
+        let expected: PathBuf = ["Queen", "A Night at the Opera", "01 - Death on Two Legs"]
+            .iter()
+            .collect();
+        assert_eq!(
+            relative_path("Queen/A Night at the Opera/01 - Death on Two Legs"),
+            expected
+        );
+        assert_eq!(
+            relative_path("a//b/"),
+            ["a", "b"].iter().collect::<PathBuf>()
+        );
+    }
+    #[test]
+    fn test_encode_case() {
+        assert_eq!(encode_case("lYBUbBu4W08"), "l^y^b^ub^bu4^w08");
+        assert_ne!(
+            encode_case("abc").to_lowercase(),
+            encode_case("aBc").to_lowercase()
+        );
+    }
+    #[test]
+    fn test_is_reserved_file_name() {
+        assert!(is_reserved_file_name("con"));
+        assert!(is_reserved_file_name("NUL.m4a"));
+        assert!(is_reserved_file_name("Com1 "));
+        assert!(!is_reserved_file_name("Console"));
+        assert!(!is_reserved_file_name("01 - Aux"));
+    }
+    #[test]
+    fn test_shell_command() {
+        let command = shell_command("echo hi");
+        let args: Vec<_> = command.get_args().collect();
+        if cfg!(windows) {
+            assert_eq!(command.get_program(), "cmd");
+            assert_eq!(args, ["/C", "echo hi"]);
+        } else {
+            assert_eq!(command.get_program(), "sh");
+            assert_eq!(args, ["-c", "echo hi"]);
+        }
+    }
+    #[test]
+    fn test_open_url_command() {
+        let url = "https://music.youtube.com/watch?v=lYBUbBu4W08&list=RDAMVM";
+        let command = open_url_command(url);
+        // The url must reach the opener whole, without being interpreted by a shell.
+        assert_eq!(command.get_args().last(), Some(OsStr::new(url)));
+    }
+}
@@ -1,17 +1,23 @@
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
-mod structures;
-use crate::config::ApiKey;
+pub mod structures;
+use crate::config::{ApiKey, OutputSettings};
 use crate::Result;
-use tracing::info;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tracing::{error, info, warn};
 
+use super::structures::Percentage;
 use super::taskmanager::TaskID;
 
 pub mod api;
+pub mod cast;
 pub mod downloader;
+pub mod export;
+mod musiccache;
 pub mod player;
 
-const DL_CALLBACK_CHUNK_SIZE: u64 = 100000; // How often song download will pause to execute code.
+const DL_CALLBACK_CHUNK_SIZE: u64 = 100000; // Size of each range requested when downloading a song.
 
 #[derive(Debug)]
 pub struct KillRequest;
@@ -22,6 +28,23 @@ pub struct KillableTask {
     pub kill_rx: oneshot::Receiver<KillRequest>,
 }
 
+/// How long tasks may run before they're stopped, so that a hung API call or stalled download
+/// doesn't hold up the caller forever. Stopped tasks are reported as timed out.
+#[derive(Clone)]
+pub struct TaskTimeout {
+    timeout: Option<Duration>,
+    response_tx: mpsc::Sender<Response>,
+}
+
+impl TaskTimeout {
+    pub fn new(timeout: Option<Duration>, response_tx: mpsc::Sender<Response>) -> Self {
+        Self {
+            timeout,
+            response_tx,
+        }
+    }
+}
+
 impl KillableTask {
     pub fn new(id: TaskID, kill_rx: oneshot::Receiver<KillRequest>) -> Self {
         Self { id, kill_rx }
@@ -32,6 +55,7 @@ pub enum Request {
     Api(api::Request),
     Player(player::Request),
     Downloader(downloader::Request),
+    Cast(cast::Request),
 }
 // Should this implement something like Killable/Blockable?
 #[derive(Debug)]
@@ -39,6 +63,15 @@ pub enum Response {
     Api(api::Response),
     Player(player::Response),
     Downloader(downloader::Response),
+    Cast(cast::Response),
+    /// How far through a long running task the server is, with a short description such as
+    /// "3/10 albums". Progress may be reported several times before the task's final response.
+    TaskProgress(TaskID, Percentage, String),
+    /// The task ran for longer than the task timeout, so it was stopped.
+    TaskTimedOut(TaskID),
+    /// The task failed in a way that may not happen again, e.g a network blip, so it will be
+    /// retried after a delay.
+    TaskRetrying(TaskID),
 }
 
 pub struct Server {
@@ -46,6 +79,7 @@ pub struct Server {
     api: api::Api,
     player: player::PlayerManager,
     downloader: downloader::Downloader,
+    cast: cast::Cast,
     _response_tx: mpsc::Sender<Response>,
     request_rx: mpsc::Receiver<Request>,
 }
@@ -53,17 +87,29 @@ pub struct Server {
 impl Server {
     pub fn new(
         api_key: ApiKey,
+        download_settings: downloader::DownloadSettings,
+        output_settings: OutputSettings,
+        task_timeout: Option<Duration>,
         response_tx: mpsc::Sender<Response>,
         request_rx: mpsc::Receiver<Request>,
     ) -> Result<Self> {
-        let api = api::Api::new(api_key, response_tx.clone());
+        let task_timeout = TaskTimeout::new(task_timeout, response_tx.clone());
+        let api = api::Api::new(api_key, response_tx.clone(), task_timeout.clone());
         // TODO: Error handling
-        let player = player::PlayerManager::new(response_tx.clone())?;
-        let downloader = downloader::Downloader::new(response_tx.clone());
+        let player = player::PlayerManager::new(response_tx.clone(), output_settings)?;
+        let music_cache = musiccache::MusicCache::load(download_settings.music_cache_size)?;
+        let downloader = downloader::Downloader::new(
+            response_tx.clone(),
+            music_cache,
+            download_settings,
+            task_timeout.clone(),
+        );
+        let cast = cast::Cast::new(response_tx.clone(), task_timeout);
         Ok(Self {
             api,
             player,
             downloader,
+            cast,
             request_rx,
             _response_tx: response_tx,
         })
@@ -74,28 +120,57 @@ impl Server {
         while let Some(request) = self.request_rx.recv().await {
             match request {
                 // TODO: Error handling for the queues.
-                Request::Api(rx) => self.api.handle_request(rx).await?,
+                // An API error, such as expired credentials, shouldn't stop the server, as the
+                // user may be able to resolve it.
+                Request::Api(rx) => {
+                    if let Err(e) = self.api.handle_request(rx).await {
+                        error!("Error <{e}> handling API request");
+                    }
+                }
                 Request::Downloader(rx) => self.downloader.handle_request(rx).await,
                 Request::Player(rx) => self.player.handle_request(rx).await?,
+                Request::Cast(rx) => self.cast.handle_request(rx).await,
             }
         }
         Ok(())
     }
 }
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<T> {
+    // The data is still usable if another task panicked while holding the lock.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 // Consider using this instead of macro above.
 async fn run_or_kill(
     future: impl futures::Future<Output = ()>,
     kill_rx: oneshot::Receiver<KillRequest>,
+    id: TaskID,
+    task_timeout: TaskTimeout,
 ) {
+    let timed_out = async {
+        match task_timeout.timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
     tokio::select! {
         _ = future => (),
         _ = kill_rx => info!("Task killed by caller"), // Is there a better way to do this?
+        _ = timed_out => {
+            warn!("Task {:?} timed out", id);
+            let _ = task_timeout
+                .response_tx
+                .send(Response::TaskTimedOut(id))
+                .await;
+        }
     }
 }
 
 async fn spawn_run_or_kill(
     future: impl futures::Future<Output = ()> + Send + 'static,
     kill_rx: oneshot::Receiver<KillRequest>,
+    id: TaskID,
+    task_timeout: &TaskTimeout,
 ) {
-    tokio::spawn(run_or_kill(future, kill_rx));
+    tokio::spawn(run_or_kill(future, kill_rx, id, task_timeout.clone()));
 }
@@ -1,30 +1,136 @@
 use super::spawn_run_or_kill;
-use super::KillableTask;
+use super::structures::{SearchFilter, SearchResultsList};
+use super::{KillableTask, TaskTimeout};
+use crate::app::structures::{ListSongID, Percentage};
 use crate::app::taskmanager::TaskID;
 use crate::config::ApiKey;
 use crate::error::Error;
-use crate::Result;
+use crate::i18n::tr_format;
+use crate::{get_cache_dir, Result};
+use futures::{Future, StreamExt};
+use std::time::Duration;
 use tokio::sync::mpsc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use ytmapi_rs::auth::BrowserToken;
+use ytmapi_rs::common::browsing::Lyrics;
+use ytmapi_rs::common::library::{LibraryAlbum, LibraryArtist, LibraryPodcast, Playlist};
 use ytmapi_rs::common::youtuberesult::YoutubeResult;
 use ytmapi_rs::common::AlbumID;
+use ytmapi_rs::common::LikeStatus;
+use ytmapi_rs::common::PlaylistID;
+use ytmapi_rs::common::PodcastID;
 use ytmapi_rs::common::SearchSuggestion;
 use ytmapi_rs::common::YoutubeID;
+use ytmapi_rs::parse::ArtistParams;
+use ytmapi_rs::parse::Charts;
 use ytmapi_rs::parse::GetArtistAlbums;
+use ytmapi_rs::parse::PlaylistSong;
+use ytmapi_rs::parse::Podcast;
 use ytmapi_rs::parse::SongResult;
-use ytmapi_rs::ChannelID;
+use ytmapi_rs::query::{lyrics::GetLyricsQuery, watch::GetWatchPlaylistQuery};
+use ytmapi_rs::query::{
+    AddPlaylistItemsQuery, GetChartsQuery, GetLibraryArtistsQuery, GetPlaylistContinuationQuery,
+    GetPlaylistQuery, GetPodcastQuery, RateSongQuery, SearchArtistsContinuationQuery,
+    SubscribeArtistQuery, UnsubscribeArtistQuery,
+};
+use ytmapi_rs::{ChannelID, VideoID};
+
+/// Json that couldn't be parsed is written here, to help debug the parser.
+const API_ERROR_JSON_FILE_NAME: &str = "api_error.json";
+/// Albums requested at once when listing an artist's songs.
+const MAX_CONCURRENT_ALBUM_QUERIES: usize = 8;
+/// Times a query is retried after an error that may not happen again, before giving up.
+const MAX_QUERY_RETRIES: u32 = 3;
 
 pub enum Request {
     GetSearchSuggestions(String, KillableTask),
     NewArtistSearch(String, KillableTask),
+    /// Get the next page of an artist search, using the continuation from the previous page.
+    SearchArtistsContinuation(String, KillableTask),
     SearchSelectedArtist(ChannelID<'static>, KillableTask),
+    GetArtistDetails(ChannelID<'static>, KillableTask),
+    NewSearch(String, SearchFilter, KillableTask),
+    GetAlbumSongs {
+        album_id: AlbumID<'static>,
+        artist: String,
+        play: bool,
+        task: KillableTask,
+    },
+    GetLibrary(KillableTask),
+    GetPlaylistSongs(PlaylistID<'static>, KillableTask),
+    /// Get all the songs in a playlist to add to the queue, rather than to display.
+    QueuePlaylistSongs(PlaylistID<'static>, KillableTask),
+    /// Get the songs for an album to display in the browser, rather than to queue.
+    ListAlbumSongs(AlbumID<'static>, KillableTask),
+    /// Get the charts for a country, or the global charts if None.
+    GetCharts(Option<String>, KillableTask),
+    GetLibraryPodcasts(KillableTask),
+    GetPodcastEpisodes(PodcastID<'static>, KillableTask),
+    QueuePodcastEpisodes(PodcastID<'static>, KillableTask),
+    GetLyrics(VideoID<'static>, KillableTask),
+    GetLibraryPlaylists(KillableTask),
+    AddSongToLibraryPlaylist(PlaylistID<'static>, VideoID<'static>, KillableTask),
+    RateSong(VideoID<'static>, LikeStatus, ListSongID, KillableTask),
+    GetLibrarySubscriptions(KillableTask),
+    /// Subscribe to the artist if true, otherwise unsubscribe.
+    SetArtistSubscribed(ChannelID<'static>, bool, KillableTask),
+    /// Replace the API using new credentials.
+    ReinitialiseApi(ApiKey),
 }
 #[derive(Debug)]
 pub enum Response {
-    ReplaceArtistList(Vec<ytmapi_rs::parse::SearchResultArtist>, TaskID),
+    ReplaceArtistList(
+        Vec<ytmapi_rs::parse::SearchResultArtist>,
+        Option<String>,
+        TaskID,
+    ),
+    AppendArtistList(
+        Vec<ytmapi_rs::parse::SearchResultArtist>,
+        Option<String>,
+        TaskID,
+    ),
     SearchArtistError(TaskID),
+    ReplaceArtistDetails(Box<ArtistParams>, TaskID),
+    ArtistDetailsError(TaskID),
     ReplaceSearchSuggestions(Vec<SearchSuggestion>, TaskID, String),
+    ReplaceSearchResults(SearchResultsList, TaskID),
+    SearchError(TaskID),
+    AlbumSongs {
+        song_list: Vec<SongResult>,
+        album: String,
+        year: String,
+        artist: String,
+        play: bool,
+        id: TaskID,
+    },
+    AlbumSongsError(TaskID),
+    ReplaceLibrary {
+        playlists: Vec<Playlist>,
+        albums: Vec<LibraryAlbum>,
+        artists: Vec<LibraryArtist>,
+        id: TaskID,
+    },
+    LibraryError(TaskID),
+    ReplaceCharts(Charts, TaskID),
+    ChartsError(TaskID),
+    ReplaceLibraryPodcasts(Vec<LibraryPodcast>, TaskID),
+    LibraryPodcastsError(TaskID),
+    ReplacePodcastEpisodes(Podcast, TaskID),
+    PodcastEpisodesError(TaskID),
+    QueuePodcastEpisodes(Podcast, TaskID),
+    QueuePodcastEpisodesError(TaskID),
+    ReplaceLyrics(Lyrics, TaskID),
+    LyricsError(TaskID),
+    ReplaceLibraryPlaylists(Vec<Playlist>, TaskID),
+    LibraryPlaylistsError(TaskID),
+    SongAddedToLibraryPlaylist(TaskID),
+    AddSongToLibraryPlaylistError(TaskID),
+    SongRated(ListSongID, LikeStatus, TaskID),
+    RateSongError(TaskID),
+    ReplaceLibrarySubscriptions(Vec<ChannelID<'static>>, TaskID),
+    LibrarySubscriptionsError(TaskID),
+    ArtistSubscribed(ChannelID<'static>, bool, TaskID),
+    ArtistSubscriptionError(TaskID),
     SongListLoading(TaskID),
     SongListLoaded(TaskID),
     NoSongsFound(TaskID),
@@ -34,37 +140,43 @@ pub enum Response {
         album: String,
         year: String,
         artist: String,
+        album_id: AlbumID<'static>,
+        artist_id: Option<ChannelID<'static>>,
+        id: TaskID,
+    },
+    AppendPlaylistSongList {
+        song_list: Vec<PlaylistSong>,
+        id: TaskID,
+    },
+    PlaylistSongsError(TaskID),
+    QueuePlaylistSongs {
+        song_list: Vec<PlaylistSong>,
         id: TaskID,
     },
+    QueuePlaylistSongsError(TaskID),
     ApiError(Error),
+    /// The API was replaced successfully using new credentials.
+    ApiReinitialised,
 }
 pub struct Api {
     // Do I want to keep track of tasks here in a joinhandle?
     api: Option<ytmapi_rs::YtMusic<BrowserToken>>,
     api_init: Option<tokio::task::JoinHandle<Result<ytmapi_rs::YtMusic<BrowserToken>>>>,
     response_tx: mpsc::Sender<super::Response>,
+    task_timeout: TaskTimeout,
 }
 
 impl Api {
-    pub fn new(api_key: ApiKey, response_tx: mpsc::Sender<super::Response>) -> Self {
-        let api_init = Some(tokio::spawn(async move {
-            info!("Initialising API");
-            // TODO: Error handling
-            let api = match api_key {
-                ApiKey::BrowserToken(c) => ytmapi_rs::YtMusic::from_cookie(c).await?,
-                ApiKey::OAuthToken(_) =>
-                // TODO: Add OAuth
-                {
-                    unimplemented!()
-                } // ytmapi_rs::YtMusic::from_oauth_token(t),
-            };
-            info!("API initialised");
-            Ok(api)
-        }));
+    pub fn new(
+        api_key: ApiKey,
+        response_tx: mpsc::Sender<super::Response>,
+        task_timeout: TaskTimeout,
+    ) -> Self {
         Self {
             api: None,
-            api_init,
+            api_init: Some(spawn_api_init(api_key)),
             response_tx,
+            task_timeout,
         }
     }
     async fn get_api(&mut self) -> Result<&ytmapi_rs::YtMusic<BrowserToken>> {
@@ -86,14 +198,84 @@ impl Api {
     pub async fn handle_request(&mut self, request: Request) -> Result<()> {
         match request {
             Request::NewArtistSearch(a, task) => self.handle_new_artist_search(a, task).await,
+            Request::SearchArtistsContinuation(continuation, task) => {
+                self.handle_search_artists_continuation(continuation, task)
+                    .await
+            }
             Request::GetSearchSuggestions(text, task) => {
                 self.handle_get_search_suggestions(text, task).await
             }
             Request::SearchSelectedArtist(browse_id, task) => {
                 self.handle_search_selected_artist(browse_id, task).await
             }
+            Request::GetArtistDetails(browse_id, task) => {
+                self.handle_get_artist_details(browse_id, task).await
+            }
+            Request::NewSearch(query, filter, task) => {
+                self.handle_new_search(query, filter, task).await
+            }
+            Request::GetAlbumSongs {
+                album_id,
+                artist,
+                play,
+                task,
+            } => {
+                self.handle_get_album_songs(album_id, artist, play, task)
+                    .await
+            }
+            Request::GetLibrary(task) => self.handle_get_library(task).await,
+            Request::GetPlaylistSongs(playlist_id, task) => {
+                self.handle_get_playlist_songs(playlist_id, task).await
+            }
+            Request::QueuePlaylistSongs(playlist_id, task) => {
+                self.handle_queue_playlist_songs(playlist_id, task).await
+            }
+            Request::ListAlbumSongs(album_id, task) => {
+                self.handle_list_album_songs(album_id, task).await
+            }
+            Request::GetCharts(country, task) => self.handle_get_charts(country, task).await,
+            Request::GetLibraryPodcasts(task) => self.handle_get_library_podcasts(task).await,
+            Request::GetPodcastEpisodes(podcast_id, task) => {
+                self.handle_get_podcast_episodes(podcast_id, task).await
+            }
+            Request::QueuePodcastEpisodes(podcast_id, task) => {
+                self.handle_queue_podcast_episodes(podcast_id, task).await
+            }
+            Request::GetLyrics(video_id, task) => self.handle_get_lyrics(video_id, task).await,
+            Request::GetLibraryPlaylists(task) => self.handle_get_library_playlists(task).await,
+            Request::AddSongToLibraryPlaylist(playlist_id, video_id, task) => {
+                self.handle_add_song_to_library_playlist(playlist_id, video_id, task)
+                    .await
+            }
+            Request::RateSong(video_id, rating, song_id, task) => {
+                self.handle_rate_song(video_id, rating, song_id, task).await
+            }
+            Request::GetLibrarySubscriptions(task) => {
+                self.handle_get_library_subscriptions(task).await
+            }
+            Request::SetArtistSubscribed(channel_id, subscribed, task) => {
+                self.handle_set_artist_subscribed(channel_id, subscribed, task)
+                    .await
+            }
+            Request::ReinitialiseApi(api_key) => self.handle_reinitialise_api(api_key).await,
         }
     }
+    /// Replace the API using new credentials, e.g after the previous ones expired.
+    async fn handle_reinitialise_api(&mut self, api_key: ApiKey) -> Result<()> {
+        self.api = None;
+        self.api_init = Some(spawn_api_init(api_key));
+        let response = match self.get_api().await {
+            Ok(_) => Response::ApiReinitialised,
+            Err(e) => {
+                error!("Error {e} reinitialising API");
+                Response::ApiError(e)
+            }
+        };
+        self.response_tx
+            .send(crate::app::server::Response::Api(response))
+            .await?;
+        Ok(())
+    }
     async fn handle_get_search_suggestions(
         &mut self,
         text: String,
@@ -126,6 +308,7 @@ impl Api {
                     Ok(t) => t,
                     Err(e) => {
                         error!("Received error on search suggestions query \"{}\"", e);
+                        report_auth_error(&tx, e).await;
                         return;
                     }
                 };
@@ -139,6 +322,8 @@ impl Api {
                     .await;
             },
             kill_rx,
+            id,
+            &self.task_timeout,
         )
         .await;
         Ok(())
@@ -170,40 +355,44 @@ impl Api {
                 //            let api = crate::app::api::APIHandler::new();
                 //            let search_res = api.search_artists(&self.search_contents, 20);
                 tracing::info!("Running search query");
-                let search_res = match api
-                    .search_artists(
-                        ytmapi_rs::query::SearchQuery::new(artist)
+                let search_res = match query_with_retry(&tx, id, || {
+                    api.search_artists_with_continuation(
+                        ytmapi_rs::query::SearchQuery::new(artist.clone())
                             .with_filter(ytmapi_rs::query::ArtistsFilter)
                             .with_spelling_mode(ytmapi_rs::query::SpellingMode::ExactMatch),
                     )
-                    .await
+                })
+                .await
                 {
                     Ok(t) => t,
                     Err(e) => {
                         error!("Received error on search artist query \"{}\"", e);
+                        report_auth_error(&tx, e).await;
                         tx.send(super::Response::Api(Response::SearchArtistError(id)))
                             .await
                             .unwrap_or_else(|_| error!("Error sending response"));
                         return;
                     }
                 };
-                let artist_list = search_res.into_iter().collect();
                 tracing::info!("Requesting caller to replace artist list");
                 let _ = tx
                     .send(super::Response::Api(Response::ReplaceArtistList(
-                        artist_list,
+                        search_res.artists,
+                        search_res.continuation,
                         id,
                     )))
                     .await;
             },
             kill_rx,
+            id,
+            &self.task_timeout,
         )
         .await;
         Ok(())
     }
-    async fn handle_search_selected_artist(
+    async fn handle_search_artists_continuation(
         &mut self,
-        browse_id: ChannelID<'static>,
+        continuation: String,
         task: KillableTask,
     ) -> Result<()> {
         let KillableTask { id, kill_rx } = task;
@@ -224,153 +413,1318 @@ impl Api {
         .clone();
         let _ = spawn_run_or_kill(
             async move {
-                let tx = tx.clone();
+                tracing::info!("Getting next page of artist search");
+                let page = match api
+                    .search_artists_continuation(SearchArtistsContinuationQuery::new(continuation))
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(e) => {
+                        // Artists already received are kept.
+                        error!("Error <{e}> getting next page of artist search");
+                        report_auth_error(&tx, e).await;
+                        return;
+                    }
+                };
+                tracing::info!("Requesting caller to append to artist list");
                 let _ = tx
-                    .send(super::Response::Api(Response::SongListLoading(id)))
-                    .await;
-                tracing::info!("Running songs query");
-                // Should this be a ChannelID or BrowseID? Should take a trait?.
-                // Should this actually take ChannelID::try_from(BrowseID::Artist) -> ChannelID::Artist?
-                let artist = api
-                    .get_artist(ytmapi_rs::query::GetArtistQuery::new(
-                        ytmapi_rs::ChannelID::from_raw(browse_id.get_raw()),
-                    ))
+                    .send(super::Response::Api(Response::AppendArtistList(
+                        page.artists,
+                        page.continuation,
+                        id,
+                    )))
                     .await;
-                let artist = match artist {
-                    Ok(a) => a,
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_new_search(
+        &mut self,
+        query: String,
+        filter: SearchFilter,
+        task: KillableTask,
+    ) -> Result<()> {
+        if filter == SearchFilter::Artists {
+            return self.handle_new_artist_search(query, task).await;
+        }
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Running {filter} search query");
+                let (api, query) = (&api, query.as_str());
+                let search_res = query_with_retry(&tx, id, || async move {
+                    match filter {
+                        SearchFilter::Songs => {
+                            api.search_songs(query).await.map(SearchResultsList::Songs)
+                        }
+                        SearchFilter::Albums => api
+                            .search_albums(query)
+                            .await
+                            .map(SearchResultsList::Albums),
+                        SearchFilter::Playlists => api
+                            .search_playlists(query)
+                            .await
+                            .map(SearchResultsList::Playlists),
+                        SearchFilter::Videos => api
+                            .search_videos(query)
+                            .await
+                            .map(SearchResultsList::Videos),
+                        SearchFilter::Podcasts => api
+                            .search_podcasts(query)
+                            .await
+                            .map(SearchResultsList::Podcasts),
+                        SearchFilter::All => api
+                            .search(query)
+                            .await
+                            .map(|r| SearchResultsList::All(Box::new(r))),
+                        SearchFilter::Artists => unreachable!("Artist search handled above"),
+                    }
+                })
+                .await;
+                let search_res = match search_res {
+                    Ok(r) => r,
                     Err(e) => {
-                        let Some((json, key)) = e.get_json_and_key() else {
-                            return;
-                        };
-                        // TODO: Bring loggable json errors into their own function.
-                        error!("API error recieved at key {:?}", key);
-                        let path = std::path::Path::new("test.json");
-                        std::fs::write(path, json)
-                            .unwrap_or_else(|e| error!("Error <{e}> writing json log"));
-                        info!("Wrote json to {:?}", path);
-                        tracing::info!("Telling caller no songs found (error)");
-                        let _ = tx
-                            .send(super::Response::Api(Response::NoSongsFound(id)))
-                            .await;
+                        error!("Received error on {filter} search query \"{}\"", e);
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::SearchError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
                         return;
                     }
                 };
-                let Some(albums) = artist.top_releases.albums else {
-                    tracing::info!("Telling caller no songs found (no params)");
-                    let _ = tx
-                        .send(super::Response::Api(Response::NoSongsFound(id)))
-                        .await;
-                    return;
+                tracing::info!("Requesting caller to replace search results");
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceSearchResults(
+                        search_res, id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_album_songs(
+        &mut self,
+        album_id: AlbumID<'static>,
+        artist: String,
+        play: bool,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting songs for album {:?}", album_id);
+                let album = match query_with_retry(&tx, id, || {
+                    api.get_album(ytmapi_rs::query::GetAlbumQuery::new(&album_id))
+                })
+                .await
+                {
+                    Ok(album) => album,
+                    Err(e) => {
+                        error!("Error <{e}> getting album {:?}", album_id);
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::AlbumSongsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
                 };
-
-                let GetArtistAlbums {
-                    browse_id: artist_albums_browse_id,
-                    params: artist_albums_params,
-                    results: artist_albums_results,
-                } = albums;
-                let browse_id_list = if artist_albums_browse_id.is_none()
-                    && artist_albums_params.is_none()
-                    && !artist_albums_results.is_empty()
+                let _ = tx
+                    .send(super::Response::Api(Response::AlbumSongs {
+                        song_list: album.tracks,
+                        album: album.title,
+                        year: album.year,
+                        artist,
+                        play,
+                        id,
+                    }))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_list_album_songs(
+        &mut self,
+        album_id: AlbumID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                let _ = tx
+                    .send(super::Response::Api(Response::SongListLoading(id)))
+                    .await;
+                tracing::info!("Getting songs for album {:?}", album_id);
+                let album = match query_with_retry(&tx, id, || {
+                    api.get_album(ytmapi_rs::query::GetAlbumQuery::new(&album_id))
+                })
+                .await
                 {
-                    // Assume we already got all the albums from the search.
-                    let browse_id_list: Option<Vec<_>> = artist_albums_results
-                        .iter()
-                        .map(|r| {
-                            r.get_channel_id()
-                                .as_ref()
-                                .map(|c_id| AlbumID::from_raw(c_id.get_raw()))
-                        })
-                        .collect();
-                    if let Some(browse_id_list) = browse_id_list {
-                        browse_id_list
-                    } else {
-                        tracing::info!(
-                            "Telling caller no songs found (some albums missing browse id)"
-                        );
+                    Ok(album) => album,
+                    Err(e) => {
+                        error!("Error <{e}> getting album {:?}", album_id);
+                        report_auth_error(&tx, e).await;
                         let _ = tx
                             .send(super::Response::Api(Response::NoSongsFound(id)))
                             .await;
                         return;
                     }
-                } else if artist_albums_params.is_none() || artist_albums_browse_id.is_none() {
-                    tracing::info!("Telling caller no songs found (no params or browse_id)");
+                };
+                if album.tracks.is_empty() {
+                    tracing::info!("Telling caller no songs found (empty album)");
                     let _ = tx
                         .send(super::Response::Api(Response::NoSongsFound(id)))
                         .await;
                     return;
-                } else {
-                    // Must have params and browse_id
-                    let Some(temp_browse_id) = artist_albums_browse_id else {
-                        unreachable!("Checked not none above")
-                    };
-                    let Some(temp_params) = artist_albums_params else {
-                        unreachable!("Checked not none above")
-                    };
-
-                    let albums = match api
-                        .get_artist_albums(ytmapi_rs::query::GetArtistAlbumsQuery::new(
-                            ytmapi_rs::ChannelID::from_raw(temp_browse_id.get_raw()),
-                            temp_params,
-                        ))
-                        .await
-                    {
-                        Ok(r) => r,
-                        Err(e) => {
-                            error!("Received error on get_artist_albums query \"{}\"", e);
-
-                            // TODO: Better Error type
-                            tx.send(super::Response::Api(Response::SearchArtistError(id)))
-                                .await
-                                .unwrap_or_else(|_| error!("Error sending response"));
-                            return;
-                        }
-                    };
-                    albums.into_iter().map(|a| a.browse_id).collect()
-                };
+                }
                 let _ = tx
                     .send(super::Response::Api(Response::SongsFound(id)))
                     .await;
-                // Concurrently request all albums.
-                let futures = browse_id_list.into_iter().map(|b_id| {
-                    let api = &api;
-                    let tx = tx.clone();
-                    // TODO: remove allocation
-                    let artist_name = artist.name.clone();
-                    async move {
-                        tracing::info!(
-                            "Spawning request for caller tracks for request ID {:?}",
-                            id
-                        );
-                        let album = match api
-                            .get_album(ytmapi_rs::query::GetAlbumQuery::new(&b_id))
-                            .await
-                        {
-                            Ok(album) => album,
-                            Err(e) => {
-                                error!("Error <{e}> getting album {:?}", b_id);
-                                return;
-                            }
-                        };
-                        tracing::info!("Sending caller tracks for request ID {:?}", id);
-                        let _ = tx
-                            .send(super::Response::Api(Response::AppendSongList {
-                                song_list: album.tracks,
-                                album: album.title,
-                                year: album.year,
-                                artist: artist_name,
-                                id,
-                            }))
-                            .await;
-                    }
-                });
-                let _ = futures::future::join_all(futures).await;
+                // Album tracks don't contain the artist's browse id.
+                let _ = tx
+                    .send(super::Response::Api(Response::AppendSongList {
+                        song_list: album.tracks,
+                        album: album.title,
+                        year: album.year,
+                        artist: album.artists.unwrap_or_default(),
+                        album_id,
+                        artist_id: None,
+                        id,
+                    }))
+                    .await;
                 let _ = tx
                     .send(super::Response::Api(Response::SongListLoaded(id)))
                     .await;
             },
             kill_rx,
+            id,
+            &self.task_timeout,
         )
         .await;
         Ok(())
     }
+    async fn handle_get_library(&mut self, task: KillableTask) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting library");
+                let api = &api;
+                let library = query_with_retry(&tx, id, || async move {
+                    tokio::try_join!(
+                        api.get_library_playlists(),
+                        api.get_library_albums(),
+                        api.get_library_artists(GetLibraryArtistsQuery::default())
+                    )
+                })
+                .await;
+                let (playlists, albums, artists) = match library {
+                    Ok(library) => library,
+                    Err(e) => {
+                        error!("Error <{e}> getting library");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::LibraryError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceLibrary {
+                        playlists,
+                        albums,
+                        artists,
+                        id,
+                    }))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_charts(
+        &mut self,
+        country: Option<String>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting charts for country {:?}", country);
+                let query = || match &country {
+                    Some(country) => GetChartsQuery::new().with_country(country.clone()),
+                    None => GetChartsQuery::new(),
+                };
+                let charts = match query_with_retry(&tx, id, || api.get_charts(query())).await {
+                    Ok(charts) => charts,
+                    Err(e) => {
+                        error!("Error <{e}> getting charts");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::ChartsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceCharts(charts, id)))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_library_podcasts(&mut self, task: KillableTask) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting library podcasts");
+                let podcasts = match query_with_retry(&tx, id, || api.get_library_podcasts()).await
+                {
+                    Ok(podcasts) => podcasts,
+                    Err(e) => {
+                        error!("Error <{e}> getting library podcasts");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::LibraryPodcastsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceLibraryPodcasts(
+                        podcasts, id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_artist_details(
+        &mut self,
+        browse_id: ChannelID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting details for artist {}", browse_id.get_raw());
+                let artist = match query_with_retry(&tx, id, || {
+                    api.get_artist(ytmapi_rs::query::GetArtistQuery::new(browse_id.clone()))
+                })
+                .await
+                {
+                    Ok(artist) => artist,
+                    Err(e) => {
+                        error!("Error <{e}> getting artist details");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::ArtistDetailsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceArtistDetails(
+                        Box::new(artist),
+                        id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_podcast_episodes(
+        &mut self,
+        podcast_id: PodcastID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting episodes for podcast {}", podcast_id.get_raw());
+                let podcast = match query_with_retry(&tx, id, || {
+                    api.get_podcast(GetPodcastQuery::new(podcast_id.clone()))
+                })
+                .await
+                {
+                    Ok(podcast) => podcast,
+                    Err(e) => {
+                        error!("Error <{e}> getting podcast");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::PodcastEpisodesError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplacePodcastEpisodes(
+                        podcast, id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_queue_podcast_episodes(
+        &mut self,
+        podcast_id: PodcastID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!(
+                    "Getting episodes to queue for podcast {}",
+                    podcast_id.get_raw()
+                );
+                let podcast = match query_with_retry(&tx, id, || {
+                    api.get_podcast(GetPodcastQuery::new(podcast_id.clone()))
+                })
+                .await
+                {
+                    Ok(podcast) => podcast,
+                    Err(e) => {
+                        error!("Error <{e}> getting podcast");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::QueuePodcastEpisodesError(
+                            id,
+                        )))
+                        .await
+                        .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::QueuePodcastEpisodes(
+                        podcast, id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_lyrics(
+        &mut self,
+        video_id: VideoID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting lyrics for {}", video_id.get_raw());
+                // The lyrics id is only available from the watch playlist.
+                let (api, video_id) = (&api, &video_id);
+                let lyrics = query_with_retry(&tx, id, || async move {
+                    let watch_playlist = api
+                        .get_watch_playlist(GetWatchPlaylistQuery::new_from_video_id(
+                            video_id.clone(),
+                        ))
+                        .await?;
+                    api.get_lyrics(GetLyricsQuery::new(watch_playlist.lyrics_id))
+                        .await
+                })
+                .await;
+                let lyrics = match lyrics {
+                    Ok(lyrics) => lyrics,
+                    Err(e) => {
+                        error!("Error <{e}> getting lyrics");
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::LyricsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceLyrics(lyrics, id)))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_library_playlists(&mut self, task: KillableTask) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting library playlists");
+                let playlists =
+                    match query_with_retry(&tx, id, || api.get_library_playlists()).await {
+                        Ok(playlists) => playlists,
+                        Err(e) => {
+                            error!("Error <{e}> getting library playlists");
+                            report_auth_error(&tx, e).await;
+                            tx.send(super::Response::Api(Response::LibraryPlaylistsError(id)))
+                                .await
+                                .unwrap_or_else(|_| error!("Error sending response"));
+                            return;
+                        }
+                    };
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceLibraryPlaylists(
+                        playlists, id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_library_subscriptions(&mut self, task: KillableTask) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting library subscriptions");
+                let subscriptions =
+                    match query_with_retry(&tx, id, || api.get_library_subscriptions()).await {
+                        Ok(subscriptions) => subscriptions,
+                        Err(e) => {
+                            error!("Error <{e}> getting library subscriptions");
+                            report_auth_error(&tx, e).await;
+                            tx.send(super::Response::Api(Response::LibrarySubscriptionsError(
+                                id,
+                            )))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                            return;
+                        }
+                    };
+                let channel_ids = subscriptions.into_iter().map(|a| a.channel_id).collect();
+                let _ = tx
+                    .send(super::Response::Api(Response::ReplaceLibrarySubscriptions(
+                        channel_ids,
+                        id,
+                    )))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_set_artist_subscribed(
+        &mut self,
+        channel_id: ChannelID<'static>,
+        subscribed: bool,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!(
+                    "Setting subscription to {} to {subscribed}",
+                    channel_id.get_raw()
+                );
+                let result = if subscribed {
+                    api.subscribe_artist(SubscribeArtistQuery::new(channel_id.clone()))
+                        .await
+                } else {
+                    api.unsubscribe_artist(UnsubscribeArtistQuery::new(channel_id.clone()))
+                        .await
+                };
+                let response = match result {
+                    Ok(()) => Response::ArtistSubscribed(channel_id, subscribed, id),
+                    Err(e) => {
+                        error!("Error <{e}> changing artist subscription");
+                        report_auth_error(&tx, e).await;
+                        Response::ArtistSubscriptionError(id)
+                    }
+                };
+                let _ = tx.send(super::Response::Api(response)).await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_add_song_to_library_playlist(
+        &mut self,
+        playlist_id: PlaylistID<'static>,
+        video_id: VideoID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!(
+                    "Adding song {} to playlist {}",
+                    video_id.get_raw(),
+                    playlist_id.get_raw()
+                );
+                let response = match api
+                    .add_playlist_items(AddPlaylistItemsQuery::new(playlist_id, vec![video_id]))
+                    .await
+                {
+                    Ok(()) => Response::SongAddedToLibraryPlaylist(id),
+                    Err(e) => {
+                        error!("Error <{e}> adding song to playlist");
+                        report_auth_error(&tx, e).await;
+                        Response::AddSongToLibraryPlaylistError(id)
+                    }
+                };
+                let _ = tx.send(super::Response::Api(response)).await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_rate_song(
+        &mut self,
+        video_id: VideoID<'static>,
+        rating: LikeStatus,
+        song_id: ListSongID,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Rating song {} as {:?}", video_id.get_raw(), rating);
+                let response = match api.rate_song(RateSongQuery::new(video_id, rating)).await {
+                    Ok(()) => Response::SongRated(song_id, rating, id),
+                    Err(e) => {
+                        error!("Error <{e}> rating song");
+                        report_auth_error(&tx, e).await;
+                        Response::RateSongError(id)
+                    }
+                };
+                let _ = tx.send(super::Response::Api(response)).await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_get_playlist_songs(
+        &mut self,
+        playlist_id: PlaylistID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                let _ = tx
+                    .send(super::Response::Api(Response::SongListLoading(id)))
+                    .await;
+                tracing::info!("Getting songs for playlist {:?}", playlist_id);
+                let playlist = match query_with_retry(&tx, id, || {
+                    api.get_playlist(GetPlaylistQuery::new(&playlist_id))
+                })
+                .await
+                {
+                    Ok(playlist) => playlist,
+                    Err(e) => {
+                        error!("Error <{e}> getting playlist {:?}", playlist_id);
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::PlaylistSongsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                if playlist.tracks.is_empty() {
+                    tracing::info!("Telling caller no songs found (empty playlist)");
+                    let _ = tx
+                        .send(super::Response::Api(Response::NoSongsFound(id)))
+                        .await;
+                    return;
+                }
+                let _ = tx
+                    .send(super::Response::Api(Response::SongsFound(id)))
+                    .await;
+                let mut songs_received = playlist.tracks.len();
+                if let Some(total) = playlist.track_count {
+                    send_progress(&tx, id, songs_received, total, "{}/{} songs").await;
+                }
+                let _ = tx
+                    .send(super::Response::Api(Response::AppendPlaylistSongList {
+                        song_list: playlist.tracks,
+                        id,
+                    }))
+                    .await;
+                // Large playlists are returned in pages, so keep requesting until all received.
+                let mut continuation = playlist.continuation;
+                while let Some(c) = continuation.take() {
+                    tracing::info!("Getting next page of playlist {:?}", playlist_id);
+                    let page = match api
+                        .get_playlist_continuation(GetPlaylistContinuationQuery::new(c))
+                        .await
+                    {
+                        Ok(page) => page,
+                        Err(e) => {
+                            // Songs already received are kept.
+                            error!("Error <{e}> getting next page of playlist");
+                            report_auth_error(&tx, e).await;
+                            break;
+                        }
+                    };
+                    continuation = page.continuation;
+                    songs_received += page.tracks.len();
+                    if let Some(total) = playlist.track_count {
+                        send_progress(&tx, id, songs_received, total, "{}/{} songs").await;
+                    }
+                    let _ = tx
+                        .send(super::Response::Api(Response::AppendPlaylistSongList {
+                            song_list: page.tracks,
+                            id,
+                        }))
+                        .await;
+                }
+                let _ = tx
+                    .send(super::Response::Api(Response::SongListLoaded(id)))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_queue_playlist_songs(
+        &mut self,
+        playlist_id: PlaylistID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Getting songs to queue for playlist {:?}", playlist_id);
+                let playlist = match query_with_retry(&tx, id, || {
+                    api.get_playlist(GetPlaylistQuery::new(&playlist_id))
+                })
+                .await
+                {
+                    Ok(playlist) => playlist,
+                    Err(e) => {
+                        error!("Error <{e}> getting playlist {:?}", playlist_id);
+                        report_auth_error(&tx, e).await;
+                        tx.send(super::Response::Api(Response::QueuePlaylistSongsError(id)))
+                            .await
+                            .unwrap_or_else(|_| error!("Error sending response"));
+                        return;
+                    }
+                };
+                let mut song_list = playlist.tracks;
+                // The songs are queued together, so all pages are received first.
+                let mut continuation = playlist.continuation;
+                while let Some(c) = continuation.take() {
+                    let page = match api
+                        .get_playlist_continuation(GetPlaylistContinuationQuery::new(c))
+                        .await
+                    {
+                        Ok(page) => page,
+                        Err(e) => {
+                            // Songs already received are still queued.
+                            error!("Error <{e}> getting next page of playlist");
+                            report_auth_error(&tx, e).await;
+                            break;
+                        }
+                    };
+                    continuation = page.continuation;
+                    song_list.extend(page.tracks);
+                    if let Some(total) = playlist.track_count {
+                        send_progress(&tx, id, song_list.len(), total, "{}/{} songs").await;
+                    }
+                }
+                let _ = tx
+                    .send(super::Response::Api(Response::QueuePlaylistSongs {
+                        song_list,
+                        id,
+                    }))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+    async fn handle_search_selected_artist(
+        &mut self,
+        browse_id: ChannelID<'static>,
+        task: KillableTask,
+    ) -> Result<()> {
+        let KillableTask { id, kill_rx } = task;
+        // See above note
+        let tx = self.response_tx.clone();
+        let api = match self.get_api().await {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Error {e} connecting to API");
+                tx.send(crate::app::server::Response::Api(Response::ApiError(e)))
+                    .await?;
+                // Rough guard against the case of sending an unkown api error.
+                // TODO: Better handling for this edge case.
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                return Err(Error::UnknownAPIError);
+            }
+        }
+        .clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                let tx = tx.clone();
+                let _ = tx
+                    .send(super::Response::Api(Response::SongListLoading(id)))
+                    .await;
+                tracing::info!("Running songs query");
+                // Should this be a ChannelID or BrowseID? Should take a trait?.
+                // Should this actually take ChannelID::try_from(BrowseID::Artist) -> ChannelID::Artist?
+                let artist = query_with_retry(&tx, id, || {
+                    api.get_artist(ytmapi_rs::query::GetArtistQuery::new(
+                        ytmapi_rs::ChannelID::from_raw(browse_id.get_raw()),
+                    ))
+                })
+                .await;
+                let artist = match artist {
+                    Ok(a) => a,
+                    Err(e) => {
+                        let Some((json, key)) = e.get_json_and_key() else {
+                            return;
+                        };
+                        // TODO: Bring loggable json errors into their own function.
+                        error!("API error recieved at key {:?}", key);
+                        match get_cache_dir().map(|dir| dir.join(API_ERROR_JSON_FILE_NAME)) {
+                            Ok(path) => {
+                                std::fs::write(&path, json)
+                                    .unwrap_or_else(|e| error!("Error <{e}> writing json log"));
+                                info!("Wrote json to {:?}", path);
+                            }
+                            Err(e) => error!("Error <{e}> writing json log"),
+                        }
+                        tracing::info!("Telling caller no songs found (error)");
+                        let _ = tx
+                            .send(super::Response::Api(Response::NoSongsFound(id)))
+                            .await;
+                        return;
+                    }
+                };
+                let Some(albums) = artist.top_releases.albums else {
+                    tracing::info!("Telling caller no songs found (no params)");
+                    let _ = tx
+                        .send(super::Response::Api(Response::NoSongsFound(id)))
+                        .await;
+                    return;
+                };
+
+                let GetArtistAlbums {
+                    browse_id: artist_albums_browse_id,
+                    params: artist_albums_params,
+                    results: artist_albums_results,
+                } = albums;
+                let browse_id_list = if artist_albums_browse_id.is_none()
+                    && artist_albums_params.is_none()
+                    && !artist_albums_results.is_empty()
+                {
+                    // Assume we already got all the albums from the search.
+                    let browse_id_list: Option<Vec<_>> = artist_albums_results
+                        .iter()
+                        .map(|r| {
+                            r.get_channel_id()
+                                .as_ref()
+                                .map(|c_id| AlbumID::from_raw(c_id.get_raw()))
+                        })
+                        .collect();
+                    if let Some(browse_id_list) = browse_id_list {
+                        browse_id_list
+                    } else {
+                        tracing::info!(
+                            "Telling caller no songs found (some albums missing browse id)"
+                        );
+                        let _ = tx
+                            .send(super::Response::Api(Response::NoSongsFound(id)))
+                            .await;
+                        return;
+                    }
+                } else if artist_albums_params.is_none() || artist_albums_browse_id.is_none() {
+                    tracing::info!("Telling caller no songs found (no params or browse_id)");
+                    let _ = tx
+                        .send(super::Response::Api(Response::NoSongsFound(id)))
+                        .await;
+                    return;
+                } else {
+                    // Must have params and browse_id
+                    let Some(temp_browse_id) = artist_albums_browse_id else {
+                        unreachable!("Checked not none above")
+                    };
+                    let Some(temp_params) = artist_albums_params else {
+                        unreachable!("Checked not none above")
+                    };
+
+                    let albums = match api
+                        .get_artist_albums(ytmapi_rs::query::GetArtistAlbumsQuery::new(
+                            ytmapi_rs::ChannelID::from_raw(temp_browse_id.get_raw()),
+                            temp_params,
+                        ))
+                        .await
+                    {
+                        Ok(r) => r,
+                        Err(e) => {
+                            error!("Received error on get_artist_albums query \"{}\"", e);
+                            report_auth_error(&tx, e).await;
+
+                            // TODO: Better Error type
+                            tx.send(super::Response::Api(Response::SearchArtistError(id)))
+                                .await
+                                .unwrap_or_else(|_| error!("Error sending response"));
+                            return;
+                        }
+                    };
+                    albums.into_iter().map(|a| a.browse_id).collect()
+                };
+                let _ = tx
+                    .send(super::Response::Api(Response::SongsFound(id)))
+                    .await;
+                let albums_total = browse_id_list.len();
+                send_progress(&tx, id, 0, albums_total, "{}/{} albums").await;
+                // Request the albums concurrently, sending each one once received.
+                let albums = api.get_albums(browse_id_list, MAX_CONCURRENT_ALBUM_QUERIES);
+                let mut albums = std::pin::pin!(albums);
+                tracing::info!(
+                    "Spawning requests for caller tracks for request ID {:?}",
+                    id
+                );
+                let mut albums_done = 0;
+                while let Some((b_id, album)) = albums.next().await {
+                    albums_done += 1;
+                    send_progress(&tx, id, albums_done, albums_total, "{}/{} albums").await;
+                    let album = match album {
+                        Ok(album) => album,
+                        Err(e) => {
+                            error!("Error <{e}> getting album {:?}", b_id);
+                            report_auth_error(&tx, e).await;
+                            continue;
+                        }
+                    };
+                    tracing::info!("Sending caller tracks for request ID {:?}", id);
+                    let _ = tx
+                        .send(super::Response::Api(Response::AppendSongList {
+                            song_list: album.tracks,
+                            album: album.title,
+                            year: album.year,
+                            // TODO: remove allocation
+                            artist: artist.name.clone(),
+                            album_id: AlbumID::from_raw(b_id.get_raw().to_string()),
+                            artist_id: Some(browse_id.clone()),
+                            id,
+                        }))
+                        .await;
+                }
+                let _ = tx
+                    .send(super::Response::Api(Response::SongListLoaded(id)))
+                    .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+        Ok(())
+    }
+}
+
+fn spawn_api_init(
+    api_key: ApiKey,
+) -> tokio::task::JoinHandle<Result<ytmapi_rs::YtMusic<BrowserToken>>> {
+    tokio::spawn(async move {
+        info!("Initialising API");
+        let api = match api_key {
+            ApiKey::BrowserToken(c) => ytmapi_rs::YtMusic::from_cookie(c).await?,
+            // TODO: Add OAuth
+            // ytmapi_rs::YtMusic::from_oauth_token(t),
+            ApiKey::OAuthToken(_) => return Err(Error::OAuthNotYetSupportedByApp),
+            ApiKey::Demo => crate::demo::get_demo_api(),
+        };
+        info!("API initialised");
+        Ok(api)
+    })
+}
+
+/// Let the caller know how much of a task is done, labelled with a template such as
+/// "{}/{} albums".
+async fn send_progress(
+    tx: &mpsc::Sender<super::Response>,
+    id: TaskID,
+    done: usize,
+    total: usize,
+    template: &'static str,
+) {
+    let progress = Percentage((done * 100 / total.max(1)).min(100) as u8);
+    let label = tr_format(template, &[&done, &total]);
+    let _ = tx
+        .send(super::Response::TaskProgress(id, progress, label))
+        .await;
+}
+
+/// Run the query, retrying it with backoff if it fails in a way that may not happen again, e.g
+/// a network blip or being rate limited. The caller is told each time the task is retried.
+async fn query_with_retry<T, F, Fut>(
+    tx: &mpsc::Sender<super::Response>,
+    id: TaskID,
+    mut query: F,
+) -> ytmapi_rs::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ytmapi_rs::Result<T>>,
+{
+    let mut retries = 0;
+    loop {
+        let e = match query().await {
+            Ok(t) => return Ok(t),
+            Err(e) => e,
+        };
+        let Some(delay) = retry_delay(&e, retries) else {
+            return Err(e);
+        };
+        retries += 1;
+        warn!(
+            "Error <{e}> running query, retrying in {}s ({retries}/{MAX_QUERY_RETRIES})",
+            delay.as_secs()
+        );
+        let _ = tx.send(super::Response::TaskRetrying(id)).await;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// How long to wait before retrying a query that failed with the error, or None if it
+/// shouldn't be retried.
+fn retry_delay(e: &ytmapi_rs::Error, retries: u32) -> Option<Duration> {
+    if retries >= MAX_QUERY_RETRIES {
+        return None;
+    }
+    e.retry_delay(retries)
+}
+
+/// If the error was caused by the credentials, e.g they have expired, let the caller know so
+/// that the user can sign in again.
+async fn report_auth_error(tx: &mpsc::Sender<super::Response>, e: ytmapi_rs::Error) {
+    if e.is_auth_error() {
+        let _ = tx
+            .send(super::Response::Api(Response::ApiError(Error::ApiError(e))))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retry_delay, MAX_QUERY_RETRIES};
+    use std::time::Duration;
+
+    #[test]
+    fn test_retry_delay() {
+        let network_error = || ytmapi_rs::Error::from(std::io::Error::other("Connection reset"));
+        assert_eq!(
+            retry_delay(&network_error(), 0),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            retry_delay(&network_error(), 2),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(retry_delay(&network_error(), MAX_QUERY_RETRIES), None);
+    }
 }
@@ -0,0 +1,700 @@
+//! Hands the queue off to a Google Cast device, such as a Chromecast, on the local network.
+//! Devices are found with mDNS, and the YouTube app is launched on them with DIAL. The queue and
+//! transport controls are then sent through YouTube's lounge API, which also reports the
+//! device's play state back.
+use super::{spawn_run_or_kill, KillableTask, TaskTimeout};
+use crate::app::taskmanager::TaskID;
+use crate::core::send_or_error;
+use crate::error::Error;
+use crate::Result;
+use serde_json::Value;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use ytmapi_rs::{common::YoutubeID, VideoID};
+
+const MDNS_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(224, 0, 0, 251)), 5353);
+const CAST_SERVICE_NAME: &str = "_googlecast._tcp.local";
+/// How long to wait for devices to answer a discovery query.
+const DISCOVERY_DURATION: Duration = Duration::from_secs(3);
+const DIAL_PORT: u16 = 8008;
+/// The YouTube app's screen id is only available once it has started.
+const SCREEN_ID_ATTEMPTS: usize = 10;
+const SCREEN_ID_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const LOUNGE_TOKEN_URL: &str = "https://www.youtube.com/api/lounge/pairing/get_lounge_token_batch";
+const BIND_URL: &str = "https://www.youtube.com/api/lounge/bc/bind";
+const LOUNGE_TOKEN_HEADER: &str = "X-YouTube-LoungeId-Token";
+const CAST_COMMAND_QUEUE_SIZE: usize = 16;
+// DNS record types and the class used by mDNS.
+const DNS_TYPE_PTR: u16 = 12;
+const DNS_TYPE_TXT: u16 = 16;
+const DNS_CLASS_IN: u16 = 1;
+
+#[derive(Debug)]
+pub enum Request {
+    FindDevices(KillableTask),
+    /// Play the songs on the device, starting from the song at the index.
+    CastQueue(CastDevice, Vec<VideoID<'static>>, usize, KillableTask),
+    Command(CastCommand),
+    /// Leave the device playing, but stop controlling it.
+    Disconnect,
+}
+
+#[derive(Debug)]
+pub enum Response {
+    DevicesFound(Vec<CastDevice>, TaskID),
+    Connected(CastDevice, TaskID),
+    ConnectError(String, TaskID),
+    /// The device's play state changed, e.g it was paused from another remote.
+    StateChanged(CastState),
+    /// The session with the device ended, e.g as it was closed on the device.
+    Disconnected,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CastDevice {
+    pub name: String,
+    pub addr: IpAddr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CastCommand {
+    Play,
+    Pause,
+    Next,
+    Previous,
+    /// Set the device's volume, from 0 to 100.
+    SetVolume(u8),
+}
+
+impl CastCommand {
+    fn action(&self) -> &'static str {
+        match self {
+            CastCommand::Play => "play",
+            CastCommand::Pause => "pause",
+            CastCommand::Next => "next",
+            CastCommand::Previous => "previous",
+            CastCommand::SetVolume(_) => "setVolume",
+        }
+    }
+    fn args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            CastCommand::SetVolume(volume) => vec![("volume", (*volume).min(100).to_string())],
+            CastCommand::Play | CastCommand::Pause | CastCommand::Next | CastCommand::Previous => {
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CastPlayStatus {
+    Playing,
+    Paused,
+    Buffering,
+    Stopped,
+}
+
+/// Play state reported by the device. The video is only reported when it changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CastState {
+    pub video_id: Option<String>,
+    pub status: CastPlayStatus,
+    pub elapsed_secs: Option<f64>,
+}
+
+pub struct Cast {
+    client: reqwest::Client,
+    response_tx: mpsc::Sender<super::Response>,
+    task_timeout: TaskTimeout,
+    /// Commands for the device being cast to. Dropping this ends the session.
+    session_tx: Option<mpsc::Sender<CastCommand>>,
+}
+
+impl Cast {
+    pub fn new(response_tx: mpsc::Sender<super::Response>, task_timeout: TaskTimeout) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            response_tx,
+            task_timeout,
+            session_tx: None,
+        }
+    }
+    pub async fn handle_request(&mut self, request: Request) {
+        match request {
+            Request::FindDevices(task) => self.handle_find_devices(task).await,
+            Request::CastQueue(device, video_ids, index, task) => {
+                self.handle_cast_queue(device, video_ids, index, task).await
+            }
+            Request::Command(command) => match &self.session_tx {
+                Some(tx) => send_or_error(tx, command).await,
+                None => warn!("Received cast command {:?} while not casting", command),
+            },
+            Request::Disconnect => {
+                info!("Disconnecting from cast device");
+                self.session_tx = None;
+            }
+        }
+    }
+    async fn handle_find_devices(&self, task: KillableTask) {
+        let KillableTask { id, kill_rx } = task;
+        let tx = self.response_tx.clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                let devices = find_devices().await.unwrap_or_else(|e| {
+                    warn!("Error <{e}> finding cast devices");
+                    Vec::new()
+                });
+                info!("Found {} cast devices", devices.len());
+                send_or_error(
+                    tx,
+                    super::Response::Cast(Response::DevicesFound(devices, id)),
+                )
+                .await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+    }
+    /// Connect to the device and hand off the queue. Once connected, the session runs until
+    /// it's disconnected, independent of the task.
+    async fn handle_cast_queue(
+        &mut self,
+        device: CastDevice,
+        video_ids: Vec<VideoID<'static>>,
+        index: usize,
+        task: KillableTask,
+    ) {
+        let KillableTask { id, kill_rx } = task;
+        let (session_tx, session_rx) = mpsc::channel(CAST_COMMAND_QUEUE_SIZE);
+        // Replacing the sender ends any previous session.
+        self.session_tx = Some(session_tx);
+        let client = self.client.clone();
+        let tx = self.response_tx.clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                info!("Casting {} songs to {}", video_ids.len(), device.name);
+                let session = match LoungeSession::connect(&client, &device).await {
+                    Ok(session) => session,
+                    Err(e) => {
+                        send_or_error(
+                            tx,
+                            super::Response::Cast(Response::ConnectError(e.to_string(), id)),
+                        )
+                        .await;
+                        return;
+                    }
+                };
+                if let Err(e) = session.set_playlist(&client, &video_ids, index).await {
+                    send_or_error(
+                        tx,
+                        super::Response::Cast(Response::ConnectError(e.to_string(), id)),
+                    )
+                    .await;
+                    return;
+                }
+                send_or_error(&tx, super::Response::Cast(Response::Connected(device, id))).await;
+                tokio::spawn(run_session(session, client, session_rx, tx));
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+    }
+}
+
+/// Send commands to the device as they're received, and report its state, until the command
+/// sender is dropped or the session ends.
+async fn run_session(
+    session: LoungeSession,
+    client: reqwest::Client,
+    mut command_rx: mpsc::Receiver<CastCommand>,
+    tx: mpsc::Sender<super::Response>,
+) {
+    let mut poll = Box::pin(session.poll(&client));
+    loop {
+        tokio::select! {
+            command = command_rx.recv() => {
+                let Some(command) = command else {
+                    session.terminate(&client).await;
+                    return;
+                };
+                let args = command.args();
+                let args: Vec<_> = args.iter().map(|(k, v)| (*k, v.as_str())).collect();
+                if let Err(e) = session.send_action(&client, command.action(), &args).await {
+                    warn!("Error <{e}> sending {:?} to cast device", command);
+                }
+            }
+            events = &mut poll => {
+                match events {
+                    Ok(events) => {
+                        for state in events.iter().filter_map(LoungeEvent::to_cast_state) {
+                            send_or_error(&tx, super::Response::Cast(Response::StateChanged(state)))
+                                .await;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Error <{e}> polling cast device, disconnecting");
+                        send_or_error(&tx, super::Response::Cast(Response::Disconnected)).await;
+                        return;
+                    }
+                }
+                poll = Box::pin(session.poll(&client));
+            }
+        }
+    }
+}
+
+/// Ask every cast device on the network to identify itself, and collect the answers.
+async fn find_devices() -> Result<Vec<CastDevice>> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    // Queries from a port other than 5353 are answered directly, so the multicast group
+    // doesn't need to be joined.
+    socket.send_to(&discovery_query(), MDNS_ADDR).await?;
+    let mut devices: Vec<CastDevice> = Vec::new();
+    let mut buf = [0; 4096];
+    let deadline = tokio::time::Instant::now() + DISCOVERY_DURATION;
+    while let Ok(received) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+        let (len, addr) = received?;
+        let Some(name) = parse_device_name(&buf[..len]) else {
+            continue;
+        };
+        if !devices.iter().any(|d| d.addr == addr.ip()) {
+            devices.push(CastDevice {
+                name,
+                addr: addr.ip(),
+            });
+        }
+    }
+    Ok(devices)
+}
+
+/// An mDNS query for cast devices.
+fn discovery_query() -> Vec<u8> {
+    // Header: id, flags, then one question and no records.
+    let mut query = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in CAST_SERVICE_NAME.split('.') {
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&DNS_TYPE_PTR.to_be_bytes());
+    query.extend_from_slice(&DNS_CLASS_IN.to_be_bytes());
+    query
+}
+
+/// The friendly name of the device from the "fn" entry of the TXT record in an mDNS answer, or
+/// None if the packet isn't from a cast device.
+fn parse_device_name(packet: &[u8]) -> Option<String> {
+    let read_u16 = |pos: usize| {
+        Some(u16::from_be_bytes([
+            *packet.get(pos)?,
+            *packet.get(pos + 1)?,
+        ]))
+    };
+    let questions = read_u16(4)?;
+    let records = read_u16(6)? + read_u16(8)? + read_u16(10)?;
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = skip_name(packet, pos)? + 4;
+    }
+    for _ in 0..records {
+        pos = skip_name(packet, pos)?;
+        let record_type = read_u16(pos)?;
+        let len = read_u16(pos + 8)? as usize;
+        pos += 10;
+        let data = packet.get(pos..pos + len)?;
+        pos += len;
+        if record_type != DNS_TYPE_TXT {
+            continue;
+        }
+        let mut entries = data;
+        while let Some((&entry_len, rest)) = entries.split_first() {
+            let entry = rest.get(..entry_len as usize)?;
+            if let Some(name) = entry.strip_prefix(b"fn=") {
+                return Some(String::from_utf8_lossy(name).into_owned());
+            }
+            entries = &rest[entry_len as usize..];
+        }
+    }
+    None
+}
+
+/// The position after the name starting at pos. Names end with an empty label, or a pointer to
+/// a name earlier in the packet.
+fn skip_name(packet: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(pos)?;
+        match len {
+            0 => return Some(pos + 1),
+            len if len & 0xC0 == 0xC0 => return Some(pos + 2),
+            len => pos += 1 + len as usize,
+        }
+    }
+}
+
+/// The screen id of the YouTube app from its DIAL status, which is needed to pair with it.
+fn parse_screen_id(status: &str) -> Option<&str> {
+    let start = status.find("<screenId>")? + "<screenId>".len();
+    let len = status[start..].find("</screenId>")?;
+    Some(&status[start..start + len]).filter(|id| !id.is_empty())
+}
+
+/// A message from the lounge API, e.g ["onStateChange", {"state": "1"}].
+#[derive(Clone, Debug, PartialEq)]
+struct LoungeEvent {
+    id: u64,
+    name: String,
+    args: Vec<Value>,
+}
+
+impl LoungeEvent {
+    fn to_cast_state(&self) -> Option<CastState> {
+        let args = self.args.first()?;
+        let video_id = match self.name.as_str() {
+            "nowPlaying" => Some(args.get("videoId")?.as_str()?.to_string()),
+            "onStateChange" => None,
+            _ => return None,
+        };
+        // Numbers are sent as strings.
+        let status = match args.get("state")?.as_str()? {
+            "1" => CastPlayStatus::Playing,
+            "2" => CastPlayStatus::Paused,
+            "-1" | "3" => CastPlayStatus::Buffering,
+            "0" => CastPlayStatus::Stopped,
+            _ => return None,
+        };
+        Some(CastState {
+            video_id,
+            status,
+            elapsed_secs: args
+                .get("currentTime")
+                .and_then(Value::as_str)
+                .and_then(|t| t.parse().ok()),
+        })
+    }
+}
+
+/// Parse a lounge API response, which is made up of chunks of a length line followed by a json
+/// array of [id, [name, args...]] events.
+fn parse_lounge_events(body: &str) -> Vec<LoungeEvent> {
+    let mut events = Vec::new();
+    let mut rest = body.trim_start();
+    // The length line is skipped, as the end of the array can be found by parsing it.
+    while let Some((_, chunk)) = rest.split_once('\n') {
+        let mut stream = serde_json::Deserializer::from_str(chunk).into_iter::<Vec<Value>>();
+        let Some(Ok(array)) = stream.next() else {
+            break;
+        };
+        rest = chunk[stream.byte_offset()..].trim_start();
+        for event in array {
+            let (Some(id), Some(Value::Array(message))) = (event.get(0), event.get(1)) else {
+                continue;
+            };
+            let Some((name, args)) = message.split_first() else {
+                continue;
+            };
+            events.push(LoungeEvent {
+                id: id.as_u64().unwrap_or_default(),
+                name: name.as_str().unwrap_or_default().to_string(),
+                args: args.to_vec(),
+            });
+        }
+    }
+    events
+}
+
+/// A connection to the YouTube app on a cast device, as a remote control.
+struct LoungeSession {
+    lounge_token: String,
+    sid: String,
+    gsessionid: String,
+    client_id: String,
+    /// Each request is numbered, and events are acknowledged by the id of the last one seen.
+    rid: AtomicU64,
+    ofs: AtomicU64,
+    aid: AtomicU64,
+}
+
+impl LoungeSession {
+    async fn connect(client: &reqwest::Client, device: &CastDevice) -> Result<Self> {
+        let app_url = format!("http://{}:{DIAL_PORT}/apps/YouTube", device.addr);
+        client
+            .post(&app_url)
+            .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body("")
+            .send()
+            .await?
+            .error_for_status()?;
+        let mut screen_id = None;
+        for _ in 0..SCREEN_ID_ATTEMPTS {
+            let status = client.get(&app_url).send().await?.text().await?;
+            if let Some(id) = parse_screen_id(&status) {
+                screen_id = Some(id.to_string());
+                break;
+            }
+            tokio::time::sleep(SCREEN_ID_RETRY_INTERVAL).await;
+        }
+        let screen_id = screen_id
+            .ok_or_else(|| Error::Other(format!("YouTube didn't start on {}", device.name)))?;
+        let tokens: Value = serde_json::from_str(
+            &client
+                .post(LOUNGE_TOKEN_URL)
+                .form(&[("screen_ids", &screen_id)])
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?,
+        )?;
+        let lounge_token = tokens["screens"][0]["loungeToken"]
+            .as_str()
+            .ok_or_else(|| Error::Other("No lounge token for the device".into()))?
+            .to_string();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let mut session = Self {
+            lounge_token,
+            sid: String::new(),
+            gsessionid: String::new(),
+            client_id: format!("youtui{nanos:x}"),
+            rid: AtomicU64::new(nanos as u64 % 10000),
+            ofs: AtomicU64::new(0),
+            aid: AtomicU64::new(0),
+        };
+        let body = client
+            .post(BIND_URL)
+            .header(LOUNGE_TOKEN_HEADER, &session.lounge_token)
+            .query(&session.device_params())
+            .query(&[("RID", session.next_rid().to_string())])
+            .form(&[("count", "0")])
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        for event in parse_lounge_events(&body) {
+            let arg = || {
+                event
+                    .args
+                    .first()
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            match event.name.as_str() {
+                "c" => session.sid = arg(),
+                "S" => session.gsessionid = arg(),
+                _ => (),
+            }
+        }
+        if session.sid.is_empty() {
+            return Err(Error::Other(format!(
+                "Unable to connect to {}",
+                device.name
+            )));
+        }
+        Ok(session)
+    }
+    fn device_params(&self) -> [(&'static str, &str); 6] {
+        [
+            ("device", "REMOTE_CONTROL"),
+            ("id", &self.client_id),
+            ("name", "youtui"),
+            ("app", "youtui"),
+            ("mdx-version", "3"),
+            ("VER", "8"),
+        ]
+    }
+    fn session_params(&self) -> [(&'static str, &str); 3] {
+        [
+            ("SID", &self.sid),
+            ("gsessionid", &self.gsessionid),
+            ("CVER", "1"),
+        ]
+    }
+    fn next_rid(&self) -> u64 {
+        self.rid.fetch_add(1, Ordering::Relaxed)
+    }
+    async fn set_playlist(
+        &self,
+        client: &reqwest::Client,
+        video_ids: &[VideoID<'static>],
+        index: usize,
+    ) -> Result<()> {
+        let ids: Vec<_> = video_ids.iter().map(|id| id.get_raw()).collect();
+        let current = ids.get(index).copied().unwrap_or_default();
+        let all = ids.join(",");
+        let index = index.to_string();
+        self.send_action(
+            client,
+            "setPlaylist",
+            &[
+                ("videoId", current),
+                ("videoIds", all.as_str()),
+                ("currentIndex", index.as_str()),
+                ("currentTime", "0"),
+            ],
+        )
+        .await
+    }
+    async fn send_action(
+        &self,
+        client: &reqwest::Client,
+        action: &str,
+        args: &[(&str, &str)],
+    ) -> Result<()> {
+        let ofs = self.ofs.fetch_add(1, Ordering::Relaxed).to_string();
+        let mut form = vec![
+            ("count".to_string(), "1".to_string()),
+            ("ofs".to_string(), ofs),
+            ("req0__sc".to_string(), action.to_string()),
+        ];
+        form.extend(
+            args.iter()
+                .map(|(k, v)| (format!("req0_{k}"), v.to_string())),
+        );
+        client
+            .post(BIND_URL)
+            .header(LOUNGE_TOKEN_HEADER, &self.lounge_token)
+            .query(&self.device_params())
+            .query(&self.session_params())
+            .query(&[("RID", self.next_rid().to_string())])
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+    /// Wait for the next events from the device. The request is held open by the server until
+    /// there's something to report.
+    async fn poll(&self, client: &reqwest::Client) -> Result<Vec<LoungeEvent>> {
+        let aid = self.aid.load(Ordering::Relaxed).to_string();
+        let body = client
+            .get(BIND_URL)
+            .header(LOUNGE_TOKEN_HEADER, &self.lounge_token)
+            .query(&self.device_params())
+            .query(&self.session_params())
+            .query(&[
+                ("RID", "rpc"),
+                ("TYPE", "xmlhttp"),
+                ("CI", "1"),
+                ("AID", aid.as_str()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let events = parse_lounge_events(&body);
+        if let Some(last) = events.last() {
+            self.aid.store(last.id, Ordering::Relaxed);
+        }
+        Ok(events)
+    }
+    async fn terminate(&self, client: &reqwest::Client) {
+        let result = client
+            .post(BIND_URL)
+            .header(LOUNGE_TOKEN_HEADER, &self.lounge_token)
+            .query(&self.device_params())
+            .query(&self.session_params())
+            .query(&[("RID", self.next_rid().to_string())])
+            .form(&[("ui", ""), ("TYPE", "terminate")])
+            .send()
+            .await;
+        if let Err(e) = result {
+            warn!("Error <{e}> ending cast session");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        discovery_query, parse_device_name, parse_lounge_events, parse_screen_id, CastCommand,
+        CastPlayStatus, CastState,
+    };
+
+    /// An mDNS answer with a PTR record pointing back to the question by compression, and a
+    /// TXT record for the device.
+    fn answer(txt: &[&str]) -> Vec<u8> {
+        let mut packet = discovery_query();
+        packet[2] = 0x84;
+        packet[7] = 2;
+        // PTR record, with the name as a pointer to the question.
+        packet.extend_from_slice(&[0xC0, 12, 0, 12, 0, 1, 0, 0, 0, 120, 0, 2, 0xC0, 12]);
+        let data: Vec<u8> = txt
+            .iter()
+            .flat_map(|e| std::iter::once(e.len() as u8).chain(e.bytes()))
+            .collect();
+        packet.extend_from_slice(&[0xC0, 12, 0, 16, 0x80, 1, 0, 0, 0, 120]);
+        packet.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&data);
+        packet
+    }
+    #[test]
+    fn test_discovery_query() {
+        let query = discovery_query();
+        assert_eq!(&query[..12], &[0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(&query[12..24], b"\x0b_googlecast");
+        assert_eq!(&query[query.len() - 4..], &[0, 12, 0, 1]);
+    }
+    #[test]
+    fn test_parse_device_name() {
+        assert_eq!(
+            parse_device_name(&answer(&["id=abc", "fn=Living Room", "md=Chromecast"])),
+            Some("Living Room".to_string())
+        );
+        assert_eq!(parse_device_name(&answer(&["id=abc"])), None);
+        assert_eq!(parse_device_name(&discovery_query()), None);
+        // Truncated packets are ignored rather than read out of bounds.
+        let packet = answer(&["fn=Kitchen"]);
+        assert_eq!(parse_device_name(&packet[..packet.len() - 3]), None);
+    }
+    #[test]
+    fn test_parse_screen_id() {
+        let status = "<service><name>YouTube</name><state>running</state><additionalData>\
+                      <screenId>abc123</screenId></additionalData></service>";
+        assert_eq!(parse_screen_id(status), Some("abc123"));
+        assert_eq!(parse_screen_id("<state>stopped</state>"), None);
+    }
+    #[test]
+    fn test_parse_lounge_events() {
+        let body = "39\n[[0,[\"c\",\"sid\",\"\",8]],[1,[\"S\",\"gsid\"]]]\n\
+                    70\n[[2,[\"nowPlaying\",{\"videoId\":\"abc\",\"currentTime\":\"1.5\",\
+                    \"state\":\"1\"}]]]\n";
+        let events = parse_lounge_events(body);
+        let names: Vec<_> = events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["c", "S", "nowPlaying"]);
+        assert_eq!(events[2].id, 2);
+        assert_eq!(
+            events[2].to_cast_state(),
+            Some(CastState {
+                video_id: Some("abc".to_string()),
+                status: CastPlayStatus::Playing,
+                elapsed_secs: Some(1.5),
+            })
+        );
+        assert_eq!(events[0].to_cast_state(), None);
+    }
+    #[test]
+    fn test_cast_command_args() {
+        assert!(CastCommand::Play.args().is_empty());
+        assert_eq!(
+            CastCommand::SetVolume(40).args(),
+            vec![("volume", "40".to_string())]
+        );
+        assert_eq!(
+            CastCommand::SetVolume(150).args(),
+            vec![("volume", "100".to_string())]
+        );
+    }
+}
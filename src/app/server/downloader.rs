@@ -1,47 +1,215 @@
-use super::{spawn_run_or_kill, KillableTask, DL_CALLBACK_CHUNK_SIZE};
+use super::export::{
+    cover_to_jpeg, export_path, song_extension, write_song, ExportSettings, SongTags,
+    EXPORT_AUDIO_ITAGS,
+};
+use super::musiccache::MusicCache;
+use super::{lock, spawn_run_or_kill, KillableTask, TaskTimeout, DL_CALLBACK_CHUNK_SIZE};
 use crate::{
     app::{
         structures::{ListSongID, Percentage},
         taskmanager::TaskID,
     },
+    config::{AudioFormatPreference, AudioQuality, DownloadRateLimit},
     core::send_or_error,
+    error::Error,
+    Result,
 };
-use rusty_ytdl::{DownloadOptions, Video, VideoOptions};
-use tokio::sync::mpsc;
-use tracing::{error, info};
+use image::DynamicImage;
+use rusty_ytdl::Video;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, Semaphore};
+use tokio::time::Instant;
+use tracing::{error, info, warn};
 use ytmapi_rs::{common::YoutubeID, VideoID};
 
+/// Number of progress updates buffered for each request of a song download.
+const PROGRESS_CHANNEL_SIZE: usize = 16;
+/// Number of times a failed chunk is retried before the download fails.
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+const RESUME_DELAY: Duration = Duration::from_millis(500);
+/// Number of times a song download is attempted before it fails.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 4;
+/// Delay before the first retry of a failed song download, doubled for each further retry.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
 pub enum Request {
     DownloadSong(VideoID<'static>, ListSongID, KillableTask),
+    /// Download and decode the thumbnail at the url.
+    DownloadAlbumArt(String, ListSongID, KillableTask),
+    /// Save a song to the export directory, tagged with its metadata and cover art.
+    ExportSong(VideoID<'static>, SongTags, KillableTask),
 }
 #[derive(Debug)]
 pub enum Response {
     DownloadProgressUpdate(DownloadProgressUpdateType, ListSongID, TaskID),
+    AlbumArt(DynamicImage, ListSongID, TaskID),
+    AlbumArtError(ListSongID, TaskID),
+    /// A song was exported to the path.
+    SongExported(PathBuf, TaskID),
+    /// Exporting the song with the title failed, with the error message.
+    ExportError(String, String, TaskID),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum DownloadProgressUpdateType {
     Started,
     Downloading(Percentage),
-    Completed(Vec<u8>),
-    Error,
+    /// The download was interrupted, and is continuing from the last received byte.
+    Resuming(Percentage),
+    /// The downloaded song, and its bitrate in bits per second if known.
+    Completed(Vec<u8>, Option<u64>),
+    /// The download failed after retrying, with the error message.
+    Error(String),
+}
+
+/// Progress senders of the songs being downloaded, by video id.
+type InFlightDownloads = Arc<Mutex<HashMap<String, broadcast::Sender<DownloadProgressUpdateType>>>>;
+
+/// Settings for downloading songs, from the config file.
+pub struct DownloadSettings {
+    /// Maximum size of the music cache in bytes.
+    pub music_cache_size: u64,
+    pub max_concurrent_downloads: usize,
+    pub rate_limit: DownloadRateLimit,
+    pub audio_format: AudioFormatPreference,
+    pub export: ExportSettings,
+}
+
+/// An audio only stream of a song.
+#[derive(Clone, Debug, PartialEq)]
+struct AudioStream {
+    itag: u64,
+    /// Bits per second.
+    bitrate: u64,
+    url: String,
+    content_length: Option<u64>,
+}
+
+/// A song that has been partly downloaded from the url of its audio stream.
+#[derive(Default)]
+struct PartialDownload {
+    url: String,
+    content_length: u64,
+    bitrate: Option<u64>,
+    song: Vec<u8>,
+}
+
+impl PartialDownload {
+    fn progress(&self) -> Percentage {
+        Percentage((self.song.len() as u64 * 100 / self.content_length.max(1)) as u8)
+    }
+}
+
+/// A song being downloaded. If it is dropped before the download completes, including when the
+/// download task is killed, the received bytes are kept in the partial downloads so that the
+/// next download of the song can resume from them.
+struct SongDownload {
+    video_id: String,
+    partial: PartialDownload,
+    partial_downloads: Option<Arc<Mutex<HashMap<String, PartialDownload>>>>,
+}
+
+impl Drop for SongDownload {
+    fn drop(&mut self) {
+        let partial = std::mem::take(&mut self.partial);
+        let Some(partial_downloads) = &self.partial_downloads else {
+            return;
+        };
+        if partial.song.is_empty() {
+            return;
+        }
+        lock(partial_downloads).insert(std::mem::take(&mut self.video_id), partial);
+    }
+}
+
+/// Paces requests so that downloads don't exceed a rate.
+#[derive(Debug)]
+struct RateLimiter {
+    bytes_per_sec: u64,
+    /// When the next request may start.
+    next: Instant,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec,
+            next: Instant::now(),
+        }
+    }
+    /// Reserve the bytes of a request, returning when it may start. Unused time isn't saved up,
+    /// so a download that has been slow can't then burst above the rate.
+    fn reserve(&mut self, bytes: u64, now: Instant) -> Instant {
+        let start = self.next.max(now);
+        self.next = start + Duration::from_secs_f64(bytes as f64 / self.bytes_per_sec as f64);
+        start
+    }
+}
+
+/// The rate limits applied to song downloads.
+#[derive(Clone)]
+struct Throttle {
+    /// Shared between all downloads.
+    total: Option<Arc<Mutex<RateLimiter>>>,
+    per_song: Option<u64>,
 }
+
+impl Throttle {
+    fn new(limit: DownloadRateLimit) -> Self {
+        Self {
+            total: limit
+                .total
+                .map(|rate| Arc::new(Mutex::new(RateLimiter::new(rate)))),
+            per_song: limit.per_song,
+        }
+    }
+    /// Wait until the bytes can be requested without exceeding either limit.
+    async fn wait(&self, song_limiter: &mut Option<RateLimiter>, bytes: u64) {
+        let now = Instant::now();
+        let total_start = self.total.as_ref().map(|l| lock(l).reserve(bytes, now));
+        let song_start = song_limiter.as_mut().map(|l| l.reserve(bytes, now));
+        if let Some(start) = total_start.max(song_start) {
+            tokio::time::sleep_until(start).await;
+        }
+    }
+}
+
 pub struct Downloader {
-    options: VideoOptions,
+    audio_format: AudioFormatPreference,
+    export: ExportSettings,
+    cache: Arc<MusicCache>,
+    /// Limits the number of songs downloading at once. Songs waiting for a slot are queued in
+    /// the order they were requested.
+    download_slots: Arc<Semaphore>,
+    throttle: Throttle,
+    /// Songs that stopped downloading partway, by video id.
+    partial_downloads: Arc<Mutex<HashMap<String, PartialDownload>>>,
+    in_flight: InFlightDownloads,
+    client: reqwest::Client,
     response_tx: mpsc::Sender<super::Response>,
+    task_timeout: TaskTimeout,
 }
 impl Downloader {
-    pub fn new(response_tx: mpsc::Sender<super::Response>) -> Self {
+    pub fn new(
+        response_tx: mpsc::Sender<super::Response>,
+        cache: MusicCache,
+        settings: DownloadSettings,
+        task_timeout: TaskTimeout,
+    ) -> Self {
         Self {
-            options: VideoOptions {
-                quality: rusty_ytdl::VideoQuality::LowestAudio,
-                filter: rusty_ytdl::VideoSearchOptions::Audio,
-                download_options: DownloadOptions {
-                    dl_chunk_size: Some(DL_CALLBACK_CHUNK_SIZE),
-                },
-                ..Default::default()
-            },
+            audio_format: settings.audio_format,
+            export: settings.export,
+            cache: Arc::new(cache),
+            download_slots: Arc::new(Semaphore::new(settings.max_concurrent_downloads)),
+            throttle: Throttle::new(settings.rate_limit),
+            partial_downloads: Default::default(),
+            in_flight: Default::default(),
+            client: reqwest::Client::new(),
             response_tx,
+            task_timeout,
         }
     }
     pub async fn handle_request(&self, request: Request) {
@@ -49,6 +217,12 @@ impl Downloader {
             Request::DownloadSong(s_id, p_id, task) => {
                 self.handle_download_song(s_id, p_id, task).await
             }
+            Request::DownloadAlbumArt(url, p_id, task) => {
+                self.handle_download_album_art(url, p_id, task).await
+            }
+            Request::ExportSong(s_id, tags, task) => {
+                self.handle_export_song(s_id, tags, task).await
+            }
         }
     }
     async fn handle_download_song(
@@ -59,100 +233,608 @@ impl Downloader {
     ) {
         let KillableTask { id, kill_rx } = task;
         let tx = self.response_tx.clone();
-        // TODO: Find way to avoid clone of options here.
-        let options = self.options.clone();
+        let mut updates = self.subscribe_to_download(song_video_id);
         let _ = spawn_run_or_kill(
             async move {
-                tracing::info!("Running download");
-                send_or_error(
-                    &tx,
-                    super::Response::Downloader(Response::DownloadProgressUpdate(
-                        DownloadProgressUpdateType::Started,
-                        playlist_id,
-                        id,
-                    )),
+                send_progress(&tx, DownloadProgressUpdateType::Started, playlist_id, id).await;
+                loop {
+                    let update = match updates.recv().await {
+                        Ok(update) => update,
+                        // Only progress can be missed, as the final update is sent last.
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    };
+                    let finished = matches!(
+                        update,
+                        DownloadProgressUpdateType::Completed(..)
+                            | DownloadProgressUpdateType::Error(_)
+                    );
+                    send_progress(&tx, update, playlist_id, id).await;
+                    if finished {
+                        return;
+                    }
+                }
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+    }
+    /// Subscribe to the progress of a song's download, starting the download unless the song is
+    /// already downloading.
+    fn subscribe_to_download(
+        &self,
+        song_video_id: VideoID<'static>,
+    ) -> broadcast::Receiver<DownloadProgressUpdateType> {
+        let mut in_flight = lock(&self.in_flight);
+        if let Some(updates_tx) = in_flight.get(song_video_id.get_raw()) {
+            info!("Song already downloading, sharing its download");
+            return updates_tx.subscribe();
+        }
+        let (updates_tx, updates) = broadcast::channel(PROGRESS_CHANNEL_SIZE);
+        in_flight.insert(song_video_id.get_raw().to_string(), updates_tx.clone());
+        let mut progress = SharedDownload {
+            video_id: song_video_id.get_raw().to_string(),
+            in_flight: self.in_flight.clone(),
+            tx: updates_tx,
+            finished: false,
+        };
+        let audio_format = self.audio_format.clone();
+        let cache = self.cache.clone();
+        let download_slots = self.download_slots.clone();
+        let throttle = self.throttle.clone();
+        let client = self.client.clone();
+        let partial_downloads = self.partial_downloads.clone();
+        tokio::spawn(async move {
+            tracing::info!("Running download");
+            if let Some((song, bitrate)) = read_cached_song(&cache, song_video_id.get_raw()).await {
+                info!("Song found in music cache");
+                progress.finish(DownloadProgressUpdateType::Completed(song, bitrate));
+                return;
+            }
+            let mut attempt = 1;
+            let (songbuffer, bitrate) = loop {
+                // Held until the attempt finishes, so that other songs can download while
+                // waiting to retry.
+                let Ok(download_slot) = download_slots.acquire().await else {
+                    error!("Download queue closed");
+                    return;
+                };
+                // Every request for the song may have been killed while it was queued.
+                if progress.abandon_if_unused() {
+                    info!("Song no longer requested, stopping download");
+                    return;
+                }
+                let song = download_song(
+                    &client,
+                    &throttle,
+                    song_video_id.get_raw(),
+                    &audio_format,
+                    Some(partial_downloads.clone()),
+                    Some(&progress),
                 )
                 .await;
-                let Ok(video) = Video::new_with_options(song_video_id.get_raw(), options) else {
-                    error!("Error received finding song");
-                    send_or_error(
-                        &tx,
-                        super::Response::Downloader(Response::DownloadProgressUpdate(
-                            DownloadProgressUpdateType::Error,
-                            playlist_id,
-                            id,
-                        )),
-                    )
-                    .await;
-                    return;
+                drop(download_slot);
+                let e = match song {
+                    Ok(song) => break song,
+                    Err(e) => e,
                 };
-                let stream = match video.stream().await {
-                    Ok(s) => s,
+                if progress.abandon_if_unused() {
+                    info!("Song no longer requested, stopping download");
+                    return;
+                }
+                if attempt >= MAX_DOWNLOAD_ATTEMPTS {
+                    error!("Error <{e}> received downloading song");
+                    progress.finish(DownloadProgressUpdateType::Error(e.to_string()));
+                    return;
+                }
+                let delay = RETRY_BACKOFF * 2u32.pow(attempt - 1);
+                warn!("Error <{e}> downloading song, retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            };
+            info!("Song downloaded");
+            let songbuffer =
+                store_cached_song(&cache, song_video_id.get_raw(), songbuffer, bitrate).await;
+            progress.finish(DownloadProgressUpdateType::Completed(songbuffer, bitrate));
+        });
+        updates
+    }
+    async fn handle_download_album_art(
+        &self,
+        url: String,
+        playlist_id: ListSongID,
+        task: KillableTask,
+    ) {
+        let KillableTask { id, kill_rx } = task;
+        let tx = self.response_tx.clone();
+        let client = self.client.clone();
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Downloading album art");
+                let image = match download_image(&client, &url).await {
+                    Ok(image) => image,
                     Err(e) => {
-                        error!("Error <{e}> received converting song to stream");
+                        error!("Error <{e}> downloading album art");
                         send_or_error(
                             &tx,
-                            super::Response::Downloader(Response::DownloadProgressUpdate(
-                                DownloadProgressUpdateType::Error,
-                                playlist_id,
-                                id,
-                            )),
+                            super::Response::Downloader(Response::AlbumArtError(playlist_id, id)),
                         )
                         .await;
                         return;
                     }
                 };
-                let mut i = 0;
-                let mut songbuffer = Vec::new();
-                loop {
-                    match stream.chunk().await {
-                        Ok(Some(mut chunk)) => {
-                            i += 1;
-                            songbuffer.append(&mut chunk);
-                            let progress =
-                                (i * DL_CALLBACK_CHUNK_SIZE) * 100 / stream.content_length() as u64;
-                            info!("Sending song progress update");
-                            send_or_error(
-                                &tx,
-                                super::Response::Downloader(Response::DownloadProgressUpdate(
-                                    DownloadProgressUpdateType::Downloading(Percentage(
-                                        progress as u8,
-                                    )),
-                                    playlist_id,
-                                    id,
-                                )),
-                            )
-                            .await;
-                        }
-                        Err(e) => {
-                            error!("Error <{e}> received downloading song");
-                            send_or_error(
-                                &tx,
-                                super::Response::Downloader(Response::DownloadProgressUpdate(
-                                    DownloadProgressUpdateType::Error,
-                                    playlist_id,
-                                    id,
-                                )),
-                            )
-                            .await;
-                            return;
-                        }
-                        Ok(None) => break,
-                    }
-                }
-                info!("Song downloaded");
                 send_or_error(
                     &tx,
-                    super::Response::Downloader(Response::DownloadProgressUpdate(
-                        DownloadProgressUpdateType::Completed(songbuffer),
-                        playlist_id,
-                        id,
-                    )),
+                    super::Response::Downloader(Response::AlbumArt(image, playlist_id, id)),
                 )
                 .await;
             },
             kill_rx,
+            id,
+            &self.task_timeout,
         )
         .await;
     }
+    async fn handle_export_song(
+        &self,
+        song_video_id: VideoID<'static>,
+        tags: SongTags,
+        task: KillableTask,
+    ) {
+        let KillableTask { id, kill_rx } = task;
+        let tx = self.response_tx.clone();
+        let cache = self.cache.clone();
+        let download_slots = self.download_slots.clone();
+        let throttle = self.throttle.clone();
+        let client = self.client.clone();
+        let settings = self.export.clone();
+        // Formats that can be tagged are preferred, falling back to the configured quality.
+        let audio_format = AudioFormatPreference {
+            quality: self.audio_format.quality,
+            itags: EXPORT_AUDIO_ITAGS.to_vec(),
+        };
+        let _ = spawn_run_or_kill(
+            async move {
+                tracing::info!("Exporting song");
+                let song = ExportedSong {
+                    video_id: song_video_id.get_raw(),
+                    tags: &tags,
+                    audio_format: &audio_format,
+                    settings: &settings,
+                };
+                let response =
+                    match export_song(&client, &throttle, &cache, &download_slots, song).await {
+                        Ok(path) => Response::SongExported(path, id),
+                        Err(e) => {
+                            error!("Error <{e}> exporting song");
+                            Response::ExportError(tags.title.clone(), e.to_string(), id)
+                        }
+                    };
+                send_or_error(&tx, super::Response::Downloader(response)).await;
+            },
+            kill_rx,
+            id,
+            &self.task_timeout,
+        )
+        .await;
+    }
+}
+
+/// A song to export, and how to export it.
+struct ExportedSong<'a> {
+    video_id: &'a str,
+    tags: &'a SongTags,
+    audio_format: &'a AudioFormatPreference,
+    settings: &'a ExportSettings,
+}
+
+/// Save a song to the export directory with its tags and cover art, returning the path it was
+/// saved to. The cached song is used if it can be tagged, otherwise the song is downloaded.
+async fn export_song(
+    client: &reqwest::Client,
+    throttle: &Throttle,
+    cache: &Arc<MusicCache>,
+    download_slots: &Semaphore,
+    song: ExportedSong<'_>,
+) -> Result<PathBuf> {
+    let cached = read_cached_song(cache, song.video_id)
+        .await
+        .map(|(buffer, _)| buffer)
+        .filter(|buffer| song_extension(buffer) == Some("m4a"));
+    let buffer = match cached {
+        Some(buffer) => buffer,
+        None => {
+            let _download_slot = download_slots
+                .acquire()
+                .await
+                .map_err(|_| Error::Other("Download queue closed".into()))?;
+            download_song(
+                client,
+                throttle,
+                song.video_id,
+                song.audio_format,
+                None,
+                None,
+            )
+            .await?
+            .0
+        }
+    };
+    let extension =
+        song_extension(&buffer).ok_or_else(|| Error::Other("Unknown song format".into()))?;
+    let cover = match &song.tags.thumbnail_url {
+        Some(url) => download_cover(client, url)
+            .await
+            .map_err(|e| warn!("Error <{e}> downloading cover art"))
+            .ok(),
+        None => None,
+    };
+    let path = export_path(song.settings, song.tags, extension);
+    write_song(&path, &buffer, song.tags, cover)?;
+    Ok(path)
+}
+
+/// Read a song from the music cache without blocking the runtime.
+async fn read_cached_song(
+    cache: &Arc<MusicCache>,
+    video_id: &str,
+) -> Option<(Vec<u8>, Option<u64>)> {
+    let cache = cache.clone();
+    let video_id = video_id.to_string();
+    tokio::task::spawn_blocking(move || cache.read(&video_id))
+        .await
+        .unwrap_or_else(|e| {
+            error!("Error <{e}> reading song from music cache");
+            None
+        })
+}
+
+/// Save a song to the music cache without blocking the runtime, handing the song back once
+/// it has been saved.
+async fn store_cached_song(
+    cache: &Arc<MusicCache>,
+    video_id: &str,
+    song: Vec<u8>,
+    bitrate: Option<u64>,
+) -> Vec<u8> {
+    let cache = cache.clone();
+    let video_id = video_id.to_string();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = cache.store(&video_id, &song, bitrate) {
+            warn!("Error <{e}> saving song to music cache");
+        }
+        song
+    })
+    .await
+    .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+}
+
+async fn download_cover(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    cover_to_jpeg(&bytes)
+}
+
+async fn send_progress(
+    tx: &mpsc::Sender<super::Response>,
+    update: DownloadProgressUpdateType,
+    playlist_id: ListSongID,
+    id: TaskID,
+) {
+    send_or_error(
+        tx,
+        super::Response::Downloader(Response::DownloadProgressUpdate(update, playlist_id, id)),
+    )
+    .await;
+}
+
+/// A song download shared by every request for the song, which are sent its progress. Until it
+/// finishes, further requests for the song subscribe to it rather than downloading it again.
+struct SharedDownload {
+    video_id: String,
+    in_flight: InFlightDownloads,
+    tx: broadcast::Sender<DownloadProgressUpdateType>,
+    finished: bool,
+}
+
+impl SharedDownload {
+    fn send(&self, update: DownloadProgressUpdateType) {
+        // Only fails if every request for the song has been killed.
+        let _ = self.tx.send(update);
+    }
+    fn is_unused(&self) -> bool {
+        self.tx.receiver_count() == 0
+    }
+    /// Stop the download if every request for the song has been killed. This is checked with
+    /// the downloads in flight locked, so that a new request can't subscribe to it as it stops.
+    fn abandon_if_unused(&mut self) -> bool {
+        let mut in_flight = lock(&self.in_flight);
+        if !self.is_unused() {
+            return false;
+        }
+        in_flight.remove(&self.video_id);
+        self.finished = true;
+        true
+    }
+    /// Send the final update. Later requests for the song start a new download, which will
+    /// usually find the song in the cache.
+    fn finish(mut self, update: DownloadProgressUpdateType) {
+        lock(&self.in_flight).remove(&self.video_id);
+        self.finished = true;
+        self.send(update);
+    }
+}
+
+impl Drop for SharedDownload {
+    fn drop(&mut self) {
+        // E.g if the download task panicked.
+        if !self.finished {
+            lock(&self.in_flight).remove(&self.video_id);
+        }
+    }
+}
+
+/// Download a song in chunks using HTTP range requests. If partial downloads are given, a
+/// previous partial download of the song is resumed if there is one, and the download is kept
+/// there if it doesn't complete. If a request fails, the download is retried from the last
+/// received byte. Requests are paced to keep within the throttle's rate limits. Returns the
+/// song and its bitrate.
+async fn download_song(
+    client: &reqwest::Client,
+    throttle: &Throttle,
+    video_id: &str,
+    audio_format: &AudioFormatPreference,
+    partial_downloads: Option<Arc<Mutex<HashMap<String, PartialDownload>>>>,
+    progress: Option<&SharedDownload>,
+) -> Result<(Vec<u8>, Option<u64>)> {
+    let resumed = partial_downloads
+        .as_ref()
+        .and_then(|partials| lock(partials).remove(video_id));
+    let partial = match resumed {
+        Some(partial) => {
+            info!("Resuming download from byte {}", partial.song.len());
+            if let Some(progress) = progress {
+                progress.send(DownloadProgressUpdateType::Resuming(partial.progress()));
+            }
+            partial
+        }
+        None => get_song_stream(video_id, audio_format).await?,
+    };
+    let resumed_from = partial.song.len() as u64;
+    let mut download = SongDownload {
+        video_id: video_id.to_string(),
+        partial,
+        partial_downloads,
+    };
+    let mut song_limiter = throttle.per_song.map(RateLimiter::new);
+    let mut failed_attempts = 0;
+    loop {
+        let start = download.partial.song.len() as u64;
+        let content_length = download.partial.content_length;
+        if start >= content_length {
+            break;
+        }
+        let end = (start + DL_CALLBACK_CHUNK_SIZE).min(content_length) - 1;
+        throttle.wait(&mut song_limiter, end + 1 - start).await;
+        match get_range(client, &download.partial.url, start, end).await {
+            Ok(chunk) => {
+                failed_attempts = 0;
+                download.partial.song.extend_from_slice(&chunk);
+                if let Some(progress) = progress {
+                    // The partial download is kept, in case the song is requested again.
+                    if progress.is_unused() {
+                        return Err(Error::Other("Song no longer requested".into()));
+                    }
+                    info!("Sending song progress update");
+                    let update =
+                        DownloadProgressUpdateType::Downloading(download.partial.progress());
+                    progress.send(update);
+                }
+            }
+            Err(e) if failed_attempts < MAX_RESUME_ATTEMPTS => {
+                failed_attempts += 1;
+                warn!("Error <{e}> downloading song, resuming from byte {start}");
+                tokio::time::sleep(RESUME_DELAY * failed_attempts).await;
+                if let Some(progress) = progress {
+                    let update = DownloadProgressUpdateType::Resuming(download.partial.progress());
+                    progress.send(update);
+                }
+            }
+            Err(e) => {
+                // If a resumed download made no progress, the stream's url may have expired, so
+                // the next download of the song should start again.
+                if start == resumed_from {
+                    download.partial.song.clear();
+                }
+                return Err(e);
+            }
+        }
+    }
+    let song = std::mem::take(&mut download.partial.song);
+    Ok((song, download.partial.bitrate))
+}
+
+/// Find the url and size of the song's audio stream, in the preferred format.
+async fn get_song_stream(
+    video_id: &str,
+    audio_format: &AudioFormatPreference,
+) -> Result<PartialDownload> {
+    let info = Video::new(video_id)?.get_info().await?;
+    let streams = info
+        .formats
+        .into_iter()
+        .filter(|f| f.has_audio && !f.has_video)
+        .map(|f| AudioStream {
+            itag: f.itag,
+            bitrate: f.bitrate,
+            url: f.url,
+            content_length: f.content_length.and_then(|l| l.parse().ok()),
+        })
+        .collect();
+    let stream = choose_audio_stream(streams, audio_format)
+        .ok_or_else(|| Error::Other("Song has no audio streams".into()))?;
+    info!("Downloading itag {} at {} bps", stream.itag, stream.bitrate);
+    let content_length = stream
+        .content_length
+        .ok_or_else(|| Error::Other("Song stream has unknown length".into()))?;
+    Ok(PartialDownload {
+        url: stream.url,
+        content_length,
+        bitrate: Some(stream.bitrate),
+        song: Vec::new(),
+    })
+}
+
+/// Choose the first available stream in the preferred itags, otherwise the stream with the
+/// bitrate matching the preferred quality. Streams with an unknown length can't be downloaded.
+fn choose_audio_stream(
+    mut streams: Vec<AudioStream>,
+    audio_format: &AudioFormatPreference,
+) -> Option<AudioStream> {
+    streams.retain(|s| s.content_length.is_some());
+    if let Some(idx) = audio_format
+        .itags
+        .iter()
+        .find_map(|itag| streams.iter().position(|s| s.itag == *itag))
+    {
+        return Some(streams.swap_remove(idx));
+    }
+    streams.sort_by_key(|s| s.bitrate);
+    let idx = match audio_format.quality {
+        AudioQuality::Low => 0,
+        AudioQuality::Medium => streams.len() / 2,
+        AudioQuality::High => streams.len().checked_sub(1)?,
+    };
+    (idx < streams.len()).then(|| streams.swap_remove(idx))
+}
+
+/// Download the bytes from start to end inclusive.
+async fn get_range(client: &reqwest::Client, url: &str, start: u64, end: u64) -> Result<Vec<u8>> {
+    let response = client
+        .get(url)
+        .header(reqwest::header::RANGE, format!("bytes={start}-{end}"))
+        .send()
+        .await?
+        .error_for_status()?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return Err(Error::Other("Song stream doesn't support ranges".into()));
+    }
+    let chunk = response.bytes().await?;
+    if chunk.is_empty() {
+        return Err(Error::Other("Song stream returned no data".into()));
+    }
+    Ok(chunk.to_vec())
+}
+
+async fn download_image(client: &reqwest::Client, url: &str) -> Result<DynamicImage> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    Ok(image::load_from_memory(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        choose_audio_stream, AudioStream, DownloadProgressUpdateType, InFlightDownloads,
+        RateLimiter, SharedDownload,
+    };
+    use crate::config::{AudioFormatPreference, AudioQuality};
+    use std::time::Duration;
+    use tokio::sync::broadcast;
+    use tokio::time::Instant;
+
+    fn stream(itag: u64, bitrate: u64) -> AudioStream {
+        AudioStream {
+            itag,
+            bitrate,
+            url: String::new(),
+            content_length: Some(1000),
+        }
+    }
+    fn chosen_itag(quality: AudioQuality, itags: Vec<u64>) -> Option<u64> {
+        let streams = vec![stream(251, 160000), stream(139, 48000), stream(140, 128000)];
+        choose_audio_stream(streams, &AudioFormatPreference { quality, itags }).map(|s| s.itag)
+    }
+    #[test]
+    fn test_choose_audio_stream_by_quality() {
+        assert_eq!(chosen_itag(AudioQuality::Low, vec![]), Some(139));
+        assert_eq!(chosen_itag(AudioQuality::Medium, vec![]), Some(140));
+        assert_eq!(chosen_itag(AudioQuality::High, vec![]), Some(251));
+        assert_eq!(
+            choose_audio_stream(vec![], &AudioFormatPreference::default()),
+            None
+        );
+    }
+    #[test]
+    fn test_choose_audio_stream_by_itag() {
+        // The first available itag is preferred, regardless of quality.
+        assert_eq!(
+            chosen_itag(AudioQuality::High, vec![1, 140, 251]),
+            Some(140)
+        );
+        assert_eq!(chosen_itag(AudioQuality::High, vec![1]), Some(251));
+    }
+    #[test]
+    fn test_rate_limiter_paces_requests() {
+        let mut limiter = RateLimiter::new(1000);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(500, now), now);
+        assert_eq!(limiter.reserve(500, now), now + Duration::from_millis(500));
+        // Time spent waiting on a slow download isn't saved up.
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(500, later), later);
+        assert_eq!(
+            limiter.reserve(500, later),
+            later + Duration::from_millis(500)
+        );
+    }
+    #[test]
+    fn test_shared_download_abandoned_when_unused() {
+        let in_flight = InFlightDownloads::default();
+        let (tx, first) = broadcast::channel(1);
+        in_flight.lock().unwrap().insert("a".into(), tx.clone());
+        let mut download = SharedDownload {
+            video_id: "a".into(),
+            in_flight: in_flight.clone(),
+            tx,
+            finished: false,
+        };
+        let second = in_flight.lock().unwrap()["a"].subscribe();
+        drop(first);
+        assert!(!download.abandon_if_unused());
+        drop(second);
+        assert!(download.abandon_if_unused());
+        assert!(in_flight.lock().unwrap().is_empty());
+    }
+    #[test]
+    fn test_shared_download_finish() {
+        let in_flight = InFlightDownloads::default();
+        let (tx, mut updates) = broadcast::channel(1);
+        in_flight.lock().unwrap().insert("a".into(), tx.clone());
+        let download = SharedDownload {
+            video_id: "a".into(),
+            in_flight: in_flight.clone(),
+            tx,
+            finished: false,
+        };
+        download.finish(DownloadProgressUpdateType::Error("error".into()));
+        // Later requests for the song start a new download.
+        assert!(in_flight.lock().unwrap().is_empty());
+        assert!(matches!(
+            updates.try_recv(),
+            Ok(DownloadProgressUpdateType::Error(_))
+        ));
+    }
 }
@@ -0,0 +1,207 @@
+use crate::app::platform::{is_reserved_file_name, relative_path};
+use crate::app::structures::ListSong;
+use crate::Result;
+use lofty::{Accessor, AudioFile, MimeType, Picture, PictureType, Tag, TaggedFileExt};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use ytmapi_rs::common::youtuberesult::YoutubeResult;
+
+/// Itags of the AAC audio formats, from highest to lowest bitrate. These are preferred when
+/// exporting, as m4a files can be tagged.
+pub const EXPORT_AUDIO_ITAGS: [u64; 2] = [140, 139];
+
+/// Where exported songs are saved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportSettings {
+    pub dir: PathBuf,
+    /// Path of each song relative to dir, without the extension, e.g
+    /// "{artist}/{album}/{track} - {title}".
+    pub template: String,
+}
+
+/// Metadata written to an exported song.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SongTags {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub year: String,
+    pub track_no: usize,
+    pub thumbnail_url: Option<String>,
+}
+
+impl SongTags {
+    pub fn from_song(song: &ListSong) -> Self {
+        Self {
+            title: song.get_title().clone(),
+            artist: song
+                .get_artists()
+                .iter()
+                .map(|a| a.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            album: song.get_album().clone(),
+            year: song.get_year().clone(),
+            track_no: song.get_track_no(),
+            // Largest thumbnail is used, as it is the best quality cover art.
+            thumbnail_url: song
+                .raw
+                .get_thumbnails()
+                .iter()
+                .max_by_key(|t| t.width)
+                .map(|t| t.url.clone()),
+        }
+    }
+}
+
+/// The file extension of the song, detected from the container format.
+pub fn song_extension(song: &[u8]) -> Option<&'static str> {
+    match song {
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("m4a"),
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => Some("webm"),
+        _ => None,
+    }
+}
+
+/// Fill the template with the song's tags, giving the path to export the song to.
+/// Each field is sanitised so that it can't add directories to the path, and the template's
+/// '/' separators are converted to the platform's own.
+pub fn export_path(settings: &ExportSettings, tags: &SongTags, extension: &str) -> PathBuf {
+    let or_unknown = |s: &str| {
+        let s = sanitise(s);
+        if s.is_empty() {
+            "Unknown".to_string()
+        } else {
+            s
+        }
+    };
+    let relative = settings
+        .template
+        .replace("{artist}", &or_unknown(&tags.artist))
+        .replace("{album}", &or_unknown(&tags.album))
+        .replace("{title}", &or_unknown(&tags.title))
+        .replace("{year}", &or_unknown(&tags.year))
+        .replace("{track}", &format!("{:02}", tags.track_no));
+    settings
+        .dir
+        .join(relative_path(&format!("{relative}.{extension}")))
+}
+
+fn sanitise(field: &str) -> String {
+    let sanitised: String = field
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Leading dots would create hidden files, or refer to parent directories. Windows drops
+    // trailing dots and spaces, so names ending in them can't be found again.
+    let sanitised = sanitised
+        .trim_start_matches('.')
+        .trim()
+        .trim_end_matches(['.', ' ']);
+    // Exported songs may be copied to Windows, so its reserved names are avoided everywhere.
+    if is_reserved_file_name(sanitised) {
+        format!("_{sanitised}")
+    } else {
+        sanitised.to_string()
+    }
+}
+
+/// Save the song to the path, creating any directories needed, and tag it if the format
+/// supports tags.
+pub fn write_song(path: &Path, song: &[u8], tags: &SongTags, cover: Option<Vec<u8>>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, song)?;
+    if path.extension().is_some_and(|e| e == "m4a") {
+        write_tags(path, tags, cover)?;
+    } else {
+        tracing::warn!(
+            "Unable to tag {}, as the format has no tags",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn write_tags(path: &Path, tags: &SongTags, cover: Option<Vec<u8>>) -> Result<()> {
+    let mut file = lofty::read_from_path(path)?;
+    let mut tag = Tag::new(file.primary_tag_type());
+    tag.set_title(tags.title.clone());
+    tag.set_artist(tags.artist.clone());
+    tag.set_album(tags.album.clone());
+    if let Ok(track_no) = u32::try_from(tags.track_no) {
+        tag.set_track(track_no);
+    }
+    if let Ok(year) = tags.year.parse() {
+        tag.set_year(year);
+    }
+    if let Some(cover) = cover {
+        tag.push_picture(Picture::new_unchecked(
+            PictureType::CoverFront,
+            Some(MimeType::Jpeg),
+            None,
+            cover,
+        ));
+    }
+    file.insert_tag(tag);
+    file.save_to_path(path)?;
+    Ok(())
+}
+
+/// Convert downloaded cover art to a jpeg, which can be embedded in any tag format.
+pub fn cover_to_jpeg(image: &[u8]) -> Result<Vec<u8>> {
+    let mut jpeg = Vec::new();
+    image::load_from_memory(image)?.to_rgb8().write_to(
+        &mut Cursor::new(&mut jpeg),
+        image::ImageOutputFormat::Jpeg(90),
+    )?;
+    Ok(jpeg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_path, sanitise, song_extension, ExportSettings, SongTags};
+    use std::path::PathBuf;
+
+    fn tags() -> SongTags {
+        SongTags {
+            title: "Song: Part 1/2".into(),
+            artist: "..Artist".into(),
+            album: String::new(),
+            year: "2020".into(),
+            track_no: 3,
+            thumbnail_url: None,
+        }
+    }
+    #[test]
+    fn test_export_path() {
+        let settings = ExportSettings {
+            dir: PathBuf::from("music"),
+            template: "{artist}/{album}/{track} - {title}".into(),
+        };
+        assert_eq!(
+            export_path(&settings, &tags(), "m4a"),
+            ["music", "Artist", "Unknown", "03 - Song_ Part 1_2.m4a"]
+                .iter()
+                .collect::<PathBuf>()
+        );
+    }
+    #[test]
+    fn test_sanitise() {
+        assert_eq!(sanitise("..\\Artist..."), "_Artist");
+        assert_eq!(sanitise("Con"), "_Con");
+        assert_eq!(sanitise("nul.txt"), "_nul.txt");
+        assert_eq!(sanitise("Contact. "), "Contact");
+    }
+    #[test]
+    fn test_song_extension() {
+        assert_eq!(song_extension(b"\0\0\0\x18ftypdash"), Some("m4a"));
+        assert_eq!(song_extension(&[0x1A, 0x45, 0xDF, 0xA3, 0]), Some("webm"));
+        assert_eq!(song_extension(b"ftyp"), None);
+    }
+}
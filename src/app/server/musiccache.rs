@@ -0,0 +1,318 @@
+use super::lock;
+use crate::app::platform::id_file_name;
+use crate::Result;
+use crate::{get_cache_dir, get_data_dir};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+const MUSIC_CACHE_DIR_NAME: &str = "music_cache";
+const MUSIC_CACHE_INDEX_FILE_NAME: &str = "index.json";
+/// Files are written with this extension then renamed, so a file is only ever complete.
+const PARTIAL_FILE_EXTENSION: &str = "part";
+
+/// A song saved in the cache, stored in a file named after its video id.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CacheEntry {
+    video_id: String,
+    size: u64,
+    /// Bits per second, if known.
+    #[serde(default)]
+    bitrate: Option<u64>,
+    /// Checksum of the file, to detect corruption. Songs cached before this was recorded
+    /// can't be verified, so are dropped.
+    #[serde(default)]
+    checksum: Option<u64>,
+}
+
+/// Downloaded songs saved to the data directory, so they can be played again without
+/// downloading. Once the cache is full, the least recently played songs are removed.
+/// Songs are read and written without holding the lock on the index, as they are large.
+#[derive(Debug)]
+pub struct MusicCache {
+    dir: PathBuf,
+    index: Mutex<CacheIndex>,
+}
+
+#[derive(Debug)]
+struct CacheIndex {
+    max_size: u64,
+    /// Least recently used first.
+    entries: Vec<CacheEntry>,
+}
+
+impl MusicCache {
+    pub fn new(dir: PathBuf, max_size: u64) -> Self {
+        Self {
+            dir,
+            index: Mutex::new(CacheIndex {
+                max_size,
+                entries: Vec::new(),
+            }),
+        }
+    }
+    /// Load the cache index from the cache directory, or an empty cache if it doesn't exist or
+    /// can't be read. The index is then reconciled with the files in the cache directory.
+    pub fn load(max_size: u64) -> Result<Self> {
+        let mut cache = Self::new(get_cache_dir()?.join(MUSIC_CACHE_DIR_NAME), max_size);
+        // The cache used to be kept in the data directory, so is moved rather than lost.
+        let old_dir = get_data_dir()?.join(MUSIC_CACHE_DIR_NAME);
+        if old_dir.exists() && !cache.dir.exists() {
+            if let Err(e) = std::fs::rename(&old_dir, &cache.dir) {
+                warn!("Error <{e}> moving music cache to {}", cache.dir.display());
+            }
+        }
+        if let Ok(contents) = std::fs::read_to_string(cache.dir.join(MUSIC_CACHE_INDEX_FILE_NAME)) {
+            cache.index_mut().entries = serde_json::from_str(&contents)
+                .map_err(|e| warn!("Error <{e}> reading music cache index"))
+                .unwrap_or_default();
+        }
+        cache.reconcile();
+        // The max size may have been reduced since the cache was last used.
+        for video_id in cache.index_mut().evict(0) {
+            remove_cached_file(cache.song_path(&video_id));
+        }
+        if cache.dir.exists() {
+            cache.save(&lock(&cache.index))?;
+        }
+        Ok(cache)
+    }
+    fn save(&self, index: &CacheIndex) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(MUSIC_CACHE_INDEX_FILE_NAME);
+        write_complete(&path, serde_json::to_string(&index.entries)?.as_bytes())
+    }
+    /// Make the index match the cache directory, so that a crash while writing can't leave a
+    /// corrupt song to be played later. Songs whose files are missing or the wrong size are
+    /// dropped, and files not in the index, such as partially written ones, are deleted.
+    fn reconcile(&mut self) {
+        let dir = self.dir.clone();
+        let index = self.index_mut();
+        index.entries.retain(|entry| {
+            let valid = entry.checksum.is_some()
+                && std::fs::metadata(dir.join(&*id_file_name(&entry.video_id)))
+                    .is_ok_and(|m| m.len() == entry.size);
+            if !valid {
+                warn!("Dropping invalid song {} from music cache", entry.video_id);
+            }
+            valid
+        });
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            return;
+        };
+        for file in files.flatten() {
+            let name = file.file_name();
+            let indexed = name == MUSIC_CACHE_INDEX_FILE_NAME
+                || index
+                    .entries
+                    .iter()
+                    .any(|e| name == *id_file_name(&e.video_id));
+            if !indexed {
+                remove_cached_file(file.path());
+            }
+        }
+    }
+    /// Read a cached song and its bitrate, marking it as the most recently used. If the file
+    /// doesn't match what was cached, it is removed from the cache. This blocks, so should be
+    /// run with spawn_blocking.
+    pub fn read(&self, video_id: &str) -> Option<(Vec<u8>, Option<u64>)> {
+        let entry = lock(&self.index).get(video_id)?.clone();
+        let path = self.song_path(video_id);
+        let error = match std::fs::read(&path) {
+            Ok(song)
+                if song.len() as u64 == entry.size && entry.checksum == Some(checksum(&song)) =>
+            {
+                return Some((song, entry.bitrate));
+            }
+            Ok(_) => "checksum mismatch".to_string(),
+            Err(e) => e.to_string(),
+        };
+        warn!(
+            "Error <{error}> reading {} from music cache",
+            path.display()
+        );
+        let mut index = lock(&self.index);
+        // The song may have been cached again while it was being read.
+        if !index.entries.contains(&entry) {
+            return None;
+        }
+        index.remove(video_id);
+        if let Err(e) = self.save(&index) {
+            warn!("Error <{e}> saving music cache index");
+        }
+        drop(index);
+        remove_cached_file(path);
+        None
+    }
+    /// Save a downloaded song as the most recently used, evicting the least recently used
+    /// songs if the cache is full. This blocks, so should be run with spawn_blocking.
+    pub fn store(&self, video_id: &str, song: &[u8], bitrate: Option<u64>) -> Result<()> {
+        let size = song.len() as u64;
+        if size > lock(&self.index).max_size {
+            return Ok(());
+        }
+        // Written before it is indexed, so that a song in the index is always complete.
+        std::fs::create_dir_all(&self.dir)?;
+        write_complete(&self.song_path(video_id), song)?;
+        let evicted = {
+            let mut index = lock(&self.index);
+            let evicted = index
+                .insert(video_id, size, bitrate, checksum(song))
+                .unwrap_or_default();
+            self.save(&index)?;
+            evicted
+        };
+        for video_id in evicted {
+            remove_cached_file(self.song_path(&video_id));
+        }
+        Ok(())
+    }
+    fn index_mut(&mut self) -> &mut CacheIndex {
+        self.index.get_mut().unwrap_or_else(|e| e.into_inner())
+    }
+    /// Where files are case insensitive the file name differs from the video id, as ids that
+    /// differ only by case are different songs.
+    fn song_path(&self, video_id: &str) -> PathBuf {
+        self.dir.join(&*id_file_name(video_id))
+    }
+}
+
+impl CacheIndex {
+    /// Get a cached song, marking it as the most recently used.
+    fn get(&mut self, video_id: &str) -> Option<&CacheEntry> {
+        let idx = self.entries.iter().position(|e| e.video_id == video_id)?;
+        let entry = self.entries.remove(idx);
+        self.entries.push(entry);
+        self.entries.last()
+    }
+    /// Drop a song from the index, e.g if its file couldn't be read.
+    fn remove(&mut self, video_id: &str) {
+        self.entries.retain(|e| e.video_id != video_id);
+    }
+    /// Add a song to the index as the most recently used. Returns the video ids of the songs
+    /// evicted to make room, whose files should be deleted, or None if the song is too large to
+    /// cache.
+    fn insert(
+        &mut self,
+        video_id: &str,
+        size: u64,
+        bitrate: Option<u64>,
+        checksum: u64,
+    ) -> Option<Vec<String>> {
+        if size > self.max_size {
+            return None;
+        }
+        self.remove(video_id);
+        let evicted = self.evict(size);
+        self.entries.push(CacheEntry {
+            video_id: video_id.to_string(),
+            size,
+            bitrate,
+            checksum: Some(checksum),
+        });
+        Some(evicted)
+    }
+    /// Remove the least recently used songs until there is room for a song of the given size.
+    fn evict(&mut self, size: u64) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.total_size() + size > self.max_size && !self.entries.is_empty() {
+            evicted.push(self.entries.remove(0).video_id);
+        }
+        evicted
+    }
+    fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+}
+
+/// FNV-1a hash of a song. This is stable between versions, unlike the std hasher.
+fn checksum(song: &[u8]) -> u64 {
+    song.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Write to a partial file first then rename it, so that the file at path is never incomplete.
+fn write_complete(path: &Path, contents: &[u8]) -> Result<()> {
+    let partial = path.with_extension(PARTIAL_FILE_EXTENSION);
+    std::fs::write(&partial, contents)?;
+    std::fs::rename(partial, path)?;
+    Ok(())
+}
+
+/// Delete a song evicted or dropped from the cache.
+fn remove_cached_file(path: PathBuf) {
+    if let Err(e) = std::fs::remove_file(&path) {
+        warn!("Error <{e}> removing {} from music cache", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, CacheIndex, MusicCache};
+    use crate::core::TestDir;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut index = CacheIndex {
+            max_size: 10,
+            entries: Vec::new(),
+        };
+        assert_eq!(index.insert("a", 4, Some(128000), 0), Some(vec![]));
+        assert_eq!(index.insert("b", 4, None, 0), Some(vec![]));
+        // Playing a song makes it the most recently used.
+        assert_eq!(index.get("a").and_then(|e| e.bitrate), Some(128000));
+        assert_eq!(index.insert("c", 4, None, 0), Some(vec!["b".to_string()]));
+        assert!(index.get("b").is_none());
+        assert!(index.get("a").is_some());
+        assert!(index.get("c").is_some());
+    }
+    #[test]
+    fn test_too_large_not_cached() {
+        let mut index = CacheIndex {
+            max_size: 10,
+            entries: Vec::new(),
+        };
+        index.insert("a", 4, None, 0);
+        assert_eq!(index.insert("b", 11, None, 0), None);
+        assert!(index.get("a").is_some());
+        // Replacing a song doesn't count its old size.
+        assert_eq!(index.insert("a", 10, None, 0), Some(vec![]));
+    }
+    #[test]
+    fn test_store_evicts_files() {
+        let dir = TestDir::new("music_cache_evict");
+        let cache = MusicCache::new(dir.to_path_buf(), 12);
+        cache.store("a", b"song a", None).unwrap();
+        cache.store("b", b"song b", None).unwrap();
+        cache.store("c", b"song c", None).unwrap();
+        assert!(!dir.join("a").exists());
+        assert_eq!(cache.read("a"), None);
+        assert_eq!(cache.read("c"), Some((b"song c".to_vec(), None)));
+    }
+    #[test]
+    fn test_reconcile_drops_corrupt_songs() {
+        let dir = TestDir::new("music_cache_reconcile");
+        let mut cache = MusicCache::new(dir.to_path_buf(), 100);
+        cache.store("a", b"song a", None).unwrap();
+        cache.store("b", b"song b", None).unwrap();
+        // Simulate a crash partway through writing, and a truncated song.
+        std::fs::write(dir.join("c.part"), b"so").unwrap();
+        std::fs::write(dir.join("b"), b"so").unwrap();
+        cache.reconcile();
+        assert!(cache.index_mut().get("b").is_none());
+        assert!(!dir.join("b").exists());
+        assert!(!dir.join("c.part").exists());
+        assert_eq!(cache.read("a"), Some((b"song a".to_vec(), None)));
+        // A corrupt song with the right size is caught when read.
+        std::fs::write(dir.join("a"), b"song x").unwrap();
+        assert_eq!(cache.read("a"), None);
+        assert!(!dir.join("a").exists());
+    }
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), 0xcbf29ce484222325);
+        assert_ne!(checksum(b"song a"), checksum(b"song b"));
+    }
+}
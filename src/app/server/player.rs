@@ -4,11 +4,13 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 
 use tracing::debug;
+use tracing::error;
 use tracing::info;
 use tracing::trace;
 use tracing::warn;
 
 use crate::app::structures::Percentage;
+use crate::config::{AudioOutput, OutputSettings};
 use crate::core::blocking_send_or_error;
 use crate::Result;
 
@@ -16,6 +18,9 @@ use crate::app::structures::ListSongID;
 use crate::app::taskmanager::TaskID;
 
 use super::KillableTask;
+use stream::AudioStream;
+
+mod stream;
 
 const EVENT_POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(10);
 const PROGRESS_UPDATE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(100);
@@ -29,6 +34,8 @@ pub enum Request {
     GetPlayProgress(ListSongID, TaskID), // Should give ID?
     Stop(ListSongID, TaskID),
     PausePlay(ListSongID, TaskID),
+    /// Change where songs are played, from the next song played.
+    SetOutput(OutputSettings),
 }
 
 #[derive(Debug)]
@@ -45,28 +52,105 @@ pub struct PlayerManager {
     _response_tx: mpsc::Sender<super::Response>,
     _rodio: JoinHandle<()>,
     msg_tx: mpsc::Sender<Request>,
+    stream: AudioStream,
+    output_settings: OutputSettings,
+    /// None unless the stream is being served.
+    stream_server: Option<tokio::task::JoinHandle<()>>,
 }
 
 // Consider if this can be managed by Server.
 impl PlayerManager {
-    pub fn new(response_tx: mpsc::Sender<super::Response>) -> Result<Self> {
+    pub fn new(
+        response_tx: mpsc::Sender<super::Response>,
+        output_settings: OutputSettings,
+    ) -> Result<Self> {
         let (msg_tx, msg_rx) = mpsc::channel(PLAYER_MSG_QUEUE_SIZE);
         let response_tx_clone = response_tx.clone();
-        let rodio = spawn_rodio_thread(msg_rx, response_tx_clone);
+        let stream = AudioStream::new();
+        let stream_server = start_stream_server(&stream, output_settings);
+        let output = effective_output(output_settings.output, &stream_server);
+        let rodio = spawn_rodio_thread(msg_rx, response_tx_clone, stream.clone(), output);
         Ok(Self {
             _response_tx: response_tx,
             msg_tx,
             _rodio: rodio,
+            stream,
+            output_settings,
+            stream_server,
         })
     }
-    pub async fn handle_request(&self, request: Request) -> Result<()> {
+    pub async fn handle_request(&mut self, request: Request) -> Result<()> {
+        let request = match request {
+            Request::SetOutput(settings) => Request::SetOutput(self.set_output(settings)),
+            request => request,
+        };
         Ok(self.msg_tx.send(request).await?)
     }
+    /// Serve the stream again if its address has changed or it's needed now, returning the
+    /// settings the player should use.
+    fn set_output(&mut self, settings: OutputSettings) -> OutputSettings {
+        let restart = settings.stream_address != self.output_settings.stream_address
+            || settings.stream_port != self.output_settings.stream_port
+            || settings.output == AudioOutput::Local
+            || self.stream_server.is_none();
+        if restart {
+            // The old server has to stop first, in case the new one uses the same port.
+            if let Some(server) = self.stream_server.take() {
+                server.abort();
+            }
+            self.stream_server = start_stream_server(&self.stream, settings);
+        }
+        self.output_settings = settings;
+        OutputSettings {
+            output: effective_output(settings.output, &self.stream_server),
+            ..settings
+        }
+    }
+}
+
+impl Drop for PlayerManager {
+    fn drop(&mut self) {
+        if let Some(server) = self.stream_server.take() {
+            server.abort();
+        }
+    }
 }
 
+/// Serve the stream if the output settings use it.
+fn start_stream_server(
+    stream: &AudioStream,
+    settings: OutputSettings,
+) -> Option<tokio::task::JoinHandle<()>> {
+    match settings.output {
+        AudioOutput::Local => None,
+        AudioOutput::Network | AudioOutput::Both => {
+            // Songs can still be played locally, so this isn't fatal.
+            stream
+                .serve(settings.stream_address, settings.stream_port)
+                .map_err(|e| error!("Error <{e}> starting audio stream, playing locally"))
+                .ok()
+        }
+    }
+}
+
+/// Songs are played locally if the stream couldn't be served.
+fn effective_output(
+    output: AudioOutput,
+    stream_server: &Option<tokio::task::JoinHandle<()>>,
+) -> AudioOutput {
+    match stream_server {
+        Some(_) => output,
+        None => AudioOutput::Local,
+    }
+}
+
+/// Unless output is Local, songs are also sent to the stream, and if it's Network they're only
+/// sent to the stream.
 pub fn spawn_rodio_thread(
     mut msg_rx: mpsc::Receiver<Request>,
     response_tx: mpsc::Sender<super::Response>,
+    stream: AudioStream,
+    mut output: AudioOutput,
 ) -> JoinHandle<()> {
     std::thread::spawn(move || {
         // Rodio can produce output to stderr when we don't want it to, so we use Gag to suppress stdout/stderr.
@@ -101,7 +185,11 @@ pub fn spawn_rodio_thread(
                         if !sink.empty() {
                             sink.stop()
                         }
-                        sink.append(source);
+                        match output {
+                            AudioOutput::Local => sink.append(source),
+                            AudioOutput::Network => sink.append(stream.tee(source, true)),
+                            AudioOutput::Both => sink.append(stream.tee(source, false)),
+                        }
                         // Handle case we're we've received a play message but queue was paused.
                         if sink.is_paused() {
                             sink.play();
@@ -193,6 +281,10 @@ pub fn spawn_rodio_thread(
                         );
                         info!("Sending volume update");
                     }
+                    Request::SetOutput(settings) => {
+                        info!("Received {:?}", msg);
+                        output = settings.output;
+                    }
                 }
             }
             // Avoid empty infinite loop, but still poll more frequently than when sending progress updates for responsiveness.
@@ -0,0 +1,246 @@
+//! Serves the audio that's playing over HTTP, so that it can be played on another machine, e.g
+//! by a multiroom audio server. Songs are converted to one sample format and sent as a single
+//! endless WAV stream, so listeners stay connected between songs.
+use crate::Result;
+use rodio::cpal::FromSample;
+use rodio::source::UniformSourceIterator;
+use rodio::{Sample, Source};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+const STREAM_SAMPLE_RATE: u32 = 48000;
+const STREAM_CHANNELS: u16 = 2;
+const BYTES_PER_SAMPLE: u16 = 2;
+/// Samples are sent to listeners in chunks of this many, 20ms of audio.
+const CHUNK_SAMPLES: usize = 1920;
+/// Chunks that a slow listener hasn't received are dropped after this many, 2s of audio.
+const STREAM_BUFFER_CHUNKS: usize = 100;
+const MAX_REQUEST_SIZE: usize = 4096;
+
+/// Sends chunks of audio to every connected listener.
+#[derive(Clone)]
+pub struct AudioStream {
+    tx: broadcast::Sender<Arc<[u8]>>,
+}
+
+impl AudioStream {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(STREAM_BUFFER_CHUNKS);
+        Self { tx }
+    }
+    /// Serve the stream on the address, which must allow other devices for them to play it.
+    /// Aborting the returned handle stops accepting listeners.
+    pub fn serve(&self, address: IpAddr, port: u16) -> Result<JoinHandle<()>> {
+        let listener = std::net::TcpListener::bind((address, port))?;
+        listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(listener)?;
+        info!("Serving audio stream on {address}:{port}");
+        let tx = self.tx.clone();
+        Ok(tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((connection, addr)) => {
+                        info!("Audio stream listener connected from {addr}");
+                        tokio::spawn(handle_connection(connection, tx.subscribe()));
+                    }
+                    Err(e) => warn!("Error <{e}> accepting audio stream connection"),
+                }
+            }
+        }))
+    }
+    /// Convert the source to the stream's format, sending its samples to listeners as they're
+    /// played. If mute_local is set, the samples are sent but silence is played locally.
+    pub fn tee<S>(&self, source: S, mute_local: bool) -> TeeSource<S>
+    where
+        S: Source,
+        S::Item: Sample,
+        i16: FromSample<S::Item>,
+    {
+        TeeSource {
+            input: UniformSourceIterator::new(source, STREAM_CHANNELS, STREAM_SAMPLE_RATE),
+            tx: self.tx.clone(),
+            buf: Vec::with_capacity(CHUNK_SAMPLES * BYTES_PER_SAMPLE as usize),
+            mute_local,
+        }
+    }
+}
+
+/// A source that passes its samples through to the local output, and sends a copy to the
+/// stream's listeners.
+pub struct TeeSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    input: UniformSourceIterator<S, i16>,
+    tx: broadcast::Sender<Arc<[u8]>>,
+    buf: Vec<u8>,
+    mute_local: bool,
+}
+
+impl<S> TeeSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn flush(&mut self) {
+        if self.buf.is_empty() {
+            return;
+        }
+        // An error only means that there are no listeners.
+        let _ = self.tx.send(Arc::from(self.buf.as_slice()));
+        self.buf.clear();
+    }
+}
+
+impl<S> Iterator for TeeSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+    i16: FromSample<S::Item>,
+{
+    type Item = i16;
+    fn next(&mut self) -> Option<i16> {
+        let Some(sample) = self.input.next() else {
+            self.flush();
+            return None;
+        };
+        self.buf.extend_from_slice(&sample.to_le_bytes());
+        if self.buf.len() >= CHUNK_SAMPLES * BYTES_PER_SAMPLE as usize {
+            self.flush();
+        }
+        if self.mute_local {
+            Some(0)
+        } else {
+            Some(sample)
+        }
+    }
+}
+
+impl<S> Source for TeeSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+    i16: FromSample<S::Item>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+    fn channels(&self) -> u16 {
+        STREAM_CHANNELS
+    }
+    fn sample_rate(&self) -> u32 {
+        STREAM_SAMPLE_RATE
+    }
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
+
+/// Header of a WAV file of unknown length, for 16 bit samples in the stream's format.
+fn wav_header() -> Vec<u8> {
+    let block_align = STREAM_CHANNELS * BYTES_PER_SAMPLE;
+    let byte_rate = STREAM_SAMPLE_RATE * block_align as u32;
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    // The length isn't known, so the largest length is given and players read until the end.
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    // PCM
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&STREAM_CHANNELS.to_le_bytes());
+    header.extend_from_slice(&STREAM_SAMPLE_RATE.to_le_bytes());
+    header.extend_from_slice(&byte_rate.to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header
+}
+
+/// Send the stream to the listener until it disconnects. Whatever is requested, the response
+/// is the stream.
+async fn handle_connection(mut connection: TcpStream, mut rx: broadcast::Receiver<Arc<[u8]>>) {
+    let mut request = [0; MAX_REQUEST_SIZE];
+    if let Err(e) = connection.read(&mut request).await {
+        warn!("Error <{e}> reading audio stream request");
+        return;
+    }
+    let mut response = b"HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nCache-Control: no-cache\r\n\
+                         Connection: close\r\n\r\n"
+        .to_vec();
+    response.extend(wav_header());
+    if connection.write_all(&response).await.is_err() {
+        return;
+    }
+    loop {
+        let chunk = match rx.recv().await {
+            Ok(chunk) => chunk,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                warn!("Audio stream listener is behind, skipped {n} chunks");
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        if connection.write_all(&chunk).await.is_err() {
+            info!("Audio stream listener disconnected");
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wav_header, AudioStream, STREAM_CHANNELS, STREAM_SAMPLE_RATE};
+    use rodio::buffer::SamplesBuffer;
+    use rodio::Source;
+
+    #[test]
+    fn test_wav_header() {
+        let header = wav_header();
+        assert_eq!(header.len(), 44);
+        assert_eq!(&header[0..4], b"RIFF");
+        assert_eq!(&header[8..16], b"WAVEfmt ");
+        assert_eq!(&header[22..24], &STREAM_CHANNELS.to_le_bytes());
+        assert_eq!(&header[24..28], &STREAM_SAMPLE_RATE.to_le_bytes());
+        // Byte rate, for 16 bit stereo.
+        assert_eq!(&header[28..32], &(STREAM_SAMPLE_RATE * 4).to_le_bytes());
+        assert_eq!(&header[36..40], b"data");
+    }
+    #[test]
+    fn test_tee() {
+        let stream = AudioStream::new();
+        let mut rx = stream.tx.subscribe();
+        let source = SamplesBuffer::new(STREAM_CHANNELS, STREAM_SAMPLE_RATE, vec![1i16, -2, 3, -4]);
+        let played: Vec<i16> = stream.tee(source, false).collect();
+        assert_eq!(played, vec![1, -2, 3, -4]);
+        let sent = rx.try_recv().unwrap();
+        assert_eq!(&*sent, &[1, 0, 254, 255, 3, 0, 252, 255]);
+        assert!(rx.try_recv().is_err());
+    }
+    #[test]
+    fn test_tee_mute_local() {
+        let stream = AudioStream::new();
+        let mut rx = stream.tx.subscribe();
+        let source = SamplesBuffer::new(STREAM_CHANNELS, STREAM_SAMPLE_RATE, vec![1i16, 2]);
+        let played: Vec<i16> = stream.tee(source, true).collect();
+        assert_eq!(played, vec![0, 0]);
+        assert_eq!(&*rx.try_recv().unwrap(), &[1, 0, 2, 0]);
+    }
+    #[test]
+    fn test_tee_converts_format() {
+        let stream = AudioStream::new();
+        let source = SamplesBuffer::new(1, STREAM_SAMPLE_RATE, vec![5i16, 6]);
+        let tee = stream.tee(source, false);
+        assert_eq!(tee.channels(), STREAM_CHANNELS);
+        // Mono samples are played on both channels.
+        assert_eq!(tee.collect::<Vec<_>>(), vec![5, 5, 6, 6]);
+    }
+}
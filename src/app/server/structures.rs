@@ -0,0 +1,98 @@
+use std::fmt::Display;
+use ytmapi_rs::parse::{
+    SearchResultAlbum, SearchResultPlaylist, SearchResultPodcast, SearchResultSong,
+    SearchResultVideo, SearchResults,
+};
+
+/// The type of results a browser search will return.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SearchFilter {
+    #[default]
+    Artists,
+    Songs,
+    Albums,
+    Playlists,
+    Videos,
+    Podcasts,
+    /// Unfiltered search, with results in categorized sections.
+    All,
+}
+
+/// Search results for the non-artist search filters.
+#[derive(Debug)]
+pub enum SearchResultsList {
+    Songs(Vec<SearchResultSong>),
+    Albums(Vec<SearchResultAlbum>),
+    Playlists(Vec<SearchResultPlaylist>),
+    Videos(Vec<SearchResultVideo>),
+    Podcasts(Vec<SearchResultPodcast>),
+    All(Box<SearchResults>),
+}
+
+impl SearchFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Artists => Self::Songs,
+            Self::Songs => Self::Albums,
+            Self::Albums => Self::Playlists,
+            Self::Playlists => Self::Videos,
+            Self::Videos => Self::Podcasts,
+            Self::Podcasts => Self::All,
+            Self::All => Self::Artists,
+        }
+    }
+    pub fn prev(self) -> Self {
+        match self {
+            Self::Artists => Self::All,
+            Self::All => Self::Podcasts,
+            Self::Songs => Self::Artists,
+            Self::Albums => Self::Songs,
+            Self::Playlists => Self::Albums,
+            Self::Videos => Self::Playlists,
+            Self::Podcasts => Self::Videos,
+        }
+    }
+}
+
+impl Display for SearchFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Artists => "Artists",
+            Self::Songs => "Songs",
+            Self::Albums => "Albums",
+            Self::Playlists => "Playlists",
+            Self::Videos => "Videos",
+            Self::Podcasts => "Podcasts",
+            Self::All => "All",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl SearchResultsList {
+    pub fn filter(&self) -> SearchFilter {
+        match self {
+            Self::Songs(_) => SearchFilter::Songs,
+            Self::Albums(_) => SearchFilter::Albums,
+            Self::Playlists(_) => SearchFilter::Playlists,
+            Self::Videos(_) => SearchFilter::Videos,
+            Self::Podcasts(_) => SearchFilter::Podcasts,
+            Self::All(_) => SearchFilter::All,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchFilter;
+
+    #[test]
+    fn test_search_filter_cycles() {
+        let mut filter = SearchFilter::default();
+        for _ in 0..7 {
+            assert_eq!(filter.next().prev(), filter);
+            filter = filter.next();
+        }
+        assert_eq!(filter, SearchFilter::Artists);
+    }
+}
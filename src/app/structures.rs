@@ -1,9 +1,15 @@
 use super::view::{SortDirection, TableItem};
+use crate::drawutils::{icon, text_gauge};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::rc::Rc;
 use std::sync::Arc;
 use ytmapi_rs::common::youtuberesult::{ResultCore, YoutubeResult};
-use ytmapi_rs::parse::SongResult;
+use ytmapi_rs::common::{AlbumID, ChannelID, Explicit, LikeStatus, Thumbnail, VideoID, YoutubeID};
+use ytmapi_rs::parse::{PlaylistSong, SearchResultSong, SongResult};
+
+/// Width of the gauge drawn in the download status column while a song downloads.
+pub const DOWNLOAD_GAUGE_WIDTH: usize = 5;
 
 pub trait SongListComponent {
     fn get_song_from_idx(&self, idx: usize) -> Option<&ListSong>;
@@ -29,19 +35,57 @@ pub struct ListSong {
     pub raw: SongResult,
     pub download_status: DownloadStatus,
     pub id: ListSongID,
+    /// None if the song has no rating information, e.g when not signed in.
+    pub like_status: Option<LikeStatus>,
+    /// Bitrate of the downloaded audio in bits per second, once downloaded.
+    pub audio_bitrate: Option<u64>,
     year: Rc<String>,
     artists: Vec<Rc<String>>,
     album: Rc<String>,
+    /// Browse ids used to navigate from the song to its album and artist, if known.
+    album_id: Option<AlbumID<'static>>,
+    artist_id: Option<ChannelID<'static>>,
+}
+/// A song stored locally, e.g in the play history, with enough metadata to queue it again
+/// without a request.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedSong {
+    pub video_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub artists: Vec<String>,
+    #[serde(default)]
+    pub album: String,
+    #[serde(default)]
+    pub year: String,
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub explicit: bool,
+    pub album_id: Option<String>,
+    pub artist_id: Option<String>,
+    #[serde(default)]
+    pub thumbnails: Vec<Thumbnail>,
 }
 #[derive(Clone)]
 pub enum ListStatus {
     New,
     Loading,
+    /// Loading failed in a way that may not happen again, and the list is being loaded again.
+    Retrying,
     InProgress,
     Loaded,
     Error,
 }
 
+impl ListStatus {
+    /// Mark the list as retrying, if it's still loading.
+    pub fn set_retrying(&mut self) {
+        if matches!(self, ListStatus::Loading) {
+            *self = ListStatus::Retrying;
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum DownloadStatus {
     None,
@@ -64,11 +108,11 @@ pub enum PlayState {
 impl PlayState {
     pub fn list_icon(&self) -> char {
         match self {
-            PlayState::Buffering(_) => '',
-            PlayState::NotPlaying => '',
-            PlayState::Playing(_) => '',
-            PlayState::Paused(_) => '',
-            PlayState::Stopped => '',
+            PlayState::Buffering(_) => icon('', '~'),
+            PlayState::NotPlaying => icon('', ' '),
+            PlayState::Playing(_) => icon('', '>'),
+            PlayState::Paused(_) => icon('', '='),
+            PlayState::Stopped => icon('', '#'),
         }
     }
 }
@@ -76,11 +120,11 @@ impl PlayState {
 impl DownloadStatus {
     pub fn list_icon(&self) -> char {
         match self {
-            Self::Failed => '',
-            Self::Queued => '',
+            Self::Failed => icon('', 'x'),
+            Self::Queued => icon('', '.'),
             Self::None => ' ',
-            Self::Downloading(_) => '',
-            Self::Downloaded(_) => '',
+            Self::Downloading(_) => icon('', 'v'),
+            Self::Downloaded(_) => icon('', '*'),
         }
     }
 }
@@ -104,6 +148,19 @@ impl ListSong {
     pub fn get_album(&self) -> &String {
         &self.album
     }
+    pub fn get_album_id(&self) -> Option<&AlbumID<'static>> {
+        self.album_id.as_ref()
+    }
+    pub fn get_artist_id(&self) -> Option<&ChannelID<'static>> {
+        self.artist_id.as_ref()
+    }
+    pub fn like_icon(&self) -> char {
+        match self.like_status {
+            Some(LikeStatus::Liked) => icon('', '+'),
+            Some(LikeStatus::Disliked) => icon('', '-'),
+            Some(LikeStatus::Indifferent) | None => ' ',
+        }
+    }
     pub fn get_track_no(&self) -> usize {
         self.raw.get_track_no()
     }
@@ -112,9 +169,12 @@ impl ListSong {
             [
                 // Type annotation to help rust compiler
                 Cow::from(match self.download_status {
-                    DownloadStatus::Downloading(p) => {
-                        format!("{}[{}]%", self.download_status.list_icon(), p.0)
-                    }
+                    DownloadStatus::Downloading(p) => format!(
+                        "{}{}{:>3}%",
+                        self.download_status.list_icon(),
+                        text_gauge(p.0, DOWNLOAD_GAUGE_WIDTH),
+                        p.0
+                    ),
                     _ => self.download_status.list_icon().to_string(),
                 }),
                 self.get_track_no().to_string().into(),
@@ -139,6 +199,84 @@ impl ListSong {
     }
 }
 
+impl SavedSong {
+    pub fn from_song(song: &ListSong) -> Self {
+        Self {
+            video_id: song.raw.get_video_id().get_raw().to_string(),
+            title: song.get_title().clone(),
+            artists: song.get_artists().iter().map(|a| a.to_string()).collect(),
+            album: song.get_album().clone(),
+            year: song.get_year().clone(),
+            duration: song.get_duration().clone(),
+            explicit: *song.get_is_explicit(),
+            album_id: song.get_album_id().map(|id| id.get_raw().to_string()),
+            artist_id: song.get_artist_id().map(|id| id.get_raw().to_string()),
+            thumbnails: song.get_thumbnails().clone(),
+        }
+    }
+}
+
+/// A saved song with placeholder metadata, shared by tests.
+#[cfg(test)]
+pub fn test_saved_song(video_id: &str) -> SavedSong {
+    SavedSong {
+        video_id: video_id.into(),
+        title: format!("Song {video_id}"),
+        artists: vec!["Artist".into()],
+        album: "Album".into(),
+        year: "2024".into(),
+        duration: Some("3:25".into()),
+        explicit: false,
+        album_id: None,
+        artist_id: None,
+        thumbnails: Vec::new(),
+    }
+}
+
+/// Create songs that can be added to the playlist from saved songs.
+pub fn saved_songs_to_list<'a>(songs: impl IntoIterator<Item = &'a SavedSong>) -> Vec<ListSong> {
+    let mut list = AlbumSongsList::default();
+    for song in songs {
+        let explicit = if song.explicit {
+            Explicit::IsExplicit
+        } else {
+            Explicit::NotExplicit
+        };
+        let raw = SongResult::from(SearchResultSong {
+            title: song.title.clone(),
+            artist: song.artists.join(", "),
+            album: song.album.clone(),
+            duration: song.duration.clone().unwrap_or_default(),
+            plays: String::new(),
+            explicit,
+            video_id: VideoID::from_raw(song.video_id.clone()),
+            thumbnails: song.thumbnails.clone(),
+        });
+        list.add_raw_song_with_ids(
+            raw,
+            song.album.clone(),
+            song.year.clone(),
+            song.artists.clone(),
+            song.album_id.clone().map(AlbumID::from_raw),
+            song.artist_id.clone().map(ChannelID::from_raw),
+        );
+    }
+    list.list
+}
+
+fn parse_like_status(song: &SongResult) -> Option<LikeStatus> {
+    song.get_like_status()
+        .as_deref()
+        .and_then(LikeStatus::from_raw)
+}
+
+fn parse_album_id(song: &SongResult) -> Option<AlbumID<'static>> {
+    song.get_album()
+        .as_ref()
+        .and_then(|a| a.id.clone())
+        .map(AlbumID::from_raw)
+}
+
 impl YoutubeResult for ListSong {
     fn get_core(&self) -> &ResultCore {
         self.raw.get_core()
@@ -181,6 +319,11 @@ impl AlbumSongsList {
         self.state = ListStatus::New;
         self.list.clear();
     }
+    /// Replace the songs in the list, e.g to restore a previous state of the list.
+    /// As with clear, the ID is not reset.
+    pub fn replace_songs(&mut self, songs: Vec<ListSong>) {
+        self.list = songs;
+    }
     // Naive implementation
     pub fn append_raw_songs(
         &mut self,
@@ -199,6 +342,57 @@ impl AlbumSongsList {
             self.add_raw_song(song, album.clone(), year.clone(), artist.clone());
         }
     }
+    /// As append_raw_songs, but the album is known, so that the songs can be used to navigate
+    /// back to the album and its artist.
+    pub fn append_raw_album_songs(
+        &mut self,
+        raw_list: Vec<SongResult>,
+        album: String,
+        year: String,
+        artist: String,
+        album_id: AlbumID<'static>,
+        artist_id: Option<ChannelID<'static>>,
+    ) {
+        let first_new = self.list.len();
+        self.append_raw_songs(raw_list, album, year, artist);
+        for song in &mut self.list[first_new..] {
+            song.album_id = Some(album_id.clone());
+            song.artist_id = artist_id.clone();
+        }
+    }
+    /// Songs on a playlist each have their own album and artists, so these are taken from the
+    /// song instead of being shared.
+    pub fn append_raw_playlist_songs(&mut self, raw_list: Vec<PlaylistSong>) {
+        // Playlists don't contain a year.
+        let year = Rc::new(String::new());
+        for PlaylistSong { song, artists } in raw_list {
+            let album = song
+                .get_album()
+                .as_ref()
+                .and_then(|a| a.name.clone())
+                .unwrap_or_default();
+            let album_id = parse_album_id(&song);
+            // Only the first artist with a browse id can be navigated to.
+            let artist_id = artists
+                .iter()
+                .find_map(|a| a.id.clone())
+                .map(ChannelID::from_raw);
+            let id = self.create_next_id();
+            let like_status = parse_like_status(&song);
+            self.list.push(ListSong {
+                raw: song,
+                download_status: DownloadStatus::None,
+                id,
+                like_status,
+                audio_bitrate: None,
+                year: year.clone(),
+                artists: artists.into_iter().map(|a| Rc::new(a.name)).collect(),
+                album: Rc::new(album),
+                album_id,
+                artist_id,
+            });
+        }
+    }
     pub fn add_raw_song(
         &mut self,
         song: SongResult,
@@ -207,13 +401,46 @@ impl AlbumSongsList {
         artist: Rc<String>,
     ) -> ListSongID {
         let id = self.create_next_id();
+        let like_status = parse_like_status(&song);
+        let album_id = parse_album_id(&song);
         self.list.push(ListSong {
             raw: song,
             download_status: DownloadStatus::None,
             id,
+            like_status,
+            audio_bitrate: None,
             year,
             artists: vec![artist],
             album,
+            album_id,
+            artist_id: None,
+        });
+        id
+    }
+    /// As add_raw_song, but for a song whose artists and browse ids are already known, e.g a song
+    /// saved locally.
+    pub fn add_raw_song_with_ids(
+        &mut self,
+        song: SongResult,
+        album: String,
+        year: String,
+        artists: Vec<String>,
+        album_id: Option<AlbumID<'static>>,
+        artist_id: Option<ChannelID<'static>>,
+    ) -> ListSongID {
+        let id = self.create_next_id();
+        let like_status = parse_like_status(&song);
+        self.list.push(ListSong {
+            raw: song,
+            download_status: DownloadStatus::None,
+            id,
+            like_status,
+            audio_bitrate: None,
+            year: Rc::new(year),
+            artists: artists.into_iter().map(Rc::new).collect(),
+            album: Rc::new(album),
+            album_id,
+            artist_id,
         });
         id
     }
@@ -1,22 +1,35 @@
-use super::server::{api, downloader, player};
-use super::structures::ListSongID;
+use super::server::cast::{CastCommand, CastDevice};
+use super::server::downloader::DownloadProgressUpdateType;
+use super::server::export::SongTags;
+use super::server::structures::SearchFilter;
+use super::server::{api, cast, downloader, player};
+use super::structures::{ListSongID, Percentage};
 use super::ui::YoutuiWindow;
 use crate::app::server::KillRequest;
 use crate::app::server::{self, KillableTask};
-use crate::config::ApiKey;
+use crate::config::{ApiKey, OutputSettings};
 use crate::core::send_or_error;
+use crate::i18n::tr;
 use crate::Result;
+use std::cell::Cell;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, warn};
+use ytmapi_rs::common::{AlbumID, LikeStatus, PlaylistID, PodcastID};
 use ytmapi_rs::{ChannelID, VideoID};
 
 const MESSAGE_QUEUE_LENGTH: usize = 256;
+const MAX_CONCURRENT_ALBUM_ART_TASKS: usize = 2;
 
 pub struct TaskManager {
     cur_id: TaskID,
     tasks: Vec<Task>,
+    /// Tasks waiting for a task of the same category to finish, or for interactive tasks to
+    /// respond, before they are sent to the server.
+    queued: Vec<QueuedTask>,
+    limits: TaskLimits,
     _server_handle: tokio::task::JoinHandle<Result<()>>,
     server_request_tx: mpsc::Sender<server::Request>,
     server_response_rx: mpsc::Receiver<server::Response>,
@@ -36,35 +49,225 @@ struct Task {
     // XXX: to check if valid, is it as simple as check if Option is taken?
     kill: Option<oneshot::Sender<KillRequest>>,
     message: AppRequest,
+    started: Instant,
+    // Set when a response is received for the task. Cell as responses are processed by &self.
+    responded: Cell<bool>,
+    // Latest progress reported by the server, for tasks that report it.
+    progress: Option<(Percentage, String)>,
+    // Set when the server reports that the task failed and is being retried.
+    retrying: bool,
+}
+
+struct QueuedTask {
+    id: TaskID,
+    request: AppRequest,
+    kill_rx: oneshot::Receiver<KillRequest>,
+}
+
+/// How urgently a task should be started, as it may compete with other tasks for the network.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum TaskPriority {
+    /// Work ahead of time, e.g buffering songs, that can wait for other tasks.
+    Background,
+    Normal,
+    /// Tasks the user is waiting on, e.g a search they just typed.
+    Interactive,
+}
+
+/// Limits on the tasks running at once, so that a burst of background tasks can't hold up
+/// tasks the user is waiting on.
+struct TaskLimits {
+    max_album_art: usize,
+}
+
+impl TaskLimits {
+    /// Most tasks of the category that can run at once, or None if unlimited.
+    fn max_running(&self, category: &RequestCategory) -> Option<usize> {
+        match category {
+            RequestCategory::GetAlbumArt => Some(self.max_album_art),
+            _ => None,
+        }
+    }
+    /// Background tasks wait until interactive tasks have responded, and tasks of a limited
+    /// category wait until there is room.
+    fn can_start(&self, category: &RequestCategory, running: &[TaskSummary]) -> bool {
+        let running = || running.iter().filter(|t| t.status != TaskStatus::Queued);
+        if category.priority() == TaskPriority::Background
+            && running().any(|t| {
+                t.category.priority() == TaskPriority::Interactive
+                    && t.status == TaskStatus::Waiting
+            })
+        {
+            return false;
+        }
+        match self.max_running(category) {
+            Some(max) => running().filter(|t| t.category == *category).count() < max,
+            None => true,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TaskStatus {
+    /// The task hasn't been sent to the server yet, as it's waiting on other tasks.
+    Queued,
+    /// No response has been received yet.
+    Waiting,
+    /// At least one response has been received, but the task is still running.
+    Receiving,
+    /// The task failed in a way that may not happen again, and is being retried.
+    Retrying,
+}
+
+/// Snapshot of an in-flight task, for display.
+#[derive(Clone, Debug)]
+pub struct TaskSummary {
+    pub id: TaskID,
+    pub category: RequestCategory,
+    pub age: Duration,
+    pub status: TaskStatus,
+    /// How far through the task the server is, with a short description, if reported.
+    pub progress: Option<(Percentage, String)>,
 }
 
 #[derive(Clone)]
 pub enum AppRequest {
     SearchArtists(String),
+    SearchArtistsContinuation(String),
+    SearchSongs(String),
+    SearchAlbums(String),
+    SearchPlaylists(String),
+    SearchVideos(String),
+    SearchPodcasts(String),
+    SearchAll(String),
     GetSearchSuggestions(String),
     GetArtistSongs(ChannelID<'static>),
+    GetArtistDetails(ChannelID<'static>),
+    GetPlaylistSongs(PlaylistID<'static>),
+    QueuePlaylistSongs(PlaylistID<'static>),
+    ListAlbumSongs(AlbumID<'static>),
+    GetLibrary,
+    GetCharts(Option<String>),
+    GetLibraryPodcasts,
+    GetPodcastEpisodes(PodcastID<'static>),
+    QueuePodcastEpisodes(PodcastID<'static>),
+    GetLyrics(VideoID<'static>),
+    GetLibraryPlaylists,
+    AddSongToLibraryPlaylist(PlaylistID<'static>, VideoID<'static>),
+    RateSong(VideoID<'static>, LikeStatus, ListSongID),
+    GetLibrarySubscriptions,
+    SetArtistSubscribed(ChannelID<'static>, bool),
+    GetAlbumSongs {
+        album_id: AlbumID<'static>,
+        artist: String,
+        play: bool,
+    },
     Download(VideoID<'static>, ListSongID),
+    GetAlbumArt(String, ListSongID),
+    ExportSong(VideoID<'static>, SongTags),
     IncreaseVolume(i8),
     GetVolume,
     PlaySong(Arc<Vec<u8>>, ListSongID),
     GetPlayProgress(ListSongID),
     Stop(ListSongID),
     PausePlay(ListSongID),
+    FindCastDevices,
+    /// Play the songs on the cast device, starting from the song at the index.
+    CastQueue(CastDevice, Vec<VideoID<'static>>, usize),
+    CastCommand(CastCommand),
+    StopCasting,
 }
 
 impl AppRequest {
-    fn category(&self) -> RequestCategory {
+    pub fn category(&self) -> RequestCategory {
         match self {
-            AppRequest::SearchArtists(_) => RequestCategory::Search,
+            AppRequest::SearchArtists(_)
+            | AppRequest::SearchArtistsContinuation(_)
+            | AppRequest::SearchSongs(_)
+            | AppRequest::SearchAlbums(_)
+            | AppRequest::SearchPlaylists(_)
+            | AppRequest::SearchVideos(_)
+            | AppRequest::SearchPodcasts(_)
+            | AppRequest::SearchAll(_) => RequestCategory::Search,
             AppRequest::GetSearchSuggestions(_) => RequestCategory::GetSearchSuggestions,
-            AppRequest::GetArtistSongs(_) => RequestCategory::Get,
+            AppRequest::GetArtistSongs(_)
+            | AppRequest::GetPlaylistSongs(_)
+            | AppRequest::ListAlbumSongs(_) => RequestCategory::Get,
+            AppRequest::GetArtistDetails(_) => RequestCategory::GetArtistDetails,
+            AppRequest::GetAlbumSongs { .. } => RequestCategory::GetAlbumSongs,
+            AppRequest::QueuePlaylistSongs(_) => RequestCategory::QueuePlaylistSongs,
+            AppRequest::GetLibrary => RequestCategory::GetLibrary,
+            AppRequest::GetCharts(_) => RequestCategory::GetCharts,
+            AppRequest::GetLibraryPodcasts => RequestCategory::GetLibraryPodcasts,
+            AppRequest::GetPodcastEpisodes(_) => RequestCategory::GetPodcastEpisodes,
+            AppRequest::QueuePodcastEpisodes(_) => RequestCategory::QueuePodcastEpisodes,
+            AppRequest::GetLyrics(_) => RequestCategory::GetLyrics,
+            AppRequest::GetLibraryPlaylists => RequestCategory::GetLibraryPlaylists,
+            AppRequest::AddSongToLibraryPlaylist(..) => RequestCategory::AddSongToLibraryPlaylist,
+            AppRequest::RateSong(..) => RequestCategory::RateSong,
+            AppRequest::GetLibrarySubscriptions => RequestCategory::GetLibrarySubscriptions,
+            AppRequest::SetArtistSubscribed(..) => RequestCategory::SetArtistSubscribed,
             AppRequest::Download(..) => RequestCategory::Download,
+            AppRequest::GetAlbumArt(..) => RequestCategory::GetAlbumArt,
+            AppRequest::ExportSong(..) => RequestCategory::ExportSong,
             AppRequest::IncreaseVolume(_) => RequestCategory::IncreaseVolume,
             AppRequest::GetVolume => RequestCategory::GetVolume,
             AppRequest::PlaySong(..) => RequestCategory::PlayPauseStop,
             AppRequest::GetPlayProgress(_) => RequestCategory::ProgressUpdate,
             AppRequest::Stop(_) => RequestCategory::PlayPauseStop,
             AppRequest::PausePlay(_) => RequestCategory::PlayPauseStop,
+            AppRequest::FindCastDevices => RequestCategory::FindCastDevices,
+            AppRequest::CastQueue(..) => RequestCategory::CastQueue,
+            AppRequest::CastCommand(_) | AppRequest::StopCasting => RequestCategory::PlayPauseStop,
+        }
+    }
+}
+
+impl RequestCategory {
+    /// What the request does, as shown to the user, e.g when it times out.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            RequestCategory::Search => tr("Search"),
+            RequestCategory::Get => tr("Get songs"),
+            RequestCategory::GetArtistDetails => tr("Get artist details"),
+            RequestCategory::GetAlbumSongs => tr("Get album songs"),
+            RequestCategory::QueuePlaylistSongs => tr("Queue playlist"),
+            RequestCategory::GetLibrary => tr("Get library"),
+            RequestCategory::GetCharts => tr("Get charts"),
+            RequestCategory::GetLibraryPodcasts => tr("Get library podcasts"),
+            RequestCategory::GetPodcastEpisodes => tr("Get podcast episodes"),
+            RequestCategory::QueuePodcastEpisodes => tr("Queue podcast"),
+            RequestCategory::GetLyrics => tr("Get lyrics"),
+            RequestCategory::GetLibraryPlaylists => tr("Get library playlists"),
+            RequestCategory::AddSongToLibraryPlaylist => tr("Add song to playlist"),
+            RequestCategory::RateSong => tr("Rate song"),
+            RequestCategory::GetLibrarySubscriptions => tr("Get library subscriptions"),
+            RequestCategory::SetArtistSubscribed => tr("Change artist subscription"),
+            RequestCategory::Download => tr("Download song"),
+            RequestCategory::GetAlbumArt => tr("Get album art"),
+            RequestCategory::ExportSong => tr("Export song"),
+            RequestCategory::GetSearchSuggestions => tr("Get search suggestions"),
+            RequestCategory::GetVolume => tr("Get volume"),
+            RequestCategory::ProgressUpdate => tr("Get play progress"),
+            RequestCategory::IncreaseVolume => tr("Change volume"),
+            RequestCategory::PlayPauseStop => tr("Play/pause"),
+            RequestCategory::FindCastDevices => tr("Find cast devices"),
+            RequestCategory::CastQueue => tr("Cast queue"),
+        }
+    }
+    pub fn priority(&self) -> TaskPriority {
+        match self {
+            RequestCategory::Search
+            | RequestCategory::GetSearchSuggestions
+            | RequestCategory::GetVolume
+            | RequestCategory::ProgressUpdate
+            | RequestCategory::IncreaseVolume
+            | RequestCategory::PlayPauseStop
+            | RequestCategory::CastQueue => TaskPriority::Interactive,
+            RequestCategory::Download
+            | RequestCategory::GetAlbumArt
+            | RequestCategory::ExportSong => TaskPriority::Background,
+            _ => TaskPriority::Normal,
         }
     }
 }
@@ -73,28 +276,64 @@ impl AppRequest {
 pub enum RequestCategory {
     Search,
     Get,
+    GetArtistDetails,
+    GetAlbumSongs,
+    QueuePlaylistSongs,
+    GetLibrary,
+    GetCharts,
+    GetLibraryPodcasts,
+    GetPodcastEpisodes,
+    QueuePodcastEpisodes,
+    GetLyrics,
+    GetLibraryPlaylists,
+    AddSongToLibraryPlaylist,
+    RateSong,
+    GetLibrarySubscriptions,
+    SetArtistSubscribed,
     Download,
+    GetAlbumArt,
+    ExportSong,
     GetSearchSuggestions,
     GetVolume,
     ProgressUpdate,
     IncreaseVolume, // TODO: generalize
     PlayPauseStop,
+    FindCastDevices,
+    CastQueue,
 }
 
 impl TaskManager {
     // This should handle messages as well.
     // TODO: Error handling
-    pub fn new(api_key: ApiKey) -> Self {
+    pub fn new(
+        api_key: ApiKey,
+        download_settings: downloader::DownloadSettings,
+        output_settings: OutputSettings,
+        task_timeout: Option<Duration>,
+    ) -> Self {
+        // Downloads are limited by the downloader itself.
+        let limits = TaskLimits {
+            max_album_art: MAX_CONCURRENT_ALBUM_ART_TASKS,
+        };
         let (server_request_tx, server_request_rx) = mpsc::channel(MESSAGE_QUEUE_LENGTH);
         let (server_response_tx, server_response_rx) = mpsc::channel(MESSAGE_QUEUE_LENGTH);
-        let _server_handle = tokio::spawn(async {
-            let mut a = server::Server::new(api_key, server_response_tx, server_request_rx)?;
+        let _server_handle = tokio::spawn(async move {
+            let mut a = server::Server::new(
+                api_key,
+                download_settings,
+                output_settings,
+                task_timeout,
+                server_response_tx,
+                server_request_rx,
+            )?;
             a.run().await?;
             Ok(())
         });
         Self {
             cur_id: TaskID::default(),
             tasks: Vec::new(),
+            queued: Vec::new(),
+            limits,
             _server_handle,
             server_request_tx,
             server_response_rx,
@@ -104,21 +343,148 @@ impl TaskManager {
         let (kill_tx, kill_rx) = tokio::sync::oneshot::channel();
         // NOTE: We allocate as we want to keep a copy of the same message that was sent.
         let id = self.add_task(kill_tx, request.clone());
+        if !self
+            .limits
+            .can_start(&request.category(), &self.get_running_tasks())
+        {
+            debug!("Queueing task {:?}", id);
+            self.queued.push(QueuedTask {
+                id,
+                request,
+                kill_rx,
+            });
+            return;
+        }
+        self.start_task(request, id, kill_rx).await
+    }
+    /// Start queued tasks that there is now room for, highest priority and oldest first.
+    async fn start_queued_tasks(&mut self) {
+        // Queued tasks that have been killed are no longer tracked.
+        let tasks = &self.tasks;
+        self.queued.retain(|q| tasks.iter().any(|t| t.id == q.id));
+        loop {
+            let running = self.get_running_tasks();
+            let next = self
+                .queued
+                .iter()
+                .enumerate()
+                .filter(|(_, q)| self.limits.can_start(&q.request.category(), &running))
+                .max_by_key(|(i, q)| (q.request.category().priority(), std::cmp::Reverse(*i)))
+                .map(|(i, _)| i);
+            let Some(next) = next else {
+                return;
+            };
+            let QueuedTask {
+                id,
+                request,
+                kill_rx,
+            } = self.queued.remove(next);
+            debug!("Starting queued task {:?}", id);
+            self.start_task(request, id, kill_rx).await;
+        }
+    }
+    async fn start_task(
+        &mut self,
+        request: AppRequest,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
         match request {
             AppRequest::SearchArtists(a) => self.spawn_search_artists(a, id, kill_rx).await,
+            AppRequest::SearchArtistsContinuation(c) => {
+                self.spawn_search_artists_continuation(c, id, kill_rx).await
+            }
+            AppRequest::SearchSongs(q) => {
+                self.spawn_search(q, SearchFilter::Songs, id, kill_rx).await
+            }
+            AppRequest::SearchAlbums(q) => {
+                self.spawn_search(q, SearchFilter::Albums, id, kill_rx)
+                    .await
+            }
+            AppRequest::SearchPlaylists(q) => {
+                self.spawn_search(q, SearchFilter::Playlists, id, kill_rx)
+                    .await
+            }
+            AppRequest::SearchVideos(q) => {
+                self.spawn_search(q, SearchFilter::Videos, id, kill_rx)
+                    .await
+            }
+            AppRequest::SearchPodcasts(q) => {
+                self.spawn_search(q, SearchFilter::Podcasts, id, kill_rx)
+                    .await
+            }
+            AppRequest::SearchAll(q) => self.spawn_search(q, SearchFilter::All, id, kill_rx).await,
+            AppRequest::GetAlbumSongs {
+                album_id,
+                artist,
+                play,
+            } => {
+                self.spawn_get_album_songs(album_id, artist, play, id, kill_rx)
+                    .await
+            }
             AppRequest::GetSearchSuggestions(q) => {
                 self.spawn_get_search_suggestions(q, id, kill_rx).await
             }
             AppRequest::GetArtistSongs(a_id) => {
                 self.spawn_get_artist_songs(a_id, id, kill_rx).await
             }
+            AppRequest::GetArtistDetails(a_id) => {
+                self.spawn_get_artist_details(a_id, id, kill_rx).await
+            }
+            AppRequest::GetPlaylistSongs(p_id) => {
+                self.spawn_get_playlist_songs(p_id, id, kill_rx).await
+            }
+            AppRequest::QueuePlaylistSongs(p_id) => {
+                self.spawn_queue_playlist_songs(p_id, id, kill_rx).await
+            }
+            AppRequest::ListAlbumSongs(a_id) => {
+                self.spawn_list_album_songs(a_id, id, kill_rx).await
+            }
+            AppRequest::GetLibrary => self.spawn_get_library(id, kill_rx).await,
+            AppRequest::GetCharts(country) => self.spawn_get_charts(country, id, kill_rx).await,
+            AppRequest::GetLibraryPodcasts => self.spawn_get_library_podcasts(id, kill_rx).await,
+            AppRequest::GetPodcastEpisodes(p_id) => {
+                self.spawn_get_podcast_episodes(p_id, id, kill_rx).await
+            }
+            AppRequest::QueuePodcastEpisodes(p_id) => {
+                self.spawn_queue_podcast_episodes(p_id, id, kill_rx).await
+            }
+            AppRequest::GetLyrics(v_id) => self.spawn_get_lyrics(v_id, id, kill_rx).await,
+            AppRequest::GetLibraryPlaylists => self.spawn_get_library_playlists(id, kill_rx).await,
+            AppRequest::AddSongToLibraryPlaylist(p_id, v_id) => {
+                self.spawn_add_song_to_library_playlist(p_id, v_id, id, kill_rx)
+                    .await
+            }
+            AppRequest::RateSong(v_id, rating, s_id) => {
+                self.spawn_rate_song(v_id, rating, s_id, id, kill_rx).await
+            }
+            AppRequest::GetLibrarySubscriptions => {
+                self.spawn_get_library_subscriptions(id, kill_rx).await
+            }
+            AppRequest::SetArtistSubscribed(c_id, subscribed) => {
+                self.spawn_set_artist_subscribed(c_id, subscribed, id, kill_rx)
+                    .await
+            }
             AppRequest::Download(v_id, s_id) => self.spawn_download(v_id, s_id, id, kill_rx).await,
+            AppRequest::GetAlbumArt(url, s_id) => {
+                self.spawn_get_album_art(url, s_id, id, kill_rx).await
+            }
+            AppRequest::ExportSong(v_id, tags) => {
+                self.spawn_export_song(v_id, tags, id, kill_rx).await
+            }
             AppRequest::IncreaseVolume(i) => self.spawn_increase_volume(i, id).await,
             AppRequest::GetVolume => self.spawn_get_volume(id, kill_rx).await,
             AppRequest::PlaySong(song, song_id) => self.spawn_play_song(song, song_id, id).await,
             AppRequest::GetPlayProgress(song_id) => self.spawn_get_play_progress(song_id, id).await,
             AppRequest::Stop(song_id) => self.spawn_stop(song_id, id).await,
             AppRequest::PausePlay(song_id) => self.spawn_pause_play(song_id, id).await,
+            AppRequest::FindCastDevices => self.spawn_find_cast_devices(id, kill_rx).await,
+            AppRequest::CastQueue(device, video_ids, index) => {
+                self.spawn_cast_queue(device, video_ids, index, id, kill_rx)
+                    .await
+            }
+            AppRequest::CastCommand(command) => self.spawn_cast_command(command, id).await,
+            AppRequest::StopCasting => self.spawn_stop_casting(id).await,
         };
     }
     // TODO: Consider if this should create it's own channel and return a KillableTask.
@@ -138,6 +504,10 @@ impl TaskManager {
             id: self.cur_id,
             kill: Some(kill),
             message,
+            started: Instant::now(),
+            responded: Cell::new(false),
+            progress: None,
+            retrying: false,
         });
         self.cur_id
     }
@@ -159,6 +529,249 @@ impl TaskManager {
         )
         .await
     }
+    pub async fn spawn_search_artists_continuation(
+        &mut self,
+        continuation: String,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // A new search will kill this task, so that results aren't appended to the wrong list.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::SearchArtistsContinuation(
+                continuation,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_search(
+        &mut self,
+        query: String,
+        filter: SearchFilter,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Supersedes previous searches, regardless of filter.
+        self.kill_all_task_type_except_id(RequestCategory::Search, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::NewSearch(
+                query,
+                filter,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_get_album_songs(
+        &mut self,
+        album_id: AlbumID<'static>,
+        artist: String,
+        play: bool,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Does not kill previous tasks, as multiple albums can be queued at once.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetAlbumSongs {
+                album_id,
+                artist,
+                play,
+                task: KillableTask::new(id, kill_rx),
+            }),
+        )
+        .await
+    }
+    pub async fn spawn_get_library(&mut self, id: TaskID, kill_rx: oneshot::Receiver<KillRequest>) {
+        self.kill_all_task_type_except_id(RequestCategory::GetLibrary, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetLibrary(KillableTask::new(
+                id, kill_rx,
+            ))),
+        )
+        .await
+    }
+    pub async fn spawn_get_charts(
+        &mut self,
+        country: Option<String>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetCharts, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetCharts(
+                country,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_get_artist_details(
+        &mut self,
+        artist_id: ChannelID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetArtistDetails, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetArtistDetails(
+                artist_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_get_library_podcasts(
+        &mut self,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetLibraryPodcasts, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetLibraryPodcasts(KillableTask::new(
+                id, kill_rx,
+            ))),
+        )
+        .await
+    }
+    pub async fn spawn_get_podcast_episodes(
+        &mut self,
+        podcast_id: PodcastID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetPodcastEpisodes, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetPodcastEpisodes(
+                podcast_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_queue_podcast_episodes(
+        &mut self,
+        podcast_id: PodcastID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Does not kill previous tasks, as multiple podcasts can be queued at once.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::QueuePodcastEpisodes(
+                podcast_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_get_lyrics(
+        &mut self,
+        video_id: VideoID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetLyrics, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetLyrics(
+                video_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_get_library_playlists(
+        &mut self,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetLibraryPlaylists, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetLibraryPlaylists(
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_add_song_to_library_playlist(
+        &mut self,
+        playlist_id: PlaylistID<'static>,
+        video_id: VideoID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Other tasks are not killed, as each song added is independent.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::AddSongToLibraryPlaylist(
+                playlist_id,
+                video_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_get_library_subscriptions(
+        &mut self,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::GetLibrarySubscriptions, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetLibrarySubscriptions(
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_set_artist_subscribed(
+        &mut self,
+        channel_id: ChannelID<'static>,
+        subscribed: bool,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Other tasks are not killed, as each subscription change is independent.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::SetArtistSubscribed(
+                channel_id,
+                subscribed,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_rate_song(
+        &mut self,
+        video_id: VideoID<'static>,
+        rating: LikeStatus,
+        song_id: ListSongID,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Other tasks are not killed, as each song rated is independent.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::RateSong(
+                video_id,
+                rating,
+                song_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
     pub async fn spawn_get_search_suggestions(
         &mut self,
         query: String,
@@ -191,6 +804,56 @@ impl TaskManager {
         )
         .await
     }
+    pub async fn spawn_get_playlist_songs(
+        &mut self,
+        playlist_id: PlaylistID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Playlist songs replace the same list as artist songs.
+        self.kill_all_task_type_except_id(RequestCategory::Get, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::GetPlaylistSongs(
+                playlist_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_queue_playlist_songs(
+        &mut self,
+        playlist_id: PlaylistID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Does not kill previous tasks, as multiple playlists can be queued at once.
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::QueuePlaylistSongs(
+                playlist_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_list_album_songs(
+        &mut self,
+        album_id: AlbumID<'static>,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Album songs replace the same list as artist songs.
+        self.kill_all_task_type_except_id(RequestCategory::Get, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(server::api::Request::ListAlbumSongs(
+                album_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
     pub async fn spawn_download(
         &mut self,
         video_id: VideoID<'static>,
@@ -209,6 +872,43 @@ impl TaskManager {
         )
         .await
     }
+    pub async fn spawn_get_album_art(
+        &mut self,
+        url: String,
+        list_song_id: ListSongID,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Only the art for the current song is displayed.
+        self.kill_all_task_type_except_id(RequestCategory::GetAlbumArt, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Downloader(server::downloader::Request::DownloadAlbumArt(
+                url,
+                list_song_id,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_export_song(
+        &mut self,
+        video_id: VideoID<'static>,
+        tags: SongTags,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        send_or_error(
+            // Does not kill previous tasks, as multiple songs can be exported at once.
+            &self.server_request_tx,
+            server::Request::Downloader(server::downloader::Request::ExportSong(
+                video_id,
+                tags,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
     pub async fn spawn_increase_volume(&mut self, vol_inc: i8, id: TaskID) {
         self.block_all_task_type_except_id(RequestCategory::IncreaseVolume, id);
         self.kill_all_task_type_except_id(RequestCategory::GetVolume, id);
@@ -261,9 +961,121 @@ impl TaskManager {
         )
         .await
     }
+    pub async fn spawn_find_cast_devices(
+        &mut self,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        self.kill_all_task_type_except_id(RequestCategory::FindCastDevices, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Cast(cast::Request::FindDevices(KillableTask::new(id, kill_rx))),
+        )
+        .await
+    }
+    pub async fn spawn_cast_queue(
+        &mut self,
+        device: CastDevice,
+        video_ids: Vec<VideoID<'static>>,
+        index: usize,
+        id: TaskID,
+        kill_rx: oneshot::Receiver<KillRequest>,
+    ) {
+        // Only one device can be cast to at once.
+        self.kill_all_task_type_except_id(RequestCategory::CastQueue, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Cast(cast::Request::CastQueue(
+                device,
+                video_ids,
+                index,
+                KillableTask::new(id, kill_rx),
+            )),
+        )
+        .await
+    }
+    pub async fn spawn_cast_command(&mut self, command: CastCommand, id: TaskID) {
+        self.block_all_task_type_except_id(RequestCategory::PlayPauseStop, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Cast(cast::Request::Command(command)),
+        )
+        .await
+    }
+    pub async fn spawn_stop_casting(&mut self, id: TaskID) {
+        self.kill_all_task_type_except_id(RequestCategory::CastQueue, id);
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Cast(cast::Request::Disconnect),
+        )
+        .await
+    }
     pub fn is_task_valid(&self, id: TaskID) -> bool {
         self.tasks.iter().any(|x| x.id == id)
     }
+    /// Note that a response was received for the task. Returns false if the task is no longer
+    /// valid, in which case the response should be ignored.
+    fn receive_response(&self, id: TaskID) -> bool {
+        let Some(task) = self.tasks.iter().find(|x| x.id == id) else {
+            return false;
+        };
+        task.responded.set(true);
+        true
+    }
+    /// Tasks that are queued, or still running on the server, and can be killed.
+    /// Once a task finishes the server drops its kill receiver, so it is no longer listed.
+    pub fn get_running_tasks(&self) -> Vec<TaskSummary> {
+        self.tasks
+            .iter()
+            .filter(|x| x.kill.as_ref().is_some_and(|tx| !tx.is_closed()))
+            .map(|x| TaskSummary {
+                id: x.id,
+                category: x.message.category(),
+                age: x.started.elapsed(),
+                status: if self.queued.iter().any(|q| q.id == x.id) {
+                    TaskStatus::Queued
+                } else if x.retrying {
+                    TaskStatus::Retrying
+                } else if x.responded.get() {
+                    TaskStatus::Receiving
+                } else {
+                    TaskStatus::Waiting
+                },
+                progress: x.progress.clone(),
+            })
+            .collect()
+    }
+    /// Replace the API using new credentials. This isn't tracked as a task, as it should never be
+    /// killed or blocked.
+    pub async fn reinitialise_api(&self, api_key: ApiKey) {
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Api(api::Request::ReinitialiseApi(api_key)),
+        )
+        .await
+    }
+    /// Change where songs are played, e.g when the config file is edited. This isn't tracked as
+    /// a task, as there is no response.
+    pub async fn set_output(&self, output_settings: OutputSettings) {
+        send_or_error(
+            &self.server_request_tx,
+            server::Request::Player(server::player::Request::SetOutput(output_settings)),
+        )
+        .await
+    }
+    pub fn kill_task(&mut self, id: TaskID) {
+        debug!("Killing task {:?}", id);
+        if let Some(tx) = self
+            .tasks
+            .iter_mut()
+            .find(|x| x.id == id)
+            .and_then(|x| x.kill.take())
+        {
+            tx.send(KillRequest)
+                .unwrap_or_else(|_| error!("Error sending kill message"));
+        }
+        self.tasks.retain(|x| x.id != id);
+    }
     pub fn kill_all_task_type_except_id(&mut self, request_category: RequestCategory, id: TaskID) {
         debug!(
             "Killing all pending {:?} tasks except {:?}",
@@ -292,62 +1104,320 @@ impl TaskManager {
         self.tasks
             .retain(|x| x.message.category() != request_category || x.id == id);
     }
-    pub async fn action_messages(&mut self, ui_state: &mut YoutuiWindow) {
+    /// Apply the messages received from the server to the UI state.
+    /// Returns true if any messages were received.
+    pub async fn action_messages(&mut self, ui_state: &mut YoutuiWindow) -> bool {
+        let mut received = false;
         // XXX: Consider general case to check if task is valid.
         // In this case, message could implement Task with get_id() function?
         while let Ok(msg) = self.server_response_rx.try_recv() {
+            received = true;
             match msg {
                 server::Response::Api(msg) => self.process_api_msg(msg, ui_state).await,
                 server::Response::Player(msg) => self.process_player_msg(msg, ui_state).await,
                 server::Response::Downloader(msg) => {
                     self.process_downloader_msg(msg, ui_state).await
                 }
+                server::Response::Cast(msg) => self.process_cast_msg(msg, ui_state).await,
+                server::Response::TaskProgress(id, progress, label) => {
+                    self.process_task_progress(id, progress, label, ui_state)
+                }
+                server::Response::TaskTimedOut(id) => {
+                    self.process_task_timed_out(id, ui_state).await
+                }
+                server::Response::TaskRetrying(id) => self.process_task_retrying(id, ui_state),
             };
         }
+        // Tasks may have finished, making room for queued tasks.
+        self.start_queued_tasks().await;
+        received
+    }
+    pub fn process_task_progress(
+        &mut self,
+        id: TaskID,
+        progress: Percentage,
+        label: String,
+        ui_state: &mut YoutuiWindow,
+    ) {
+        if !self.receive_response(id) {
+            return;
+        }
+        let Some(task) = self.tasks.iter_mut().find(|x| x.id == id) else {
+            return;
+        };
+        // Song lists show their progress alongside the songs.
+        if matches!(
+            task.message.category(),
+            RequestCategory::Get | RequestCategory::GetAlbumSongs
+        ) {
+            ui_state.handle_song_list_progress(label.clone());
+        }
+        task.progress = Some((progress, label));
+    }
+    pub fn process_task_retrying(&mut self, id: TaskID, ui_state: &mut YoutuiWindow) {
+        if !self.is_task_valid(id) {
+            return;
+        }
+        let Some(task) = self.tasks.iter_mut().find(|x| x.id == id) else {
+            return;
+        };
+        task.retrying = true;
+        ui_state.handle_task_retrying(&task.message);
+    }
+    /// The server stopped the task as it ran for too long. Tasks with their own error state,
+    /// e.g downloads, are marked as failed, and the user is told about the rest.
+    pub async fn process_task_timed_out(&mut self, id: TaskID, ui_state: &mut YoutuiWindow) {
+        if !self.is_task_valid(id) {
+            return;
+        }
+        let Some(task) = self.tasks.iter().find(|x| x.id == id) else {
+            return;
+        };
+        match &task.message {
+            AppRequest::Download(_, song_id) => {
+                let update = DownloadProgressUpdateType::Error(tr("Timed out").to_string());
+                ui_state
+                    .handle_set_song_download_progress(update, *song_id)
+                    .await
+            }
+            AppRequest::GetAlbumArt(_, song_id) => ui_state.handle_album_art_error(*song_id),
+            message => ui_state.handle_task_timed_out(message.category()),
+        }
+        self.tasks.retain(|x| x.id != id);
     }
     pub async fn process_api_msg(&self, msg: api::Response, ui_state: &mut YoutuiWindow) {
         tracing::debug!("Processing {:?}", msg);
         match msg {
-            api::Response::ReplaceArtistList(list, id) => {
-                if !self.is_task_valid(id) {
+            api::Response::ReplaceArtistList(list, continuation, id) => {
+                if !self.receive_response(id) {
                     return;
                 }
-                ui_state.handle_replace_artist_list(list).await;
+                ui_state
+                    .handle_replace_artist_list(list, continuation)
+                    .await;
+            }
+            api::Response::AppendArtistList(list, continuation, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_append_artist_list(list, continuation);
             }
             api::Response::SearchArtistError(id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_search_artist_error();
             }
             api::Response::ReplaceSearchSuggestions(runs, id, search) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state
                     .handle_replace_search_suggestions(runs, search)
                     .await;
             }
+            api::Response::ReplaceSearchResults(results, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_search_results(results);
+            }
+            api::Response::SearchError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_search_error();
+            }
+            api::Response::AlbumSongs {
+                song_list,
+                album,
+                year,
+                artist,
+                play,
+                id,
+            } => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state
+                    .handle_album_songs(song_list, album, year, artist, play)
+                    .await;
+            }
+            api::Response::AlbumSongsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_album_songs_error();
+            }
+            api::Response::ReplaceLibrary {
+                playlists,
+                albums,
+                artists,
+                id,
+            } => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_library(playlists, albums, artists);
+            }
+            api::Response::LibraryError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_library_error();
+            }
+            api::Response::ReplaceCharts(charts, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_charts(charts);
+            }
+            api::Response::ChartsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_charts_error();
+            }
+            api::Response::ReplaceArtistDetails(artist, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_artist_details(*artist);
+            }
+            api::Response::ArtistDetailsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_artist_details_error();
+            }
+            api::Response::ReplaceLibraryPodcasts(podcasts, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_library_podcasts(podcasts);
+            }
+            api::Response::LibraryPodcastsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_library_podcasts_error();
+            }
+            api::Response::ReplacePodcastEpisodes(podcast, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_podcast_episodes(podcast);
+            }
+            api::Response::PodcastEpisodesError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_podcast_episodes_error();
+            }
+            api::Response::QueuePodcastEpisodes(podcast, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_queue_podcast_episodes(podcast);
+            }
+            api::Response::QueuePodcastEpisodesError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_queue_podcast_episodes_error();
+            }
+            api::Response::ReplaceLyrics(lyrics, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_lyrics(lyrics);
+            }
+            api::Response::LyricsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_lyrics_error();
+            }
+            api::Response::ReplaceLibraryPlaylists(playlists, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_library_playlists(playlists);
+            }
+            api::Response::LibraryPlaylistsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_library_playlists_error();
+            }
+            api::Response::SongAddedToLibraryPlaylist(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_song_added_to_library_playlist();
+            }
+            api::Response::AddSongToLibraryPlaylistError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_add_song_to_library_playlist_error();
+            }
+            api::Response::SongRated(song_id, rating, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_song_rated(song_id, rating);
+            }
+            api::Response::RateSongError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_rate_song_error();
+            }
+            api::Response::ReplaceLibrarySubscriptions(channel_ids, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_replace_library_subscriptions(channel_ids);
+            }
+            api::Response::LibrarySubscriptionsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_library_subscriptions_error();
+            }
+            api::Response::ArtistSubscribed(channel_id, subscribed, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_artist_subscribed(channel_id, subscribed);
+            }
+            api::Response::ArtistSubscriptionError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_artist_subscription_error();
+            }
             api::Response::SongListLoading(id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_song_list_loading();
             }
             api::Response::SongListLoaded(id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_song_list_loaded();
             }
             api::Response::NoSongsFound(id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_no_songs_found();
             }
             api::Response::SongsFound(id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_songs_found();
@@ -357,15 +1427,43 @@ impl TaskManager {
                 album,
                 year,
                 artist,
+                album_id,
+                artist_id,
                 id,
             } => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state
+                    .handle_append_song_list(song_list, album, year, artist, album_id, artist_id);
+            }
+            api::Response::AppendPlaylistSongList { song_list, id } => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_append_playlist_song_list(song_list);
+            }
+            api::Response::PlaylistSongsError(id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_playlist_songs_error();
+            }
+            api::Response::QueuePlaylistSongs { song_list, id } => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_queue_playlist_songs(song_list);
+            }
+            api::Response::QueuePlaylistSongsError(id) => {
+                if !self.receive_response(id) {
                     return;
                 }
-                ui_state.handle_append_song_list(song_list, album, year, artist);
+                ui_state.handle_queue_playlist_songs_error();
             }
             // XXX: Improve routing for this action.
             api::Response::ApiError(e) => ui_state.handle_api_error(e).await,
+            api::Response::ApiReinitialised => ui_state.handle_api_reinitialised(),
         }
     }
     pub async fn process_downloader_msg(
@@ -375,13 +1473,37 @@ impl TaskManager {
     ) {
         match msg {
             downloader::Response::DownloadProgressUpdate(update_type, song_id, task_id) => {
-                if !self.is_task_valid(task_id) {
+                if !self.receive_response(task_id) {
                     return;
                 }
                 ui_state
                     .handle_set_song_download_progress(update_type, song_id)
                     .await;
             }
+            downloader::Response::AlbumArt(image, song_id, task_id) => {
+                if !self.receive_response(task_id) {
+                    return;
+                }
+                ui_state.handle_replace_album_art(image, song_id);
+            }
+            downloader::Response::AlbumArtError(song_id, task_id) => {
+                if !self.receive_response(task_id) {
+                    return;
+                }
+                ui_state.handle_album_art_error(song_id);
+            }
+            downloader::Response::SongExported(path, task_id) => {
+                if !self.receive_response(task_id) {
+                    return;
+                }
+                ui_state.handle_song_exported(path);
+            }
+            downloader::Response::ExportError(title, e, task_id) => {
+                if !self.receive_response(task_id) {
+                    return;
+                }
+                ui_state.handle_export_error(title, e);
+            }
         }
     }
     pub async fn process_player_msg(&self, msg: player::Response, ui_state: &mut YoutuiWindow) {
@@ -392,35 +1514,94 @@ impl TaskManager {
                 ui_state.handle_done_playing(song_id).await;
             }
             player::Response::Paused(song_id, id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_set_to_paused(song_id).await;
             }
             player::Response::Playing(song_id, id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_set_to_playing(song_id).await;
             }
             player::Response::Stopped(song_id, id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_set_to_stopped(song_id).await;
             }
             player::Response::ProgressUpdate(perc, song_id, id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_set_song_play_progress(perc, song_id);
             }
             player::Response::VolumeUpdate(vol, id) => {
-                if !self.is_task_valid(id) {
+                if !self.receive_response(id) {
                     return;
                 }
                 ui_state.handle_set_volume(vol);
             }
         }
     }
+    pub async fn process_cast_msg(&self, msg: cast::Response, ui_state: &mut YoutuiWindow) {
+        match msg {
+            cast::Response::DevicesFound(devices, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_cast_devices_found(devices);
+            }
+            cast::Response::Connected(device, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_cast_connected(device);
+            }
+            cast::Response::ConnectError(error, id) => {
+                if !self.receive_response(id) {
+                    return;
+                }
+                ui_state.handle_cast_error(error);
+            }
+            // Sent by the device rather than in response to a task.
+            cast::Response::StateChanged(state) => ui_state.handle_cast_state(state),
+            cast::Response::Disconnected => ui_state.handle_cast_disconnected(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RequestCategory, TaskID, TaskLimits, TaskStatus, TaskSummary};
+    use std::time::Duration;
+
+    fn task(category: RequestCategory, status: TaskStatus) -> TaskSummary {
+        TaskSummary {
+            id: TaskID::default(),
+            category,
+            age: Duration::ZERO,
+            status,
+            progress: None,
+        }
+    }
+
+    #[test]
+    fn test_task_limits() {
+        let limits = TaskLimits { max_album_art: 1 };
+        let fetching = [task(RequestCategory::GetAlbumArt, TaskStatus::Queued)];
+        assert!(limits.can_start(&RequestCategory::GetAlbumArt, &fetching));
+        let fetching = [task(RequestCategory::GetAlbumArt, TaskStatus::Waiting)];
+        assert!(!limits.can_start(&RequestCategory::GetAlbumArt, &fetching));
+        // Limits only apply within a category.
+        assert!(limits.can_start(&RequestCategory::Download, &fetching));
+        assert!(limits.can_start(&RequestCategory::Search, &fetching));
+        // Background tasks wait for a search to respond, but other tasks don't.
+        let searching = [task(RequestCategory::Search, TaskStatus::Waiting)];
+        assert!(!limits.can_start(&RequestCategory::Download, &searching));
+        assert!(limits.can_start(&RequestCategory::GetLyrics, &searching));
+        let searching = [task(RequestCategory::Search, TaskStatus::Receiving)];
+        assert!(limits.can_start(&RequestCategory::Download, &searching));
+    }
 }
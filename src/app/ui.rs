@@ -1,28 +1,86 @@
-use self::{browser::Browser, logger::Logger, playlist::Playlist};
+use self::commandline::{parse_command, CommandLine, ParsedCommand, ARGUMENT_COMMANDS};
+use self::{
+    addtolibraryplaylist::AddToLibraryPlaylistPopup,
+    albumart::AlbumArt,
+    artistdetails::ArtistDetails,
+    autherror::AuthErrorPopup,
+    browser::Browser,
+    charts::Charts,
+    favorites::Favorites,
+    help::{get_help_entries, HelpEntry, HelpMenu},
+    library::Library,
+    localplaylists::LocalPlaylistsPopup,
+    logger::Logger,
+    lyrics::{Lyrics, LyricsSong},
+    playlist::Playlist,
+    podcasts::Podcasts,
+    recentlyplayed::RecentlyPlayed,
+    songdetails::{SongDetails, SongDetailsPopup},
+    tasks::TasksPopup,
+    toast::{NotifyLevel, Toasts, UIMessage},
+};
 use super::component::actionhandler::{
-    get_key_subset, handle_key_stack, handle_key_stack_and_action, Action, ActionHandler,
-    DominantKeyRouter, KeyDisplayer, KeyHandleAction, KeyHandleOutcome, KeyRouter, TextHandler,
+    find_action_by_description, get_action_descriptions, get_key_subset, handle_key_stack,
+    handle_key_stack_and_action, Action, ActionHandler, DominantKeyRouter, KeyDisplayer,
+    KeyHandleAction, KeyHandleOutcome, KeyRouter, TextEdit, TextHandler,
 };
+use super::component::confirm::ConfirmDialog;
+use super::events::EventBus;
 use super::keycommand::{
     CommandVisibility, DisplayableCommand, DisplayableMode, KeyCommand, Keymap,
 };
 use super::structures::*;
-use super::view::Scrollable;
+use super::taskmanager::{AppRequest, RequestCategory, TaskSummary};
+use super::view::{Scrollable, TypeAheadJump};
 use super::AppCallback;
+use crate::app::server::cast::{CastCommand, CastDevice, CastState};
 use crate::app::server::downloader::DownloadProgressUpdateType;
-use crate::core::send_or_error;
+use crate::app::server::structures::{SearchFilter, SearchResultsList};
+use crate::config::{AlbumArtPosition, LiveSettings, PaneSizes, StatusFormat};
+use crate::core::{copy_to_clipboard, open_in_browser, send_or_error};
+use crate::drawutils::{advance_jump_flash, advance_spinner, start_jump_flash};
 use crate::error::Error;
+use crate::i18n::{tr, tr_format};
+use crate::remote::{RemoteCommand, RemoteReply, RemoteSong, RemoteStatus};
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Instant;
 use tokio::sync::mpsc;
-use ytmapi_rs::common::SearchSuggestion;
-use ytmapi_rs::parse::{SearchResultArtist, SongResult};
+use tracing::{error, info, warn};
+use ytmapi_rs::common::library::{
+    LibraryAlbum, LibraryArtist, LibraryPodcast, Playlist as LibraryPlaylist,
+};
+use ytmapi_rs::common::youtuberesult::YoutubeResult;
+use ytmapi_rs::common::{AlbumID, LikeStatus, PlaylistID, SearchSuggestion, YoutubeID};
+use ytmapi_rs::parse::{PlaylistSong, SearchResultArtist, SongResult};
+use ytmapi_rs::{ChannelID, VideoID};
 
+mod addtolibraryplaylist;
+pub mod albumart;
+mod artistdetails;
+mod autherror;
 mod browser;
+mod charts;
+mod commandline;
 pub mod draw;
+mod favorites;
 mod footer;
 mod header;
-mod logger;
+mod help;
+mod hintbar;
+mod library;
+mod localplaylists;
+pub mod logger;
+mod lyrics;
+mod nowplaying;
 mod playlist;
+mod podcasts;
+mod recentlyplayed;
+pub mod songdetails;
+mod tasks;
+pub mod toast;
 
 const VOL_TICK: i8 = 5;
 
@@ -35,6 +93,13 @@ pub enum WindowContext {
     Browser,
     Playlist,
     Logs,
+    Library,
+    Charts,
+    ArtistDetails,
+    Podcasts,
+    Lyrics,
+    RecentlyPlayed,
+    Favorites,
 }
 
 // An Action that can be triggered from a keybind.
@@ -50,58 +115,138 @@ pub enum UIAction {
     HelpUp,
     HelpDown,
     ViewLogs,
+    ViewLibrary,
+    ViewCharts,
+    ViewPodcasts,
+    ViewLyrics,
+    ViewRecentlyPlayed,
+    ViewFavorites,
+    ToggleSplitLayout,
+    NewBrowserTab,
+    CloseBrowserTab,
+    NextBrowserTab,
+    PrevBrowserTab,
+    ShowCommandLine,
+    CloseCommandLine,
+    ExecuteCommandLine,
+    NextCompletion,
+    PrevCompletion,
+    AddToLibraryPlaylistUp,
+    AddToLibraryPlaylistDown,
+    ConfirmAddToLibraryPlaylist,
+    CloseAddToLibraryPlaylist,
+    LikeCurrentSong,
+    DislikeCurrentSong,
+    StarCurrentSong,
+    ToggleTasks,
+    TasksUp,
+    TasksDown,
+    CancelSelectedTask,
+    ToggleNotifications,
+    NotificationsUp,
+    NotificationsDown,
+    ConfirmYes,
+    ConfirmNo,
+    OpenFromUrl,
+    CopyCurrentSongUrl,
+    GrowArtistList,
+    ShrinkArtistList,
+    GrowSplit,
+    ShrinkSplit,
+    SongDetailsUp,
+    SongDetailsDown,
+    CopySongDetailsField,
+    CloseSongDetails,
+    UndoPlaylistEdit,
+    RedoPlaylistEdit,
+    ToggleLocalPlaylists,
+    LocalPlaylistsUp,
+    LocalPlaylistsDown,
+    LoadLocalPlaylist,
+    AppendLocalPlaylist,
+    SaveLocalPlaylist,
+    DeleteLocalPlaylist,
+    RetrySignIn,
+    RerunSetup,
+}
+
+/// Destructive actions that are only run once confirmed by the user.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfirmAction {
+    Quit,
+    ClearPlaylist,
+    DeleteLocalPlaylist(String),
 }
 
 pub struct YoutuiWindow {
     context: WindowContext,
     prev_context: WindowContext,
     playlist: Playlist,
+    /// The browser tab that is currently shown.
     browser: Browser,
+    /// All browser tabs except the current one, which is stored in browser.
+    browser_tabs: Vec<Browser>,
+    cur_browser_tab: usize,
+    /// Show the playlist beside the browser, instead of switching between them.
+    /// Focus still moves between them by changing context.
+    split_layout: bool,
+    pane_sizes: PaneSizes,
+    /// Custom format for the now playing line, if set in config.
+    status_format: Option<StatusFormat>,
+    /// Searching for and casting to Google Cast devices is opt in, as it queries the network.
+    cast_enabled: bool,
+    /// Devices found by the last search.
+    cast_devices: Vec<CastDevice>,
     logger: Logger,
+    library: Library,
+    charts: Charts,
+    artist_details: ArtistDetails,
+    podcasts: Podcasts,
+    lyrics: Lyrics,
+    recently_played: RecentlyPlayed,
+    favorites: Favorites,
+    album_art: AlbumArt,
     callback_tx: mpsc::Sender<AppCallback>,
     keybinds: Vec<KeyCommand<UIAction>>,
     key_stack: Vec<KeyEvent>,
     help: HelpMenu,
-}
-
-pub struct HelpMenu {
-    shown: bool,
-    cur: usize,
-    len: usize,
-    keybinds: Vec<KeyCommand<UIAction>>,
-}
-
-impl Default for HelpMenu {
-    fn default() -> Self {
-        HelpMenu {
-            shown: Default::default(),
-            cur: Default::default(),
-            len: Default::default(),
-            keybinds: help_keybinds(),
-        }
-    }
-}
-
-impl Scrollable for HelpMenu {
-    fn increment_list(&mut self, amount: isize) {
-        self.cur = self
-            .cur
-            .saturating_add_signed(amount)
-            .min(self.len.saturating_sub(1));
-    }
-
-    fn get_selected_item(&self) -> usize {
-        self.cur
-    }
+    command_line: CommandLine,
+    command_line_keybinds: Vec<KeyCommand<UIAction>>,
+    add_to_library_playlist: AddToLibraryPlaylistPopup,
+    add_to_library_playlist_keybinds: Vec<KeyCommand<UIAction>>,
+    song_details: SongDetailsPopup,
+    song_details_keybinds: Vec<KeyCommand<UIAction>>,
+    local_playlists: LocalPlaylistsPopup,
+    local_playlists_keybinds: Vec<KeyCommand<UIAction>>,
+    tasks: TasksPopup,
+    tasks_keybinds: Vec<KeyCommand<UIAction>>,
+    toasts: Toasts,
+    notifications_keybinds: Vec<KeyCommand<UIAction>>,
+    confirm: ConfirmDialog<ConfirmAction>,
+    confirm_keybinds: Vec<KeyCommand<UIAction>>,
+    auth_error: AuthErrorPopup,
+    auth_error_keybinds: Vec<KeyCommand<UIAction>>,
 }
 
 impl DominantKeyRouter for YoutuiWindow {
     fn dominant_keybinds_active(&self) -> bool {
-        self.help.shown
+        self.auth_error.shown
+            || self.help.shown
+            || self.command_line.shown
+            || self.add_to_library_playlist.shown
+            || self.song_details.shown
+            || self.local_playlists.shown
             || match self.context {
                 WindowContext::Browser => self.browser.dominant_keybinds_active(),
-                WindowContext::Playlist => false,
+                WindowContext::Playlist => self.playlist.dominant_keybinds_active(),
                 WindowContext::Logs => false,
+                WindowContext::Lyrics => false,
+                WindowContext::Library => false,
+                WindowContext::Charts => self.charts.dominant_keybinds_active(),
+                WindowContext::ArtistDetails => false,
+                WindowContext::Podcasts => false,
+                WindowContext::RecentlyPlayed => false,
+                WindowContext::Favorites => false,
             }
     }
 }
@@ -130,6 +275,43 @@ impl KeyDisplayer for YoutuiWindow {
                 Box::new(self.logger.get_all_keybinds().map(|kb| kb.as_displayable()))
                     as Box<dyn Iterator<Item = DisplayableCommand>>
             }
+            WindowContext::Lyrics => {
+                Box::new(self.lyrics.get_all_keybinds().map(|kb| kb.as_displayable()))
+                    as Box<dyn Iterator<Item = DisplayableCommand>>
+            }
+            WindowContext::Library => Box::new(
+                self.library
+                    .get_all_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Charts => {
+                Box::new(self.charts.get_all_keybinds().map(|kb| kb.as_displayable()))
+                    as Box<dyn Iterator<Item = DisplayableCommand>>
+            }
+            WindowContext::ArtistDetails => Box::new(
+                self.artist_details
+                    .get_all_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Podcasts => Box::new(
+                self.podcasts
+                    .get_all_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::RecentlyPlayed => Box::new(
+                self.recently_played
+                    .get_all_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Favorites => Box::new(
+                self.favorites
+                    .get_all_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
         };
         Box::new(kb.chain(cx))
     }
@@ -162,39 +344,131 @@ impl KeyDisplayer for YoutuiWindow {
                     .get_routed_global_keybinds()
                     .map(|kb| kb.as_displayable()),
             ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Lyrics => Box::new(
+                self.lyrics
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Library => Box::new(
+                self.library
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Charts => Box::new(
+                self.charts
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::ArtistDetails => Box::new(
+                self.artist_details
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Podcasts => Box::new(
+                self.podcasts
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::RecentlyPlayed => Box::new(
+                self.recently_played
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Favorites => Box::new(
+                self.favorites
+                    .get_routed_global_keybinds()
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
         };
         Box::new(kb.chain(cx))
     }
 
-    fn get_all_visible_keybinds_as_readable_iter<'a>(
+    /// Visible keybinds for the focused pane, that aren't already shown in the header.
+    /// If a key is pending, the keybinds for the current mode are returned instead.
+    fn get_context_hint_keybinds_as_readable_iter<'a>(
         &'a self,
     ) -> Box<dyn Iterator<Item = DisplayableCommand> + 'a> {
-        // Self.keybinds is incorrect
-        let kb = self
-            .keybinds
-            .iter()
-            .filter(|kb| kb.visibility != CommandVisibility::Hidden)
-            .map(|kb| kb.as_displayable());
-        let cx = match self.context {
-            // Consider if double boxing can be removed.
+        if let Some(mode) = self.get_cur_displayable_mode() {
+            return mode.displayable_commands;
+        }
+        if self.is_dominant_keybinds() {
+            return Box::new(
+                self.get_this_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            );
+        }
+        match self.context {
             WindowContext::Browser => Box::new(
                 self.browser
-                    .get_all_visible_keybinds()
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
                     .map(|kb| kb.as_displayable()),
             ) as Box<dyn Iterator<Item = DisplayableCommand>>,
             WindowContext::Playlist => Box::new(
                 self.playlist
-                    .get_all_visible_keybinds()
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
                     .map(|kb| kb.as_displayable()),
             )
                 as Box<dyn Iterator<Item = DisplayableCommand>>,
             WindowContext::Logs => Box::new(
                 self.logger
-                    .get_all_visible_keybinds()
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
                     .map(|kb| kb.as_displayable()),
             ) as Box<dyn Iterator<Item = DisplayableCommand>>,
-        };
-        Box::new(kb.chain(cx))
+            WindowContext::Lyrics => Box::new(
+                self.lyrics
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Library => Box::new(
+                self.library
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Charts => Box::new(
+                self.charts
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            ) as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::ArtistDetails => Box::new(
+                self.artist_details
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Podcasts => Box::new(
+                self.podcasts
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::RecentlyPlayed => Box::new(
+                self.recently_played
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+            WindowContext::Favorites => Box::new(
+                self.favorites
+                    .get_routed_keybinds()
+                    .filter(|kb| kb.visibility == CommandVisibility::Standard)
+                    .map(|kb| kb.as_displayable()),
+            )
+                as Box<dyn Iterator<Item = DisplayableCommand>>,
+        }
     }
 }
 
@@ -206,98 +480,436 @@ impl ActionHandler<UIAction> for YoutuiWindow {
             UIAction::Pause => self.playlist.pauseplay().await,
             UIAction::StepVolUp => self.handle_increase_volume(VOL_TICK).await,
             UIAction::StepVolDown => self.handle_increase_volume(-VOL_TICK).await,
-            UIAction::Quit => send_or_error(&self.callback_tx, AppCallback::Quit).await,
+            UIAction::Quit => self.quit().await,
             UIAction::ToggleHelp => self.toggle_help(),
             UIAction::ViewLogs => self.handle_change_context(WindowContext::Logs),
+            UIAction::ViewLibrary => self.handle_view_library().await,
+            UIAction::ViewCharts => self.handle_view_charts().await,
+            UIAction::ViewPodcasts => self.handle_view_podcasts().await,
+            UIAction::ViewLyrics => self.handle_view_lyrics().await,
+            UIAction::ViewRecentlyPlayed => {
+                self.handle_change_context(WindowContext::RecentlyPlayed)
+            }
+            UIAction::ViewFavorites => self.handle_change_context(WindowContext::Favorites),
+            UIAction::ToggleSplitLayout => self.split_layout = !self.split_layout,
+            UIAction::NewBrowserTab => self.handle_new_browser_tab(),
+            UIAction::CloseBrowserTab => self.handle_close_browser_tab(),
+            UIAction::NextBrowserTab => self
+                .handle_change_browser_tab((self.cur_browser_tab + 1) % self.browser_tab_count()),
+            UIAction::PrevBrowserTab => self.handle_change_browser_tab(
+                (self.cur_browser_tab + self.browser_tab_count() - 1) % self.browser_tab_count(),
+            ),
             UIAction::HelpUp => self.help.increment_list(-1),
             UIAction::HelpDown => self.help.increment_list(1),
+            UIAction::ShowCommandLine => self.show_command_line(),
+            UIAction::CloseCommandLine => self.command_line.close(),
+            UIAction::ExecuteCommandLine => self.execute_command_line().await,
+            UIAction::NextCompletion => self.command_line.increment_list(1),
+            UIAction::PrevCompletion => self.command_line.increment_list(-1),
+            UIAction::AddToLibraryPlaylistUp => self.add_to_library_playlist.increment_list(-1),
+            UIAction::AddToLibraryPlaylistDown => self.add_to_library_playlist.increment_list(1),
+            UIAction::ConfirmAddToLibraryPlaylist => self.confirm_add_to_library_playlist().await,
+            UIAction::CloseAddToLibraryPlaylist => self.add_to_library_playlist.close(),
+            UIAction::SongDetailsUp => self.song_details.increment_list(-1),
+            UIAction::SongDetailsDown => self.song_details.increment_list(1),
+            UIAction::CopySongDetailsField => self.copy_song_details_field(),
+            UIAction::CloseSongDetails => self.song_details.close(),
+            UIAction::ToggleLocalPlaylists => self.toggle_local_playlists(),
+            UIAction::LocalPlaylistsUp => self.local_playlists.increment_list(-1),
+            UIAction::LocalPlaylistsDown => self.local_playlists.increment_list(1),
+            UIAction::LoadLocalPlaylist => self.load_local_playlist(true).await,
+            UIAction::AppendLocalPlaylist => self.load_local_playlist(false).await,
+            UIAction::SaveLocalPlaylist => self.save_local_playlist(),
+            UIAction::DeleteLocalPlaylist => self.delete_local_playlist(),
+            UIAction::RetrySignIn => self.retry_sign_in().await,
+            UIAction::RerunSetup => send_or_error(&self.callback_tx, AppCallback::RerunSetup).await,
+            UIAction::LikeCurrentSong => self.rate_current_song(LikeStatus::Liked).await,
+            UIAction::StarCurrentSong => self.star_current_song(),
+            UIAction::DislikeCurrentSong => self.rate_current_song(LikeStatus::Disliked).await,
+            UIAction::ToggleTasks => self.toggle_tasks(),
+            UIAction::TasksUp => self.tasks.increment_list(-1),
+            UIAction::TasksDown => self.tasks.increment_list(1),
+            UIAction::CancelSelectedTask => self.cancel_selected_task().await,
+            UIAction::ToggleNotifications => self.toasts.toggle_history(),
+            UIAction::NotificationsUp => self.toasts.increment_list(-1),
+            UIAction::NotificationsDown => self.toasts.increment_list(1),
+            UIAction::ConfirmYes => {
+                if let Some(action) = self.confirm.confirm() {
+                    self.run_confirm_action(action).await
+                }
+            }
+            UIAction::ConfirmNo => self.confirm.cancel(),
+            UIAction::OpenFromUrl => self.show_open_command_line(),
+            UIAction::CopyCurrentSongUrl => self.copy_current_song_url(),
+            UIAction::GrowArtistList => self.resize_panes(|s| s.resize_artist_list(true)),
+            UIAction::ShrinkArtistList => self.resize_panes(|s| s.resize_artist_list(false)),
+            UIAction::GrowSplit => self.resize_panes(|s| s.resize_split(true)),
+            UIAction::ShrinkSplit => self.resize_panes(|s| s.resize_split(false)),
+            UIAction::UndoPlaylistEdit => {
+                if !self.playlist.undo().await {
+                    self.notify(NotifyLevel::Info, tr("Nothing to undo"));
+                }
+            }
+            UIAction::RedoPlaylistEdit => {
+                if !self.playlist.redo().await {
+                    self.notify(NotifyLevel::Info, tr("Nothing to redo"));
+                }
+            }
         }
     }
 }
 
 impl Action for UIAction {
     fn context(&self) -> std::borrow::Cow<str> {
-        match self {
+        tr(match self {
             UIAction::Next | UIAction::Prev | UIAction::StepVolUp | UIAction::StepVolDown => {
-                "Global".into()
+                "Global"
             }
-            UIAction::Quit => "Global".into(),
-            UIAction::ToggleHelp => "Global".into(),
-            UIAction::ViewLogs => "Global".into(),
-            UIAction::Pause => "Global".into(),
-            UIAction::HelpUp => "Help".into(),
-            UIAction::HelpDown => "Help".into(),
-        }
+            UIAction::Quit => "Global",
+            UIAction::ToggleHelp => "Global",
+            UIAction::ViewLogs => "Global",
+            UIAction::ViewLibrary => "Global",
+            UIAction::ViewCharts => "Global",
+            UIAction::ViewPodcasts => "Global",
+            UIAction::ViewLyrics => "Global",
+            UIAction::ViewRecentlyPlayed => "Global",
+            UIAction::ViewFavorites => "Global",
+            UIAction::ToggleSplitLayout => "Global",
+            UIAction::GrowArtistList
+            | UIAction::ShrinkArtistList
+            | UIAction::GrowSplit
+            | UIAction::ShrinkSplit => "Global",
+            UIAction::NewBrowserTab
+            | UIAction::CloseBrowserTab
+            | UIAction::NextBrowserTab
+            | UIAction::PrevBrowserTab => "Global",
+            UIAction::Pause => "Global",
+            UIAction::LikeCurrentSong
+            | UIAction::DislikeCurrentSong
+            | UIAction::StarCurrentSong => "Global",
+            UIAction::UndoPlaylistEdit | UIAction::RedoPlaylistEdit => "Global",
+            UIAction::ToggleTasks => "Global",
+            UIAction::ToggleLocalPlaylists => "Global",
+            UIAction::OpenFromUrl | UIAction::CopyCurrentSongUrl => "Global",
+            UIAction::ToggleNotifications => "Global",
+            UIAction::NotificationsUp | UIAction::NotificationsDown => "Notifications",
+            UIAction::ConfirmYes | UIAction::ConfirmNo => "Confirm",
+            UIAction::TasksUp | UIAction::TasksDown | UIAction::CancelSelectedTask => "Tasks",
+            UIAction::HelpUp => "Help",
+            UIAction::HelpDown => "Help",
+            UIAction::ShowCommandLine => "Global",
+            UIAction::CloseCommandLine
+            | UIAction::ExecuteCommandLine
+            | UIAction::NextCompletion
+            | UIAction::PrevCompletion => "Command Line",
+            UIAction::AddToLibraryPlaylistUp
+            | UIAction::AddToLibraryPlaylistDown
+            | UIAction::ConfirmAddToLibraryPlaylist
+            | UIAction::CloseAddToLibraryPlaylist => "Add To Playlist",
+            UIAction::SongDetailsUp
+            | UIAction::SongDetailsDown
+            | UIAction::CopySongDetailsField
+            | UIAction::CloseSongDetails => "Song Details",
+            UIAction::LocalPlaylistsUp
+            | UIAction::LocalPlaylistsDown
+            | UIAction::LoadLocalPlaylist
+            | UIAction::AppendLocalPlaylist
+            | UIAction::SaveLocalPlaylist
+            | UIAction::DeleteLocalPlaylist => "Local Playlists",
+            UIAction::RetrySignIn | UIAction::RerunSetup => "Sign In Failed",
+        })
+        .into()
     }
     fn describe(&self) -> std::borrow::Cow<str> {
-        match self {
-            UIAction::Quit => "Quit".into(),
-            UIAction::Prev => "Prev Song".into(),
-            UIAction::Next => "Next Song".into(),
-            UIAction::Pause => "Pause".into(),
-            UIAction::StepVolUp => "Vol Up".into(),
-            UIAction::StepVolDown => "Vol Down".into(),
-            UIAction::ToggleHelp => "Toggle Help".into(),
-            UIAction::ViewLogs => "View Logs".into(),
-            UIAction::HelpUp => "Help".into(),
-            UIAction::HelpDown => "Help".into(),
-        }
+        tr(match self {
+            UIAction::Quit => "Quit",
+            UIAction::Prev => "Prev Song",
+            UIAction::Next => "Next Song",
+            UIAction::Pause => "Pause",
+            UIAction::StepVolUp => "Vol Up",
+            UIAction::StepVolDown => "Vol Down",
+            UIAction::ToggleHelp => "Toggle Help",
+            UIAction::ViewLogs => "View Logs",
+            UIAction::ViewLibrary => "View Library",
+            UIAction::ViewCharts => "View Charts",
+            UIAction::ViewPodcasts => "View Podcasts",
+            UIAction::ViewLyrics => "View Lyrics",
+            UIAction::ViewRecentlyPlayed => "View Recently Played",
+            UIAction::ViewFavorites => "View Favorites",
+            UIAction::ToggleSplitLayout => "Toggle Split Layout",
+            UIAction::NewBrowserTab => "New Browser Tab",
+            UIAction::CloseBrowserTab => "Close Browser Tab",
+            UIAction::NextBrowserTab => "Next Browser Tab",
+            UIAction::PrevBrowserTab => "Prev Browser Tab",
+            UIAction::HelpUp => "Help",
+            UIAction::HelpDown => "Help",
+            UIAction::ShowCommandLine => "Command Line",
+            UIAction::CloseCommandLine => "Close",
+            UIAction::ExecuteCommandLine => "Execute",
+            UIAction::NextCompletion => "Next Completion",
+            UIAction::PrevCompletion => "Prev Completion",
+            UIAction::AddToLibraryPlaylistUp => "Up",
+            UIAction::AddToLibraryPlaylistDown => "Down",
+            UIAction::ConfirmAddToLibraryPlaylist => "Add To Playlist",
+            UIAction::CloseAddToLibraryPlaylist => "Close",
+            UIAction::SongDetailsUp => "Up",
+            UIAction::SongDetailsDown => "Down",
+            UIAction::CopySongDetailsField => "Copy Field",
+            UIAction::CloseSongDetails => "Close",
+            UIAction::LikeCurrentSong => "Like Current Song",
+            UIAction::StarCurrentSong => "Star Current Song",
+            UIAction::DislikeCurrentSong => "Dislike Current Song",
+            UIAction::ToggleTasks => "Toggle Tasks",
+            UIAction::TasksUp => "Up",
+            UIAction::TasksDown => "Down",
+            UIAction::CancelSelectedTask => "Cancel Task",
+            UIAction::ToggleNotifications => "Toggle Notifications",
+            UIAction::NotificationsUp => "Up",
+            UIAction::NotificationsDown => "Down",
+            UIAction::ConfirmYes => "Yes",
+            UIAction::ConfirmNo => "No",
+            UIAction::OpenFromUrl => "Open URL",
+            UIAction::CopyCurrentSongUrl => "Copy URL of Current Song",
+            UIAction::GrowArtistList => "Widen Artist List",
+            UIAction::ShrinkArtistList => "Narrow Artist List",
+            UIAction::GrowSplit => "Widen Split Browser",
+            UIAction::ShrinkSplit => "Narrow Split Browser",
+            UIAction::UndoPlaylistEdit => "Undo Playlist Edit",
+            UIAction::RedoPlaylistEdit => "Redo Playlist Edit",
+            UIAction::ToggleLocalPlaylists => "Toggle Local Playlists",
+            UIAction::LocalPlaylistsUp => "Up",
+            UIAction::LocalPlaylistsDown => "Down",
+            UIAction::LoadLocalPlaylist => "Load",
+            UIAction::AppendLocalPlaylist => "Append to Playlist",
+            UIAction::SaveLocalPlaylist => "Save Playlist",
+            UIAction::DeleteLocalPlaylist => "Delete",
+            UIAction::RetrySignIn => "Retry Sign In",
+            UIAction::RerunSetup => "Re-run Setup",
+        })
+        .into()
     }
 }
 
 impl TextHandler for YoutuiWindow {
     fn push_text(&mut self, c: char) {
+        if self.help.shown {
+            self.help.push_text(c);
+            return;
+        }
+        if self.command_line.shown {
+            self.command_line.push_text(c);
+            self.update_command_line_completions();
+            return;
+        }
+        if self.local_playlists.shown {
+            self.local_playlists.push_text(c);
+            return;
+        }
         match self.context {
             WindowContext::Browser => self.browser.push_text(c),
             WindowContext::Playlist => self.playlist.push_text(c),
             WindowContext::Logs => self.logger.push_text(c),
+            WindowContext::Lyrics => self.lyrics.push_text(c),
+            WindowContext::Library => self.library.push_text(c),
+            WindowContext::Charts => self.charts.push_text(c),
+            WindowContext::ArtistDetails => self.artist_details.push_text(c),
+            WindowContext::Podcasts => self.podcasts.push_text(c),
+            WindowContext::RecentlyPlayed => self.recently_played.push_text(c),
+            WindowContext::Favorites => self.favorites.push_text(c),
         }
     }
     fn pop_text(&mut self) {
+        if self.help.shown {
+            self.help.pop_text();
+            return;
+        }
+        if self.command_line.shown {
+            self.command_line.pop_text();
+            self.update_command_line_completions();
+            return;
+        }
+        if self.local_playlists.shown {
+            self.local_playlists.pop_text();
+            return;
+        }
         match self.context {
             WindowContext::Browser => self.browser.pop_text(),
             WindowContext::Playlist => self.playlist.pop_text(),
             WindowContext::Logs => self.logger.pop_text(),
+            WindowContext::Lyrics => self.lyrics.pop_text(),
+            WindowContext::Library => self.library.pop_text(),
+            WindowContext::Charts => self.charts.pop_text(),
+            WindowContext::ArtistDetails => self.artist_details.pop_text(),
+            WindowContext::Podcasts => self.podcasts.pop_text(),
+            WindowContext::RecentlyPlayed => self.recently_played.pop_text(),
+            WindowContext::Favorites => self.favorites.pop_text(),
         }
     }
     fn is_text_handling(&self) -> bool {
+        // Keys must reach the confirmation dialog, even if a text box is open beneath it.
+        if self.confirm.is_shown() || self.auth_error.shown {
+            return false;
+        }
+        if self.help.shown || self.command_line.shown || self.local_playlists.shown {
+            return true;
+        }
         match self.context {
             WindowContext::Browser => self.browser.is_text_handling(),
             WindowContext::Playlist => self.playlist.is_text_handling(),
             WindowContext::Logs => self.logger.is_text_handling(),
+            WindowContext::Lyrics => self.lyrics.is_text_handling(),
+            WindowContext::Library => self.library.is_text_handling(),
+            WindowContext::Charts => self.charts.is_text_handling(),
+            WindowContext::ArtistDetails => self.artist_details.is_text_handling(),
+            WindowContext::Podcasts => self.podcasts.is_text_handling(),
+            WindowContext::RecentlyPlayed => self.recently_played.is_text_handling(),
+            WindowContext::Favorites => self.favorites.is_text_handling(),
+        }
+    }
+    fn edit_text(&mut self, edit: TextEdit) -> bool {
+        if self.help.shown || self.command_line.shown || self.local_playlists.shown {
+            return false;
+        }
+        match self.context {
+            WindowContext::Browser => self.browser.edit_text(edit),
+            WindowContext::Playlist => self.playlist.edit_text(edit),
+            WindowContext::Logs => self.logger.edit_text(edit),
+            WindowContext::Lyrics => self.lyrics.edit_text(edit),
+            WindowContext::Library => self.library.edit_text(edit),
+            WindowContext::Charts => self.charts.edit_text(edit),
+            WindowContext::ArtistDetails => self.artist_details.edit_text(edit),
+            WindowContext::Podcasts => self.podcasts.edit_text(edit),
+            WindowContext::RecentlyPlayed => self.recently_played.edit_text(edit),
+            WindowContext::Favorites => self.favorites.edit_text(edit),
         }
     }
     fn take_text(&mut self) -> String {
+        if self.help.shown {
+            return self.help.take_text();
+        }
+        if self.command_line.shown {
+            return self.command_line.take_text();
+        }
+        if self.local_playlists.shown {
+            return self.local_playlists.take_text();
+        }
         match self.context {
             WindowContext::Browser => self.browser.take_text(),
             WindowContext::Playlist => self.playlist.take_text(),
             WindowContext::Logs => self.logger.take_text(),
+            WindowContext::Lyrics => self.lyrics.take_text(),
+            WindowContext::Library => self.library.take_text(),
+            WindowContext::Charts => self.charts.take_text(),
+            WindowContext::ArtistDetails => self.artist_details.take_text(),
+            WindowContext::Podcasts => self.podcasts.take_text(),
+            WindowContext::RecentlyPlayed => self.recently_played.take_text(),
+            WindowContext::Favorites => self.favorites.take_text(),
         }
     }
     fn replace_text(&mut self, text: String) {
+        if self.help.shown {
+            self.help.replace_text(text);
+            return;
+        }
+        if self.command_line.shown {
+            self.command_line.replace_text(text);
+            return;
+        }
+        if self.local_playlists.shown {
+            self.local_playlists.replace_text(text);
+            return;
+        }
         match self.context {
             WindowContext::Browser => self.browser.replace_text(text),
             WindowContext::Playlist => self.playlist.replace_text(text),
             WindowContext::Logs => self.logger.replace_text(text),
+            WindowContext::Lyrics => self.lyrics.replace_text(text),
+            WindowContext::Library => self.library.replace_text(text),
+            WindowContext::Charts => self.charts.replace_text(text),
+            WindowContext::ArtistDetails => self.artist_details.replace_text(text),
+            WindowContext::Podcasts => self.podcasts.replace_text(text),
+            WindowContext::RecentlyPlayed => self.recently_played.replace_text(text),
+            WindowContext::Favorites => self.favorites.replace_text(text),
         }
     }
 }
 
+/// Every keybind in the app with its default keys, for documentation outside the app.
+pub fn get_default_help_entries() -> Vec<HelpEntry> {
+    // The window is only used to list its keybinds, so callbacks are never received.
+    let (callback_tx, _) = mpsc::channel(1);
+    YoutuiWindow::new(
+        callback_tx,
+        Default::default(),
+        Default::default(),
+        None,
+        Default::default(),
+    )
+    .get_help_entries()
+}
+
 impl YoutuiWindow {
-    pub fn new(callback_tx: mpsc::Sender<AppCallback>) -> YoutuiWindow {
+    pub fn new(
+        callback_tx: mpsc::Sender<AppCallback>,
+        album_art_position: AlbumArtPosition,
+        pane_sizes: PaneSizes,
+        status_format: Option<StatusFormat>,
+        events: EventBus,
+    ) -> YoutuiWindow {
+        let mut browser = Browser::new(callback_tx.clone());
+        browser.artist_list_width = pane_sizes.artist_list_width;
         // TODO: derive default
         YoutuiWindow {
             context: WindowContext::Browser,
             prev_context: WindowContext::Browser,
-            playlist: Playlist::new(callback_tx.clone()),
-            browser: Browser::new(callback_tx.clone()),
+            playlist: Playlist::new(callback_tx.clone(), events),
+            browser,
+            browser_tabs: Vec::new(),
+            cur_browser_tab: 0,
+            split_layout: false,
+            pane_sizes,
+            status_format,
+            cast_enabled: false,
+            cast_devices: Vec::new(),
             logger: Logger::new(callback_tx.clone()),
+            library: Library::new(callback_tx.clone()),
+            charts: Charts::new(callback_tx.clone()),
+            artist_details: ArtistDetails::new(callback_tx.clone()),
+            podcasts: Podcasts::new(callback_tx.clone()),
+            lyrics: Lyrics::new(callback_tx.clone()),
+            recently_played: RecentlyPlayed::new(callback_tx.clone()),
+            favorites: Favorites::new(callback_tx.clone()),
+            album_art: AlbumArt::new(callback_tx.clone(), album_art_position),
             keybinds: global_keybinds(),
             key_stack: Vec::new(),
             help: Default::default(),
+            command_line: Default::default(),
+            command_line_keybinds: command_line_keybinds(),
+            add_to_library_playlist: Default::default(),
+            add_to_library_playlist_keybinds: add_to_library_playlist_keybinds(),
+            song_details: Default::default(),
+            song_details_keybinds: song_details_keybinds(),
+            local_playlists: LocalPlaylistsPopup::load(),
+            local_playlists_keybinds: local_playlists_keybinds(),
+            tasks: Default::default(),
+            tasks_keybinds: tasks_keybinds(),
+            toasts: Default::default(),
+            notifications_keybinds: notifications_keybinds(),
+            confirm: Default::default(),
+            confirm_keybinds: confirm_keybinds(),
+            auth_error: Default::default(),
+            auth_error_keybinds: auth_error_keybinds(),
             callback_tx,
         }
     }
+    pub fn set_cast_enabled(&mut self, enabled: bool) {
+        self.cast_enabled = enabled;
+    }
+    /// Restore the browser and queue to where they were when the app was last closed.
+    pub fn restore_session(&mut self) {
+        self.browser.restore_session();
+        self.playlist.restore_session();
+    }
     // Splitting out event types removes one layer of indentation.
     pub async fn handle_event(&mut self, event: crossterm::event::Event) {
         match event {
@@ -306,8 +918,21 @@ impl YoutuiWindow {
             other => tracing::warn!("Received unimplemented {:?} event", other),
         }
     }
-    pub async fn handle_tick(&mut self) {
+    /// Returns true if the tick changed anything drawn, other than animations.
+    pub async fn handle_tick(&mut self) -> bool {
+        advance_spinner();
+        advance_jump_flash();
+        let toasts_expired = self.toasts.expire(Instant::now());
         self.playlist.handle_tick().await;
+        self.update_album_art_song().await;
+        // Lyrics only follow the playing song while they are displayed.
+        if let WindowContext::Lyrics = self.context {
+            self.update_lyrics_song().await;
+        }
+        // The Logs pane shows messages as they are captured.
+        let logs_shown = matches!(self.context, WindowContext::Logs);
+        let new_log_records = logger::take_new_log_records();
+        toasts_expired || logs_shown || new_log_records
     }
     async fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) {
         if self.handle_text_entry(key_event) {
@@ -321,13 +946,75 @@ impl YoutuiWindow {
     }
     // XXX: Should not be here, but required for now due to callback routing.
     pub async fn handle_api_error(&mut self, e: Error) {
-        send_or_error(&self.callback_tx, AppCallback::HandleApiError(e)).await;
+        // Credentials can be fixed without restarting the app, so these errors aren't fatal.
+        if e.is_auth_error() || self.auth_error.retrying {
+            self.auth_error.open(e.to_string());
+        } else if self.auth_error.shown {
+            // Requests sent before the credentials failed are expected to fail too.
+            warn!("Received error <{e}> while signed out");
+        } else {
+            send_or_error(&self.callback_tx, AppCallback::HandleApiError(e)).await;
+        }
+    }
+    pub fn handle_api_reinitialised(&mut self) {
+        if self.auth_error.shown {
+            self.auth_error.close();
+            self.notify(NotifyLevel::Info, tr("Signed in to YouTube Music"));
+        }
+    }
+    /// Load the credentials from the config directory again, and sign in using them.
+    pub async fn retry_sign_in(&mut self) {
+        self.auth_error.retrying = true;
+        send_or_error(&self.callback_tx, AppCallback::RetrySignIn).await;
     }
     pub async fn handle_increase_volume(&mut self, inc: i8) {
         // Visually update the state first for instant feedback.
         self.increase_volume(inc);
+        if self.playlist.cast_device.is_some() {
+            // The device plays at its own volume, which is set to match.
+            let command = CastCommand::SetVolume(self.playlist.volume.0);
+            send_or_error(&self.callback_tx, AppCallback::CastCommand(command)).await;
+            return;
+        }
         send_or_error(&self.callback_tx, AppCallback::IncreaseVolume(inc)).await;
     }
+    /// Handle a command from `youtui remote` or the web remote, returning the reply to send back.
+    pub async fn handle_remote_command(&mut self, command: RemoteCommand) -> RemoteReply {
+        match command {
+            RemoteCommand::PlayPause => self.playlist.pauseplay().await,
+            RemoteCommand::Next => self.playlist.handle_next().await,
+            RemoteCommand::Prev => self.playlist.handle_previous().await,
+            RemoteCommand::Volume { step } => self.handle_increase_volume(step).await,
+            RemoteCommand::Status => return RemoteReply::Status(self.get_remote_status()),
+            RemoteCommand::Queue => return RemoteReply::Queue(self.get_remote_queue()),
+        }
+        RemoteReply::Done
+    }
+    fn get_remote_status(&self) -> RemoteStatus {
+        let state = match self.playlist.play_status {
+            PlayState::Playing(_) => "Playing",
+            PlayState::Paused(_) => "Paused",
+            PlayState::Buffering(_) => "Buffering",
+            PlayState::NotPlaying | PlayState::Stopped => "Stopped",
+        };
+        let cur_id = self.playlist.get_cur_playing_id();
+        RemoteStatus {
+            state,
+            song: cur_id
+                .and_then(|id| self.playlist.get_song_from_id(id))
+                .map(|song| remote_song(song, true)),
+            elapsed_secs: self.playlist.cur_played_secs.unwrap_or(0.0) as usize,
+            volume: self.playlist.volume.0,
+        }
+    }
+    fn get_remote_queue(&self) -> Vec<RemoteSong> {
+        let cur_id = self.playlist.get_cur_playing_id();
+        self.playlist
+            .list
+            .get_list_iter()
+            .map(|song| remote_song(song, Some(song.id) == cur_id))
+            .collect()
+    }
     pub async fn handle_done_playing(&mut self, id: ListSongID) {
         self.playlist.handle_done_playing(id).await
     }
@@ -337,6 +1024,12 @@ impl YoutuiWindow {
     pub async fn handle_set_to_playing(&mut self, id: ListSongID) {
         self.playlist.handle_set_to_playing(id)
     }
+    /// Record a song that has started playing in the recently played history.
+    pub fn handle_song_played(&mut self, id: ListSongID) {
+        if let Some(song) = self.playlist.get_song_from_id(id) {
+            self.recently_played.record(song);
+        }
+    }
     pub async fn handle_set_to_stopped(&mut self, id: ListSongID) {
         self.playlist.handle_set_to_stopped(id)
     }
@@ -351,10 +1044,84 @@ impl YoutuiWindow {
         update: DownloadProgressUpdateType,
         playlist_id: ListSongID,
     ) {
+        if let DownloadProgressUpdateType::Error(e) = &update {
+            if let Some(song) = self.playlist.get_song_from_id(playlist_id) {
+                let text = tr_format("Error downloading {}: {}", &[song.get_title(), e]);
+                self.notify(NotifyLevel::Error, text);
+            }
+        }
         self.playlist
             .handle_song_progress_update(update, playlist_id)
             .await
     }
+    pub fn handle_song_exported(&mut self, path: PathBuf) {
+        let text = tr_format("Exported {}", &[&path.display()]);
+        self.notify(NotifyLevel::Info, text);
+    }
+    pub fn handle_export_error(&mut self, title: String, e: String) {
+        let text = tr_format("Error exporting {}: {}", &[&title, &e]);
+        self.notify(NotifyLevel::Error, text);
+    }
+    pub fn handle_cast_devices_found(&mut self, devices: Vec<CastDevice>) {
+        let text = match devices.as_slice() {
+            [] => tr("No cast devices found").to_string(),
+            devices => {
+                let names: Vec<_> = devices.iter().map(|d| d.name.as_str()).collect();
+                tr_format("Cast devices: {}", &[&names.join(", ")])
+            }
+        };
+        self.notify(NotifyLevel::Info, text);
+        self.cast_devices = devices;
+    }
+    pub fn handle_cast_connected(&mut self, device: CastDevice) {
+        let text = tr_format("Casting to {}", &[&device.name]);
+        self.notify(NotifyLevel::Info, text);
+    }
+    pub fn handle_cast_error(&mut self, e: String) {
+        self.playlist.handle_cast_disconnected();
+        let text = tr_format("Error casting: {}", &[&e]);
+        self.notify(NotifyLevel::Error, text);
+    }
+    pub fn handle_cast_state(&mut self, state: CastState) {
+        self.playlist.handle_cast_state(state);
+    }
+    pub fn handle_cast_disconnected(&mut self) {
+        if self.playlist.cast_device.is_some() {
+            self.playlist.handle_cast_disconnected();
+            self.notify(NotifyLevel::Warning, tr("Disconnected from cast device"));
+        }
+    }
+    pub fn handle_task_timed_out(&mut self, category: RequestCategory) {
+        let text = tr_format("{} timed out", &[&category.describe()]);
+        self.notify(NotifyLevel::Warning, text);
+    }
+    /// A task failed and is being retried, shown in the pane waiting on it.
+    pub fn handle_task_retrying(&mut self, request: &AppRequest) {
+        match request.category() {
+            // Artist searches fill the artist list, other searches fill the results panel.
+            RequestCategory::Search => match request {
+                AppRequest::SearchArtists(_) | AppRequest::SearchArtistsContinuation(_) => {
+                    self.browser.handle_artist_search_retrying()
+                }
+                _ => self.browser.handle_search_retrying(),
+            },
+            RequestCategory::Get | RequestCategory::GetAlbumSongs => {
+                self.browser.handle_song_list_retrying()
+            }
+            RequestCategory::GetArtistDetails => {
+                self.artist_details.handle_artist_details_retrying()
+            }
+            RequestCategory::GetLibrary => self.library.handle_library_retrying(),
+            RequestCategory::GetCharts => self.charts.handle_charts_retrying(),
+            RequestCategory::GetLibraryPodcasts => self.podcasts.handle_library_podcasts_retrying(),
+            RequestCategory::GetPodcastEpisodes => self.podcasts.handle_podcast_episodes_retrying(),
+            RequestCategory::GetLyrics => self.lyrics.handle_lyrics_retrying(),
+            RequestCategory::GetLibraryPlaylists => self
+                .add_to_library_playlist
+                .handle_library_playlists_retrying(),
+            _ => (),
+        }
+    }
     pub async fn handle_replace_search_suggestions(
         &mut self,
         x: Vec<SearchSuggestion>,
@@ -362,8 +1129,77 @@ impl YoutuiWindow {
     ) {
         self.browser.handle_replace_search_suggestions(x, search);
     }
-    pub async fn handle_replace_artist_list(&mut self, x: Vec<SearchResultArtist>) {
-        self.browser.handle_replace_artist_list(x).await;
+    pub async fn handle_replace_artist_list(
+        &mut self,
+        x: Vec<SearchResultArtist>,
+        continuation: Option<String>,
+    ) {
+        self.browser
+            .handle_replace_artist_list(x, continuation)
+            .await;
+    }
+    pub fn handle_append_artist_list(
+        &mut self,
+        x: Vec<SearchResultArtist>,
+        continuation: Option<String>,
+    ) {
+        self.browser.handle_append_artist_list(x, continuation);
+    }
+    /// Apply settings from the config file after it was edited.
+    pub fn apply_live_settings(&mut self, settings: &LiveSettings) {
+        self.status_format = settings.status_format.clone();
+        if self.album_art.position != settings.album_art_position {
+            // The album art is fetched again for the next song, if it is no longer hidden.
+            self.album_art = AlbumArt::new(self.callback_tx.clone(), settings.album_art_position);
+        }
+    }
+    pub fn handle_ui_message(&mut self, msg: UIMessage) {
+        match msg {
+            UIMessage::Notify(level, text) => {
+                // Notifications are also logged, so that they are visible in the Logs pane.
+                match level {
+                    NotifyLevel::Info => info!("{text}"),
+                    NotifyLevel::Warning => warn!("{text}"),
+                    NotifyLevel::Error => error!("{text}"),
+                }
+                self.toasts.notify(level, text, Instant::now());
+            }
+        }
+    }
+    fn notify(&mut self, level: NotifyLevel, text: impl Into<String>) {
+        self.handle_ui_message(UIMessage::Notify(level, text.into()));
+    }
+    pub fn handle_replace_library_subscriptions(&mut self, channel_ids: Vec<ChannelID<'static>>) {
+        // Subscriptions are shared by all browser tabs.
+        for browser in self.browser_tabs.iter_mut() {
+            browser.handle_replace_library_subscriptions(channel_ids.clone());
+        }
+        self.browser
+            .handle_replace_library_subscriptions(channel_ids);
+    }
+    pub fn handle_library_subscriptions_error(&mut self) {
+        self.notify(
+            NotifyLevel::Warning,
+            tr("Unable to get library subscriptions"),
+        );
+    }
+    pub fn handle_artist_subscribed(&mut self, channel_id: ChannelID<'static>, subscribed: bool) {
+        for browser in self.browser_tabs.iter_mut() {
+            browser.handle_artist_subscribed(channel_id.clone(), subscribed);
+        }
+        self.browser
+            .handle_artist_subscribed(channel_id, subscribed);
+        if subscribed {
+            self.notify(NotifyLevel::Info, tr("Subscribed to artist"));
+        } else {
+            self.notify(NotifyLevel::Info, tr("Unsubscribed from artist"));
+        }
+    }
+    pub fn handle_artist_subscription_error(&mut self) {
+        self.notify(
+            NotifyLevel::Error,
+            tr("Unable to change artist subscription"),
+        );
     }
     pub fn handle_song_list_loaded(&mut self) {
         self.browser.handle_song_list_loaded();
@@ -380,91 +1216,912 @@ impl YoutuiWindow {
         album: String,
         year: String,
         artist: String,
+        album_id: AlbumID<'static>,
+        artist_id: Option<ChannelID<'static>>,
     ) {
         self.browser
-            .handle_append_song_list(song_list, album, year, artist)
+            .handle_append_song_list(song_list, album, year, artist, album_id, artist_id)
+    }
+    pub fn handle_append_playlist_song_list(&mut self, song_list: Vec<PlaylistSong>) {
+        self.browser.handle_append_playlist_song_list(song_list)
+    }
+    pub fn handle_playlist_songs_error(&mut self) {
+        self.browser.handle_playlist_songs_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get playlist songs"));
+    }
+    pub fn handle_queue_playlist_songs(&mut self, song_list: Vec<PlaylistSong>) {
+        // Playlist requires at least one song to be pushed.
+        if song_list.is_empty() {
+            return;
+        }
+        let mut list = AlbumSongsList::default();
+        list.append_raw_playlist_songs(song_list);
+        self.handle_add_songs_to_playlist(list.get_list_iter().cloned().collect())
+    }
+    pub fn handle_queue_playlist_songs_error(&mut self) {
+        self.notify(NotifyLevel::Error, tr("Unable to queue playlist"));
     }
     pub fn handle_add_songs_to_playlist(&mut self, song_list: Vec<ListSong>) {
         let _ = self.playlist.push_song_list(song_list);
     }
     pub async fn handle_add_songs_to_playlist_and_play(&mut self, song_list: Vec<ListSong>) {
-        self.playlist.reset().await;
-        let id = self.playlist.push_song_list(song_list);
-        self.playlist.play_song_id(id).await;
+        self.playlist.replace_and_play(song_list).await;
     }
     pub fn handle_songs_found(&mut self) {
         self.browser.handle_songs_found();
     }
+    pub fn handle_song_list_progress(&mut self, label: String) {
+        self.browser.handle_song_list_progress(label);
+    }
     pub fn handle_search_artist_error(&mut self) {
         self.browser.handle_search_artist_error();
+        self.notify(NotifyLevel::Error, tr("Unable to search for artists"));
     }
-    fn is_dominant_keybinds(&self) -> bool {
-        self.help.shown
+    pub fn handle_replace_library(
+        &mut self,
+        playlists: Vec<LibraryPlaylist>,
+        albums: Vec<LibraryAlbum>,
+        artists: Vec<LibraryArtist>,
+    ) {
+        self.library
+            .handle_replace_library(playlists, albums, artists);
     }
-    fn get_this_keybinds(&self) -> Box<dyn Iterator<Item = &KeyCommand<UIAction>> + '_> {
-        Box::new(if self.help.shown {
-            Box::new(self.help.keybinds.iter()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
-        } else if self.dominant_keybinds_active() {
-            Box::new(std::iter::empty()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
-        } else {
-            Box::new(self.keybinds.iter()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
-        })
+    pub fn handle_library_error(&mut self) {
+        self.library.handle_library_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get library"));
     }
-
-    async fn global_handle_key_stack(&mut self) {
-        // First handle my own keybinds, otherwise forward if our keybinds are not dominant.
-        // TODO: Remove allocation
-        match handle_key_stack(self.get_this_keybinds(), self.key_stack.clone()) {
-            KeyHandleAction::Action(a) => {
-                self.handle_action(&a).await;
-                self.key_stack.clear();
-                return;
-            }
-            KeyHandleAction::Mode => {
-                return;
-            }
-            KeyHandleAction::NoMap => {
-                if self.is_dominant_keybinds() {
-                    self.key_stack.clear();
-                    return;
-                }
-            }
-        };
-        if let KeyHandleOutcome::Mode = match self.context {
-            // TODO: Remove allocation
-            WindowContext::Browser => {
-                handle_key_stack_and_action(&mut self.browser, self.key_stack.clone()).await
-            }
-            WindowContext::Playlist => {
-                handle_key_stack_and_action(&mut self.playlist, self.key_stack.clone()).await
-            }
-            WindowContext::Logs => {
-                handle_key_stack_and_action(&mut self.logger, self.key_stack.clone()).await
-            }
-        } {
-            return;
-        } else {
-            self.key_stack.clear()
-        }
+    pub fn handle_replace_charts(&mut self, charts: ytmapi_rs::parse::Charts) {
+        self.charts.handle_replace_charts(charts);
     }
-    fn key_pending(&self) -> bool {
-        !self.key_stack.is_empty()
+    pub fn handle_charts_error(&mut self) {
+        self.charts.handle_charts_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get charts"));
     }
-    fn toggle_help(&mut self) {
-        if self.help.shown {
-            self.help.shown = false;
-        } else {
-            self.help.shown = true;
-            // Setup Help menu parameters
-            self.help.cur = 0;
-            // We have to get the keybind length this way as the help menu iterator is not ExactSized
-            self.help.len = self.get_all_visible_keybinds_as_readable_iter().count();
+    pub fn handle_replace_artist_details(&mut self, artist: ytmapi_rs::parse::ArtistParams) {
+        self.artist_details.handle_replace_artist_details(artist);
+    }
+    pub fn handle_artist_details_error(&mut self) {
+        self.artist_details.handle_artist_details_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get artist details"));
+    }
+    pub fn handle_replace_library_podcasts(&mut self, podcasts: Vec<LibraryPodcast>) {
+        self.podcasts.handle_replace_library_podcasts(podcasts);
+    }
+    pub fn handle_library_podcasts_error(&mut self) {
+        self.podcasts.handle_library_podcasts_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get library podcasts"));
+    }
+    pub fn handle_replace_podcast_episodes(&mut self, podcast: ytmapi_rs::parse::Podcast) {
+        self.podcasts.handle_replace_podcast_episodes(podcast);
+    }
+    pub fn handle_podcast_episodes_error(&mut self) {
+        self.podcasts.handle_podcast_episodes_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get podcast episodes"));
+    }
+    pub fn handle_queue_podcast_episodes(&mut self, podcast: ytmapi_rs::parse::Podcast) {
+        // Playlist requires at least one song to be pushed.
+        if podcast.episodes.is_empty() {
+            return;
+        }
+        let song_list = podcasts::episodes_to_song_list(&podcast, &podcast.episodes);
+        self.handle_add_songs_to_playlist(song_list)
+    }
+    pub fn handle_queue_podcast_episodes_error(&mut self) {
+        self.notify(NotifyLevel::Error, tr("Unable to queue podcast"));
+    }
+    pub fn handle_replace_lyrics(&mut self, lyrics: ytmapi_rs::common::browsing::Lyrics) {
+        self.lyrics.handle_replace_lyrics(lyrics);
+    }
+    pub fn handle_lyrics_error(&mut self) {
+        self.lyrics.handle_lyrics_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get lyrics"));
+    }
+    pub async fn handle_show_add_to_library_playlist(
+        &mut self,
+        video_id: VideoID<'static>,
+        title: String,
+    ) {
+        self.add_to_library_playlist.open(video_id, title);
+        // Playlists are fetched every time, as they may have been changed elsewhere.
+        send_or_error(&self.callback_tx, AppCallback::GetLibraryPlaylists).await;
+    }
+    pub fn handle_show_song_details(&mut self, details: SongDetails) {
+        self.song_details.open(details);
+    }
+    fn copy_song_details_field(&mut self) {
+        let Some(value) = self.song_details.get_selected_value() else {
+            return;
+        };
+        self.copy_to_clipboard(value.to_string());
+    }
+    fn toggle_local_playlists(&mut self) {
+        if self.local_playlists.shown {
+            self.local_playlists.close();
+        } else {
+            self.local_playlists.open();
+        }
+    }
+    /// Save the playlist under the name entered in the local playlists popup.
+    fn save_local_playlist(&mut self) {
+        let name = self.local_playlists.name.trim().to_string();
+        if name.is_empty() {
+            self.notify(
+                NotifyLevel::Info,
+                tr("Enter a name to save the playlist as"),
+            );
+            return;
+        }
+        let songs: Vec<_> = self
+            .playlist
+            .list
+            .get_list_iter()
+            .map(SavedSong::from_song)
+            .collect();
+        if songs.is_empty() {
+            self.notify(NotifyLevel::Info, tr("Playlist is empty"));
+            return;
+        }
+        match self.local_playlists.save_songs(songs) {
+            Ok(true) => self.notify(
+                NotifyLevel::Info,
+                tr_format("Replaced playlist {}", &[&name]),
+            ),
+            Ok(false) => self.notify(NotifyLevel::Info, tr_format("Saved playlist {}", &[&name])),
+            Err(e) => self.notify(
+                NotifyLevel::Error,
+                tr_format("Unable to save playlist {}: {}", &[&name, &e]),
+            ),
+        }
+    }
+    /// Replace the playlist with the selected local playlist and play it, or append to the
+    /// playlist instead.
+    async fn load_local_playlist(&mut self, replace: bool) {
+        let Some(local_playlist) = self.local_playlists.get_selected() else {
+            return;
+        };
+        // Playlist requires at least one song to be pushed.
+        if local_playlist.songs.is_empty() {
+            return;
+        }
+        let name = local_playlist.name.clone();
+        let song_list = saved_songs_to_list(&local_playlist.songs);
+        self.local_playlists.close();
+        if replace {
+            self.playlist.replace_and_play(song_list).await;
+            self.notify(NotifyLevel::Info, tr_format("Loaded playlist {}", &[&name]));
+        } else {
+            self.playlist.push_song_list(song_list);
+            self.notify(
+                NotifyLevel::Info,
+                tr_format("Appended playlist {}", &[&name]),
+            );
+        }
+    }
+    fn delete_local_playlist(&mut self) {
+        let Some(local_playlist) = self.local_playlists.get_selected() else {
+            return;
+        };
+        let name = local_playlist.name.clone();
+        self.confirm.open(
+            format!("Delete playlist {name}?"),
+            ConfirmAction::DeleteLocalPlaylist(name),
+        );
+    }
+    pub fn handle_replace_library_playlists(&mut self, playlists: Vec<LibraryPlaylist>) {
+        self.add_to_library_playlist
+            .handle_replace_library_playlists(playlists);
+    }
+    pub fn handle_library_playlists_error(&mut self) {
+        self.add_to_library_playlist
+            .handle_library_playlists_error();
+        self.notify(NotifyLevel::Error, tr("Unable to get library playlists"));
+    }
+    pub fn handle_song_added_to_library_playlist(&mut self) {
+        self.notify(NotifyLevel::Info, tr("Song added to playlist"));
+    }
+    pub fn handle_add_song_to_library_playlist_error(&mut self) {
+        self.notify(NotifyLevel::Error, tr("Unable to add song to playlist"));
+    }
+    async fn rate_current_song(&mut self, rating: LikeStatus) {
+        if let Some(id) = self.playlist.get_cur_playing_id() {
+            self.playlist.rate_song(id, rating).await;
+        }
+    }
+    pub fn handle_song_rated(&mut self, id: ListSongID, rating: LikeStatus) {
+        self.playlist.handle_song_rated(id, rating);
+    }
+    fn star_current_song(&mut self) {
+        let Some(song) = self
+            .playlist
+            .get_cur_playing_id()
+            .and_then(|id| self.playlist.get_song_from_id(id))
+        else {
+            return;
+        };
+        self.handle_toggle_star_song(song.clone());
+    }
+    /// Star the song in the local favorites, or unstar it if it was already starred.
+    pub fn handle_toggle_star_song(&mut self, song: ListSong) {
+        let message = if self.favorites.toggle_star(&song) {
+            tr_format("Starred {}", &[song.get_title()])
+        } else {
+            tr_format("Unstarred {}", &[song.get_title()])
+        };
+        self.notify(NotifyLevel::Info, message);
+    }
+    pub fn handle_rate_song_error(&mut self) {
+        self.notify(NotifyLevel::Error, tr("Unable to rate song"));
+    }
+    fn copy_current_song_url(&mut self) {
+        let Some(song) = self
+            .playlist
+            .get_cur_playing_id()
+            .and_then(|id| self.playlist.get_song_from_id(id))
+        else {
+            return;
+        };
+        let url = song.raw.get_video_id().get_url();
+        self.copy_to_clipboard(url);
+    }
+    pub fn copy_to_clipboard(&mut self, text: String) {
+        match copy_to_clipboard(&text) {
+            Ok(()) => self.notify(
+                NotifyLevel::Info,
+                tr_format("Copied {} to clipboard", &[&text]),
+            ),
+            Err(e) => self.notify(
+                NotifyLevel::Error,
+                tr_format("Unable to copy to clipboard: {}", &[&e]),
+            ),
+        }
+    }
+    pub fn open_in_browser(&mut self, url: String) {
+        match open_in_browser(&url) {
+            Ok(()) => self.notify(
+                NotifyLevel::Info,
+                tr_format("Opened {} in web browser", &[&url]),
+            ),
+            Err(e) => self.notify(
+                NotifyLevel::Error,
+                tr_format("Unable to open web browser: {}", &[&e]),
+            ),
+        }
+    }
+    async fn quit(&mut self) {
+        if let PlayState::Playing(_) = self.playlist.play_status {
+            self.confirm
+                .open("Quit while a song is playing?", ConfirmAction::Quit);
+        } else {
+            send_or_error(&self.callback_tx, AppCallback::Quit).await;
+        }
+    }
+    pub fn handle_show_confirm(&mut self, message: String, action: ConfirmAction) {
+        self.confirm.open(message, action);
+    }
+    async fn run_confirm_action(&mut self, action: ConfirmAction) {
+        match action {
+            ConfirmAction::Quit => send_or_error(&self.callback_tx, AppCallback::Quit).await,
+            ConfirmAction::ClearPlaylist => self.playlist.delete_all().await,
+            ConfirmAction::DeleteLocalPlaylist(name) => match self.local_playlists.delete(&name) {
+                Ok(()) => self.notify(
+                    NotifyLevel::Info,
+                    tr_format("Deleted playlist {}", &[&name]),
+                ),
+                Err(e) => self.notify(
+                    NotifyLevel::Error,
+                    tr_format("Unable to delete playlist {}: {}", &[&name, &e]),
+                ),
+            },
+        }
+    }
+    fn toggle_tasks(&mut self) {
+        if self.tasks.shown {
+            self.tasks.close();
+        } else {
+            self.tasks.open();
+        }
+    }
+    /// Save the current browser tab's state and the queue, to be restored next time youtui is
+    /// started.
+    pub fn save_session(&self) {
+        self.browser.save_session();
+        self.playlist.save_session();
+    }
+    pub fn is_tasks_shown(&self) -> bool {
+        self.tasks.shown
+    }
+    /// Update the task list displayed in the tasks popup.
+    pub fn set_running_tasks(&mut self, tasks: Vec<TaskSummary>) {
+        self.tasks.set_tasks(tasks);
+    }
+    async fn cancel_selected_task(&mut self) {
+        if let Some(id) = self.tasks.get_selected_id() {
+            send_or_error(&self.callback_tx, AppCallback::KillTask(id)).await;
+        }
+    }
+    async fn confirm_add_to_library_playlist(&mut self) {
+        let Some((playlist_id, video_id)) = self.add_to_library_playlist.take_selected() else {
+            return;
+        };
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::AddSongToLibraryPlaylist(playlist_id, video_id),
+        )
+        .await;
+    }
+    pub fn handle_replace_search_results(&mut self, results: SearchResultsList) {
+        self.browser.handle_replace_search_results(results);
+    }
+    pub fn handle_search_error(&mut self) {
+        self.browser.handle_search_error();
+        self.notify(NotifyLevel::Error, tr("Unable to search"));
+    }
+    pub async fn handle_album_songs(
+        &mut self,
+        song_list: Vec<SongResult>,
+        album: String,
+        year: String,
+        artist: String,
+        play: bool,
+    ) {
+        // Playlist requires at least one song to be pushed.
+        if song_list.is_empty() {
+            return;
+        }
+        let mut list = AlbumSongsList::default();
+        list.append_raw_songs(song_list, album, year, artist);
+        let song_list = list.get_list_iter().cloned().collect();
+        if play {
+            self.handle_add_songs_to_playlist_and_play(song_list).await
+        } else {
+            self.handle_add_songs_to_playlist(song_list)
+        }
+    }
+    pub fn handle_album_songs_error(&mut self) {
+        self.notify(NotifyLevel::Error, tr("Unable to get album songs"));
+    }
+    fn is_dominant_keybinds(&self) -> bool {
+        self.auth_error.shown
+            || self.help.shown
+            || self.command_line.shown
+            || self.add_to_library_playlist.shown
+            || self.song_details.shown
+            || self.local_playlists.shown
+            || self.tasks.shown
+            || self.toasts.history_shown
+            || self.confirm.is_shown()
+    }
+    fn get_this_keybinds(&self) -> Box<dyn Iterator<Item = &KeyCommand<UIAction>> + '_> {
+        Box::new(if self.confirm.is_shown() {
+            // Confirmation captures focus from everything else, including other popups.
+            Box::new(self.confirm_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.auth_error.shown {
+            Box::new(self.auth_error_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.command_line.shown {
+            Box::new(self.command_line_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.help.shown {
+            Box::new(self.help.keybinds.iter()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.add_to_library_playlist.shown {
+            Box::new(self.add_to_library_playlist_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.song_details.shown {
+            Box::new(self.song_details_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.local_playlists.shown {
+            Box::new(self.local_playlists_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.tasks.shown {
+            Box::new(self.tasks_keybinds.iter()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.toasts.history_shown {
+            Box::new(self.notifications_keybinds.iter())
+                as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else if self.dominant_keybinds_active() {
+            Box::new(std::iter::empty()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        } else {
+            Box::new(self.keybinds.iter()) as Box<dyn Iterator<Item = &KeyCommand<UIAction>>>
+        })
+    }
+
+    async fn global_handle_key_stack(&mut self) {
+        // First handle my own keybinds, otherwise forward if our keybinds are not dominant.
+        // TODO: Remove allocation
+        match handle_key_stack(self.get_this_keybinds(), self.key_stack.clone()) {
+            KeyHandleAction::Action(a) => {
+                self.handle_action(&a).await;
+                self.key_stack.clear();
+                return;
+            }
+            KeyHandleAction::Mode => {
+                return;
+            }
+            KeyHandleAction::NoMap => {
+                if self.is_dominant_keybinds() {
+                    self.key_stack.clear();
+                    return;
+                }
+            }
+        };
+        let outcome = match self.context {
+            // TODO: Remove allocation
+            WindowContext::Browser => {
+                handle_key_stack_and_action(&mut self.browser, self.key_stack.clone()).await
+            }
+            WindowContext::Playlist => {
+                handle_key_stack_and_action(&mut self.playlist, self.key_stack.clone()).await
+            }
+            WindowContext::Logs => {
+                handle_key_stack_and_action(&mut self.logger, self.key_stack.clone()).await
+            }
+            WindowContext::Lyrics => {
+                handle_key_stack_and_action(&mut self.lyrics, self.key_stack.clone()).await
+            }
+            WindowContext::Library => {
+                handle_key_stack_and_action(&mut self.library, self.key_stack.clone()).await
+            }
+            WindowContext::Charts => {
+                handle_key_stack_and_action(&mut self.charts, self.key_stack.clone()).await
+            }
+            WindowContext::ArtistDetails => {
+                handle_key_stack_and_action(&mut self.artist_details, self.key_stack.clone()).await
+            }
+            WindowContext::Podcasts => {
+                handle_key_stack_and_action(&mut self.podcasts, self.key_stack.clone()).await
+            }
+            WindowContext::RecentlyPlayed => {
+                handle_key_stack_and_action(&mut self.recently_played, self.key_stack.clone()).await
+            }
+            WindowContext::Favorites => {
+                handle_key_stack_and_action(&mut self.favorites, self.key_stack.clone()).await
+            }
+        };
+        match outcome {
+            KeyHandleOutcome::Mode => return,
+            KeyHandleOutcome::NoMap => self.handle_type_ahead(),
+            KeyHandleOutcome::Action => (),
+        }
+        self.key_stack.clear()
+    }
+    /// If an unbound letter was typed, jump the focused list or table to the next row starting
+    /// with it.
+    fn handle_type_ahead(&mut self) {
+        let [KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers,
+            ..
+        }] = self.key_stack.as_slice()
+        else {
+            return;
+        };
+        if !(modifiers.is_empty() || *modifiers == KeyModifiers::SHIFT) || !c.is_alphanumeric() {
+            return;
+        }
+        let c = *c;
+        let jumped = match self.context {
+            WindowContext::Browser => self.browser.jump_to_char(c),
+            WindowContext::Playlist => self.playlist.jump_to_char(c),
+            WindowContext::Library => self.library.jump_to_char(c),
+            WindowContext::Charts => self.charts.jump_to_char(c),
+            WindowContext::ArtistDetails => self.artist_details.jump_to_char(c),
+            WindowContext::Podcasts => self.podcasts.jump_to_char(c),
+            WindowContext::RecentlyPlayed => self.recently_played.jump_to_char(c),
+            WindowContext::Favorites => self.favorites.jump_to_char(c),
+            WindowContext::Logs | WindowContext::Lyrics => false,
+        };
+        if jumped {
+            start_jump_flash();
+        }
+    }
+    fn key_pending(&self) -> bool {
+        !self.key_stack.is_empty()
+    }
+    fn toggle_help(&mut self) {
+        if self.help.shown {
+            self.help.close();
+        } else {
+            let entries = self.get_help_entries();
+            self.help.open(entries);
+        }
+    }
+    /// Every keybind in the app, across all contexts and popups, including hidden keybinds.
+    fn get_help_entries(&self) -> Vec<HelpEntry> {
+        [
+            get_help_entries(self.keybinds.iter()),
+            get_help_entries(self.help.keybinds.iter()),
+            get_help_entries(self.command_line_keybinds.iter()),
+            get_help_entries(self.add_to_library_playlist_keybinds.iter()),
+            get_help_entries(self.song_details_keybinds.iter()),
+            get_help_entries(self.local_playlists_keybinds.iter()),
+            get_help_entries(self.tasks_keybinds.iter()),
+            get_help_entries(self.notifications_keybinds.iter()),
+            get_help_entries(self.confirm_keybinds.iter()),
+            get_help_entries(self.auth_error_keybinds.iter()),
+            get_help_entries(self.browser.get_all_keybinds()),
+            get_help_entries(self.playlist.get_all_keybinds()),
+            get_help_entries(self.logger.get_all_keybinds()),
+            get_help_entries(self.lyrics.get_all_keybinds()),
+            get_help_entries(self.library.get_all_keybinds()),
+            get_help_entries(self.charts.get_all_keybinds()),
+            get_help_entries(self.artist_details.get_all_keybinds()),
+            get_help_entries(self.podcasts.get_all_keybinds()),
+            get_help_entries(self.recently_played.get_all_keybinds()),
+            get_help_entries(self.favorites.get_all_keybinds()),
+        ]
+        .concat()
+    }
+    fn show_command_line(&mut self) {
+        self.command_line.open();
+        self.update_command_line_completions();
+    }
+    /// Open the command line with the open command pre-filled, ready for a url to be pasted.
+    fn show_open_command_line(&mut self) {
+        self.command_line.open();
+        self.command_line.replace_text("open ".into());
+        self.update_command_line_completions();
+    }
+    /// Names of every command reachable from the command line in the current context.
+    fn get_command_names(&self) -> Vec<Cow<str>> {
+        let context_descriptions = match self.context {
+            WindowContext::Browser => get_action_descriptions(self.browser.get_all_keybinds()),
+            WindowContext::Playlist => get_action_descriptions(self.playlist.get_all_keybinds()),
+            WindowContext::Logs => get_action_descriptions(self.logger.get_all_keybinds()),
+            WindowContext::Lyrics => get_action_descriptions(self.lyrics.get_all_keybinds()),
+            WindowContext::Library => get_action_descriptions(self.library.get_all_keybinds()),
+            WindowContext::Charts => get_action_descriptions(self.charts.get_all_keybinds()),
+            WindowContext::ArtistDetails => {
+                get_action_descriptions(self.artist_details.get_all_keybinds())
+            }
+            WindowContext::Podcasts => get_action_descriptions(self.podcasts.get_all_keybinds()),
+            WindowContext::RecentlyPlayed => {
+                get_action_descriptions(self.recently_played.get_all_keybinds())
+            }
+            WindowContext::Favorites => get_action_descriptions(self.favorites.get_all_keybinds()),
+        };
+        ARGUMENT_COMMANDS
+            .into_iter()
+            .map(Cow::from)
+            .chain(get_action_descriptions(Box::new(self.keybinds.iter())))
+            .chain(context_descriptions)
+            .unique()
+            .collect()
+    }
+    fn update_command_line_completions(&mut self) {
+        // Names are collected into owned Strings, as the command line is borrowed mutably.
+        let names: Vec<String> = self
+            .get_command_names()
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect();
+        self.command_line
+            .update_completions(names.iter().map(|n| n.as_str()));
+    }
+    async fn execute_command_line(&mut self) {
+        // If a completion is selected, enter accepts it rather than executing.
+        if self.command_line.completions_cur.is_some() {
+            self.command_line.accept_completion();
+            self.update_command_line_completions();
+            return;
+        }
+        let text = self.command_line.take_text();
+        self.command_line.close();
+        match parse_command(&text) {
+            Ok(ParsedCommand::SetVolume(vol)) => {
+                let inc = vol as i16 - self.playlist.volume.0 as i16;
+                self.handle_increase_volume(inc.clamp(i8::MIN as i16, i8::MAX as i16) as i8)
+                    .await
+            }
+            Ok(ParsedCommand::SearchArtist(query)) => {
+                self.handle_change_context(WindowContext::Browser);
+                send_or_error(&self.callback_tx, AppCallback::SearchArtist(query)).await;
+            }
+            Ok(ParsedCommand::OpenVideo(id)) => {
+                // There is no way to look up a single song, so find it by searching for its id.
+                self.handle_change_context(WindowContext::Browser);
+                send_or_error(
+                    &self.callback_tx,
+                    AppCallback::Search(id.get_raw().to_string(), SearchFilter::Songs),
+                )
+                .await;
+            }
+            Ok(ParsedCommand::OpenPlaylist(id)) => {
+                send_or_error(&self.callback_tx, AppCallback::ViewPlaylistSongs(id)).await;
+            }
+            Ok(ParsedCommand::Action(description)) => {
+                self.handle_action_by_description(&description).await
+            }
+            Ok(ParsedCommand::FindCastDevices) => {
+                if !self.cast_enabled {
+                    self.notify(
+                        NotifyLevel::Warning,
+                        tr("Casting is disabled, set experimental_cast in the config file"),
+                    );
+                    return;
+                }
+                send_or_error(&self.callback_tx, AppCallback::FindCastDevices).await;
+                self.notify(NotifyLevel::Info, tr("Searching for cast devices"));
+            }
+            Ok(ParsedCommand::CastTo(name)) => {
+                let name = name.to_lowercase();
+                let device = self
+                    .cast_devices
+                    .iter()
+                    .find(|d| d.name.to_lowercase().contains(&name))
+                    .cloned();
+                match device {
+                    Some(device) => self.playlist.cast_to(device).await,
+                    None => self.notify(
+                        NotifyLevel::Warning,
+                        tr("No matching cast device, run :cast to search for devices"),
+                    ),
+                }
+            }
+            Ok(ParsedCommand::StopCasting) => self.playlist.stop_casting().await,
+            Err(e) => self.notify(
+                NotifyLevel::Warning,
+                tr_format("Command line error: {}", &[&e]),
+            ),
+        }
+    }
+    /// Run the first Action with a description matching description, searching global Actions
+    /// before Actions for the current context.
+    async fn handle_action_by_description(&mut self, description: &str) {
+        let action = find_action_by_description(Box::new(self.keybinds.iter()), description);
+        if let Some(a) = &action {
+            // Boxed, as the command line is itself run by an action.
+            Box::pin(self.handle_action(a)).await;
+            return;
+        }
+        let found = match self.context {
+            WindowContext::Browser => {
+                let action =
+                    find_action_by_description(self.browser.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.browser.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Playlist => {
+                let action =
+                    find_action_by_description(self.playlist.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.playlist.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Logs => {
+                let action =
+                    find_action_by_description(self.logger.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.logger.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Lyrics => {
+                let action =
+                    find_action_by_description(self.lyrics.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.lyrics.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Library => {
+                let action =
+                    find_action_by_description(self.library.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.library.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Charts => {
+                let action =
+                    find_action_by_description(self.charts.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.charts.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::ArtistDetails => {
+                let action =
+                    find_action_by_description(self.artist_details.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.artist_details.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Podcasts => {
+                let action =
+                    find_action_by_description(self.podcasts.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.podcasts.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::RecentlyPlayed => {
+                let action = find_action_by_description(
+                    self.recently_played.get_all_keybinds(),
+                    description,
+                );
+                if let Some(a) = &action {
+                    self.recently_played.handle_action(a).await;
+                }
+                action.is_some()
+            }
+            WindowContext::Favorites => {
+                let action =
+                    find_action_by_description(self.favorites.get_all_keybinds(), description);
+                if let Some(a) = &action {
+                    self.favorites.handle_action(a).await;
+                }
+                action.is_some()
+            }
+        };
+        if !found {
+            self.notify(
+                NotifyLevel::Warning,
+                tr_format("Command line error: unknown command <{}>", &[&description]),
+            );
         }
     }
     /// Visually increment the volume, note, does not actually change the volume.
     fn increase_volume(&mut self, inc: i8) {
         self.playlist.increase_volume(inc);
     }
+    async fn handle_view_library(&mut self) {
+        self.handle_change_context(WindowContext::Library);
+        // Library is only fetched on first view, after that it must be refreshed manually.
+        if self.library.is_new() {
+            self.library.refresh().await;
+        }
+    }
+    async fn handle_view_charts(&mut self) {
+        self.handle_change_context(WindowContext::Charts);
+        // Charts are only fetched on first view, after that they must be refreshed manually.
+        if self.charts.is_new() {
+            self.charts.refresh().await;
+        }
+    }
+    async fn handle_view_podcasts(&mut self) {
+        self.handle_change_context(WindowContext::Podcasts);
+        // Saved podcasts are only fetched on first view, after that they must be refreshed manually.
+        if self.podcasts.is_new() {
+            self.podcasts.refresh().await;
+        }
+    }
+    /// Set the number of rows moved by page up and page down to the visible height of the
+    /// main pane. Only the current browser tab is updated, as it is the one that was drawn.
+    pub fn set_page_lines(&mut self, lines: usize) {
+        let lines = lines.max(1) as isize;
+        self.browser.page_lines = lines;
+        self.playlist.page_lines = lines;
+        self.library.page_lines = lines;
+        self.charts.page_lines = lines;
+        self.artist_details.page_lines = lines;
+        self.podcasts.page_lines = lines;
+        self.lyrics.page_lines = lines;
+        self.recently_played.page_lines = lines;
+        self.favorites.page_lines = lines;
+    }
+    /// Resize panes, applying the new sizes to every browser tab and saving them to config.
+    fn resize_panes(&mut self, resize: impl FnOnce(&mut PaneSizes)) {
+        resize(&mut self.pane_sizes);
+        let width = self.pane_sizes.artist_list_width;
+        for browser in self.browser_tabs.iter_mut() {
+            browser.artist_list_width = width;
+        }
+        self.browser.artist_list_width = width;
+        if let Err(e) = self.pane_sizes.save() {
+            warn!("Error <{e}> saving pane sizes to config");
+        }
+    }
+    fn browser_tab_count(&self) -> usize {
+        self.browser_tabs.len() + 1
+    }
+    /// Titles of all browser tabs in order, and the index of the current tab.
+    pub fn get_browser_tab_titles(&self) -> (Vec<Cow<str>>, usize) {
+        let mut titles: Vec<_> = self
+            .browser_tabs
+            .iter()
+            .map(|b| b.get_tab_title())
+            .collect();
+        titles.insert(self.cur_browser_tab, self.browser.get_tab_title());
+        (titles, self.cur_browser_tab)
+    }
+    fn handle_new_browser_tab(&mut self) {
+        let new_tab = self.browser_tab_count();
+        let mut browser = Browser::new(self.callback_tx.clone());
+        browser.artist_list_width = self.pane_sizes.artist_list_width;
+        self.browser_tabs.push(browser);
+        self.handle_change_browser_tab(new_tab);
+    }
+    fn handle_close_browser_tab(&mut self) {
+        // The last tab can't be closed.
+        if self.browser_tabs.is_empty() {
+            return;
+        }
+        // Show the tab to the left, or the tab to the right if closing the first tab.
+        let new_tab = self.cur_browser_tab.saturating_sub(1);
+        self.browser = self.browser_tabs.remove(new_tab);
+        self.cur_browser_tab = new_tab;
+        self.handle_change_context(WindowContext::Browser);
+    }
+    fn handle_change_browser_tab(&mut self, new_tab: usize) {
+        if new_tab >= self.browser_tab_count() {
+            return;
+        }
+        if new_tab != self.cur_browser_tab {
+            // Removing the new tab shifts the tabs after it to the left.
+            let remove_idx = if new_tab < self.cur_browser_tab {
+                new_tab
+            } else {
+                new_tab - 1
+            };
+            let insert_idx = if self.cur_browser_tab < new_tab {
+                self.cur_browser_tab
+            } else {
+                self.cur_browser_tab - 1
+            };
+            let new_browser = self.browser_tabs.remove(remove_idx);
+            let old_browser = std::mem::replace(&mut self.browser, new_browser);
+            self.browser_tabs.insert(insert_idx, old_browser);
+            self.cur_browser_tab = new_tab;
+        }
+        self.handle_change_context(WindowContext::Browser);
+    }
+    async fn handle_view_lyrics(&mut self) {
+        self.handle_change_context(WindowContext::Lyrics);
+        self.update_lyrics_song().await;
+    }
+    async fn update_lyrics_song(&mut self) {
+        let song = self.playlist.get_cur_playing_id().and_then(|id| {
+            let song = self.playlist.get_song_from_id(id)?;
+            Some(LyricsSong {
+                id,
+                video_id: song.raw.get_video_id().clone(),
+                title: song.raw.get_title().clone(),
+            })
+        });
+        self.lyrics
+            .handle_song_update(song, self.playlist.cur_played_secs)
+            .await;
+    }
+    async fn update_album_art_song(&mut self) {
+        let song = self.playlist.get_cur_playing_id().map(|id| {
+            // Largest thumbnail is used, as it is scaled to fit the panel.
+            let url = self.playlist.get_song_from_id(id).and_then(|song| {
+                song.raw
+                    .get_thumbnails()
+                    .iter()
+                    .max_by_key(|t| t.width)
+                    .map(|t| t.url.clone())
+            });
+            (id, url)
+        });
+        self.album_art.handle_song_update(song).await;
+    }
+    pub fn handle_replace_album_art(&mut self, image: image::DynamicImage, song_id: ListSongID) {
+        self.album_art.handle_replace_album_art(image, song_id);
+    }
+    pub fn handle_album_art_error(&mut self, song_id: ListSongID) {
+        self.album_art.handle_album_art_error(song_id);
+    }
+    pub async fn handle_view_artist(&mut self, channel_id: ChannelID<'static>) {
+        self.handle_change_context(WindowContext::Browser);
+        self.browser.show_artist_songs(channel_id).await;
+    }
+    pub async fn handle_view_artist_details(&mut self, channel_id: ChannelID<'static>) {
+        self.handle_change_context(WindowContext::ArtistDetails);
+        self.artist_details.show_artist(channel_id).await;
+    }
+    pub async fn handle_view_playlist_songs(&mut self, playlist_id: PlaylistID<'static>) {
+        self.handle_change_context(WindowContext::Browser);
+        self.browser.show_playlist_songs(playlist_id).await;
+    }
+    pub async fn handle_view_album(&mut self, album_id: AlbumID<'static>) {
+        self.handle_change_context(WindowContext::Browser);
+        self.browser.show_album_songs(album_id).await;
+    }
     pub fn handle_change_context(&mut self, new_context: WindowContext) {
         std::mem::swap(&mut self.context, &mut self.prev_context);
         self.context = new_context;
@@ -476,49 +2133,112 @@ impl YoutuiWindow {
     // it is gettign called every tick.
     // Consider a way to set this in the in state memory.
     fn get_cur_displayable_mode<'a>(&'a self) -> Option<DisplayableMode<'a>> {
-        if let Some(map) = get_key_subset(self.get_this_keybinds(), &self.key_stack) {
-            if let Keymap::Mode(mode) = map {
-                return Some(DisplayableMode {
-                    displayable_commands: mode.as_displayable_iter(),
-                    description: mode.describe(),
-                });
-            }
+        if let Some(Keymap::Mode(mode)) = get_key_subset(self.get_this_keybinds(), &self.key_stack)
+        {
+            return Some(DisplayableMode {
+                displayable_commands: mode.as_displayable_iter(),
+                description: mode.describe(),
+            });
         }
         match self.context {
             WindowContext::Browser => {
-                if let Some(map) =
+                if let Some(Keymap::Mode(mode)) =
                     get_key_subset(self.browser.get_routed_keybinds(), &self.key_stack)
                 {
-                    if let Keymap::Mode(mode) = map {
-                        return Some(DisplayableMode {
-                            displayable_commands: mode.as_displayable_iter(),
-                            description: mode.describe(),
-                        });
-                    }
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
                 }
             }
             WindowContext::Playlist => {
-                if let Some(map) =
+                if let Some(Keymap::Mode(mode)) =
                     get_key_subset(self.playlist.get_routed_keybinds(), &self.key_stack)
                 {
-                    if let Keymap::Mode(mode) = map {
-                        return Some(DisplayableMode {
-                            displayable_commands: mode.as_displayable_iter(),
-                            description: mode.describe(),
-                        });
-                    }
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
                 }
             }
             WindowContext::Logs => {
-                if let Some(map) =
+                if let Some(Keymap::Mode(mode)) =
                     get_key_subset(self.logger.get_routed_keybinds(), &self.key_stack)
                 {
-                    if let Keymap::Mode(mode) = map {
-                        return Some(DisplayableMode {
-                            displayable_commands: mode.as_displayable_iter(),
-                            description: mode.describe(),
-                        });
-                    }
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::Lyrics => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.lyrics.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::Library => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.library.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::Charts => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.charts.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::ArtistDetails => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.artist_details.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::Podcasts => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.podcasts.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::RecentlyPlayed => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.recently_played.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
+                }
+            }
+            WindowContext::Favorites => {
+                if let Some(Keymap::Mode(mode)) =
+                    get_key_subset(self.favorites.get_routed_keybinds(), &self.key_stack)
+                {
+                    return Some(DisplayableMode {
+                        displayable_commands: mode.as_displayable_iter(),
+                        description: mode.describe(),
+                    });
                 }
             }
         }
@@ -526,6 +2246,15 @@ impl YoutuiWindow {
     }
 }
 
+fn remote_song(song: &ListSong, playing: bool) -> RemoteSong {
+    RemoteSong {
+        title: song.raw.get_title().to_owned(),
+        artists: song.get_artists().iter().map(|a| a.to_string()).collect(),
+        album: song.get_album().to_owned(),
+        playing,
+    }
+}
+
 fn global_keybinds() -> Vec<KeyCommand<UIAction>> {
     vec![
         KeyCommand::new_from_code(KeyCode::Char('+'), UIAction::StepVolUp),
@@ -533,9 +2262,116 @@ fn global_keybinds() -> Vec<KeyCommand<UIAction>> {
         KeyCommand::new_from_code(KeyCode::Char('<'), UIAction::Prev),
         KeyCommand::new_from_code(KeyCode::Char('>'), UIAction::Next),
         KeyCommand::new_global_from_code(KeyCode::F(1), UIAction::ToggleHelp),
+        KeyCommand::new_from_code(KeyCode::Char('?'), UIAction::ToggleHelp),
         KeyCommand::new_global_from_code(KeyCode::F(10), UIAction::Quit),
+        KeyCommand::new_global_from_code(KeyCode::F(11), UIAction::ViewLyrics),
+        KeyCommand::new_global_from_code(KeyCode::F(7), UIAction::ViewPodcasts),
+        KeyCommand::new_global_from_code(KeyCode::F(8), UIAction::ViewCharts),
+        KeyCommand::new_global_from_code(KeyCode::F(9), UIAction::ViewLibrary),
         KeyCommand::new_global_from_code(KeyCode::F(12), UIAction::ViewLogs),
         KeyCommand::new_global_from_code(KeyCode::Char(' '), UIAction::Pause),
+        KeyCommand::new_from_code(KeyCode::Char(':'), UIAction::ShowCommandLine),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('s'),
+            KeyModifiers::CONTROL,
+            UIAction::ToggleSplitLayout,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('l'),
+            KeyModifiers::CONTROL,
+            UIAction::LikeCurrentSong,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('d'),
+            KeyModifiers::CONTROL,
+            UIAction::DislikeCurrentSong,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('k'),
+            KeyModifiers::CONTROL,
+            UIAction::ToggleTasks,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('p'),
+            KeyModifiers::CONTROL,
+            UIAction::ViewRecentlyPlayed,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('b'),
+            KeyModifiers::CONTROL,
+            UIAction::ViewFavorites,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('f'),
+            KeyModifiers::CONTROL,
+            UIAction::StarCurrentSong,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('n'),
+            KeyModifiers::CONTROL,
+            UIAction::ToggleNotifications,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('e'),
+            KeyModifiers::CONTROL,
+            UIAction::ToggleLocalPlaylists,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('o'),
+            KeyModifiers::CONTROL,
+            UIAction::OpenFromUrl,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('y'),
+            KeyModifiers::CONTROL,
+            UIAction::CopyCurrentSongUrl,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('t'),
+            KeyModifiers::CONTROL,
+            UIAction::NewBrowserTab,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('z'),
+            KeyModifiers::CONTROL,
+            UIAction::UndoPlaylistEdit,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL,
+            UIAction::RedoPlaylistEdit,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Right,
+            KeyModifiers::ALT,
+            UIAction::GrowArtistList,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Left,
+            KeyModifiers::ALT,
+            UIAction::ShrinkArtistList,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Right,
+            KeyModifiers::CONTROL,
+            UIAction::GrowSplit,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Left,
+            KeyModifiers::CONTROL,
+            UIAction::ShrinkSplit,
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::Char('w'),
+            KeyModifiers::CONTROL,
+            UIAction::CloseBrowserTab,
+        ),
+        KeyCommand::new_from_code(KeyCode::Tab, UIAction::NextBrowserTab),
+        KeyCommand::new_modified_from_code(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+            UIAction::PrevBrowserTab,
+        ),
         KeyCommand::new_modified_from_code(
             KeyCode::Char('c'),
             KeyModifiers::CONTROL,
@@ -551,3 +2387,87 @@ fn help_keybinds() -> Vec<KeyCommand<UIAction>> {
         KeyCommand::new_global_from_code(KeyCode::F(1), UIAction::ToggleHelp),
     ]
 }
+fn confirm_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Char('y'), UIAction::ConfirmYes),
+        KeyCommand::new_global_from_code(KeyCode::Char('n'), UIAction::ConfirmNo),
+        KeyCommand::new_hidden_from_code(KeyCode::Enter, UIAction::ConfirmYes),
+        KeyCommand::new_hidden_from_code(KeyCode::Esc, UIAction::ConfirmNo),
+    ]
+}
+fn auth_error_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Char('r'), UIAction::RetrySignIn),
+        KeyCommand::new_global_from_code(KeyCode::Char('s'), UIAction::RerunSetup),
+        KeyCommand::new_global_from_code(KeyCode::F(10), UIAction::Quit),
+    ]
+}
+fn notifications_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Esc, UIAction::ToggleNotifications),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, UIAction::NotificationsDown),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, UIAction::NotificationsUp),
+    ]
+}
+fn tasks_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, UIAction::CancelSelectedTask),
+        KeyCommand::new_global_from_code(KeyCode::Esc, UIAction::ToggleTasks),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, UIAction::TasksDown),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, UIAction::TasksUp),
+    ]
+}
+fn add_to_library_playlist_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, UIAction::ConfirmAddToLibraryPlaylist),
+        KeyCommand::new_global_from_code(KeyCode::Esc, UIAction::CloseAddToLibraryPlaylist),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, UIAction::AddToLibraryPlaylistDown),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, UIAction::AddToLibraryPlaylistUp),
+    ]
+}
+fn song_details_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, UIAction::CopySongDetailsField),
+        KeyCommand::new_global_from_code(KeyCode::Char('y'), UIAction::CopySongDetailsField),
+        KeyCommand::new_global_from_code(KeyCode::Esc, UIAction::CloseSongDetails),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, UIAction::SongDetailsDown),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, UIAction::SongDetailsUp),
+    ]
+}
+fn local_playlists_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, UIAction::LoadLocalPlaylist),
+        KeyCommand::new_global_modified_from_code(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL,
+            UIAction::AppendLocalPlaylist,
+        ),
+        KeyCommand::new_global_modified_from_code(
+            KeyCode::Char('s'),
+            KeyModifiers::CONTROL,
+            UIAction::SaveLocalPlaylist,
+        ),
+        KeyCommand::new_global_modified_from_code(
+            KeyCode::Char('d'),
+            KeyModifiers::CONTROL,
+            UIAction::DeleteLocalPlaylist,
+        ),
+        KeyCommand::new_global_from_code(KeyCode::Esc, UIAction::ToggleLocalPlaylists),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, UIAction::LocalPlaylistsDown),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, UIAction::LocalPlaylistsUp),
+    ]
+}
+fn command_line_keybinds() -> Vec<KeyCommand<UIAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, UIAction::ExecuteCommandLine),
+        KeyCommand::new_global_from_code(KeyCode::Esc, UIAction::CloseCommandLine),
+        KeyCommand::new_hidden_from_code(KeyCode::Tab, UIAction::NextCompletion),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, UIAction::NextCompletion),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, UIAction::PrevCompletion),
+        KeyCommand::new_hidden_modified_from_code(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+            UIAction::PrevCompletion,
+        ),
+    ]
+}
@@ -0,0 +1,116 @@
+use crate::app::structures::ListStatus;
+use crate::app::view::Scrollable;
+use ytmapi_rs::common::library::Playlist as LibraryPlaylist;
+use ytmapi_rs::common::PlaylistID;
+use ytmapi_rs::VideoID;
+
+/// Popup listing the user's YouTube Music playlists, to choose one to add a song to.
+pub struct AddToLibraryPlaylistPopup {
+    pub shown: bool,
+    pub state: ListStatus,
+    pub playlists: Vec<LibraryPlaylist>,
+    pub cur: usize,
+    /// Song to be added, and its title for display.
+    pub song: Option<(VideoID<'static>, String)>,
+}
+
+impl Default for AddToLibraryPlaylistPopup {
+    fn default() -> Self {
+        Self {
+            shown: false,
+            state: ListStatus::New,
+            playlists: Vec::new(),
+            cur: 0,
+            song: None,
+        }
+    }
+}
+
+impl Scrollable for AddToLibraryPlaylistPopup {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur = self
+            .cur
+            .saturating_add_signed(amount)
+            .min(self.playlists.len().saturating_sub(1));
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur
+    }
+}
+
+impl AddToLibraryPlaylistPopup {
+    /// Show the popup for a song. The caller is responsible for fetching the playlists.
+    pub fn open(&mut self, video_id: VideoID<'static>, title: String) {
+        self.shown = true;
+        self.song = Some((video_id, title));
+        self.state = ListStatus::Loading;
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+        self.song = None;
+    }
+    pub fn handle_replace_library_playlists(&mut self, playlists: Vec<LibraryPlaylist>) {
+        self.playlists = playlists;
+        self.cur = self.cur.min(self.playlists.len().saturating_sub(1));
+        self.state = ListStatus::Loaded;
+    }
+    pub fn handle_library_playlists_error(&mut self) {
+        self.state = ListStatus::Error;
+    }
+    pub fn handle_library_playlists_retrying(&mut self) {
+        self.state.set_retrying();
+    }
+    /// Close the popup, returning the selected playlist and the song to add to it.
+    pub fn take_selected(&mut self) -> Option<(PlaylistID<'static>, VideoID<'static>)> {
+        let playlist_id = self.playlists.get(self.cur)?.playlist_id.clone();
+        let (video_id, _) = self.song.take()?;
+        self.shown = false;
+        Some((playlist_id, video_id))
+    }
+}
+
+pub mod draw {
+    use super::AddToLibraryPlaylistPopup;
+    use crate::app::structures::ListStatus;
+    use crate::drawutils::{
+        centered_rect, get_spinner, highlight_style, SELECTED_BORDER_COLOUR, TEXT_COLOUR,
+    };
+    use crate::i18n::{tr, tr_format};
+    use ratatui::{
+        prelude::Rect,
+        style::Style,
+        widgets::{Block, Borders, Clear, List, ListItem, ListState},
+        Frame,
+    };
+
+    const POPUP_WIDTH: u16 = 40;
+    const POPUP_MAX_HEIGHT: u16 = 15;
+
+    pub fn draw_add_to_library_playlist(f: &mut Frame, p: &AddToLibraryPlaylistPopup, chunk: Rect) {
+        let song_title = p.song.as_ref().map(|(_, t)| t.as_str()).unwrap_or_default();
+        let title = match p.state {
+            ListStatus::Loading | ListStatus::New => {
+                tr_format("{} Loading playlists", &[&get_spinner()])
+            }
+            ListStatus::Retrying => tr_format("{} Retrying playlists", &[&get_spinner()]),
+            ListStatus::Error => tr("Error getting playlists").to_string(),
+            _ if p.playlists.is_empty() => tr("No playlists found").to_string(),
+            _ => tr_format("Add {} to", &[&song_title]),
+        };
+        let items = p
+            .playlists
+            .iter()
+            .map(|pl| ListItem::new(pl.title.as_str()).style(Style::new().fg(TEXT_COLOUR)));
+        let mut state = ListState::default().with_selected(Some(p.cur));
+        let list = List::new(items).highlight_style(highlight_style()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(title),
+        );
+        let height = (p.playlists.len() as u16 + 2).clamp(3, POPUP_MAX_HEIGHT);
+        let area = centered_rect(height, POPUP_WIDTH, chunk);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
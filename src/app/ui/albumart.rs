@@ -0,0 +1,158 @@
+use crate::app::{structures::ListSongID, ui::AppCallback};
+use crate::config::AlbumArtPosition;
+use crate::core::send_or_error;
+use image::DynamicImage;
+use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
+use tokio::sync::mpsc::Sender;
+
+// Terminal cells are roughly twice as tall as they are wide, so this is close to square.
+pub const ALBUM_ART_WIDTH: u16 = 24;
+pub const ALBUM_ART_HEIGHT: u16 = 12;
+// Used for the halfblock fallback if the font size can't be queried from the terminal.
+const DEFAULT_FONT_SIZE: (u16, u16) = (8, 16);
+
+/// Thumbnail for the currently playing song, drawn in a corner of the main window.
+pub struct AlbumArt {
+    ui_tx: Sender<AppCallback>,
+    pub position: AlbumArtPosition,
+    /// Song the album art has been requested for.
+    pub song_id: Option<ListSongID>,
+    pub image: Option<DynamicImage>,
+}
+
+/// Terminal graphics protocol state, this is encoded from the image when first drawn.
+pub struct AlbumArtState {
+    picker: Picker,
+    protocol: Option<(ListSongID, Box<dyn StatefulProtocol>)>,
+}
+
+impl AlbumArt {
+    pub fn new(ui_tx: Sender<AppCallback>, position: AlbumArtPosition) -> Self {
+        Self {
+            ui_tx,
+            position,
+            song_id: None,
+            image: None,
+        }
+    }
+    /// Update the currently playing song and the url of its largest thumbnail, fetching the
+    /// album art if the song has changed.
+    pub async fn handle_song_update(&mut self, song: Option<(ListSongID, Option<String>)>) {
+        if self.position == AlbumArtPosition::Hidden {
+            return;
+        }
+        let song_id = song.as_ref().map(|(id, _)| *id);
+        if song_id == self.song_id {
+            return;
+        }
+        self.song_id = song_id;
+        self.image = None;
+        let Some((id, Some(url))) = song else {
+            return;
+        };
+        send_or_error(&self.ui_tx, AppCallback::GetAlbumArt(url, id)).await;
+    }
+    pub fn handle_replace_album_art(&mut self, image: DynamicImage, song_id: ListSongID) {
+        if self.song_id != Some(song_id) {
+            return;
+        }
+        self.image = Some(image);
+    }
+    pub fn handle_album_art_error(&mut self, song_id: ListSongID) {
+        tracing::warn!("Unable to display album art for song {:?}", song_id);
+    }
+}
+
+impl AlbumArtState {
+    /// Detect the best graphics protocol supported by the terminal, falling back to unicode
+    /// halfblocks. Must be called after entering the alternate screen.
+    pub fn new() -> Self {
+        let mut picker = query_picker();
+        let protocol_type = picker.guess_protocol();
+        tracing::info!("Using {:?} protocol for album art", protocol_type);
+        Self {
+            picker,
+            protocol: None,
+        }
+    }
+}
+
+/// The font size is only known from termios, so elsewhere, e.g on Windows, the default is used.
+#[cfg(unix)]
+fn query_picker() -> Picker {
+    Picker::from_termios().unwrap_or_else(|e| {
+        tracing::warn!("Error <{e}> querying terminal font size, using default");
+        Picker::new(DEFAULT_FONT_SIZE)
+    })
+}
+#[cfg(not(unix))]
+fn query_picker() -> Picker {
+    Picker::new(DEFAULT_FONT_SIZE)
+}
+
+impl Default for AlbumArtState {
+    fn default() -> Self {
+        Self {
+            picker: Picker::new(DEFAULT_FONT_SIZE),
+            protocol: None,
+        }
+    }
+}
+
+pub mod draw {
+    use super::{AlbumArt, AlbumArtState, ALBUM_ART_HEIGHT, ALBUM_ART_WIDTH};
+    use crate::config::AlbumArtPosition;
+    use crate::drawutils::DESELECTED_BORDER_COLOUR;
+    use ratatui::{
+        prelude::Rect,
+        style::Style,
+        widgets::{Block, Borders, Clear},
+        Frame,
+    };
+    use ratatui_image::StatefulImage;
+
+    pub fn draw_album_art(f: &mut Frame, a: &AlbumArt, chunk: Rect, state: &mut AlbumArtState) {
+        let (Some(song_id), Some(image)) = (a.song_id, &a.image) else {
+            return;
+        };
+        let Some(art_chunk) = corner_rect(a.position, chunk) else {
+            return;
+        };
+        // Only re-encode the image when the song changes.
+        if !matches!(&state.protocol, Some((id, _)) if *id == song_id) {
+            let protocol = state.picker.new_resize_protocol(image.clone());
+            state.protocol = Some((song_id, protocol));
+        }
+        let Some((_, protocol)) = &mut state.protocol else {
+            return;
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(DESELECTED_BORDER_COLOUR));
+        let inner = block.inner(art_chunk);
+        f.render_widget(Clear, art_chunk);
+        f.render_widget(block, art_chunk);
+        f.render_stateful_widget(StatefulImage::new(None), inner, protocol);
+    }
+
+    /// Rect in the configured corner of chunk, or None if it doesn't fit or is hidden.
+    fn corner_rect(position: AlbumArtPosition, chunk: Rect) -> Option<Rect> {
+        let width = ALBUM_ART_WIDTH + 2;
+        let height = ALBUM_ART_HEIGHT + 2;
+        if chunk.width < width * 2 || chunk.height < height {
+            return None;
+        }
+        let left = chunk.x;
+        let right = chunk.x + chunk.width - width;
+        let top = chunk.y;
+        let bottom = chunk.y + chunk.height - height;
+        let (x, y) = match position {
+            AlbumArtPosition::TopLeft => (left, top),
+            AlbumArtPosition::TopRight => (right, top),
+            AlbumArtPosition::BottomLeft => (left, bottom),
+            AlbumArtPosition::BottomRight => (right, bottom),
+            AlbumArtPosition::Hidden => return None,
+        };
+        Some(Rect::new(x, y, width, height))
+    }
+}
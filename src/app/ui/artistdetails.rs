@@ -0,0 +1,536 @@
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{AlbumSongsList, ListSong, ListStatus, Percentage},
+    ui::{AppCallback, WindowContext},
+    view::{
+        get_table_column_texts, BasicConstraint, DrawableMut, Loadable, Scrollable, TableItem,
+        TableView, TypeAheadJump,
+    },
+    YoutuiMutableState,
+};
+use crate::core::send_or_error;
+use crate::i18n::tr;
+use crossterm::event::KeyCode;
+use draw::draw_artist_details;
+use ratatui::{prelude::Rect, Frame};
+use std::{borrow::Cow, fmt::Display, rc::Rc};
+use tokio::sync::mpsc::Sender;
+use ytmapi_rs::{
+    common::{youtuberesult::YoutubeResult, AlbumID, YoutubeID},
+    parse::{AlbumResult, ArtistParams, RelatedResult, SongResult},
+    ChannelID,
+};
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArtistDetailsAction {
+    ViewBrowser,
+    ViewAllSongs,
+    Refresh,
+    NextTab,
+    PrevTab,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    PlaySelected,
+    AddSelectedToPlaylist,
+    PlayAll,
+    AddAllToPlaylist,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ArtistTab {
+    #[default]
+    TopSongs,
+    Albums,
+    Singles,
+    Related,
+}
+
+/// Description, top releases and related artists for a single artist.
+pub struct ArtistDetails {
+    ui_tx: Sender<AppCallback>,
+    pub artist: Option<ArtistParams>,
+    pub state: ListStatus,
+    pub tab: ArtistTab,
+    artist_id: Option<ChannelID<'static>>,
+    cur_selected: usize,
+    keybinds: Vec<KeyCommand<ArtistDetailsAction>>,
+    pub page_lines: isize,
+}
+
+impl ArtistTab {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::TopSongs => Self::Albums,
+            Self::Albums => Self::Singles,
+            Self::Singles => Self::Related,
+            Self::Related => Self::TopSongs,
+        }
+    }
+    pub fn prev(&self) -> Self {
+        match self {
+            Self::TopSongs => Self::Related,
+            Self::Albums => Self::TopSongs,
+            Self::Singles => Self::Albums,
+            Self::Related => Self::Singles,
+        }
+    }
+}
+
+impl Display for ArtistTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::TopSongs => "Top songs",
+            Self::Albums => "Albums",
+            Self::Singles => "Singles",
+            Self::Related => "Fans might also like",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl Action for ArtistDetailsAction {
+    fn context(&self) -> Cow<str> {
+        tr("Artist").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            Self::ViewBrowser => "View Browser",
+            Self::ViewAllSongs => "Display all songs in browser",
+            Self::Refresh => "Refresh",
+            Self::NextTab => "Next tab",
+            Self::PrevTab => "Prev tab",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::PageUp => "Page Up",
+            Self::PageDown => "Page Down",
+            Self::Home => "Top",
+            Self::End => "Bottom",
+            Self::PlaySelected => "Play / Open",
+            Self::AddSelectedToPlaylist => "Add to playlist",
+            Self::PlayAll => "Play all top songs",
+            Self::AddAllToPlaylist => "Add all top songs to playlist",
+        })
+        .into()
+    }
+}
+
+impl DrawableMut for ArtistDetails {
+    fn draw_mut_chunk(
+        &self,
+        f: &mut Frame,
+        chunk: Rect,
+        mutable_state: &mut YoutuiMutableState,
+        selected: bool,
+    ) {
+        draw_artist_details(
+            f,
+            self,
+            chunk,
+            &mut mutable_state.artist_details_state,
+            selected,
+        )
+    }
+}
+
+impl KeyRouter<ArtistDetailsAction> for ArtistDetails {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<ArtistDetailsAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<ArtistDetailsAction>> + 'a> {
+        self.get_routed_keybinds()
+    }
+}
+
+impl TextHandler for ArtistDetails {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<ArtistDetailsAction> for ArtistDetails {
+    async fn handle_action(&mut self, action: &ArtistDetailsAction) {
+        match action {
+            ArtistDetailsAction::ViewBrowser => self.handle_view_browser().await,
+            ArtistDetailsAction::ViewAllSongs => self.view_all_songs().await,
+            ArtistDetailsAction::Refresh => self.refresh().await,
+            ArtistDetailsAction::NextTab => self.change_tab(self.tab.next()),
+            ArtistDetailsAction::PrevTab => self.change_tab(self.tab.prev()),
+            ArtistDetailsAction::Up => self.increment_list(-1),
+            ArtistDetailsAction::Down => self.increment_list(1),
+            ArtistDetailsAction::PageUp => self.increment_list(-self.page_lines),
+            ArtistDetailsAction::PageDown => self.increment_list(self.page_lines),
+            ArtistDetailsAction::Home => self.increment_list(isize::MIN),
+            ArtistDetailsAction::End => self.increment_list(isize::MAX),
+            ArtistDetailsAction::PlaySelected => self.handle_selected(true).await,
+            ArtistDetailsAction::AddSelectedToPlaylist => self.handle_selected(false).await,
+            ArtistDetailsAction::PlayAll => self.queue_top_songs(true).await,
+            ArtistDetailsAction::AddAllToPlaylist => self.queue_top_songs(false).await,
+        }
+    }
+}
+
+impl ArtistDetails {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            artist: None,
+            state: ListStatus::New,
+            tab: Default::default(),
+            artist_id: None,
+            cur_selected: 0,
+            keybinds: artist_details_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Clear the current artist and request details for a new one.
+    pub async fn show_artist(&mut self, artist_id: ChannelID<'static>) {
+        self.artist = None;
+        self.artist_id = Some(artist_id);
+        self.tab = Default::default();
+        self.cur_selected = 0;
+        self.refresh().await;
+    }
+    pub async fn refresh(&mut self) {
+        let Some(artist_id) = &self.artist_id else {
+            return;
+        };
+        self.state = ListStatus::Loading;
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::GetArtistDetails(artist_id.clone()),
+        )
+        .await;
+    }
+    pub fn handle_replace_artist_details(&mut self, artist: ArtistParams) {
+        self.artist = Some(artist);
+        self.state = ListStatus::Loaded;
+        self.increment_list(0);
+    }
+    pub fn handle_artist_details_error(&mut self) {
+        self.state = ListStatus::Error;
+    }
+    pub fn handle_artist_details_retrying(&mut self) {
+        self.state.set_retrying();
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    async fn view_all_songs(&mut self) {
+        let Some(artist_id) = &self.artist_id else {
+            return;
+        };
+        send_or_error(&self.ui_tx, AppCallback::ViewArtist(artist_id.clone())).await;
+    }
+    fn change_tab(&mut self, tab: ArtistTab) {
+        self.tab = tab;
+        self.cur_selected = 0;
+    }
+    fn top_songs(&self) -> &[SongResult] {
+        self.artist
+            .as_ref()
+            .and_then(|a| a.top_releases.songs.as_ref())
+            .map(|s| s.results.as_slice())
+            .unwrap_or_default()
+    }
+    fn albums(&self) -> &[AlbumResult] {
+        let top_releases = self.artist.as_ref().map(|a| &a.top_releases);
+        match self.tab {
+            ArtistTab::Albums => top_releases.and_then(|r| r.albums.as_ref()),
+            ArtistTab::Singles => top_releases.and_then(|r| r.singles.as_ref()),
+            _ => None,
+        }
+        .map(|a| a.results.as_slice())
+        .unwrap_or_default()
+    }
+    fn related(&self) -> &[RelatedResult] {
+        self.artist
+            .as_ref()
+            .and_then(|a| a.top_releases.related.as_ref())
+            .map(|r| r.results.as_slice())
+            .unwrap_or_default()
+    }
+    fn artist_name(&self) -> &str {
+        self.artist
+            .as_ref()
+            .map(|a| a.name.as_str())
+            .unwrap_or_default()
+    }
+    /// Play or queue the selected song or album, or open the selected related artist.
+    async fn handle_selected(&mut self, play: bool) {
+        match self.tab {
+            ArtistTab::TopSongs => {
+                let Some(song) = self.top_songs().get(self.cur_selected) else {
+                    return;
+                };
+                let song_list = songs_to_list(std::slice::from_ref(song), self.artist_name());
+                let callback = if play {
+                    AppCallback::AddSongsToPlaylistAndPlay(song_list)
+                } else {
+                    AppCallback::AddSongsToPlaylist(song_list)
+                };
+                send_or_error(&self.ui_tx, callback).await;
+            }
+            ArtistTab::Albums | ArtistTab::Singles => {
+                let Some(album_id) = self
+                    .albums()
+                    .get(self.cur_selected)
+                    .and_then(|a| a.get_channel_id().as_ref())
+                    .map(|c_id| AlbumID::from_raw(c_id.get_raw().to_string()))
+                else {
+                    return;
+                };
+                send_or_error(
+                    &self.ui_tx,
+                    AppCallback::GetAlbumSongs {
+                        album_id,
+                        artist: self.artist_name().to_string(),
+                        play,
+                    },
+                )
+                .await;
+            }
+            ArtistTab::Related => {
+                // Related artists can't be queued, so only opening them is supported.
+                if !play {
+                    return;
+                }
+                let Some(artist) = self.related().get(self.cur_selected) else {
+                    return;
+                };
+                let artist_id = artist.browse_id.clone();
+                self.show_artist(artist_id).await;
+            }
+        }
+    }
+    async fn queue_top_songs(&mut self, play: bool) {
+        let songs = self.top_songs();
+        // Playlist requires at least one song to be pushed.
+        if songs.is_empty() {
+            return;
+        }
+        let song_list = songs_to_list(songs, self.artist_name());
+        let callback = if play {
+            AppCallback::AddSongsToPlaylistAndPlay(song_list)
+        } else {
+            AppCallback::AddSongsToPlaylist(song_list)
+        };
+        send_or_error(&self.ui_tx, callback).await;
+    }
+}
+
+fn songs_to_list(songs: &[SongResult], artist: &str) -> Vec<ListSong> {
+    let mut list = AlbumSongsList::default();
+    // Top songs are from different albums but share the artist.
+    let artist = Rc::new(artist.to_string());
+    let year = Rc::new(String::new());
+    for song in songs {
+        let album = song
+            .get_album()
+            .as_ref()
+            .and_then(|a| a.name.clone())
+            .unwrap_or_default();
+        list.add_raw_song(song.clone(), Rc::new(album), year.clone(), artist.clone());
+    }
+    list.get_list_iter().cloned().collect()
+}
+
+impl Loadable for ArtistDetails {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for ArtistDetails {
+    fn increment_list(&mut self, amount: isize) {
+        let len = match self.tab {
+            ArtistTab::TopSongs => self.top_songs().len(),
+            ArtistTab::Albums | ArtistTab::Singles => self.albums().len(),
+            ArtistTab::Related => self.related().len(),
+        };
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(len.saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for ArtistDetails {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for ArtistDetails {
+    fn get_title(&self) -> Cow<str> {
+        match self.state {
+            ListStatus::Loaded => format!("{} - {}", self.artist_name(), self.tab).into(),
+            ListStatus::Error => tr("Artist - Error receieved").into(),
+            _ => tr("Artist").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        match self.tab {
+            ArtistTab::TopSongs | ArtistTab::Albums | ArtistTab::Singles => &[
+                BasicConstraint::Percentage(Percentage(60)),
+                BasicConstraint::Percentage(Percentage(40)),
+            ],
+            ArtistTab::Related => &[
+                BasicConstraint::Percentage(Percentage(70)),
+                BasicConstraint::Percentage(Percentage(30)),
+            ],
+        }
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        match self.tab {
+            ArtistTab::TopSongs => Box::new(self.top_songs().iter().map(|s| {
+                Box::new(
+                    [
+                        Cow::from(s.get_title().as_str()),
+                        Cow::from(
+                            s.get_album()
+                                .as_ref()
+                                .and_then(|a| a.name.as_deref())
+                                .unwrap_or_default(),
+                        ),
+                    ]
+                    .into_iter(),
+                ) as TableItem
+            })),
+            ArtistTab::Albums | ArtistTab::Singles => Box::new(self.albums().iter().map(|a| {
+                Box::new(
+                    [
+                        Cow::from(a.get_title().as_str()),
+                        Cow::from(a.year.as_deref().unwrap_or_default()),
+                    ]
+                    .into_iter(),
+                ) as TableItem
+            })),
+            ArtistTab::Related => Box::new(self.related().iter().map(|a| {
+                Box::new(
+                    [
+                        Cow::from(a.title.as_str()),
+                        Cow::from(a.subscribers.as_str()),
+                    ]
+                    .into_iter(),
+                ) as TableItem
+            })),
+        }
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        match self.tab {
+            ArtistTab::TopSongs => Box::new(["Song", "Album"].into_iter()),
+            ArtistTab::Albums => Box::new(["Album", "Year"].into_iter()),
+            ArtistTab::Singles => Box::new(["Single", "Year"].into_iter()),
+            ArtistTab::Related => Box::new(["Artist", "Subscribers"].into_iter()),
+        }
+    }
+}
+
+fn artist_details_keybinds() -> Vec<KeyCommand<ArtistDetailsAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), ArtistDetailsAction::ViewBrowser),
+        KeyCommand::new_from_code(KeyCode::Char('s'), ArtistDetailsAction::ViewAllSongs),
+        KeyCommand::new_from_code(KeyCode::Char('r'), ArtistDetailsAction::Refresh),
+        KeyCommand::new_from_code(KeyCode::Right, ArtistDetailsAction::NextTab),
+        KeyCommand::new_from_code(KeyCode::Left, ArtistDetailsAction::PrevTab),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, ArtistDetailsAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, ArtistDetailsAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, ArtistDetailsAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, ArtistDetailsAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, ArtistDetailsAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, ArtistDetailsAction::End),
+        KeyCommand::new_action_only_mode(
+            vec![
+                (KeyCode::Enter, ArtistDetailsAction::PlaySelected),
+                (
+                    KeyCode::Char(' '),
+                    ArtistDetailsAction::AddSelectedToPlaylist,
+                ),
+                (KeyCode::Char('l'), ArtistDetailsAction::PlayAll),
+                (KeyCode::Char('L'), ArtistDetailsAction::AddAllToPlaylist),
+            ],
+            KeyCode::Enter,
+            "Play",
+        ),
+    ]
+}
+
+pub mod draw {
+    use super::ArtistDetails;
+    use crate::app::view::draw::draw_table;
+    use crate::drawutils::{DESELECTED_BORDER_COLOUR, SELECTED_BORDER_COLOUR};
+    use crate::i18n::tr;
+    use ratatui::{
+        prelude::{Constraint, Direction, Layout, Rect},
+        style::Style,
+        text::Line,
+        widgets::{Block, Borders, Paragraph, TableState, Wrap},
+        Frame,
+    };
+
+    const HEADER_HEIGHT: u16 = 7;
+
+    pub fn draw_artist_details(
+        f: &mut Frame,
+        a: &ArtistDetails,
+        chunk: Rect,
+        table_state: &mut TableState,
+        selected: bool,
+    ) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(HEADER_HEIGHT), Constraint::Min(0)])
+            .split(chunk);
+        draw_header(f, a, layout[0]);
+        draw_table(f, a, layout[1], table_state, selected);
+    }
+
+    fn draw_header(f: &mut Frame, a: &ArtistDetails, chunk: Rect) {
+        let border_colour = if a.artist.is_some() {
+            SELECTED_BORDER_COLOUR
+        } else {
+            DESELECTED_BORDER_COLOUR
+        };
+        let block = Block::default()
+            .title(tr("About"))
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(border_colour));
+        let Some(artist) = &a.artist else {
+            f.render_widget(block, chunk);
+            return;
+        };
+        let stats = [artist.subscribers.as_deref(), Some(artist.views.as_str())]
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" - ");
+        let lines = vec![Line::from(stats), Line::from(artist.description.as_str())];
+        let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+        f.render_widget(paragraph, chunk);
+    }
+}
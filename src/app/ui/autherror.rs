@@ -0,0 +1,90 @@
+/// Screen shown when YouTube Music rejects the credentials, e.g because the cookie has expired.
+/// The user can retry once the credentials have been updated, or sign in again using setup.
+#[derive(Default)]
+pub struct AuthErrorPopup {
+    pub shown: bool,
+    pub message: String,
+    /// True while the credentials are being loaded and checked again.
+    pub retrying: bool,
+}
+
+impl AuthErrorPopup {
+    pub fn open(&mut self, message: String) {
+        self.shown = true;
+        self.message = message;
+        self.retrying = false;
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+        self.retrying = false;
+    }
+}
+
+pub mod draw {
+    use super::AuthErrorPopup;
+    use crate::drawutils::{
+        centered_rect, get_spinner, SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR, TEXT_COLOUR,
+    };
+    use crate::i18n::{tr, tr_format};
+    use ratatui::{
+        prelude::Rect,
+        style::Style,
+        text::Line,
+        widgets::{Block, Borders, Clear, Paragraph, Wrap},
+        Frame,
+    };
+
+    const POPUP_WIDTH: u16 = 70;
+    const POPUP_HEIGHT: u16 = 12;
+
+    pub fn draw_auth_error(f: &mut Frame, p: &AuthErrorPopup, chunk: Rect) {
+        let text_style = Style::new().fg(TEXT_COLOUR);
+        let hint_style = Style::new().fg(TABLE_HEADINGS_COLOUR);
+        let status = if p.retrying {
+            Line::styled(tr_format("{} Signing in", &[&get_spinner()]), text_style)
+        } else {
+            Line::styled(tr("r: retry  s: re-run setup  F10: quit"), hint_style)
+        };
+        let lines = vec![
+            Line::styled(p.message.as_str(), text_style),
+            Line::default(),
+            Line::styled(
+                tr("Your YouTube Music credentials may be invalid or have expired."),
+                text_style,
+            ),
+            Line::styled(
+                tr("Update cookie.txt in the config directory and retry, or sign in again."),
+                text_style,
+            ),
+            Line::default(),
+            status,
+        ];
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(tr("Sign In Failed")),
+        );
+        let area = centered_rect(POPUP_HEIGHT, POPUP_WIDTH, chunk);
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuthErrorPopup;
+
+    #[test]
+    fn test_open_resets_retrying() {
+        let mut popup = AuthErrorPopup::default();
+        popup.open("Expired".into());
+        popup.retrying = true;
+        popup.open("Still expired".into());
+        assert!(popup.shown);
+        assert!(!popup.retrying);
+        assert_eq!(popup.message, "Still expired");
+        popup.close();
+        assert!(!popup.shown);
+    }
+}
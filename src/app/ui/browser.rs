@@ -1,48 +1,66 @@
 use self::{
     artistalbums::{
         albumsongs::{AlbumSongsPanel, ArtistSongsAction},
-        artistsearch::{ArtistAction, ArtistSearchPanel},
+        artistsearch::{ArtistAction, ArtistInputRouting, ArtistSearchPanel},
     },
     draw::draw_browser,
+    searchhistory::{merge_suggestions, SearchHistory},
+    searchresults::{SearchResultItem, SearchResultsAction, SearchResultsPanel},
+    session::BrowserSession,
 };
-use super::{AppCallback, WindowContext};
+use super::{songdetails::SongDetails, AppCallback, WindowContext};
 use crate::app::{
     component::actionhandler::{
-        Action, ActionHandler, DominantKeyRouter, KeyRouter, Suggestable, TextHandler,
+        Action, ActionHandler, DominantKeyRouter, KeyRouter, Suggestable, TextEdit, TextHandler,
     },
-    structures::{ListStatus, SongListComponent},
-    view::{DrawableMut, Scrollable},
+    server::structures::{SearchFilter, SearchResultsList},
+    structures::{AlbumSongsList, ListStatus, SongListComponent},
+    view::{DrawableMut, ListView, Scrollable, TypeAheadJump},
     YoutuiMutableState,
 };
+use crate::config::PaneSizes;
+use crate::i18n::tr;
 use crate::{app::keycommand::KeyCommand, core::send_or_error};
 use crossterm::event::KeyCode;
 use std::{borrow::Cow, mem};
 use tokio::sync::mpsc;
-use tracing::error;
+use tracing::{error, warn};
 use ytmapi_rs::{
-    common::SearchSuggestion,
-    parse::{SearchResultArtist, SongResult},
+    common::{
+        youtuberesult::YoutubeResult, AlbumID, Explicit, PlaylistID, PodcastID, SearchSuggestion,
+        YoutubeID,
+    },
+    parse::{
+        PlaylistSong, SearchResultArtist, SearchResultSong, SongResult, TopResult, TopResultType,
+    },
+    ChannelID, VideoID,
 };
 
 const PAGE_KEY_LINES: isize = 10;
 
 mod artistalbums;
 mod draw;
+mod searchhistory;
+mod searchresults;
+mod session;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum BrowserAction {
     ViewPlaylist,
     ToggleSearch,
+    OpenInBrowser,
     Left,
     Right,
     Artist(ArtistAction),
     ArtistSongs(ArtistSongsAction),
+    SearchResults(SearchResultsAction),
 }
 
 #[derive(PartialEq)]
 pub enum InputRouting {
     Artist,
     Song,
+    Results,
 }
 
 pub struct Browser {
@@ -51,40 +69,58 @@ pub struct Browser {
     pub prev_input_routing: InputRouting,
     pub artist_list: ArtistSearchPanel,
     pub album_songs_list: AlbumSongsPanel,
+    pub search_results: SearchResultsPanel,
+    /// Most recent search, used to identify the browser when multiple tabs are open.
+    pub last_query: Option<String>,
+    pub search_history: SearchHistory,
     keybinds: Vec<KeyCommand<BrowserAction>>,
+    pub page_lines: isize,
+    /// Maximum width of the artist list column, resizable by the user.
+    pub artist_list_width: u16,
+    /// Artist whose songs are shown in the songs list, if any.
+    shown_artist: Option<ChannelID<'static>>,
+    /// Selections from a restored session, applied once the requested results arrive.
+    restore_artist_id: Option<ChannelID<'static>>,
+    restore_song_id: Option<VideoID<'static>>,
 }
 
 impl InputRouting {
     pub fn left(&self) -> Self {
         match self {
-            Self::Song => Self::Artist,
+            Self::Song | Self::Results => Self::Artist,
             Self::Artist => Self::Artist,
         }
     }
-    pub fn right(&self) -> Self {
+    /// The pane to the right of the artist list depends on whether search results are shown.
+    pub fn right(&self, results_shown: bool) -> Self {
         match self {
+            Self::Artist if results_shown => Self::Results,
             Self::Artist => Self::Song,
             Self::Song => Self::Song,
+            Self::Results => Self::Results,
         }
     }
 }
 impl Action for BrowserAction {
     fn context(&self) -> Cow<str> {
-        let context = "Browser";
+        let context = tr("Browser");
         match self {
             Self::Artist(a) => format!("{context}->{}", a.context()).into(),
             Self::ArtistSongs(a) => format!("{context}->{}", a.context()).into(),
+            Self::SearchResults(a) => format!("{context}->{}", a.context()).into(),
             _ => context.into(),
         }
     }
     fn describe(&self) -> Cow<str> {
         match self {
-            Self::Left => "Left".into(),
-            Self::Right => "Right".into(),
-            Self::ViewPlaylist => "View Playlist".into(),
-            Self::ToggleSearch => "Toggle Search".into(),
+            Self::Left => tr("Left").into(),
+            Self::Right => tr("Right").into(),
+            Self::ViewPlaylist => tr("View Playlist").into(),
+            Self::ToggleSearch => tr("Toggle Search").into(),
+            Self::OpenInBrowser => tr("Open Selected in Web Browser").into(),
             Self::Artist(x) => x.describe(),
             Self::ArtistSongs(x) => x.describe(),
+            Self::SearchResults(x) => x.describe(),
         }
     }
 }
@@ -93,13 +129,13 @@ impl Suggestable for Browser {
     fn get_search_suggestions(&self) -> &[SearchSuggestion] {
         match self.input_routing {
             InputRouting::Artist => self.artist_list.get_search_suggestions(),
-            InputRouting::Song => &[],
+            InputRouting::Song | InputRouting::Results => &[],
         }
     }
     fn has_search_suggestions(&self) -> bool {
         match self.input_routing {
             InputRouting::Artist => self.artist_list.has_search_suggestions(),
-            InputRouting::Song => false,
+            InputRouting::Song | InputRouting::Results => false,
         }
     }
 }
@@ -109,9 +145,12 @@ impl TextHandler for Browser {
             InputRouting::Artist => {
                 self.artist_list.push_text(c);
                 // Should be on artist_list instead?
-                self.fetch_search_suggestions();
+                if self.artist_list.route == ArtistInputRouting::Search {
+                    self.fetch_search_suggestions();
+                }
             }
             InputRouting::Song => self.album_songs_list.push_text(c),
+            InputRouting::Results => (),
         }
     }
     fn pop_text(&mut self) {
@@ -119,27 +158,49 @@ impl TextHandler for Browser {
             InputRouting::Artist => {
                 self.artist_list.pop_text();
                 // Should be on artist_list instead?
-                self.fetch_search_suggestions();
+                if self.artist_list.route == ArtistInputRouting::Search {
+                    self.fetch_search_suggestions();
+                }
             }
             InputRouting::Song => self.album_songs_list.pop_text(),
+            InputRouting::Results => (),
+        }
+    }
+    fn edit_text(&mut self, edit: TextEdit) -> bool {
+        match self.input_routing {
+            InputRouting::Artist => {
+                let handled = self.artist_list.edit_text(edit);
+                if handled
+                    && edit.changes_text()
+                    && self.artist_list.route == ArtistInputRouting::Search
+                {
+                    self.fetch_search_suggestions();
+                }
+                handled
+            }
+            InputRouting::Song => self.album_songs_list.edit_text(edit),
+            InputRouting::Results => false,
         }
     }
     fn is_text_handling(&self) -> bool {
         match self.input_routing {
             InputRouting::Artist => self.artist_list.is_text_handling(),
             InputRouting::Song => self.album_songs_list.is_text_handling(),
+            InputRouting::Results => false,
         }
     }
     fn take_text(&mut self) -> String {
         match self.input_routing {
             InputRouting::Artist => self.artist_list.take_text(),
             InputRouting::Song => self.album_songs_list.take_text(),
+            InputRouting::Results => String::new(),
         }
     }
     fn replace_text(&mut self, text: String) {
         match self.input_routing {
             InputRouting::Artist => self.artist_list.replace_text(text),
             InputRouting::Song => self.album_songs_list.replace_text(text),
+            InputRouting::Results => (),
         }
     }
 }
@@ -158,6 +219,7 @@ impl DrawableMut for Browser {
             chunk,
             &mut mutable_state.browser_artists_state,
             &mut mutable_state.browser_album_songs_state,
+            &mut mutable_state.browser_search_results_state,
             selected,
         );
     }
@@ -170,7 +232,8 @@ impl KeyRouter<BrowserAction> for Browser {
             self.keybinds
                 .iter()
                 .chain(self.artist_list.get_all_keybinds())
-                .chain(self.album_songs_list.get_all_keybinds()),
+                .chain(self.album_songs_list.get_all_keybinds())
+                .chain(self.search_results.get_all_keybinds()),
         )
     }
     fn get_routed_keybinds<'a>(
@@ -179,12 +242,16 @@ impl KeyRouter<BrowserAction> for Browser {
         let additional_binds = match self.input_routing {
             InputRouting::Song => self.album_songs_list.get_routed_keybinds(),
             InputRouting::Artist => self.artist_list.get_routed_keybinds(),
+            InputRouting::Results => self.search_results.get_routed_keybinds(),
         };
         // TODO: Better implementation
         if self.album_songs_list.dominant_keybinds_active()
             || self.album_songs_list.dominant_keybinds_active()
         {
             additional_binds
+        } else if self.artist_list.search_popped {
+            // Search keybinds such as Right take priority over moving between panes.
+            Box::new(additional_binds.chain(self.keybinds.iter()))
         } else {
             Box::new(self.keybinds.iter().chain(additional_binds))
         }
@@ -194,13 +261,32 @@ impl ActionHandler<ArtistAction> for Browser {
     async fn handle_action(&mut self, action: &ArtistAction) {
         match action {
             ArtistAction::DisplayAlbums => self.get_songs().await,
+            ArtistAction::DisplayDetails => self.get_details().await,
+            ArtistAction::ToggleSubscribed => self.toggle_subscribed().await,
             ArtistAction::Search => self.search().await,
             ArtistAction::Up => self.artist_list.increment_list(-1),
-            ArtistAction::Down => self.artist_list.increment_list(1),
-            ArtistAction::PageUp => self.artist_list.increment_list(-10),
-            ArtistAction::PageDown => self.artist_list.increment_list(10),
+            ArtistAction::Down => self.increment_artist_list(1).await,
+            ArtistAction::PageUp => self.artist_list.increment_list(-self.page_lines),
+            ArtistAction::PageDown => self.increment_artist_list(self.page_lines).await,
+            ArtistAction::Home => self.artist_list.increment_list(isize::MIN),
+            ArtistAction::End => self.increment_artist_list(isize::MAX).await,
             ArtistAction::PrevSearchSuggestion => self.artist_list.search.increment_list(-1),
             ArtistAction::NextSearchSuggestion => self.artist_list.search.increment_list(1),
+            ArtistAction::AcceptSearchSuggestion => {
+                self.accept_search_suggestion();
+            }
+            // Tab completes a highlighted suggestion, otherwise it cycles the filter.
+            ArtistAction::NextSearchFilter => {
+                if !self.accept_search_suggestion() {
+                    self.artist_list.search.filter = self.artist_list.search.filter.next()
+                }
+            }
+            ArtistAction::PrevSearchFilter => {
+                self.artist_list.search.filter = self.artist_list.search.filter.prev()
+            }
+            ArtistAction::ToggleFilter => self.artist_list.toggle_filter(),
+            ArtistAction::ApplyFilter => self.artist_list.apply_filter(),
+            ArtistAction::ClearFilter => self.artist_list.clear_filter(),
         }
     }
 }
@@ -211,12 +297,29 @@ impl ActionHandler<ArtistSongsAction> for Browser {
             ArtistSongsAction::PlaySong => self.play_song().await,
             ArtistSongsAction::PlaySongs => self.play_songs().await,
             ArtistSongsAction::AddAlbumToPlaylist => self.add_album_to_playlist().await,
+            ArtistSongsAction::PlayAll => self.play_all().await,
+            ArtistSongsAction::AddAllToPlaylist => self.add_all_to_playlist().await,
             ArtistSongsAction::AddSongToPlaylist => self.add_song_to_playlist().await,
             ArtistSongsAction::AddSongsToPlaylist => self.add_songs_to_playlist().await,
+            ArtistSongsAction::AddSongToLibraryPlaylist => {
+                self.add_song_to_library_playlist().await
+            }
+            ArtistSongsAction::ShowSongDetails => self.show_song_details().await,
+            ArtistSongsAction::StarSong => self.star_song().await,
+            ArtistSongsAction::ExportSong => self.export_song().await,
+            ArtistSongsAction::ExportAlbum => self.export_album().await,
+            ArtistSongsAction::ToggleAlbumCollapsed => {
+                self.album_songs_list.toggle_selected_album_collapsed()
+            }
+            ArtistSongsAction::ToggleAllAlbumsCollapsed => {
+                self.album_songs_list.toggle_all_albums_collapsed()
+            }
             ArtistSongsAction::Up => self.album_songs_list.increment_list(-1),
             ArtistSongsAction::Down => self.album_songs_list.increment_list(1),
-            ArtistSongsAction::PageUp => self.album_songs_list.increment_list(-PAGE_KEY_LINES),
-            ArtistSongsAction::PageDown => self.album_songs_list.increment_list(PAGE_KEY_LINES),
+            ArtistSongsAction::PageUp => self.album_songs_list.increment_list(-self.page_lines),
+            ArtistSongsAction::PageDown => self.album_songs_list.increment_list(self.page_lines),
+            ArtistSongsAction::Home => self.album_songs_list.increment_list(isize::MIN),
+            ArtistSongsAction::End => self.album_songs_list.increment_list(isize::MAX),
             ArtistSongsAction::PopSort => self.album_songs_list.handle_pop_sort(),
             ArtistSongsAction::CloseSort => self.album_songs_list.close_sort(),
             ArtistSongsAction::ClearSort => self.album_songs_list.handle_clear_sort(),
@@ -224,17 +327,33 @@ impl ActionHandler<ArtistSongsAction> for Browser {
             ArtistSongsAction::SortDown => self.album_songs_list.handle_sort_down(),
             ArtistSongsAction::SortSelectedAsc => self.album_songs_list.handle_sort_cur_asc(),
             ArtistSongsAction::SortSelectedDesc => self.album_songs_list.handle_sort_cur_desc(),
+            ArtistSongsAction::ToggleSortSelected => self.album_songs_list.handle_toggle_sort_cur(),
             ArtistSongsAction::ToggleFilter => self.album_songs_list.toggle_filter(),
             ArtistSongsAction::ApplyFilter => self.album_songs_list.apply_filter(),
             ArtistSongsAction::ClearFilter => self.album_songs_list.clear_filter(),
         }
     }
 }
+impl ActionHandler<SearchResultsAction> for Browser {
+    async fn handle_action(&mut self, action: &SearchResultsAction) {
+        match action {
+            SearchResultsAction::Play => self.queue_search_result(true).await,
+            SearchResultsAction::AddToPlaylist => self.queue_search_result(false).await,
+            SearchResultsAction::Up => self.search_results.increment_list(-1),
+            SearchResultsAction::Down => self.search_results.increment_list(1),
+            SearchResultsAction::PageUp => self.search_results.increment_list(-self.page_lines),
+            SearchResultsAction::PageDown => self.search_results.increment_list(self.page_lines),
+            SearchResultsAction::Home => self.search_results.increment_list(isize::MIN),
+            SearchResultsAction::End => self.search_results.increment_list(isize::MAX),
+        }
+    }
+}
 impl ActionHandler<BrowserAction> for Browser {
     async fn handle_action(&mut self, action: &BrowserAction) {
         match action {
             BrowserAction::ArtistSongs(a) => self.handle_action(a).await,
             BrowserAction::Artist(a) => self.handle_action(a).await,
+            BrowserAction::SearchResults(a) => self.handle_action(a).await,
             BrowserAction::Left => self.left(),
             BrowserAction::Right => self.right(),
             BrowserAction::ViewPlaylist => {
@@ -245,6 +364,7 @@ impl ActionHandler<BrowserAction> for Browser {
                 .await
             }
             BrowserAction::ToggleSearch => self.handle_toggle_search(),
+            BrowserAction::OpenInBrowser => self.open_selected_in_browser().await,
         }
     }
 }
@@ -252,7 +372,7 @@ impl ActionHandler<BrowserAction> for Browser {
 impl DominantKeyRouter for Browser {
     fn dominant_keybinds_active(&self) -> bool {
         match self.input_routing {
-            InputRouting::Artist => false,
+            InputRouting::Artist | InputRouting::Results => false,
             InputRouting::Song => self.album_songs_list.dominant_keybinds_active(),
         }
     }
@@ -264,9 +384,81 @@ impl Browser {
             callback_tx: ui_tx,
             artist_list: ArtistSearchPanel::new(),
             album_songs_list: AlbumSongsPanel::new(),
+            search_results: SearchResultsPanel::new(),
+            last_query: None,
+            search_history: SearchHistory::load(),
             input_routing: InputRouting::Artist,
             prev_input_routing: InputRouting::Artist,
             keybinds: browser_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+            artist_list_width: PaneSizes::default().artist_list_width,
+            shown_artist: None,
+            restore_artist_id: None,
+            restore_song_id: None,
+        }
+    }
+    /// Save the state of the browser to the data directory, to be restored next time youtui is
+    /// started.
+    pub fn save_session(&self) {
+        // Only artist searches are restored.
+        let search = if self.search_results.shown {
+            None
+        } else {
+            self.last_query.clone()
+        };
+        let song_id = self.shown_artist.as_ref().and_then(|_| {
+            self.album_songs_list
+                .get_song_from_idx(self.album_songs_list.get_selected_item())
+                .map(|s| s.raw.get_video_id().get_raw().to_string())
+        });
+        let session = BrowserSession {
+            search,
+            artist_id: self.shown_artist.as_ref().map(|a| a.get_raw().to_string()),
+            song_id,
+        };
+        if let Err(e) = session.save() {
+            warn!("Error <{e}> saving browser session");
+        }
+    }
+    /// Request the search and artist songs saved from the previous session again, and select the
+    /// previously selected artist and song once they arrive.
+    pub fn restore_session(&mut self) {
+        let Some(BrowserSession {
+            search,
+            artist_id,
+            song_id,
+        }) = BrowserSession::load()
+        else {
+            return;
+        };
+        let artist_id = artist_id.map(ChannelID::from_raw);
+        if let Some(search) = search {
+            self.last_query = Some(search.clone());
+            if let Err(e) = self
+                .callback_tx
+                .try_send(AppCallback::Search(search, SearchFilter::Artists))
+            {
+                error!("Error <{e}> recieved sending message")
+            };
+            self.restore_artist_id = artist_id.clone();
+        }
+        if let Some(artist_id) = artist_id {
+            self.input_routing = InputRouting::Song;
+            self.album_songs_list.group_by_album = true;
+            self.shown_artist = Some(artist_id.clone());
+            if let Err(e) = self
+                .callback_tx
+                .try_send(AppCallback::GetArtistSongs(artist_id))
+            {
+                error!("Error <{e}> recieved sending message")
+            };
+            self.restore_song_id = song_id.map(VideoID::from_raw);
+        }
+    }
+    pub fn get_tab_title(&self) -> Cow<str> {
+        match &self.last_query {
+            Some(query) => query.as_str().into(),
+            None => tr("New tab").into(),
         }
     }
     fn left(&mut self) {
@@ -275,24 +467,31 @@ impl Browser {
     }
     fn right(&mut self) {
         // Doesn't consider previous routing.
-        self.input_routing = self.input_routing.right();
+        self.input_routing = self.input_routing.right(self.search_results.shown);
     }
     fn handle_toggle_search(&mut self) {
         if self.artist_list.search_popped {
             self.artist_list.close_search();
-            self.revert_routing();
+            // Back to the pane that was routed to before the search was opened.
+            mem::swap(&mut self.input_routing, &mut self.prev_input_routing);
         } else {
             self.artist_list.open_search();
-            self.change_routing(InputRouting::Artist);
+            self.prev_input_routing = mem::replace(&mut self.input_routing, InputRouting::Artist);
+            self.fetch_search_suggestions();
         }
     }
     // Ask the UI for search suggestions for the current query
     // XXX: Currently has race conditions - if list is cleared response will arrive afterwards.
     // Proposal: When recieving a message from the app validate against query string.
     fn fetch_search_suggestions(&mut self) {
+        // Matching previous searches are shown straight away, and merged with the API's
+        // suggestions when they arrive.
+        self.artist_list.search.search_suggestions = self
+            .search_history
+            .get_suggestions(&self.artist_list.search.search_contents);
+        self.artist_list.search.suggestions_cur = None;
         // No need to fetch search suggestions if contents is empty.
         if self.artist_list.search.search_contents.is_empty() {
-            self.artist_list.search.search_suggestions.clear();
             return;
         }
         if let Err(e) = self.callback_tx.try_send(AppCallback::GetSearchSuggestions(
@@ -301,7 +500,54 @@ impl Browser {
             error!("Error <{e}> recieved sending message")
         };
     }
+    /// Copy the highlighted search suggestion into the search box, returning true if there was
+    /// one.
+    fn accept_search_suggestion(&mut self) -> bool {
+        let accepted = self.artist_list.search.accept_suggestion();
+        if accepted {
+            self.fetch_search_suggestions();
+        }
+        accepted
+    }
+    /// YouTube Music url of the selected item in the focused pane.
+    fn get_selected_url(&self) -> Option<String> {
+        match self.input_routing {
+            InputRouting::Artist => self
+                .artist_list
+                .get_selected_artist()
+                .map(|a| a.browse_id.get_url()),
+            InputRouting::Song => self
+                .album_songs_list
+                .get_song_from_idx(self.album_songs_list.get_selected_item())
+                .map(|s| s.raw.get_video_id().get_url()),
+            InputRouting::Results => match self.search_results.get_selected_result()? {
+                SearchResultItem::Song(song) => Some(song.video_id.get_url()),
+                SearchResultItem::Video(video) => Some(video.video_id.get_url()),
+                SearchResultItem::Album(album) => Some(album.browse_id.get_url()),
+                SearchResultItem::Artist(artist) => Some(artist.browse_id.get_url()),
+                SearchResultItem::FeaturedPlaylist(playlist) => {
+                    Some(playlist.playlist_id.get_url())
+                }
+                SearchResultItem::CommunityPlaylist(playlist) => {
+                    Some(playlist.playlist_id.get_url())
+                }
+                SearchResultItem::Podcast(podcast) => Some(podcast.podcast_id.get_url()),
+                SearchResultItem::TopResult(result) => get_top_result_url(result),
+            },
+        }
+    }
+    async fn open_selected_in_browser(&mut self) {
+        let Some(url) = self.get_selected_url() else {
+            warn!("Selected item doesn't have a url to open");
+            return;
+        };
+        send_or_error(&self.callback_tx, AppCallback::OpenInBrowser(url)).await;
+    }
     async fn play_song(&mut self) {
+        // Playing an album's header plays the whole album.
+        if self.album_songs_list.is_album_selected() {
+            return self.play_album().await;
+        }
         // Consider how resource intensive this is as it runs in the main thread.
         let cur_song_idx = self.album_songs_list.get_selected_item();
         if let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_song_idx) {
@@ -315,11 +561,10 @@ impl Browser {
     }
     async fn play_songs(&mut self) {
         // Consider how resource intensive this is as it runs in the main thread.
-        let cur_idx = self.album_songs_list.get_selected_item();
         let song_list = self
             .album_songs_list
-            .get_filtered_list_iter()
-            .skip(cur_idx)
+            .get_songs_from_selected()
+            .into_iter()
             .cloned()
             .collect();
         send_or_error(
@@ -331,11 +576,10 @@ impl Browser {
     }
     async fn add_songs_to_playlist(&mut self) {
         // Consider how resource intensive this is as it runs in the main thread.
-        let cur_idx = self.album_songs_list.get_selected_item();
         let song_list = self
             .album_songs_list
-            .get_filtered_list_iter()
-            .skip(cur_idx)
+            .get_songs_from_selected()
+            .into_iter()
             .cloned()
             .collect();
         send_or_error(
@@ -346,6 +590,9 @@ impl Browser {
         // XXX: Do we want to indicate that song has been added to playlist?
     }
     async fn add_song_to_playlist(&mut self) {
+        if self.album_songs_list.is_album_selected() {
+            return self.add_album_to_playlist().await;
+        }
         // Consider how resource intensive this is as it runs in the main thread.
         let cur_idx = self.album_songs_list.get_selected_item();
         if let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_idx) {
@@ -357,18 +604,81 @@ impl Browser {
         }
         // XXX: Do we want to indicate that song has been added to playlist?
     }
-    async fn add_album_to_playlist(&mut self) {
-        // Consider how resource intensive this is as it runs in the main thread.
+    async fn add_song_to_library_playlist(&mut self) {
+        let cur_idx = self.album_songs_list.get_selected_item();
+        let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_idx) else {
+            return;
+        };
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::ShowAddToLibraryPlaylist(
+                cur_song.raw.get_video_id().clone(),
+                cur_song.get_title().clone(),
+            ),
+        )
+        .await;
+    }
+    async fn show_song_details(&mut self) {
         let cur_idx = self.album_songs_list.get_selected_item();
         let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_idx) else {
             return;
         };
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::ShowSongDetails(SongDetails::from_song(cur_song)),
+        )
+        .await;
+    }
+    async fn star_song(&mut self) {
+        let cur_idx = self.album_songs_list.get_selected_item();
+        let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_idx) else {
+            return;
+        };
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::ToggleStarSong(Box::new(cur_song.clone())),
+        )
+        .await;
+    }
+    async fn export_song(&mut self) {
+        if self.album_songs_list.is_album_selected() {
+            return self.export_album().await;
+        }
+        let cur_idx = self.album_songs_list.get_selected_item();
+        let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_idx) else {
+            return;
+        };
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::ExportSongs(vec![cur_song.clone()]),
+        )
+        .await;
+    }
+    async fn export_album(&mut self) {
+        let Some(album) = self.album_songs_list.get_selected_album() else {
+            return;
+        };
+        let song_list = self
+            .album_songs_list
+            .list
+            // Even if list is filtered, still export the whole album.
+            .get_list_iter()
+            .filter(|song| *song.get_album() == album)
+            .cloned()
+            .collect();
+        send_or_error(&self.callback_tx, AppCallback::ExportSongs(song_list)).await;
+    }
+    async fn add_album_to_playlist(&mut self) {
+        // Consider how resource intensive this is as it runs in the main thread.
+        let Some(album) = self.album_songs_list.get_selected_album() else {
+            return;
+        };
         let song_list = self
             .album_songs_list
             .list
             // Even if list is filtered, still play the whole album.
             .get_list_iter()
-            .filter(|song| song.get_album() == cur_song.get_album())
+            .filter(|song| *song.get_album() == album)
             .cloned()
             .collect();
         send_or_error(
@@ -380,8 +690,7 @@ impl Browser {
     }
     async fn play_album(&mut self) {
         // Consider how resource intensive this is as it runs in the main thread.
-        let cur_idx = self.album_songs_list.get_selected_item();
-        let Some(cur_song) = self.album_songs_list.get_song_from_idx(cur_idx) else {
+        let Some(album) = self.album_songs_list.get_selected_album() else {
             return;
         };
         let song_list = self
@@ -389,7 +698,7 @@ impl Browser {
             .list
             // Even if list is filtered, still play the whole album.
             .get_list_iter()
-            .filter(|song| song.get_album() == cur_song.get_album())
+            .filter(|song| *song.get_album() == album)
             // XXX: Could instead be inside an Rc.
             .cloned()
             .collect();
@@ -400,48 +709,339 @@ impl Browser {
         .await;
         // XXX: Do we want to indicate that song has been added to playlist?
     }
+    async fn play_all(&mut self) {
+        // Consider how resource intensive this is as it runs in the main thread.
+        let song_list: Vec<_> = self
+            .album_songs_list
+            .get_filtered_list_iter()
+            .cloned()
+            .collect();
+        // Playlist requires at least one song to be pushed.
+        if song_list.is_empty() {
+            return;
+        }
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::AddSongsToPlaylistAndPlay(song_list),
+        )
+        .await;
+    }
+    async fn add_all_to_playlist(&mut self) {
+        // Consider how resource intensive this is as it runs in the main thread.
+        let song_list: Vec<_> = self
+            .album_songs_list
+            .get_filtered_list_iter()
+            .cloned()
+            .collect();
+        // Playlist requires at least one song to be pushed.
+        if song_list.is_empty() {
+            return;
+        }
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::AddSongsToPlaylist(song_list),
+        )
+        .await;
+    }
     async fn get_songs(&mut self) {
-        let selected = self.artist_list.get_selected_item();
-        self.change_routing(InputRouting::Song);
-        self.album_songs_list.list.clear();
-
         let Some(cur_artist_id) = self
             .artist_list
-            .list
-            .get(selected)
-            .cloned()
-            .map(|a| a.browse_id)
+            .get_selected_artist()
+            .map(|a| a.browse_id.clone())
+        else {
+            tracing::warn!("Tried to get item from list with index out of range");
+            return;
+        };
+        self.show_artist_songs(cur_artist_id).await;
+    }
+    async fn toggle_subscribed(&mut self) {
+        let Some(cur_artist_id) = self
+            .artist_list
+            .get_selected_artist()
+            .map(|a| a.browse_id.clone())
+        else {
+            tracing::warn!("Tried to get item from list with index out of range");
+            return;
+        };
+        // If subscriptions aren't known yet, assume the user wants to subscribe.
+        let subscribed = !self
+            .artist_list
+            .is_subscribed(&cur_artist_id)
+            .unwrap_or_default();
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::SetArtistSubscribed(cur_artist_id, subscribed),
+        )
+        .await;
+    }
+    async fn get_details(&mut self) {
+        let Some(cur_artist_id) = self
+            .artist_list
+            .get_selected_artist()
+            .map(|a| a.browse_id.clone())
         else {
             tracing::warn!("Tried to get item from list with index out of range");
             return;
         };
         send_or_error(
             &self.callback_tx,
-            AppCallback::GetArtistSongs(cur_artist_id),
+            AppCallback::ViewArtistDetails(cur_artist_id),
         )
         .await;
+    }
+    /// Clear the songs list and request songs for the artist.
+    pub async fn show_artist_songs(&mut self, artist_id: ChannelID<'static>) {
+        self.input_routing = InputRouting::Song;
+        self.search_results.shown = false;
+        self.album_songs_list.list.clear();
+        self.album_songs_list.group_by_album = true;
+        self.shown_artist = Some(artist_id.clone());
+        send_or_error(&self.callback_tx, AppCallback::GetArtistSongs(artist_id)).await;
         tracing::info!("Sent request to UI to get songs");
     }
+    /// Clear the songs list and request songs for the playlist.
+    pub async fn show_playlist_songs(&mut self, playlist_id: PlaylistID<'static>) {
+        self.input_routing = InputRouting::Song;
+        self.search_results.shown = false;
+        self.album_songs_list.list.clear();
+        self.album_songs_list.group_by_album = false;
+        self.shown_artist = None;
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::GetPlaylistSongs(playlist_id),
+        )
+        .await;
+        tracing::info!("Sent request to UI to get playlist songs");
+    }
+    /// Clear the songs list and request songs for the album.
+    pub async fn show_album_songs(&mut self, album_id: AlbumID<'static>) {
+        self.input_routing = InputRouting::Song;
+        self.search_results.shown = false;
+        self.album_songs_list.list.clear();
+        self.album_songs_list.group_by_album = false;
+        self.shown_artist = None;
+        send_or_error(&self.callback_tx, AppCallback::ListAlbumSongs(album_id)).await;
+        tracing::info!("Sent request to UI to get album songs");
+    }
+    /// Move down the artist list, requesting the next page of results once the end is reached.
+    async fn increment_artist_list(&mut self, amount: isize) {
+        self.artist_list.increment_list(amount);
+        if self.artist_list.get_selected_item() + 1 < self.artist_list.len() {
+            return;
+        }
+        // Taken so that the next page is only requested once.
+        let Some(continuation) = self.artist_list.continuation.take() else {
+            return;
+        };
+        send_or_error(
+            &self.callback_tx,
+            AppCallback::SearchArtistsContinuation(continuation),
+        )
+        .await;
+        tracing::info!("Sent request to UI to get next page of artists");
+    }
     async fn search(&mut self) {
+        // Searching with a suggestion highlighted searches the suggestion.
+        self.artist_list.search.accept_suggestion();
         self.artist_list.close_search();
         let search_query = self.artist_list.search.take_text();
-        send_or_error(&self.callback_tx, AppCallback::SearchArtist(search_query)).await;
+        let filter = self.artist_list.search.filter;
+        self.last_query = Some(search_query.clone());
+        self.search_history.push(&search_query);
+        if filter == SearchFilter::Artists {
+            self.search_results.shown = false;
+        } else {
+            self.search_results.handle_search_started(filter);
+            self.input_routing = InputRouting::Results;
+        }
+        send_or_error(&self.callback_tx, AppCallback::Search(search_query, filter)).await;
         tracing::info!("Sent request to UI to search");
     }
+    /// Queue the selected search result, and optionally play it.
+    /// Albums, playlists and podcasts need their songs fetched first, so are requested from the
+    /// app. Playlists and podcasts are only added to the queue.
+    async fn queue_search_result(&mut self, play: bool) {
+        let Some(item) = self.search_results.get_selected_result() else {
+            return;
+        };
+        let mut list = AlbumSongsList::default();
+        match item {
+            SearchResultItem::Song(song) => {
+                list.append_raw_songs(
+                    vec![SongResult::from(song.clone())],
+                    song.album.clone(),
+                    String::new(),
+                    song.artist.clone(),
+                );
+            }
+            SearchResultItem::Video(video) => {
+                list.append_raw_songs(
+                    vec![SongResult::from(video.clone())],
+                    String::new(),
+                    String::new(),
+                    video.channel_name.clone(),
+                );
+            }
+            SearchResultItem::Album(album) => {
+                send_or_error(
+                    &self.callback_tx,
+                    AppCallback::GetAlbumSongs {
+                        album_id: album.browse_id.clone(),
+                        artist: album.artist.clone(),
+                        play,
+                    },
+                )
+                .await;
+                return;
+            }
+            SearchResultItem::Artist(artist) => {
+                // Artists can't be queued, so display their songs instead.
+                let artist_id = artist.browse_id.clone();
+                self.show_artist_songs(artist_id).await;
+                return;
+            }
+            // Songs and videos in the top results can be queued directly.
+            SearchResultItem::TopResult(result) => match result.video_id.clone() {
+                Some(video_id) => {
+                    list.append_raw_songs(
+                        vec![top_result_to_song(result, video_id)],
+                        result.album.clone().unwrap_or_default(),
+                        String::new(),
+                        result.artist.clone().unwrap_or_default(),
+                    );
+                }
+                None => {
+                    let result = result.clone();
+                    self.queue_top_result(result, play).await;
+                    return;
+                }
+            },
+            SearchResultItem::FeaturedPlaylist(playlist) => {
+                send_or_error(
+                    &self.callback_tx,
+                    AppCallback::QueuePlaylistSongs(playlist.playlist_id.clone()),
+                )
+                .await;
+                return;
+            }
+            SearchResultItem::CommunityPlaylist(playlist) => {
+                send_or_error(
+                    &self.callback_tx,
+                    AppCallback::QueuePlaylistSongs(playlist.playlist_id.clone()),
+                )
+                .await;
+                return;
+            }
+            SearchResultItem::Podcast(podcast) => {
+                send_or_error(
+                    &self.callback_tx,
+                    AppCallback::QueuePodcastEpisodes(podcast.podcast_id.clone()),
+                )
+                .await;
+                return;
+            }
+        }
+        let song_list = list.get_list_iter().cloned().collect();
+        let callback = if play {
+            AppCallback::AddSongsToPlaylistAndPlay(song_list)
+        } else {
+            AppCallback::AddSongsToPlaylist(song_list)
+        };
+        send_or_error(&self.callback_tx, callback).await;
+    }
+    /// Queue a top result that isn't a song or video. What its browse id refers to depends on the
+    /// result type.
+    async fn queue_top_result(&mut self, result: TopResult, play: bool) {
+        let (Some(result_type), Some(browse_id)) = (result.result_type, result.browse_id) else {
+            warn!("Top result doesn't contain an id, so can't be queued");
+            return;
+        };
+        let callback = match result_type {
+            TopResultType::Artist => {
+                // Artists can't be queued, so display their songs instead.
+                return self.show_artist_songs(ChannelID::from_raw(browse_id)).await;
+            }
+            TopResultType::Album(_) => AppCallback::GetAlbumSongs {
+                album_id: AlbumID::from_raw(browse_id),
+                artist: result.artist.unwrap_or_default(),
+                play,
+            },
+            TopResultType::Playlist => {
+                AppCallback::QueuePlaylistSongs(PlaylistID::from_raw(browse_id))
+            }
+            TopResultType::Podcast => {
+                AppCallback::QueuePodcastEpisodes(PodcastID::from_raw(browse_id))
+            }
+            TopResultType::Song | TopResultType::Video | TopResultType::Station => {
+                warn!("Top result {:?} doesn't contain a video id", result_type);
+                return;
+            }
+        };
+        send_or_error(&self.callback_tx, callback).await;
+    }
+    pub fn handle_replace_search_results(&mut self, results: SearchResultsList) {
+        self.search_results.handle_replace_results(results);
+    }
+    pub fn handle_search_error(&mut self) {
+        self.search_results.handle_search_error();
+    }
     pub fn handle_search_artist_error(&mut self) {
+        self.artist_list.retrying = false;
         self.album_songs_list.list.state = ListStatus::Error;
     }
+    pub fn handle_replace_library_subscriptions(&mut self, channel_ids: Vec<ChannelID<'static>>) {
+        self.artist_list.subscriptions = Some(channel_ids);
+    }
+    pub fn handle_artist_subscribed(&mut self, channel_id: ChannelID<'static>, subscribed: bool) {
+        self.artist_list.set_subscribed(channel_id, subscribed);
+    }
     pub fn handle_song_list_loaded(&mut self) {
         self.album_songs_list.list.state = ListStatus::Loaded;
+        if let Some(song_id) = self.restore_song_id.take() {
+            self.album_songs_list.select_song(&song_id);
+        }
     }
     pub fn handle_song_list_loading(&mut self) {
         self.album_songs_list.list.state = ListStatus::Loading;
     }
-    pub async fn handle_replace_artist_list(&mut self, artist_list: Vec<SearchResultArtist>) {
+    pub fn handle_song_list_retrying(&mut self) {
+        self.album_songs_list.list.state.set_retrying();
+    }
+    pub fn handle_search_retrying(&mut self) {
+        self.search_results.handle_search_retrying();
+    }
+    pub fn handle_artist_search_retrying(&mut self) {
+        self.artist_list.retrying = true;
+    }
+    pub async fn handle_replace_artist_list(
+        &mut self,
+        artist_list: Vec<SearchResultArtist>,
+        continuation: Option<String>,
+    ) {
         self.artist_list.list = artist_list;
+        self.artist_list.continuation = continuation;
+        self.artist_list.retrying = false;
+        // A filter on the previous results is unlikely to apply to the new ones.
+        self.artist_list.filter_text.clear();
+        self.artist_list.filter_cur = 0;
         // XXX: What to do if position in list was greater than new list length?
         // Handled by this function?
         self.increment_cur_list(0);
+        if let Some(artist_id) = self.restore_artist_id.take() {
+            self.artist_list.select_artist(&artist_id);
+        }
+        if self.artist_list.subscriptions.is_none() {
+            send_or_error(&self.callback_tx, AppCallback::GetLibrarySubscriptions).await;
+        }
+    }
+    pub fn handle_append_artist_list(
+        &mut self,
+        artist_list: Vec<SearchResultArtist>,
+        continuation: Option<String>,
+    ) {
+        self.artist_list.list.extend(artist_list);
+        self.artist_list.continuation = continuation;
+        self.artist_list.retrying = false;
     }
     pub fn handle_replace_search_suggestions(
         &mut self,
@@ -449,7 +1049,10 @@ impl Browser {
         search: String,
     ) {
         if self.artist_list.search.search_contents == search {
-            self.artist_list.search.search_suggestions = search_suggestions;
+            self.artist_list.search.search_suggestions = merge_suggestions(
+                self.search_history.get_suggestions(&search),
+                search_suggestions,
+            );
             self.artist_list.search.suggestions_cur = None;
         }
     }
@@ -462,18 +1065,38 @@ impl Browser {
         album: String,
         year: String,
         artist: String,
+        album_id: AlbumID<'static>,
+        artist_id: Option<ChannelID<'static>>,
     ) {
         self.album_songs_list
             .list
-            .append_raw_songs(song_list, album, year, artist);
+            .append_raw_album_songs(song_list, album, year, artist, album_id, artist_id);
         // If sort commands exist, sort the list.
         // Naive - can result in multiple calls to sort every time songs are appended.
-        self.album_songs_list.apply_sort_commands();
+        if let Err(e) = self.album_songs_list.apply_sort_commands() {
+            warn!("Tried to sort a column that is not sortable - error {e}")
+        };
+        self.album_songs_list.list.state = ListStatus::InProgress;
+    }
+    pub fn handle_append_playlist_song_list(&mut self, song_list: Vec<PlaylistSong>) {
+        self.album_songs_list
+            .list
+            .append_raw_playlist_songs(song_list);
+        // See above.
+        if let Err(e) = self.album_songs_list.apply_sort_commands() {
+            warn!("Tried to sort a column that is not sortable - error {e}")
+        };
         self.album_songs_list.list.state = ListStatus::InProgress;
     }
+    pub fn handle_playlist_songs_error(&mut self) {
+        self.album_songs_list.list.state = ListStatus::Error;
+    }
     pub fn handle_songs_found(&mut self) {
         self.album_songs_list.handle_songs_found()
     }
+    pub fn handle_song_list_progress(&mut self, label: String) {
+        self.album_songs_list.progress = Some(label);
+    }
     fn increment_cur_list(&mut self, increment: isize) {
         match self.input_routing {
             InputRouting::Artist => {
@@ -482,16 +1105,46 @@ impl Browser {
             InputRouting::Song => {
                 self.album_songs_list.increment_list(increment);
             }
+            InputRouting::Results => {
+                self.search_results.increment_list(increment);
+            }
         };
     }
-    #[deprecated]
-    pub fn revert_routing(&mut self) {
-        mem::swap(&mut self.input_routing, &mut self.prev_input_routing);
+    /// Jump the focused pane to the next row starting with the character.
+    pub fn jump_to_char(&mut self, c: char) -> bool {
+        match self.input_routing {
+            InputRouting::Artist => self.artist_list.jump_to_char(c),
+            InputRouting::Song => self.album_songs_list.jump_to_char(c),
+            InputRouting::Results => self.search_results.jump_to_char(c),
+        }
+    }
+}
+
+/// Top results that are songs or videos only contain some of the fields of a song search result.
+fn top_result_to_song(result: &TopResult, video_id: VideoID<'static>) -> SongResult {
+    SongResult::from(SearchResultSong {
+        title: result.result_name.clone(),
+        artist: result.artist.clone().unwrap_or_default(),
+        album: result.album.clone().unwrap_or_default(),
+        duration: result.duration.clone().unwrap_or_default(),
+        plays: result.plays.clone().unwrap_or_default(),
+        explicit: Explicit::NotExplicit,
+        video_id,
+        thumbnails: result.thumbnails.clone(),
+    })
+}
+
+fn get_top_result_url(result: &TopResult) -> Option<String> {
+    if let Some(video_id) = &result.video_id {
+        return Some(video_id.get_url());
     }
-    // Could be in trait.
-    #[deprecated = "Should be in a trait"]
-    pub fn change_routing(&mut self, input_routing: InputRouting) {
-        self.prev_input_routing = mem::replace(&mut self.input_routing, input_routing);
+    let browse_id = result.browse_id.as_deref()?;
+    match result.result_type.as_ref()? {
+        TopResultType::Artist => Some(ChannelID::from_raw(browse_id).get_url()),
+        TopResultType::Album(_) => Some(AlbumID::from_raw(browse_id).get_url()),
+        TopResultType::Playlist => Some(PlaylistID::from_raw(browse_id).get_url()),
+        TopResultType::Podcast => Some(PodcastID::from_raw(browse_id).get_url()),
+        TopResultType::Song | TopResultType::Video | TopResultType::Station => None,
     }
 }
 
@@ -499,6 +1152,7 @@ fn browser_keybinds() -> Vec<KeyCommand<BrowserAction>> {
     vec![
         KeyCommand::new_global_from_code(KeyCode::F(5), BrowserAction::ViewPlaylist),
         KeyCommand::new_global_from_code(KeyCode::F(2), BrowserAction::ToggleSearch),
+        KeyCommand::new_from_code(KeyCode::Char('o'), BrowserAction::OpenInBrowser),
         KeyCommand::new_from_code(KeyCode::Left, BrowserAction::Left),
         KeyCommand::new_from_code(KeyCode::Right, BrowserAction::Right),
     ]
@@ -1,5 +1,7 @@
 use super::get_adjusted_list_column;
-use crate::app::component::actionhandler::{DominantKeyRouter, TextHandler};
+use crate::app::component::actionhandler::{
+    insert_at_cursor, remove_before_cursor, DominantKeyRouter, TextEdit, TextHandler,
+};
 use crate::app::structures::{ListSong, SongListComponent};
 use crate::app::ui::browser::BrowserAction;
 use crate::app::view::{
@@ -9,13 +11,17 @@ use crate::app::{
     component::actionhandler::{Action, KeyRouter},
     keycommand::KeyCommand,
     structures::{AlbumSongsList, ListStatus, Percentage},
-    view::{BasicConstraint, Loadable, Scrollable, TableView},
+    view::{BasicConstraint, Loadable, Scrollable, TableView, TypeAheadJump},
 };
+use crate::drawutils::{get_spinner, icon};
 use crate::error::Error;
+use crate::i18n::{tr, tr_format};
 use crate::Result;
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::KeyCode;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use tracing::warn;
+use ytmapi_rs::VideoID;
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum AlbumSongsInputRouting {
@@ -33,6 +39,44 @@ pub struct AlbumSongsPanel {
     pub sort: SortManager,
     pub filter: FilterManager,
     cur_selected: usize,
+    /// How far through fetching the songs the server is, e.g "3/10 albums", if it's known.
+    pub progress: Option<String>,
+    /// Group the songs under a header for each album, e.g for an artist's songs.
+    pub group_by_album: bool,
+    /// Albums whose songs are hidden under their header.
+    collapsed_albums: HashSet<String>,
+}
+
+/// A row of the panel. Songs are grouped under a header for their album, so that a large
+/// discography can be collapsed to a list of albums.
+#[derive(Clone, Debug)]
+pub enum AlbumSongsRow<'a> {
+    Album {
+        album: &'a str,
+        year: &'a str,
+        songs: usize,
+        collapsed: bool,
+        /// Index of the album's first song in the filtered list.
+        first_song: usize,
+    },
+    /// A song, with its index in the filtered list.
+    Song(usize, &'a ListSong),
+}
+
+impl AlbumSongsRow<'_> {
+    pub fn get_album(&self) -> &str {
+        match self {
+            AlbumSongsRow::Album { album, .. } => album,
+            AlbumSongsRow::Song(_, song) => song.get_album(),
+        }
+    }
+    /// Index in the filtered list of the song, or the album's first song.
+    fn get_song_idx(&self) -> usize {
+        match self {
+            AlbumSongsRow::Album { first_song, .. } => *first_song,
+            AlbumSongsRow::Song(idx, _) => *idx,
+        }
+    }
 }
 
 // TODO: refactor
@@ -66,7 +110,7 @@ impl Default for SortManager {
 }
 impl FilterManager {
     fn move_cursor_to_end(&mut self) {
-        self.filter_cur = self.filter_text.len();
+        self.filter_cur = self.filter_text.chars().count();
     }
 }
 
@@ -83,14 +127,15 @@ impl Default for FilterManager {
 }
 
 impl TextHandler for FilterManager {
-    // XXX: This is copy/paste from SearchBlock, so can an interface be made for this?
     fn push_text(&mut self, c: char) {
-        self.filter_text.push(c);
-        self.filter_cur += 1;
+        insert_at_cursor(&mut self.filter_text, &mut self.filter_cur, c);
     }
     fn pop_text(&mut self) {
-        self.filter_text.pop();
-        self.filter_cur = self.filter_cur.saturating_sub(1);
+        remove_before_cursor(&mut self.filter_text, &mut self.filter_cur);
+    }
+    fn edit_text(&mut self, edit: TextEdit) -> bool {
+        edit.apply(&mut self.filter_text, &mut self.filter_cur);
+        true
     }
     fn is_text_handling(&self) -> bool {
         true
@@ -113,10 +158,21 @@ pub enum ArtistSongsAction {
     AddSongToPlaylist,
     AddSongsToPlaylist,
     AddAlbumToPlaylist,
+    PlayAll,
+    AddAllToPlaylist,
+    AddSongToLibraryPlaylist,
+    ShowSongDetails,
+    StarSong,
+    ExportSong,
+    ExportAlbum,
+    ToggleAlbumCollapsed,
+    ToggleAllAlbumsCollapsed,
     Up,
     Down,
     PageUp,
     PageDown,
+    Home,
+    End,
     SortUp,
     SortDown,
     // Could just be two commands.
@@ -125,6 +181,7 @@ pub enum ArtistSongsAction {
     ClearSort,
     SortSelectedAsc,
     SortSelectedDesc,
+    ToggleSortSelected,
     ToggleFilter,
     ApplyFilter,
     ClearFilter,
@@ -139,6 +196,9 @@ impl AlbumSongsPanel {
             route: Default::default(),
             sort: Default::default(),
             filter: Default::default(),
+            progress: None,
+            group_by_album: false,
+            collapsed_albums: Default::default(),
         }
     }
     pub fn subcolumns_of_vec() -> &'static [usize] {
@@ -198,6 +258,105 @@ impl AlbumSongsPanel {
             }
         }))
     }
+    /// The rows shown in the panel. If grouping by album, consecutive songs from the same album
+    /// are grouped under a header, and the songs of collapsed albums are hidden.
+    pub fn get_rows(&self) -> Vec<AlbumSongsRow<'_>> {
+        let songs: Vec<_> = self.get_filtered_list_iter().collect();
+        if !self.group_by_album {
+            return songs
+                .into_iter()
+                .enumerate()
+                .map(|(i, s)| AlbumSongsRow::Song(i, s))
+                .collect();
+        }
+        let mut rows = Vec::new();
+        let mut first_song = 0;
+        while let Some(first) = songs.get(first_song) {
+            let album = first.get_album();
+            let len = songs[first_song..]
+                .iter()
+                .take_while(|s| s.get_album() == album)
+                .count();
+            let collapsed = self.collapsed_albums.contains(album);
+            rows.push(AlbumSongsRow::Album {
+                album,
+                year: first.get_year(),
+                songs: len,
+                collapsed,
+                first_song,
+            });
+            if !collapsed {
+                rows.extend(
+                    songs[first_song..first_song + len]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, s)| AlbumSongsRow::Song(first_song + i, s)),
+                );
+            }
+            first_song += len;
+        }
+        rows
+    }
+    pub fn get_selected_row(&self) -> Option<AlbumSongsRow<'_>> {
+        self.get_rows().into_iter().nth(self.cur_selected)
+    }
+    /// True if the selected row is an album header, rather than a song.
+    pub fn is_album_selected(&self) -> bool {
+        matches!(self.get_selected_row(), Some(AlbumSongsRow::Album { .. }))
+    }
+    /// Album of the selected song, or of the selected header.
+    pub fn get_selected_album(&self) -> Option<String> {
+        self.get_selected_row()
+            .map(|row| row.get_album().to_owned())
+    }
+    /// Songs from the selected row to the end of the list, including those of collapsed albums.
+    pub fn get_songs_from_selected(&self) -> Vec<&ListSong> {
+        let Some(idx) = self.get_selected_row().map(|row| row.get_song_idx()) else {
+            return Vec::new();
+        };
+        self.get_filtered_list_iter().skip(idx).collect()
+    }
+    /// Collapse the album of the selected row, selecting its header, or expand it if collapsed.
+    pub fn toggle_selected_album_collapsed(&mut self) {
+        let rows = self.get_rows();
+        let Some(header) = rows
+            .iter()
+            .take(self.cur_selected + 1)
+            .rposition(|row| matches!(row, AlbumSongsRow::Album { .. }))
+        else {
+            return;
+        };
+        let album = rows[header].get_album().to_owned();
+        if !self.collapsed_albums.remove(&album) {
+            self.collapsed_albums.insert(album);
+        }
+        self.cur_selected = header;
+    }
+    /// Collapse every album, or expand them all if any are collapsed. The selected album stays
+    /// selected.
+    pub fn toggle_all_albums_collapsed(&mut self) {
+        if !self.group_by_album {
+            return;
+        }
+        let selected = self.get_selected_album();
+        if self.collapsed_albums.is_empty() {
+            self.collapsed_albums = self
+                .list
+                .get_list_iter()
+                .map(|song| song.get_album().clone())
+                .collect();
+        } else {
+            self.collapsed_albums.clear();
+        }
+        self.cur_selected = self
+            .get_rows()
+            .iter()
+            .position(|row| {
+                matches!(row, AlbumSongsRow::Album { .. })
+                    && Some(row.get_album()) == selected.as_deref()
+            })
+            .unwrap_or_default();
+    }
     pub fn apply_filter(&mut self) {
         let filter = self.filter.take_text();
         self.filter.shown = false;
@@ -258,6 +417,29 @@ impl AlbumSongsPanel {
             .saturating_add(1)
             .min(self.get_sortable_columns().len().saturating_sub(1));
     }
+    /// Get the direction a column is currently sorted in, if it is sorted.
+    pub fn get_sort_direction(&self, column: usize) -> Option<SortDirection> {
+        self.sort
+            .sort_commands
+            .iter()
+            .find(|cmd| cmd.column == column)
+            .map(|cmd| cmd.direction)
+    }
+    /// Sort the selected column ascending, or flip its direction if already sorted.
+    /// The popup is left open so that multiple columns can be sorted.
+    pub fn handle_toggle_sort_cur(&mut self) {
+        let Some(column) = self.get_sortable_columns().get(self.sort.cur).copied() else {
+            warn!("Tried to index sortable columns but was out of range");
+            return;
+        };
+        let direction = match self.get_sort_direction(column) {
+            Some(SortDirection::Asc) => SortDirection::Desc,
+            Some(SortDirection::Desc) | None => SortDirection::Asc,
+        };
+        if let Err(e) = self.push_sort_command(TableSortCommand { column, direction }) {
+            warn!("Tried to sort a column that is not sortable - error {e}")
+        };
+    }
     pub fn handle_sort_cur_asc(&mut self) {
         // TODO: Better error handling
         let Some(column) = self.get_sortable_columns().get(self.sort.cur) else {
@@ -291,13 +473,28 @@ impl AlbumSongsPanel {
         // XXX: Consider clearing sort params here, so that we don't need to sort all the incoming songs. Performance seems OK for now.
         // XXX: Consider also clearing filter params here.
         self.cur_selected = 0;
+        self.progress = None;
+        self.collapsed_albums.clear();
         self.list.state = ListStatus::InProgress;
     }
+    /// Select the song with the video id, if it's shown. Returns true if found.
+    pub fn select_song(&mut self, video_id: &VideoID) -> bool {
+        let Some(idx) = self.get_rows().iter().position(
+            |row| matches!(row, AlbumSongsRow::Song(_, s) if s.raw.get_video_id() == video_id),
+        ) else {
+            return false;
+        };
+        self.cur_selected = idx;
+        true
+    }
 }
 
 impl SongListComponent for AlbumSongsPanel {
     fn get_song_from_idx(&self, idx: usize) -> Option<&crate::app::structures::ListSong> {
-        self.get_filtered_list_iter().nth(idx)
+        match self.get_rows().into_iter().nth(idx)? {
+            AlbumSongsRow::Song(_, song) => Some(song),
+            AlbumSongsRow::Album { .. } => None,
+        }
     }
 }
 
@@ -318,6 +515,10 @@ impl TextHandler for AlbumSongsPanel {
         self.filter.replace_text(text)
     }
 
+    fn edit_text(&mut self, edit: TextEdit) -> bool {
+        self.filter.edit_text(edit)
+    }
+
     fn is_text_handling(&self) -> bool {
         self.route == AlbumSongsInputRouting::Filter
     }
@@ -325,20 +526,31 @@ impl TextHandler for AlbumSongsPanel {
 
 impl Action for ArtistSongsAction {
     fn context(&self) -> Cow<str> {
-        "Artist Songs Panel".into()
+        tr("Artist Songs Panel").into()
     }
     fn describe(&self) -> Cow<str> {
-        match &self {
+        tr(match &self {
             ArtistSongsAction::PlaySong => "Play song",
             ArtistSongsAction::PlaySongs => "Play songs",
             ArtistSongsAction::PlayAlbum => "Play album",
             ArtistSongsAction::AddSongToPlaylist => "Add song to playlist",
             ArtistSongsAction::AddSongsToPlaylist => "Add songs to playlist",
             ArtistSongsAction::AddAlbumToPlaylist => "Add album to playlist",
+            ArtistSongsAction::PlayAll => "Play all",
+            ArtistSongsAction::AddAllToPlaylist => "Add all to playlist",
+            ArtistSongsAction::AddSongToLibraryPlaylist => "Add song to YouTube playlist",
+            ArtistSongsAction::ShowSongDetails => "Song details",
+            ArtistSongsAction::StarSong => "Star song",
+            ArtistSongsAction::ExportSong => "Export song to music folder",
+            ArtistSongsAction::ExportAlbum => "Export album to music folder",
+            ArtistSongsAction::ToggleAlbumCollapsed => "Collapse/expand album",
+            ArtistSongsAction::ToggleAllAlbumsCollapsed => "Collapse/expand all albums",
             ArtistSongsAction::Up | Self::SortUp => "Up",
             ArtistSongsAction::Down | Self::SortDown => "Down",
             ArtistSongsAction::PageUp => "Page Up",
             ArtistSongsAction::PageDown => "Page Down",
+            ArtistSongsAction::Home => "Top",
+            ArtistSongsAction::End => "Bottom",
             ArtistSongsAction::PopSort => "Sort",
             ArtistSongsAction::ToggleFilter => "Filter",
             ArtistSongsAction::ApplyFilter => "Apply filter",
@@ -347,7 +559,8 @@ impl Action for ArtistSongsAction {
             ArtistSongsAction::ClearSort => "Clear sort",
             ArtistSongsAction::SortSelectedAsc => "Sort ascending",
             ArtistSongsAction::SortSelectedDesc => "Sort descending",
-        }
+            ArtistSongsAction::ToggleSortSelected => "Toggle sort direction",
+        })
         .into()
     }
 }
@@ -380,9 +593,13 @@ impl Loadable for AlbumSongsPanel {
     fn is_loading(&self) -> bool {
         match self.list.state {
             crate::app::structures::ListStatus::Loading => true,
+            crate::app::structures::ListStatus::Retrying => true,
             _ => false,
         }
     }
+    fn is_retrying(&self) -> bool {
+        matches!(self.list.state, ListStatus::Retrying)
+    }
 }
 impl Scrollable for AlbumSongsPanel {
     fn increment_list(&mut self, amount: isize) {
@@ -397,20 +614,37 @@ impl Scrollable for AlbumSongsPanel {
     }
 }
 
+impl TypeAheadJump for AlbumSongsPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        // Jump by song title rather than track number. Sorted and filtered, as drawn.
+        self.get_filtered_items()
+            .map(|mut row| row.nth(2).unwrap_or_default())
+            .collect()
+    }
+}
+
 impl TableView for AlbumSongsPanel {
     fn get_title(&self) -> Cow<str> {
         match self.list.state {
-            ListStatus::New => "Songs".into(),
-            ListStatus::Loading => "Songs - loading".into(),
-            ListStatus::InProgress => format!(
-                "Songs - {} results - loading",
-                self.list.get_list_iter().len()
-            )
-            .into(),
+            ListStatus::New => tr("Songs").into(),
+            ListStatus::Loading => tr("Songs - loading").into(),
+            ListStatus::Retrying => tr("Songs - retrying").into(),
+            ListStatus::InProgress => match &self.progress {
+                Some(progress) => tr_format(
+                    "Songs - {} results - {} loaded {}",
+                    &[&self.list.get_list_iter().len(), &get_spinner(), progress],
+                )
+                .into(),
+                None => tr_format(
+                    "Songs - {} results - {} loading",
+                    &[&self.list.get_list_iter().len(), &get_spinner()],
+                )
+                .into(),
+            },
             ListStatus::Loaded => {
-                format!("Songs - {} results", self.list.get_list_iter().len()).into()
+                tr_format("Songs - {} results", &[&self.list.get_list_iter().len()]).into()
             }
-            ListStatus::Error => "Songs - Error receieved".into(),
+            ListStatus::Error => tr("Songs - Error receieved").into(),
         }
     }
     fn get_layout(&self) -> &[BasicConstraint] {
@@ -475,14 +709,37 @@ impl SortableTableView for AlbumSongsPanel {
     }
     fn get_filtered_items(&self) -> Box<dyn Iterator<Item = crate::app::view::TableItem> + '_> {
         // We are doing a lot here every draw cycle!
-        Box::new(self.get_filtered_list_iter().map(|ls| {
-            Box::new(ls.get_fields_iter().enumerate().filter_map(|(i, f)| {
-                if Self::subcolumns_of_vec().contains(&i) {
-                    Some(f)
+        Box::new(self.get_rows().into_iter().map(|row| match row {
+            AlbumSongsRow::Album {
+                album,
+                year,
+                songs,
+                collapsed,
+                ..
+            } => {
+                let marker = if collapsed {
+                    icon('▸', '+')
                 } else {
-                    None
-                }
-            })) as Box<dyn Iterator<Item = Cow<str>>>
+                    icon('▾', '-')
+                };
+                let fields: [Cow<str>; 5] = [
+                    marker.to_string().into(),
+                    album.into(),
+                    tr_format("{} songs", &[&songs]).into(),
+                    "".into(),
+                    year.into(),
+                ];
+                Box::new(fields.into_iter()) as Box<dyn Iterator<Item = Cow<str>>>
+            }
+            AlbumSongsRow::Song(_, ls) => {
+                Box::new(ls.get_fields_iter().enumerate().filter_map(|(i, f)| {
+                    if Self::subcolumns_of_vec().contains(&i) {
+                        Some(f)
+                    } else {
+                        None
+                    }
+                })) as Box<dyn Iterator<Item = Cow<str>>>
+            }
         }))
     }
     fn get_filterable_columns(&self) -> &[usize] {
@@ -508,12 +765,14 @@ fn sort_keybinds() -> Vec<KeyCommand<BrowserAction>> {
         ),
         KeyCommand::new_global_from_code(
             KeyCode::Enter,
+            BrowserAction::ArtistSongs(ArtistSongsAction::ToggleSortSelected),
+        ),
+        KeyCommand::new_global_from_code(
+            KeyCode::Char('a'),
             BrowserAction::ArtistSongs(ArtistSongsAction::SortSelectedAsc),
         ),
-        // Seems to not work on Windows.
-        KeyCommand::new_global_modified_from_code(
-            KeyCode::Enter,
-            KeyModifiers::ALT,
+        KeyCommand::new_global_from_code(
+            KeyCode::Char('d'),
             BrowserAction::ArtistSongs(ArtistSongsAction::SortSelectedDesc),
         ),
         KeyCommand::new_global_from_code(
@@ -576,6 +835,34 @@ pub fn songs_keybinds() -> Vec<KeyCommand<BrowserAction>> {
             KeyCode::PageDown,
             BrowserAction::ArtistSongs(ArtistSongsAction::PageDown),
         ),
+        KeyCommand::new_from_code(
+            KeyCode::Home,
+            BrowserAction::ArtistSongs(ArtistSongsAction::Home),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::End,
+            BrowserAction::ArtistSongs(ArtistSongsAction::End),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('s'),
+            BrowserAction::ArtistSongs(ArtistSongsAction::AddSongToLibraryPlaylist),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('i'),
+            BrowserAction::ArtistSongs(ArtistSongsAction::ShowSongDetails),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('f'),
+            BrowserAction::ArtistSongs(ArtistSongsAction::StarSong),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('c'),
+            BrowserAction::ArtistSongs(ArtistSongsAction::ToggleAlbumCollapsed),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('C'),
+            BrowserAction::ArtistSongs(ArtistSongsAction::ToggleAllAlbumsCollapsed),
+        ),
         KeyCommand::new_hidden_from_code(
             KeyCode::Down,
             BrowserAction::ArtistSongs(ArtistSongsAction::Down),
@@ -610,9 +897,144 @@ pub fn songs_keybinds() -> Vec<KeyCommand<BrowserAction>> {
                     KeyCode::Char('A'),
                     BrowserAction::ArtistSongs(ArtistSongsAction::AddAlbumToPlaylist),
                 ),
+                (
+                    KeyCode::Char('l'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::PlayAll),
+                ),
+                (
+                    KeyCode::Char('L'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::AddAllToPlaylist),
+                ),
             ],
             KeyCode::Enter,
             "Play",
         ),
+        // Adding to the queue leaves current playback untouched.
+        KeyCommand::new_action_only_mode(
+            vec![
+                (
+                    KeyCode::Char('q'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::AddSongToPlaylist),
+                ),
+                (
+                    KeyCode::Char('a'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::AddAlbumToPlaylist),
+                ),
+                (
+                    KeyCode::Char('l'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::AddAllToPlaylist),
+                ),
+            ],
+            KeyCode::Char('q'),
+            "Add to queue",
+        ),
+        KeyCommand::new_action_only_mode(
+            vec![
+                (
+                    KeyCode::Char('x'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::ExportSong),
+                ),
+                (
+                    KeyCode::Char('a'),
+                    BrowserAction::ArtistSongs(ArtistSongsAction::ExportAlbum),
+                ),
+            ],
+            KeyCode::Char('x'),
+            "Export",
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AlbumSongsPanel, AlbumSongsRow};
+    use crate::app::structures::{
+        saved_songs_to_list, test_saved_song, SavedSong, SongListComponent,
+    };
+    use crate::app::view::Scrollable;
+    use ytmapi_rs::common::youtuberesult::YoutubeResult;
+
+    fn song(video_id: &str, album: &str) -> SavedSong {
+        SavedSong {
+            album: album.into(),
+            ..test_saved_song(video_id)
+        }
+    }
+    fn panel() -> AlbumSongsPanel {
+        let mut panel = AlbumSongsPanel::new();
+        panel.group_by_album = true;
+        panel.list.replace_songs(saved_songs_to_list(&[
+            song("a", "First"),
+            song("b", "First"),
+            song("c", "Second"),
+        ]));
+        panel
+    }
+
+    #[test]
+    fn test_rows_grouped_by_album() {
+        let panel = panel();
+        let rows = panel.get_rows();
+        assert_eq!(rows.len(), 5);
+        assert!(matches!(
+            rows[0],
+            AlbumSongsRow::Album {
+                album: "First",
+                songs: 2,
+                collapsed: false,
+                ..
+            }
+        ));
+        assert!(matches!(rows[2], AlbumSongsRow::Song(1, _)));
+        assert!(matches!(
+            rows[3],
+            AlbumSongsRow::Album {
+                album: "Second",
+                first_song: 2,
+                ..
+            }
+        ));
+        assert!(panel.get_song_from_idx(0).is_none());
+        assert_eq!(panel.get_song_from_idx(1).unwrap().get_title(), "Song a");
+    }
+    #[test]
+    fn test_rows_ungrouped() {
+        let mut panel = panel();
+        panel.group_by_album = false;
+        assert_eq!(panel.get_rows().len(), 3);
+        assert_eq!(panel.get_song_from_idx(2).unwrap().get_title(), "Song c");
+    }
+    #[test]
+    fn test_toggle_album_collapsed() {
+        let mut panel = panel();
+        // Select the second song of the first album.
+        panel.increment_list(2);
+        panel.toggle_selected_album_collapsed();
+        assert_eq!(panel.get_selected_item(), 0);
+        let rows = panel.get_rows();
+        assert_eq!(rows.len(), 3);
+        assert!(matches!(
+            rows[0],
+            AlbumSongsRow::Album {
+                collapsed: true,
+                ..
+            }
+        ));
+        // Songs of collapsed albums are still queued from the header.
+        assert_eq!(panel.get_songs_from_selected().len(), 3);
+        panel.toggle_selected_album_collapsed();
+        assert_eq!(panel.get_rows().len(), 5);
+    }
+    #[test]
+    fn test_toggle_all_albums_collapsed() {
+        let mut panel = panel();
+        // Select the song of the second album.
+        panel.increment_list(4);
+        panel.toggle_all_albums_collapsed();
+        assert_eq!(panel.get_rows().len(), 2);
+        assert_eq!(panel.get_selected_album().as_deref(), Some("Second"));
+        panel.toggle_all_albums_collapsed();
+        assert_eq!(panel.get_rows().len(), 5);
+        assert_eq!(panel.get_selected_item(), 3);
+    }
+}
@@ -1,18 +1,25 @@
 use std::borrow::Cow;
 
-use crossterm::event::KeyCode;
-use ytmapi_rs::{common::SearchSuggestion, parse::SearchResultArtist};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ytmapi_rs::{common::SearchSuggestion, parse::SearchResultArtist, ChannelID};
 
 use crate::app::{
-    component::actionhandler::{Action, KeyRouter, Suggestable, TextHandler},
+    component::actionhandler::{
+        insert_at_cursor, remove_before_cursor, Action, KeyRouter, Suggestable, TextEdit,
+        TextHandler,
+    },
     keycommand::KeyCommand,
+    server::structures::SearchFilter,
     ui::browser::BrowserAction,
-    view::{ListView, Loadable, Scrollable, SortableList},
+    view::{fuzzy_match_score, ListView, Loadable, Scrollable, SortableList, TypeAheadJump},
 };
+use crate::i18n::{tr, tr_format};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum ArtistInputRouting {
     Search,
+    /// Typing narrows the already fetched results, without searching again.
+    Filter,
     #[default]
     List,
 }
@@ -27,8 +34,17 @@ pub struct ArtistSearchPanel {
     sort_commands_list: Vec<String>,
     keybinds: Vec<KeyCommand<BrowserAction>>,
     search_keybinds: Vec<KeyCommand<BrowserAction>>,
+    filter_keybinds: Vec<KeyCommand<BrowserAction>>,
     pub search_popped: bool,
     pub search: SearchBlock,
+    pub filter_text: String,
+    pub filter_cur: usize,
+    /// Artists the user is subscribed to, or None if not yet known.
+    pub subscriptions: Option<Vec<ChannelID<'static>>>,
+    /// Token to get the next page of search results, if there are more.
+    pub continuation: Option<String>,
+    /// Whether the search for the list failed and is being retried.
+    pub retrying: bool,
 }
 
 #[derive(Default, Clone)]
@@ -37,20 +53,32 @@ pub struct SearchBlock {
     pub search_suggestions: Vec<SearchSuggestion>,
     pub text_cur: usize,
     pub suggestions_cur: Option<usize>,
+    /// Type of results the search will return.
+    pub filter: SearchFilter,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ArtistAction {
     DisplayAlbums,
+    DisplayDetails,
     // XXX: This could be a subset - eg ListAction
     Up,
     Down,
     PageUp,
     PageDown,
+    Home,
+    End,
+    ToggleSubscribed,
     // XXX: Could be a subset just for search
     Search,
     PrevSearchSuggestion,
     NextSearchSuggestion,
+    AcceptSearchSuggestion,
+    NextSearchFilter,
+    PrevSearchFilter,
+    ToggleFilter,
+    ApplyFilter,
+    ClearFilter,
 }
 
 impl ArtistSearchPanel {
@@ -58,9 +86,61 @@ impl ArtistSearchPanel {
         Self {
             keybinds: browser_artist_search_keybinds(),
             search_keybinds: search_keybinds(),
+            filter_keybinds: filter_keybinds(),
             ..Default::default()
         }
     }
+    pub fn toggle_filter(&mut self) {
+        if self.route == ArtistInputRouting::Filter {
+            self.route = ArtistInputRouting::List;
+        } else {
+            self.filter_cur = self.filter_text.chars().count();
+            self.route = ArtistInputRouting::Filter;
+        }
+    }
+    /// Stop typing into the filter, leaving the results filtered.
+    pub fn apply_filter(&mut self) {
+        self.route = ArtistInputRouting::List;
+    }
+    pub fn clear_filter(&mut self) {
+        // Keep the selected artist selected once the filter is removed.
+        let selected_id = self.get_selected_artist().map(|a| a.browse_id.clone());
+        self.route = ArtistInputRouting::List;
+        self.filter_text.clear();
+        self.filter_cur = 0;
+        self.selected = selected_id
+            .and_then(|id| self.list.iter().position(|a| a.browse_id == id))
+            .unwrap_or_default();
+    }
+    /// Artists matching the filter, best matches first.
+    pub fn get_filtered_list(&self) -> Vec<&SearchResultArtist> {
+        if self.filter_text.is_empty() {
+            return self.list.iter().collect();
+        }
+        let mut scored: Vec<_> = self
+            .list
+            .iter()
+            .filter_map(|a| fuzzy_match_score(&self.filter_text, &a.artist).map(|s| (s, a)))
+            .collect();
+        // Stable sort, so that equal matches keep the order returned by the search.
+        scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+        scored.into_iter().map(|(_, a)| a).collect()
+    }
+    pub fn get_selected_artist(&self) -> Option<&SearchResultArtist> {
+        self.get_filtered_list().get(self.selected).copied()
+    }
+    /// Select the artist with the browse id, if it's in the list. Returns true if found.
+    pub fn select_artist(&mut self, artist_id: &ChannelID) -> bool {
+        let Some(idx) = self
+            .get_filtered_list()
+            .iter()
+            .position(|a| &a.browse_id == artist_id)
+        else {
+            return false;
+        };
+        self.selected = idx;
+        true
+    }
     pub fn open_search(&mut self) {
         self.search_popped = true;
         self.route = ArtistInputRouting::Search;
@@ -69,38 +149,69 @@ impl ArtistSearchPanel {
         self.search_popped = false;
         self.route = ArtistInputRouting::List;
     }
+    /// Whether the user is subscribed to the artist, or None if subscriptions are not yet known.
+    pub fn is_subscribed(&self, channel_id: &ChannelID) -> Option<bool> {
+        self.subscriptions
+            .as_ref()
+            .map(|subs| subs.iter().any(|s| s == channel_id))
+    }
+    pub fn set_subscribed(&mut self, channel_id: ChannelID<'static>, subscribed: bool) {
+        let Some(subs) = self.subscriptions.as_mut() else {
+            return;
+        };
+        subs.retain(|s| s != &channel_id);
+        if subscribed {
+            subs.push(channel_id);
+        }
+    }
 }
 impl Action for ArtistAction {
     fn context(&self) -> Cow<str> {
-        "Artist Search Panel".into()
+        tr("Artist Search Panel").into()
     }
     fn describe(&self) -> Cow<str> {
-        match &self {
+        tr(match &self {
             Self::Search => "Search",
             Self::DisplayAlbums => "Display albums for selected artist",
+            Self::DisplayDetails => "Display details for selected artist",
             Self::Up => "Up",
             Self::Down => "Down",
             Self::PageUp => "Page Up",
             Self::PageDown => "Page Down",
+            Self::Home => "Top",
+            Self::End => "Bottom",
+            Self::ToggleSubscribed => "Subscribe/Unsubscribe to selected artist",
             ArtistAction::PrevSearchSuggestion => "Next Search Suggestion",
             ArtistAction::NextSearchSuggestion => "Prev Search Suggestion",
-        }
+            ArtistAction::AcceptSearchSuggestion => "Accept Search Suggestion",
+            ArtistAction::NextSearchFilter => "Next Search Filter / Accept Suggestion",
+            ArtistAction::PrevSearchFilter => "Prev Search Filter",
+            ArtistAction::ToggleFilter => "Filter",
+            ArtistAction::ApplyFilter => "Apply filter",
+            ArtistAction::ClearFilter => "Clear filter",
+        })
         .into()
     }
 }
 
 impl TextHandler for SearchBlock {
     fn push_text(&mut self, c: char) {
-        self.search_contents.push(c);
-        self.text_cur += 1;
+        insert_at_cursor(&mut self.search_contents, &mut self.text_cur, c);
     }
     fn pop_text(&mut self) {
-        self.search_contents.pop();
-        self.text_cur = self.text_cur.saturating_sub(1);
+        remove_before_cursor(&mut self.search_contents, &mut self.text_cur);
     }
     fn is_text_handling(&self) -> bool {
         true
     }
+    fn edit_text(&mut self, edit: TextEdit) -> bool {
+        // Right accepts the highlighted suggestion instead, if there is one.
+        if edit == TextEdit::CursorRight && self.suggestions_cur.is_some() {
+            return false;
+        }
+        edit.apply(&mut self.search_contents, &mut self.text_cur);
+        true
+    }
     fn take_text(&mut self) -> String {
         self.text_cur = 0;
         self.search_suggestions.clear();
@@ -123,34 +234,75 @@ impl SearchBlock {
                     })
                     .unwrap_or_default(),
             );
-            // Safe - clamped above
-            // Clone is ok here as we want to duplicate the search suggestion.
-            self.search_contents = self.search_suggestions
-                [self.suggestions_cur.expect("Set to non-None value above")]
-            .get_text();
-            self.move_cursor_to_end();
         }
     }
+    /// Replace the search contents with the highlighted suggestion, returning true if there was
+    /// one.
+    pub fn accept_suggestion(&mut self) -> bool {
+        let Some(suggestion) = self
+            .suggestions_cur
+            .and_then(|cur| self.search_suggestions.get(cur))
+        else {
+            return false;
+        };
+        self.search_contents = suggestion.get_text();
+        self.suggestions_cur = None;
+        self.move_cursor_to_end();
+        true
+    }
     fn move_cursor_to_end(&mut self) {
-        self.text_cur = self.search_contents.len();
+        self.text_cur = self.search_contents.chars().count();
     }
 }
 
 impl TextHandler for ArtistSearchPanel {
     fn push_text(&mut self, c: char) {
-        self.search.push_text(c);
+        if self.route == ArtistInputRouting::Filter {
+            insert_at_cursor(&mut self.filter_text, &mut self.filter_cur, c);
+            self.increment_list(0);
+        } else {
+            self.search.push_text(c);
+        }
     }
     fn pop_text(&mut self) {
-        self.search.pop_text();
+        if self.route == ArtistInputRouting::Filter {
+            remove_before_cursor(&mut self.filter_text, &mut self.filter_cur);
+            self.increment_list(0);
+        } else {
+            self.search.pop_text();
+        }
     }
     fn is_text_handling(&self) -> bool {
-        self.route == ArtistInputRouting::Search
+        matches!(
+            self.route,
+            ArtistInputRouting::Search | ArtistInputRouting::Filter
+        )
     }
     fn take_text(&mut self) -> String {
-        self.search.take_text()
+        if self.route == ArtistInputRouting::Filter {
+            self.filter_cur = 0;
+            std::mem::take(&mut self.filter_text)
+        } else {
+            self.search.take_text()
+        }
     }
     fn replace_text(&mut self, text: String) {
-        self.search.replace_text(text)
+        if self.route == ArtistInputRouting::Filter {
+            self.filter_text = text;
+            self.filter_cur = self.filter_text.chars().count();
+            self.increment_list(0);
+        } else {
+            self.search.replace_text(text)
+        }
+    }
+    fn edit_text(&mut self, edit: TextEdit) -> bool {
+        if self.route == ArtistInputRouting::Filter {
+            edit.apply(&mut self.filter_text, &mut self.filter_cur);
+            self.increment_list(0);
+            true
+        } else {
+            self.search.edit_text(edit)
+        }
     }
 }
 
@@ -167,7 +319,12 @@ impl KeyRouter<BrowserAction> for ArtistSearchPanel {
     fn get_all_keybinds<'a>(
         &'a self,
     ) -> Box<dyn Iterator<Item = &'a KeyCommand<BrowserAction>> + 'a> {
-        Box::new(self.keybinds.iter().chain(self.search_keybinds.iter()))
+        Box::new(
+            self.keybinds
+                .iter()
+                .chain(self.search_keybinds.iter())
+                .chain(self.filter_keybinds.iter()),
+        )
     }
     fn get_routed_keybinds<'a>(
         &'a self,
@@ -175,6 +332,7 @@ impl KeyRouter<BrowserAction> for ArtistSearchPanel {
         Box::new(match self.route {
             ArtistInputRouting::List => self.keybinds.iter(),
             ArtistInputRouting::Search => self.search_keybinds.iter(),
+            ArtistInputRouting::Filter => self.filter_keybinds.iter(),
         })
     }
 }
@@ -192,6 +350,15 @@ impl Scrollable for ArtistSearchPanel {
     }
 }
 
+impl TypeAheadJump for ArtistSearchPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        self.get_filtered_list()
+            .into_iter()
+            .map(|a| a.artist.as_str().into())
+            .collect()
+    }
+}
+
 impl SortableList for ArtistSearchPanel {
     // Could instead be lazy
     fn push_sort_command(&mut self, _list_sort_command: String) {
@@ -203,20 +370,36 @@ impl SortableList for ArtistSearchPanel {
 }
 impl Loadable for ArtistSearchPanel {
     fn is_loading(&self) -> bool {
-        // This is just a basic list without a loading function.
-        false
+        // This is just a basic list that keeps its results while searching, so is only shown
+        // as loading while a search is being retried.
+        self.retrying
+    }
+    fn is_retrying(&self) -> bool {
+        self.retrying
     }
 }
 impl ListView for ArtistSearchPanel {
     type DisplayItem = String;
     fn get_items_display(&self) -> Vec<&Self::DisplayItem> {
-        self.list
-            .iter()
+        self.get_filtered_list()
+            .into_iter()
             .map(|search_result| &search_result.artist)
             .collect()
     }
     fn get_title(&self) -> Cow<str> {
-        "Artists".into()
+        let subscribed = self
+            .get_selected_artist()
+            .and_then(|a| self.is_subscribed(&a.browse_id));
+        let title = match subscribed {
+            Some(true) => tr("Artists - Subscribed"),
+            Some(false) => tr("Artists - Not subscribed"),
+            None => tr("Artists"),
+        };
+        if self.filter_text.is_empty() {
+            title.into()
+        } else {
+            tr_format("{} - filter: {}", &[&title, &self.filter_text]).into()
+        }
     }
 }
 fn search_keybinds() -> Vec<KeyCommand<BrowserAction>> {
@@ -230,6 +413,33 @@ fn search_keybinds() -> Vec<KeyCommand<BrowserAction>> {
             KeyCode::Up,
             BrowserAction::Artist(ArtistAction::PrevSearchSuggestion),
         ),
+        KeyCommand::new_from_code(
+            KeyCode::Right,
+            BrowserAction::Artist(ArtistAction::AcceptSearchSuggestion),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Tab,
+            BrowserAction::Artist(ArtistAction::NextSearchFilter),
+        ),
+        KeyCommand::new_modified_from_code(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+            BrowserAction::Artist(ArtistAction::PrevSearchFilter),
+        ),
+    ]
+}
+fn filter_keybinds() -> Vec<KeyCommand<BrowserAction>> {
+    vec![
+        KeyCommand::new_global_from_code(
+            KeyCode::Enter,
+            BrowserAction::Artist(ArtistAction::ApplyFilter),
+        ),
+        KeyCommand::new_global_from_code(
+            KeyCode::Esc,
+            BrowserAction::Artist(ArtistAction::ClearFilter),
+        ),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, BrowserAction::Artist(ArtistAction::Down)),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, BrowserAction::Artist(ArtistAction::Up)),
     ]
 }
 fn browser_artist_search_keybinds() -> Vec<KeyCommand<BrowserAction>> {
@@ -238,6 +448,18 @@ fn browser_artist_search_keybinds() -> Vec<KeyCommand<BrowserAction>> {
             KeyCode::Enter,
             BrowserAction::Artist(ArtistAction::DisplayAlbums),
         ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('i'),
+            BrowserAction::Artist(ArtistAction::DisplayDetails),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('s'),
+            BrowserAction::Artist(ArtistAction::ToggleSubscribed),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('/'),
+            BrowserAction::Artist(ArtistAction::ToggleFilter),
+        ),
         // XXX: Consider if these type of actions can be for all lists.
         KeyCommand::new_hidden_from_code(KeyCode::Down, BrowserAction::Artist(ArtistAction::Down)),
         KeyCommand::new_hidden_from_code(KeyCode::Up, BrowserAction::Artist(ArtistAction::Up)),
@@ -246,5 +468,7 @@ fn browser_artist_search_keybinds() -> Vec<KeyCommand<BrowserAction>> {
             KeyCode::PageDown,
             BrowserAction::Artist(ArtistAction::PageDown),
         ),
+        KeyCommand::new_from_code(KeyCode::Home, BrowserAction::Artist(ArtistAction::Home)),
+        KeyCommand::new_from_code(KeyCode::End, BrowserAction::Artist(ArtistAction::End)),
     ]
 }
@@ -2,11 +2,15 @@ use super::artistalbums::albumsongs::{AlbumSongsInputRouting, AlbumSongsPanel};
 use super::artistalbums::artistsearch::ArtistInputRouting;
 use super::{Browser, InputRouting};
 use crate::app::component::actionhandler::Suggestable;
-use crate::app::view::draw::{draw_list, draw_sortable_table};
+use crate::app::view::draw::{
+    draw_list, draw_sortable_table, draw_table, get_table_sort_character_array,
+};
 use crate::app::view::{SortableTableView, TableView};
 use crate::drawutils::{
-    below_left_rect, bottom_of_rect, ROW_HIGHLIGHT_COLOUR, SELECTED_BORDER_COLOUR, TEXT_COLOUR,
+    below_left_rect, bottom_of_rect, icon, ROW_HIGHLIGHT_COLOUR, SELECTED_BORDER_COLOUR,
+    TEXT_COLOUR,
 };
+use crate::i18n::tr;
 use ratatui::widgets::TableState;
 use ratatui::{
     prelude::{Constraint, Direction, Layout, Rect},
@@ -26,23 +30,45 @@ pub fn draw_browser(
     chunk: Rect,
     artist_list_state: &mut ListState,
     album_songs_table_state: &mut TableState,
+    search_results_table_state: &mut TableState,
     selected: bool,
 ) {
     let layout = Layout::new(
         ratatui::prelude::Direction::Horizontal,
-        [Constraint::Max(30), Constraint::Min(0)],
+        [Constraint::Max(browser.artist_list_width), Constraint::Min(0)],
     )
     .split(chunk);
     // Potentially could handle this better.
     let albumsongsselected = selected
         && browser.input_routing == InputRouting::Song
         && browser.album_songs_list.route == AlbumSongsInputRouting::List;
+    let resultsselected = selected && browser.input_routing == InputRouting::Results;
     let artistselected = !albumsongsselected
         && selected
         && browser.input_routing == InputRouting::Artist
         && browser.artist_list.route == ArtistInputRouting::List;
 
-    if !browser.artist_list.search_popped {
+    if browser.artist_list.route == ArtistInputRouting::Filter {
+        let s = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(0)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(layout[0]);
+        draw_list(
+            f,
+            &browser.artist_list,
+            s[1],
+            artistselected,
+            artist_list_state,
+        );
+        draw_text_box(
+            f,
+            tr("Filter"),
+            browser.artist_list.filter_text.as_str(),
+            browser.artist_list.filter_cur,
+            s[0],
+        );
+    } else if !browser.artist_list.search_popped {
         draw_list(
             f,
             &browser.artist_list,
@@ -69,6 +95,17 @@ pub fn draw_browser(
             draw_search_suggestions(f, &browser, s[0], layout[0])
         }
     }
+    // Search results are displayed in place of the album songs, until an artist is selected.
+    if browser.search_results.shown {
+        draw_table(
+            f,
+            &browser.search_results,
+            layout[1],
+            search_results_table_state,
+            resultsselected,
+        );
+        return;
+    }
     draw_sortable_table(
         f,
         &browser.album_songs_list,
@@ -86,15 +123,21 @@ pub fn draw_browser(
 
 // TODO: Generalize
 fn draw_sort_popup(f: &mut Frame, album_songs_panel: &AlbumSongsPanel, chunk: Rect) {
-    let title = "Sort";
+    let title = tr("Sort");
     let sortable_columns = album_songs_panel.get_sortable_columns();
+    let sort_chars = get_table_sort_character_array(album_songs_panel.get_sort_commands());
     let headers: Vec<_> = album_songs_panel
         .get_headings()
         .enumerate()
         .filter_map(|(i, h)| {
             if sortable_columns.contains(&i) {
+                // Show the direction each column is currently sorted in.
+                let sort_char = sort_chars.get(i).copied().flatten();
                 // TODO: Remove allocation
-                Some(ListItem::new(h))
+                Some(ListItem::new(match sort_char {
+                    Some(c) => format!("{h} {c}"),
+                    None => h.to_string(),
+                }))
             } else {
                 None
             }
@@ -106,7 +149,6 @@ fn draw_sort_popup(f: &mut Frame, album_songs_panel: &AlbumSongsPanel, chunk: Re
     let width = max_header_len.max(title.len()).max(MIN_POPUP_WIDTH) + 2;
     let height = sortable_columns.len() + 2;
     let popup_chunk = crate::drawutils::centered_rect(height as u16, width as u16, chunk);
-    // TODO: Save the state.
     let mut state = ListState::default().with_selected(Some(album_songs_panel.sort.cur));
     let list = List::new(headers)
         .highlight_style(Style::default().bg(ROW_HIGHLIGHT_COLOUR))
@@ -121,7 +163,7 @@ fn draw_sort_popup(f: &mut Frame, album_songs_panel: &AlbumSongsPanel, chunk: Re
 }
 
 fn draw_filter_popup(f: &mut Frame, album_songs_panel: &AlbumSongsPanel, chunk: Rect) {
-    let title = "Filter";
+    let title = tr("Filter");
     // Hardocde dimensions of filter input.
     let popup_chunk = crate::drawutils::centered_rect(3, 22, chunk);
     f.render_widget(Clear, popup_chunk);
@@ -153,7 +195,7 @@ fn draw_text_box<S: AsRef<str>>(f: &mut Frame, title: S, contents: S, cur: usize
 fn draw_search_box(f: &mut Frame, browser: &Browser, chunk: Rect) {
     draw_text_box(
         f,
-        "Search",
+        format!("Search [{}]", browser.artist_list.search.filter).as_str(),
         browser.artist_list.search.search_contents.as_str(),
         browser.artist_list.search.text_cur,
         chunk,
@@ -182,8 +224,8 @@ fn draw_search_suggestions(f: &mut Frame, browser: &Browser, chunk: Rect, max_bo
             ListItem::new(Line::from(
                 std::iter::once(s.suggestion_type)
                     .map(|ty| match ty {
-                        SuggestionType::History => Span::raw(" "),
-                        SuggestionType::Prediction => Span::raw(" "),
+                        SuggestionType::History => Span::raw(format!("{} ", icon('', 'H'))),
+                        SuggestionType::Prediction => Span::raw(format!("{} ", icon('', '?'))),
                     })
                     .chain(s.runs.iter().map(|s| match s {
                         TextRun::Bold(str) => {
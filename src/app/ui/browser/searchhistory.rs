@@ -0,0 +1,147 @@
+use crate::app::datafile::{load_data_file, save_data_file};
+use crate::Result;
+use ytmapi_rs::common::{SearchSuggestion, SuggestionType, TextRun};
+
+const SEARCH_HISTORY_FILE_NAME: &str = "search_history.json";
+const MAX_SEARCH_HISTORY_LEN: usize = 100;
+// Only the most recent searches are shown before anything has been typed.
+const MAX_EMPTY_QUERY_SUGGESTIONS: usize = 10;
+
+/// Previous browser searches, most recent first.
+/// Stored in the data directory.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct SearchHistory {
+    entries: Vec<String>,
+}
+
+impl SearchHistory {
+    /// Load the search history from the data directory, or an empty history if it doesn't exist.
+    pub fn load() -> Self {
+        let mut entries: Vec<String> = load_data_file(SEARCH_HISTORY_FILE_NAME).unwrap_or_default();
+        entries.truncate(MAX_SEARCH_HISTORY_LEN);
+        Self { entries }
+    }
+    /// Add a search to the history and save it to the data directory.
+    /// History is reloaded first, as other browser tabs may have added to it.
+    pub fn push(&mut self, query: &str) {
+        *self = Self::load();
+        self.add_entry(query);
+        if let Err(e) = self.save() {
+            tracing::warn!("Error <{e}> saving search history");
+        }
+    }
+    /// Previous searches starting with the query, as history type search suggestions.
+    pub fn get_suggestions(&self, query: &str) -> Vec<SearchSuggestion> {
+        let max = if query.is_empty() {
+            MAX_EMPTY_QUERY_SUGGESTIONS
+        } else {
+            MAX_SEARCH_HISTORY_LEN
+        };
+        let query_lower = query.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|e| e.to_lowercase().starts_with(&query_lower))
+            .take(max)
+            .map(|e| {
+                // Embolden the part that hasn't been typed yet, like the API does.
+                // Lowercasing can change byte lengths, so check the split is still valid.
+                let split = if e.is_char_boundary(query.len()) {
+                    query.len()
+                } else {
+                    0
+                };
+                let (typed, rest) = e.split_at(split);
+                SearchSuggestion::new(
+                    SuggestionType::History,
+                    vec![TextRun::Normal(typed.into()), TextRun::Bold(rest.into())],
+                )
+            })
+            .collect()
+    }
+    fn add_entry(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.entries.retain(|e| e != query);
+        self.entries.insert(0, query.to_string());
+        self.entries.truncate(MAX_SEARCH_HISTORY_LEN);
+    }
+    fn save(&self) -> Result<()> {
+        save_data_file(SEARCH_HISTORY_FILE_NAME, &self.entries)
+    }
+}
+
+/// Merge local history into the API's suggestions. History is shown first, and suggestions
+/// duplicating a local history entry are removed.
+pub fn merge_suggestions(
+    history: Vec<SearchSuggestion>,
+    api_suggestions: Vec<SearchSuggestion>,
+) -> Vec<SearchSuggestion> {
+    let history_text: Vec<String> = history.iter().map(|s| s.get_text()).collect();
+    history
+        .into_iter()
+        .chain(
+            api_suggestions
+                .into_iter()
+                .filter(|s| !history_text.contains(&s.get_text())),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_suggestions, SearchHistory};
+    use ytmapi_rs::common::{SearchSuggestion, SuggestionType, TextRun};
+
+    fn search_history(entries: &[&str]) -> SearchHistory {
+        SearchHistory {
+            entries: entries.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+    #[test]
+    fn test_add_entry_moves_duplicate_to_front() {
+        let mut history = search_history(&["beatles", "queen"]);
+        history.add_entry("queen");
+        history.add_entry("  ");
+        assert_eq!(history, search_history(&["queen", "beatles"]));
+    }
+    #[test]
+    fn test_get_suggestions_matches_prefix_case_insensitively() {
+        let history = search_history(&["Queen", "beatles", "queens of the stone age"]);
+        let suggestions: Vec<_> = history
+            .get_suggestions("que")
+            .iter()
+            .map(|s| s.get_text())
+            .collect();
+        assert_eq!(suggestions, vec!["Queen", "queens of the stone age"]);
+    }
+    #[test]
+    fn test_merge_suggestions_removes_duplicates() {
+        let history = search_history(&["queen"]).get_suggestions("q");
+        let api = vec![
+            SearchSuggestion::new(
+                SuggestionType::Prediction,
+                vec![TextRun::Normal("q".into()), TextRun::Bold("ueen".into())],
+            ),
+            SearchSuggestion::new(
+                SuggestionType::Prediction,
+                vec![
+                    TextRun::Normal("q".into()),
+                    TextRun::Bold("uiet riot".into()),
+                ],
+            ),
+        ];
+        let merged: Vec<_> = merge_suggestions(history, api)
+            .iter()
+            .map(|s| (s.suggestion_type, s.get_text()))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![
+                (SuggestionType::History, "queen".to_string()),
+                (SuggestionType::Prediction, "quiet riot".to_string())
+            ]
+        );
+    }
+}
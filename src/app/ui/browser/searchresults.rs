@@ -0,0 +1,459 @@
+use crate::app::{
+    component::actionhandler::{Action, KeyRouter},
+    keycommand::KeyCommand,
+    server::structures::{SearchFilter, SearchResultsList},
+    structures::{ListStatus, Percentage},
+    ui::browser::BrowserAction,
+    view::{
+        get_table_column_texts, BasicConstraint, Loadable, Scrollable, TableItem, TableView,
+        TypeAheadJump,
+    },
+};
+use crate::i18n::{tr, tr_format};
+use crossterm::event::KeyCode;
+use std::borrow::Cow;
+use ytmapi_rs::parse::{
+    SearchResultAlbum, SearchResultArtist, SearchResultCommunityPlaylist,
+    SearchResultFeaturedPlaylist, SearchResultPlaylist, SearchResultPodcast, SearchResultSong,
+    SearchResultVideo, SearchResults, TopResult,
+};
+
+/// Displays results for searches other than artist searches.
+/// Songs and videos can be queued directly, other results are fetched before queueing.
+pub struct SearchResultsPanel {
+    pub results: Option<SearchResultsList>,
+    pub state: ListStatus,
+    /// Filter of the most recent search, used for the title.
+    pub filter: SearchFilter,
+    /// Whether the results panel is displayed in place of the album songs panel.
+    pub shown: bool,
+    /// Unfiltered results flattened into a single list, in the order they are displayed.
+    /// Built when the results are received, rather than every draw.
+    all_results_index: Vec<(ResultSection, usize)>,
+    cur_selected: usize,
+    keybinds: Vec<KeyCommand<BrowserAction>>,
+}
+
+/// Section of the unfiltered search results that a result belongs to.
+/// Mirrors the sections of the YouTube Music search page.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ResultSection {
+    TopResult,
+    Song,
+    Album,
+    Artist,
+    FeaturedPlaylist,
+    CommunityPlaylist,
+    Video,
+    Podcast,
+}
+
+/// A single search result, borrowed from the underlying results list.
+pub enum SearchResultItem<'a> {
+    TopResult(&'a TopResult),
+    Song(&'a SearchResultSong),
+    Album(&'a SearchResultAlbum),
+    Artist(&'a SearchResultArtist),
+    FeaturedPlaylist(&'a SearchResultFeaturedPlaylist),
+    CommunityPlaylist(&'a SearchResultCommunityPlaylist),
+    Video(&'a SearchResultVideo),
+    Podcast(&'a SearchResultPodcast),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchResultsAction {
+    Play,
+    AddToPlaylist,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+impl SearchResultsPanel {
+    pub fn new() -> Self {
+        Self {
+            results: None,
+            state: ListStatus::New,
+            filter: SearchFilter::Songs,
+            shown: false,
+            all_results_index: Vec::new(),
+            cur_selected: 0,
+            keybinds: search_results_keybinds(),
+        }
+    }
+    pub fn handle_search_started(&mut self, filter: SearchFilter) {
+        self.results = None;
+        self.all_results_index.clear();
+        self.filter = filter;
+        self.state = ListStatus::Loading;
+        self.shown = true;
+        self.cur_selected = 0;
+    }
+    pub fn handle_replace_results(&mut self, results: SearchResultsList) {
+        self.filter = results.filter();
+        self.all_results_index = match &results {
+            SearchResultsList::All(r) => all_results_index(r),
+            _ => Vec::new(),
+        };
+        self.results = Some(results);
+        self.state = ListStatus::Loaded;
+        self.increment_list(0);
+    }
+    pub fn handle_search_error(&mut self) {
+        self.results = None;
+        self.all_results_index.clear();
+        self.state = ListStatus::Error;
+    }
+    pub fn handle_search_retrying(&mut self) {
+        self.state.set_retrying();
+    }
+    pub fn get_selected_result(&self) -> Option<SearchResultItem> {
+        let cur = self.cur_selected;
+        match self.results.as_ref()? {
+            SearchResultsList::Songs(l) => l.get(cur).map(SearchResultItem::Song),
+            SearchResultsList::Albums(l) => l.get(cur).map(SearchResultItem::Album),
+            SearchResultsList::Playlists(l) => l.get(cur).map(|p| match p {
+                SearchResultPlaylist::Featured(p) => SearchResultItem::FeaturedPlaylist(p),
+                SearchResultPlaylist::Community(p) => SearchResultItem::CommunityPlaylist(p),
+            }),
+            SearchResultsList::Videos(l) => l.get(cur).map(SearchResultItem::Video),
+            SearchResultsList::Podcasts(l) => l.get(cur).map(SearchResultItem::Podcast),
+            SearchResultsList::All(r) => {
+                let (section, idx) = *self.all_results_index.get(cur)?;
+                get_result_item(r, section, idx)
+            }
+        }
+    }
+}
+
+/// Flatten unfiltered search results into a single list of indexes, in the order they are
+/// displayed.
+fn all_results_index(results: &SearchResults) -> Vec<(ResultSection, usize)> {
+    [
+        (ResultSection::TopResult, results.top_results.len()),
+        (ResultSection::Song, results.songs.len()),
+        (ResultSection::Album, results.albums.len()),
+        (ResultSection::Artist, results.artists.len()),
+        (
+            ResultSection::FeaturedPlaylist,
+            results.featured_playlists.len(),
+        ),
+        (
+            ResultSection::CommunityPlaylist,
+            results.community_playlists.len(),
+        ),
+        (ResultSection::Video, results.videos.len()),
+        (ResultSection::Podcast, results.podcasts.len()),
+    ]
+    .into_iter()
+    .flat_map(|(section, len)| (0..len).map(move |idx| (section, idx)))
+    .collect()
+}
+
+fn get_result_item(
+    results: &SearchResults,
+    section: ResultSection,
+    idx: usize,
+) -> Option<SearchResultItem> {
+    match section {
+        ResultSection::TopResult => results
+            .top_results
+            .get(idx)
+            .map(SearchResultItem::TopResult),
+        ResultSection::Song => results.songs.get(idx).map(SearchResultItem::Song),
+        ResultSection::Album => results.albums.get(idx).map(SearchResultItem::Album),
+        ResultSection::Artist => results.artists.get(idx).map(SearchResultItem::Artist),
+        ResultSection::FeaturedPlaylist => results
+            .featured_playlists
+            .get(idx)
+            .map(SearchResultItem::FeaturedPlaylist),
+        ResultSection::CommunityPlaylist => results
+            .community_playlists
+            .get(idx)
+            .map(SearchResultItem::CommunityPlaylist),
+        ResultSection::Video => results.videos.get(idx).map(SearchResultItem::Video),
+        ResultSection::Podcast => results.podcasts.get(idx).map(SearchResultItem::Podcast),
+    }
+}
+
+impl<'a> SearchResultItem<'a> {
+    /// Row for the unfiltered results table - section, title, subtitle and detail.
+    fn as_row(&self) -> [&'a str; 4] {
+        match *self {
+            Self::TopResult(r) => [
+                "Top result",
+                r.result_name.as_str(),
+                r.artist
+                    .as_deref()
+                    .or(r.publisher.as_deref())
+                    .unwrap_or_default(),
+                r.duration
+                    .as_deref()
+                    .or(r.year.as_deref())
+                    .or(r.subscribers.as_deref())
+                    .unwrap_or_default(),
+            ],
+            Self::Song(s) => ["Song", &s.title, &s.artist, &s.duration],
+            Self::Album(a) => ["Album", &a.title, &a.artist, &a.year],
+            Self::Artist(a) => [
+                "Artist",
+                &a.artist,
+                "",
+                a.subscribers.as_deref().unwrap_or_default(),
+            ],
+            Self::FeaturedPlaylist(p) => ["Playlist", &p.title, &p.author, &p.songs],
+            Self::CommunityPlaylist(p) => ["Playlist", &p.title, &p.author, &p.views],
+            Self::Video(v) => ["Video", &v.title, &v.channel_name, &v.length],
+            Self::Podcast(p) => ["Podcast", &p.title, &p.publisher, ""],
+        }
+    }
+}
+
+impl Action for SearchResultsAction {
+    fn context(&self) -> Cow<str> {
+        tr("Search Results Panel").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            Self::Play => "Play",
+            Self::AddToPlaylist => "Add to playlist",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::PageUp => "Page Up",
+            Self::PageDown => "Page Down",
+            Self::Home => "Top",
+            Self::End => "Bottom",
+        })
+        .into()
+    }
+}
+
+impl KeyRouter<BrowserAction> for SearchResultsPanel {
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<BrowserAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<BrowserAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+}
+
+impl Loadable for SearchResultsPanel {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+
+impl Scrollable for SearchResultsPanel {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+
+impl TypeAheadJump for SearchResultsPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+
+impl TableView for SearchResultsPanel {
+    fn get_title(&self) -> Cow<str> {
+        let filter = self.filter;
+        match self.state {
+            ListStatus::New => filter.to_string().into(),
+            ListStatus::Loading | ListStatus::InProgress => {
+                tr_format("{} - loading", &[&filter]).into()
+            }
+            ListStatus::Retrying => tr_format("{} - retrying", &[&filter]).into(),
+            ListStatus::Loaded => tr_format("{} - {} results", &[&filter, &self.len()]).into(),
+            ListStatus::Error => tr_format("{} - Error receieved", &[&filter]).into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        match self.results {
+            Some(SearchResultsList::Songs(_)) => &[
+                BasicConstraint::Percentage(Percentage(40)),
+                BasicConstraint::Percentage(Percentage(30)),
+                BasicConstraint::Percentage(Percentage(30)),
+                BasicConstraint::Length(10),
+            ],
+            Some(SearchResultsList::Albums(_)) => &[
+                BasicConstraint::Percentage(Percentage(60)),
+                BasicConstraint::Percentage(Percentage(40)),
+                BasicConstraint::Length(5),
+            ],
+            Some(SearchResultsList::Videos(_)) => &[
+                BasicConstraint::Percentage(Percentage(60)),
+                BasicConstraint::Percentage(Percentage(40)),
+                BasicConstraint::Length(10),
+            ],
+            Some(SearchResultsList::Playlists(_)) | Some(SearchResultsList::Podcasts(_)) => &[
+                BasicConstraint::Percentage(Percentage(60)),
+                BasicConstraint::Percentage(Percentage(40)),
+            ],
+            Some(SearchResultsList::All(_)) => &[
+                BasicConstraint::Length(10),
+                BasicConstraint::Percentage(Percentage(60)),
+                BasicConstraint::Percentage(Percentage(40)),
+                BasicConstraint::Length(10),
+            ],
+            None => &[BasicConstraint::Percentage(Percentage(100))],
+        }
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        match &self.results {
+            Some(SearchResultsList::Songs(l)) => Box::new(l.iter().map(|s| {
+                Box::new(
+                    [
+                        s.title.as_str(),
+                        s.artist.as_str(),
+                        s.album.as_str(),
+                        s.duration.as_str(),
+                    ]
+                    .into_iter()
+                    .map(Cow::from),
+                ) as TableItem
+            })),
+            Some(SearchResultsList::Albums(l)) => Box::new(l.iter().map(|a| {
+                Box::new(
+                    [a.title.as_str(), a.artist.as_str(), a.year.as_str()]
+                        .into_iter()
+                        .map(Cow::from),
+                ) as TableItem
+            })),
+            Some(SearchResultsList::Videos(l)) => Box::new(l.iter().map(|v| {
+                Box::new(
+                    [v.title.as_str(), v.channel_name.as_str(), v.length.as_str()]
+                        .into_iter()
+                        .map(Cow::from),
+                ) as TableItem
+            })),
+            Some(SearchResultsList::Playlists(l)) => Box::new(l.iter().map(|p| {
+                let (title, author) = match p {
+                    SearchResultPlaylist::Featured(p) => (&p.title, &p.author),
+                    SearchResultPlaylist::Community(p) => (&p.title, &p.author),
+                };
+                Box::new([title.as_str(), author.as_str()].into_iter().map(Cow::from)) as TableItem
+            })),
+            Some(SearchResultsList::Podcasts(l)) => Box::new(l.iter().map(|p| {
+                Box::new(
+                    [p.title.as_str(), p.publisher.as_str()]
+                        .into_iter()
+                        .map(Cow::from),
+                ) as TableItem
+            })),
+            Some(SearchResultsList::All(r)) => {
+                Box::new(self.all_results_index.iter().map(|&(section, idx)| {
+                    let row = get_result_item(r, section, idx)
+                        .map(|item| item.as_row())
+                        .unwrap_or_default();
+                    Box::new(row.into_iter().map(Cow::from)) as TableItem
+                }))
+            }
+            None => Box::new(std::iter::empty()),
+        }
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        let headings: &'static [&'static str] = match self.results {
+            Some(SearchResultsList::Songs(_)) => &["Song", "Artist", "Album", "Duration"],
+            Some(SearchResultsList::Albums(_)) => &["Album", "Artist", "Year"],
+            Some(SearchResultsList::Videos(_)) => &["Video", "Channel", "Length"],
+            Some(SearchResultsList::Playlists(_)) => &["Playlist", "Author"],
+            Some(SearchResultsList::Podcasts(_)) => &["Podcast", "Publisher"],
+            Some(SearchResultsList::All(_)) => &["Section", "Title", "Artist", "Detail"],
+            None => &["Results"],
+        };
+        Box::new(headings.iter().copied())
+    }
+}
+
+fn search_results_keybinds() -> Vec<KeyCommand<BrowserAction>> {
+    vec![
+        KeyCommand::new_from_code(
+            KeyCode::Enter,
+            BrowserAction::SearchResults(SearchResultsAction::Play),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Char('a'),
+            BrowserAction::SearchResults(SearchResultsAction::AddToPlaylist),
+        ),
+        KeyCommand::new_hidden_from_code(
+            KeyCode::Down,
+            BrowserAction::SearchResults(SearchResultsAction::Down),
+        ),
+        KeyCommand::new_hidden_from_code(
+            KeyCode::Up,
+            BrowserAction::SearchResults(SearchResultsAction::Up),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::PageUp,
+            BrowserAction::SearchResults(SearchResultsAction::PageUp),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::PageDown,
+            BrowserAction::SearchResults(SearchResultsAction::PageDown),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::Home,
+            BrowserAction::SearchResults(SearchResultsAction::Home),
+        ),
+        KeyCommand::new_from_code(
+            KeyCode::End,
+            BrowserAction::SearchResults(SearchResultsAction::End),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all_results_index, ResultSection};
+    use ytmapi_rs::{
+        common::{PodcastID, YoutubeID},
+        parse::{SearchResultArtist, SearchResultPodcast, SearchResults},
+        ChannelID,
+    };
+
+    #[test]
+    fn test_all_results_index_in_display_order() {
+        let artist = SearchResultArtist {
+            artist: "Artist".into(),
+            subscribers: None,
+            browse_id: ChannelID::from_raw("UC1"),
+            thumbnails: Vec::new(),
+        };
+        let podcast = SearchResultPodcast {
+            title: "Podcast".into(),
+            publisher: "Publisher".into(),
+            podcast_id: PodcastID::from_raw("MPSP1"),
+            thumbnails: Vec::new(),
+        };
+        let results = SearchResults {
+            podcasts: vec![podcast],
+            artists: vec![artist.clone(), artist],
+            ..Default::default()
+        };
+        assert_eq!(
+            all_results_index(&results),
+            vec![
+                (ResultSection::Artist, 0),
+                (ResultSection::Artist, 1),
+                (ResultSection::Podcast, 0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::app::datafile::{load_data_file, save_data_file};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+const BROWSER_SESSION_FILE_NAME: &str = "browser_session.json";
+
+/// State of the browser when youtui was last closed, stored in the data directory so that it can
+/// be restored on startup.
+/// Results aren't stored, instead they are requested again using the search and artist.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserSession {
+    /// Most recent artist search.
+    pub search: Option<String>,
+    /// Browse id of the artist whose songs were shown.
+    pub artist_id: Option<String>,
+    /// Video id of the selected song in the artist's songs.
+    pub song_id: Option<String>,
+}
+
+impl BrowserSession {
+    /// Load the session from the data directory, or None if it doesn't exist or can't be read.
+    pub fn load() -> Option<Self> {
+        load_data_file(BROWSER_SESSION_FILE_NAME)
+    }
+    pub fn save(&self) -> Result<()> {
+        save_data_file(BROWSER_SESSION_FILE_NAME, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BrowserSession;
+
+    #[test]
+    fn test_session_round_trip() {
+        let session = BrowserSession {
+            search: Some("queen".into()),
+            artist_id: Some("UCiMhD4jzUqG-IgPzUmmytRQ".into()),
+            song_id: None,
+        };
+        let serialized = serde_json::to_string(&session).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BrowserSession>(&serialized).unwrap(),
+            session
+        );
+    }
+    #[test]
+    fn test_session_missing_fields_default() {
+        let session: BrowserSession = serde_json::from_str("{\"search\": \"queen\"}").unwrap();
+        assert_eq!(session.artist_id, None);
+        assert_eq!(session.search.as_deref(), Some("queen"));
+    }
+}
@@ -0,0 +1,520 @@
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, DominantKeyRouter, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{AlbumSongsList, ListSong, ListStatus, Percentage},
+    ui::{AppCallback, WindowContext},
+    view::{
+        get_table_column_texts, BasicConstraint, DrawableMut, Loadable, Scrollable, TableItem,
+        TableView, TypeAheadJump,
+    },
+    YoutuiMutableState,
+};
+use crate::core::send_or_error;
+use crate::i18n::tr;
+use crossterm::event::KeyCode;
+use draw::draw_charts;
+use itertools::Itertools;
+use ratatui::{prelude::Rect, Frame};
+use std::{borrow::Cow, fmt::Display};
+use tokio::sync::mpsc::Sender;
+use ytmapi_rs::parse::{ChartSong, Charts as ChartsResults, PlaylistSong, SongResult};
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChartsAction {
+    ViewBrowser,
+    Refresh,
+    NextCategory,
+    PrevCategory,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    PlaySong,
+    AddSongToPlaylist,
+    PlayAll,
+    AddAllToPlaylist,
+    ToggleCountrySelector,
+    CountryUp,
+    CountryDown,
+    SelectCountry,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ChartCategory {
+    #[default]
+    Songs,
+    Videos,
+    Artists,
+    Trending,
+}
+
+/// The top songs, videos and artists for a country.
+pub struct Charts {
+    ui_tx: Sender<AppCallback>,
+    pub results: ChartsResults,
+    pub state: ListStatus,
+    pub category: ChartCategory,
+    pub country_selector: CountrySelector,
+    cur_selected: usize,
+    keybinds: Vec<KeyCommand<ChartsAction>>,
+    pub page_lines: isize,
+}
+
+pub struct CountrySelector {
+    pub shown: bool,
+    pub cur: usize,
+    keybinds: Vec<KeyCommand<ChartsAction>>,
+}
+
+impl ChartCategory {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Songs => Self::Videos,
+            Self::Videos => Self::Artists,
+            Self::Artists => Self::Trending,
+            Self::Trending => Self::Songs,
+        }
+    }
+    pub fn prev(&self) -> Self {
+        match self {
+            Self::Songs => Self::Trending,
+            Self::Videos => Self::Songs,
+            Self::Artists => Self::Videos,
+            Self::Trending => Self::Artists,
+        }
+    }
+}
+
+impl Display for ChartCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Songs => "Top songs",
+            Self::Videos => "Top videos",
+            Self::Artists => "Top artists",
+            Self::Trending => "Trending",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl Action for ChartsAction {
+    fn context(&self) -> Cow<str> {
+        match self {
+            Self::CountryUp | Self::CountryDown | Self::SelectCountry => tr("Country").into(),
+            _ => tr("Charts").into(),
+        }
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            Self::ViewBrowser => "View Browser",
+            Self::Refresh => "Refresh",
+            Self::NextCategory => "Next chart",
+            Self::PrevCategory => "Prev chart",
+            Self::Up | Self::CountryUp => "Up",
+            Self::Down | Self::CountryDown => "Down",
+            Self::PageUp => "Page Up",
+            Self::PageDown => "Page Down",
+            Self::Home => "Top",
+            Self::End => "Bottom",
+            Self::PlaySong => "Play",
+            Self::AddSongToPlaylist => "Add song to playlist",
+            Self::PlayAll => "Play all",
+            Self::AddAllToPlaylist => "Add all to playlist",
+            Self::ToggleCountrySelector => "Country",
+            Self::SelectCountry => "Select country",
+        })
+        .into()
+    }
+}
+
+impl DrawableMut for Charts {
+    fn draw_mut_chunk(
+        &self,
+        f: &mut Frame,
+        chunk: Rect,
+        mutable_state: &mut YoutuiMutableState,
+        selected: bool,
+    ) {
+        draw_charts(f, self, chunk, &mut mutable_state.charts_state, selected)
+    }
+}
+
+impl KeyRouter<ChartsAction> for Charts {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<ChartsAction>> + 'a> {
+        if self.country_selector.shown {
+            Box::new(self.country_selector.keybinds.iter())
+        } else {
+            Box::new(self.keybinds.iter())
+        }
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<ChartsAction>> + 'a> {
+        Box::new(
+            self.keybinds
+                .iter()
+                .chain(self.country_selector.keybinds.iter()),
+        )
+    }
+}
+
+impl DominantKeyRouter for Charts {
+    fn dominant_keybinds_active(&self) -> bool {
+        self.country_selector.shown
+    }
+}
+
+impl TextHandler for Charts {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<ChartsAction> for Charts {
+    async fn handle_action(&mut self, action: &ChartsAction) {
+        match action {
+            ChartsAction::ViewBrowser => self.handle_view_browser().await,
+            ChartsAction::Refresh => self.refresh().await,
+            ChartsAction::NextCategory => self.change_category(self.category.next()),
+            ChartsAction::PrevCategory => self.change_category(self.category.prev()),
+            ChartsAction::Up => self.increment_list(-1),
+            ChartsAction::Down => self.increment_list(1),
+            ChartsAction::PageUp => self.increment_list(-self.page_lines),
+            ChartsAction::PageDown => self.increment_list(self.page_lines),
+            ChartsAction::Home => self.increment_list(isize::MIN),
+            ChartsAction::End => self.increment_list(isize::MAX),
+            ChartsAction::PlaySong => self.play_selected().await,
+            ChartsAction::AddSongToPlaylist => self.add_selected_to_playlist().await,
+            ChartsAction::PlayAll => self.queue_all(true).await,
+            ChartsAction::AddAllToPlaylist => self.queue_all(false).await,
+            ChartsAction::ToggleCountrySelector => self.toggle_country_selector(),
+            ChartsAction::CountryUp => self.increment_country(-1),
+            ChartsAction::CountryDown => self.increment_country(1),
+            ChartsAction::SelectCountry => self.select_country().await,
+        }
+    }
+}
+
+impl Charts {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            results: Default::default(),
+            state: ListStatus::New,
+            category: Default::default(),
+            country_selector: CountrySelector {
+                shown: false,
+                cur: 0,
+                keybinds: country_selector_keybinds(),
+            },
+            cur_selected: 0,
+            keybinds: charts_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Returns true if the charts have not yet been requested.
+    pub fn is_new(&self) -> bool {
+        matches!(self.state, ListStatus::New)
+    }
+    pub async fn refresh(&mut self) {
+        self.state = ListStatus::Loading;
+        send_or_error(&self.ui_tx, AppCallback::GetCharts(None)).await;
+    }
+    pub fn handle_replace_charts(&mut self, results: ChartsResults) {
+        self.results = results;
+        self.state = ListStatus::Loaded;
+        self.increment_list(0);
+    }
+    pub fn handle_charts_error(&mut self) {
+        self.state = ListStatus::Error;
+    }
+    pub fn handle_charts_retrying(&mut self) {
+        self.state.set_retrying();
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    fn change_category(&mut self, category: ChartCategory) {
+        self.category = category;
+        self.cur_selected = 0;
+    }
+    /// The song-like chart for the current category, or None if showing artists.
+    fn cur_songs(&self) -> Option<&[ChartSong]> {
+        match self.category {
+            ChartCategory::Songs => Some(&self.results.songs),
+            ChartCategory::Videos => Some(&self.results.videos),
+            ChartCategory::Trending => Some(&self.results.trending),
+            ChartCategory::Artists => None,
+        }
+    }
+    async fn play_selected(&mut self) {
+        if self.category == ChartCategory::Artists {
+            // Artists can't be played, so display their songs instead.
+            let Some(artist) = self.results.artists.get(self.cur_selected) else {
+                return;
+            };
+            send_or_error(
+                &self.ui_tx,
+                AppCallback::ViewArtist(artist.browse_id.clone()),
+            )
+            .await;
+            return;
+        }
+        let Some(song) = self.cur_songs().and_then(|s| s.get(self.cur_selected)) else {
+            return;
+        };
+        let song_list = chart_songs_to_list(std::slice::from_ref(song));
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::AddSongsToPlaylistAndPlay(song_list),
+        )
+        .await;
+    }
+    async fn add_selected_to_playlist(&mut self) {
+        let Some(song) = self.cur_songs().and_then(|s| s.get(self.cur_selected)) else {
+            return;
+        };
+        let song_list = chart_songs_to_list(std::slice::from_ref(song));
+        send_or_error(&self.ui_tx, AppCallback::AddSongsToPlaylist(song_list)).await;
+    }
+    async fn queue_all(&mut self, play: bool) {
+        let Some(songs) = self.cur_songs() else {
+            return;
+        };
+        // Playlist requires at least one song to be pushed.
+        if songs.is_empty() {
+            return;
+        }
+        let song_list = chart_songs_to_list(songs);
+        let callback = if play {
+            AppCallback::AddSongsToPlaylistAndPlay(song_list)
+        } else {
+            AppCallback::AddSongsToPlaylist(song_list)
+        };
+        send_or_error(&self.ui_tx, callback).await;
+    }
+    fn toggle_country_selector(&mut self) {
+        self.country_selector.shown = !self.country_selector.shown;
+    }
+    fn increment_country(&mut self, amount: isize) {
+        self.country_selector.cur = self
+            .country_selector
+            .cur
+            .saturating_add_signed(amount)
+            .min(self.results.country_options.len().saturating_sub(1));
+    }
+    async fn select_country(&mut self) {
+        self.country_selector.shown = false;
+        let Some(country) = self
+            .results
+            .country_options
+            .get(self.country_selector.cur)
+            .cloned()
+        else {
+            return;
+        };
+        self.state = ListStatus::Loading;
+        send_or_error(&self.ui_tx, AppCallback::GetCharts(Some(country))).await;
+    }
+}
+
+fn chart_songs_to_list(songs: &[ChartSong]) -> Vec<ListSong> {
+    let mut list = AlbumSongsList::default();
+    // Chart songs each have their own artists, like songs on a playlist.
+    list.append_raw_playlist_songs(
+        songs
+            .iter()
+            .map(|s| PlaylistSong {
+                song: SongResult::from(s.clone()),
+                artists: s.artists.clone(),
+            })
+            .collect(),
+    );
+    list.get_list_iter().cloned().collect()
+}
+
+impl Loadable for Charts {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for Charts {
+    fn increment_list(&mut self, amount: isize) {
+        let len = match self.cur_songs() {
+            Some(songs) => songs.len(),
+            None => self.results.artists.len(),
+        };
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(len.saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for Charts {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        // Skip the position column.
+        get_table_column_texts(self, 1)
+    }
+}
+impl TableView for Charts {
+    fn get_title(&self) -> Cow<str> {
+        match self.state {
+            ListStatus::Loaded => format!("{} - {}", self.category, self.results.country).into(),
+            ListStatus::Error => tr("Charts - Error receieved").into(),
+            _ => tr("Charts").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Length(4),
+            BasicConstraint::Percentage(Percentage(60)),
+            BasicConstraint::Percentage(Percentage(40)),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        match self.cur_songs() {
+            Some(songs) => Box::new(songs.iter().enumerate().map(|(i, s)| {
+                Box::new(
+                    [
+                        s.rank
+                            .as_deref()
+                            .map(Cow::from)
+                            .unwrap_or_else(|| (i + 1).to_string().into()),
+                        Cow::from(s.title.as_str()),
+                        Cow::from(s.artists.iter().map(|a| a.name.as_str()).join(", ")),
+                    ]
+                    .into_iter(),
+                ) as TableItem
+            })),
+            None => Box::new(self.results.artists.iter().enumerate().map(|(i, a)| {
+                Box::new(
+                    [
+                        a.rank
+                            .as_deref()
+                            .map(Cow::from)
+                            .unwrap_or_else(|| (i + 1).to_string().into()),
+                        Cow::from(a.title.as_str()),
+                        Cow::from(a.subscribers.as_deref().unwrap_or_default()),
+                    ]
+                    .into_iter(),
+                ) as TableItem
+            })),
+        }
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        match self.category {
+            ChartCategory::Artists => Box::new(["#", "Artist", "Subscribers"].into_iter()),
+            _ => Box::new(["#", "Song", "Artists"].into_iter()),
+        }
+    }
+}
+
+fn charts_keybinds() -> Vec<KeyCommand<ChartsAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), ChartsAction::ViewBrowser),
+        KeyCommand::new_global_from_code(KeyCode::Char('c'), ChartsAction::ToggleCountrySelector),
+        KeyCommand::new_from_code(KeyCode::Char('r'), ChartsAction::Refresh),
+        KeyCommand::new_from_code(KeyCode::Right, ChartsAction::NextCategory),
+        KeyCommand::new_from_code(KeyCode::Left, ChartsAction::PrevCategory),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, ChartsAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, ChartsAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, ChartsAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, ChartsAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, ChartsAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, ChartsAction::End),
+        KeyCommand::new_action_only_mode(
+            vec![
+                (KeyCode::Enter, ChartsAction::PlaySong),
+                (KeyCode::Char('l'), ChartsAction::PlayAll),
+                (KeyCode::Char(' '), ChartsAction::AddSongToPlaylist),
+                (KeyCode::Char('L'), ChartsAction::AddAllToPlaylist),
+            ],
+            KeyCode::Enter,
+            "Play",
+        ),
+    ]
+}
+
+fn country_selector_keybinds() -> Vec<KeyCommand<ChartsAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, ChartsAction::SelectCountry),
+        KeyCommand::new_hidden_from_code(KeyCode::Esc, ChartsAction::ToggleCountrySelector),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, ChartsAction::CountryUp),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, ChartsAction::CountryDown),
+    ]
+}
+
+pub mod draw {
+    use super::Charts;
+    use crate::app::view::draw::draw_table;
+    use crate::drawutils::{ROW_HIGHLIGHT_COLOUR, SELECTED_BORDER_COLOUR};
+    use crate::i18n::tr;
+    use ratatui::{
+        prelude::Rect,
+        style::Style,
+        widgets::{Block, Borders, Clear, List, ListState, TableState},
+        Frame,
+    };
+
+    // Country codes are two characters, so the popup can be narrow.
+    const COUNTRY_POPUP_WIDTH: u16 = 12;
+    const COUNTRY_POPUP_MAX_HEIGHT: u16 = 20;
+
+    pub fn draw_charts(
+        f: &mut Frame,
+        c: &Charts,
+        chunk: Rect,
+        table_state: &mut TableState,
+        selected: bool,
+    ) {
+        draw_table(
+            f,
+            c,
+            chunk,
+            table_state,
+            selected && !c.country_selector.shown,
+        );
+        if c.country_selector.shown {
+            draw_country_selector(f, c, chunk);
+        }
+    }
+
+    fn draw_country_selector(f: &mut Frame, c: &Charts, chunk: Rect) {
+        let height = (c.results.country_options.len() as u16 + 2).min(COUNTRY_POPUP_MAX_HEIGHT);
+        let popup_chunk = crate::drawutils::centered_rect(height, COUNTRY_POPUP_WIDTH, chunk);
+        // TODO: Save the state.
+        let mut state = ListState::default().with_selected(Some(c.country_selector.cur));
+        let list = List::new(c.results.country_options.iter().map(|c| c.as_str()))
+            .highlight_style(Style::default().bg(ROW_HIGHLIGHT_COLOUR))
+            .block(
+                Block::new()
+                    .title(tr("Country"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::new().fg(SELECTED_BORDER_COLOUR)),
+            );
+        f.render_widget(Clear, popup_chunk);
+        f.render_stateful_widget(list, popup_chunk, &mut state);
+    }
+}
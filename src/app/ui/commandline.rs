@@ -0,0 +1,281 @@
+use crate::app::component::actionhandler::TextHandler;
+use crate::app::view::Scrollable;
+use ytmapi_rs::common::PlaylistID;
+use ytmapi_rs::VideoID;
+
+/// Commands that take arguments, and so can't be represented by a simple Action.
+/// Listed with their usage for display in the completion list.
+pub const ARGUMENT_COMMANDS: [&str; 4] = [
+    "volume <0-100>",
+    "search artist <query>",
+    "open <url or id>",
+    "cast [device name | stop]",
+];
+/// Maximum number of completions to display.
+pub const MAX_COMPLETIONS: usize = 10;
+
+/// A command parsed from the command line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParsedCommand {
+    SetVolume(u8),
+    SearchArtist(String),
+    OpenVideo(VideoID<'static>),
+    OpenPlaylist(PlaylistID<'static>),
+    FindCastDevices,
+    /// Cast the queue to the device whose name contains the text.
+    CastTo(String),
+    StopCasting,
+    /// Any other input is treated as the description of a registered Action.
+    Action(String),
+}
+
+/// A `:` triggered command line, that allows every Action to be reached by name.
+#[derive(Default)]
+pub struct CommandLine {
+    pub shown: bool,
+    pub text: String,
+    pub completions: Vec<String>,
+    pub completions_cur: Option<usize>,
+}
+
+impl TextHandler for CommandLine {
+    fn push_text(&mut self, c: char) {
+        self.text.push(c);
+        self.completions_cur = None;
+    }
+    fn pop_text(&mut self) {
+        self.text.pop();
+        self.completions_cur = None;
+    }
+    fn is_text_handling(&self) -> bool {
+        self.shown
+    }
+    fn take_text(&mut self) -> String {
+        self.completions_cur = None;
+        std::mem::take(&mut self.text)
+    }
+    fn replace_text(&mut self, text: String) {
+        self.text = text;
+    }
+}
+
+impl Scrollable for CommandLine {
+    fn increment_list(&mut self, amount: isize) {
+        if self.completions.is_empty() {
+            self.completions_cur = None;
+            return;
+        }
+        let last = self.completions.len() - 1;
+        self.completions_cur = Some(match self.completions_cur {
+            None if amount < 0 => last,
+            None => 0,
+            Some(cur) => cur.saturating_add_signed(amount).min(last),
+        });
+    }
+    fn get_selected_item(&self) -> usize {
+        self.completions_cur.unwrap_or_default()
+    }
+}
+
+impl CommandLine {
+    pub fn open(&mut self) {
+        self.shown = true;
+        self.text.clear();
+        self.completions_cur = None;
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+        self.text.clear();
+        self.completions.clear();
+        self.completions_cur = None;
+    }
+    /// Replace the list of completions, ranked by how well they match the current text.
+    pub fn update_completions<'a>(&mut self, candidates: impl Iterator<Item = &'a str>) {
+        self.completions = rank_completions(&self.text, candidates)
+            .into_iter()
+            .take(MAX_COMPLETIONS)
+            .map(ToString::to_string)
+            .collect();
+        if self
+            .completions_cur
+            .is_some_and(|cur| cur >= self.completions.len())
+        {
+            self.completions_cur = None;
+        }
+    }
+    /// Replace the command text with the currently selected completion.
+    /// Argument placeholders are stripped so that the argument can be typed straight away.
+    pub fn accept_completion(&mut self) {
+        let Some(completion) = self
+            .completions_cur
+            .and_then(|cur| self.completions.get(cur))
+        else {
+            return;
+        };
+        let text = match completion.find('<') {
+            Some(idx) => completion[..idx].to_string(),
+            None => completion.clone(),
+        };
+        self.replace_text(text);
+        self.completions_cur = None;
+    }
+}
+
+/// Parse the text of the command line into a command.
+/// Returns an error message if a command with arguments was recognised but the arguments were not.
+pub fn parse_command(input: &str) -> Result<ParsedCommand, String> {
+    let input = input.trim();
+    let mut words = input.split_whitespace();
+    match words.next() {
+        Some("volume" | "vol") => {
+            let arg = words.next().ok_or("Usage: volume <0-100>")?;
+            let vol = arg
+                .parse::<u8>()
+                .ok()
+                .filter(|v| *v <= 100)
+                .ok_or_else(|| format!("Invalid volume <{arg}>, expected 0-100"))?;
+            Ok(ParsedCommand::SetVolume(vol))
+        }
+        Some("search") => {
+            if words.next() != Some("artist") {
+                return Err("Usage: search artist <query>".into());
+            }
+            let query = words.collect::<Vec<_>>().join(" ");
+            if query.is_empty() {
+                return Err("Usage: search artist <query>".into());
+            }
+            Ok(ParsedCommand::SearchArtist(query))
+        }
+        Some("open") => {
+            let arg = words.next().ok_or("Usage: open <url or id>")?;
+            // A url containing both a video and a playlist opens the video.
+            if let Some(video_id) = VideoID::from_url_or_id(arg) {
+                Ok(ParsedCommand::OpenVideo(video_id))
+            } else if let Some(playlist_id) = PlaylistID::from_url_or_id(arg) {
+                Ok(ParsedCommand::OpenPlaylist(playlist_id))
+            } else {
+                Err(format!("Unable to find a video or playlist in <{arg}>"))
+            }
+        }
+        Some("cast") => match words.collect::<Vec<_>>().join(" ").as_str() {
+            "" => Ok(ParsedCommand::FindCastDevices),
+            "stop" => Ok(ParsedCommand::StopCasting),
+            name => Ok(ParsedCommand::CastTo(name.to_string())),
+        },
+        _ => Ok(ParsedCommand::Action(input.to_string())),
+    }
+}
+
+/// Score how well pattern fuzzy matches candidate, case insensitive.
+/// Every character in pattern must appear in candidate in order.
+/// Consecutive matches and matches at the start of a word score higher.
+/// Returns None if there is no match.
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<usize> {
+    let mut score = 0;
+    let mut prev_matched = false;
+    let mut prev_char = ' ';
+    let mut candidate_chars = candidate.chars().flat_map(char::to_lowercase);
+    for p in pattern.chars().flat_map(char::to_lowercase) {
+        loop {
+            let c = candidate_chars.next()?;
+            let is_match = c == p;
+            if is_match {
+                score += 1;
+                if prev_matched {
+                    score += 2;
+                }
+                if prev_char == ' ' {
+                    score += 3;
+                }
+            }
+            prev_matched = is_match;
+            prev_char = c;
+            if is_match {
+                break;
+            }
+        }
+    }
+    Some(score)
+}
+
+/// Filter candidates that fuzzy match pattern, best matches first.
+/// Order of equally scored candidates is preserved.
+pub fn rank_completions<'a>(
+    pattern: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let mut scored: Vec<_> = candidates
+        .filter_map(|c| fuzzy_score(pattern, c).map(|score| (score, c)))
+        .collect();
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_score, parse_command, rank_completions, ParsedCommand};
+
+    #[test]
+    fn test_parse_volume() {
+        assert_eq!(parse_command("volume 80"), Ok(ParsedCommand::SetVolume(80)));
+        assert_eq!(parse_command(" vol 0 "), Ok(ParsedCommand::SetVolume(0)));
+        assert!(parse_command("volume 101").is_err());
+        assert!(parse_command("volume").is_err());
+    }
+    #[test]
+    fn test_parse_open() {
+        use ytmapi_rs::common::{PlaylistID, YoutubeID};
+        use ytmapi_rs::VideoID;
+        assert_eq!(
+            parse_command("open https://music.youtube.com/watch?v=dQw4w9WgXcQ"),
+            Ok(ParsedCommand::OpenVideo(VideoID::from_raw("dQw4w9WgXcQ")))
+        );
+        assert_eq!(
+            parse_command("open PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"),
+            Ok(ParsedCommand::OpenPlaylist(PlaylistID::from_raw(
+                "PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"
+            )))
+        );
+        assert!(parse_command("open").is_err());
+        assert!(parse_command("open nonsense").is_err());
+    }
+    #[test]
+    fn test_parse_search_artist() {
+        assert_eq!(
+            parse_command("search artist the beatles"),
+            Ok(ParsedCommand::SearchArtist("the beatles".into()))
+        );
+        assert!(parse_command("search artist").is_err());
+        assert!(parse_command("search album foo").is_err());
+    }
+    #[test]
+    fn test_parse_cast() {
+        assert_eq!(parse_command("cast"), Ok(ParsedCommand::FindCastDevices));
+        assert_eq!(
+            parse_command("cast living  room"),
+            Ok(ParsedCommand::CastTo("living room".into()))
+        );
+        assert_eq!(parse_command("cast stop"), Ok(ParsedCommand::StopCasting));
+    }
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(
+            parse_command("Next Song"),
+            Ok(ParsedCommand::Action("Next Song".into()))
+        );
+    }
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("nxs", "Next Song").is_some());
+        assert!(fuzzy_score("snx", "Next Song").is_none());
+        assert_eq!(fuzzy_score("", "Next Song"), Some(0));
+    }
+    #[test]
+    fn test_rank_completions() {
+        let candidates = ["Vol Up", "View Logs", "Vol Down"];
+        let ranked = rank_completions("vd", candidates.into_iter());
+        assert_eq!(ranked, vec!["Vol Down"]);
+        let ranked = rank_completions("vl", candidates.into_iter());
+        assert_eq!(ranked.first(), Some(&"View Logs"));
+    }
+}
@@ -1,25 +1,54 @@
-use super::{footer, header, WindowContext, YoutuiWindow};
-use crate::app::component::actionhandler::KeyDisplayer;
+use super::{
+    addtolibraryplaylist, albumart, autherror, footer, header, hintbar, localplaylists, nowplaying,
+    songdetails, tasks, toast, WindowContext, YoutuiWindow,
+};
 use crate::app::keycommand::{DisplayableCommand, DisplayableMode};
-use crate::app::view::draw::draw_panel;
+use crate::app::view::draw::{draw_panel, table_visible_rows};
 use crate::app::view::{Drawable, DrawableMut};
 use crate::app::YoutuiMutableState;
 use crate::drawutils::{
-    highlight_style, left_bottom_corner_rect, SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR,
-    TEXT_COLOUR,
+    apply_high_contrast, centered_rect, high_contrast, highlight_style, left_bottom_corner_rect,
+    SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR, TEXT_COLOUR,
 };
+use crate::i18n::{tr, tr_format};
 use ratatui::prelude::{Margin, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::symbols::{block, line};
+use ratatui::text::Line;
 use ratatui::widgets::{
-    Block, Borders, Clear, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState,
+    Block, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Scrollbar,
+    ScrollbarOrientation, ScrollbarState, Table, TableState,
 };
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout},
     terminal::Frame,
 };
 use std::borrow::Cow;
 
+const CONFIRM_MIN_WIDTH: u16 = 30;
+
+/// Browser on the left and playlist on the right, with the focused one selected.
+fn draw_split_layout(
+    f: &mut Frame,
+    w: &YoutuiWindow,
+    m: &mut YoutuiMutableState,
+    chunk: Rect,
+    context_selected: bool,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(w.pane_sizes.split_percentage),
+            Constraint::Percentage(100 - w.pane_sizes.split_percentage),
+        ])
+        .split(chunk);
+    let browser_selected = matches!(w.context, WindowContext::Browser);
+    w.browser
+        .draw_mut_chunk(f, layout[0], m, context_selected && browser_selected);
+    w.playlist
+        .draw_mut_chunk(f, layout[1], m, context_selected && !browser_selected);
+}
+
 // Add tests to try and draw app with oddly sized windows.
 pub fn draw_app(f: &mut Frame, w: &YoutuiWindow, m: &mut YoutuiMutableState) {
     let base_layout = Layout::default()
@@ -28,31 +57,122 @@ pub fn draw_app(f: &mut Frame, w: &YoutuiWindow, m: &mut YoutuiMutableState) {
         .constraints(
             [
                 Constraint::Length(3),
+                Constraint::Length(1),
                 Constraint::Min(2),
+                Constraint::Length(1),
                 Constraint::Length(5),
             ]
             .as_ref(),
         )
         .split(f.size());
+    m.page_lines = table_visible_rows(base_layout[2]);
     header::draw_header(f, w, base_layout[0]);
-    let context_selected = !w.help.shown && !w.key_pending();
+    nowplaying::draw_now_playing(f, w, base_layout[1]);
+    let context_selected = !w.help.shown
+        && !w.command_line.shown
+        && !w.add_to_library_playlist.shown
+        && !w.song_details.shown
+        && !w.local_playlists.shown
+        && !w.tasks.shown
+        && !w.toasts.history_shown
+        && !w.confirm.is_shown()
+        && !w.auth_error.shown
+        && !w.key_pending();
     match w.context {
+        WindowContext::Browser | WindowContext::Playlist if w.split_layout => {
+            draw_split_layout(f, w, m, base_layout[2], context_selected)
+        }
         WindowContext::Browser => w
             .browser
-            .draw_mut_chunk(f, base_layout[1], m, context_selected),
-        WindowContext::Logs => w.logger.draw_chunk(f, base_layout[1], context_selected),
+            .draw_mut_chunk(f, base_layout[2], m, context_selected),
+        WindowContext::Logs => w.logger.draw_chunk(f, base_layout[2], context_selected),
+        WindowContext::Lyrics => w.lyrics.draw_chunk(f, base_layout[2], context_selected),
+        WindowContext::Library => w
+            .library
+            .draw_mut_chunk(f, base_layout[2], m, context_selected),
+        WindowContext::Charts => w
+            .charts
+            .draw_mut_chunk(f, base_layout[2], m, context_selected),
+        WindowContext::ArtistDetails => {
+            w.artist_details
+                .draw_mut_chunk(f, base_layout[2], m, context_selected)
+        }
+        WindowContext::Podcasts => {
+            w.podcasts
+                .draw_mut_chunk(f, base_layout[2], m, context_selected)
+        }
+        WindowContext::RecentlyPlayed => {
+            w.recently_played
+                .draw_mut_chunk(f, base_layout[2], m, context_selected)
+        }
+        WindowContext::Favorites => {
+            w.favorites
+                .draw_mut_chunk(f, base_layout[2], m, context_selected)
+        }
         WindowContext::Playlist => {
             w.playlist
-                .draw_mut_chunk(f, base_layout[1], m, context_selected)
+                .draw_mut_chunk(f, base_layout[2], m, context_selected)
         }
     }
+    albumart::draw::draw_album_art(f, &w.album_art, base_layout[2], &mut m.album_art_state);
     if w.help.shown {
-        draw_help(f, w, &mut m.help_state, base_layout[1]);
+        draw_help(f, w, &mut m.help_state, base_layout[2]);
     }
     if w.key_pending() {
-        draw_popup(f, w, base_layout[1]);
+        draw_popup(f, w, base_layout[2]);
+    }
+    if w.add_to_library_playlist.shown {
+        addtolibraryplaylist::draw::draw_add_to_library_playlist(
+            f,
+            &w.add_to_library_playlist,
+            base_layout[2],
+        );
     }
-    footer::draw_footer(f, w, base_layout[2]);
+    if w.song_details.shown {
+        songdetails::draw::draw_song_details(f, &w.song_details, base_layout[2]);
+    }
+    if w.local_playlists.shown {
+        localplaylists::draw::draw_local_playlists(f, &w.local_playlists, base_layout[2]);
+    }
+    toast::draw::draw_toasts(f, &w.toasts, base_layout[2]);
+    if w.toasts.history_shown {
+        toast::draw::draw_history(f, &w.toasts, base_layout[2]);
+    }
+    if w.tasks.shown {
+        tasks::draw::draw_tasks(f, &w.tasks, base_layout[2]);
+    }
+    if w.command_line.shown {
+        draw_command_line(f, w, base_layout[2]);
+    }
+    if w.auth_error.shown {
+        autherror::draw::draw_auth_error(f, &w.auth_error, base_layout[2]);
+    }
+    if let Some(message) = w.confirm.get_message() {
+        draw_confirm(f, message, base_layout[2]);
+    }
+    hintbar::draw_hint_bar(f, w, base_layout[3]);
+    footer::draw_footer(f, w, base_layout[4]);
+    if high_contrast() {
+        apply_high_contrast(f.buffer_mut());
+    }
+}
+fn draw_confirm(f: &mut Frame, message: &str, chunk: Rect) {
+    // Wide enough for the message and borders, but no wider than the chunk.
+    let width = (message.len() as u16 + 4).max(CONFIRM_MIN_WIDTH);
+    let area = centered_rect(4, width, chunk);
+    let dialog = Paragraph::new(vec![
+        Line::from(message),
+        Line::styled("y: Yes / n: No", Style::new().fg(TABLE_HEADINGS_COLOUR)),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .title(tr("Confirm"))
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(SELECTED_BORDER_COLOUR)),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(dialog, area);
 }
 fn draw_popup(f: &mut Frame, w: &YoutuiWindow, chunk: Rect) {
     // NOTE: if there are more commands than we can fit on the screen, some will be cut off.
@@ -106,68 +226,94 @@ fn draw_popup(f: &mut Frame, w: &YoutuiWindow, chunk: Rect) {
     f.render_widget(block, area);
 }
 
+fn draw_command_line(f: &mut Frame, w: &YoutuiWindow, chunk: Rect) {
+    let command_line = &w.command_line;
+    let input_chunk = left_bottom_corner_rect(3, chunk.width, chunk);
+    // Completions are drawn directly above the input box.
+    let completions_height = (command_line.completions.len() as u16)
+        .saturating_add(2)
+        .min(chunk.height.saturating_sub(input_chunk.height));
+    let completions_chunk = Rect {
+        x: input_chunk.x,
+        y: input_chunk.y.saturating_sub(completions_height),
+        width: input_chunk.width,
+        height: completions_height,
+    };
+    let input_widget = Paragraph::new(format!(":{}", command_line.text)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+            .title(tr("Command")),
+    );
+    let completions = command_line
+        .completions
+        .iter()
+        .map(|c| ListItem::new(c.as_str()).style(Style::new().fg(TEXT_COLOUR)));
+    let mut list_state = ListState::default().with_selected(command_line.completions_cur);
+    let completions_widget = List::new(completions)
+        .highlight_style(highlight_style())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(tr("Completions")),
+        );
+    f.render_widget(Clear, input_chunk);
+    f.render_widget(input_widget, input_chunk);
+    if !command_line.completions.is_empty() {
+        f.render_widget(Clear, completions_chunk);
+        f.render_stateful_widget(completions_widget, completions_chunk, &mut list_state);
+    }
+    f.set_cursor(
+        (input_chunk.x + command_line.text.chars().count() as u16 + 2)
+            .min(input_chunk.right().saturating_sub(2)),
+        input_chunk.y + 1,
+    );
+}
+
 fn draw_help(f: &mut Frame, w: &YoutuiWindow, state: &mut TableState, chunk: Rect) {
-    // NOTE: if there are more commands than we can fit on the screen, some will be cut off.
-    let commands = w.get_all_visible_keybinds_as_readable_iter();
-    // Get the maximum length of each element in the tuple vector created above, as well as the number of items.
-    // XXX: Probably don't need to map then fold, just fold.
-    // XXX: Fold closure could be written as a function, then becomes testable.
-    let (mut s_len, mut c_len, mut d_len, items) = commands
-        .map(
-            |DisplayableCommand {
-                 keybinds,
-                 context,
-                 description,
-             }| (keybinds.len(), context.len(), description.len()),
-        )
-        .fold((0, 0, 0, 0), |(smax, cmax, dmax, n), (s, c, d)| {
-            (smax.max(s), cmax.max(c), dmax.max(d), n + 1)
-        });
+    // Get the maximum length of each column, as well as the number of items.
+    let (s_len, c_len, d_len, items) =
+        w.help
+            .get_filtered_entries()
+            .fold((0, 0, 0, 0), |(smax, cmax, dmax, n), e| {
+                (
+                    smax.max(e.keybinds.len()),
+                    cmax.max(e.context.len()),
+                    dmax.max(e.description.len()),
+                    n + 1,
+                )
+            });
+    let commands_table = w.help.get_filtered_entries().map(|e| {
+        Row::new(vec![
+            e.keybinds.clone(),
+            e.context.clone(),
+            e.description.clone(),
+        ])
+        .style(Style::new().fg(TEXT_COLOUR))
+    });
     // Ensure the width of each column is at least as wide as header.
-    (s_len, c_len, d_len) = (s_len.max(3), c_len.max(7), d_len.max(7));
-    // Total block width required, including padding and borders.
-    let width = s_len + c_len + d_len + 4;
-    // Total block height required, including header and borders.
-    let height = items + 3;
-    // Naive implementation
-    // XXX: We're running get_all_visible_keybinds a second time here.
-    // Better to move to the fold above.
-    let commands_table = w.get_all_visible_keybinds_as_readable_iter().map(
-        |DisplayableCommand {
-             keybinds,
-             context,
-             description,
-         }| {
-            // TODO: Remove vec allocation?
-            Row::new(vec![
-                keybinds.to_string(),
-                context.to_string(),
-                description.to_string(),
-            ])
-            .style(Style::new().fg(TEXT_COLOUR))
-        },
-    );
     let table_constraints = [
-        Constraint::Min(s_len.try_into().unwrap_or(u16::MAX)),
-        Constraint::Min(c_len.try_into().unwrap_or(u16::MAX)),
-        Constraint::Min(d_len.try_into().unwrap_or(u16::MAX)),
+        Constraint::Min(s_len.max(3).try_into().unwrap_or(u16::MAX)),
+        Constraint::Min(c_len.max(7).try_into().unwrap_or(u16::MAX)),
+        Constraint::Min(d_len.max(7).try_into().unwrap_or(u16::MAX)),
     ];
-    let headings = ["Key", "Context", "Command"];
-    let area = left_bottom_corner_rect(
-        height.try_into().unwrap_or(u16::MAX),
-        width.try_into().unwrap_or(u16::MAX),
-        chunk,
-    );
-    f.render_widget(Clear, area);
+    let headings = ["Key", "Context", "Command"].map(tr);
+    let title = if w.help.filter.is_empty() {
+        tr("Help - type to filter").into()
+    } else {
+        tr_format("Help - filter: {}", &[&w.help.filter]).into()
+    };
+    f.render_widget(Clear, chunk);
     draw_generic_scrollable_table(
         f,
         commands_table,
-        "Help".into(),
+        title,
         w.help.cur,
         items,
         &table_constraints,
         &headings,
-        area,
+        chunk,
         state,
         true,
     );
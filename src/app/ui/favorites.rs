@@ -0,0 +1,263 @@
+use self::starred::StarredSongs;
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{saved_songs_to_list, ListSong, Percentage, SavedSong},
+    ui::{AppCallback, WindowContext},
+    view::{
+        draw::draw_table, get_table_column_texts, BasicConstraint, DrawableMut, Loadable,
+        Scrollable, TableItem, TableView, TypeAheadJump,
+    },
+    YoutuiMutableState,
+};
+use crate::core::send_or_error;
+use crate::i18n::{tr, tr_format};
+use crossterm::event::KeyCode;
+use ratatui::{prelude::Rect, Frame};
+use std::borrow::Cow;
+use tokio::sync::mpsc::Sender;
+use tracing::warn;
+
+mod starred;
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FavoritesAction {
+    ViewBrowser,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    PlaySong,
+    AddSongToPlaylist,
+    PlayAll,
+    AddAllToPlaylist,
+    UnstarSelected,
+}
+
+/// Songs starred locally, which can be queued at any time.
+/// Unlike the library on YouTube Music, this doesn't require an authenticated API.
+pub struct Favorites {
+    ui_tx: Sender<AppCallback>,
+    pub starred: StarredSongs,
+    cur_selected: usize,
+    keybinds: Vec<KeyCommand<FavoritesAction>>,
+    pub page_lines: isize,
+}
+
+impl Action for FavoritesAction {
+    fn context(&self) -> Cow<str> {
+        tr("Favorites").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            Self::ViewBrowser => "View Browser",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::PageUp => "Page Up",
+            Self::PageDown => "Page Down",
+            Self::Home => "Top",
+            Self::End => "Bottom",
+            Self::PlaySong => "Play",
+            Self::AddSongToPlaylist => "Add song to playlist",
+            Self::PlayAll => "Play all",
+            Self::AddAllToPlaylist => "Add all to playlist",
+            Self::UnstarSelected => "Unstar",
+        })
+        .into()
+    }
+}
+
+impl DrawableMut for Favorites {
+    fn draw_mut_chunk(
+        &self,
+        f: &mut Frame,
+        chunk: Rect,
+        mutable_state: &mut YoutuiMutableState,
+        selected: bool,
+    ) {
+        draw_table(f, self, chunk, &mut mutable_state.favorites_state, selected)
+    }
+}
+
+impl KeyRouter<FavoritesAction> for Favorites {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<FavoritesAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<FavoritesAction>> + 'a> {
+        self.get_routed_keybinds()
+    }
+}
+
+impl TextHandler for Favorites {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<FavoritesAction> for Favorites {
+    async fn handle_action(&mut self, action: &FavoritesAction) {
+        match action {
+            FavoritesAction::ViewBrowser => self.handle_view_browser().await,
+            FavoritesAction::Up => self.increment_list(-1),
+            FavoritesAction::Down => self.increment_list(1),
+            FavoritesAction::PageUp => self.increment_list(-self.page_lines),
+            FavoritesAction::PageDown => self.increment_list(self.page_lines),
+            FavoritesAction::Home => self.increment_list(isize::MIN),
+            FavoritesAction::End => self.increment_list(isize::MAX),
+            FavoritesAction::PlaySong => self.queue_selected(true).await,
+            FavoritesAction::AddSongToPlaylist => self.queue_selected(false).await,
+            FavoritesAction::PlayAll => self.queue_all(true).await,
+            FavoritesAction::AddAllToPlaylist => self.queue_all(false).await,
+            FavoritesAction::UnstarSelected => self.unstar_selected(),
+        }
+    }
+}
+
+impl Favorites {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            starred: StarredSongs::load(),
+            cur_selected: 0,
+            keybinds: favorites_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Star the song, or unstar it if it was already starred, and save the favorites.
+    /// Returns true if the song is now starred.
+    pub fn toggle_star(&mut self, song: &ListSong) -> bool {
+        let starred = self.starred.toggle(SavedSong::from_song(song));
+        self.increment_list(0);
+        self.save();
+        starred
+    }
+    fn save(&self) {
+        if let Err(e) = self.starred.save() {
+            warn!("Error <{e}> saving favorites");
+        }
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    async fn queue_selected(&mut self, play: bool) {
+        let Some(song) = self.starred.songs.get(self.cur_selected) else {
+            return;
+        };
+        let song_list = saved_songs_to_list([song]);
+        self.queue(song_list, play).await;
+    }
+    async fn queue_all(&mut self, play: bool) {
+        // Playlist requires at least one song to be pushed.
+        if self.starred.songs.is_empty() {
+            return;
+        }
+        let song_list = saved_songs_to_list(&self.starred.songs);
+        self.queue(song_list, play).await;
+    }
+    async fn queue(&self, song_list: Vec<ListSong>, play: bool) {
+        let callback = if play {
+            AppCallback::AddSongsToPlaylistAndPlay(song_list)
+        } else {
+            AppCallback::AddSongsToPlaylist(song_list)
+        };
+        send_or_error(&self.ui_tx, callback).await;
+    }
+    fn unstar_selected(&mut self) {
+        if self.starred.remove(self.cur_selected).is_some() {
+            self.increment_list(0);
+            self.save();
+        }
+    }
+}
+
+impl Loadable for Favorites {
+    fn is_loading(&self) -> bool {
+        false
+    }
+}
+impl Scrollable for Favorites {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.starred.songs.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for Favorites {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for Favorites {
+    fn get_title(&self) -> Cow<str> {
+        tr_format("Favorites - {} songs", &[&self.starred.songs.len()]).into()
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(40)),
+            BasicConstraint::Percentage(Percentage(30)),
+            BasicConstraint::Percentage(Percentage(30)),
+            BasicConstraint::Length(9),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        Box::new(self.starred.songs.iter().map(|s| {
+            Box::new(
+                [
+                    Cow::from(s.title.as_str()),
+                    Cow::from(s.artists.join(", ")),
+                    Cow::from(s.album.as_str()),
+                    Cow::from(s.duration.as_deref().unwrap_or_default()),
+                ]
+                .into_iter(),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Song", "Artists", "Album", "Duration"].into_iter())
+    }
+}
+
+fn favorites_keybinds() -> Vec<KeyCommand<FavoritesAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), FavoritesAction::ViewBrowser),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, FavoritesAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, FavoritesAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, FavoritesAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, FavoritesAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, FavoritesAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, FavoritesAction::End),
+        KeyCommand::new_from_code(KeyCode::Char('d'), FavoritesAction::UnstarSelected),
+        KeyCommand::new_action_only_mode(
+            vec![
+                (KeyCode::Enter, FavoritesAction::PlaySong),
+                (KeyCode::Char('l'), FavoritesAction::PlayAll),
+                (KeyCode::Char(' '), FavoritesAction::AddSongToPlaylist),
+                (KeyCode::Char('L'), FavoritesAction::AddAllToPlaylist),
+            ],
+            KeyCode::Enter,
+            "Play",
+        ),
+    ]
+}
@@ -0,0 +1,68 @@
+use crate::app::datafile::{load_data_file, save_data_file};
+use crate::app::structures::SavedSong;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+const FAVORITES_FILE_NAME: &str = "favorites.json";
+
+/// Songs starred by the user, most recently starred first, stored in the data directory.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StarredSongs {
+    pub songs: Vec<SavedSong>,
+}
+
+impl StarredSongs {
+    /// Load the starred songs from the data directory, or an empty list if they don't exist or
+    /// can't be read.
+    pub fn load() -> Self {
+        load_data_file(FAVORITES_FILE_NAME).unwrap_or_default()
+    }
+    pub fn save(&self) -> Result<()> {
+        save_data_file(FAVORITES_FILE_NAME, self)
+    }
+    /// Star the song, or unstar it if it was already starred.
+    /// Returns true if the song is now starred.
+    pub fn toggle(&mut self, song: SavedSong) -> bool {
+        if let Some(idx) = self.songs.iter().position(|s| s.video_id == song.video_id) {
+            self.songs.remove(idx);
+            false
+        } else {
+            self.songs.insert(0, song);
+            true
+        }
+    }
+    pub fn remove(&mut self, idx: usize) -> Option<SavedSong> {
+        (idx < self.songs.len()).then(|| self.songs.remove(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StarredSongs;
+    use crate::app::structures::test_saved_song;
+
+    #[test]
+    fn test_toggle_star() {
+        let mut starred = StarredSongs::default();
+        assert!(starred.toggle(test_saved_song("a")));
+        assert!(starred.toggle(test_saved_song("b")));
+        assert_eq!(
+            starred.songs.first().map(|s| s.video_id.as_str()),
+            Some("b")
+        );
+        assert!(!starred.toggle(test_saved_song("a")));
+        assert_eq!(starred.songs.len(), 1);
+        assert_eq!(starred.songs[0].video_id, "b");
+    }
+    #[test]
+    fn test_starred_round_trip() {
+        let mut starred = StarredSongs::default();
+        starred.toggle(test_saved_song("a"));
+        let serialized = serde_json::to_string(&starred).unwrap();
+        assert_eq!(
+            serde_json::from_str::<StarredSongs>(&serialized).unwrap(),
+            starred
+        );
+    }
+}
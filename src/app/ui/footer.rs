@@ -1,3 +1,4 @@
+use crate::i18n::tr;
 use crate::{
     app::structures::PlayState,
     drawutils::{BUTTON_BG_COLOUR, BUTTON_FG_COLOUR, PROGRESS_BG_COLOUR, PROGRESS_FG_COLOUR},
@@ -98,7 +99,7 @@ pub fn draw_footer(f: &mut Frame, w: &super::YoutuiWindow, chunk: Rect) {
     };
     let footer = Paragraph::new(vec![Line::from(song_title_string), Line::from(album_title)]);
     let block = Block::default()
-        .title("Status")
+        .title(tr("Status"))
         .title(Title::from("Youtui").alignment(Alignment::Right))
         .borders(Borders::ALL);
     let block_inner = block.inner(chunk);
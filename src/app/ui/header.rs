@@ -1,13 +1,14 @@
+use crate::i18n::tr;
 use crate::{
     app::{component::actionhandler::KeyDisplayer, keycommand::DisplayableCommand},
     drawutils::{BUTTON_BG_COLOUR, BUTTON_FG_COLOUR},
 };
 use ratatui::{
-    layout::Rect,
-    style::Style,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
     terminal::Frame,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{block::Title, Block, Borders, Paragraph},
 };
 
 pub fn draw_header(f: &mut Frame, w: &super::YoutuiWindow, chunk: Rect) {
@@ -36,7 +37,35 @@ pub fn draw_header(f: &mut Frame, w: &super::YoutuiWindow, chunk: Rect) {
             .collect::<Vec<_>>(),
     );
 
-    let header =
-        Paragraph::new(help_string).block(Block::default().borders(Borders::ALL).title("Commands"));
+    let header = Paragraph::new(help_string).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(tr("Commands"))
+            .title(Title::from(browser_tab_strip(w)).alignment(Alignment::Right)),
+    );
     f.render_widget(header, chunk);
 }
+
+fn browser_tab_strip(w: &super::YoutuiWindow) -> Line {
+    let (titles, cur) = w.get_browser_tab_titles();
+    Line::from(
+        titles
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, title)| {
+                let style = if i == cur {
+                    Style::default()
+                        .bg(BUTTON_BG_COLOUR)
+                        .fg(BUTTON_FG_COLOUR)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                [
+                    Span::styled(format!("{}:{title}", i + 1), style),
+                    Span::raw(" "),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    )
+}
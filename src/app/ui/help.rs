@@ -0,0 +1,176 @@
+use super::{help_keybinds, UIAction};
+use crate::app::component::actionhandler::{Action, TextHandler};
+use crate::app::keycommand::{KeyCommand, Keymap};
+use crate::app::view::Scrollable;
+
+/// A single keybind as listed in the help overlay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HelpEntry {
+    pub keybinds: String,
+    pub context: String,
+    pub description: String,
+}
+
+/// Full screen overlay listing every keybind in the app, filterable by typing.
+pub struct HelpMenu {
+    pub shown: bool,
+    pub cur: usize,
+    pub filter: String,
+    entries: Vec<HelpEntry>,
+    pub keybinds: Vec<KeyCommand<UIAction>>,
+}
+
+impl Default for HelpMenu {
+    fn default() -> Self {
+        HelpMenu {
+            shown: Default::default(),
+            cur: Default::default(),
+            filter: Default::default(),
+            entries: Default::default(),
+            keybinds: help_keybinds(),
+        }
+    }
+}
+
+impl HelpMenu {
+    /// Show the overlay, listing the provided entries grouped by context.
+    pub fn open(&mut self, mut entries: Vec<HelpEntry>) {
+        // Stable sort, so that keybinds keep their declared order within each context.
+        entries.sort_by(|a, b| a.context.cmp(&b.context));
+        self.entries = entries;
+        self.filter.clear();
+        self.cur = 0;
+        self.shown = true;
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+        self.entries.clear();
+    }
+    pub fn get_filtered_entries(&self) -> impl Iterator<Item = &HelpEntry> {
+        let filter = self.filter.to_lowercase();
+        self.entries.iter().filter(move |e| {
+            [&e.keybinds, &e.context, &e.description]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&filter))
+        })
+    }
+    pub fn filtered_len(&self) -> usize {
+        self.get_filtered_entries().count()
+    }
+    fn clamp_cur(&mut self) {
+        self.cur = self.cur.min(self.filtered_len().saturating_sub(1));
+    }
+}
+
+impl Scrollable for HelpMenu {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur = self
+            .cur
+            .saturating_add_signed(amount)
+            .min(self.filtered_len().saturating_sub(1));
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur
+    }
+}
+
+impl TextHandler for HelpMenu {
+    fn push_text(&mut self, c: char) {
+        self.filter.push(c);
+        self.clamp_cur();
+    }
+    fn pop_text(&mut self) {
+        self.filter.pop();
+        self.clamp_cur();
+    }
+    fn is_text_handling(&self) -> bool {
+        self.shown
+    }
+    fn take_text(&mut self) -> String {
+        let text = std::mem::take(&mut self.filter);
+        self.clamp_cur();
+        text
+    }
+    fn replace_text(&mut self, text: String) {
+        self.filter = text;
+        self.clamp_cur();
+    }
+}
+
+/// Flatten a list of keybinds into help entries, including hidden keybinds and the commands
+/// inside each Mode. Commands inside a Mode are listed with the full key sequence required.
+pub fn get_help_entries<'a, A: Action + 'a>(
+    commands: impl IntoIterator<Item = &'a KeyCommand<A>>,
+) -> Vec<HelpEntry> {
+    let mut entries = Vec::new();
+    for command in commands {
+        push_help_entries(&mut entries, command, "");
+    }
+    entries
+}
+
+fn push_help_entries<A: Action>(
+    entries: &mut Vec<HelpEntry>,
+    command: &KeyCommand<A>,
+    prefix: &str,
+) {
+    let keybinds = format!("{prefix}{command}");
+    entries.push(HelpEntry {
+        keybinds: keybinds.clone(),
+        context: command.context().into_owned(),
+        description: command.describe().into_owned(),
+    });
+    if let Keymap::Mode(mode) = &command.key_map {
+        let prefix = format!("{keybinds} ");
+        for sub_command in mode.commands.iter() {
+            push_help_entries(entries, sub_command, &prefix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_help_entries, HelpEntry, HelpMenu};
+    use crate::app::component::actionhandler::TextHandler;
+    use crate::app::keycommand::KeyCommand;
+    use crate::app::ui::UIAction;
+    use crossterm::event::KeyCode;
+
+    fn entry(keybinds: &str, context: &str, description: &str) -> HelpEntry {
+        HelpEntry {
+            keybinds: keybinds.into(),
+            context: context.into(),
+            description: description.into(),
+        }
+    }
+    #[test]
+    fn test_help_entries_expand_modes() {
+        let commands = [
+            KeyCommand::new_hidden_from_code(KeyCode::Char('x'), UIAction::Quit),
+            KeyCommand::new_action_only_mode(
+                vec![(KeyCode::Char('a'), UIAction::Quit)],
+                KeyCode::Char('m'),
+                "Mode",
+            ),
+        ];
+        let entries = get_help_entries(commands.iter());
+        let keybinds: Vec<_> = entries.iter().map(|e| e.keybinds.as_str()).collect();
+        assert_eq!(keybinds, vec!["x", "m", "m a"]);
+    }
+    #[test]
+    fn test_help_filter() {
+        let mut help = HelpMenu::default();
+        help.open(vec![
+            entry("a", "Playlist", "Play"),
+            entry("b", "Browser", "Search"),
+            entry("c", "Browser", "Play Album"),
+        ]);
+        // Entries are grouped by context.
+        assert_eq!(help.get_filtered_entries().next().unwrap().keybinds, "b");
+        "PLAY".chars().for_each(|c| help.push_text(c));
+        assert_eq!(help.filtered_len(), 2);
+        help.push_text('x');
+        assert_eq!(help.filtered_len(), 0);
+        assert_eq!(help.cur, 0);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    app::{
+        component::actionhandler::KeyDisplayer,
+        keycommand::{DisplayableCommand, DisplayableMode},
+    },
+    drawutils::{BUTTON_BG_COLOUR, BUTTON_FG_COLOUR, TABLE_HEADINGS_COLOUR},
+};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    terminal::Frame,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/// Single line of the most relevant keybinds for the focused pane.
+/// If a key is pending, shows the commands for the current mode instead.
+/// Keybinds that don't fit on the line are cut off.
+pub fn draw_hint_bar(f: &mut Frame, w: &super::YoutuiWindow, chunk: Rect) {
+    let mode = w
+        .get_cur_displayable_mode()
+        .map(|DisplayableMode { description, .. }| {
+            vec![
+                Span::styled(
+                    description,
+                    Style::new()
+                        .fg(TABLE_HEADINGS_COLOUR)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(": "),
+            ]
+        })
+        .unwrap_or_default();
+    let hints = w.get_context_hint_keybinds_as_readable_iter().flat_map(
+        |DisplayableCommand {
+             keybinds,
+             description,
+             ..
+         }| {
+            vec![
+                Span::styled(
+                    keybinds,
+                    Style::default().bg(BUTTON_BG_COLOUR).fg(BUTTON_FG_COLOUR),
+                ),
+                Span::raw(" "),
+                Span::raw(description),
+                Span::raw(" "),
+            ]
+        },
+    );
+    let line = Line::from(mode.into_iter().chain(hints).collect::<Vec<_>>());
+    f.render_widget(Paragraph::new(line), chunk);
+}
@@ -0,0 +1,617 @@
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{ListStatus, Percentage},
+    ui::{AppCallback, WindowContext},
+    view::{
+        get_table_column_texts, BasicConstraint, DrawableMut, Loadable, Scrollable, TableItem,
+        TableView, TypeAheadJump,
+    },
+    YoutuiMutableState,
+};
+use crate::core::send_or_error;
+use crate::i18n::{tr, tr_format};
+use crossterm::event::KeyCode;
+use draw::draw_library;
+use ratatui::{prelude::Rect, Frame};
+use std::borrow::Cow;
+use tokio::sync::mpsc::Sender;
+use ytmapi_rs::common::library::{LibraryAlbum, LibraryArtist, Playlist};
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LibraryAction {
+    ViewBrowser,
+    Refresh,
+    Open,
+    QueueAll,
+    OpenInBrowser,
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum LibraryInputRouting {
+    #[default]
+    Playlists,
+    Albums,
+    Artists,
+}
+
+/// The user's library - their saved playlists, including liked songs, saved albums and
+/// subscribed artists.
+/// NOTE: Requires an authenticated API.
+pub struct Library {
+    ui_tx: Sender<AppCallback>,
+    pub route: LibraryInputRouting,
+    pub playlists: LibraryPlaylistsPanel,
+    pub albums: LibraryAlbumsPanel,
+    pub artists: LibraryArtistsPanel,
+    keybinds: Vec<KeyCommand<LibraryAction>>,
+    // Not available for artists, whose songs are only listed once opened.
+    queue_keybinds: Vec<KeyCommand<LibraryAction>>,
+    pub page_lines: isize,
+}
+
+pub struct LibraryPlaylistsPanel {
+    pub list: Vec<Playlist>,
+    pub state: ListStatus,
+    cur_selected: usize,
+}
+
+pub struct LibraryAlbumsPanel {
+    pub list: Vec<LibraryAlbum>,
+    pub state: ListStatus,
+    cur_selected: usize,
+}
+
+pub struct LibraryArtistsPanel {
+    pub list: Vec<LibraryArtist>,
+    pub state: ListStatus,
+    cur_selected: usize,
+}
+
+impl Action for LibraryAction {
+    fn context(&self) -> Cow<str> {
+        tr("Library").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            LibraryAction::ViewBrowser => "View Browser",
+            LibraryAction::Refresh => "Refresh",
+            LibraryAction::Open => "Open",
+            LibraryAction::QueueAll => "Queue all",
+            LibraryAction::OpenInBrowser => "Open in Web Browser",
+            LibraryAction::Left => "Left",
+            LibraryAction::Right => "Right",
+            LibraryAction::Up => "Up",
+            LibraryAction::Down => "Down",
+            LibraryAction::PageUp => "Page Up",
+            LibraryAction::PageDown => "Page Down",
+            LibraryAction::Home => "Top",
+            LibraryAction::End => "Bottom",
+        })
+        .into()
+    }
+}
+
+impl DrawableMut for Library {
+    fn draw_mut_chunk(
+        &self,
+        f: &mut Frame,
+        chunk: Rect,
+        mutable_state: &mut YoutuiMutableState,
+        selected: bool,
+    ) {
+        draw_library(
+            f,
+            self,
+            chunk,
+            &mut mutable_state.library_playlists_state,
+            &mut mutable_state.library_albums_state,
+            &mut mutable_state.library_artists_state,
+            selected,
+        )
+    }
+}
+
+impl KeyRouter<LibraryAction> for Library {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<LibraryAction>> + 'a> {
+        match self.route {
+            LibraryInputRouting::Artists => Box::new(self.keybinds.iter()),
+            _ => Box::new(self.keybinds.iter().chain(self.queue_keybinds.iter())),
+        }
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<LibraryAction>> + 'a> {
+        Box::new(self.keybinds.iter().chain(self.queue_keybinds.iter()))
+    }
+}
+
+impl TextHandler for Library {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<LibraryAction> for Library {
+    async fn handle_action(&mut self, action: &LibraryAction) {
+        match action {
+            LibraryAction::ViewBrowser => self.handle_view_browser().await,
+            LibraryAction::Refresh => self.refresh().await,
+            LibraryAction::Open => self.open().await,
+            LibraryAction::QueueAll => self.queue_all().await,
+            LibraryAction::OpenInBrowser => self.open_in_browser().await,
+            LibraryAction::Left => self.left(),
+            LibraryAction::Right => self.right(),
+            LibraryAction::Up => self.increment_cur_list(-1),
+            LibraryAction::Down => self.increment_cur_list(1),
+            LibraryAction::PageUp => self.increment_cur_list(-self.page_lines),
+            LibraryAction::PageDown => self.increment_cur_list(self.page_lines),
+            LibraryAction::Home => self.increment_cur_list(isize::MIN),
+            LibraryAction::End => self.increment_cur_list(isize::MAX),
+        }
+    }
+}
+
+impl Library {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            route: Default::default(),
+            playlists: LibraryPlaylistsPanel {
+                list: Vec::new(),
+                state: ListStatus::New,
+                cur_selected: 0,
+            },
+            albums: LibraryAlbumsPanel {
+                list: Vec::new(),
+                state: ListStatus::New,
+                cur_selected: 0,
+            },
+            artists: LibraryArtistsPanel {
+                list: Vec::new(),
+                state: ListStatus::New,
+                cur_selected: 0,
+            },
+            keybinds: library_keybinds(),
+            queue_keybinds: library_queue_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Returns true if the library has not yet been requested.
+    pub fn is_new(&self) -> bool {
+        matches!(self.playlists.state, ListStatus::New)
+    }
+    /// Jump the focused pane to the next row starting with the character.
+    pub fn jump_to_char(&mut self, c: char) -> bool {
+        match self.route {
+            LibraryInputRouting::Playlists => self.playlists.jump_to_char(c),
+            LibraryInputRouting::Albums => self.albums.jump_to_char(c),
+            LibraryInputRouting::Artists => self.artists.jump_to_char(c),
+        }
+    }
+    pub async fn refresh(&mut self) {
+        self.playlists.state = ListStatus::Loading;
+        self.albums.state = ListStatus::Loading;
+        self.artists.state = ListStatus::Loading;
+        send_or_error(&self.ui_tx, AppCallback::GetLibrary).await;
+    }
+    pub fn handle_replace_library(
+        &mut self,
+        playlists: Vec<Playlist>,
+        albums: Vec<LibraryAlbum>,
+        artists: Vec<LibraryArtist>,
+    ) {
+        self.playlists.list = playlists;
+        self.playlists.state = ListStatus::Loaded;
+        self.playlists.increment_list(0);
+        self.albums.list = albums;
+        self.albums.state = ListStatus::Loaded;
+        self.albums.increment_list(0);
+        self.artists.list = artists;
+        self.artists.state = ListStatus::Loaded;
+        self.artists.increment_list(0);
+    }
+    pub fn handle_library_error(&mut self) {
+        self.playlists.state = ListStatus::Error;
+        self.albums.state = ListStatus::Error;
+        self.artists.state = ListStatus::Error;
+    }
+    pub fn handle_library_retrying(&mut self) {
+        self.playlists.state.set_retrying();
+        self.albums.state.set_retrying();
+        self.artists.state.set_retrying();
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    fn left(&mut self) {
+        self.route = match self.route {
+            LibraryInputRouting::Playlists | LibraryInputRouting::Albums => {
+                LibraryInputRouting::Playlists
+            }
+            LibraryInputRouting::Artists => LibraryInputRouting::Albums,
+        }
+    }
+    fn right(&mut self) {
+        self.route = match self.route {
+            LibraryInputRouting::Playlists => LibraryInputRouting::Albums,
+            LibraryInputRouting::Albums | LibraryInputRouting::Artists => {
+                LibraryInputRouting::Artists
+            }
+        }
+    }
+    fn increment_cur_list(&mut self, amount: isize) {
+        match self.route {
+            LibraryInputRouting::Playlists => self.playlists.increment_list(amount),
+            LibraryInputRouting::Albums => self.albums.increment_list(amount),
+            LibraryInputRouting::Artists => self.artists.increment_list(amount),
+        }
+    }
+    async fn open(&mut self) {
+        match self.route {
+            LibraryInputRouting::Playlists => {
+                let Some(playlist) = self.playlists.list.get(self.playlists.cur_selected) else {
+                    return;
+                };
+                send_or_error(
+                    &self.ui_tx,
+                    AppCallback::ViewPlaylistSongs(playlist.playlist_id.clone()),
+                )
+                .await;
+            }
+            LibraryInputRouting::Albums => {
+                let Some(album) = self.albums.list.get(self.albums.cur_selected) else {
+                    return;
+                };
+                send_or_error(&self.ui_tx, AppCallback::ViewAlbum(album.album_id.clone())).await;
+            }
+            LibraryInputRouting::Artists => {
+                let Some(artist) = self.artists.list.get(self.artists.cur_selected) else {
+                    return;
+                };
+                send_or_error(
+                    &self.ui_tx,
+                    AppCallback::ViewArtist(artist.channel_id.clone()),
+                )
+                .await;
+            }
+        }
+    }
+    async fn open_in_browser(&mut self) {
+        let url = match self.route {
+            LibraryInputRouting::Playlists => self
+                .playlists
+                .list
+                .get(self.playlists.cur_selected)
+                .map(|p| p.playlist_id.get_url()),
+            LibraryInputRouting::Albums => self
+                .albums
+                .list
+                .get(self.albums.cur_selected)
+                .map(|a| a.album_id.get_url()),
+            LibraryInputRouting::Artists => self
+                .artists
+                .list
+                .get(self.artists.cur_selected)
+                .map(|a| a.channel_id.get_url()),
+        };
+        if let Some(url) = url {
+            send_or_error(&self.ui_tx, AppCallback::OpenInBrowser(url)).await;
+        }
+    }
+    async fn queue_all(&mut self) {
+        let callback = match self.route {
+            LibraryInputRouting::Playlists => {
+                let Some(playlist) = self.playlists.list.get(self.playlists.cur_selected) else {
+                    return;
+                };
+                AppCallback::QueuePlaylistSongs(playlist.playlist_id.clone())
+            }
+            LibraryInputRouting::Albums => {
+                let Some(album) = self.albums.list.get(self.albums.cur_selected) else {
+                    return;
+                };
+                AppCallback::GetAlbumSongs {
+                    album_id: album.album_id.clone(),
+                    artist: album.artist.clone(),
+                    play: false,
+                }
+            }
+            // Not routed, see queue_keybinds.
+            LibraryInputRouting::Artists => return,
+        };
+        send_or_error(&self.ui_tx, callback).await;
+    }
+}
+
+impl Loadable for LibraryPlaylistsPanel {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for LibraryPlaylistsPanel {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.list.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for LibraryPlaylistsPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for LibraryPlaylistsPanel {
+    fn get_title(&self) -> Cow<str> {
+        match self.state {
+            ListStatus::Loaded => tr_format("Playlists - {} results", &[&self.list.len()]).into(),
+            ListStatus::Error => tr("Playlists - Error receieved").into(),
+            _ => tr("Playlists").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(60)),
+            BasicConstraint::Percentage(Percentage(40)),
+            BasicConstraint::Length(6),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        Box::new(self.list.iter().map(|p| {
+            Box::new(
+                [
+                    Cow::from(p.title.as_str()),
+                    Cow::from(p.author.as_deref().unwrap_or_default()),
+                    p.count
+                        .map(|c| Cow::from(c.to_string()))
+                        .unwrap_or_default(),
+                ]
+                .into_iter(),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Playlist", "Author", "Songs"].into_iter())
+    }
+}
+
+impl Loadable for LibraryAlbumsPanel {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for LibraryAlbumsPanel {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.list.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for LibraryAlbumsPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for LibraryAlbumsPanel {
+    fn get_title(&self) -> Cow<str> {
+        match self.state {
+            ListStatus::Loaded => tr_format("Albums - {} results", &[&self.list.len()]).into(),
+            ListStatus::Error => tr("Albums - Error receieved").into(),
+            _ => tr("Albums").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(50)),
+            BasicConstraint::Percentage(Percentage(50)),
+            BasicConstraint::Length(4),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        Box::new(self.list.iter().map(|a| {
+            Box::new(
+                [
+                    a.title.as_str(),
+                    a.artist.as_str(),
+                    a.year.as_deref().unwrap_or_default(),
+                ]
+                .into_iter()
+                .map(Cow::from),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Album", "Artist", "Year"].into_iter())
+    }
+}
+
+impl Loadable for LibraryArtistsPanel {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for LibraryArtistsPanel {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.list.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for LibraryArtistsPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for LibraryArtistsPanel {
+    fn get_title(&self) -> Cow<str> {
+        match self.state {
+            ListStatus::Loaded => tr_format("Artists - {} results", &[&self.list.len()]).into(),
+            ListStatus::Error => tr("Artists - Error receieved").into(),
+            _ => tr("Artists").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(60)),
+            BasicConstraint::Percentage(Percentage(40)),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        Box::new(self.list.iter().map(|a| {
+            Box::new(
+                [a.artist.as_str(), a.byline.as_str()]
+                    .into_iter()
+                    .map(Cow::from),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Artist", "Info"].into_iter())
+    }
+}
+
+fn library_keybinds() -> Vec<KeyCommand<LibraryAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), LibraryAction::ViewBrowser),
+        KeyCommand::new_from_code(KeyCode::Char('r'), LibraryAction::Refresh),
+        KeyCommand::new_from_code(KeyCode::Enter, LibraryAction::Open),
+        KeyCommand::new_from_code(KeyCode::Char('o'), LibraryAction::OpenInBrowser),
+        KeyCommand::new_from_code(KeyCode::Left, LibraryAction::Left),
+        KeyCommand::new_from_code(KeyCode::Right, LibraryAction::Right),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, LibraryAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, LibraryAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, LibraryAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, LibraryAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, LibraryAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, LibraryAction::End),
+    ]
+}
+
+fn library_queue_keybinds() -> Vec<KeyCommand<LibraryAction>> {
+    vec![KeyCommand::new_from_code(
+        KeyCode::Char('a'),
+        LibraryAction::QueueAll,
+    )]
+}
+
+pub mod draw {
+    use super::{Library, LibraryInputRouting};
+    use crate::app::view::draw::draw_table;
+    use ratatui::{
+        prelude::{Constraint, Direction, Layout, Rect},
+        widgets::TableState,
+        Frame,
+    };
+
+    pub fn draw_library(
+        f: &mut Frame,
+        l: &Library,
+        chunk: Rect,
+        playlists_state: &mut TableState,
+        albums_state: &mut TableState,
+        artists_state: &mut TableState,
+        selected: bool,
+    ) {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            [
+                Constraint::Percentage(40),
+                Constraint::Percentage(35),
+                Constraint::Percentage(25),
+            ],
+        )
+        .split(chunk);
+        draw_table(
+            f,
+            &l.playlists,
+            layout[0],
+            playlists_state,
+            selected && l.route == LibraryInputRouting::Playlists,
+        );
+        draw_table(
+            f,
+            &l.albums,
+            layout[1],
+            albums_state,
+            selected && l.route == LibraryInputRouting::Albums,
+        );
+        draw_table(
+            f,
+            &l.artists,
+            layout[2],
+            artists_state,
+            selected && l.route == LibraryInputRouting::Artists,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Library, LibraryAction, LibraryInputRouting};
+    use crate::app::component::actionhandler::KeyRouter;
+    use crate::app::keycommand::Keymap;
+
+    fn routes_queue_all(library: &Library) -> bool {
+        library
+            .get_routed_keybinds()
+            .any(|kb| matches!(kb.key_map, Keymap::Action(LibraryAction::QueueAll)))
+    }
+    #[test]
+    fn test_queue_all_routed_except_for_artists() {
+        let (tx, _rx) = tokio::sync::mpsc::channel(1);
+        let mut library = Library::new(tx);
+        assert!(routes_queue_all(&library));
+        library.right();
+        assert_eq!(library.route, LibraryInputRouting::Albums);
+        assert!(routes_queue_all(&library));
+        library.right();
+        library.right();
+        assert_eq!(library.route, LibraryInputRouting::Artists);
+        assert!(!routes_queue_all(&library));
+        library.left();
+        assert_eq!(library.route, LibraryInputRouting::Albums);
+    }
+}
@@ -0,0 +1,144 @@
+use self::storage::{LocalPlaylist, LocalPlaylists};
+use crate::app::component::actionhandler::TextHandler;
+use crate::app::structures::SavedSong;
+use crate::app::view::Scrollable;
+use crate::Result;
+
+mod storage;
+
+/// Popup to save the playlist under a name, and to load, append or delete saved playlists.
+/// Saved playlists are stored locally, and are independent of YouTube Music.
+pub struct LocalPlaylistsPopup {
+    pub shown: bool,
+    pub cur: usize,
+    /// Name to save the playlist as.
+    pub name: String,
+    pub playlists: LocalPlaylists,
+}
+
+impl Scrollable for LocalPlaylistsPopup {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur = self
+            .cur
+            .saturating_add_signed(amount)
+            .min(self.playlists.playlists.len().saturating_sub(1));
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur
+    }
+}
+
+impl TextHandler for LocalPlaylistsPopup {
+    fn push_text(&mut self, c: char) {
+        self.name.push(c);
+    }
+    fn pop_text(&mut self) {
+        self.name.pop();
+    }
+    fn is_text_handling(&self) -> bool {
+        self.shown
+    }
+    fn take_text(&mut self) -> String {
+        std::mem::take(&mut self.name)
+    }
+    fn replace_text(&mut self, text: String) {
+        self.name = text;
+    }
+}
+
+impl LocalPlaylistsPopup {
+    /// Create the popup, loading the saved playlists from the data directory.
+    pub fn load() -> Self {
+        Self {
+            shown: false,
+            cur: 0,
+            name: String::new(),
+            playlists: LocalPlaylists::load(),
+        }
+    }
+    pub fn open(&mut self) {
+        self.shown = true;
+        self.name.clear();
+        self.increment_list(0);
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+    }
+    pub fn get_selected(&self) -> Option<&LocalPlaylist> {
+        self.playlists.playlists.get(self.cur)
+    }
+    /// Save the songs under the entered name, selecting the saved playlist.
+    /// Returns true if an existing playlist was replaced.
+    pub fn save_songs(&mut self, songs: Vec<SavedSong>) -> Result<bool> {
+        let name = self.name.trim().to_string();
+        let replaced = self.playlists.insert(LocalPlaylist {
+            name: name.clone(),
+            songs,
+        });
+        if let Some(idx) = self.playlists.playlists.iter().position(|p| p.name == name) {
+            self.cur = idx;
+        }
+        self.playlists.save()?;
+        Ok(replaced)
+    }
+    pub fn delete(&mut self, name: &str) -> Result<()> {
+        if self.playlists.remove(name).is_some() {
+            self.increment_list(0);
+            self.playlists.save()?;
+        }
+        Ok(())
+    }
+}
+
+pub mod draw {
+    use super::LocalPlaylistsPopup;
+    use crate::drawutils::{
+        centered_rect, highlight_style, SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR, TEXT_COLOUR,
+    };
+    use crate::i18n::{tr, tr_format};
+    use ratatui::{
+        prelude::{Constraint, Direction, Layout, Rect},
+        style::Style,
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+        Frame,
+    };
+
+    const POPUP_WIDTH: u16 = 50;
+    const POPUP_MAX_HEIGHT: u16 = 20;
+
+    pub fn draw_local_playlists(f: &mut Frame, p: &LocalPlaylistsPopup, chunk: Rect) {
+        let height = (p.playlists.playlists.len() as u16 + 4).clamp(5, POPUP_MAX_HEIGHT);
+        let area = centered_rect(height, POPUP_WIDTH, chunk);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+            .title(tr("Local Playlists"));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(inner);
+        let name = Paragraph::new(Line::from(vec![
+            Span::styled(tr("Save as: "), Style::new().fg(TABLE_HEADINGS_COLOUR)),
+            Span::styled(p.name.as_str(), Style::new().fg(TEXT_COLOUR)),
+        ]))
+        .block(Block::default().borders(Borders::BOTTOM));
+        f.render_widget(name, layout[0]);
+        if p.playlists.playlists.is_empty() {
+            let empty =
+                Paragraph::new(tr("No saved playlists")).style(Style::new().fg(TEXT_COLOUR));
+            f.render_widget(empty, layout[1]);
+            return;
+        }
+        let items = p.playlists.playlists.iter().map(|pl| {
+            ListItem::new(tr_format("{} - {} songs", &[&pl.name, &pl.songs.len()]))
+                .style(Style::new().fg(TEXT_COLOUR))
+        });
+        let mut state = ListState::default().with_selected(Some(p.cur));
+        let list = List::new(items).highlight_style(highlight_style());
+        f.render_stateful_widget(list, layout[1], &mut state);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::app::datafile::{load_data_file, save_data_file};
+use crate::app::structures::SavedSong;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+const LOCAL_PLAYLISTS_FILE_NAME: &str = "local_playlists.json";
+
+/// A named list of songs saved from the playlist.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LocalPlaylist {
+    pub name: String,
+    pub songs: Vec<SavedSong>,
+}
+
+/// Playlists saved by the user, stored in the data directory.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalPlaylists {
+    pub playlists: Vec<LocalPlaylist>,
+}
+
+impl LocalPlaylists {
+    /// Load the playlists from the data directory, or no playlists if they don't exist or can't
+    /// be read.
+    pub fn load() -> Self {
+        load_data_file(LOCAL_PLAYLISTS_FILE_NAME).unwrap_or_default()
+    }
+    pub fn save(&self) -> Result<()> {
+        save_data_file(LOCAL_PLAYLISTS_FILE_NAME, self)
+    }
+    /// Add the playlist, replacing any existing playlist with the same name.
+    /// Returns true if a playlist was replaced.
+    pub fn insert(&mut self, playlist: LocalPlaylist) -> bool {
+        if let Some(existing) = self.playlists.iter_mut().find(|p| p.name == playlist.name) {
+            *existing = playlist;
+            true
+        } else {
+            self.playlists.push(playlist);
+            false
+        }
+    }
+    pub fn remove(&mut self, name: &str) -> Option<LocalPlaylist> {
+        let idx = self.playlists.iter().position(|p| p.name == name)?;
+        Some(self.playlists.remove(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalPlaylist, LocalPlaylists};
+    use crate::app::structures::test_saved_song;
+
+    fn playlist(name: &str, video_ids: &[&str]) -> LocalPlaylist {
+        LocalPlaylist {
+            name: name.into(),
+            songs: video_ids.iter().copied().map(test_saved_song).collect(),
+        }
+    }
+    #[test]
+    fn test_insert_replaces_same_name() {
+        let mut playlists = LocalPlaylists::default();
+        assert!(!playlists.insert(playlist("Mix", &["a"])));
+        assert!(!playlists.insert(playlist("Chill", &["b"])));
+        assert!(playlists.insert(playlist("Mix", &["c", "d"])));
+        assert_eq!(playlists.playlists.len(), 2);
+        assert_eq!(playlists.playlists[0].name, "Mix");
+        assert_eq!(playlists.playlists[0].songs.len(), 2);
+        assert_eq!(playlists.remove("Mix").map(|p| p.songs.len()), Some(2));
+        assert_eq!(playlists.remove("Mix"), None);
+    }
+    #[test]
+    fn test_local_playlists_round_trip() {
+        let mut playlists = LocalPlaylists::default();
+        playlists.insert(playlist("Mix", &["a", "b"]));
+        let serialized = serde_json::to_string(&playlists).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LocalPlaylists>(&serialized).unwrap(),
+            playlists
+        );
+    }
+}
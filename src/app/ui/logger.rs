@@ -5,13 +5,101 @@ use crate::app::{
     view::Drawable,
 };
 use crate::core::send_or_error;
+use crate::i18n::tr;
 use crossterm::event::KeyCode;
 use draw::draw_logger;
 use ratatui::{prelude::Rect, Frame};
 use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::mpsc::Sender;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::{Context, Layer};
 use tui_logger::TuiWidgetEvent;
 
+/// Set when a message is captured for the Logs pane, so that it is redrawn.
+static NEW_LOG_RECORDS: AtomicBool = AtomicBool::new(false);
+
+/// Which crates log messages are captured from.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum LogTarget {
+    #[default]
+    Youtui,
+    Ytmapi,
+    All,
+}
+
+/// Filter applied to log messages as they are captured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogFilter {
+    pub level: Level,
+    pub target: LogTarget,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::DEBUG,
+            target: LogTarget::Youtui,
+        }
+    }
+}
+
+impl LogFilter {
+    pub fn to_targets(self) -> Targets {
+        match self.target {
+            LogTarget::Youtui => Targets::new().with_target("youtui", self.level),
+            LogTarget::Ytmapi => Targets::new().with_target("ytmapi_rs", self.level),
+            LogTarget::All => Targets::new()
+                .with_target("youtui", self.level)
+                .with_target("ytmapi_rs", self.level),
+        }
+    }
+    /// Cycle from most to least verbose, wrapping around.
+    fn next_level(self) -> Self {
+        let level = match self.level {
+            Level::TRACE => Level::DEBUG,
+            Level::DEBUG => Level::INFO,
+            Level::INFO => Level::WARN,
+            Level::WARN => Level::ERROR,
+            Level::ERROR => Level::TRACE,
+        };
+        Self { level, ..self }
+    }
+    fn next_target(self) -> Self {
+        let target = match self.target {
+            LogTarget::Youtui => LogTarget::Ytmapi,
+            LogTarget::Ytmapi => LogTarget::All,
+            LogTarget::All => LogTarget::Youtui,
+        };
+        Self { target, ..self }
+    }
+}
+
+/// Notes when a message has been captured for the Logs pane. Uses the same filter as the pane.
+pub struct NewLogRecordsLayer;
+
+impl<S: Subscriber> Layer<S> for NewLogRecordsLayer {
+    fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, S>) {
+        NEW_LOG_RECORDS.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Returns true if a message has been captured since this was last called.
+pub fn take_new_log_records() -> bool {
+    NEW_LOG_RECORDS.swap(false, Ordering::Relaxed)
+}
+
+impl std::fmt::Display for LogTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogTarget::Youtui => write!(f, "youtui"),
+            LogTarget::Ytmapi => write!(f, "ytmapi-rs"),
+            LogTarget::All => write!(f, "all"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum LoggerAction {
     ToggleTargetSelector,
@@ -26,34 +114,44 @@ pub enum LoggerAction {
     ReduceCaptured,
     IncreaseCaptured,
     ExitPageMode,
+    ToggleFollow,
+    CycleLevel,
+    CycleTarget,
     ViewBrowser,
 }
 impl Action for LoggerAction {
     fn context(&self) -> Cow<str> {
-        "Logger".into()
+        tr("Logger").into()
     }
     fn describe(&self) -> Cow<str> {
-        match self {
-            LoggerAction::ViewBrowser => "View Browser".into(),
-            LoggerAction::ToggleTargetSelector => "Toggle Target Selector Widget".into(),
-            LoggerAction::ToggleTargetFocus => "Toggle Focus Selected Target".into(),
-            LoggerAction::ToggleHideFiltered => "Toggle Hide Filtered Targets".into(),
-            LoggerAction::Up => "Up - Selector".into(),
-            LoggerAction::Down => "Down - Selector".into(),
-            LoggerAction::PageUp => "Enter Page Mode, Scroll History Up".into(),
-            LoggerAction::PageDown => "In Page Mode: Scroll History Down".into(),
-            LoggerAction::ReduceShown => "Reduce SHOWN (!) Messages".into(),
-            LoggerAction::IncreaseShown => "Increase SHOWN (!) Messages".into(),
-            LoggerAction::ReduceCaptured => "Reduce CAPTURED (!) Messages".into(),
-            LoggerAction::IncreaseCaptured => "Increase CAPTURED (!) Messages".into(),
-            LoggerAction::ExitPageMode => "Exit Page Mode".into(),
-        }
+        tr(match self {
+            LoggerAction::ViewBrowser => "View Browser",
+            LoggerAction::ToggleTargetSelector => "Toggle Target Selector Widget",
+            LoggerAction::ToggleTargetFocus => "Toggle Focus Selected Target",
+            LoggerAction::ToggleHideFiltered => "Toggle Hide Filtered Targets",
+            LoggerAction::Up => "Up - Selector",
+            LoggerAction::Down => "Down - Selector",
+            LoggerAction::PageUp => "Enter Page Mode, Scroll History Up",
+            LoggerAction::PageDown => "In Page Mode: Scroll History Down",
+            LoggerAction::ReduceShown => "Reduce SHOWN (!) Messages",
+            LoggerAction::IncreaseShown => "Increase SHOWN (!) Messages",
+            LoggerAction::ReduceCaptured => "Reduce CAPTURED (!) Messages",
+            LoggerAction::IncreaseCaptured => "Increase CAPTURED (!) Messages",
+            LoggerAction::ExitPageMode => "Exit Page Mode",
+            LoggerAction::ToggleFollow => "Pause/Follow Log",
+            LoggerAction::CycleLevel => "Cycle Captured Level",
+            LoggerAction::CycleTarget => "Cycle Captured Target",
+        })
+        .into()
     }
 }
 pub struct Logger {
     logger_state: tui_logger::TuiWidgetState,
     ui_tx: Sender<AppCallback>,
     keybinds: Vec<KeyCommand<LoggerAction>>,
+    filter: LogFilter,
+    /// True if the log is in page mode, and so not following new messages.
+    paused: bool,
 }
 
 impl Drawable for Logger {
@@ -103,6 +201,9 @@ impl ActionHandler<LoggerAction> for Logger {
             LoggerAction::ReduceCaptured => self.handle_reduce_captured(),
             LoggerAction::IncreaseCaptured => self.handle_increase_captured(),
             LoggerAction::ExitPageMode => self.handle_exit_page_mode(),
+            LoggerAction::ToggleFollow => self.handle_toggle_follow(),
+            LoggerAction::CycleLevel => self.set_filter(self.filter.next_level()).await,
+            LoggerAction::CycleTarget => self.set_filter(self.filter.next_target()).await,
             LoggerAction::ViewBrowser => self.handle_view_browser().await,
         }
     }
@@ -114,6 +215,19 @@ impl Logger {
             ui_tx,
             logger_state: tui_logger::TuiWidgetState::default(),
             keybinds: logger_keybinds(),
+            filter: LogFilter::default(),
+            paused: false,
+        }
+    }
+    async fn set_filter(&mut self, filter: LogFilter) {
+        self.filter = filter;
+        send_or_error(&self.ui_tx, AppCallback::SetLogFilter(filter)).await;
+    }
+    fn handle_toggle_follow(&mut self) {
+        if self.paused {
+            self.handle_exit_page_mode();
+        } else {
+            self.handle_pgup();
         }
     }
     async fn handle_view_browser(&mut self) {
@@ -136,6 +250,7 @@ impl Logger {
         self.logger_state.transition(&TuiWidgetEvent::NextPageKey);
     }
     fn handle_pgup(&mut self) {
+        self.paused = true;
         self.logger_state.transition(&TuiWidgetEvent::PrevPageKey);
     }
     fn handle_reduce_shown(&mut self) {
@@ -145,6 +260,7 @@ impl Logger {
         self.logger_state.transition(&TuiWidgetEvent::RightKey);
     }
     fn handle_exit_page_mode(&mut self) {
+        self.paused = false;
         self.logger_state.transition(&TuiWidgetEvent::EscapeKey);
     }
     fn handle_increase_captured(&mut self) {
@@ -176,6 +292,9 @@ fn logger_keybinds() -> Vec<KeyCommand<LoggerAction>> {
         KeyCommand::new_from_code(KeyCode::Esc, LoggerAction::ExitPageMode),
         KeyCommand::new_from_code(KeyCode::Char('f'), LoggerAction::ToggleTargetFocus),
         KeyCommand::new_from_code(KeyCode::Char('h'), LoggerAction::ToggleTargetSelector),
+        KeyCommand::new_from_code(KeyCode::Char('p'), LoggerAction::ToggleFollow),
+        KeyCommand::new_from_code(KeyCode::Char('l'), LoggerAction::CycleLevel),
+        KeyCommand::new_from_code(KeyCode::Char('t'), LoggerAction::CycleTarget),
     ]
 }
 
@@ -183,8 +302,9 @@ pub mod draw {
     use super::Logger;
     use crate::drawutils::{DESELECTED_BORDER_COLOUR, SELECTED_BORDER_COLOUR};
     use ratatui::{
-        prelude::Rect,
+        prelude::{Constraint, Direction, Layout, Rect},
         style::{Color, Style},
+        widgets::Paragraph,
         Frame,
     };
 
@@ -203,6 +323,17 @@ pub mod draw {
             .border_style(Style::default().fg(border_colour))
             .state(&l.logger_state)
             .output_timestamp(Some("%H:%M:%S:%3f".to_string()));
-        f.render_widget(log, chunk);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(chunk);
+        let status = format!(
+            "Capturing: {} from {} | {}",
+            l.filter.level,
+            l.filter.target,
+            if l.paused { "Paused" } else { "Following" }
+        );
+        f.render_widget(Paragraph::new(status), layout[0]);
+        f.render_widget(log, layout[1]);
     }
 }
@@ -0,0 +1,293 @@
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{ListSongID, ListStatus},
+    ui::{AppCallback, WindowContext},
+    view::Drawable,
+};
+use crate::core::send_or_error;
+use crate::i18n::tr;
+use crossterm::event::KeyCode;
+use draw::draw_lyrics;
+use ratatui::{prelude::Rect, Frame};
+use std::borrow::Cow;
+use tokio::sync::mpsc::Sender;
+use ytmapi_rs::{common::browsing::Lyrics as LyricsResult, VideoID};
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LyricsAction {
+    ViewBrowser,
+    Refresh,
+    ToggleAutoScroll,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+/// Lyrics for the currently playing song.
+pub struct Lyrics {
+    ui_tx: Sender<AppCallback>,
+    pub lyrics: Option<LyricsResult>,
+    pub state: ListStatus,
+    /// Song the lyrics have been requested for.
+    pub song: Option<LyricsSong>,
+    pub cur_played_secs: Option<f64>,
+    /// Follow the current line if timed lyrics are available.
+    pub auto_scroll: bool,
+    pub scroll: u16,
+    keybinds: Vec<KeyCommand<LyricsAction>>,
+    pub page_lines: isize,
+}
+
+pub struct LyricsSong {
+    pub id: ListSongID,
+    pub video_id: VideoID<'static>,
+    pub title: String,
+}
+
+impl Action for LyricsAction {
+    fn context(&self) -> Cow<str> {
+        tr("Lyrics").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            LyricsAction::ViewBrowser => "View Browser",
+            LyricsAction::Refresh => "Refresh",
+            LyricsAction::ToggleAutoScroll => "Toggle Auto Scroll",
+            LyricsAction::Up => "Up",
+            LyricsAction::Down => "Down",
+            LyricsAction::PageUp => "Page Up",
+            LyricsAction::PageDown => "Page Down",
+            LyricsAction::Home => "Top",
+            LyricsAction::End => "Bottom",
+        })
+        .into()
+    }
+}
+
+impl Drawable for Lyrics {
+    fn draw_chunk(&self, f: &mut Frame, chunk: Rect, selected: bool) {
+        draw_lyrics(f, self, chunk, selected)
+    }
+}
+
+impl KeyRouter<LyricsAction> for Lyrics {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<LyricsAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<LyricsAction>> + 'a> {
+        self.get_routed_keybinds()
+    }
+}
+
+impl TextHandler for Lyrics {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<LyricsAction> for Lyrics {
+    async fn handle_action(&mut self, action: &LyricsAction) {
+        match action {
+            LyricsAction::ViewBrowser => self.handle_view_browser().await,
+            LyricsAction::Refresh => self.refresh().await,
+            LyricsAction::ToggleAutoScroll => self.auto_scroll = !self.auto_scroll,
+            LyricsAction::Up => self.scroll_lines(-1),
+            LyricsAction::Down => self.scroll_lines(1),
+            LyricsAction::PageUp => self.scroll_lines(-self.page_lines),
+            LyricsAction::PageDown => self.scroll_lines(self.page_lines),
+            LyricsAction::Home => self.scroll_lines(isize::MIN),
+            LyricsAction::End => self.scroll_lines(isize::MAX),
+        }
+    }
+}
+
+impl Lyrics {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            lyrics: None,
+            state: ListStatus::New,
+            song: None,
+            cur_played_secs: None,
+            auto_scroll: true,
+            scroll: 0,
+            keybinds: lyrics_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Update the currently playing song, fetching its lyrics if it has changed.
+    pub async fn handle_song_update(
+        &mut self,
+        song: Option<LyricsSong>,
+        cur_played_secs: Option<f64>,
+    ) {
+        self.cur_played_secs = cur_played_secs;
+        let changed = match (&self.song, &song) {
+            (Some(old), Some(new)) => old.id != new.id,
+            (None, None) => false,
+            _ => true,
+        };
+        if !changed {
+            return;
+        }
+        self.song = song;
+        self.lyrics = None;
+        self.scroll = 0;
+        match self.song {
+            Some(_) => self.refresh().await,
+            None => self.state = ListStatus::New,
+        }
+    }
+    pub async fn refresh(&mut self) {
+        let Some(song) = &self.song else {
+            return;
+        };
+        self.state = ListStatus::Loading;
+        send_or_error(&self.ui_tx, AppCallback::GetLyrics(song.video_id.clone())).await;
+    }
+    pub fn handle_replace_lyrics(&mut self, lyrics: LyricsResult) {
+        self.lyrics = Some(lyrics);
+        self.state = ListStatus::Loaded;
+    }
+    pub fn handle_lyrics_error(&mut self) {
+        self.state = ListStatus::Error;
+    }
+    pub fn handle_lyrics_retrying(&mut self) {
+        self.state.set_retrying();
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    fn scroll_lines(&mut self, amount: isize) {
+        // Scrolling manually stops following the song.
+        self.auto_scroll = false;
+        let max = self
+            .lyrics
+            .as_ref()
+            .map(|l| l.get_lyrics().lines().count())
+            .unwrap_or_default();
+        self.scroll = (self.scroll as usize)
+            .saturating_add_signed(amount)
+            .min(max.saturating_sub(1)) as u16;
+    }
+    /// Index of the line currently being sung, if timed lyrics are available.
+    pub fn get_cur_timed_line(&self) -> Option<usize> {
+        let timed_lyrics = self.lyrics.as_ref()?.get_timed_lyrics()?;
+        let cur_ms = (self.cur_played_secs? * 1000.0) as u64;
+        timed_lyrics
+            .iter()
+            .rposition(|line| line.start_ms <= cur_ms)
+    }
+}
+
+fn lyrics_keybinds() -> Vec<KeyCommand<LyricsAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), LyricsAction::ViewBrowser),
+        KeyCommand::new_from_code(KeyCode::Char('r'), LyricsAction::Refresh),
+        KeyCommand::new_from_code(KeyCode::Char('a'), LyricsAction::ToggleAutoScroll),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, LyricsAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, LyricsAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, LyricsAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, LyricsAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, LyricsAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, LyricsAction::End),
+    ]
+}
+
+pub mod draw {
+    use super::Lyrics;
+    use crate::app::structures::ListStatus;
+    use crate::drawutils::{
+        get_spinner, DESELECTED_BORDER_COLOUR, ROW_HIGHLIGHT_COLOUR, SELECTED_BORDER_COLOUR,
+    };
+    use crate::i18n::{tr, tr_format};
+    use ratatui::{
+        prelude::{Alignment, Rect},
+        style::{Modifier, Style},
+        text::Line,
+        widgets::{
+            block::{Position, Title},
+            Block, Borders, Paragraph, Wrap,
+        },
+        Frame,
+    };
+
+    pub fn draw_lyrics(f: &mut Frame, l: &Lyrics, chunk: Rect, selected: bool) {
+        let border_colour = if selected {
+            SELECTED_BORDER_COLOUR
+        } else {
+            DESELECTED_BORDER_COLOUR
+        };
+        let title = match (&l.state, &l.song) {
+            (ListStatus::Loaded, Some(song)) => tr_format("Lyrics - {}", &[&song.title]),
+            (ListStatus::Loading, _) => tr_format("Lyrics - {} Loading", &[&get_spinner()]),
+            (ListStatus::Retrying, _) => tr_format("Lyrics - {} Retrying", &[&get_spinner()]),
+            (ListStatus::Error, _) => tr("Lyrics - No lyrics found").to_string(),
+            (_, None) => tr("Lyrics - Nothing playing").to_string(),
+            _ => tr("Lyrics").to_string(),
+        };
+        let mut block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(border_colour));
+        let Some(lyrics) = &l.lyrics else {
+            f.render_widget(block, chunk);
+            return;
+        };
+        block = block.title(
+            Title::from(lyrics.get_source())
+                .alignment(Alignment::Right)
+                .position(Position::Bottom),
+        );
+        let cur_line = l.get_cur_timed_line();
+        let lines: Vec<Line> = lyrics
+            .get_lyrics()
+            .lines()
+            .enumerate()
+            .map(|(i, text)| {
+                if Some(i) == cur_line {
+                    Line::styled(
+                        text,
+                        Style::new()
+                            .fg(ROW_HIGHLIGHT_COLOUR)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+        // Keep the current line in the middle of the pane when following the song.
+        let scroll = match cur_line {
+            Some(cur_line) if l.auto_scroll => {
+                cur_line.saturating_sub(block.inner(chunk).height as usize / 2) as u16
+            }
+            _ => l.scroll,
+        };
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+        f.render_widget(paragraph, chunk);
+    }
+}
@@ -0,0 +1,116 @@
+use super::footer::{parse_simple_time_to_secs, secs_to_time_string};
+use crate::{
+    app::structures::{ListSong, PlayState},
+    config::{StatusField, StatusFormat, StatusSegment},
+    drawutils::{BUTTON_BG_COLOUR, BUTTON_FG_COLOUR},
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    prelude::Alignment,
+    style::{Modifier, Style},
+    terminal::Frame,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use ytmapi_rs::common::youtuberesult::YoutubeResult;
+
+/// Render the status line using a custom format from config.
+/// Song fields are empty when nothing is playing.
+fn format_status(
+    format: &StatusFormat,
+    w: &super::YoutuiWindow,
+    song: Option<&ListSong>,
+) -> String {
+    format
+        .0
+        .iter()
+        .map(|segment| match segment {
+            StatusSegment::Text(text) => text.clone(),
+            StatusSegment::Field(StatusField::State) => {
+                w.playlist.play_status.list_icon().to_string()
+            }
+            StatusSegment::Field(StatusField::Volume) => w.playlist.volume.0.to_string(),
+            StatusSegment::Field(StatusField::Elapsed) => {
+                secs_to_time_string(w.playlist.cur_played_secs.unwrap_or(0.0) as usize)
+            }
+            StatusSegment::Field(StatusField::Artist) => song
+                .and_then(|s| s.get_artists().first())
+                .map(|a| a.to_string())
+                .unwrap_or_default(),
+            StatusSegment::Field(StatusField::Title) => song
+                .map(|s| s.raw.get_title().to_owned())
+                .unwrap_or_default(),
+            StatusSegment::Field(StatusField::Album) => {
+                song.map(|s| s.get_album().to_owned()).unwrap_or_default()
+            }
+            StatusSegment::Field(StatusField::Duration) => secs_to_time_string(
+                song.and_then(|s| s.raw.get_duration().as_deref())
+                    .map(parse_simple_time_to_secs)
+                    .unwrap_or(0),
+            ),
+        })
+        .collect()
+}
+
+/// Single line summary of the playing song, drawn regardless of the window context.
+pub fn draw_now_playing(f: &mut Frame, w: &super::YoutuiWindow, chunk: Rect) {
+    let song = match w.playlist.play_status {
+        PlayState::Playing(id) | PlayState::Paused(id) | PlayState::Buffering(id) => {
+            w.playlist.get_song_from_id(id)
+        }
+        PlayState::NotPlaying | PlayState::Stopped => None,
+    };
+    if let Some(format) = &w.status_format {
+        let status = format_status(format, w, song);
+        f.render_widget(Paragraph::new(status), chunk);
+        return;
+    }
+    let vol_str = format!(" Vol {:>3}% ", w.playlist.volume.0);
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(vol_str.chars().count() as u16),
+        ])
+        .split(chunk);
+    let vol = Paragraph::new(Line::from(Span::styled(
+        vol_str,
+        Style::new().fg(BUTTON_FG_COLOUR).bg(BUTTON_BG_COLOUR),
+    )))
+    .alignment(Alignment::Right);
+    let mut spans = vec![Span::raw(format!(
+        "{} ",
+        w.playlist.play_status.list_icon()
+    ))];
+    match song {
+        Some(song) => {
+            let artist = song
+                .get_artists()
+                .first()
+                .map(|a| a.as_str())
+                .unwrap_or_default();
+            let duration = song
+                .raw
+                .get_duration()
+                .as_deref()
+                .map(parse_simple_time_to_secs)
+                .unwrap_or(0);
+            let progress = w.playlist.cur_played_secs.unwrap_or(0.0) as usize;
+            spans.push(Span::styled(
+                format!("{artist} – {}", song.raw.get_title()),
+                Style::new().add_modifier(Modifier::BOLD),
+            ));
+            if !song.get_album().is_empty() {
+                spans.push(Span::raw(format!(" ({})", song.get_album())));
+            }
+            spans.push(Span::raw(format!(
+                " {}/{}",
+                secs_to_time_string(progress),
+                secs_to_time_string(duration)
+            )));
+        }
+        None => spans.push(Span::raw("Not playing")),
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), layout[0]);
+    f.render_widget(vol, layout[1]);
+}
@@ -1,27 +1,50 @@
+use crate::app::server::cast::{CastCommand, CastDevice, CastPlayStatus, CastState};
 use crate::app::server::downloader::DownloadProgressUpdateType;
-use crate::app::structures::{Percentage, SongListComponent};
+use crate::app::structures::{Percentage, SongListComponent, DOWNLOAD_GAUGE_WIDTH};
 use crate::app::view::draw::draw_table;
+use crate::app::view::{
+    get_table_column_texts, FilterString, Loadable, Scrollable, TableView, TypeAheadJump,
+};
 use crate::app::view::{BasicConstraint, DrawableMut, TableItem};
-use crate::app::view::{Loadable, Scrollable, TableView};
 use crate::app::{
-    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    component::actionhandler::{Action, ActionHandler, DominantKeyRouter, KeyRouter, TextHandler},
+    events::{EventBus, PlayerEvent},
     keycommand::KeyCommand,
-    structures::{AlbumSongsList, ListSong, ListSongID, PlayState},
-    ui::{AppCallback, WindowContext},
+    structures::{saved_songs_to_list, AlbumSongsList, ListSong, ListSongID, PlayState, SavedSong},
+    ui::{
+        footer::{parse_simple_time_to_secs, secs_to_time_string},
+        songdetails::SongDetails,
+        AppCallback, ConfirmAction, WindowContext,
+    },
 };
 
+use self::queuehistory::QueueHistory;
+use self::session::PlaylistSession;
 use crate::app::YoutuiMutableState;
+use crate::drawutils::SELECTED_BORDER_COLOUR;
+use crate::i18n::{tr, tr_format};
 use crate::{app::structures::DownloadStatus, core::send_or_error};
 use crossterm::event::KeyCode;
-use ratatui::{layout::Rect, terminal::Frame};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    terminal::Frame,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
 use std::iter;
 use std::sync::Arc;
 use std::{borrow::Cow, fmt::Debug};
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
+use ytmapi_rs::common::youtuberesult::YoutubeResult;
+use ytmapi_rs::common::{LikeStatus, YoutubeID};
 
 const SONGS_AHEAD_TO_BUFFER: usize = 3;
 const SONGS_BEHIND_TO_SAVE: usize = 1;
+const PAGE_KEY_LINES: isize = 10;
+
+mod queuehistory;
+mod session;
 
 pub struct Playlist {
     pub list: AlbumSongsList,
@@ -31,7 +54,47 @@ pub struct Playlist {
     ui_tx: mpsc::Sender<AppCallback>,
     pub help_shown: bool,
     keybinds: Vec<KeyCommand<PlaylistAction>>,
+    /// Index into the filtered view of the list.
     cur_selected: usize,
+    pub filter: PlaylistFilter,
+    pub page_lines: isize,
+    /// Previous states of the list, so that edits can be undone.
+    history: QueueHistory<Vec<ListSong>>,
+    events: EventBus,
+    /// While set, the queue is played on the cast device instead of locally.
+    pub cast_device: Option<CastDevice>,
+}
+
+/// Filter box for the playlist. Rows are filtered while typing.
+pub struct PlaylistFilter {
+    pub text: String,
+    pub cur: usize,
+    pub shown: bool,
+    keybinds: Vec<KeyCommand<PlaylistAction>>,
+}
+
+impl Default for PlaylistFilter {
+    fn default() -> Self {
+        Self {
+            text: Default::default(),
+            cur: 0,
+            shown: false,
+            keybinds: filter_keybinds(),
+        }
+    }
+}
+
+impl PlaylistFilter {
+    /// Case insensitive match on artist, album or title. An empty filter matches all songs.
+    pub fn matches(&self, song: &ListSong) -> bool {
+        if self.text.is_empty() {
+            return true;
+        }
+        let filter = FilterString::CaseInsensitive(self.text.clone());
+        song.get_artists().iter().any(|a| filter.is_in(a.as_str()))
+            || filter.is_in(song.get_album())
+            || filter.is_in(song.get_title())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -41,26 +104,58 @@ pub enum PlaylistAction {
     Up,
     PageDown,
     PageUp,
+    Home,
+    End,
     PlaySelected,
     DeleteSelected,
     DeleteAll,
+    AddSelectedToLibraryPlaylist,
+    LikeSelected,
+    DislikeSelected,
+    ToggleFilter,
+    ApplyFilter,
+    ClearFilter,
+    CopySelectedUrl,
+    OpenSelectedInBrowser,
+    GoToSelectedAlbum,
+    GoToSelectedArtist,
+    ShowSelectedDetails,
+    StarSelected,
+    RetrySelectedDownload,
+    ExportSelected,
 }
 
 impl Action for PlaylistAction {
     fn context(&self) -> Cow<str> {
-        "Playlist".into()
+        tr("Playlist").into()
     }
     fn describe(&self) -> Cow<str> {
-        match self {
+        tr(match self {
             PlaylistAction::ViewBrowser => "View Browser",
             PlaylistAction::Down => "Down",
             PlaylistAction::Up => "Up",
             PlaylistAction::PageDown => "Page Down",
             PlaylistAction::PageUp => "Page Up",
+            PlaylistAction::Home => "Top",
+            PlaylistAction::End => "Bottom",
             PlaylistAction::PlaySelected => "Play Selected",
             PlaylistAction::DeleteSelected => "Delete Selected",
             PlaylistAction::DeleteAll => "Delete All",
-        }
+            PlaylistAction::AddSelectedToLibraryPlaylist => "Add Selected to YouTube Playlist",
+            PlaylistAction::LikeSelected => "Like Selected",
+            PlaylistAction::DislikeSelected => "Dislike Selected",
+            PlaylistAction::ToggleFilter => "Filter",
+            PlaylistAction::ApplyFilter => "Apply Filter",
+            PlaylistAction::ClearFilter => "Clear Filter",
+            PlaylistAction::CopySelectedUrl => "Copy URL of Selected",
+            PlaylistAction::OpenSelectedInBrowser => "Open Selected in Web Browser",
+            PlaylistAction::GoToSelectedAlbum => "Go to Album of Selected",
+            PlaylistAction::GoToSelectedArtist => "Go to Artist of Selected",
+            PlaylistAction::ShowSelectedDetails => "Details of Selected",
+            PlaylistAction::StarSelected => "Star Selected",
+            PlaylistAction::RetrySelectedDownload => "Retry Download of Selected",
+            PlaylistAction::ExportSelected => "Export Selected to Music Folder",
+        })
         .into()
     }
 }
@@ -69,25 +164,50 @@ impl KeyRouter<PlaylistAction> for Playlist {
     fn get_all_keybinds<'a>(
         &'a self,
     ) -> Box<dyn Iterator<Item = &'a crate::app::keycommand::KeyCommand<PlaylistAction>> + 'a> {
-        self.get_routed_keybinds()
+        Box::new(self.keybinds.iter().chain(self.filter.keybinds.iter()))
     }
     fn get_routed_keybinds<'a>(
         &'a self,
     ) -> Box<dyn Iterator<Item = &'a crate::app::keycommand::KeyCommand<PlaylistAction>> + 'a> {
-        Box::new(self.keybinds.iter())
+        if self.filter.shown {
+            Box::new(self.filter.keybinds.iter())
+        } else {
+            Box::new(self.keybinds.iter())
+        }
+    }
+}
+
+impl DominantKeyRouter for Playlist {
+    fn dominant_keybinds_active(&self) -> bool {
+        self.filter.shown
     }
 }
 
 impl TextHandler for Playlist {
-    fn push_text(&mut self, _c: char) {}
-    fn pop_text(&mut self) {}
+    fn push_text(&mut self, c: char) {
+        self.filter.text.push(c);
+        self.filter.cur += 1;
+        self.clamp_selected();
+    }
+    fn pop_text(&mut self) {
+        self.filter.text.pop();
+        self.filter.cur = self.filter.cur.saturating_sub(1);
+        self.clamp_selected();
+    }
     fn is_text_handling(&self) -> bool {
-        false
+        self.filter.shown
     }
     fn take_text(&mut self) -> String {
-        Default::default()
+        self.filter.cur = 0;
+        let text = std::mem::take(&mut self.filter.text);
+        self.clamp_selected();
+        text
+    }
+    fn replace_text(&mut self, text: String) {
+        self.filter.cur = text.len();
+        self.filter.text = text;
+        self.clamp_selected();
     }
-    fn replace_text(&mut self, _text: String) {}
 }
 
 impl DrawableMut for Playlist {
@@ -99,9 +219,29 @@ impl DrawableMut for Playlist {
         selected: bool,
     ) {
         draw_table(f, self, chunk, &mut mutable_state.playlist_state, selected);
+        if self.filter.shown {
+            draw_filter_box(f, self, chunk);
+        }
     }
 }
 
+fn draw_filter_box(f: &mut Frame, playlist: &Playlist, chunk: Rect) {
+    // Hardcode dimensions of filter input.
+    let popup_chunk = crate::drawutils::centered_rect(3, 22, chunk);
+    let filter_widget = Paragraph::new(playlist.filter.text.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+            .title(tr("Filter")),
+    );
+    f.render_widget(Clear, popup_chunk);
+    f.render_widget(filter_widget, popup_chunk);
+    f.set_cursor(
+        (popup_chunk.x + playlist.filter.cur as u16 + 1).min(popup_chunk.right().saturating_sub(2)),
+        popup_chunk.y + 1,
+    );
+}
+
 impl Loadable for Playlist {
     fn is_loading(&self) -> bool {
         false
@@ -113,23 +253,54 @@ impl Scrollable for Playlist {
         self.cur_selected = self
             .cur_selected
             .saturating_add_signed(amount)
-            .min(self.list.get_list_iter().len().saturating_sub(1))
+            .min(self.get_filtered_list_iter().count().saturating_sub(1))
     }
     fn get_selected_item(&self) -> usize {
         self.cur_selected
     }
 }
 
+impl TypeAheadJump for Playlist {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        // Jump by song title, rather than by the position or status columns.
+        get_table_column_texts(self, 6)
+    }
+}
+
 impl TableView for Playlist {
     fn get_title(&self) -> Cow<str> {
-        format!("Local playlist - {} songs", self.list.get_list_iter().len()).into()
+        let len = self.list.get_list_iter().len();
+        let title = if self.filter.text.is_empty() {
+            tr_format("Local playlist - {} songs", &[&len])
+        } else {
+            tr_format(
+                "Local playlist - {} of {} songs - filter: {}",
+                &[
+                    &self.get_filtered_list_iter().count(),
+                    &len,
+                    &self.filter.text,
+                ],
+            )
+        };
+        let (downloading, queued) = self.get_download_counts();
+        if downloading == 0 && queued == 0 {
+            title.into()
+        } else {
+            format!(
+                "{title} - {}",
+                tr_format("{} downloading / {} queued", &[&downloading, &queued])
+            )
+            .into()
+        }
     }
     fn get_layout(&self) -> &[BasicConstraint] {
         // Not perfect as this method doesn't know the size of the parent.
         // TODO: Change the get_layout function to something more appropriate.
         &[
             BasicConstraint::Length(3),
-            BasicConstraint::Length(6),
+            BasicConstraint::Length(2),
+            // Icon, gauge and percentage.
+            BasicConstraint::Length(DOWNLOAD_GAUGE_WIDTH as u16 + 5),
             BasicConstraint::Length(3),
             BasicConstraint::Percentage(Percentage(33)),
             BasicConstraint::Percentage(Percentage(33)),
@@ -139,19 +310,49 @@ impl TableView for Playlist {
         ]
     }
     fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
-        Box::new(self.list.get_list_iter().enumerate().map(|(i, ls)| {
-            Box::new(iter::once((i + 1).to_string().into()).chain(ls.get_fields_iter()))
-                as Box<dyn Iterator<Item = Cow<str>>>
-        }))
+        // Position numbers are kept from the unfiltered list.
+        // Collected so that the filtered iterator is ExactSize.
+        let items: Vec<_> = self
+            .list
+            .get_list_iter()
+            .enumerate()
+            .filter(|(_, ls)| self.filter.matches(ls))
+            .map(|(i, ls)| {
+                Box::new(
+                    iter::once((i + 1).to_string().into())
+                        .chain(iter::once(ls.like_icon().to_string().into()))
+                        .chain(ls.get_fields_iter()),
+                ) as Box<dyn Iterator<Item = Cow<str>>>
+            })
+            .collect();
+        Box::new(items.into_iter())
     }
     fn get_headings(&self) -> Box<(dyn Iterator<Item = &'static str> + 'static)> {
         Box::new(
             [
-                "p#", "", "t#", "Artist", "Album", "Song", "Duration", "Year",
+                "p#", "", "", "t#", "Artist", "Album", "Song", "Duration", "Year",
             ]
             .into_iter(),
         )
     }
+    fn get_footer(&self) -> Option<Cow<str>> {
+        let tracks = self.list.get_list_iter().len();
+        if tracks == 0 {
+            return None;
+        }
+        let (total, remaining) = self.get_queue_durations();
+        Some(
+            tr_format(
+                "{} tracks - {} total - {} remaining",
+                &[
+                    &tracks,
+                    &secs_to_time_string(total),
+                    &secs_to_time_string(remaining),
+                ],
+            )
+            .into(),
+        )
+    }
 }
 
 impl ActionHandler<PlaylistAction> for Playlist {
@@ -160,11 +361,37 @@ impl ActionHandler<PlaylistAction> for Playlist {
             PlaylistAction::ViewBrowser => self.view_browser().await,
             PlaylistAction::Down => self.increment_list(1),
             PlaylistAction::Up => self.increment_list(-1),
-            PlaylistAction::PageDown => self.increment_list(10),
-            PlaylistAction::PageUp => self.increment_list(-10),
+            PlaylistAction::PageDown => self.increment_list(self.page_lines),
+            PlaylistAction::PageUp => self.increment_list(-self.page_lines),
+            PlaylistAction::Home => self.increment_list(isize::MIN),
+            PlaylistAction::End => self.increment_list(isize::MAX),
             PlaylistAction::PlaySelected => self.play_selected().await,
             PlaylistAction::DeleteSelected => self.delete_selected().await,
-            PlaylistAction::DeleteAll => self.delete_all().await,
+            PlaylistAction::DeleteAll => self.confirm_delete_all().await,
+            PlaylistAction::AddSelectedToLibraryPlaylist => {
+                self.add_selected_to_library_playlist().await
+            }
+            PlaylistAction::LikeSelected => {
+                if let Some(id) = self.get_selected_id() {
+                    self.rate_song(id, LikeStatus::Liked).await
+                }
+            }
+            PlaylistAction::DislikeSelected => {
+                if let Some(id) = self.get_selected_id() {
+                    self.rate_song(id, LikeStatus::Disliked).await
+                }
+            }
+            PlaylistAction::ToggleFilter => self.toggle_filter(),
+            PlaylistAction::ApplyFilter => self.filter.shown = false,
+            PlaylistAction::ClearFilter => self.clear_filter(),
+            PlaylistAction::CopySelectedUrl => self.copy_selected_url().await,
+            PlaylistAction::OpenSelectedInBrowser => self.open_selected_in_browser().await,
+            PlaylistAction::GoToSelectedAlbum => self.go_to_selected_album().await,
+            PlaylistAction::GoToSelectedArtist => self.go_to_selected_artist().await,
+            PlaylistAction::ShowSelectedDetails => self.show_selected_details().await,
+            PlaylistAction::StarSelected => self.star_selected().await,
+            PlaylistAction::RetrySelectedDownload => self.retry_selected_download().await,
+            PlaylistAction::ExportSelected => self.export_selected().await,
         }
     }
 }
@@ -176,7 +403,7 @@ impl SongListComponent for Playlist {
 }
 
 impl Playlist {
-    pub fn new(ui_tx: mpsc::Sender<AppCallback>) -> Self {
+    pub fn new(ui_tx: mpsc::Sender<AppCallback>, events: EventBus) -> Self {
         // This could fail, made to try send to avoid needing to change function signature to asynchronous. Should change.
         ui_tx
             .try_send(AppCallback::GetVolume)
@@ -190,14 +417,54 @@ impl Playlist {
             cur_played_secs: None,
             keybinds: playlist_keybinds(),
             cur_selected: 0,
+            filter: Default::default(),
+            page_lines: PAGE_KEY_LINES,
+            history: Default::default(),
+            events,
+            cast_device: None,
         }
     }
+    /// Total duration of the queue, and duration remaining from the current position in the
+    /// current song, in seconds. If nothing is playing the whole queue remains.
+    pub fn get_queue_durations(&self) -> (usize, usize) {
+        let durations = self.list.get_list_iter().map(|song| {
+            song.get_duration()
+                .as_deref()
+                .map(parse_simple_time_to_secs)
+                .unwrap_or(0)
+        });
+        let total = durations.clone().sum();
+        let remaining = match self.get_cur_playing_index() {
+            Some(idx) => {
+                let elapsed = self.cur_played_secs.unwrap_or(0.0) as usize;
+                durations.skip(idx).sum::<usize>().saturating_sub(elapsed)
+            }
+            None => total,
+        };
+        (total, remaining)
+    }
+    /// Number of songs currently downloading, and number queued for download.
+    pub fn get_download_counts(&self) -> (usize, usize) {
+        self.list
+            .get_list_iter()
+            .fold((0, 0), |(downloading, queued), song| {
+                match song.download_status {
+                    DownloadStatus::Downloading(_) => (downloading + 1, queued),
+                    DownloadStatus::Queued => (downloading, queued + 1),
+                    _ => (downloading, queued),
+                }
+            })
+    }
     pub async fn handle_tick(&mut self) {
         self.check_song_progress().await;
         // XXX: Consider downloading upcoming songs here.
         // self.download_upcoming_songs().await;
     }
     pub async fn check_song_progress(&mut self) {
+        // Progress is reported by the cast device, when casting.
+        if self.cast_device.is_some() {
+            return;
+        }
         // Ask player for a progress update.
         if let PlayState::Playing(id) = self.play_status {
             info!("Tick received - requesting song progress update");
@@ -227,11 +494,13 @@ impl Playlist {
                     song.download_status = DownloadStatus::Queued;
                 }
             }
-            DownloadProgressUpdateType::Completed(song_buf) => {
+            DownloadProgressUpdateType::Completed(song_buf, bitrate) => {
+                self.emit_song_event(id, PlayerEvent::DownloadCompleted);
                 let fut = self
                     .get_mut_song_from_id(id)
                     .map(|s| {
                         s.download_status = DownloadStatus::Downloaded(Arc::new(song_buf));
+                        s.audio_bitrate = bitrate;
                         s.id
                     })
                     .map(|id| async move { self.play_if_was_buffering(id).await });
@@ -239,7 +508,7 @@ impl Playlist {
                     f.await
                 }
             }
-            DownloadProgressUpdateType::Error => {
+            DownloadProgressUpdateType::Error(_) => {
                 if let Some(song) = self.list.get_list_iter_mut().find(|x| x.id == id) {
                     song.download_status = DownloadStatus::Failed;
                 }
@@ -249,6 +518,12 @@ impl Playlist {
                     song.download_status = DownloadStatus::Downloading(p);
                 }
             }
+            DownloadProgressUpdateType::Resuming(p) => {
+                tracing::info!("Resuming song download from {}%", p.0);
+                if let Some(song) = self.list.get_list_iter_mut().find(|x| x.id == id) {
+                    song.download_status = DownloadStatus::Downloading(p);
+                }
+            }
         }
     }
     pub fn handle_set_volume(&mut self, p: Percentage) {
@@ -269,6 +544,7 @@ impl Playlist {
         }
     }
     pub async fn handle_done_playing(&mut self, id: ListSongID) {
+        self.emit_song_event(id, PlayerEvent::TrackFinished);
         self.play_next_or_finish(id).await;
     }
     pub fn handle_set_to_playing(&mut self, id: ListSongID) {
@@ -286,13 +562,18 @@ impl Playlist {
         }
     }
     pub async fn play_selected(&mut self) {
-        let Some(id) = self.get_id_from_index(self.cur_selected) else {
+        let Some(id) = self.get_selected_id() else {
             return;
         };
         self.play_song_id(id).await;
     }
     pub async fn delete_selected(&mut self) {
-        let cur_selected_idx = self.cur_selected;
+        let Some(selected_id) = self.get_selected_id() else {
+            return;
+        };
+        let Some(cur_selected_idx) = self.get_index_from_id(selected_id) else {
+            return;
+        };
         // If current song is playing, stop it.
         if let Some(cur_playing_id) = self.get_cur_playing_id() {
             if Some(cur_selected_idx) == self.get_cur_playing_index() {
@@ -300,17 +581,168 @@ impl Playlist {
                 send_or_error(&self.ui_tx, AppCallback::Stop(cur_playing_id)).await;
             }
         }
+        self.record_history();
         self.list.remove_song_index(cur_selected_idx);
-        // If we are removing a song at a position less than current index, decrement current index.
-        // NOTE: Ok to simply take, if list only had one element.
-        if self.cur_selected >= cur_selected_idx && cur_selected_idx != 0 {
-            // Safe, as checked above that cur_idx >= 0
-            self.cur_selected -= 1;
+        // Move selection up to the previous row, unless already at the top.
+        self.cur_selected = self.cur_selected.saturating_sub(1);
+        self.emit_queue_changed();
+    }
+    /// Songs that match the current filter, in playlist order.
+    pub fn get_filtered_list_iter(&self) -> impl Iterator<Item = &ListSong> + '_ {
+        self.list
+            .get_list_iter()
+            .filter(|ls| self.filter.matches(ls))
+    }
+    pub fn get_selected_song(&self) -> Option<&ListSong> {
+        self.get_filtered_list_iter().nth(self.cur_selected)
+    }
+    pub fn get_selected_id(&self) -> Option<ListSongID> {
+        self.get_selected_song().map(|s| s.id)
+    }
+    /// Keep the selected row within the filtered view.
+    fn clamp_selected(&mut self) {
+        self.increment_list(0);
+    }
+    pub fn toggle_filter(&mut self) {
+        if !self.filter.shown {
+            // Start from the current filter, so that it can be edited.
+            self.filter.cur = self.filter.text.len();
         }
+        self.filter.shown = !self.filter.shown;
+    }
+    pub fn clear_filter(&mut self) {
+        // Keep the selected song selected once the filter is removed.
+        let selected_id = self.get_selected_id();
+        self.filter.shown = false;
+        self.filter.text.clear();
+        self.filter.cur = 0;
+        self.cur_selected = selected_id
+            .and_then(|id| self.get_index_from_id(id))
+            .unwrap_or(self.cur_selected);
     }
     pub async fn delete_all(&mut self) {
         self.reset().await;
     }
+    /// Ask the user to confirm before clearing the playlist.
+    async fn confirm_delete_all(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::Confirm("Clear the playlist?".into(), ConfirmAction::ClearPlaylist),
+        )
+        .await;
+    }
+    pub async fn add_selected_to_library_playlist(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ShowAddToLibraryPlaylist(
+                song.raw.get_video_id().clone(),
+                song.get_title().clone(),
+            ),
+        )
+        .await;
+    }
+    pub async fn copy_selected_url(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::CopyToClipboard(song.raw.get_video_id().get_url()),
+        )
+        .await;
+    }
+    pub async fn open_selected_in_browser(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::OpenInBrowser(song.raw.get_video_id().get_url()),
+        )
+        .await;
+    }
+    /// Show the songs from the selected song's album in the browser.
+    pub async fn go_to_selected_album(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        let Some(album_id) = song.get_album_id() else {
+            info!("Selected song has no album to go to");
+            return;
+        };
+        send_or_error(&self.ui_tx, AppCallback::ViewAlbum(album_id.clone())).await;
+    }
+    /// Show the songs from the selected song's artist in the browser.
+    pub async fn go_to_selected_artist(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        let Some(artist_id) = song.get_artist_id() else {
+            info!("Selected song has no artist to go to");
+            return;
+        };
+        send_or_error(&self.ui_tx, AppCallback::ViewArtist(artist_id.clone())).await;
+    }
+    pub async fn show_selected_details(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ShowSongDetails(SongDetails::from_song(song)),
+        )
+        .await;
+    }
+    pub async fn star_selected(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ToggleStarSong(Box::new(song.clone())),
+        )
+        .await;
+    }
+    pub async fn export_selected(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        send_or_error(&self.ui_tx, AppCallback::ExportSongs(vec![song.clone()])).await;
+    }
+    /// Download the selected song again, if its download failed.
+    pub async fn retry_selected_download(&mut self) {
+        let Some(song) = self.get_selected_song() else {
+            return;
+        };
+        if let DownloadStatus::Failed = song.download_status {
+            let id = song.id;
+            self.download_song_if_exists(id).await;
+        }
+    }
+    /// Rate a song on YouTube Music. Rating a song that already has that rating removes it.
+    pub async fn rate_song(&mut self, id: ListSongID, rating: LikeStatus) {
+        let Some(song) = self.get_song_from_id(id) else {
+            return;
+        };
+        let rating = if song.like_status == Some(rating) {
+            LikeStatus::Indifferent
+        } else {
+            rating
+        };
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::RateSong(song.raw.get_video_id().clone(), rating, id),
+        )
+        .await;
+    }
+    pub fn handle_song_rated(&mut self, id: ListSongID, rating: LikeStatus) {
+        if let Some(song) = self.get_mut_song_from_id(id) {
+            song.like_status = Some(rating);
+        }
+    }
     pub async fn view_browser(&mut self) {
         send_or_error(
             &self.ui_tx,
@@ -319,6 +751,10 @@ impl Playlist {
         .await;
     }
     pub async fn handle_next(&mut self) {
+        if self.cast_device.is_some() {
+            send_or_error(&self.ui_tx, AppCallback::CastCommand(CastCommand::Next)).await;
+            return;
+        }
         match self.play_status {
             PlayState::Playing(id) => {
                 self.play_next_or_finish(id).await;
@@ -336,7 +772,112 @@ impl Playlist {
     }
     // Returns the ID of the first song added.
     pub fn push_song_list(&mut self, song_list: Vec<ListSong>) -> ListSongID {
-        self.list.push_song_list(song_list)
+        self.record_history();
+        let id = self.list.push_song_list(song_list);
+        self.emit_queue_changed();
+        id
+    }
+    /// Replace the playlist with the songs and play the first one.
+    /// This is recorded as a single edit, so undoing it restores the previous playlist.
+    pub async fn replace_and_play(&mut self, song_list: Vec<ListSong>) {
+        self.record_history();
+        self.stop_and_clear().await;
+        let id = self.list.push_song_list(song_list);
+        self.emit_queue_changed();
+        self.play_song_id(id).await;
+    }
+    /// Save the queue and volume to the data directory, to be restored next time youtui is
+    /// started.
+    pub fn save_session(&self) {
+        let songs = self
+            .list
+            .get_list_iter()
+            .map(SavedSong::from_song)
+            .collect();
+        let session = PlaylistSession {
+            songs,
+            volume: Some(self.volume.0),
+        };
+        if let Err(e) = session.save() {
+            warn!("Error <{e}> saving playlist session");
+        }
+    }
+    /// Queue the songs saved from the previous session, without playing them, and set the
+    /// volume back to what it was.
+    pub fn restore_session(&mut self) {
+        let Some(PlaylistSession { songs, volume }) = PlaylistSession::load() else {
+            return;
+        };
+        if !songs.is_empty() {
+            self.list.push_song_list(saved_songs_to_list(&songs));
+            self.emit_queue_changed();
+        }
+        if let Some(volume) = volume {
+            // The player always starts at full volume.
+            let inc = volume.min(100) as i8 - 100;
+            self.volume = Percentage(volume.min(100));
+            self.ui_tx
+                .try_send(AppCallback::IncreaseVolume(inc))
+                .unwrap_or_else(|e| error!("Error <{e}> received sending Increase Volume message"));
+        }
+    }
+    fn emit_song_event(&self, id: ListSongID, event: fn(SavedSong) -> PlayerEvent) {
+        if let Some(song) = self.get_song_from_id(id) {
+            self.events.emit(event(SavedSong::from_song(song)));
+        }
+    }
+    fn emit_queue_changed(&self) {
+        self.events.emit(PlayerEvent::QueueChanged {
+            len: self.list.get_list_iter().count(),
+        });
+    }
+    fn record_history(&mut self) {
+        self.history
+            .record(self.list.get_list_iter().cloned().collect());
+    }
+    /// Undo the last edit to the playlist. Returns false if there was nothing to undo.
+    pub async fn undo(&mut self) -> bool {
+        let current = self.list.get_list_iter().cloned().collect();
+        let Some(prev) = self.history.undo(current) else {
+            return false;
+        };
+        self.restore_songs(prev).await;
+        true
+    }
+    /// Redo the last undone edit to the playlist. Returns false if there was nothing to redo.
+    pub async fn redo(&mut self) -> bool {
+        let current = self.list.get_list_iter().cloned().collect();
+        let Some(next) = self.history.redo(current) else {
+            return false;
+        };
+        self.restore_songs(next).await;
+        true
+    }
+    async fn restore_songs(&mut self, mut songs: Vec<ListSong>) {
+        for song in songs.iter_mut() {
+            // Download and rating status may have changed since the songs were recorded.
+            if let Some(cur) = self.get_song_from_id(song.id) {
+                song.download_status = cur.download_status.clone();
+                song.like_status = cur.like_status;
+            } else if matches!(
+                song.download_status,
+                DownloadStatus::Queued | DownloadStatus::Downloading(_)
+            ) {
+                // Updates for removed songs are ignored, so the download needs to be restarted.
+                song.download_status = DownloadStatus::None;
+            }
+        }
+        // If the current song is no longer in the playlist, stop it.
+        if let Some(cur_playing_id) = self.get_cur_playing_id() {
+            if !songs.iter().any(|s| s.id == cur_playing_id) {
+                send_or_error(&self.ui_tx, AppCallback::Stop(cur_playing_id)).await;
+                self.cur_played_secs = None;
+                self.play_status = PlayState::NotPlaying;
+            }
+        }
+        self.list.replace_songs(songs);
+        self.clamp_selected();
+        self.emit_queue_changed();
     }
     pub async fn play_if_was_buffering(&mut self, id: ListSongID) {
         if let PlayState::Buffering(target_id) = self.play_status {
@@ -347,6 +888,11 @@ impl Playlist {
         }
     }
     pub async fn reset(&mut self) {
+        self.record_history();
+        self.stop_and_clear().await;
+        self.emit_queue_changed();
+    }
+    async fn stop_and_clear(&mut self) {
         // Stop playback, if playing.
         if let Some(cur_id) = self.get_cur_playing_id() {
             send_or_error(&self.ui_tx, AppCallback::Stop(cur_id)).await;
@@ -361,6 +907,10 @@ impl Playlist {
         self.list.clear();
     }
     pub async fn play_song_id(&mut self, id: ListSongID) {
+        if let Some(device) = self.cast_device.clone() {
+            self.cast_from_id(device, id).await;
+            return;
+        }
         if let Some(cur_id) = self.get_cur_playing_id() {
             send_or_error(&self.ui_tx, AppCallback::Stop(cur_id)).await;
         }
@@ -376,6 +926,7 @@ impl Playlist {
             {
                 send_or_error(&self.ui_tx, AppCallback::PlaySong(pointer.clone(), id)).await;
                 self.play_status = PlayState::Playing(id);
+                self.emit_song_event(id, PlayerEvent::TrackStarted);
             } else {
                 self.play_status = PlayState::Buffering(id);
             }
@@ -468,6 +1019,10 @@ impl Playlist {
         }
     }
     pub async fn play_prev(&mut self) {
+        if self.cast_device.is_some() {
+            send_or_error(&self.ui_tx, AppCallback::CastCommand(CastCommand::Previous)).await;
+            return;
+        }
         let cur = &self.play_status;
         match cur {
             PlayState::NotPlaying | PlayState::Stopped => {
@@ -504,8 +1059,99 @@ impl Playlist {
             }
             _ => return,
         };
+        if self.cast_device.is_some() {
+            let command = match self.play_status {
+                PlayState::Paused(_) => CastCommand::Pause,
+                _ => CastCommand::Play,
+            };
+            send_or_error(&self.ui_tx, AppCallback::CastCommand(command)).await;
+            return;
+        }
         send_or_error(&self.ui_tx, AppCallback::PausePlay(id)).await;
     }
+    /// Stop playing locally, and play the queue on the device from the current song.
+    pub async fn cast_to(&mut self, device: CastDevice) {
+        let id = self
+            .get_cur_playing_id()
+            .or_else(|| self.get_id_from_index(0));
+        let Some(id) = id else {
+            return;
+        };
+        if let Some(cur_id) = self.get_cur_playing_id() {
+            if self.cast_device.is_none() {
+                send_or_error(&self.ui_tx, AppCallback::Stop(cur_id)).await;
+            }
+        }
+        self.cast_from_id(device, id).await;
+    }
+    async fn cast_from_id(&mut self, device: CastDevice, id: ListSongID) {
+        let Some(index) = self.get_index_from_id(id) else {
+            return;
+        };
+        let video_ids = self
+            .list
+            .get_list_iter()
+            .map(|song| song.raw.get_video_id().clone())
+            .collect();
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::CastQueue(device.clone(), video_ids, index),
+        )
+        .await;
+        self.cast_device = Some(device);
+        self.cur_played_secs = None;
+        self.play_status = PlayState::Buffering(id);
+    }
+    /// Stop controlling the device. The device keeps playing, but the queue isn't played
+    /// locally until a song is played again.
+    pub async fn stop_casting(&mut self) {
+        if self.cast_device.take().is_none() {
+            return;
+        }
+        send_or_error(&self.ui_tx, AppCallback::StopCasting).await;
+        self.play_status = PlayState::Stopped;
+    }
+    /// Reflect the device's play state in the queue.
+    pub fn handle_cast_state(&mut self, state: CastState) {
+        if self.cast_device.is_none() {
+            return;
+        }
+        let id = match &state.video_id {
+            Some(video_id) => {
+                // The same song may be queued more than once, so prefer the next occurrence.
+                let cur_index = self.get_cur_playing_index().unwrap_or(0);
+                let matches = |song: &&ListSong| song.raw.get_video_id().get_raw() == video_id;
+                self.list
+                    .get_list_iter()
+                    .skip(cur_index)
+                    .find(matches)
+                    .or_else(|| self.list.get_list_iter().find(matches))
+                    .map(|song| song.id)
+            }
+            None => self.get_cur_playing_id(),
+        };
+        let Some(id) = id else {
+            warn!("Cast device is playing a song that isn't in the queue");
+            return;
+        };
+        if !self.check_id_is_cur(id) {
+            self.emit_song_event(id, PlayerEvent::TrackStarted);
+        }
+        self.play_status = match state.status {
+            CastPlayStatus::Playing => PlayState::Playing(id),
+            CastPlayStatus::Paused => PlayState::Paused(id),
+            CastPlayStatus::Buffering => PlayState::Buffering(id),
+            CastPlayStatus::Stopped => PlayState::Stopped,
+        };
+        if state.elapsed_secs.is_some() {
+            self.cur_played_secs = state.elapsed_secs;
+        }
+    }
+    pub fn handle_cast_disconnected(&mut self) {
+        if self.cast_device.take().is_some() {
+            self.play_status = PlayState::Stopped;
+        }
+    }
     pub fn get_cur_playing_id(&self) -> Option<ListSongID> {
         match self.play_status {
             PlayState::Playing(id) | PlayState::Paused(id) | PlayState::Buffering(id) => Some(id),
@@ -540,14 +1186,38 @@ fn playlist_keybinds() -> Vec<KeyCommand<PlaylistAction>> {
         KeyCommand::new_hidden_from_code(KeyCode::Up, PlaylistAction::Up),
         KeyCommand::new_from_code(KeyCode::PageDown, PlaylistAction::PageDown),
         KeyCommand::new_from_code(KeyCode::PageUp, PlaylistAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::Home, PlaylistAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, PlaylistAction::End),
+        KeyCommand::new_from_code(
+            KeyCode::Char('s'),
+            PlaylistAction::AddSelectedToLibraryPlaylist,
+        ),
+        KeyCommand::new_from_code(KeyCode::Char('/'), PlaylistAction::ToggleFilter),
         KeyCommand::new_action_only_mode(
             vec![
                 (KeyCode::Enter, PlaylistAction::PlaySelected),
                 (KeyCode::Char('d'), PlaylistAction::DeleteSelected),
                 (KeyCode::Char('D'), PlaylistAction::DeleteAll),
+                (KeyCode::Char('l'), PlaylistAction::LikeSelected),
+                (KeyCode::Char('x'), PlaylistAction::DislikeSelected),
+                (KeyCode::Char('y'), PlaylistAction::CopySelectedUrl),
+                (KeyCode::Char('o'), PlaylistAction::OpenSelectedInBrowser),
+                (KeyCode::Char('a'), PlaylistAction::GoToSelectedAlbum),
+                (KeyCode::Char('r'), PlaylistAction::GoToSelectedArtist),
+                (KeyCode::Char('i'), PlaylistAction::ShowSelectedDetails),
+                (KeyCode::Char('f'), PlaylistAction::StarSelected),
+                (KeyCode::Char('R'), PlaylistAction::RetrySelectedDownload),
+                (KeyCode::Char('e'), PlaylistAction::ExportSelected),
             ],
             KeyCode::Enter,
             "Playlist Action",
         ),
     ]
 }
+
+fn filter_keybinds() -> Vec<KeyCommand<PlaylistAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::Enter, PlaylistAction::ApplyFilter),
+        KeyCommand::new_global_from_code(KeyCode::Esc, PlaylistAction::ClearFilter),
+    ]
+}
@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+
+const MAX_QUEUE_HISTORY_LEN: usize = 50;
+
+/// Bounded undo and redo stacks of previous states of the queue.
+/// A snapshot is recorded before each edit, and the oldest snapshots are dropped once full.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueueHistory<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for QueueHistory<T> {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T> QueueHistory<T> {
+    /// Record the state before an edit. A new edit can't be redone past, so the redo stack is
+    /// cleared.
+    pub fn record(&mut self, state: T) {
+        if self.undo.len() == MAX_QUEUE_HISTORY_LEN {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(state);
+        self.redo.clear();
+    }
+    /// Swap the current state for the state before the last edit, if there is one.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let prev = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(prev)
+    }
+    /// Swap the current state for the state before the last undo, if there is one.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueueHistory, MAX_QUEUE_HISTORY_LEN};
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = QueueHistory::default();
+        history.record(vec![1]);
+        history.record(vec![1, 2]);
+        let cur = history.undo(vec![1, 2, 3]).unwrap();
+        assert_eq!(cur, vec![1, 2]);
+        let cur = history.undo(cur).unwrap();
+        assert_eq!(cur, vec![1]);
+        assert_eq!(history.undo(cur.clone()), None);
+        let cur = history.redo(cur).unwrap();
+        assert_eq!(cur, vec![1, 2]);
+        // A new edit discards the redo history.
+        history.record(cur);
+        assert_eq!(history.redo(vec![2]), None);
+    }
+    #[test]
+    fn test_history_bounded() {
+        let mut history = QueueHistory::default();
+        for i in 0..MAX_QUEUE_HISTORY_LEN + 10 {
+            history.record(i);
+        }
+        let mut cur = 0;
+        let mut undos = 0;
+        while let Some(prev) = history.undo(cur) {
+            cur = prev;
+            undos += 1;
+        }
+        assert_eq!(undos, MAX_QUEUE_HISTORY_LEN);
+        assert_eq!(cur, 10);
+    }
+}
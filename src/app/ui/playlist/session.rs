@@ -0,0 +1,51 @@
+use crate::app::datafile::{load_data_file, save_data_file};
+use crate::app::structures::SavedSong;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+const PLAYLIST_SESSION_FILE_NAME: &str = "playlist_session.json";
+
+/// The queue and volume when youtui was last closed, stored in the data directory so that they
+/// can be restored on startup.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaylistSession {
+    pub songs: Vec<SavedSong>,
+    /// Volume as a percentage, if it was known.
+    pub volume: Option<u8>,
+}
+
+impl PlaylistSession {
+    /// Load the session from the data directory, or None if it doesn't exist or can't be read.
+    pub fn load() -> Option<Self> {
+        load_data_file(PLAYLIST_SESSION_FILE_NAME)
+    }
+    pub fn save(&self) -> Result<()> {
+        save_data_file(PLAYLIST_SESSION_FILE_NAME, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlaylistSession;
+    use crate::app::structures::test_saved_song;
+
+    #[test]
+    fn test_session_round_trip() {
+        let session = PlaylistSession {
+            songs: vec![test_saved_song("abc")],
+            volume: Some(40),
+        };
+        let serialized = serde_json::to_string(&session).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PlaylistSession>(&serialized).unwrap(),
+            session
+        );
+    }
+    #[test]
+    fn test_session_missing_fields_default() {
+        let session: PlaylistSession = serde_json::from_str("{\"volume\": 40}").unwrap();
+        assert!(session.songs.is_empty());
+        assert_eq!(session.volume, Some(40));
+    }
+}
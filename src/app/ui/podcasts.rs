@@ -0,0 +1,482 @@
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{AlbumSongsList, ListSong, ListStatus, Percentage},
+    ui::{AppCallback, WindowContext},
+    view::{
+        get_table_column_texts, BasicConstraint, DrawableMut, Loadable, Scrollable, TableItem,
+        TableView, TypeAheadJump,
+    },
+    YoutuiMutableState,
+};
+use crate::core::send_or_error;
+use crate::i18n::{tr, tr_format};
+use crossterm::event::KeyCode;
+use draw::draw_podcasts;
+use ratatui::{prelude::Rect, Frame};
+use std::borrow::Cow;
+use tokio::sync::mpsc::Sender;
+use tracing::warn;
+use ytmapi_rs::common::{library::LibraryPodcast, PodcastID};
+use ytmapi_rs::parse::{Episode, Podcast, SongResult};
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PodcastsAction {
+    ViewBrowser,
+    Refresh,
+    Open,
+    AddToPlaylist,
+    AddAllToPlaylist,
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum PodcastsInputRouting {
+    #[default]
+    Podcasts,
+    Episodes,
+}
+
+/// The user's saved podcasts, and the episodes of the selected podcast.
+/// NOTE: Requires an authenticated API to list saved podcasts.
+pub struct Podcasts {
+    ui_tx: Sender<AppCallback>,
+    pub route: PodcastsInputRouting,
+    pub podcasts: PodcastsPanel,
+    pub episodes: EpisodesPanel,
+    keybinds: Vec<KeyCommand<PodcastsAction>>,
+    pub page_lines: isize,
+}
+
+pub struct PodcastsPanel {
+    pub list: Vec<LibraryPodcast>,
+    pub state: ListStatus,
+    cur_selected: usize,
+}
+
+pub struct EpisodesPanel {
+    pub podcast: Option<Podcast>,
+    pub state: ListStatus,
+    cur_selected: usize,
+}
+
+impl Action for PodcastsAction {
+    fn context(&self) -> Cow<str> {
+        tr("Podcasts").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            PodcastsAction::ViewBrowser => "View Browser",
+            PodcastsAction::Refresh => "Refresh",
+            PodcastsAction::Open => "Open",
+            PodcastsAction::AddToPlaylist => "Add episode to playlist",
+            PodcastsAction::AddAllToPlaylist => "Add all episodes to playlist",
+            PodcastsAction::Left => "Left",
+            PodcastsAction::Right => "Right",
+            PodcastsAction::Up => "Up",
+            PodcastsAction::Down => "Down",
+            PodcastsAction::PageUp => "Page Up",
+            PodcastsAction::PageDown => "Page Down",
+            PodcastsAction::Home => "Top",
+            PodcastsAction::End => "Bottom",
+        })
+        .into()
+    }
+}
+
+impl DrawableMut for Podcasts {
+    fn draw_mut_chunk(
+        &self,
+        f: &mut Frame,
+        chunk: Rect,
+        mutable_state: &mut YoutuiMutableState,
+        selected: bool,
+    ) {
+        draw_podcasts(
+            f,
+            self,
+            chunk,
+            &mut mutable_state.podcasts_list_state,
+            &mut mutable_state.podcast_episodes_state,
+            selected,
+        )
+    }
+}
+
+impl KeyRouter<PodcastsAction> for Podcasts {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<PodcastsAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<PodcastsAction>> + 'a> {
+        self.get_routed_keybinds()
+    }
+}
+
+impl TextHandler for Podcasts {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<PodcastsAction> for Podcasts {
+    async fn handle_action(&mut self, action: &PodcastsAction) {
+        match action {
+            PodcastsAction::ViewBrowser => self.handle_view_browser().await,
+            PodcastsAction::Refresh => self.refresh().await,
+            PodcastsAction::Open => self.open().await,
+            PodcastsAction::AddToPlaylist => self.add_to_playlist().await,
+            PodcastsAction::AddAllToPlaylist => self.add_all_to_playlist().await,
+            PodcastsAction::Left => self.route = PodcastsInputRouting::Podcasts,
+            PodcastsAction::Right => self.route = PodcastsInputRouting::Episodes,
+            PodcastsAction::Up => self.increment_cur_list(-1),
+            PodcastsAction::Down => self.increment_cur_list(1),
+            PodcastsAction::PageUp => self.increment_cur_list(-self.page_lines),
+            PodcastsAction::PageDown => self.increment_cur_list(self.page_lines),
+            PodcastsAction::Home => self.increment_cur_list(isize::MIN),
+            PodcastsAction::End => self.increment_cur_list(isize::MAX),
+        }
+    }
+}
+
+impl Podcasts {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            route: Default::default(),
+            podcasts: PodcastsPanel {
+                list: Vec::new(),
+                state: ListStatus::New,
+                cur_selected: 0,
+            },
+            episodes: EpisodesPanel {
+                podcast: None,
+                state: ListStatus::New,
+                cur_selected: 0,
+            },
+            keybinds: podcasts_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Returns true if the saved podcasts have not yet been requested.
+    pub fn is_new(&self) -> bool {
+        matches!(self.podcasts.state, ListStatus::New)
+    }
+    /// Jump the focused pane to the next row starting with the character.
+    pub fn jump_to_char(&mut self, c: char) -> bool {
+        match self.route {
+            PodcastsInputRouting::Podcasts => self.podcasts.jump_to_char(c),
+            PodcastsInputRouting::Episodes => self.episodes.jump_to_char(c),
+        }
+    }
+    pub async fn refresh(&mut self) {
+        self.podcasts.state = ListStatus::Loading;
+        send_or_error(&self.ui_tx, AppCallback::GetLibraryPodcasts).await;
+    }
+    /// Clear the episodes list and request episodes for the podcast.
+    pub async fn show_podcast_episodes(&mut self, podcast_id: PodcastID<'static>) {
+        self.route = PodcastsInputRouting::Episodes;
+        self.episodes.podcast = None;
+        self.episodes.state = ListStatus::Loading;
+        self.episodes.cur_selected = 0;
+        send_or_error(&self.ui_tx, AppCallback::GetPodcastEpisodes(podcast_id)).await;
+    }
+    pub fn handle_replace_library_podcasts(&mut self, podcasts: Vec<LibraryPodcast>) {
+        self.podcasts.list = podcasts;
+        self.podcasts.state = ListStatus::Loaded;
+        self.podcasts.increment_list(0);
+    }
+    pub fn handle_library_podcasts_error(&mut self) {
+        self.podcasts.state = ListStatus::Error;
+    }
+    pub fn handle_library_podcasts_retrying(&mut self) {
+        self.podcasts.state.set_retrying();
+    }
+    pub fn handle_replace_podcast_episodes(&mut self, podcast: Podcast) {
+        self.episodes.podcast = Some(podcast);
+        self.episodes.state = ListStatus::Loaded;
+        self.episodes.increment_list(0);
+    }
+    pub fn handle_podcast_episodes_error(&mut self) {
+        self.episodes.state = ListStatus::Error;
+    }
+    pub fn handle_podcast_episodes_retrying(&mut self) {
+        self.episodes.state.set_retrying();
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    fn increment_cur_list(&mut self, amount: isize) {
+        match self.route {
+            PodcastsInputRouting::Podcasts => self.podcasts.increment_list(amount),
+            PodcastsInputRouting::Episodes => self.episodes.increment_list(amount),
+        }
+    }
+    async fn open(&mut self) {
+        match self.route {
+            PodcastsInputRouting::Podcasts => {
+                let Some(podcast) = self.podcasts.list.get(self.podcasts.cur_selected) else {
+                    return;
+                };
+                let podcast_id = podcast.podcast_id.clone();
+                self.show_podcast_episodes(podcast_id).await;
+            }
+            PodcastsInputRouting::Episodes => {
+                let Some(song_list) = self.episodes.selected_as_song_list() else {
+                    return;
+                };
+                send_or_error(
+                    &self.ui_tx,
+                    AppCallback::AddSongsToPlaylistAndPlay(song_list),
+                )
+                .await;
+            }
+        }
+    }
+    async fn add_to_playlist(&mut self) {
+        match self.route {
+            PodcastsInputRouting::Podcasts => {
+                warn!("Open the podcast to queue its episodes")
+            }
+            PodcastsInputRouting::Episodes => {
+                let Some(song_list) = self.episodes.selected_as_song_list() else {
+                    return;
+                };
+                send_or_error(&self.ui_tx, AppCallback::AddSongsToPlaylist(song_list)).await;
+            }
+        }
+    }
+    async fn add_all_to_playlist(&mut self) {
+        let Some(podcast) = &self.episodes.podcast else {
+            warn!("Open a podcast to queue its episodes");
+            return;
+        };
+        // Playlist requires at least one song to be pushed.
+        if podcast.episodes.is_empty() {
+            return;
+        }
+        let song_list = episodes_to_song_list(podcast, &podcast.episodes);
+        send_or_error(&self.ui_tx, AppCallback::AddSongsToPlaylist(song_list)).await;
+    }
+}
+
+impl EpisodesPanel {
+    fn selected_as_song_list(&self) -> Option<Vec<ListSong>> {
+        let podcast = self.podcast.as_ref()?;
+        let episode = podcast.episodes.get(self.cur_selected)?;
+        Some(episodes_to_song_list(
+            podcast,
+            std::slice::from_ref(episode),
+        ))
+    }
+}
+
+/// Episodes are played like songs, with the podcast in place of the album.
+pub fn episodes_to_song_list(podcast: &Podcast, episodes: &[Episode]) -> Vec<ListSong> {
+    let mut list = AlbumSongsList::default();
+    list.append_raw_songs(
+        episodes.iter().cloned().map(SongResult::from).collect(),
+        podcast.title.clone(),
+        String::new(),
+        podcast.author.clone().unwrap_or_default(),
+    );
+    list.get_list_iter().cloned().collect()
+}
+
+impl Loadable for PodcastsPanel {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for PodcastsPanel {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.list.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for PodcastsPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for PodcastsPanel {
+    fn get_title(&self) -> Cow<str> {
+        match self.state {
+            ListStatus::Loaded => tr_format("Podcasts - {} results", &[&self.list.len()]).into(),
+            ListStatus::Error => tr("Podcasts - Error receieved").into(),
+            _ => tr("Podcasts").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(60)),
+            BasicConstraint::Percentage(Percentage(40)),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        Box::new(self.list.iter().map(|p| {
+            Box::new(
+                [
+                    Cow::from(p.title.as_str()),
+                    Cow::from(p.channel.as_deref().unwrap_or_default()),
+                ]
+                .into_iter(),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Podcast", "Channel"].into_iter())
+    }
+}
+
+impl Loadable for EpisodesPanel {
+    fn is_loading(&self) -> bool {
+        matches!(self.state, ListStatus::Loading | ListStatus::Retrying)
+    }
+    fn is_retrying(&self) -> bool {
+        matches!(self.state, ListStatus::Retrying)
+    }
+}
+impl Scrollable for EpisodesPanel {
+    fn increment_list(&mut self, amount: isize) {
+        let len = self.podcast.as_ref().map(|p| p.episodes.len()).unwrap_or(0);
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(len.saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for EpisodesPanel {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for EpisodesPanel {
+    fn get_title(&self) -> Cow<str> {
+        match (&self.state, &self.podcast) {
+            (ListStatus::Loaded, Some(p)) => {
+                tr_format("{} - {} episodes", &[&p.title, &p.episodes.len()]).into()
+            }
+            (ListStatus::Error, _) => tr("Episodes - Error receieved").into(),
+            _ => tr("Episodes").into(),
+        }
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(70)),
+            BasicConstraint::Length(12),
+            BasicConstraint::Length(9),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        let episodes = self
+            .podcast
+            .as_ref()
+            .map(|p| p.episodes.as_slice())
+            .unwrap_or_default();
+        Box::new(episodes.iter().map(|e| {
+            Box::new(
+                [
+                    e.title.as_str(),
+                    e.date.as_deref().unwrap_or_default(),
+                    e.duration.as_deref().unwrap_or_default(),
+                ]
+                .into_iter()
+                .map(Cow::from),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Episode", "Date", "Duration"].into_iter())
+    }
+}
+
+fn podcasts_keybinds() -> Vec<KeyCommand<PodcastsAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), PodcastsAction::ViewBrowser),
+        KeyCommand::new_from_code(KeyCode::Char('r'), PodcastsAction::Refresh),
+        KeyCommand::new_from_code(KeyCode::Enter, PodcastsAction::Open),
+        KeyCommand::new_from_code(KeyCode::Char('a'), PodcastsAction::AddToPlaylist),
+        KeyCommand::new_from_code(KeyCode::Char('A'), PodcastsAction::AddAllToPlaylist),
+        KeyCommand::new_from_code(KeyCode::Left, PodcastsAction::Left),
+        KeyCommand::new_from_code(KeyCode::Right, PodcastsAction::Right),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, PodcastsAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, PodcastsAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, PodcastsAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, PodcastsAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, PodcastsAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, PodcastsAction::End),
+    ]
+}
+
+pub mod draw {
+    use super::{Podcasts, PodcastsInputRouting};
+    use crate::app::view::draw::draw_table;
+    use ratatui::{
+        prelude::{Constraint, Direction, Layout, Rect},
+        widgets::TableState,
+        Frame,
+    };
+
+    pub fn draw_podcasts(
+        f: &mut Frame,
+        p: &Podcasts,
+        chunk: Rect,
+        podcasts_state: &mut TableState,
+        episodes_state: &mut TableState,
+        selected: bool,
+    ) {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Percentage(35), Constraint::Percentage(65)],
+        )
+        .split(chunk);
+        draw_table(
+            f,
+            &p.podcasts,
+            layout[0],
+            podcasts_state,
+            selected && p.route == PodcastsInputRouting::Podcasts,
+        );
+        draw_table(
+            f,
+            &p.episodes,
+            layout[1],
+            episodes_state,
+            selected && p.route == PodcastsInputRouting::Episodes,
+        );
+    }
+}
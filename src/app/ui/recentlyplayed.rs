@@ -0,0 +1,275 @@
+use self::playhistory::{format_played_ago, now_secs, PlayHistory, PlayedSong};
+use crate::app::{
+    component::actionhandler::{Action, ActionHandler, KeyRouter, TextHandler},
+    keycommand::KeyCommand,
+    structures::{saved_songs_to_list, ListSong, Percentage, SavedSong},
+    ui::{AppCallback, WindowContext},
+    view::{
+        draw::draw_table, get_table_column_texts, BasicConstraint, DrawableMut, Loadable,
+        Scrollable, TableItem, TableView, TypeAheadJump,
+    },
+    YoutuiMutableState,
+};
+use crate::core::send_or_error;
+use crate::i18n::{tr, tr_format};
+use crossterm::event::KeyCode;
+use ratatui::{prelude::Rect, Frame};
+use std::borrow::Cow;
+use tokio::sync::mpsc::Sender;
+use tracing::warn;
+
+mod playhistory;
+
+const PAGE_KEY_LINES: isize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecentlyPlayedAction {
+    ViewBrowser,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    PlaySong,
+    AddSongToPlaylist,
+    PlayAll,
+    AddAllToPlaylist,
+    RemoveSelected,
+}
+
+/// Songs played locally, which can be queued again.
+/// Unlike the history on YouTube Music, this doesn't require an authenticated API.
+pub struct RecentlyPlayed {
+    ui_tx: Sender<AppCallback>,
+    pub history: PlayHistory,
+    cur_selected: usize,
+    keybinds: Vec<KeyCommand<RecentlyPlayedAction>>,
+    pub page_lines: isize,
+}
+
+impl Action for RecentlyPlayedAction {
+    fn context(&self) -> Cow<str> {
+        tr("Recently Played").into()
+    }
+    fn describe(&self) -> Cow<str> {
+        tr(match self {
+            Self::ViewBrowser => "View Browser",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::PageUp => "Page Up",
+            Self::PageDown => "Page Down",
+            Self::Home => "Top",
+            Self::End => "Bottom",
+            Self::PlaySong => "Play",
+            Self::AddSongToPlaylist => "Add song to playlist",
+            Self::PlayAll => "Play all",
+            Self::AddAllToPlaylist => "Add all to playlist",
+            Self::RemoveSelected => "Remove from history",
+        })
+        .into()
+    }
+}
+
+impl DrawableMut for RecentlyPlayed {
+    fn draw_mut_chunk(
+        &self,
+        f: &mut Frame,
+        chunk: Rect,
+        mutable_state: &mut YoutuiMutableState,
+        selected: bool,
+    ) {
+        draw_table(
+            f,
+            self,
+            chunk,
+            &mut mutable_state.recently_played_state,
+            selected,
+        )
+    }
+}
+
+impl KeyRouter<RecentlyPlayedAction> for RecentlyPlayed {
+    fn get_routed_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<RecentlyPlayedAction>> + 'a> {
+        Box::new(self.keybinds.iter())
+    }
+    fn get_all_keybinds<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a KeyCommand<RecentlyPlayedAction>> + 'a> {
+        self.get_routed_keybinds()
+    }
+}
+
+impl TextHandler for RecentlyPlayed {
+    fn push_text(&mut self, _c: char) {}
+    fn pop_text(&mut self) {}
+    fn is_text_handling(&self) -> bool {
+        false
+    }
+    fn take_text(&mut self) -> String {
+        Default::default()
+    }
+    fn replace_text(&mut self, _text: String) {}
+}
+
+impl ActionHandler<RecentlyPlayedAction> for RecentlyPlayed {
+    async fn handle_action(&mut self, action: &RecentlyPlayedAction) {
+        match action {
+            RecentlyPlayedAction::ViewBrowser => self.handle_view_browser().await,
+            RecentlyPlayedAction::Up => self.increment_list(-1),
+            RecentlyPlayedAction::Down => self.increment_list(1),
+            RecentlyPlayedAction::PageUp => self.increment_list(-self.page_lines),
+            RecentlyPlayedAction::PageDown => self.increment_list(self.page_lines),
+            RecentlyPlayedAction::Home => self.increment_list(isize::MIN),
+            RecentlyPlayedAction::End => self.increment_list(isize::MAX),
+            RecentlyPlayedAction::PlaySong => self.queue_selected(true).await,
+            RecentlyPlayedAction::AddSongToPlaylist => self.queue_selected(false).await,
+            RecentlyPlayedAction::PlayAll => self.queue_all(true).await,
+            RecentlyPlayedAction::AddAllToPlaylist => self.queue_all(false).await,
+            RecentlyPlayedAction::RemoveSelected => self.remove_selected(),
+        }
+    }
+}
+
+impl RecentlyPlayed {
+    pub fn new(ui_tx: Sender<AppCallback>) -> Self {
+        Self {
+            ui_tx,
+            history: PlayHistory::load(),
+            cur_selected: 0,
+            keybinds: recently_played_keybinds(),
+            page_lines: PAGE_KEY_LINES,
+        }
+    }
+    /// Add a song that has started playing to the history, and save it.
+    pub fn record(&mut self, song: &ListSong) {
+        let had_songs = !self.history.songs.is_empty();
+        self.history.record(PlayedSong {
+            played_at: now_secs(),
+            song: SavedSong::from_song(song),
+        });
+        // Keep the same song selected as the list shifts down.
+        if had_songs {
+            self.increment_list(1);
+        }
+        self.save();
+    }
+    fn save(&self) {
+        if let Err(e) = self.history.save() {
+            warn!("Error <{e}> saving play history");
+        }
+    }
+    async fn handle_view_browser(&mut self) {
+        send_or_error(
+            &self.ui_tx,
+            AppCallback::ChangeContext(WindowContext::Browser),
+        )
+        .await;
+    }
+    async fn queue_selected(&mut self, play: bool) {
+        let Some(song) = self.history.songs.get(self.cur_selected) else {
+            return;
+        };
+        let song_list = saved_songs_to_list([&song.song]);
+        self.queue(song_list, play).await;
+    }
+    async fn queue_all(&mut self, play: bool) {
+        // Playlist requires at least one song to be pushed.
+        if self.history.songs.is_empty() {
+            return;
+        }
+        let song_list = saved_songs_to_list(self.history.songs.iter().map(|s| &s.song));
+        self.queue(song_list, play).await;
+    }
+    async fn queue(&self, song_list: Vec<ListSong>, play: bool) {
+        let callback = if play {
+            AppCallback::AddSongsToPlaylistAndPlay(song_list)
+        } else {
+            AppCallback::AddSongsToPlaylist(song_list)
+        };
+        send_or_error(&self.ui_tx, callback).await;
+    }
+    fn remove_selected(&mut self) {
+        if self.history.remove(self.cur_selected).is_some() {
+            self.increment_list(0);
+            self.save();
+        }
+    }
+}
+
+impl Loadable for RecentlyPlayed {
+    fn is_loading(&self) -> bool {
+        false
+    }
+}
+impl Scrollable for RecentlyPlayed {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur_selected = self
+            .cur_selected
+            .saturating_add_signed(amount)
+            .min(self.history.songs.len().saturating_sub(1))
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur_selected
+    }
+}
+impl TypeAheadJump for RecentlyPlayed {
+    fn get_jump_texts(&self) -> Vec<Cow<str>> {
+        get_table_column_texts(self, 0)
+    }
+}
+impl TableView for RecentlyPlayed {
+    fn get_title(&self) -> Cow<str> {
+        tr_format("Recently Played - {} songs", &[&self.history.songs.len()]).into()
+    }
+    fn get_layout(&self) -> &[BasicConstraint] {
+        &[
+            BasicConstraint::Percentage(Percentage(40)),
+            BasicConstraint::Percentage(Percentage(30)),
+            BasicConstraint::Percentage(Percentage(30)),
+            BasicConstraint::Length(9),
+        ]
+    }
+    fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_> {
+        let now = now_secs();
+        Box::new(self.history.songs.iter().map(move |s| {
+            Box::new(
+                [
+                    Cow::from(s.song.title.as_str()),
+                    Cow::from(s.song.artists.join(", ")),
+                    Cow::from(s.song.album.as_str()),
+                    Cow::from(format_played_ago(s.played_at, now)),
+                ]
+                .into_iter(),
+            ) as TableItem
+        }))
+    }
+    fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>> {
+        Box::new(["Song", "Artists", "Album", "Played"].into_iter())
+    }
+}
+
+fn recently_played_keybinds() -> Vec<KeyCommand<RecentlyPlayedAction>> {
+    vec![
+        KeyCommand::new_global_from_code(KeyCode::F(5), RecentlyPlayedAction::ViewBrowser),
+        KeyCommand::new_hidden_from_code(KeyCode::Up, RecentlyPlayedAction::Up),
+        KeyCommand::new_hidden_from_code(KeyCode::Down, RecentlyPlayedAction::Down),
+        KeyCommand::new_from_code(KeyCode::PageUp, RecentlyPlayedAction::PageUp),
+        KeyCommand::new_from_code(KeyCode::PageDown, RecentlyPlayedAction::PageDown),
+        KeyCommand::new_from_code(KeyCode::Home, RecentlyPlayedAction::Home),
+        KeyCommand::new_from_code(KeyCode::End, RecentlyPlayedAction::End),
+        KeyCommand::new_from_code(KeyCode::Char('d'), RecentlyPlayedAction::RemoveSelected),
+        KeyCommand::new_action_only_mode(
+            vec![
+                (KeyCode::Enter, RecentlyPlayedAction::PlaySong),
+                (KeyCode::Char('l'), RecentlyPlayedAction::PlayAll),
+                (KeyCode::Char(' '), RecentlyPlayedAction::AddSongToPlaylist),
+                (KeyCode::Char('L'), RecentlyPlayedAction::AddAllToPlaylist),
+            ],
+            KeyCode::Enter,
+            "Play",
+        ),
+    ]
+}
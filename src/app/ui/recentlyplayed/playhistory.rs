@@ -0,0 +1,104 @@
+use crate::app::datafile::{load_data_file, save_data_file};
+use crate::app::structures::SavedSong;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PLAY_HISTORY_FILE_NAME: &str = "play_history.json";
+const MAX_PLAY_HISTORY_LEN: usize = 500;
+
+/// A song that was played locally.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayedSong {
+    /// Seconds since the unix epoch when the song started playing.
+    pub played_at: u64,
+    pub song: SavedSong,
+}
+
+/// Songs played locally, most recent first, stored in the data directory.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayHistory {
+    pub songs: Vec<PlayedSong>,
+}
+
+impl PlayHistory {
+    /// Load the history from the data directory, or an empty history if it doesn't exist or
+    /// can't be read.
+    pub fn load() -> Self {
+        load_data_file(PLAY_HISTORY_FILE_NAME).unwrap_or_default()
+    }
+    pub fn save(&self) -> Result<()> {
+        save_data_file(PLAY_HISTORY_FILE_NAME, self)
+    }
+    /// Add a song to the front of the history, dropping the oldest songs once full.
+    pub fn record(&mut self, song: PlayedSong) {
+        self.songs.insert(0, song);
+        self.songs.truncate(MAX_PLAY_HISTORY_LEN);
+    }
+    pub fn remove(&mut self, idx: usize) -> Option<PlayedSong> {
+        (idx < self.songs.len()).then(|| self.songs.remove(idx))
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Display how long ago a song was played, e.g "5m ago".
+pub fn format_played_ago(played_at: u64, now: u64) -> String {
+    let secs = now.saturating_sub(played_at);
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_played_ago, PlayHistory, PlayedSong, MAX_PLAY_HISTORY_LEN};
+    use crate::app::structures::test_saved_song;
+
+    fn played_song(played_at: u64) -> PlayedSong {
+        PlayedSong {
+            played_at,
+            song: test_saved_song("lYBUbBu4W08"),
+        }
+    }
+    #[test]
+    fn test_history_round_trip() {
+        let mut history = PlayHistory::default();
+        history.record(played_song(1));
+        history.record(played_song(2));
+        let serialized = serde_json::to_string(&history).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PlayHistory>(&serialized).unwrap(),
+            history
+        );
+    }
+    #[test]
+    fn test_history_most_recent_first_and_bounded() {
+        let mut history = PlayHistory::default();
+        for i in 0..MAX_PLAY_HISTORY_LEN as u64 + 10 {
+            history.record(played_song(i));
+        }
+        assert_eq!(history.songs.len(), MAX_PLAY_HISTORY_LEN);
+        assert_eq!(
+            history.songs.first().map(|s| s.played_at),
+            Some(MAX_PLAY_HISTORY_LEN as u64 + 9)
+        );
+    }
+    #[test]
+    fn test_format_played_ago() {
+        assert_eq!(format_played_ago(100, 130), "just now");
+        assert_eq!(format_played_ago(0, 300), "5m ago");
+        assert_eq!(format_played_ago(0, 7200), "2h ago");
+        assert_eq!(format_played_ago(0, 86400 * 3), "3d ago");
+    }
+}
@@ -0,0 +1,206 @@
+use crate::app::structures::{DownloadStatus, ListSong};
+use crate::app::view::Scrollable;
+use crate::i18n::{tr, tr_format};
+use ytmapi_rs::common::youtuberesult::YoutubeResult;
+use ytmapi_rs::common::{LikeStatus, YoutubeID};
+
+/// A labelled piece of song metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SongDetailsField {
+    pub label: String,
+    pub value: String,
+}
+
+/// Full metadata for a song, as displayed in the song details popup.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SongDetails {
+    pub title: String,
+    pub fields: Vec<SongDetailsField>,
+}
+
+impl SongDetails {
+    pub fn from_song(song: &ListSong) -> Self {
+        let unknown = || tr("Unknown").to_string();
+        let like_status = match song.like_status {
+            Some(LikeStatus::Liked) => tr("Liked").to_string(),
+            Some(LikeStatus::Disliked) => tr("Disliked").to_string(),
+            Some(LikeStatus::Indifferent) => tr("Not rated").to_string(),
+            None => unknown(),
+        };
+        let download_status = match song.download_status {
+            DownloadStatus::None => tr("Not downloaded").to_string(),
+            DownloadStatus::Queued => tr("Queued").to_string(),
+            DownloadStatus::Downloading(p) => tr_format("Downloading {}%", &[&p.0]),
+            DownloadStatus::Downloaded(_) => tr("Downloaded").to_string(),
+            DownloadStatus::Failed => tr("Download failed").to_string(),
+        };
+        let fields = [
+            ("Title", song.get_title().clone()),
+            (
+                "Artists",
+                song.get_artists()
+                    .iter()
+                    .map(|a| a.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            (
+                "Artist ID",
+                song.get_artist_id()
+                    .map(|a| a.get_raw().to_string())
+                    .unwrap_or_else(unknown),
+            ),
+            ("Album", song.get_album().clone()),
+            (
+                "Album ID",
+                song.get_album_id()
+                    .map(|a| a.get_raw().to_string())
+                    .unwrap_or_else(unknown),
+            ),
+            ("Year", song.get_year().clone()),
+            (
+                "Duration",
+                song.get_duration().clone().unwrap_or_else(unknown),
+            ),
+            (
+                "Explicit",
+                if *song.get_is_explicit() {
+                    tr("Yes")
+                } else {
+                    tr("No")
+                }
+                .to_string(),
+            ),
+            ("Video ID", song.raw.get_video_id().get_raw().to_string()),
+            ("Like status", like_status),
+            ("Download status", download_status),
+            (
+                "Bitrate",
+                song.audio_bitrate
+                    .map(|b| tr_format("{} kbps", &[&(b / 1000)]))
+                    .unwrap_or_else(unknown),
+            ),
+        ]
+        .into_iter()
+        .map(|(label, value)| SongDetailsField {
+            label: tr(label).to_string(),
+            value,
+        })
+        .collect();
+        Self {
+            title: song.get_title().clone(),
+            fields,
+        }
+    }
+}
+
+/// Popup showing the full metadata for a song, where any field can be copied.
+#[derive(Default)]
+pub struct SongDetailsPopup {
+    pub shown: bool,
+    pub details: SongDetails,
+    pub cur: usize,
+}
+
+impl Scrollable for SongDetailsPopup {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur = self
+            .cur
+            .saturating_add_signed(amount)
+            .min(self.details.fields.len().saturating_sub(1));
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur
+    }
+}
+
+impl SongDetailsPopup {
+    pub fn open(&mut self, details: SongDetails) {
+        self.shown = true;
+        self.details = details;
+        self.cur = 0;
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+    }
+    /// Value of the selected field, to be copied.
+    pub fn get_selected_value(&self) -> Option<&str> {
+        self.details
+            .fields
+            .get(self.cur)
+            .map(|field| field.value.as_str())
+    }
+}
+
+pub mod draw {
+    use super::SongDetailsPopup;
+    use crate::drawutils::{
+        centered_rect, highlight_style, SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR, TEXT_COLOUR,
+    };
+    use ratatui::{
+        prelude::Rect,
+        style::Style,
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, List, ListItem, ListState},
+        Frame,
+    };
+
+    const POPUP_WIDTH: u16 = 60;
+
+    pub fn draw_song_details(f: &mut Frame, p: &SongDetailsPopup, chunk: Rect) {
+        let label_width = p
+            .details
+            .fields
+            .iter()
+            .map(|field| field.label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let items = p.details.fields.iter().map(|field| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<label_width$} ", field.label),
+                    Style::new().fg(TABLE_HEADINGS_COLOUR),
+                ),
+                Span::styled(field.value.as_str(), Style::new().fg(TEXT_COLOUR)),
+            ]))
+        });
+        let mut state = ListState::default().with_selected(Some(p.cur));
+        let list = List::new(items).highlight_style(highlight_style()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(p.details.title.as_str()),
+        );
+        let height = p.details.fields.len() as u16 + 2;
+        let area = centered_rect(height, POPUP_WIDTH, chunk);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SongDetails, SongDetailsField, SongDetailsPopup};
+    use crate::app::view::Scrollable;
+
+    #[test]
+    fn test_get_selected_value_clamped() {
+        let mut popup = SongDetailsPopup::default();
+        popup.open(SongDetails {
+            title: "Song".into(),
+            fields: vec![
+                SongDetailsField {
+                    label: "Title".into(),
+                    value: "Song".into(),
+                },
+                SongDetailsField {
+                    label: "Video ID".into(),
+                    value: "abc".into(),
+                },
+            ],
+        });
+        assert_eq!(popup.get_selected_value(), Some("Song"));
+        popup.increment_list(5);
+        assert_eq!(popup.get_selected_value(), Some("abc"));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::app::taskmanager::{TaskID, TaskSummary};
+use crate::app::view::Scrollable;
+
+/// Popup listing the in-flight tasks, allowing them to be cancelled individually.
+#[derive(Default)]
+pub struct TasksPopup {
+    pub shown: bool,
+    pub cur: usize,
+    pub tasks: Vec<TaskSummary>,
+}
+
+impl Scrollable for TasksPopup {
+    fn increment_list(&mut self, amount: isize) {
+        self.cur = self
+            .cur
+            .saturating_add_signed(amount)
+            .min(self.tasks.len().saturating_sub(1));
+    }
+    fn get_selected_item(&self) -> usize {
+        self.cur
+    }
+}
+
+impl TasksPopup {
+    /// Show the popup. The caller is responsible for keeping the task list up to date.
+    pub fn open(&mut self) {
+        self.shown = true;
+        self.cur = 0;
+    }
+    pub fn close(&mut self) {
+        self.shown = false;
+        self.tasks.clear();
+    }
+    pub fn set_tasks(&mut self, tasks: Vec<TaskSummary>) {
+        self.tasks = tasks;
+        self.cur = self.cur.min(self.tasks.len().saturating_sub(1));
+    }
+    pub fn get_selected_id(&self) -> Option<TaskID> {
+        self.tasks.get(self.cur).map(|t| t.id)
+    }
+}
+
+pub mod draw {
+    use super::TasksPopup;
+    use crate::app::taskmanager::TaskStatus;
+    use crate::drawutils::{
+        centered_rect, highlight_style, SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR, TEXT_COLOUR,
+    };
+    use crate::i18n::tr;
+    use ratatui::{
+        layout::Constraint,
+        prelude::Rect,
+        style::Style,
+        widgets::{Block, Borders, Clear, Row, Table, TableState},
+        Frame,
+    };
+
+    const POPUP_WIDTH: u16 = 80;
+    const POPUP_MAX_HEIGHT: u16 = 20;
+
+    pub fn draw_tasks(f: &mut Frame, p: &TasksPopup, chunk: Rect) {
+        let title = if p.tasks.is_empty() {
+            tr("Tasks - none running")
+        } else {
+            tr("Tasks - Enter to cancel")
+        };
+        let rows = p.tasks.iter().map(|t| {
+            let status = match t.status {
+                TaskStatus::Queued => tr("Queued"),
+                TaskStatus::Waiting => tr("Waiting"),
+                TaskStatus::Receiving => tr("Receiving"),
+                TaskStatus::Retrying => tr("Retrying"),
+            };
+            let progress = match &t.progress {
+                Some((progress, label)) => format!("{label} ({}%)", progress.0),
+                None => String::new(),
+            };
+            Row::new(vec![
+                format!("{:?}", t.category),
+                format!("{}s", t.age.as_secs()),
+                status.to_string(),
+                progress,
+            ])
+            .style(Style::new().fg(TEXT_COLOUR))
+        });
+        let mut state = TableState::default().with_selected(Some(p.cur));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(20),
+            ],
+        )
+        .header(
+            Row::new(["Category", "Age", "Status", "Progress"].map(tr))
+                .style(Style::new().fg(TABLE_HEADINGS_COLOUR)),
+        )
+        .highlight_style(highlight_style())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(title),
+        );
+        let height = (p.tasks.len() as u16 + 3).clamp(3, POPUP_MAX_HEIGHT);
+        let area = centered_rect(height, POPUP_WIDTH, chunk);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(table, area, &mut state);
+    }
+}
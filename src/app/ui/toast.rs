@@ -0,0 +1,207 @@
+use crate::app::view::Scrollable;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a toast is displayed for before it is dismissed.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+/// Maximum number of toasts displayed at once.
+const MAX_TOASTS: usize = 3;
+/// Maximum number of notifications kept in the history.
+const MAX_HISTORY: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotifyLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A message to be displayed to the user, regardless of the active context.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UIMessage {
+    Notify(NotifyLevel, String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub level: NotifyLevel,
+    pub text: String,
+    pub created: Instant,
+}
+
+/// Transient toasts displayed above the active context, and a history of previous notifications.
+#[derive(Default)]
+pub struct Toasts {
+    active: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+    pub history_shown: bool,
+    pub history_cur: usize,
+}
+
+impl Toasts {
+    pub fn notify(&mut self, level: NotifyLevel, text: String, now: Instant) {
+        let notification = Notification {
+            level,
+            text,
+            created: now,
+        };
+        if self.active.len() >= MAX_TOASTS {
+            self.active.pop_front();
+        }
+        self.active.push_back(notification.clone());
+        if self.history.len() >= MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(notification);
+    }
+    /// Dismiss any toasts that have been displayed for long enough.
+    /// Remove notifications that have been shown for long enough.
+    /// Returns true if any were removed.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let len = self.active.len();
+        self.active
+            .retain(|n| now.saturating_duration_since(n.created) < TOAST_DURATION);
+        self.active.len() != len
+    }
+    pub fn get_active(&self) -> impl Iterator<Item = &Notification> {
+        self.active.iter()
+    }
+    /// Notification history, most recent first.
+    pub fn get_history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter().rev()
+    }
+    pub fn toggle_history(&mut self) {
+        self.history_shown = !self.history_shown;
+        self.history_cur = 0;
+    }
+}
+
+impl Scrollable for Toasts {
+    fn increment_list(&mut self, amount: isize) {
+        self.history_cur = self
+            .history_cur
+            .saturating_add_signed(amount)
+            .min(self.history.len().saturating_sub(1));
+    }
+    fn get_selected_item(&self) -> usize {
+        self.history_cur
+    }
+}
+
+impl std::fmt::Display for NotifyLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotifyLevel::Info => write!(f, "Info"),
+            NotifyLevel::Warning => write!(f, "Warning"),
+            NotifyLevel::Error => write!(f, "Error"),
+        }
+    }
+}
+
+pub mod draw {
+    use super::{NotifyLevel, Toasts};
+    use crate::drawutils::{centered_rect, highlight_style, SELECTED_BORDER_COLOUR, TEXT_COLOUR};
+    use crate::i18n::tr;
+    use ratatui::{
+        prelude::Rect,
+        style::{Color, Style},
+        widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+        Frame,
+    };
+
+    const TOAST_WIDTH: u16 = 50;
+    const TOAST_HEIGHT: u16 = 3;
+    const HISTORY_WIDTH: u16 = 80;
+    const HISTORY_MAX_HEIGHT: u16 = 20;
+
+    fn level_colour(level: NotifyLevel) -> Color {
+        match level {
+            NotifyLevel::Info => SELECTED_BORDER_COLOUR,
+            NotifyLevel::Warning => Color::Yellow,
+            NotifyLevel::Error => Color::Red,
+        }
+    }
+
+    /// Draw the active toasts stacked in the top right corner of the chunk.
+    pub fn draw_toasts(f: &mut Frame, t: &Toasts, chunk: Rect) {
+        let width = TOAST_WIDTH.min(chunk.width);
+        for (i, n) in t.get_active().enumerate() {
+            let y = chunk.y + i as u16 * TOAST_HEIGHT;
+            if y + TOAST_HEIGHT > chunk.bottom() {
+                break;
+            }
+            let area = Rect::new(chunk.right() - width, y, width, TOAST_HEIGHT);
+            let toast = Paragraph::new(n.text.as_str())
+                .style(Style::new().fg(TEXT_COLOUR))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::new().fg(level_colour(n.level)))
+                        .title(n.level.to_string()),
+                );
+            f.render_widget(Clear, area);
+            f.render_widget(toast, area);
+        }
+    }
+    pub fn draw_history(f: &mut Frame, t: &Toasts, chunk: Rect) {
+        let items: Vec<_> = t
+            .get_history()
+            .map(|n| {
+                ListItem::new(format!("[{}] {}", n.level, n.text))
+                    .style(Style::new().fg(level_colour(n.level)))
+            })
+            .collect();
+        let title = if items.is_empty() {
+            tr("Notifications - none")
+        } else {
+            tr("Notifications")
+        };
+        let height = (items.len() as u16 + 2).clamp(3, HISTORY_MAX_HEIGHT);
+        let mut state = ListState::default().with_selected(Some(t.history_cur));
+        let list = List::new(items).highlight_style(highlight_style()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(title),
+        );
+        let area = centered_rect(height, HISTORY_WIDTH, chunk);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NotifyLevel, Toasts, MAX_TOASTS, TOAST_DURATION};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_toasts_expire() {
+        let mut toasts = Toasts::default();
+        let now = Instant::now();
+        toasts.notify(NotifyLevel::Error, "first".into(), now);
+        toasts.notify(
+            NotifyLevel::Info,
+            "second".into(),
+            now + Duration::from_secs(1),
+        );
+        assert!(toasts.expire(now + TOAST_DURATION));
+        assert!(!toasts.expire(now + TOAST_DURATION));
+        let active: Vec<_> = toasts.get_active().map(|n| n.text.as_str()).collect();
+        assert_eq!(active, vec!["second"]);
+        // Expired toasts are kept in the history.
+        let history: Vec<_> = toasts.get_history().map(|n| n.text.as_str()).collect();
+        assert_eq!(history, vec!["second", "first"]);
+    }
+    #[test]
+    fn test_toasts_limited() {
+        let mut toasts = Toasts::default();
+        let now = Instant::now();
+        for i in 0..MAX_TOASTS + 1 {
+            toasts.notify(NotifyLevel::Info, i.to_string(), now);
+        }
+        assert_eq!(toasts.get_active().count(), MAX_TOASTS);
+        assert_eq!(toasts.get_active().next().unwrap().text, "1");
+    }
+}
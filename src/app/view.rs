@@ -91,6 +91,50 @@ impl FilterString {
     }
 }
 
+/// Case insensitive fuzzy match, where the characters of the pattern must appear in order in
+/// the text but not necessarily next to each other. Returns None if the text doesn't match,
+/// otherwise a score where higher is a better match. Consecutive characters and characters at
+/// the start of words score higher.
+pub fn fuzzy_match_score(pattern: &str, text: &str) -> Option<usize> {
+    let mut pattern = pattern.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut prev_matched = false;
+    let mut prev_char = None;
+    for c in text.chars() {
+        let Some(next) = pattern.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq(std::iter::once(*next));
+        if matched {
+            pattern.next();
+            score += 1;
+            if prev_matched {
+                score += 2;
+            }
+            if !prev_char.is_some_and(char::is_alphanumeric) {
+                score += 3;
+            }
+        }
+        prev_matched = matched;
+        prev_char = Some(c);
+    }
+    pattern.peek().is_none().then_some(score)
+}
+
+/// Index of the next item after the selected one that starts with the character, ignoring case.
+/// Wraps around to the start of the list, so repeatedly typing a letter cycles through matches.
+pub fn find_next_starting_with<S: AsRef<str>>(items: &[S], cur: usize, c: char) -> Option<usize> {
+    let starts_with = |text: &str| {
+        text.trim_start()
+            .chars()
+            .next()
+            .is_some_and(|first| first.to_lowercase().eq(c.to_lowercase()))
+    };
+    (1..=items.len())
+        .map(|offset| (cur + offset) % items.len())
+        .find(|&i| starts_with(items[i].as_ref()))
+}
+
 /// Basic wrapper around constraint to allow mixing of percentage and length.
 pub enum BasicConstraint {
     Length(u16),
@@ -127,6 +171,42 @@ pub trait Scrollable {
     // Increment the list by the specified amount.
     fn increment_list(&mut self, amount: isize);
     fn get_selected_item(&self) -> usize;
+    /// Index of the first visible item, given the total number of items and the number that fit
+    /// on screen. By default the selected item is kept in the centre where possible.
+    fn get_offset(&self, len: usize, visible_len: usize) -> usize {
+        get_centered_offset(self.get_selected_item(), len, visible_len)
+    }
+}
+
+/// Offset that keeps the selected item centred, without scrolling past either end of the list.
+pub fn get_centered_offset(selected: usize, len: usize, visible_len: usize) -> usize {
+    if len <= visible_len {
+        return 0;
+    }
+    selected
+        .saturating_sub(visible_len / 2)
+        .min(len - visible_len)
+}
+/// A list or table where typing a letter jumps the selection to the next row starting with it.
+pub trait TypeAheadJump: Scrollable {
+    /// Text of each row, in display order, that typed letters are matched against.
+    fn get_jump_texts(&self) -> Vec<Cow<str>>;
+    /// Jump to the next row starting with the character, returning false if there is none.
+    fn jump_to_char(&mut self, c: char) -> bool {
+        let cur = self.get_selected_item();
+        let Some(next) = find_next_starting_with(&self.get_jump_texts(), cur, c) else {
+            return false;
+        };
+        self.increment_list(next as isize - cur as isize);
+        true
+    }
+}
+/// Text of a single column for each row of a table, for use as jump texts.
+pub fn get_table_column_texts<T: TableView>(table: &T, column: usize) -> Vec<Cow<str>> {
+    table
+        .get_items()
+        .map(|mut row| row.nth(column).unwrap_or_default())
+        .collect()
 }
 /// A struct that can either be scrolled or forward scroll commands to a component.
 // To allow scrolling at a top level.
@@ -150,6 +230,10 @@ pub trait TableView: Scrollable + Loadable {
     fn get_items(&self) -> Box<dyn ExactSizeIterator<Item = TableItem> + '_>;
     // XXX: This doesn't need to be so fancy - could return a static slice.
     fn get_headings(&self) -> Box<dyn Iterator<Item = &'static str>>;
+    /// Text drawn along the bottom border of the table, if any.
+    fn get_footer(&self) -> Option<Cow<str>> {
+        None
+    }
     // Not a particularyl useful function for a sortabletableview
     fn len(&self) -> usize {
         self.get_items().len()
@@ -212,13 +296,20 @@ pub trait DrawableMut {
 // A part of the application that can be in a Loading state.
 pub trait Loadable {
     fn is_loading(&self) -> bool;
+    /// Whether the component is still loading, after an attempt to load it failed.
+    fn is_retrying(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use ratatui::prelude::Constraint;
 
-    use super::{basic_constraints_to_table_constraints, BasicConstraint};
+    use super::{
+        basic_constraints_to_table_constraints, find_next_starting_with, fuzzy_match_score,
+        get_centered_offset, BasicConstraint,
+    };
     use crate::app::structures::Percentage;
 
     #[test]
@@ -250,4 +341,37 @@ mod tests {
         let converted = basic_constraints_to_table_constraints(basic_constraints, 20, 0);
         assert_eq!(converted, constraints);
     }
+    #[test]
+    fn test_fuzzy_match_score() {
+        assert_eq!(fuzzy_match_score("", "Anything"), Some(0));
+        assert_eq!(fuzzy_match_score("xyz", "Beatles"), None);
+        assert_eq!(fuzzy_match_score("tsb", "Beatles"), None);
+        assert!(fuzzy_match_score("btl", "The Beatles").is_some());
+        // Consecutive matches at word starts beat scattered matches.
+        assert!(
+            fuzzy_match_score("bea", "The Beatles").unwrap()
+                > fuzzy_match_score("bea", "Bob Dylan and the Band").unwrap()
+        );
+    }
+    #[test]
+    fn test_find_next_starting_with() {
+        let items = ["Abba", "beatles", "Blur", "Coldplay"];
+        assert_eq!(find_next_starting_with(&items, 0, 'B'), Some(1));
+        assert_eq!(find_next_starting_with(&items, 1, 'b'), Some(2));
+        // Wraps around to the start, and can land back on the selected item.
+        assert_eq!(find_next_starting_with(&items, 2, 'b'), Some(1));
+        assert_eq!(find_next_starting_with(&items, 0, 'a'), Some(0));
+        assert_eq!(find_next_starting_with(&items, 0, 'z'), None);
+        assert_eq!(find_next_starting_with::<&str>(&[], 0, 'a'), None);
+    }
+    #[test]
+    fn test_centered_offset() {
+        // Short lists are never scrolled.
+        assert_eq!(get_centered_offset(4, 5, 10), 0);
+        // Near the top, the list isn't scrolled until the selection passes the middle.
+        assert_eq!(get_centered_offset(3, 100, 10), 0);
+        assert_eq!(get_centered_offset(50, 100, 10), 45);
+        // Near the bottom, the last item stays at the bottom of the view.
+        assert_eq!(get_centered_offset(98, 100, 10), 90);
+    }
 }
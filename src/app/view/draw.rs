@@ -4,7 +4,7 @@ use super::{
 use crate::{
     app::view::ListView,
     drawutils::{
-        DESELECTED_BORDER_COLOUR, ROW_HIGHLIGHT_COLOUR, SELECTED_BORDER_COLOUR,
+        get_spinner, highlight_style, icon, DESELECTED_BORDER_COLOUR, SELECTED_BORDER_COLOUR,
         TABLE_HEADINGS_COLOUR,
     },
 };
@@ -33,13 +33,19 @@ pub fn get_table_sort_character_array(sort_commands: &[TableSortCommand]) -> Vec
     sort_commands.iter().fold(temp_vec, |mut acc, e| {
         // We created the Vec to accomodate max col above so this is safe.
         acc[e.column] = match e.direction {
-            super::SortDirection::Asc => Some(''),
-            super::SortDirection::Desc => Some(''),
+            super::SortDirection::Asc => Some(icon('', '^')),
+            super::SortDirection::Desc => Some(icon('', 'v')),
         };
         acc
     })
 }
 
+/// Number of rows of a table drawn in the chunk that are visible.
+pub fn table_visible_rows(chunk: Rect) -> usize {
+    // Minus for height of block and heading.
+    chunk.height.saturating_sub(4) as usize
+}
+
 // Draw a block, and return the inner rectangle.
 pub fn draw_panel<S: AsRef<str>>(
     f: &mut Frame,
@@ -83,6 +89,9 @@ where
     // TODO: Scroll bars
     let list_title = list.get_title();
     let list_len = list.len();
+    // Minus for height of block.
+    let list_height = chunk.height.saturating_sub(2) as usize;
+    *state.offset_mut() = list.get_offset(list_len, list_height);
     let list_items: Vec<_> = list
         .get_items_display()
         .iter()
@@ -92,10 +101,13 @@ where
         .collect();
     // TODO: Better title for list
     let _title = format!("{list_title} - {list_len} items");
-    let list_widget =
-        List::new(list_items).highlight_style(Style::default().bg(ROW_HIGHLIGHT_COLOUR));
+    let list_widget = List::new(list_items).highlight_style(highlight_style());
     let inner_chunk = draw_panel(f, list_title, None, chunk, selected);
-    f.render_stateful_widget(list_widget, inner_chunk, state);
+    if list.is_loading() {
+        draw_loading(f, inner_chunk, list.is_retrying())
+    } else {
+        f.render_stateful_widget(list_widget, inner_chunk, state);
+    }
 }
 
 pub fn draw_table<T>(f: &mut Frame, table: &T, chunk: Rect, state: &mut TableState, selected: bool)
@@ -107,8 +119,8 @@ where
     // TODO: theming
     let table_items = table.get_items().map(|item| Row::new(item));
     let number_items = table.len();
-    // Minus for height of block and heading.
-    let table_height = chunk.height.saturating_sub(4) as usize;
+    let table_height = table_visible_rows(chunk);
+    *state.offset_mut() = table.get_offset(number_items, table_height);
     let table_widths = basic_constraints_to_table_constraints(
         table.get_layout(),
         chunk.width.saturating_sub(2),
@@ -116,7 +128,7 @@ where
     ); // Minus block
     let heading_names = table.get_headings();
     let table_widget = Table::new(table_items, table_widths)
-        .highlight_style(Style::default().bg(ROW_HIGHLIGHT_COLOUR))
+        .highlight_style(highlight_style())
         .header(
             Row::new(heading_names).style(
                 Style::default()
@@ -131,9 +143,9 @@ where
         .begin_symbol(None)
         .end_symbol(None);
     let scrollable_lines = number_items.saturating_sub(table_height);
-    let inner_chunk = draw_panel(f, table.get_title(), None, chunk, selected);
+    let inner_chunk = draw_panel(f, table.get_title(), table.get_footer(), chunk, selected);
     if table.is_loading() {
-        draw_loading(f, inner_chunk)
+        draw_loading(f, inner_chunk, table.is_retrying())
     } else {
         f.render_stateful_widget(table_widget, inner_chunk, state);
         // Call this after rendering table, as offset is mutated.
@@ -166,8 +178,8 @@ pub fn draw_sortable_table<T>(
     let table_items = table.get_filtered_items().map(|item| Row::new(item));
     // Likely expensive, and could be optimised.
     let number_items = table.get_filtered_items().count();
-    // Minus for height of block and heading.
-    let table_height = chunk.height.saturating_sub(4) as usize;
+    let table_height = table_visible_rows(chunk);
+    *state.offset_mut() = table.get_offset(number_items, table_height);
     let table_widths = basic_constraints_to_table_constraints(
         table.get_layout(),
         chunk.width.saturating_sub(2),
@@ -180,7 +192,7 @@ pub fn draw_sortable_table<T>(
         let mut hstr = h.to_string();
         let sort_char = sort_headings.next().unwrap_or_default().unwrap_or_default();
         if sort_char == '\x00' && sortable_headings.contains(&i) {
-            hstr.push('');
+            hstr.push(icon('', '-'));
         }
         hstr.push(sort_char);
         hstr
@@ -200,7 +212,7 @@ pub fn draw_sortable_table<T>(
         filter_str
     };
     let table_widget = Table::new(table_items, table_widths)
-        .highlight_style(Style::default().bg(ROW_HIGHLIGHT_COLOUR))
+        .highlight_style(highlight_style())
         .header(
             Row::new(combined_headings).style(
                 Style::default()
@@ -223,7 +235,7 @@ pub fn draw_sortable_table<T>(
         selected,
     );
     if table.is_loading() {
-        draw_loading(f, inner_chunk)
+        draw_loading(f, inner_chunk, table.is_retrying())
     } else {
         f.render_stateful_widget(table_widget, inner_chunk, state);
         // Call this after rendering table, as offset is mutated.
@@ -241,7 +253,13 @@ pub fn draw_sortable_table<T>(
     }
 }
 
-pub fn draw_loading(f: &mut Frame, chunk: Rect) {
-    let loading = Paragraph::new("Loading");
+/// Draw an animated loading spinner, used in place of the contents of any Loadable component.
+pub fn draw_loading(f: &mut Frame, chunk: Rect, retrying: bool) {
+    let loading = if retrying {
+        format!("{} Retrying", get_spinner())
+    } else {
+        format!("{} Loading", get_spinner())
+    };
+    let loading = Paragraph::new(loading);
     f.render_widget(loading, chunk);
 }
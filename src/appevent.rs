@@ -1,36 +1,60 @@
 /// NOTE: WASM currently not supported.
+use crate::config::{get_config_file_path, WebRemoteSettings};
+use crate::remote::{self, RemoteRequest};
 use crate::Result;
 use crossterm::event::{Event, EventStream, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
 use futures::StreamExt;
+use notify::{EventKind, RecursiveMode, Watcher};
 use std::time::Duration;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::warn;
 
+#[cfg(target_family = "unix")]
+use crate::app::platform::{SIGCONT, SIGTSTP};
 #[cfg(target_family = "unix")]
 use tokio::signal::unix::SignalKind;
 
-const TICK_RATE: Duration = Duration::from_millis(200);
-
 #[derive(Debug)]
 pub enum AppEvent {
     Tick,
     Crossterm(Event),
     QuitSignal,
+    /// Asked to stop by job control, e.g SIGTSTP, so the terminal should be restored first.
+    SuspendSignal,
+    /// Continued after being stopped, so the terminal should be taken back.
+    ContinueSignal,
+    Remote(RemoteRequest),
+    /// The config file was edited.
+    ConfigChanged,
 }
 
+/// How long to wait for an editor to finish saving the config file before it's reloaded, as a
+/// save can be several events.
+const CONFIG_SETTLE_TIME: Duration = Duration::from_millis(100);
+const CONFIG_WATCH_CHANNEL_SIZE: usize = 64;
+
 pub struct EventHandler {
     _tx: Sender<AppEvent>,
     rx: Receiver<AppEvent>,
     _ticker: EventSpawner<Ticker>,
     _signal_watcher: EventSpawner<SignalWatcher>,
     _crossterm_watcher: EventSpawner<CrosstermWatcher>,
+    /// None until started, or if the socket couldn't be created.
+    _remote_watcher: Option<EventSpawner<RemoteWatcher>>,
+    /// None unless turned on in config, or if the port couldn't be bound.
+    _web_remote: Option<EventSpawner<WebRemote>>,
+    /// None until started, or if the config file path couldn't be found.
+    _config_watcher: Option<EventSpawner<ConfigWatcher>>,
 }
 
 struct Ticker;
 struct SignalWatcher;
 struct CrosstermWatcher;
+struct RemoteWatcher;
+struct WebRemote;
+struct ConfigWatcher;
 
 struct EventSpawner<T> {
     _handler: JoinHandle<()>,
@@ -39,10 +63,10 @@ struct EventSpawner<T> {
 }
 
 impl EventSpawner<Ticker> {
-    fn new_ticker(tx: &Sender<AppEvent>) -> EventSpawner<Ticker> {
+    fn new_ticker(tx: &Sender<AppEvent>, tick_rate: Duration) -> EventSpawner<Ticker> {
         let handler_tx = tx.clone();
         let _tx = tx.clone();
-        let mut interval = interval(TICK_RATE);
+        let mut interval = interval(tick_rate);
         let _spawner_type = Ticker;
         let _handler = tokio::spawn(async move {
             loop {
@@ -71,16 +95,23 @@ impl EventSpawner<SignalWatcher> {
         let mut sigint = tokio::signal::unix::signal(SignalKind::interrupt())?;
         let mut sigquit = tokio::signal::unix::signal(SignalKind::quit())?;
         let mut sigterm = tokio::signal::unix::signal(SignalKind::terminate())?;
+        // Sent when the terminal is closed.
+        let mut sighup = tokio::signal::unix::signal(SignalKind::hangup())?;
+        let mut sigtstp = tokio::signal::unix::signal(SignalKind::from_raw(SIGTSTP))?;
+        let mut sigcont = tokio::signal::unix::signal(SignalKind::from_raw(SIGCONT))?;
 
         let _handler = tokio::spawn(async move {
             loop {
-                tokio::select! {
-                    _ = sigint.recv() => {}
-                    _ = sigquit.recv() => {}
-                    _ = sigterm.recv() => {}
-                }
+                let event = tokio::select! {
+                    _ = sigint.recv() => AppEvent::QuitSignal,
+                    _ = sigquit.recv() => AppEvent::QuitSignal,
+                    _ = sigterm.recv() => AppEvent::QuitSignal,
+                    _ = sighup.recv() => AppEvent::QuitSignal,
+                    _ = sigtstp.recv() => AppEvent::SuspendSignal,
+                    _ = sigcont.recv() => AppEvent::ContinueSignal,
+                };
                 handler_tx
-                    .send(AppEvent::QuitSignal)
+                    .send(event)
                     .await
                     .unwrap_or_else(|e| warn!("Error {:?} receieved when sending signal event", e));
             }
@@ -100,6 +131,8 @@ impl EventSpawner<SignalWatcher> {
         let _tx = tx.clone();
         let _spawner_type = SignalWatcher;
 
+        // Raw mode stops ctrl-c being sent as a signal, but it's still sent by other processes.
+        let mut ctrl_c = tokio::signal::windows::ctrl_c()?;
         let mut ctrl_break = tokio::signal::windows::ctrl_break()?;
         let mut ctrl_close = tokio::signal::windows::ctrl_close()?;
         let mut ctrl_logoff = tokio::signal::windows::ctrl_logoff()?;
@@ -108,6 +141,7 @@ impl EventSpawner<SignalWatcher> {
         let _handler = tokio::spawn(async move {
             loop {
                 tokio::select! {
+                   _ = ctrl_c.recv() => {}
                    _ = ctrl_break.recv() => {}
                    _ = ctrl_close.recv() => {}
                    _ = ctrl_logoff.recv() => {}
@@ -165,10 +199,195 @@ impl EventSpawner<CrosstermWatcher> {
         }
     }
 }
+#[cfg(unix)]
+impl EventSpawner<RemoteWatcher> {
+    fn new_remote_watcher(tx: &Sender<AppEvent>) -> Result<EventSpawner<RemoteWatcher>> {
+        let handler_tx = tx.clone();
+        let _tx = tx.clone();
+        let _spawner_type = RemoteWatcher;
+
+        let path = remote::get_remote_socket_path()?;
+        // The socket may be left over from a youtui that didn't exit cleanly, or belong to
+        // another youtui that is running. Either way, the newest youtui takes over control.
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        let listener = tokio::net::UnixListener::bind(&path)?;
+
+        let _handler = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => spawn_remote_connection(stream, handler_tx.clone()),
+                    Err(e) => warn!("Error {:?} received when accepting remote connection", e),
+                }
+            }
+        });
+        Ok(Self {
+            _tx,
+            _handler,
+            _spawner_type,
+        })
+    }
+}
+
+#[cfg(windows)]
+impl EventSpawner<RemoteWatcher> {
+    fn new_remote_watcher(tx: &Sender<AppEvent>) -> Result<EventSpawner<RemoteWatcher>> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+        let handler_tx = tx.clone();
+        let _tx = tx.clone();
+        let _spawner_type = RemoteWatcher;
+
+        let mut server = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(remote::REMOTE_PIPE_NAME)?;
+
+        let _handler = tokio::spawn(async move {
+            loop {
+                if let Err(e) = server.connect().await {
+                    warn!("Error {:?} received when accepting remote connection", e);
+                    return;
+                }
+                // Create the next instance before handling this connection, so that there is
+                // always a pipe for the next client to connect to.
+                let connected = server;
+                server = match ServerOptions::new().create(remote::REMOTE_PIPE_NAME) {
+                    Ok(server) => server,
+                    Err(e) => {
+                        warn!("Error {:?} received when creating remote pipe", e);
+                        return;
+                    }
+                };
+                spawn_remote_connection(connected, handler_tx.clone());
+            }
+        });
+        Ok(Self {
+            _tx,
+            _handler,
+            _spawner_type,
+        })
+    }
+}
+
+impl EventSpawner<ConfigWatcher> {
+    fn new_config_watcher(tx: &Sender<AppEvent>) -> Result<EventSpawner<ConfigWatcher>> {
+        let handler_tx = tx.clone();
+        let _tx = tx.clone();
+        let _spawner_type = ConfigWatcher;
+
+        let path = get_config_file_path()?;
+        let file_name = path.file_name().map(ToOwned::to_owned);
+        let (watch_tx, mut watch_rx) = channel(CONFIG_WATCH_CHANNEL_SIZE);
+        // Called from notify's own thread, not the runtime, so it's able to block.
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = watch_tx.blocking_send(event);
+        })?;
+        // The directory is watched rather than the file, as editors often replace the file when
+        // saving, which would end a watch on the file itself.
+        if let Some(dir) = path.parent() {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        let _handler = tokio::spawn(async move {
+            // The watcher stops when dropped, so it's owned by the task.
+            let _watcher = watcher;
+            while let Some(event) = watch_rx.recv().await {
+                let event: notify::Event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        warn!("Error <{e}> received when watching config file");
+                        continue;
+                    }
+                };
+                let is_config_file = event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name() == file_name.as_deref());
+                if !is_config_file
+                    || !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                {
+                    continue;
+                }
+                tokio::time::sleep(CONFIG_SETTLE_TIME).await;
+                while watch_rx.try_recv().is_ok() {}
+                handler_tx
+                    .send(AppEvent::ConfigChanged)
+                    .await
+                    .unwrap_or_else(|e| warn!("Error {:?} receieved when sending config event", e));
+            }
+        });
+        Ok(Self {
+            _tx,
+            _handler,
+            _spawner_type,
+        })
+    }
+}
+
+impl EventSpawner<WebRemote> {
+    fn new_web_remote(
+        tx: &Sender<AppEvent>,
+        settings: WebRemoteSettings,
+    ) -> Result<EventSpawner<WebRemote>> {
+        let handler_tx = tx.clone();
+        let _tx = tx.clone();
+        let _spawner_type = WebRemote;
+
+        let listener = std::net::TcpListener::bind((settings.address, settings.port))?;
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+
+        let _handler = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let tx = handler_tx.clone();
+                        let token = settings.token.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = remote::web::handle_connection(stream, token, tx).await
+                            {
+                                warn!("Error <{e}> handling web remote request");
+                            }
+                        });
+                    }
+                    Err(e) => warn!(
+                        "Error {:?} received when accepting web remote connection",
+                        e
+                    ),
+                }
+            }
+        });
+        Ok(Self {
+            _tx,
+            _handler,
+            _spawner_type,
+        })
+    }
+}
+
+fn spawn_remote_connection<S>(stream: S, tx: Sender<AppEvent>)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        if let Err(e) = remote::handle_connection(stream, tx).await {
+            warn!("Error <{e}> handling remote command");
+        }
+    });
+}
+
+// Stop the spawned task once it's no longer needed, so that a new EventHandler doesn't compete
+// with it for events.
+impl<T> Drop for EventSpawner<T> {
+    fn drop(&mut self) {
+        self._handler.abort();
+    }
+}
+
 impl EventHandler {
-    pub fn new(channel_size: usize) -> Result<Self> {
+    pub fn new(channel_size: usize, tick_rate: Duration) -> Result<Self> {
         let (tx, rx) = channel(channel_size);
-        let _ticker = EventSpawner::new_ticker(&tx);
+        let _ticker = EventSpawner::new_ticker(&tx, tick_rate);
         let _signal_watcher = EventSpawner::new_signal_watcher(&tx)?;
         let _crossterm_watcher = EventSpawner::new_crossterm_watcher(&tx);
         Ok(Self {
@@ -177,8 +396,31 @@ impl EventHandler {
             _ticker,
             _signal_watcher,
             _crossterm_watcher,
+            _remote_watcher: None,
+            _web_remote: None,
+            _config_watcher: None,
         })
     }
+    /// Listen for commands from `youtui remote`. If this fails, the app still runs, but can't
+    /// be controlled remotely.
+    pub fn start_remote_watcher(&mut self) {
+        self._remote_watcher = EventSpawner::new_remote_watcher(&self._tx)
+            .map_err(|e| warn!("Error <{e}> starting remote control, it will be unavailable"))
+            .ok();
+    }
+    /// Serve the web remote, for controlling the app from other devices on the network.
+    pub fn start_web_remote(&mut self, settings: WebRemoteSettings) {
+        let port = settings.port;
+        self._web_remote = EventSpawner::new_web_remote(&self._tx, settings)
+            .map_err(|e| warn!("Error <{e}> starting web remote on port {port}"))
+            .ok();
+    }
+    /// Send ConfigChanged when the config file is edited, so that it can be reloaded.
+    pub fn start_config_watcher(&mut self) {
+        self._config_watcher = EventSpawner::new_config_watcher(&self._tx)
+            .map_err(|e| warn!("Error <{e}> watching config file, it won't be reloaded"))
+            .ok();
+    }
     pub async fn next(&mut self) -> Option<AppEvent> {
         self.rx.recv().await
     }
@@ -1,155 +1,102 @@
-use crate::config::Config;
+use crate::app::{get_default_help_entries, headless};
+use crate::config::{
+    check_config_file, default_config_file, get_config_file_keys, get_config_file_path, AuthType,
+    Config,
+};
+use crate::error::{Error, ErrorKind};
 use crate::get_api;
+use crate::remote::{self, RemoteCommand};
 use crate::Cli;
 use crate::Commands;
+use crate::ErrorOutput;
+use crate::OutputFormat;
 use crate::Result;
 use crate::RuntimeInfo;
-use std::path::PathBuf;
+use crate::{
+    get_cache_dir, get_config_dir, get_cookie_file_path, get_data_dir, get_oauth_file_path,
+    load_cookie_file, load_oauth_file,
+};
+use crate::{
+    AddToPlaylistArgs, CreatePlaylistArgs, HistoryArgs, RateArgs, RatePlaylistArgs, Rating,
+};
+use clap_mangen::roff::{bold, roman, Roff};
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use ytmapi_rs::query::AddPlaylistItemsQuery;
 use ytmapi_rs::query::AlbumsFilter;
 use ytmapi_rs::query::ArtistsFilter;
 use ytmapi_rs::query::CommunityPlaylistsFilter;
+use ytmapi_rs::query::CreatePlaylistQuery;
 use ytmapi_rs::query::EpisodesFilter;
 use ytmapi_rs::query::FeaturedPlaylistsFilter;
+use ytmapi_rs::query::GetAlbumQuery;
+use ytmapi_rs::query::GetHistoryQuery;
 use ytmapi_rs::query::GetLibraryArtistsQuery;
 use ytmapi_rs::query::GetLibraryPlaylistsQuery;
+use ytmapi_rs::query::GetLibraryPodcastsQuery;
+use ytmapi_rs::query::GetLibrarySubscriptionsQuery;
+use ytmapi_rs::query::GetPlaylistQuery;
 use ytmapi_rs::query::PlaylistsFilter;
 use ytmapi_rs::query::PodcastsFilter;
 use ytmapi_rs::query::ProfilesFilter;
+use ytmapi_rs::query::RatePlaylistQuery;
+use ytmapi_rs::query::RateSongQuery;
+use ytmapi_rs::query::RemoveHistoryItemsQuery;
 use ytmapi_rs::query::SearchQuery;
 use ytmapi_rs::query::SongsFilter;
 use ytmapi_rs::query::VideosFilter;
 use ytmapi_rs::{
-    common::YoutubeID,
+    auth::BrowserToken,
+    common::{AlbumID, LikeStatus, LyricsID, PlaylistID, YoutubeID},
     generate_oauth_code_and_url, generate_oauth_token,
-    query::{GetArtistQuery, GetSearchSuggestionsQuery},
-    ChannelID,
+    parse::{AccountInfo, HistoryItem},
+    query::{
+        lyrics::GetLyricsQuery, watch::GetWatchPlaylistQuery, GetArtistQuery,
+        GetSearchSuggestionsQuery,
+    },
+    ChannelID, VideoID, YtMusic,
 };
 
+/// Passed instead of an id to read ids from stdin, one per line.
+const STDIN_ARG: &str = "-";
+const MAX_CONCURRENT_BATCH_QUERIES: usize = 4;
+
 pub async fn handle_cli_command(cli: Cli, rt: RuntimeInfo) -> Result<()> {
     let config = rt.config;
+    let error_output = cli.get_error_output();
+    let fallback = rt
+        .api_key
+        .get_auth_type()
+        .filter(|auth_type| *auth_type != config.get_auth_type());
+    if let (Some(auth_type), ErrorOutput::Text) = (fallback, error_output) {
+        eprintln!(
+            "Configured {:?} credentials unavailable, using {auth_type:?} credentials instead",
+            config.get_auth_type()
+        );
+    }
     match cli {
         // TODO: Block this action using type system.
-        Cli { command: None, .. } => println!("Show source requires an associated API command"),
-        Cli {
-            command: Some(Commands::GetLibraryArtists),
-            show_source: true,
-        } => print_library_artists_json(&config).await?,
-        Cli {
-            command: Some(Commands::GetLibraryArtists),
-            show_source: false,
-        } => print_library_artists(&config).await?,
-        Cli {
-            command: Some(Commands::GetLibraryPlaylists),
-            show_source: true,
-        } => print_library_playlists_json(&config).await?,
-        Cli {
-            command: Some(Commands::GetLibraryPlaylists),
-            show_source: false,
-        } => print_library_playlists(&config).await?,
-        Cli {
-            command: Some(Commands::GetSearchSuggestions { query }),
-            show_source: false,
-        } => print_search_suggestions(&config, query).await?,
-        Cli {
-            command: Some(Commands::GetSearchSuggestions { query }),
-            show_source: true,
-        } => print_search_suggestions_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::GetArtist { channel_id }),
-            show_source: false,
-        } => print_artist(&config, channel_id).await?,
-        Cli {
-            command: Some(Commands::GetArtist { channel_id }),
-            show_source: true,
-        } => print_artist_json(&config, channel_id).await?,
-        Cli {
-            command: Some(Commands::SearchArtists { query }),
-            show_source: false,
-        } => search_artists(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchArtists { query }),
-            show_source: true,
-        } => search_artists_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchAlbums { query }),
-            show_source: false,
-        } => search_albums(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchAlbums { query }),
-            show_source: true,
-        } => search_albums_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchSongs { query }),
-            show_source: false,
-        } => search_songs(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchSongs { query }),
-            show_source: true,
-        } => search_songs_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchPlaylists { query }),
-            show_source: false,
-        } => search_playlists(&config, query).await?,
+        Cli { command: None, .. } => println!("Format requires an associated API command"),
         Cli {
-            command: Some(Commands::SearchPlaylists { query }),
-            show_source: true,
-        } => search_playlists_json(&config, query).await?,
+            command: Some(Commands::Play { id_or_link }),
+            ..
+        } => headless::play(&config, rt.api_key, id_or_link).await?,
         Cli {
-            command: Some(Commands::SearchEpisodes { query }),
-            show_source: false,
-        } => search_episodes(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchEpisodes { query }),
-            show_source: true,
-        } => search_episodes_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchPodcasts { query }),
-            show_source: false,
-        } => search_podcasts(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchPodcasts { query }),
-            show_source: true,
-        } => search_podcasts_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchCommunityPlaylists { query }),
-            show_source: false,
-        } => search_community_playlists(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchCommunityPlaylists { query }),
-            show_source: true,
-        } => search_community_playlists_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchFeaturedPlaylists { query }),
-            show_source: false,
-        } => search_featured_playlists(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchFeaturedPlaylists { query }),
-            show_source: true,
-        } => search_featured_playlists_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchProfiles { query }),
-            show_source: false,
-        } => search_profiles(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchProfiles { query }),
-            show_source: true,
-        } => search_profiles_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchVideos { query }),
-            show_source: false,
-        } => search_videos(&config, query).await?,
-        Cli {
-            command: Some(Commands::SearchVideos { query }),
-            show_source: true,
-        } => search_videos_json(&config, query).await?,
-        Cli {
-            command: Some(Commands::Search { query }),
-            show_source: false,
-        } => search(&config, query).await?,
-        Cli {
-            command: Some(Commands::Search { query }),
-            show_source: true,
-        } => search_json(&config, query).await?,
+            command: Some(command),
+            format,
+            ..
+        } => {
+            let api = get_api(&rt.api_key).await?;
+            match read_batch(&command, std::io::stdin().lock())? {
+                Some(batch) => run_batch(&api, batch, format, error_output).await?,
+                None => println!("{}", get_output(&api, command, format).await?),
+            }
+        }
     }
     Ok(())
 }
@@ -173,236 +120,937 @@ async fn get_oauth_token() -> Result<String> {
     Ok(serde_json::to_string_pretty(&token)?)
 }
 
-pub async fn print_artist(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config)
-        .await?
-        .get_artist(GetArtistQuery::new(ChannelID::from_raw(query)))
-        .await?;
-    println!("{:#?}", res);
+/// Print when the saved OAuth token expires, the scope it was granted and the account it
+/// belongs to, to help diagnose authentication errors.
+pub async fn print_oauth_status() -> Result<()> {
+    let token = load_oauth_file().await?;
+    match token.get_expiry().duration_since(SystemTime::now()) {
+        Ok(remaining) => println!("Expires in {} minutes", remaining.as_secs() / 60),
+        Err(_) => println!("Expired, use oauth refresh to refresh it"),
+    }
+    match token.get_scope() {
+        Some(scope) => println!("Scope: {scope}"),
+        None => println!("Scope: Unknown, use oauth refresh to record it"),
+    }
+    match YtMusic::from_oauth_token(token).get_account_info().await {
+        Ok(AccountInfo {
+            name,
+            channel_handle: Some(handle),
+        }) => println!("Account: {name} ({handle})"),
+        Ok(AccountInfo { name, .. }) => println!("Account: {name}"),
+        Err(e) => println!("Account: Unknown, error <{e}>"),
+    }
     Ok(())
 }
 
-pub async fn print_artist_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(GetArtistQuery::new(ChannelID::from_raw(query)))
-        .await?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
+/// Refresh the saved OAuth token, saving it over the old one.
+pub async fn refresh_oauth_token() -> Result<()> {
+    let token = load_oauth_file().await?;
+    let token = YtMusic::from_oauth_token(token).refresh_token().await?;
+    let path = get_oauth_file_path()?;
+    tokio::fs::write(&path, serde_json::to_string_pretty(&token)?).await?;
+    println!("Refreshed OAuth token, saved to {}", path.display());
     Ok(())
 }
 
-pub async fn print_search_suggestions(config: &Config, query: String) -> Result<()> {
-    // TODO: remove unwrap
-    let res = get_api(&config)
-        .await?
-        .get_search_suggestions(query)
-        .await?;
-    println!("{:#?}", res);
+/// Send a command to youtui running in another terminal, printing its reply.
+pub async fn send_remote_command(command: RemoteCommand) -> Result<()> {
+    println!("{}", remote::send_command(&command).await?);
     Ok(())
 }
 
-pub async fn print_search_suggestions_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(GetSearchSuggestionsQuery::from(query))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
+/// Print the config, data and cache directories, and which of the files read from the config
+/// and data directories exist.
+pub fn print_config_paths() -> Result<()> {
+    let config_dir = get_config_dir()?;
+    println!("Config directory: {}", config_dir.display());
+    let config_files = [
+        get_config_file_path()?,
+        get_cookie_file_path()?,
+        get_oauth_file_path()?,
+    ];
+    for path in config_files {
+        let status = if path.exists() { "present" } else { "missing" };
+        println!("  {}: {status}", path.display());
+    }
+    let data_dir = get_data_dir()?;
+    println!("Data directory: {}", data_dir.display());
+    match std::fs::read_dir(&data_dir) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                println!("  {}", entry.path().display());
+            }
+        }
+        Err(_) => println!("  missing"),
+    }
+    println!("Cache directory: {}", get_cache_dir()?.display());
     Ok(())
 }
 
-pub async fn print_library_playlists(config: &Config) -> Result<()> {
-    let res = get_api(&config).await?.get_library_playlists().await?;
-    println!("{:#?}", res);
+/// Write a config file with the default settings, each documented.
+pub async fn write_default_config(force: bool) -> Result<()> {
+    let path = get_config_file_path()?;
+    if path.exists() && !force {
+        return Err(Error::Other(format!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        )));
+    }
+    tokio::fs::create_dir_all(get_config_dir()?).await?;
+    tokio::fs::write(&path, default_config_file()).await?;
+    println!("Wrote default config to {}", path.display());
     Ok(())
 }
 
-pub async fn print_library_playlists_json(config: &Config) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(GetLibraryPlaylistsQuery)
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+/// Print any problems with the config file, e.g after editing it.
+pub async fn print_config_problems() -> Result<()> {
+    let path = get_config_file_path()?;
+    let contents = match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("No config file at {}, so defaults are used", path.display());
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let problems = check_config_file(&contents);
+    if problems.is_empty() {
+        println!("{} is valid", path.display());
+        return Ok(());
+    }
+    for problem in problems.iter() {
+        println!("{problem}");
+    }
+    Err(Error::Other(format!(
+        "Found {} problems in {}",
+        problems.len(),
+        path.display()
+    )))
 }
-pub async fn search(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+/// The result of one of the doctor's checks.
+enum Diagnosis {
+    Ok(String),
+    /// What is wrong, and how to fix it.
+    Problem {
+        problem: String,
+        fix: String,
+    },
 }
-pub async fn search_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+impl Diagnosis {
+    fn problem(problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Diagnosis::Problem {
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
 }
-pub async fn search_artists(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_artists(query).await?;
-    println!("{:#?}", res);
+
+/// Check for the problems most often behind youtui not working, printing how to fix each one
+/// found.
+pub async fn run_doctor() -> Result<()> {
+    let checks = [
+        ("Config directory", diagnose_config_dir()?),
+        ("Config file", diagnose_config_file().await?),
+        ("Credentials", diagnose_credentials().await?),
+        ("YouTube Music", diagnose_test_query().await?),
+        ("Audio output", diagnose_audio_output()),
+    ];
+    let mut problems = 0;
+    for (name, diagnosis) in checks {
+        match diagnosis {
+            Diagnosis::Ok(message) => println!("ok      {name}: {message}"),
+            Diagnosis::Problem { problem, fix } => {
+                println!("PROBLEM {name}: {problem}");
+                println!("        Fix: {fix}");
+                problems += 1;
+            }
+        }
+    }
+    if problems > 0 {
+        return Err(Error::Other(format!("Found {problems} problems")));
+    }
     Ok(())
 }
-pub async fn search_artists_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(ArtistsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+fn diagnose_config_dir() -> Result<Diagnosis> {
+    let config_dir = get_config_dir()?;
+    if config_dir.is_dir() {
+        return Ok(Diagnosis::Ok(config_dir.display().to_string()));
+    }
+    Ok(Diagnosis::problem(
+        format!("{} doesn't exist", config_dir.display()),
+        "Run youtui to create it and sign in, or create it with youtui config init",
+    ))
 }
-pub async fn search_albums(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_albums(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+async fn diagnose_config_file() -> Result<Diagnosis> {
+    let path = get_config_file_path()?;
+    let contents = match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Diagnosis::Ok("Not found, so defaults are used".into()))
+        }
+        Err(e) => {
+            return Ok(Diagnosis::problem(
+                format!("Unable to read {}: {e}", path.display()),
+                "Check the file's permissions",
+            ))
+        }
+    };
+    let problems = check_config_file(&contents);
+    if problems.is_empty() {
+        return Ok(Diagnosis::Ok(format!("{} is valid", path.display())));
+    }
+    Ok(Diagnosis::problem(
+        problems.join(", "),
+        format!(
+            "Edit {}, then run youtui config check, or start again with youtui config init --force",
+            path.display()
+        ),
+    ))
 }
-pub async fn search_albums_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(AlbumsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+/// Check the credentials for the configured auth type can be read, without using them.
+async fn diagnose_credentials() -> Result<Diagnosis> {
+    // An invalid config is already reported, so check the default auth type instead.
+    let config = Config::new().unwrap_or_default();
+    match config.get_auth_type() {
+        AuthType::Browser => {
+            let path = get_cookie_file_path()?;
+            let fix = format!(
+                "Run youtui and follow the setup, or copy the Cookie header from music.youtube.com \
+                 into {}, see the README",
+                path.display()
+            );
+            match load_cookie_file().await {
+                Ok(cookie) if cookie.contains("SAPISID=") => Ok(Diagnosis::Ok(format!(
+                    "Browser cookie in {}",
+                    path.display()
+                ))),
+                Ok(_) => Ok(Diagnosis::problem(
+                    format!("{} doesn't contain a SAPISID cookie", path.display()),
+                    fix,
+                )),
+                Err(e) => Ok(Diagnosis::problem(e.to_string(), fix)),
+            }
+        }
+        // The app can't use OAuth tokens yet, so a valid one is still a problem.
+        AuthType::OAuth => {
+            let fix = "Set auth_type = \"Browser\" in the config file, then run youtui to sign in \
+                       with a browser cookie";
+            let problem = match load_oauth_file().await {
+                Ok(token) if token.get_expiry() <= SystemTime::now() => {
+                    "The OAuth token has expired, and the app doesn't support OAuth yet".into()
+                }
+                Ok(_) => "The app doesn't support OAuth yet".into(),
+                Err(e) => e.to_string(),
+            };
+            Ok(Diagnosis::problem(problem, fix))
+        }
+    }
 }
-pub async fn search_songs(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_songs(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+/// Sign in and fetch the account name, the lightest authenticated query.
+async fn diagnose_test_query() -> Result<Diagnosis> {
+    let config = Config::new().unwrap_or_default();
+    let account_info = match config.get_auth_type() {
+        AuthType::Browser => match load_cookie_file().await {
+            Ok(cookie) => match YtMusic::from_cookie(cookie).await {
+                Ok(api) => api.get_account_info().await,
+                Err(e) => Err(e),
+            },
+            Err(_) => return Ok(Diagnosis::Ok("Skipped, as there are no credentials".into())),
+        },
+        AuthType::OAuth => match load_oauth_file().await {
+            Ok(token) => YtMusic::from_oauth_token(token).get_account_info().await,
+            Err(_) => return Ok(Diagnosis::Ok("Skipped, as there are no credentials".into())),
+        },
+    };
+    let e = match account_info {
+        Ok(AccountInfo { name, .. }) => return Ok(Diagnosis::Ok(format!("Signed in as {name}"))),
+        Err(e) => Error::from(e),
+    };
+    let fix = match e.get_kind() {
+        ErrorKind::Auth => "The credentials were rejected or have expired, sign in again",
+        ErrorKind::Network => "Check your internet connection, and any proxy or firewall",
+        _ => "Try again later, and report it if it keeps happening",
+    };
+    Ok(Diagnosis::problem(e.to_string(), fix))
 }
-pub async fn search_songs_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(SongsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+fn diagnose_audio_output() -> Diagnosis {
+    // Rodio can print to stderr when opening the device, as in the player.
+    let _gag = gag::Gag::stderr();
+    match rodio::OutputStream::try_default() {
+        Ok(_) => Diagnosis::Ok("Default output device is available".into()),
+        Err(e) => Diagnosis::problem(
+            e.to_string(),
+            "Check an output device is connected and enabled, and that the sound server (e.g \
+             PipeWire or PulseAudio) is running",
+        ),
+    }
 }
-pub async fn search_playlists(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_playlists(query).await?;
-    println!("{:#?}", res);
+
+/// Write a man page generated from the command line arguments, followed by the config file
+/// keys and the default keybinds.
+pub fn write_man_page(command: clap::Command, out: &mut impl Write) -> Result<()> {
+    clap_mangen::Man::new(command).render(out)?;
+    let mut roff = Roff::new();
+    roff.control("SH", ["CONFIGURATION"]).text([roman(
+        "Settings are read from config.toml in the youtui config directory. Each can also be \
+         set with an environment variable named after the key, e.g YOUTUI_TICK_RATE_MS, or \
+         for one run with --set, e.g --set tick_rate_ms=100. Edits to ascii_icons, \
+         high_contrast, album_art_position and status_format apply while youtui is running, \
+         other keys after restarting. Keys are:",
+    )]);
+    for key in get_config_file_keys() {
+        roff.control("TP", [])
+            .text([bold(key.name)])
+            .text([roman(format!(
+                "{} e.g {} = {}",
+                key.description, key.name, key.default
+            ))]);
+    }
+    roff.control("SH", ["FILES"]).text([roman(
+        "Credentials and config.toml are kept in the config directory, state such as \
+         playlists and logs in the data directory, and downloaded songs in the cache directory. \
+         These can be moved with the YOUTUI_CONFIG_DIR, YOUTUI_DATA_DIR and YOUTUI_CACHE_DIR \
+         environment variables. Run youtui config paths to print them.",
+    )]);
+    roff.control("SH", ["EXIT STATUS"]).text([roman(
+        "0 on success, 2 if the arguments are invalid, otherwise one of:",
+    )]);
+    for kind in ErrorKind::ALL {
+        roff.control("TP", [])
+            .text([bold(kind.exit_code().to_string())])
+            .text([roman(kind.description())]);
+    }
+    roff.control("SH", ["KEYBINDS"]).text([roman(
+        "Default keybinds, grouped by where in the app they are used. Press F1 in the app to \
+         list them.",
+    )]);
+    let mut entries = get_default_help_entries();
+    // Stable sort, so that keybinds keep their declared order within each context.
+    entries.sort_by(|a, b| a.context.cmp(&b.context));
+    let mut context = None;
+    for entry in entries {
+        if context.as_ref() != Some(&entry.context) {
+            roff.control("SS", [entry.context.as_str()]);
+            context = Some(entry.context);
+        }
+        roff.control("TP", [])
+            .text([bold(entry.keybinds)])
+            .text([roman(entry.description)]);
+    }
+    roff.to_writer(out)?;
     Ok(())
 }
-pub async fn search_playlists_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(PlaylistsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+/// The lyrics id of a song is only available from its watch playlist.
+async fn get_lyrics_id(api: &YtMusic<BrowserToken>, video_id: String) -> Result<LyricsID<'static>> {
+    let query = GetWatchPlaylistQuery::new_from_video_id(VideoID::from_raw(video_id));
+    let watch_playlist = api.get_watch_playlist(query).await?;
+    Ok(watch_playlist.lyrics_id)
 }
-pub async fn search_featured_playlists(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config)
-        .await?
-        .search_featured_playlists(query)
-        .await?;
-    println!("{:#?}", res);
-    Ok(())
+
+/// Get the playlist id from a link to a playlist, e.g
+/// "https://music.youtube.com/playlist?list=<id>". Anything else is assumed to be an id.
+fn playlist_id_from_link(playlist: &str) -> &str {
+    match playlist.split_once("list=") {
+        Some((_, params)) => params.split('&').next().unwrap_or_default(),
+        None => playlist,
+    }
 }
-pub async fn search_featured_playlists_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(FeaturedPlaylistsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+fn video_id_from_link(video_id: &str) -> Result<VideoID<'static>> {
+    VideoID::from_url_or_id(video_id)
+        .ok_or_else(|| Error::Other(format!("Invalid video id or link <{video_id}>")))
 }
-pub async fn search_community_playlists(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config)
-        .await?
-        .search_community_playlists(query)
+
+/// The playlist to rate, from a playlist or album id or link. Albums are rated using their
+/// audio playlist.
+async fn get_playlist_to_rate(
+    api: &YtMusic<BrowserToken>,
+    id_or_link: &str,
+) -> Result<PlaylistID<'static>> {
+    // E.g an album link, "https://music.youtube.com/browse/<id>".
+    let album_id = id_or_link
+        .rsplit('/')
+        .next()
+        .filter(|id| id.starts_with("MPREb"));
+    let Some(album_id) = album_id else {
+        return Ok(PlaylistID::from_raw(
+            playlist_id_from_link(id_or_link).to_string(),
+        ));
+    };
+    let album = api
+        .get_album(GetAlbumQuery::new(AlbumID::from_raw(album_id.to_string())))
         .await?;
-    println!("{:#?}", res);
-    Ok(())
+    album
+        .audio_playlist_id
+        .map(PlaylistID::from_raw)
+        .ok_or_else(|| Error::Other(format!("Album {album_id} has no playlist to rate")))
+}
+
+fn like_status(rating: Rating) -> LikeStatus {
+    match rating {
+        Rating::Like => LikeStatus::Liked,
+        Rating::Dislike => LikeStatus::Disliked,
+        Rating::None => LikeStatus::Indifferent,
+    }
 }
-pub async fn search_community_playlists_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
+
+/// Each time the song, from a video id or link, appears in history and can be removed.
+async fn get_history_to_remove(
+    api: &YtMusic<BrowserToken>,
+    video_id: &str,
+) -> Result<Vec<HistoryItem>> {
+    let video_id = video_id_from_link(video_id)?;
+    let items: Vec<_> = api
+        .get_history()
         .await?
-        .json_query(SearchQuery::new(query).with_filter(CommunityPlaylistsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+        .into_iter()
+        .filter(|item| item.feedback_token.is_some())
+        .filter(|item| *item.song.song.get_video_id() == video_id)
+        .collect();
+    if items.is_empty() {
+        return Err(Error::Other(format!(
+            "Song <{}> isn't in history",
+            video_id.get_raw()
+        )));
+    }
+    Ok(items)
 }
-pub async fn search_episodes(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_episodes(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+/// Read the video ids or links in a file, or stdin if the file is "-".
+fn read_video_ids(ids_file: &Path) -> Result<Vec<VideoID<'static>>> {
+    if ids_file.as_os_str() == STDIN_ARG {
+        parse_video_ids(std::io::stdin().lock())
+    } else {
+        parse_video_ids(std::io::BufReader::new(std::fs::File::open(ids_file)?))
+    }
 }
-pub async fn search_episodes_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(EpisodesFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+/// Parse a video id or link per line. Blank lines, and lines starting with # are skipped.
+fn parse_video_ids(input: impl BufRead) -> Result<Vec<VideoID<'static>>> {
+    let mut video_ids = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let video_id = VideoID::from_url_or_id(line).ok_or_else(|| {
+            Error::Other(format!(
+                "Invalid video id or link <{line}> on line {}",
+                idx + 1
+            ))
+        })?;
+        video_ids.push(video_id);
+    }
+    Ok(video_ids)
 }
-pub async fn search_podcasts(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_podcasts(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+/// Read an id per line from input if the command's id is "-", giving the command for each
+/// id. Returns None if the command isn't a batch.
+fn read_batch(command: &Commands, input: impl BufRead) -> Result<Option<Vec<(String, Commands)>>> {
+    let with_id: Box<dyn Fn(String) -> Commands> = match command {
+        Commands::GetArtist { channel_id } if channel_id == STDIN_ARG => {
+            Box::new(|channel_id| Commands::GetArtist { channel_id })
+        }
+        Commands::GetAlbum { browse_id } if browse_id == STDIN_ARG => {
+            Box::new(|browse_id| Commands::GetAlbum { browse_id })
+        }
+        Commands::GetPlaylist { playlist_id } if playlist_id == STDIN_ARG => {
+            Box::new(|playlist_id| Commands::GetPlaylist { playlist_id })
+        }
+        Commands::GetLyrics { video_id } if video_id == STDIN_ARG => {
+            Box::new(|video_id| Commands::GetLyrics { video_id })
+        }
+        Commands::Rate(RateArgs { video_id, rating }) if video_id == STDIN_ARG => {
+            let rating = *rating;
+            Box::new(move |video_id| Commands::Rate(RateArgs { video_id, rating }))
+        }
+        Commands::RatePlaylist(RatePlaylistArgs {
+            playlist_id,
+            rating,
+        }) if playlist_id == STDIN_ARG => {
+            let rating = *rating;
+            Box::new(move |playlist_id| {
+                Commands::RatePlaylist(RatePlaylistArgs {
+                    playlist_id,
+                    rating,
+                })
+            })
+        }
+        _ => return Ok(None),
+    };
+    let mut batch = Vec::new();
+    for line in input.lines() {
+        let id = line?.trim().to_string();
+        if !id.is_empty() {
+            batch.push((id.clone(), with_id(id)));
+        }
+    }
+    Ok(Some(batch))
 }
-pub async fn search_podcasts_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(PodcastsFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
+
+/// Run a command for each id read from stdin, several at a time. Outputs are printed in the
+/// order the ids were read, and an id that fails is reported without stopping the others.
+async fn run_batch(
+    api: &YtMusic<BrowserToken>,
+    batch: Vec<(String, Commands)>,
+    format: OutputFormat,
+    error_output: ErrorOutput,
+) -> Result<()> {
+    let total = batch.len();
+    let mut outputs = futures::stream::iter(batch)
+        .map(|(id, command)| async move { (id, get_output(api, command, format).await) })
+        .buffered(MAX_CONCURRENT_BATCH_QUERIES);
+    let mut failed = 0;
+    while let Some((id, output)) = outputs.next().await {
+        match output {
+            Ok(output) => println!("{output}"),
+            Err(e) => {
+                match error_output {
+                    ErrorOutput::Text => eprintln!("Error <{e}> processing {id}"),
+                    ErrorOutput::Json => {
+                        let mut json = e.to_json();
+                        json["id"] = id.into();
+                        eprintln!("{json}");
+                    }
+                    ErrorOutput::Quiet => (),
+                }
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(Error::Other(format!("{failed} of {total} ids failed")));
+    }
     Ok(())
 }
-pub async fn search_profiles(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_profiles(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+async fn get_output(
+    api: &YtMusic<BrowserToken>,
+    command: Commands,
+    format: OutputFormat,
+) -> Result<String> {
+    match format {
+        OutputFormat::Source => get_source(api, command).await,
+        format => get_processed(api, command, format).await,
+    }
 }
-pub async fn search_profiles_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(ProfilesFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+/// The source json from YouTube Music's API for the command.
+async fn get_source(api: &YtMusic<BrowserToken>, command: Commands) -> Result<String> {
+    let json = match command {
+        Commands::GetSearchSuggestions { query } => {
+            api.json_query(GetSearchSuggestionsQuery::from(query))
+                .await?
+        }
+        Commands::GetArtist { channel_id } => {
+            api.json_query(GetArtistQuery::new(ChannelID::from_raw(channel_id)))
+                .await?
+        }
+        Commands::GetAlbum { browse_id } => {
+            api.json_query(GetAlbumQuery::new(AlbumID::from_raw(browse_id)))
+                .await?
+        }
+        Commands::GetPlaylist { playlist_id } => {
+            let id = PlaylistID::from_raw(playlist_id_from_link(&playlist_id).to_string());
+            api.json_query(GetPlaylistQuery::new(id)).await?
+        }
+        Commands::GetLyrics { video_id } => {
+            let lyrics_id = get_lyrics_id(api, video_id).await?;
+            api.json_query(GetLyricsQuery::new(lyrics_id)).await?
+        }
+        Commands::Play { .. } => unreachable!("Play is handled by handle_cli_command"),
+        Commands::CreatePlaylist(CreatePlaylistArgs { title, ids_file }) => {
+            let query = CreatePlaylistQuery::new(title).with_video_ids(read_video_ids(&ids_file)?);
+            api.json_query(query).await?
+        }
+        Commands::AddToPlaylist(AddToPlaylistArgs {
+            playlist_id,
+            ids_file,
+        }) => {
+            let id = PlaylistID::from_raw(playlist_id_from_link(&playlist_id).to_string());
+            let query = AddPlaylistItemsQuery::new(id, read_video_ids(&ids_file)?);
+            api.json_query(query).await?
+        }
+        Commands::Rate(RateArgs { video_id, rating }) => {
+            let query = RateSongQuery::new(video_id_from_link(&video_id)?, like_status(rating));
+            api.json_query(query).await?
+        }
+        Commands::RatePlaylist(RatePlaylistArgs {
+            playlist_id,
+            rating,
+        }) => {
+            let id = get_playlist_to_rate(api, &playlist_id).await?;
+            api.json_query(RatePlaylistQuery::new(id, like_status(rating)))
+                .await?
+        }
+        Commands::GetLibraryPlaylists => api.json_query(GetLibraryPlaylistsQuery).await?,
+        // TODO: Allow sorting
+        Commands::GetLibraryArtists => api.json_query(GetLibraryArtistsQuery::default()).await?,
+        Commands::GetLibrarySubscriptions => api.json_query(GetLibrarySubscriptionsQuery).await?,
+        Commands::GetLibraryPodcasts => api.json_query(GetLibraryPodcastsQuery).await?,
+        Commands::History(HistoryArgs { remove: None, .. }) => {
+            api.json_query(GetHistoryQuery).await?
+        }
+        Commands::History(HistoryArgs {
+            remove: Some(_), ..
+        }) => {
+            return Err(Error::Other(
+                "History can't be removed with the source format, use --dry-run to list the \
+                 songs that would be removed"
+                    .to_string(),
+            ))
+        }
+        Commands::Search { query } => api.json_query(SearchQuery::new(query)).await?,
+        Commands::SearchArtists { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(ArtistsFilter))
+                .await?
+        }
+        Commands::SearchAlbums { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(AlbumsFilter))
+                .await?
+        }
+        Commands::SearchSongs { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(SongsFilter))
+                .await?
+        }
+        Commands::SearchPlaylists { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(PlaylistsFilter))
+                .await?
+        }
+        Commands::SearchCommunityPlaylists { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(CommunityPlaylistsFilter))
+                .await?
+        }
+        Commands::SearchFeaturedPlaylists { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(FeaturedPlaylistsFilter))
+                .await?
+        }
+        Commands::SearchVideos { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(VideosFilter))
+                .await?
+        }
+        Commands::SearchEpisodes { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(EpisodesFilter))
+                .await?
+        }
+        Commands::SearchProfiles { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(ProfilesFilter))
+                .await?
+        }
+        Commands::SearchPodcasts { query } => {
+            api.json_query(SearchQuery::new(query).with_filter(PodcastsFilter))
+                .await?
+        }
+    };
+    let json: Value = serde_json::from_str(json.as_ref())?;
+    Ok(serde_json::to_string_pretty(&json)?)
 }
-pub async fn search_videos(config: &Config, query: String) -> Result<()> {
-    let res = get_api(&config).await?.search_videos(query).await?;
-    println!("{:#?}", res);
-    Ok(())
+
+/// The processed result of the command in the chosen format.
+async fn get_processed(
+    api: &YtMusic<BrowserToken>,
+    command: Commands,
+    format: OutputFormat,
+) -> Result<String> {
+    match command {
+        Commands::GetSearchSuggestions { query } => {
+            format_processed(api.get_search_suggestions(query).await?, format)
+        }
+        Commands::GetArtist { channel_id } => {
+            let query = GetArtistQuery::new(ChannelID::from_raw(channel_id));
+            format_processed(api.get_artist(query).await?, format)
+        }
+        Commands::GetAlbum { browse_id } => {
+            let query = GetAlbumQuery::new(AlbumID::from_raw(browse_id));
+            format_processed(api.get_album(query).await?, format)
+        }
+        Commands::GetPlaylist { playlist_id } => {
+            let id = PlaylistID::from_raw(playlist_id_from_link(&playlist_id).to_string());
+            format_processed(api.get_playlist(GetPlaylistQuery::new(id)).await?, format)
+        }
+        Commands::GetLyrics { video_id } => {
+            let lyrics_id = get_lyrics_id(api, video_id).await?;
+            format_processed(
+                api.get_lyrics(GetLyricsQuery::new(lyrics_id)).await?,
+                format,
+            )
+        }
+        Commands::Play { .. } => unreachable!("Play is handled by handle_cli_command"),
+        Commands::CreatePlaylist(CreatePlaylistArgs { title, ids_file }) => {
+            let query = CreatePlaylistQuery::new(title).with_video_ids(read_video_ids(&ids_file)?);
+            format_processed(api.create_playlist(query).await?, format)
+        }
+        // The playlist id is output, as adding songs returns nothing.
+        Commands::AddToPlaylist(AddToPlaylistArgs {
+            playlist_id,
+            ids_file,
+        }) => {
+            let id = PlaylistID::from_raw(playlist_id_from_link(&playlist_id).to_string());
+            let query = AddPlaylistItemsQuery::new(id.clone(), read_video_ids(&ids_file)?);
+            api.add_playlist_items(query).await?;
+            format_processed(id, format)
+        }
+        // The id is output, as rating returns nothing.
+        Commands::Rate(RateArgs { video_id, rating }) => {
+            let id = video_id_from_link(&video_id)?;
+            api.rate_song(RateSongQuery::new(id.clone(), like_status(rating)))
+                .await?;
+            format_processed(id, format)
+        }
+        Commands::RatePlaylist(RatePlaylistArgs {
+            playlist_id,
+            rating,
+        }) => {
+            let id = get_playlist_to_rate(api, &playlist_id).await?;
+            api.rate_playlist(RatePlaylistQuery::new(id.clone(), like_status(rating)))
+                .await?;
+            format_processed(id, format)
+        }
+        Commands::GetLibraryPlaylists => {
+            format_processed(api.get_library_playlists().await?, format)
+        }
+        // TODO: Allow sorting
+        Commands::GetLibraryArtists => format_processed(
+            api.get_library_artists(GetLibraryArtistsQuery::default())
+                .await?,
+            format,
+        ),
+        Commands::GetLibrarySubscriptions => {
+            format_processed(api.get_library_subscriptions().await?, format)
+        }
+        Commands::GetLibraryPodcasts => format_processed(api.get_library_podcasts().await?, format),
+        Commands::History(HistoryArgs { remove: None, .. }) => {
+            format_processed(api.get_history().await?, format)
+        }
+        // The removed songs are output, as removing returns nothing.
+        Commands::History(HistoryArgs {
+            remove: Some(video_id),
+            dry_run,
+        }) => {
+            let items = get_history_to_remove(api, &video_id).await?;
+            if !dry_run {
+                let tokens = items
+                    .iter()
+                    .filter_map(|item| item.feedback_token.as_deref());
+                api.remove_history_items(RemoveHistoryItemsQuery::new(tokens))
+                    .await?;
+            }
+            format_processed(items, format)
+        }
+        Commands::Search { query } => format_processed(api.search(query).await?, format),
+        Commands::SearchArtists { query } => {
+            format_processed(api.search_artists(query).await?, format)
+        }
+        Commands::SearchAlbums { query } => {
+            format_processed(api.search_albums(query).await?, format)
+        }
+        Commands::SearchSongs { query } => format_processed(api.search_songs(query).await?, format),
+        Commands::SearchPlaylists { query } => {
+            format_processed(api.search_playlists(query).await?, format)
+        }
+        Commands::SearchCommunityPlaylists { query } => {
+            format_processed(api.search_community_playlists(query).await?, format)
+        }
+        Commands::SearchFeaturedPlaylists { query } => {
+            format_processed(api.search_featured_playlists(query).await?, format)
+        }
+        Commands::SearchVideos { query } => {
+            format_processed(api.search_videos(query).await?, format)
+        }
+        Commands::SearchEpisodes { query } => {
+            format_processed(api.search_episodes(query).await?, format)
+        }
+        Commands::SearchProfiles { query } => {
+            format_processed(api.search_profiles(query).await?, format)
+        }
+        Commands::SearchPodcasts { query } => {
+            format_processed(api.search_podcasts(query).await?, format)
+        }
+    }
 }
-pub async fn search_videos_json(config: &Config, query: String) -> Result<()> {
-    let json = get_api(&config)
-        .await?
-        .json_query(SearchQuery::new(query).with_filter(VideosFilter))
-        .await?;
-    let json: serde_json::Value = serde_json::from_str(json.as_ref())?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+
+fn format_processed<T: Serialize + Debug>(output: T, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Plain => Ok(format!("{:#?}", output)),
+        format => format_output(serde_json::to_value(output)?, format),
+    }
 }
 
-pub async fn print_library_artists(config: &Config) -> Result<()> {
-    // TODO: Allow sorting
-    let res = get_api(&config)
-        .await?
-        .get_library_artists(GetLibraryArtistsQuery::default())
-        .await?;
-    println!("{:#?}", res);
-    Ok(())
+/// Format processed output as json, for other programs to read. For json lines and tsv, a
+/// list is output as one line per item, and anything else as a single line.
+fn format_output(output: Value, format: OutputFormat) -> Result<String> {
+    let formatted = match format {
+        OutputFormat::Jsonl => list_items(output)
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .join("\n"),
+        OutputFormat::Tsv => {
+            let items = list_items(output);
+            // Columns are taken from the first item, as items in a list share a type.
+            let columns: Vec<String> = match items.first() {
+                Some(Value::Object(fields)) => fields.keys().cloned().collect(),
+                _ => vec!["value".to_string()],
+            };
+            let rows = items.iter().map(|item| {
+                columns
+                    .iter()
+                    .map(|column| match item {
+                        Value::Object(fields) => {
+                            fields.get(column).map(tsv_field).unwrap_or_default()
+                        }
+                        item => tsv_field(item),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            });
+            std::iter::once(columns.join("\t"))
+                .chain(rows)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        // Plain and source output aren't formatted from json, so fall back to json.
+        OutputFormat::Json | OutputFormat::Plain | OutputFormat::Source => {
+            serde_json::to_string_pretty(&output)?
+        }
+    };
+    Ok(formatted)
 }
 
-pub async fn print_library_artists_json(config: &Config) -> Result<()> {
-    // TODO: Allow sorting
-    let json = get_api(&config)
-        .await?
-        .json_query(GetLibraryArtistsQuery::default())
-        .await?;
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+fn list_items(output: Value) -> Vec<Value> {
+    match output {
+        Value::Array(items) => items,
+        output => vec![output],
+    }
+}
+
+/// Nested values are written as json. Tabs and newlines would break the row, so are replaced.
+fn tsv_field(value: &Value) -> String {
+    let field = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_output, parse_video_ids, playlist_id_from_link, read_batch, write_man_page,
+    };
+    use crate::{Arguments, Commands, OutputFormat, RateArgs, Rating};
+    use clap::{CommandFactory, Parser};
+    use serde_json::json;
+    use ytmapi_rs::common::YoutubeID;
+    use ytmapi_rs::VideoID;
+
+    #[test]
+    fn test_parse_video_ids() {
+        let input = "lYBUbBu4W08\n\n# A comment\nhttps://music.youtube.com/watch?v=dQw4w9WgXcQ\n";
+        assert_eq!(
+            parse_video_ids(input.as_bytes()).unwrap(),
+            vec![
+                VideoID::from_raw("lYBUbBu4W08"),
+                VideoID::from_raw("dQw4w9WgXcQ")
+            ]
+        );
+        let error = parse_video_ids("lYBUbBu4W08\nnot an id".as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+    }
+    #[test]
+    fn test_playlist_id_from_link() {
+        assert_eq!(
+            playlist_id_from_link("https://music.youtube.com/playlist?list=PLabc&si=xyz"),
+            "PLabc"
+        );
+        assert_eq!(playlist_id_from_link("PLabc"), "PLabc");
+    }
+    #[test]
+    fn test_format_output() {
+        let output = json!([
+            {"title": "Song\tA", "artists": ["Artist"], "year": null},
+            {"title": "Song B", "artists": [], "year": 2020},
+        ]);
+        assert_eq!(
+            format_output(output.clone(), OutputFormat::Tsv).unwrap(),
+            "artists\ttitle\tyear\n[\"Artist\"]\tSong A\t\n[]\tSong B\t2020"
+        );
+        assert_eq!(
+            format_output(output, OutputFormat::Jsonl).unwrap(),
+            "{\"artists\":[\"Artist\"],\"title\":\"Song\\tA\",\"year\":null}\n\
+             {\"artists\":[],\"title\":\"Song B\",\"year\":2020}"
+        );
+        assert_eq!(
+            format_output(json!("suggestion"), OutputFormat::Tsv).unwrap(),
+            "value\nsuggestion"
+        );
+    }
+    #[test]
+    fn test_read_batch() {
+        let input = "MPREb_a\n\n  MPREb_b \n".as_bytes();
+        let batch = read_batch(
+            &Commands::GetAlbum {
+                browse_id: "-".into(),
+            },
+            input,
+        )
+        .unwrap()
+        .unwrap();
+        let ids: Vec<_> = batch.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["MPREb_a", "MPREb_b"]);
+        assert!(matches!(
+            &batch[1].1,
+            Commands::GetAlbum { browse_id } if browse_id == "MPREb_b"
+        ));
+        let single = Commands::GetAlbum {
+            browse_id: "MPREb_a".into(),
+        };
+        assert!(read_batch(&single, "".as_bytes()).unwrap().is_none());
+        // The rating is kept for each id.
+        let rate = Commands::Rate(RateArgs {
+            video_id: "-".into(),
+            rating: Rating::Dislike,
+        });
+        let batch = read_batch(&rate, "lYBUbBu4W08\n".as_bytes())
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            &batch[0].1,
+            Commands::Rate(RateArgs { video_id, rating: Rating::Dislike }) if video_id == "lYBUbBu4W08"
+        ));
+    }
+    #[test]
+    fn test_history_dry_run_requires_remove() {
+        assert!(Arguments::try_parse_from(["youtui", "history", "--dry-run"]).is_err());
+        assert!(Arguments::try_parse_from([
+            "youtui",
+            "history",
+            "--remove",
+            "lYBUbBu4W08",
+            "--dry-run"
+        ])
+        .is_ok());
+    }
+    #[test]
+    fn test_write_man_page() {
+        let mut page = Vec::new();
+        write_man_page(Arguments::command(), &mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+        assert!(page.contains(r"get\-album"));
+        assert!(page.contains(".SH CONFIGURATION"));
+        assert!(page.contains("music_cache_size_mb"));
+        assert!(page.contains("EXIT STATUS"));
+        assert!(page.contains("YOUTUI_CACHE_DIR"));
+        assert!(page.contains(".SH KEYBINDS"));
+    }
 }
@@ -1,9 +1,37 @@
+use crate::app::keycommand::Keybind;
+use crate::error::Error;
 use crate::get_config_dir;
+use crate::get_data_dir;
+use crate::i18n::Locale;
 use crate::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 use ytmapi_rs::auth::OAuthToken;
 
 const CONFIG_FILE_NAME: &str = "config.toml";
+const DEFAULT_TICK_RATE_MS: u64 = 200;
+const MIN_TICK_RATE_MS: u64 = 20;
+const DEFAULT_MUSIC_CACHE_SIZE_MB: u64 = 500;
+const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 3;
+const DEFAULT_EXPORT_TEMPLATE: &str = "{artist}/{album}/{track} - {title}";
+/// Only this device can connect to the web remote and audio stream unless another address is
+/// set.
+const DEFAULT_LISTEN_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const DEFAULT_WEB_REMOTE_PORT: u16 = 8642;
+const DEFAULT_AUDIO_STREAM_PORT: u16 = 8643;
+/// Settings can also be given as environment variables, e.g YOUTUI_TICK_RATE_MS.
+const ENV_VAR_PREFIX: &str = "YOUTUI_";
+
+/// Keys whose values are left out of crash reports.
+const SECRET_CONFIG_KEYS: &[&str] = &["web_remote_token"];
+
+/// Settings given on the command line, which take priority over the environment and the
+/// config file.
+static CLI_OVERRIDES: OnceLock<Vec<ConfigOverride>> = OnceLock::new();
 
 #[derive(Serialize, Deserialize)]
 pub enum ApiKey {
@@ -12,6 +40,19 @@ pub enum ApiKey {
     // Currently OAuthToken is public but not BrowserToken
     OAuthToken(OAuthToken),
     BrowserToken(String),
+    /// Answer queries with the test fixtures instead of signing in, see --demo.
+    Demo,
+}
+
+impl ApiKey {
+    /// The kind of credentials, or None in demo mode.
+    pub fn get_auth_type(&self) -> Option<AuthType> {
+        match self {
+            ApiKey::OAuthToken(_) => Some(AuthType::OAuth),
+            ApiKey::BrowserToken(_) => Some(AuthType::Browser),
+            ApiKey::Demo => None,
+        }
+    }
 }
 
 impl std::fmt::Debug for ApiKey {
@@ -19,6 +60,7 @@ impl std::fmt::Debug for ApiKey {
         match self {
             ApiKey::OAuthToken(_) => write!(f, "OAuthToken(/* private fields */"),
             ApiKey::BrowserToken(_) => write!(f, "BrowserToken(/* private fields */"),
+            ApiKey::Demo => write!(f, "Demo"),
         }
     }
 }
@@ -26,25 +68,1094 @@ impl std::fmt::Debug for ApiKey {
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Config {
     auth_type: AuthType,
+    #[serde(default)]
+    album_art_position: AlbumArtPosition,
+    #[serde(default)]
+    pane_sizes: PaneSizes,
+    /// Format of the now playing line, e.g "{state} {artist} - {title} [{elapsed}/{duration}]".
+    #[serde(default)]
+    status_format: Option<String>,
+    /// Use plain ASCII icons, for fonts without Nerd Font glyphs.
+    #[serde(default)]
+    ascii_icons: bool,
+    /// Use a high contrast colour theme.
+    #[serde(default)]
+    high_contrast: bool,
+    /// Language of the user interface.
+    #[serde(default)]
+    locale: Locale,
+    /// Milliseconds between ticks, which update playback progress and animations.
+    /// A longer tick rate uses less CPU.
+    #[serde(default)]
+    tick_rate_ms: Option<u64>,
+    /// Maximum size of the downloaded songs cache in megabytes, or 0 to disable the cache.
+    #[serde(default)]
+    music_cache_size_mb: Option<u64>,
+    /// Number of songs that can download at once. Further downloads wait in a queue.
+    #[serde(default)]
+    max_concurrent_downloads: Option<usize>,
+    /// Maximum rate of all downloads combined in kilobytes per second, or 0 for no limit.
+    #[serde(default)]
+    max_download_rate_kb: Option<u64>,
+    /// Maximum rate of each song download in kilobytes per second, or 0 for no limit.
+    #[serde(default)]
+    max_song_download_rate_kb: Option<u64>,
+    /// Quality of downloaded songs. Higher quality uses more bandwidth.
+    #[serde(default)]
+    audio_quality: AudioQuality,
+    /// Itags of audio formats to download in order of preference, e.g [251, 140]. If none are
+    /// available, the format is chosen by audio_quality.
+    #[serde(default)]
+    preferred_audio_itags: Vec<u64>,
+    /// Whether songs play on the local audio device, are streamed over the network, or both.
+    #[serde(default)]
+    audio_output: AudioOutput,
+    /// Address the audio stream is served on, e.g 0.0.0.0 to allow other devices on the
+    /// network.
+    #[serde(default)]
+    audio_stream_address: Option<IpAddr>,
+    /// Port the audio stream is served on.
+    #[serde(default)]
+    audio_stream_port: Option<u16>,
+    /// Directory that songs are exported to. Defaults to the system music directory.
+    #[serde(default)]
+    export_dir: Option<PathBuf>,
+    /// Path of exported songs within the export directory, without the extension.
+    /// Fields are {artist}, {album}, {title}, {year} and {track}.
+    #[serde(default)]
+    export_template: Option<String>,
+    /// Token that requests to the web remote must include. The web remote is off unless this
+    /// is set.
+    #[serde(default)]
+    web_remote_token: Option<String>,
+    /// Address the web remote listens on, e.g 0.0.0.0 to allow other devices on the network.
+    #[serde(default)]
+    web_remote_address: Option<IpAddr>,
+    /// Port the web remote listens on.
+    #[serde(default)]
+    web_remote_port: Option<u16>,
+    /// Most verbose level of messages written to the log file.
+    #[serde(default)]
+    log_level: LogLevel,
+    /// Command run through the shell for each player event, e.g a track starting, with the
+    /// event and song given in environment variables.
+    #[serde(default)]
+    event_hook_command: Option<String>,
+    /// Seconds that a task, such as an API query or song download, may run before it's stopped
+    /// and reported as timed out, or 0 for no limit.
+    #[serde(default)]
+    task_timeout_secs: Option<u64>,
+    /// Allow the queue to be cast to Google Cast devices, which are searched for on the local
+    /// network. Experimental, as it hasn't been tested with every device.
+    #[serde(default)]
+    experimental_cast: bool,
+    /// Keys for Actions, by the Action's description, e.g { "Next Song" = "C-n" }. These
+    /// replace the Action's default keys.
+    #[serde(default)]
+    keybinds: BTreeMap<String, String>,
+    /// Problems found in the config file, which didn't stop it loading.
+    #[serde(skip)]
+    warnings: Vec<String>,
 }
 
-#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+/// A setting given outside the config file, e.g "tick_rate_ms=100".
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigOverride {
+    key: String,
+    value: toml::Value,
+}
+
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AuthType {
+    #[serde(alias = "oauth")]
     OAuth,
     #[default]
+    #[serde(alias = "browser")]
     Browser,
 }
 
+/// Corner of the main window that the current track's album art is drawn in.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AlbumArtPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Hidden,
+}
+
+/// Bitrate of the audio format to download, from those available for a song.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AudioQuality {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+/// Where songs are played. The network stream can be played on another machine, e.g by a
+/// multiroom audio server.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AudioOutput {
+    #[default]
+    Local,
+    Network,
+    Both,
+}
+
+/// Most verbose level of messages written to the log file, or Off to not write one.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn to_level(self) -> Option<tracing::Level> {
+        match self {
+            LogLevel::Off => None,
+            LogLevel::Error => Some(tracing::Level::ERROR),
+            LogLevel::Warn => Some(tracing::Level::WARN),
+            LogLevel::Info => Some(tracing::Level::INFO),
+            LogLevel::Debug => Some(tracing::Level::DEBUG),
+            LogLevel::Trace => Some(tracing::Level::TRACE),
+        }
+    }
+}
+
+/// How to choose the audio format to download for a song.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct AudioFormatPreference {
+    pub quality: AudioQuality,
+    pub itags: Vec<u64>,
+}
+
+/// Maximum rates that songs are downloaded at in bytes per second, or None for no limit.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct DownloadRateLimit {
+    /// Shared between all downloads.
+    pub total: Option<u64>,
+    pub per_song: Option<u64>,
+}
+
+/// Where songs are played, and where the network stream is served if it's used.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OutputSettings {
+    pub output: AudioOutput,
+    pub stream_address: IpAddr,
+    pub stream_port: u16,
+}
+
+/// Where the web remote listens, and the token that its requests must include.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebRemoteSettings {
+    pub address: IpAddr,
+    pub port: u16,
+    pub token: String,
+}
+
+/// A value that can be displayed in the status line.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StatusField {
+    State,
+    Artist,
+    Title,
+    Album,
+    Elapsed,
+    Duration,
+    Volume,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatusSegment {
+    Text(String),
+    Field(StatusField),
+}
+
+/// Settings that can be changed without restarting, by editing the config file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiveSettings {
+    pub ascii_icons: bool,
+    pub high_contrast: bool,
+    pub album_art_position: AlbumArtPosition,
+    pub status_format: Option<StatusFormat>,
+    /// Keys for Actions, by the Action's description in English.
+    pub keybinds: Vec<(String, Keybind)>,
+    /// Applied from the next song played.
+    pub output_settings: OutputSettings,
+}
+
+/// A status line format string, parsed into the segments to render.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusFormat(pub Vec<StatusSegment>);
+
+impl StatusField {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "state" => Some(Self::State),
+            "artist" => Some(Self::Artist),
+            "title" => Some(Self::Title),
+            "album" => Some(Self::Album),
+            "elapsed" => Some(Self::Elapsed),
+            "duration" => Some(Self::Duration),
+            "volume" => Some(Self::Volume),
+            _ => None,
+        }
+    }
+}
+
+impl StatusFormat {
+    /// Parse a format string, where fields are written in braces e.g "{title}".
+    /// Literal braces are written twice, e.g "{{".
+    pub fn parse(format: &str) -> std::result::Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err("Unmatched <{> in status format".to_string()),
+                        }
+                    }
+                    let field = StatusField::from_name(&name)
+                        .ok_or_else(|| format!("Unknown status field <{name}>"))?;
+                    if !text.is_empty() {
+                        segments.push(StatusSegment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(StatusSegment::Field(field));
+                }
+                '}' => return Err("Unmatched <}> in status format".to_string()),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(StatusSegment::Text(text));
+        }
+        Ok(Self(segments))
+    }
+}
+
+const MIN_ARTIST_LIST_WIDTH: u16 = 10;
+const MAX_ARTIST_LIST_WIDTH: u16 = 100;
+const ARTIST_LIST_WIDTH_STEP: i16 = 2;
+const MIN_SPLIT_PERCENTAGE: u16 = 20;
+const MAX_SPLIT_PERCENTAGE: u16 = 80;
+const SPLIT_PERCENTAGE_STEP: i16 = 5;
+
+/// Sizes of resizable panes, changed by keybinds and saved back to the config file.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaneSizes {
+    /// Maximum width of the browser's artist list column.
+    pub artist_list_width: u16,
+    /// Percentage of the width given to the browser in the split layout.
+    pub split_percentage: u16,
+}
+
+impl Default for PaneSizes {
+    fn default() -> Self {
+        Self {
+            artist_list_width: 30,
+            split_percentage: 60,
+        }
+    }
+}
+
+impl PaneSizes {
+    /// Grow the artist list column by a step, or shrink it if grow is false.
+    pub fn resize_artist_list(&mut self, grow: bool) {
+        let step = if grow {
+            ARTIST_LIST_WIDTH_STEP
+        } else {
+            -ARTIST_LIST_WIDTH_STEP
+        };
+        self.artist_list_width = self
+            .artist_list_width
+            .saturating_add_signed(step)
+            .clamp(MIN_ARTIST_LIST_WIDTH, MAX_ARTIST_LIST_WIDTH);
+    }
+    /// Grow the browser side of the split layout by a step, or shrink it if grow is false.
+    pub fn resize_split(&mut self, grow: bool) {
+        let step = if grow {
+            SPLIT_PERCENTAGE_STEP
+        } else {
+            -SPLIT_PERCENTAGE_STEP
+        };
+        self.split_percentage = self
+            .split_percentage
+            .saturating_add_signed(step)
+            .clamp(MIN_SPLIT_PERCENTAGE, MAX_SPLIT_PERCENTAGE);
+    }
+    /// Save the pane sizes to the config file, leaving the rest of the config unchanged.
+    /// The config file is reloaded first, in case it has been edited since startup.
+    pub fn save(&self) -> Result<()> {
+        let path = get_config_dir()?.join(CONFIG_FILE_NAME);
+        let mut table = match std::fs::read_to_string(&path) {
+            Ok(config_file) => toml::from_str(&config_file)?,
+            Err(_) => toml::Table::new(),
+        };
+        table.insert("pane_sizes".into(), toml::Value::try_from(self)?);
+        std::fs::write(path, toml::to_string(&table)?)?;
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for ConfigOverride {
+    type Err = String;
+    /// Parse "key=value", where the value is toml. Values that aren't valid toml are taken as
+    /// strings, so that e.g locale=Spanish doesn't need quotes.
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, e.g tick_rate_ms=100, not <{s}>"))?;
+        let key = key.trim();
+        if !get_config_file_keys().iter().any(|k| k.name == key) {
+            return Err(format!("Unknown config key <{key}>"));
+        }
+        Ok(ConfigOverride {
+            key: key.to_string(),
+            value: parse_override_value(value.trim()),
+        })
+    }
+}
+
+fn parse_override_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+/// Set the settings given on the command line. Must be called before the config is loaded.
+pub fn set_cli_overrides(overrides: Vec<ConfigOverride>) {
+    let _ = CLI_OVERRIDES.set(overrides);
+}
+
+/// Settings given as environment variables, for each config key that has one set.
+fn get_env_overrides() -> Vec<ConfigOverride> {
+    get_config_file_keys()
+        .into_iter()
+        .filter_map(|key| {
+            let value = std::env::var(format!("{ENV_VAR_PREFIX}{}", key.name.to_uppercase()));
+            Some(ConfigOverride {
+                key: key.name.to_string(),
+                value: parse_override_value(value.ok()?.trim()),
+            })
+        })
+        .collect()
+}
+
+/// Combine the layers of settings, where each takes priority over those before it.
+fn layer_config(
+    file: toml::Table,
+    overrides: impl IntoIterator<Item = ConfigOverride>,
+) -> Result<Config> {
+    let toml::Value::Table(mut table) = toml::Value::try_from(Config::default())? else {
+        unreachable!("Config is a table");
+    };
+    table.extend(file);
+    table.extend(overrides.into_iter().map(|o| (o.key, o.value)));
+    Ok(toml::Value::Table(table).try_into()?)
+}
+
 impl Config {
+    /// Load the settings from, in increasing priority, the defaults, the config file, the
+    /// environment and the command line.
     pub fn new() -> Result<Self> {
         let config_dir = get_config_dir()?;
-        if let Ok(config_file) = std::fs::read_to_string(config_dir.join(CONFIG_FILE_NAME)) {
-            Ok(toml::from_str(&config_file)?)
-        } else {
-            Ok(Self::default())
-        }
+        let (file, warnings) = match std::fs::read_to_string(config_dir.join(CONFIG_FILE_NAME)) {
+            Ok(config_file) => (
+                toml::from_str(&config_file)?,
+                check_config_file(&config_file),
+            ),
+            Err(_) => (toml::Table::new(), Vec::new()),
+        };
+        let cli_overrides = CLI_OVERRIDES.get().cloned().unwrap_or_default();
+        let mut config = layer_config(file, get_env_overrides().into_iter().chain(cli_overrides))?;
+        config.warnings = warnings;
+        Ok(config)
+    }
+    /// Problems found in the config file, which didn't stop it loading, e.g unknown keys.
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
     }
     pub fn get_auth_type(&self) -> AuthType {
         self.auth_type
     }
+    /// Set the auth type and save it to the config file, keeping any other settings in the file.
+    pub fn save_auth_type(&mut self, auth_type: AuthType) -> Result<()> {
+        if self.auth_type == auth_type {
+            return Ok(());
+        }
+        self.auth_type = auth_type;
+        let path = get_config_dir()?.join(CONFIG_FILE_NAME);
+        let mut table = match std::fs::read_to_string(&path) {
+            Ok(config_file) => toml::from_str(&config_file)?,
+            Err(_) => toml::Table::new(),
+        };
+        table.insert("auth_type".into(), toml::Value::try_from(auth_type)?);
+        std::fs::write(path, toml::to_string(&table)?)?;
+        Ok(())
+    }
+    /// The settings as toml with secrets hidden, to include in crash reports.
+    pub fn get_sanitized_summary(&self) -> Result<String> {
+        let toml::Value::Table(mut table) = toml::Value::try_from(self)? else {
+            unreachable!("Config is a table");
+        };
+        for key in SECRET_CONFIG_KEYS {
+            if let Some(value) = table.get_mut(*key) {
+                *value = "<hidden>".into();
+            }
+        }
+        Ok(toml::to_string(&table)?)
+    }
+    pub fn get_pane_sizes(&self) -> PaneSizes {
+        self.pane_sizes
+    }
+    pub fn get_ascii_icons(&self) -> bool {
+        self.ascii_icons
+    }
+    pub fn get_high_contrast(&self) -> bool {
+        self.high_contrast
+    }
+    pub fn get_locale(&self) -> Locale {
+        self.locale
+    }
+    pub fn get_tick_rate(&self) -> Duration {
+        let tick_rate_ms = self
+            .tick_rate_ms
+            .unwrap_or(DEFAULT_TICK_RATE_MS)
+            .max(MIN_TICK_RATE_MS);
+        Duration::from_millis(tick_rate_ms)
+    }
+    /// Maximum size of the music cache in bytes.
+    pub fn get_music_cache_size(&self) -> u64 {
+        self.music_cache_size_mb
+            .unwrap_or(DEFAULT_MUSIC_CACHE_SIZE_MB)
+            .saturating_mul(1024 * 1024)
+    }
+    pub fn get_audio_format_preference(&self) -> AudioFormatPreference {
+        AudioFormatPreference {
+            quality: self.audio_quality,
+            itags: self.preferred_audio_itags.clone(),
+        }
+    }
+    pub fn get_output_settings(&self) -> OutputSettings {
+        OutputSettings {
+            output: self.audio_output,
+            stream_address: self.audio_stream_address.unwrap_or(DEFAULT_LISTEN_ADDRESS),
+            stream_port: self.audio_stream_port.unwrap_or(DEFAULT_AUDIO_STREAM_PORT),
+        }
+    }
+    /// The configured export directory, otherwise the system music directory if there is one.
+    pub fn get_export_dir(&self) -> Result<PathBuf> {
+        if let Some(dir) = &self.export_dir {
+            return Ok(dir.clone());
+        }
+        let music_dir = directories::UserDirs::new()
+            .and_then(|dirs| dirs.audio_dir().map(|dir| dir.to_path_buf()));
+        match music_dir {
+            Some(dir) => Ok(dir),
+            None => Ok(get_data_dir()?.join("exports")),
+        }
+    }
+    pub fn get_export_template(&self) -> String {
+        self.export_template
+            .clone()
+            .unwrap_or_else(|| DEFAULT_EXPORT_TEMPLATE.to_string())
+    }
+    /// Number of songs that can download at once, at least one.
+    pub fn get_max_concurrent_downloads(&self) -> usize {
+        self.max_concurrent_downloads
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_DOWNLOADS)
+            .max(1)
+    }
+    pub fn get_download_rate_limit(&self) -> DownloadRateLimit {
+        let bytes_per_sec = |kb: Option<u64>| kb.filter(|kb| *kb > 0).map(|kb| kb * 1024);
+        DownloadRateLimit {
+            total: bytes_per_sec(self.max_download_rate_kb),
+            per_song: bytes_per_sec(self.max_song_download_rate_kb),
+        }
+    }
+    /// The web remote settings, or None if the web remote is off. An empty token turns it off,
+    /// as it would let anyone on the network control the app.
+    pub fn get_web_remote_settings(&self) -> Option<WebRemoteSettings> {
+        let token = self.web_remote_token.clone().filter(|t| !t.is_empty())?;
+        Some(WebRemoteSettings {
+            address: self.web_remote_address.unwrap_or(DEFAULT_LISTEN_ADDRESS),
+            port: self.web_remote_port.unwrap_or(DEFAULT_WEB_REMOTE_PORT),
+            token,
+        })
+    }
+    pub fn get_log_level(&self) -> LogLevel {
+        self.log_level
+    }
+    /// How long tasks may run before they're stopped, or None for no limit.
+    pub fn get_task_timeout(&self) -> Option<Duration> {
+        self.task_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+    }
+    pub fn get_cast_enabled(&self) -> bool {
+        self.experimental_cast
+    }
+    pub fn get_event_hook_command(&self) -> Option<&str> {
+        self.event_hook_command.as_deref().filter(|c| !c.is_empty())
+    }
+    /// The settings that are applied again when the config file is edited while running.
+    pub fn get_live_settings(&self) -> Result<LiveSettings> {
+        Ok(LiveSettings {
+            ascii_icons: self.ascii_icons,
+            high_contrast: self.high_contrast,
+            album_art_position: self.album_art_position,
+            status_format: self.get_status_format()?,
+            keybinds: self.get_keybinds()?,
+            output_settings: self.get_output_settings(),
+        })
+    }
+    /// Keys for Actions, by the Action's description in English.
+    pub fn get_keybinds(&self) -> Result<Vec<(String, Keybind)>> {
+        self.keybinds
+            .iter()
+            .map(|(description, key)| Ok((description.clone(), key.parse()?)))
+            .collect::<std::result::Result<_, String>>()
+            .map_err(Error::KeybindError)
+    }
+    /// The status line format, or None if the default status line should be drawn.
+    pub fn get_status_format(&self) -> Result<Option<StatusFormat>> {
+        self.status_format
+            .as_deref()
+            .map(StatusFormat::parse)
+            .transpose()
+            .map_err(Error::StatusFormatError)
+    }
+}
+
+/// A key of the config file, for documentation outside the app.
+pub struct ConfigKey {
+    pub name: &'static str,
+    /// The default value as toml, or an example if there is no fixed default.
+    pub default: String,
+    pub description: &'static str,
+    /// The config file can't be read without this key.
+    pub required: bool,
+}
+
+impl ConfigKey {
+    fn new(name: &'static str, default: impl ToString, description: &'static str) -> Self {
+        Self {
+            name,
+            default: default.to_string(),
+            description,
+            required: false,
+        }
+    }
+}
+
+/// Keys of the config file, in the order they are written to a new config file.
+pub fn get_config_file_keys() -> Vec<ConfigKey> {
+    let pane_sizes = PaneSizes::default();
+    vec![
+        ConfigKey {
+            required: true,
+            ..ConfigKey::new(
+                "auth_type",
+                "\"Browser\"",
+                "Browser to sign in with a cookie, or OAuth. If those credentials are \
+                 missing or expired, the other kind is used instead.",
+            )
+        },
+        ConfigKey::new(
+            "album_art_position",
+            "\"TopRight\"",
+            "Corner the album art is drawn in: TopLeft, TopRight, BottomLeft, BottomRight or \
+             Hidden.",
+        ),
+        ConfigKey::new(
+            "pane_sizes",
+            format!(
+                "{{ artist_list_width = {}, split_percentage = {} }}",
+                pane_sizes.artist_list_width, pane_sizes.split_percentage
+            ),
+            "Maximum width of the artist list, and the percentage of the width given to the \
+             browser in the split layout. Saved when panes are resized.",
+        ),
+        ConfigKey::new(
+            "status_format",
+            "\"{state} {artist} - {title} [{elapsed}/{duration}]\"",
+            "Format of the now playing line. Fields are {state}, {artist}, {title}, {album}, \
+             {elapsed}, {duration} and {volume}.",
+        ),
+        ConfigKey::new(
+            "ascii_icons",
+            false,
+            "Use plain ASCII icons, for fonts without Nerd Font glyphs.",
+        ),
+        ConfigKey::new("high_contrast", false, "Use a high contrast colour theme."),
+        ConfigKey::new(
+            "locale",
+            "\"English\"",
+            "Language of the user interface: English or Spanish.",
+        ),
+        ConfigKey::new(
+            "tick_rate_ms",
+            DEFAULT_TICK_RATE_MS,
+            "Milliseconds between updates of playback progress and animations. A longer tick \
+             rate uses less CPU.",
+        ),
+        ConfigKey::new(
+            "music_cache_size_mb",
+            DEFAULT_MUSIC_CACHE_SIZE_MB,
+            "Maximum size of the downloaded songs cache in megabytes, or 0 to disable the cache.",
+        ),
+        ConfigKey::new(
+            "max_concurrent_downloads",
+            DEFAULT_MAX_CONCURRENT_DOWNLOADS,
+            "Number of songs that can download at once. Further downloads wait in a queue.",
+        ),
+        ConfigKey::new(
+            "max_download_rate_kb",
+            0,
+            "Maximum rate of all downloads combined in kilobytes per second, or 0 for no limit.",
+        ),
+        ConfigKey::new(
+            "max_song_download_rate_kb",
+            0,
+            "Maximum rate of each song download in kilobytes per second, or 0 for no limit.",
+        ),
+        ConfigKey::new(
+            "audio_quality",
+            "\"Low\"",
+            "Quality of downloaded songs: Low, Medium or High.",
+        ),
+        ConfigKey::new(
+            "preferred_audio_itags",
+            "[]",
+            "Itags of audio formats to download in order of preference, e.g [251, 140].",
+        ),
+        ConfigKey::new(
+            "audio_output",
+            "\"Local\"",
+            "Where songs are played: Local to the audio device, Network to an HTTP stream that \
+             other devices can play once audio_stream_address allows them, or Both.",
+        ),
+        ConfigKey::new(
+            "audio_stream_address",
+            format!("\"{DEFAULT_LISTEN_ADDRESS}\""),
+            "Address the audio stream is served on. Only this device can play it unless this \
+             is changed, e.g to 0.0.0.0 to allow other devices on the network.",
+        ),
+        ConfigKey::new(
+            "audio_stream_port",
+            DEFAULT_AUDIO_STREAM_PORT,
+            "Port the audio stream is served on, when audio_output is Network or Both.",
+        ),
+        ConfigKey::new(
+            "export_dir",
+            "\"/path/to/music\"",
+            "Directory that songs are exported to. Defaults to the system music directory.",
+        ),
+        ConfigKey::new(
+            "export_template",
+            format!("\"{DEFAULT_EXPORT_TEMPLATE}\""),
+            "Path of exported songs within the export directory, without the extension. \
+             Fields are {artist}, {album}, {title}, {year} and {track}.",
+        ),
+        ConfigKey::new(
+            "web_remote_token",
+            "\"a-long-random-token\"",
+            "Token that requests to the web remote must include. The web remote is off unless \
+             this is set.",
+        ),
+        ConfigKey::new(
+            "web_remote_address",
+            format!("\"{DEFAULT_LISTEN_ADDRESS}\""),
+            "Address the web remote listens on. Only this device can use it unless this is \
+             changed, e.g to 0.0.0.0 to allow other devices on the network.",
+        ),
+        ConfigKey::new(
+            "web_remote_port",
+            DEFAULT_WEB_REMOTE_PORT,
+            "Port the web remote listens on.",
+        ),
+        ConfigKey::new(
+            "log_level",
+            "\"Info\"",
+            "Most verbose level of messages written to the log file in the data directory: Off, \
+             Error, Warn, Info, Debug or Trace.",
+        ),
+        ConfigKey::new(
+            "event_hook_command",
+            "\"~/.config/youtui/on_event.sh\"",
+            "Command run through the shell for each player event: track_started, \
+             track_finished, download_completed or queue_changed. The event is given in \
+             YOUTUI_EVENT, and the song in YOUTUI_TITLE, YOUTUI_ARTISTS, YOUTUI_ALBUM, \
+             YOUTUI_DURATION and YOUTUI_VIDEO_ID.",
+        ),
+        ConfigKey::new(
+            "task_timeout_secs",
+            0,
+            "Seconds that a task, such as an API query or song download, may run before it's \
+             stopped and reported as timed out, or 0 for no limit.",
+        ),
+        ConfigKey::new(
+            "experimental_cast",
+            false,
+            "Allow the queue to be cast to Google Cast devices on the local network, with the \
+             :cast command. Experimental, as it hasn't been tested with every device.",
+        ),
+        ConfigKey::new(
+            "keybinds",
+            "{}",
+            "Keys for Actions, by the Action's description as shown in the help menu, e.g \
+             { \"Next Song\"=\"C-n\" }. These replace the Action's default keys. Keys are \
+             written as they're shown, e.g C-n, A-x, S-Tab, Space or F5.",
+        ),
+    ]
+}
+
+pub fn get_config_file_path() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join(CONFIG_FILE_NAME))
+}
+
+/// A new config file, with every key commented out at its default except those required.
+pub fn default_config_file() -> String {
+    let mut file = String::from("# youtui config file. Uncomment a key to change it.\n");
+    for key in get_config_file_keys() {
+        let comment = if key.required { "" } else { "# " };
+        file.push_str(&format!(
+            "\n# {}\n{comment}{} = {}\n",
+            key.description, key.name, key.default
+        ));
+    }
+    file
+}
+
+/// Problems found in the contents of a config file, e.g keys that aren't recognised or values
+/// that are invalid. Empty if the config file is valid.
+pub fn check_config_file(contents: &str) -> Vec<String> {
+    let table: toml::Table = match toml::from_str(contents) {
+        Ok(table) => table,
+        Err(e) => return vec![e.to_string()],
+    };
+    let keys = get_config_file_keys();
+    let mut problems: Vec<_> = table
+        .keys()
+        .filter(|name| !keys.iter().any(|key| key.name == name.as_str()))
+        .map(|name| format!("Unknown key <{name}>, which is ignored"))
+        .collect();
+    let config: Config = match toml::from_str(contents) {
+        Ok(config) => config,
+        Err(e) => {
+            problems.push(e.to_string());
+            return problems;
+        }
+    };
+    if let Err(e) = config.get_status_format() {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = config.get_keybinds() {
+        problems.push(e.to_string());
+    }
+    if config
+        .tick_rate_ms
+        .is_some_and(|rate| rate < MIN_TICK_RATE_MS)
+    {
+        problems.push(format!(
+            "tick_rate_ms is below the minimum of {MIN_TICK_RATE_MS}, which is used instead"
+        ));
+    }
+    if config.max_concurrent_downloads == Some(0) {
+        problems.push("max_concurrent_downloads is 0, so 1 is used instead".into());
+    }
+    if config.web_remote_token.as_deref() == Some("") {
+        problems.push("web_remote_token is empty, so the web remote is off".into());
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_config_file, default_config_file, get_config_file_keys, layer_config,
+        AudioFormatPreference, AudioOutput, AudioQuality, AuthType, Config, ConfigOverride,
+        DownloadRateLimit, OutputSettings, PaneSizes, StatusField, StatusFormat, StatusSegment,
+        WebRemoteSettings,
+    };
+    use crate::i18n::Locale;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_status_format() {
+        assert_eq!(
+            StatusFormat::parse("{state} {title} [{volume}%] {{x}}"),
+            Ok(StatusFormat(vec![
+                StatusSegment::Field(StatusField::State),
+                StatusSegment::Text(" ".into()),
+                StatusSegment::Field(StatusField::Title),
+                StatusSegment::Text(" [".into()),
+                StatusSegment::Field(StatusField::Volume),
+                StatusSegment::Text("%] {x}".into()),
+            ]))
+        );
+        assert!(StatusFormat::parse("{nonsense}").is_err());
+        assert!(StatusFormat::parse("{title").is_err());
+        assert!(StatusFormat::parse("title}").is_err());
+    }
+
+    #[test]
+    fn test_parse_config_override() {
+        let parsed: ConfigOverride = "tick_rate_ms=100".parse().unwrap();
+        assert_eq!(parsed.value, toml::Value::Integer(100));
+        // Strings don't need quotes.
+        let parsed: ConfigOverride = "locale=Spanish".parse().unwrap();
+        assert_eq!(parsed.value, toml::Value::String("Spanish".into()));
+        let parsed: ConfigOverride = "locale=\"Spanish\"".parse().unwrap();
+        assert_eq!(parsed.value, toml::Value::String("Spanish".into()));
+        assert!("tick_rate_ms".parse::<ConfigOverride>().is_err());
+        assert!("nonsense=1".parse::<ConfigOverride>().is_err());
+    }
+    #[test]
+    fn test_layer_config() {
+        // Without a config file, the defaults are used.
+        let config = layer_config(toml::Table::new(), []).unwrap();
+        assert_eq!(config.get_auth_type(), AuthType::Browser);
+        assert_eq!(config.get_tick_rate(), Duration::from_millis(200));
+        let file = toml::from_str("auth_type = \"OAuth\"\ntick_rate_ms = 1000").unwrap();
+        let overrides = [
+            "tick_rate_ms=500".parse().unwrap(),
+            "locale=Spanish".parse().unwrap(),
+            "tick_rate_ms=300".parse().unwrap(),
+        ];
+        let config = layer_config(file, overrides).unwrap();
+        assert_eq!(config.get_auth_type(), AuthType::OAuth);
+        // The last override wins.
+        assert_eq!(config.get_tick_rate(), Duration::from_millis(300));
+        assert_eq!(config.get_locale(), Locale::Spanish);
+        let lowercase = ["auth_type=oauth".parse().unwrap()];
+        let config = layer_config(toml::Table::new(), lowercase).unwrap();
+        assert_eq!(config.get_auth_type(), AuthType::OAuth);
+        let invalid = ["tick_rate_ms=fast".parse().unwrap()];
+        assert!(layer_config(toml::Table::new(), invalid).is_err());
+    }
+    #[test]
+    fn test_resize_panes_clamped() {
+        let mut sizes = PaneSizes::default();
+        sizes.resize_artist_list(true);
+        assert_eq!(sizes.artist_list_width, 32);
+        for _ in 0..100 {
+            sizes.resize_artist_list(false);
+            sizes.resize_split(true);
+        }
+        assert_eq!(sizes.artist_list_width, 10);
+        assert_eq!(sizes.split_percentage, 80);
+    }
+    #[test]
+    fn test_tick_rate() {
+        assert_eq!(
+            Config::default().get_tick_rate(),
+            Duration::from_millis(200)
+        );
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\ntick_rate_ms = 1000").unwrap();
+        assert_eq!(config.get_tick_rate(), Duration::from_millis(1000));
+        // Very short tick rates are limited, as they would use a lot of CPU.
+        let config: Config = toml::from_str("auth_type = \"Browser\"\ntick_rate_ms = 0").unwrap();
+        assert_eq!(config.get_tick_rate(), Duration::from_millis(20));
+    }
+    #[test]
+    fn test_music_cache_size() {
+        assert_eq!(Config::default().get_music_cache_size(), 500 * 1024 * 1024);
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nmusic_cache_size_mb = 0").unwrap();
+        assert_eq!(config.get_music_cache_size(), 0);
+    }
+    #[test]
+    fn test_max_concurrent_downloads() {
+        assert_eq!(Config::default().get_max_concurrent_downloads(), 3);
+        // At least one download is needed for songs to play.
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nmax_concurrent_downloads = 0").unwrap();
+        assert_eq!(config.get_max_concurrent_downloads(), 1);
+    }
+    #[test]
+    fn test_task_timeout() {
+        assert_eq!(Config::default().get_task_timeout(), None);
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\ntask_timeout_secs = 0").unwrap();
+        assert_eq!(config.get_task_timeout(), None);
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\ntask_timeout_secs = 30").unwrap();
+        assert_eq!(config.get_task_timeout(), Some(Duration::from_secs(30)));
+    }
+    #[test]
+    fn test_download_rate_limit() {
+        assert_eq!(
+            Config::default().get_download_rate_limit(),
+            DownloadRateLimit::default()
+        );
+        let config: Config = toml::from_str(
+            "auth_type = \"Browser\"\nmax_download_rate_kb = 0\nmax_song_download_rate_kb = 100",
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_download_rate_limit(),
+            DownloadRateLimit {
+                total: None,
+                per_song: Some(100 * 1024),
+            }
+        );
+    }
+    #[test]
+    fn test_audio_format_preference() {
+        assert_eq!(
+            Config::default().get_audio_format_preference(),
+            AudioFormatPreference {
+                quality: AudioQuality::Low,
+                itags: vec![],
+            }
+        );
+        let config: Config = toml::from_str(
+            "auth_type = \"Browser\"\naudio_quality = \"High\"\npreferred_audio_itags = [251]",
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_audio_format_preference(),
+            AudioFormatPreference {
+                quality: AudioQuality::High,
+                itags: vec![251],
+            }
+        );
+    }
+    #[test]
+    fn test_sanitized_summary() {
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nweb_remote_token = \"abc\"").unwrap();
+        let summary = config.get_sanitized_summary().unwrap();
+        assert!(summary.contains("web_remote_token = \"<hidden>\""));
+        assert!(!summary.contains("abc"));
+        assert!(summary.contains("auth_type = \"Browser\""));
+    }
+    #[test]
+    fn test_live_settings() {
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nhigh_contrast = true").unwrap();
+        let settings = config.get_live_settings().unwrap();
+        assert!(settings.high_contrast);
+        assert_eq!(settings.status_format, None);
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nstatus_format = \"{nonsense}\"").unwrap();
+        assert!(config.get_live_settings().is_err());
+    }
+    #[test]
+    fn test_web_remote_settings() {
+        assert_eq!(Config::default().get_web_remote_settings(), None);
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nweb_remote_token = \"\"").unwrap();
+        assert_eq!(config.get_web_remote_settings(), None);
+        let config: Config =
+            toml::from_str("auth_type = \"Browser\"\nweb_remote_token = \"abc\"").unwrap();
+        assert_eq!(
+            config.get_web_remote_settings(),
+            Some(WebRemoteSettings {
+                address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                port: 8642,
+                token: "abc".into(),
+            })
+        );
+        let config: Config = toml::from_str(
+            "auth_type = \"Browser\"\nweb_remote_token = \"abc\"\nweb_remote_address = \"0.0.0.0\"",
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_web_remote_settings().map(|s| s.address),
+            Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+        );
+    }
+    #[test]
+    fn test_output_settings() {
+        assert_eq!(
+            Config::default().get_output_settings(),
+            OutputSettings {
+                output: AudioOutput::Local,
+                stream_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                stream_port: 8643,
+            }
+        );
+        let config: Config = toml::from_str(
+            "auth_type = \"Browser\"\naudio_output = \"Both\"\naudio_stream_port = 9000",
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_output_settings(),
+            OutputSettings {
+                output: AudioOutput::Both,
+                stream_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                stream_port: 9000,
+            }
+        );
+    }
+    #[test]
+    fn test_config_file_keys_documented() {
+        let serde_json::Value::Object(config) = serde_json::to_value(Config::default()).unwrap()
+        else {
+            panic!("Config should serialize to a map");
+        };
+        let documented: Vec<_> = get_config_file_keys().iter().map(|k| k.name).collect();
+        assert_eq!(config.keys().count(), documented.len());
+        for key in config.keys() {
+            assert!(documented.contains(&key.as_str()), "{key} is undocumented");
+        }
+    }
+    #[test]
+    fn test_default_config_file() {
+        let file = default_config_file();
+        assert!(check_config_file(&file).is_empty());
+        // Every default should also be valid once uncommented.
+        let uncommented = file.replace("\n# ", "\n");
+        let uncommented: String = uncommented
+            .lines()
+            .filter(|line| line.contains(" = "))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        assert_eq!(check_config_file(&uncommented), Vec::<String>::new());
+    }
+    #[test]
+    fn test_check_config_file() {
+        let problems = check_config_file(
+            "auth_type = \"Browser\"\nstatus_format = \"{bad}\"\ntick_rate = 100\n",
+        );
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("tick_rate"));
+        assert!(problems[1].contains("bad"));
+        assert_eq!(check_config_file("auth_type = 1").len(), 1);
+    }
+    #[test]
+    fn test_check_config_file_keybinds() {
+        let file = "auth_type = \"Browser\"\nkeybinds = { \"Next Song\" = \"C-n\" }\n";
+        assert!(check_config_file(file).is_empty());
+        let problems = check_config_file(&file.replace("C-n", "C-Nope"));
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("keybinds"));
+    }
 }
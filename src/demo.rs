@@ -0,0 +1,121 @@
+//! Offline demo mode, where queries are answered with the ytmapi-rs test fixtures instead of
+//! YouTube Music. This allows the app to be developed, screenshotted and tested without
+//! credentials or a network connection.
+use std::borrow::Cow;
+use ytmapi_rs::auth::BrowserToken;
+use ytmapi_rs::query::{
+    AlbumsFilter, ArtistsFilter, BasicSearch, FilteredSearch, PlaylistsFilter, PodcastsFilter,
+    Query, SearchQuery, SearchType, SongsFilter, VideosFilter,
+};
+use ytmapi_rs::{OfflineResponses, YtMusic};
+
+/// The fixtures are built into the binary, so demo mode works without the source tree.
+macro_rules! fixture {
+    ($file:literal) => {
+        include_str!(concat!("../ytmapi-rs/test_json/", $file))
+    };
+}
+
+/// An API handle that answers queries with the test fixtures. Queries without a fixture, such
+/// as editing a playlist, return an error.
+pub fn get_demo_api() -> YtMusic<BrowserToken> {
+    YtMusic::from_offline_responses(OfflineResponses::new(|path, header, params| {
+        let browse_id = header.get("browseId").and_then(|id| id.as_str());
+        fixture_json(path, browse_id, params).map(str::to_string)
+    }))
+}
+
+fn search_params<S: SearchType>() -> Option<String> {
+    SearchQuery::<S>::from("").params().map(Cow::into_owned)
+}
+
+/// The fixture that answers a query, identified by its path, browse id and params.
+fn fixture_json(path: &str, browse_id: Option<&str>, params: Option<&str>) -> Option<&'static str> {
+    match (path, browse_id) {
+        ("search", _) => {
+            let searches = [
+                (
+                    search_params::<BasicSearch>(),
+                    fixture!("search_basic_top_result_20231228.json"),
+                ),
+                (
+                    search_params::<FilteredSearch<ArtistsFilter>>(),
+                    fixture!("search_artists_20231226.json"),
+                ),
+                (
+                    search_params::<FilteredSearch<SongsFilter>>(),
+                    fixture!("search_songs_20231226.json"),
+                ),
+                (
+                    search_params::<FilteredSearch<AlbumsFilter>>(),
+                    fixture!("search_albums_20231226.json"),
+                ),
+                (
+                    search_params::<FilteredSearch<PlaylistsFilter>>(),
+                    fixture!("search_playlists_20231228.json"),
+                ),
+                (
+                    search_params::<FilteredSearch<VideosFilter>>(),
+                    fixture!("search_videos_20231226.json"),
+                ),
+                (
+                    search_params::<FilteredSearch<PodcastsFilter>>(),
+                    fixture!("search_podcasts_20231226.json"),
+                ),
+            ];
+            searches
+                .into_iter()
+                .find(|(search_params, _)| search_params.as_deref() == params)
+                .map(|(_, json)| json)
+        }
+        ("browse", Some("FEmusic_liked_playlists")) => Some(fixture!("get_library_playlists.json")),
+        ("browse", Some("FEmusic_liked_albums")) => Some(fixture!("get_library_albums.json")),
+        ("browse", Some("FEmusic_library_corpus_track_artists")) => {
+            Some(fixture!("get_library_artists.json"))
+        }
+        ("browse", Some("FEmusic_history")) => Some(fixture!("get_history.json")),
+        ("browse", Some(id)) if id.starts_with("MPREb_") => Some(fixture!("get_album.json")),
+        ("browse", Some(id)) if id.starts_with("MPLY") => {
+            Some(fixture!("get_lyrics_20231219.json"))
+        }
+        // Artists are browsed with params to get their albums.
+        ("browse", Some(id)) if id.starts_with("UC") => match params {
+            Some(_) => Some(fixture!("browse_artist_albums.json")),
+            None => Some(fixture!("browse_artist.json")),
+        },
+        // Watch playlists are used to find a song's lyrics.
+        ("next", _) => Some(fixture!("get_watch_playlist.json")),
+        ("account/account_menu", _) => Some(fixture!("get_account_info.json")),
+        ("playlist/create", _) => Some(fixture!("create_playlist.json")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fixture_json, search_params};
+    use ytmapi_rs::query::{ArtistsFilter, FilteredSearch, SongsFilter};
+
+    #[test]
+    fn test_fixture_json() {
+        let songs = search_params::<FilteredSearch<SongsFilter>>();
+        assert_eq!(
+            fixture_json("search", None, songs.as_deref()),
+            Some(fixture!("search_songs_20231226.json"))
+        );
+        let artists = search_params::<FilteredSearch<ArtistsFilter>>();
+        assert_eq!(
+            fixture_json("search", None, artists.as_deref()),
+            Some(fixture!("search_artists_20231226.json"))
+        );
+        assert_eq!(
+            fixture_json("browse", Some("UCabc"), Some("params")),
+            Some(fixture!("browse_artist_albums.json"))
+        );
+        assert_eq!(
+            fixture_json("browse", Some("MPREb_abc"), None),
+            Some(fixture!("get_album.json"))
+        );
+        assert_eq!(fixture_json("browse", Some("VLabc"), None), None);
+    }
+}
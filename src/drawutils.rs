@@ -1,7 +1,9 @@
 use ratatui::{
+    buffer::Buffer,
     prelude::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
 };
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 // Standard app colour scheme
 pub const SELECTED_BORDER_COLOUR: Color = Color::Cyan;
@@ -14,6 +16,125 @@ pub const PROGRESS_BG_COLOUR: Color = Color::DarkGray;
 pub const PROGRESS_FG_COLOUR: Color = Color::LightGreen;
 pub const TABLE_HEADINGS_COLOUR: Color = Color::LightGreen;
 pub const ROW_HIGHLIGHT_COLOUR: Color = Color::Blue;
+pub const JUMP_FLASH_COLOUR: Color = Color::LightBlue;
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+// Shared by every loading indicator so that they animate in step. Stored globally as the draw
+// functions for generic tables and lists don't have access to application state.
+static SPINNER_TICKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Advance all loading spinners by one frame. Called on every tick.
+pub fn advance_spinner() {
+    SPINNER_TICKS.fetch_add(1, Ordering::Relaxed);
+}
+/// Get the current frame of the loading spinner.
+pub fn get_spinner() -> &'static str {
+    set_animated();
+    let frames: &[&'static str] = if ascii_icons() {
+        &ASCII_SPINNER_FRAMES
+    } else {
+        &SPINNER_FRAMES
+    };
+    get_spinner_frame(frames, SPINNER_TICKS.load(Ordering::Relaxed))
+}
+fn get_spinner_frame(frames: &[&'static str], ticks: usize) -> &'static str {
+    frames[ticks % frames.len()]
+}
+
+// Set while drawing a frame that contains an animation, such as a loading spinner, so that the
+// frame is drawn again on the next tick. Stored globally for the same reason as the spinner.
+static ANIMATED: AtomicBool = AtomicBool::new(false);
+
+/// Mark the frame being drawn as animated, so that it is drawn again on the next tick.
+pub fn set_animated() {
+    ANIMATED.store(true, Ordering::Relaxed);
+}
+/// True if an animation was drawn since this was last called.
+pub fn take_animated() -> bool {
+    ANIMATED.swap(false, Ordering::Relaxed)
+}
+
+const JUMP_FLASH_LENGTH_TICKS: usize = 3;
+// Ticks left to flash the selected row for, after the selection jumps to a typed letter.
+// Stored globally for the same reason as the spinner.
+static JUMP_FLASH_TICKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Briefly flash the selected row, to show where the selection jumped to.
+pub fn start_jump_flash() {
+    JUMP_FLASH_TICKS.store(JUMP_FLASH_LENGTH_TICKS, Ordering::Relaxed);
+}
+/// Count down the selected row flash. Called on every tick.
+pub fn advance_jump_flash() {
+    let _ =
+        JUMP_FLASH_TICKS.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |t| t.checked_sub(1));
+}
+
+// Display settings from the config file. Stored globally for the same reason as the spinner.
+static ASCII_ICONS: AtomicBool = AtomicBool::new(false);
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+/// Use plain ASCII icons instead of Nerd Font glyphs.
+pub fn set_ascii_icons(enabled: bool) {
+    ASCII_ICONS.store(enabled, Ordering::Relaxed);
+}
+pub fn ascii_icons() -> bool {
+    ASCII_ICONS.load(Ordering::Relaxed)
+}
+/// Pick between a Nerd Font glyph and its plain ASCII fallback.
+pub fn icon(glyph: char, ascii: char) -> char {
+    if ascii_icons() {
+        ascii
+    } else {
+        glyph
+    }
+}
+// Eighths of a block, used to draw partially filled gauge cells.
+const GAUGE_BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Draw a gauge as text, width characters wide, filled to the percentage.
+pub fn text_gauge(percentage: u8, width: usize) -> String {
+    let eighths = usize::from(percentage.min(100)) * width * 8 / 100;
+    let (full, part) = (eighths / 8, eighths % 8);
+    let (full_char, empty_char) = if ascii_icons() {
+        ('#', '.')
+    } else {
+        ('█', ' ')
+    };
+    let mut gauge: String = std::iter::repeat(full_char).take(full).collect();
+    if part > 0 && full < width {
+        gauge.push(if ascii_icons() {
+            '#'
+        } else {
+            GAUGE_BLOCKS[part - 1]
+        });
+    }
+    let drawn = gauge.chars().count();
+    gauge.extend(std::iter::repeat(empty_char).take(width - drawn));
+    gauge
+}
+/// Draw using the high contrast theme instead of the standard colour scheme.
+pub fn set_high_contrast(enabled: bool) {
+    HIGH_CONTRAST.store(enabled, Ordering::Relaxed);
+}
+pub fn high_contrast() -> bool {
+    HIGH_CONTRAST.load(Ordering::Relaxed)
+}
+/// Convert a drawn buffer to the high contrast theme.
+/// Anything drawn on a coloured background becomes black on white, and any other coloured text
+/// becomes bold yellow. Applied once the frame has been drawn so that individual widgets don't
+/// need to know about the theme.
+pub fn apply_high_contrast(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.bg = Color::White;
+            cell.fg = Color::Black;
+        } else if cell.fg != Color::Reset {
+            cell.fg = Color::Yellow;
+            cell.modifier.insert(Modifier::BOLD);
+        }
+    }
+}
 
 /// Helper function to create a popup at bottom corner of chunk.
 pub fn left_bottom_corner_rect(height: u16, width: u16, r: Rect) -> Rect {
@@ -63,13 +184,58 @@ pub fn bottom_of_rect(r: Rect) -> Rect {
 
 /// Return the standard list / table highlight style
 pub fn highlight_style() -> Style {
-    Style::new().bg(ROW_HIGHLIGHT_COLOUR)
+    if JUMP_FLASH_TICKS.load(Ordering::Relaxed) > 0 {
+        set_animated();
+        Style::new()
+            .bg(JUMP_FLASH_COLOUR)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::new().bg(ROW_HIGHLIGHT_COLOUR)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{below_left_rect, centered_rect, left_bottom_corner_rect};
-    use ratatui::layout::Rect;
+    use super::{
+        apply_high_contrast, below_left_rect, centered_rect, get_spinner_frame,
+        left_bottom_corner_rect, text_gauge, SPINNER_FRAMES,
+    };
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+    };
+
+    #[test]
+    fn test_spinner_wraps() {
+        assert_eq!(get_spinner_frame(&SPINNER_FRAMES, 0), SPINNER_FRAMES[0]);
+        assert_eq!(
+            get_spinner_frame(&SPINNER_FRAMES, SPINNER_FRAMES.len() + 1),
+            SPINNER_FRAMES[1]
+        );
+    }
+    #[test]
+    fn test_text_gauge() {
+        assert_eq!(text_gauge(0, 4), "    ");
+        assert_eq!(text_gauge(50, 4), "██  ");
+        assert_eq!(text_gauge(60, 5), "███  ");
+        assert_eq!(text_gauge(55, 5), "██▊  ");
+        assert_eq!(text_gauge(100, 4), "████");
+    }
+    #[test]
+    fn test_apply_high_contrast() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buf.get_mut(0, 0).set_style(Style::new().fg(Color::Cyan));
+        buf.get_mut(1, 0)
+            .set_style(Style::new().fg(Color::LightGreen).bg(Color::DarkGray));
+        apply_high_contrast(&mut buf);
+        assert_eq!(buf.get(0, 0).fg, Color::Yellow);
+        assert!(buf.get(0, 0).modifier.contains(Modifier::BOLD));
+        assert_eq!(buf.get(1, 0).fg, Color::Black);
+        assert_eq!(buf.get(1, 0).bg, Color::White);
+        assert_eq!(buf.get(2, 0).fg, Color::Reset);
+        assert_eq!(buf.get(2, 0).bg, Color::Reset);
+    }
 
     fn bounds_check_rect(r: Rect, max_bounds: Rect) {
         assert!(r.left() >= max_bounds.left());
@@ -1,4 +1,6 @@
 use crate::config::AuthType;
+use serde::Serialize;
+use serde_json::json;
 use std::{fmt::Display, path::PathBuf};
 use tokio::{sync::mpsc, task::JoinError};
 
@@ -16,6 +18,14 @@ pub enum Error {
     ApiError(ytmapi_rs::Error),
     JsonError(serde_json::Error),
     TomlDeserializationError(toml::de::Error),
+    TomlSerializationError(toml::ser::Error),
+    StatusFormatError(String),
+    KeybindError(String),
+    ReqwestError(reqwest::Error),
+    ImageError(image::ImageError),
+    VideoError(rusty_ytdl::VideoError),
+    TagError(lofty::error::LoftyError),
+    WatchError(notify::Error),
     AuthTokenError {
         token_type: AuthType,
         token_location: PathBuf,
@@ -32,6 +42,46 @@ pub enum Error {
     // TODO: Remove this, catchall currentl
     Other(String),
 }
+
+/// What kind of error occurred, so that scripts can act on it. Each has its own exit code.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Other,
+    Auth,
+    Network,
+    NotFound,
+    Parse,
+}
+
+impl ErrorKind {
+    pub const ALL: [ErrorKind; 5] = [
+        ErrorKind::Other,
+        ErrorKind::Auth,
+        ErrorKind::Network,
+        ErrorKind::NotFound,
+        ErrorKind::Parse,
+    ];
+    /// The process exit code. 2 isn't used, as clap exits with it when arguments are invalid.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::Auth => 3,
+            ErrorKind::Network => 4,
+            ErrorKind::NotFound => 5,
+            ErrorKind::Parse => 6,
+        }
+    }
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorKind::Other => "Any error not listed below.",
+            ErrorKind::Auth => "The credentials are missing, invalid or have expired.",
+            ErrorKind::Network => "YouTube Music couldn't be reached.",
+            ErrorKind::NotFound => "What was requested doesn't exist.",
+            ErrorKind::Parse => "The response from YouTube Music wasn't in the expected format.",
+        }
+    }
+}
 impl Error {
     // Consider taking into pathbuf.
     pub fn new_auth_token_error(
@@ -58,6 +108,38 @@ impl Error {
             io_error,
         }
     }
+    /// True if the error was caused by missing, invalid or expired credentials, and so may be
+    /// resolved by signing in again.
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            Error::OAuthNotYetSupportedByApp
+            | Error::AuthTokenError { .. }
+            | Error::AuthTokenParseError { .. } => true,
+            Error::ApiError(e) => e.is_auth_error(),
+            _ => false,
+        }
+    }
+    pub fn get_kind(&self) -> ErrorKind {
+        match self {
+            _ if self.is_auth_error() => ErrorKind::Auth,
+            Error::ApiError(e) if e.is_network_error() => ErrorKind::Network,
+            Error::ApiError(e) if e.is_not_found_error() => ErrorKind::NotFound,
+            Error::ApiError(e) if e.is_parse_error() => ErrorKind::Parse,
+            Error::ReqwestError(_) => ErrorKind::Network,
+            Error::JsonError(_) => ErrorKind::Parse,
+            _ => ErrorKind::Other,
+        }
+    }
+    /// The error as a json object, for scripts, e.g
+    /// {"kind": "auth", "exit_code": 3, "message": "..."}.
+    pub fn to_json(&self) -> serde_json::Value {
+        let kind = self.get_kind();
+        json!({
+            "kind": kind,
+            "exit_code": kind.exit_code(),
+            "message": self.to_string(),
+        })
+    }
 }
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -72,6 +154,14 @@ impl Display for Error {
             Error::ApiError(e) => write!(f, "Api error <{e}>"),
             Error::JsonError(e) => write!(f, "Json error <{e}>"),
             Error::TomlDeserializationError(e) => write!(f, "Toml deserialization error:\n{e}"),
+            Error::TomlSerializationError(e) => write!(f, "Toml serialization error <{e}>"),
+            Error::StatusFormatError(e) => write!(f, "Error in status_format in config: {e}"),
+            Error::KeybindError(e) => write!(f, "Error in keybinds in config: {e}"),
+            Error::ReqwestError(e) => write!(f, "Http error <{e}>"),
+            Error::ImageError(e) => write!(f, "Image error <{e}>"),
+            Error::VideoError(e) => write!(f, "Song download error <{e}>"),
+            Error::TagError(e) => write!(f, "Error tagging song <{e}>"),
+            Error::WatchError(e) => write!(f, "Error watching file <{e}>"),
             // TODO: Better display format for token_type.
             // XXX: Consider displaying the io error.
             Error::AuthTokenError { token_type, token_location, io_error: _} => write!(f, "Error loading {:?} auth token from {}. Does the file exist? See README.md for more information on auth tokens.", token_type, token_location.display()),
@@ -110,8 +200,71 @@ impl From<toml::de::Error> for Error {
         Error::TomlDeserializationError(value)
     }
 }
+impl From<toml::ser::Error> for Error {
+    fn from(value: toml::ser::Error) -> Self {
+        Error::TomlSerializationError(value)
+    }
+}
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        Error::ReqwestError(value)
+    }
+}
+impl From<image::ImageError> for Error {
+    fn from(value: image::ImageError) -> Self {
+        Error::ImageError(value)
+    }
+}
+impl From<rusty_ytdl::VideoError> for Error {
+    fn from(value: rusty_ytdl::VideoError) -> Self {
+        Error::VideoError(value)
+    }
+}
+impl From<lofty::error::LoftyError> for Error {
+    fn from(value: lofty::error::LoftyError) -> Self {
+        Error::TagError(value)
+    }
+}
+impl From<notify::Error> for Error {
+    fn from(value: notify::Error) -> Self {
+        Error::WatchError(value)
+    }
+}
 impl From<ytmapi_rs::Error> for Error {
     fn from(value: ytmapi_rs::Error) -> Self {
         Error::ApiError(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ErrorKind};
+    use crate::config::AuthType;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_error_to_json() {
+        let error = Error::new_auth_token_parse_error(AuthType::Browser, PathBuf::from("c.txt"));
+        assert_eq!(error.get_kind(), ErrorKind::Auth);
+        assert_eq!(
+            error.to_json(),
+            json!({
+                "kind": "auth",
+                "exit_code": 3,
+                "message": error.to_string(),
+            })
+        );
+        assert_eq!(Error::Other("x".into()).to_json()["kind"], json!("other"));
+    }
+    #[test]
+    fn test_exit_codes_distinct() {
+        for (i, a) in ErrorKind::ALL.iter().enumerate() {
+            assert_ne!(a.exit_code(), 0);
+            assert_ne!(a.exit_code(), 2);
+            for b in &ErrorKind::ALL[i + 1..] {
+                assert_ne!(a.exit_code(), b.exit_code());
+            }
+        }
+    }
+}
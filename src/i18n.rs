@@ -0,0 +1,588 @@
+//! Translation of user interface strings.
+//! English strings are used as the keys, so an untranslated string falls back to English.
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+// Set once from the config at startup. Stored globally as strings are translated deep inside
+// Action and view implementations that don't have access to application state.
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Language of the user interface.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+/// Set the language of the user interface. Only the first call has any effect.
+pub fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+fn get_locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// Translate a user interface string into the current locale.
+pub fn tr(text: &'static str) -> &'static str {
+    translate(get_locale(), text)
+}
+
+/// Translate a template into the current locale, then replace each "{}" in it with the next
+/// argument.
+pub fn tr_format(template: &'static str, args: &[&dyn Display]) -> String {
+    fill_template(tr(template), args)
+}
+
+/// Translate a string that isn't known until runtime, e.g an Action named in the config file,
+/// into the current locale.
+pub fn tr_runtime(text: &str) -> String {
+    translate(get_locale(), text).to_string()
+}
+
+fn translate(locale: Locale, text: &str) -> &str {
+    let table = match locale {
+        Locale::English => return text,
+        Locale::Spanish => SPANISH,
+    };
+    table
+        .iter()
+        .find(|(english, _)| *english == text)
+        .map(|(_, translated)| *translated)
+        .unwrap_or(text)
+}
+
+fn fill_template(template: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut pieces = template.split("{}");
+    let mut filled = pieces.next().unwrap_or_default().to_string();
+    for piece in pieces {
+        if let Some(arg) = args.next() {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(piece);
+    }
+    filled
+}
+
+const SPANISH: &[(&str, &str)] = &[
+    // Contexts and pane titles
+    ("Global", "Global"),
+    ("Notifications", "Notificaciones"),
+    ("Notifications - none", "Notificaciones - ninguna"),
+    ("Confirm", "Confirmar"),
+    ("Tasks", "Tareas"),
+    ("Tasks - none running", "Tareas - ninguna en curso"),
+    ("Tasks - Enter to cancel", "Tareas - Enter para cancelar"),
+    ("Waiting", "Esperando"),
+    ("Receiving", "Recibiendo"),
+    ("Retrying", "Reintentando"),
+    ("Category", "Categoría"),
+    ("Age", "Tiempo"),
+    ("Status", "Estado"),
+    ("Progress", "Progreso"),
+    ("Help", "Ayuda"),
+    ("Help - type to filter", "Ayuda - escribe para filtrar"),
+    ("Help - filter: {}", "Ayuda - filtro: {}"),
+    ("Key", "Tecla"),
+    ("Context", "Contexto"),
+    ("Command", "Comando"),
+    ("Commands", "Comandos"),
+    ("Completions", "Sugerencias"),
+    ("Command Line", "Línea de comandos"),
+    ("Add To Playlist", "Añadir a lista"),
+    ("{} Loading playlists", "{} Cargando listas"),
+    ("{} Retrying playlists", "{} Reintentando listas"),
+    ("Error getting playlists", "Error al obtener las listas"),
+    ("No playlists found", "No se encontraron listas"),
+    ("Add {} to", "Añadir {} a"),
+    ("Playlist", "Lista"),
+    ("Local playlist - {} songs", "Lista local - {} canciones"),
+    ("{} downloading / {} queued", "{} descargando / {} en cola"),
+    ("{} tracks - {} total - {} remaining", "{} pistas - {} en total - {} restantes"),
+    (
+        "Local playlist - {} of {} songs - filter: {}",
+        "Lista local - {} de {} canciones - filtro: {}",
+    ),
+    ("Browser", "Explorador"),
+    ("New tab", "Nueva pestaña"),
+    ("Search Results Panel", "Panel de resultados"),
+    ("{} - loading", "{} - cargando"),
+    ("{} - retrying", "{} - reintentando"),
+    ("{} - {} results", "{} - {} resultados"),
+    ("{} - Error receieved", "{} - Error recibido"),
+    ("Artist Search Panel", "Panel de búsqueda de artistas"),
+    ("Artists", "Artistas"),
+    ("Artists - {} results", "Artistas - {} resultados"),
+    ("Artists - Error receieved", "Artistas - Error recibido"),
+    ("Artists - Subscribed", "Artistas - Suscrito"),
+    ("Artists - Not subscribed", "Artistas - No suscrito"),
+    ("{} - filter: {}", "{} - filtro: {}"),
+    ("Artist Songs Panel", "Panel de canciones"),
+    ("Songs", "Canciones"),
+    ("Songs - loading", "Canciones - cargando"),
+    ("Songs - retrying", "Canciones - reintentando"),
+    ("Songs - {} results", "Canciones - {} resultados"),
+    ("Songs - {} results - {} loading", "Canciones - {} resultados - {} cargando"),
+    (
+        "Songs - {} results - {} loaded {}",
+        "Canciones - {} resultados - {} cargados {}",
+    ),
+    ("{}/{} albums", "{}/{} álbumes"),
+    ("{}/{} songs", "{}/{} canciones"),
+    ("Songs - Error receieved", "Canciones - Error recibido"),
+    ("Library", "Biblioteca"),
+    ("Playlists", "Listas"),
+    ("Playlists - {} results", "Listas - {} resultados"),
+    ("Playlists - Error receieved", "Listas - Error recibido"),
+    ("Albums", "Álbumes"),
+    ("Albums - {} results", "Álbumes - {} resultados"),
+    ("Albums - Error receieved", "Álbumes - Error recibido"),
+    ("Charts", "Éxitos"),
+    ("Charts - Error receieved", "Éxitos - Error recibido"),
+    ("Recently Played", "Reproducidas recientemente"),
+    (
+        "Recently Played - {} songs",
+        "Reproducidas recientemente - {} canciones",
+    ),
+    ("Remove from history", "Quitar del historial"),
+    ("Favorites", "Favoritas"),
+    ("Favorites - {} songs", "Favoritas - {} canciones"),
+    ("Unstar", "Quitar de favoritas"),
+    ("Country", "País"),
+    ("Artist", "Artista"),
+    ("Artist - Error receieved", "Artista - Error recibido"),
+    ("About", "Acerca de"),
+    ("Podcasts", "Podcasts"),
+    ("Podcasts - {} results", "Podcasts - {} resultados"),
+    ("Podcasts - Error receieved", "Podcasts - Error recibido"),
+    ("Episodes", "Episodios"),
+    ("Episodes - Error receieved", "Episodios - Error recibido"),
+    ("{} - {} episodes", "{} - {} episodios"),
+    ("Lyrics", "Letra"),
+    ("Lyrics - {}", "Letra - {}"),
+    ("Lyrics - {} Loading", "Letra - {} Cargando"),
+    ("Lyrics - {} Retrying", "Letra - {} Reintentando"),
+    ("Lyrics - No lyrics found", "Letra - No se encontró la letra"),
+    ("Lyrics - Nothing playing", "Letra - Nada en reproducción"),
+    ("Logger", "Registro"),
+    ("Filter", "Filtro"),
+    ("Sort", "Ordenar"),
+    // Modes
+    ("Play", "Reproducir"),
+    ("Add to queue", "Añadir a la cola"),
+    ("Playlist Action", "Acción de lista"),
+    // Actions
+    ("Quit", "Salir"),
+    ("Prev Song", "Canción anterior"),
+    ("Next Song", "Canción siguiente"),
+    ("Pause", "Pausa"),
+    ("Vol Up", "Subir volumen"),
+    ("Vol Down", "Bajar volumen"),
+    ("Toggle Help", "Mostrar/ocultar ayuda"),
+    ("View Logs", "Ver registro"),
+    ("View Library", "Ver biblioteca"),
+    ("View Charts", "Ver éxitos"),
+    ("View Podcasts", "Ver podcasts"),
+    ("View Lyrics", "Ver letra"),
+    ("View Recently Played", "Ver reproducidas recientemente"),
+    ("View Favorites", "Ver favoritas"),
+    ("View Browser", "Ver explorador"),
+    ("View Playlist", "Ver lista"),
+    ("Toggle Split Layout", "Alternar vista dividida"),
+    ("New Browser Tab", "Nueva pestaña"),
+    ("Close Browser Tab", "Cerrar pestaña"),
+    ("Next Browser Tab", "Pestaña siguiente"),
+    ("Prev Browser Tab", "Pestaña anterior"),
+    ("Close", "Cerrar"),
+    ("Execute", "Ejecutar"),
+    ("Next Completion", "Sugerencia siguiente"),
+    ("Prev Completion", "Sugerencia anterior"),
+    ("Up", "Arriba"),
+    ("Down", "Abajo"),
+    ("Left", "Izquierda"),
+    ("Right", "Derecha"),
+    ("Page Up", "Página arriba"),
+    ("Page Down", "Página abajo"),
+    ("Top", "Inicio"),
+    ("Bottom", "Final"),
+    ("Like Current Song", "Me gusta la canción actual"),
+    ("Star Current Song", "Marcar canción actual como favorita"),
+    ("Star Selected", "Marcar selección como favorita"),
+    ("Star song", "Marcar canción como favorita"),
+    ("Dislike Current Song", "No me gusta la canción actual"),
+    ("Toggle Tasks", "Mostrar/ocultar tareas"),
+    ("Cancel Task", "Cancelar tarea"),
+    ("Toggle Notifications", "Mostrar/ocultar notificaciones"),
+    ("Yes", "Sí"),
+    ("No", "No"),
+    ("Open URL", "Abrir URL"),
+    ("Copy URL of Current Song", "Copiar URL de la canción actual"),
+    ("Widen Artist List", "Ensanchar lista de artistas"),
+    ("Narrow Artist List", "Estrechar lista de artistas"),
+    ("Widen Split Browser", "Ensanchar explorador dividido"),
+    ("Narrow Split Browser", "Estrechar explorador dividido"),
+    ("Undo Playlist Edit", "Deshacer cambio en la lista"),
+    ("Redo Playlist Edit", "Rehacer cambio en la lista"),
+    ("Play Selected", "Reproducir selección"),
+    ("Delete Selected", "Eliminar selección"),
+    ("Delete All", "Eliminar todo"),
+    ("Add Selected to YouTube Playlist", "Añadir selección a lista de YouTube"),
+    ("Like Selected", "Me gusta la selección"),
+    ("Dislike Selected", "No me gusta la selección"),
+    ("Apply Filter", "Aplicar filtro"),
+    ("Clear Filter", "Borrar filtro"),
+    ("Apply filter", "Aplicar filtro"),
+    ("Clear filter", "Borrar filtro"),
+    ("Copy URL of Selected", "Copiar URL de la selección"),
+    ("Open Selected in Web Browser", "Abrir selección en el navegador"),
+    ("Go to Album of Selected", "Ir al álbum de la selección"),
+    ("Go to Artist of Selected", "Ir al artista de la selección"),
+    ("Details of Selected", "Detalles de la selección"),
+    ("Song details", "Detalles de la canción"),
+    ("Song Details", "Detalles de la canción"),
+    ("Copy Field", "Copiar campo"),
+    ("Local Playlists", "Listas locales"),
+    ("Toggle Local Playlists", "Mostrar/ocultar listas locales"),
+    ("Save as: ", "Guardar como: "),
+    ("No saved playlists", "No hay listas guardadas"),
+    ("{} - {} songs", "{} - {} canciones"),
+    ("Load", "Cargar"),
+    ("Append to Playlist", "Añadir a la lista"),
+    ("Save Playlist", "Guardar lista"),
+    ("Delete", "Eliminar"),
+    // Setup
+    ("youtui setup", "Configuración de youtui"),
+    (
+        "No YouTube Music credentials were found.",
+        "No se encontraron credenciales de YouTube Music.",
+    ),
+    ("Choose how to sign in:", "Elige cómo iniciar sesión:"),
+    ("Browser cookie", "Cookie del navegador"),
+    (
+        "OAuth (command line only)",
+        "OAuth (solo línea de comandos)",
+    ),
+    ("Enter: select  Esc: quit", "Enter: elegir  Esc: salir"),
+    (
+        "Go to {} and enter the code {}",
+        "Ve a {} e introduce el código {}",
+    ),
+    (
+        "{} Waiting for you to sign in",
+        "{} Esperando a que inicies sesión",
+    ),
+    ("Esc: back", "Esc: volver"),
+    (
+        "Open YouTube Music in your web browser while signed in.",
+        "Abre YouTube Music en tu navegador con la sesión iniciada.",
+    ),
+    (
+        "Copy the Cookie header of a POST request to music.youtube.com.",
+        "Copia la cabecera Cookie de una petición POST a music.youtube.com.",
+    ),
+    ("Cookie: {} characters", "Cookie: {} caracteres"),
+    (
+        "Paste the cookie, then Enter: save  Esc: back",
+        "Pega la cookie, luego Enter: guardar  Esc: volver",
+    ),
+    ("{} Checking cookie", "{} Comprobando cookie"),
+    ("Setup failed: {}", "Error de configuración: {}"),
+    ("Sign In Failed", "Error al iniciar sesión"),
+    ("Retry Sign In", "Reintentar inicio de sesión"),
+    ("Re-run Setup", "Repetir configuración"),
+    ("{} Signing in", "{} Iniciando sesión"),
+    (
+        "r: retry  s: re-run setup  F10: quit",
+        "r: reintentar  s: repetir configuración  F10: salir",
+    ),
+    (
+        "Your YouTube Music credentials may be invalid or have expired.",
+        "Tus credenciales de YouTube Music pueden no ser válidas o haber caducado.",
+    ),
+    (
+        "Update cookie.txt in the config directory and retry, or sign in again.",
+        "Actualiza cookie.txt en la configuración y reintenta, o inicia sesión de nuevo.",
+    ),
+    ("Unknown", "Desconocido"),
+    ("Liked", "Me gusta"),
+    ("Disliked", "No me gusta"),
+    ("Not rated", "Sin valorar"),
+    ("Not downloaded", "No descargada"),
+    ("Queued", "En cola"),
+    ("Downloading {}%", "Descargando {}%"),
+    ("Downloaded", "Descargada"),
+    ("Download failed", "Descarga fallida"),
+    ("Title", "Título"),
+    ("Artist ID", "ID del artista"),
+    ("Album", "Álbum"),
+    ("Album ID", "ID del álbum"),
+    ("Year", "Año"),
+    ("Duration", "Duración"),
+    ("Explicit", "Explícita"),
+    ("Video ID", "ID del vídeo"),
+    ("Like status", "Valoración"),
+    ("Download status", "Estado de descarga"),
+    ("Bitrate", "Tasa de bits"),
+    ("Retry Download of Selected", "Reintentar descarga"),
+    ("Error downloading {}: {}", "Error al descargar {}: {}"),
+    ("Export Selected to Music Folder", "Exportar selección"),
+    ("Export song to music folder", "Exportar canción"),
+    ("Export album to music folder", "Exportar álbum"),
+    ("Collapse/expand album", "Contraer/expandir álbum"),
+    (
+        "Collapse/expand all albums",
+        "Contraer/expandir todos los álbumes",
+    ),
+    ("{} songs", "{} canciones"),
+    ("Export", "Exportar"),
+    ("Exporting {} songs", "Exportando {} canciones"),
+    ("Exported {}", "Exportada {}"),
+    ("Error exporting {}: {}", "Error al exportar {}: {}"),
+    ("Timed out", "Tiempo agotado"),
+    ("{} timed out", "{} agotó el tiempo de espera"),
+    (
+        "Casting is disabled, set experimental_cast in the config file",
+        "La transmisión está desactivada, activa experimental_cast en el archivo de configuración",
+    ),
+    (
+        "Searching for cast devices",
+        "Buscando dispositivos de transmisión",
+    ),
+    (
+        "No matching cast device, run :cast to search for devices",
+        "Ningún dispositivo coincide, ejecuta :cast para buscar dispositivos",
+    ),
+    (
+        "No cast devices found",
+        "No se encontraron dispositivos de transmisión",
+    ),
+    ("Cast devices: {}", "Dispositivos de transmisión: {}"),
+    ("Casting to {}", "Transmitiendo a {}"),
+    ("Error casting: {}", "Error al transmitir: {}"),
+    (
+        "Disconnected from cast device",
+        "Desconectado del dispositivo de transmisión",
+    ),
+    ("Open in Web Browser", "Abrir en el navegador"),
+    ("Toggle Search", "Mostrar/ocultar búsqueda"),
+    ("Refresh", "Actualizar"),
+    ("Open", "Abrir"),
+    ("Add episode to playlist", "Añadir episodio a la lista"),
+    ("Add all episodes to playlist", "Añadir todos los episodios a la lista"),
+    ("Toggle Auto Scroll", "Alternar desplazamiento automático"),
+    ("Toggle Target Selector Widget", "Mostrar/ocultar selector de objetivos"),
+    ("Toggle Focus Selected Target", "Enfocar objetivo seleccionado"),
+    ("Toggle Hide Filtered Targets", "Ocultar objetivos filtrados"),
+    ("Up - Selector", "Arriba - Selector"),
+    ("Down - Selector", "Abajo - Selector"),
+    ("Enter Page Mode, Scroll History Up", "Entrar en modo página, historial arriba"),
+    ("In Page Mode: Scroll History Down", "En modo página: historial abajo"),
+    ("Reduce SHOWN (!) Messages", "Reducir mensajes MOSTRADOS (!)"),
+    ("Increase SHOWN (!) Messages", "Aumentar mensajes MOSTRADOS (!)"),
+    ("Reduce CAPTURED (!) Messages", "Reducir mensajes CAPTURADOS (!)"),
+    ("Increase CAPTURED (!) Messages", "Aumentar mensajes CAPTURADOS (!)"),
+    ("Exit Page Mode", "Salir del modo página"),
+    ("Pause/Follow Log", "Pausar/seguir registro"),
+    ("Cycle Captured Level", "Cambiar nivel capturado"),
+    ("Cycle Captured Target", "Cambiar objetivo capturado"),
+    ("Queue all", "Encolar todo"),
+    ("Next chart", "Lista siguiente"),
+    ("Prev chart", "Lista anterior"),
+    ("Play all", "Reproducir todo"),
+    ("Add song to playlist", "Añadir canción a la lista"),
+    ("Add all to playlist", "Añadir todo a la lista"),
+    ("Select country", "Seleccionar país"),
+    ("Display all songs in browser", "Mostrar todas las canciones en el explorador"),
+    ("Next tab", "Pestaña siguiente"),
+    ("Prev tab", "Pestaña anterior"),
+    ("Play / Open", "Reproducir / Abrir"),
+    ("Add to playlist", "Añadir a la lista"),
+    ("Play all top songs", "Reproducir las canciones principales"),
+    ("Add all top songs to playlist", "Añadir las canciones principales a la lista"),
+    ("Search", "Buscar"),
+    ("Display albums for selected artist", "Mostrar álbumes del artista"),
+    ("Display details for selected artist", "Mostrar detalles del artista"),
+    ("Subscribe/Unsubscribe to selected artist", "Suscribirse/desuscribirse del artista"),
+    ("Next Search Suggestion", "Sugerencia de búsqueda siguiente"),
+    ("Prev Search Suggestion", "Sugerencia de búsqueda anterior"),
+    ("Accept Search Suggestion", "Aceptar sugerencia de búsqueda"),
+    ("Next Search Filter / Accept Suggestion", "Filtro de búsqueda siguiente / Aceptar sugerencia"),
+    ("Prev Search Filter", "Filtro de búsqueda anterior"),
+    ("Play song", "Reproducir canción"),
+    ("Play songs", "Reproducir canciones"),
+    ("Play album", "Reproducir álbum"),
+    ("Add songs to playlist", "Añadir canciones a la lista"),
+    ("Add album to playlist", "Añadir álbum a la lista"),
+    ("Add song to YouTube playlist", "Añadir canción a lista de YouTube"),
+    ("Close sort", "Cerrar ordenación"),
+    ("Clear sort", "Borrar ordenación"),
+    ("Sort ascending", "Orden ascendente"),
+    ("Sort descending", "Orden descendente"),
+    ("Toggle sort direction", "Cambiar dirección de orden"),
+    // Request categories
+    ("Get songs", "Obtener canciones"),
+    ("Get artist details", "Obtener detalles del artista"),
+    ("Get album songs", "Obtener canciones del álbum"),
+    ("Queue playlist", "Poner lista en cola"),
+    ("Get library", "Obtener biblioteca"),
+    ("Get charts", "Obtener listas de éxitos"),
+    ("Get library podcasts", "Obtener podcasts de la biblioteca"),
+    ("Get podcast episodes", "Obtener episodios del podcast"),
+    ("Queue podcast", "Poner podcast en cola"),
+    ("Get lyrics", "Obtener letra"),
+    ("Get library playlists", "Obtener listas de la biblioteca"),
+    ("Add song to playlist", "Añadir canción a lista"),
+    ("Rate song", "Valorar canción"),
+    (
+        "Get library subscriptions",
+        "Obtener suscripciones de la biblioteca",
+    ),
+    (
+        "Change artist subscription",
+        "Cambiar suscripción al artista",
+    ),
+    ("Download song", "Descargar canción"),
+    ("Get album art", "Obtener carátula"),
+    ("Export song", "Exportar canción"),
+    ("Get search suggestions", "Obtener sugerencias de búsqueda"),
+    ("Get volume", "Obtener volumen"),
+    ("Get play progress", "Obtener progreso de reproducción"),
+    ("Change volume", "Cambiar volumen"),
+    ("Play/pause", "Reproducir/pausar"),
+    ("Find cast devices", "Buscar dispositivos de transmisión"),
+    ("Cast queue", "Transmitir cola"),
+    // Notifications
+    ("Nothing to undo", "Nada que deshacer"),
+    ("Nothing to redo", "Nada que rehacer"),
+    (
+        "Signed in to YouTube Music",
+        "Sesión iniciada en YouTube Music",
+    ),
+    (
+        "Unable to get library subscriptions",
+        "No se pudieron obtener las suscripciones de la biblioteca",
+    ),
+    ("Subscribed to artist", "Suscrito al artista"),
+    (
+        "Unsubscribed from artist",
+        "Suscripción al artista cancelada",
+    ),
+    (
+        "Unable to change artist subscription",
+        "No se pudo cambiar la suscripción al artista",
+    ),
+    (
+        "Unable to get playlist songs",
+        "No se pudieron obtener las canciones de la lista",
+    ),
+    (
+        "Unable to queue playlist",
+        "No se pudo poner en cola la lista",
+    ),
+    ("Unable to search for artists", "No se pudo buscar artistas"),
+    ("Unable to get library", "No se pudo obtener la biblioteca"),
+    (
+        "Unable to get charts",
+        "No se pudieron obtener las listas de éxitos",
+    ),
+    (
+        "Unable to get artist details",
+        "No se pudieron obtener los detalles del artista",
+    ),
+    (
+        "Unable to get library podcasts",
+        "No se pudieron obtener los podcasts de la biblioteca",
+    ),
+    (
+        "Unable to get podcast episodes",
+        "No se pudieron obtener los episodios del podcast",
+    ),
+    (
+        "Unable to queue podcast",
+        "No se pudo poner en cola el podcast",
+    ),
+    ("Unable to get lyrics", "No se pudo obtener la letra"),
+    (
+        "Enter a name to save the playlist as",
+        "Introduce un nombre para guardar la lista",
+    ),
+    ("Playlist is empty", "La lista está vacía"),
+    (
+        "Unable to get library playlists",
+        "No se pudieron obtener las listas de la biblioteca",
+    ),
+    ("Song added to playlist", "Canción añadida a la lista"),
+    (
+        "Unable to add song to playlist",
+        "No se pudo añadir la canción a la lista",
+    ),
+    ("Unable to rate song", "No se pudo valorar la canción"),
+    ("Unable to search", "No se pudo buscar"),
+    (
+        "Unable to get album songs",
+        "No se pudieron obtener las canciones del álbum",
+    ),
+    ("Replaced playlist {}", "Lista {} reemplazada"),
+    ("Saved playlist {}", "Lista {} guardada"),
+    (
+        "Unable to save playlist {}: {}",
+        "No se pudo guardar la lista {}: {}",
+    ),
+    ("Loaded playlist {}", "Lista {} cargada"),
+    ("Appended playlist {}", "Lista {} añadida"),
+    ("Copied {} to clipboard", "{} copiado al portapapeles"),
+    (
+        "Unable to copy to clipboard: {}",
+        "No se pudo copiar al portapapeles: {}",
+    ),
+    ("Opened {} in web browser", "{} abierto en el navegador"),
+    (
+        "Unable to open web browser: {}",
+        "No se pudo abrir el navegador: {}",
+    ),
+    ("Deleted playlist {}", "Lista {} eliminada"),
+    (
+        "Unable to delete playlist {}: {}",
+        "No se pudo eliminar la lista {}: {}",
+    ),
+    ("Command line error: {}", "Error de línea de comandos: {}"),
+    (
+        "Command line error: unknown command <{}>",
+        "Error de línea de comandos: comando desconocido <{}>",
+    ),
+    ("Starred {}", "{} marcada como favorita"),
+    ("Unstarred {}", "{} quitada de favoritas"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_template, translate, Locale, SPANISH};
+
+    #[test]
+    fn test_translate_falls_back_to_english() {
+        assert_eq!(translate(Locale::English, "Quit"), "Quit");
+        assert_eq!(translate(Locale::Spanish, "Quit"), "Salir");
+        assert_eq!(translate(Locale::Spanish, "Not translated"), "Not translated");
+    }
+    #[test]
+    fn test_fill_template() {
+        assert_eq!(
+            fill_template("Songs - {} results - {}", &[&5, &"loading"]),
+            "Songs - 5 results - loading"
+        );
+        assert_eq!(fill_template("{} missing", &[]), " missing");
+    }
+    #[test]
+    fn test_translations_keep_placeholders() {
+        for (english, translated) in SPANISH {
+            assert_eq!(
+                english.matches("{}").count(),
+                translated.matches("{}").count(),
+                "{english}"
+            );
+        }
+    }
+}
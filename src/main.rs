@@ -3,21 +3,30 @@ mod appevent;
 mod cli;
 mod config;
 mod core;
+mod demo;
 mod drawutils;
+mod i18n;
+mod remote;
+mod setup;
 pub mod error;
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use cli::handle_cli_command;
 use config::{ApiKey, Config};
 use directories::ProjectDirs;
 use error::Error;
 pub use error::Result;
 use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::OnceLock;
+use std::time::SystemTime;
 use ytmapi_rs::auth::{BrowserToken, OAuthToken};
 
 pub const COOKIE_FILENAME: &str = "cookie.txt";
 pub const OAUTH_FILENAME: &str = "oauth.json";
 
+static PATH_OVERRIDES: OnceLock<PathOverrides> = OnceLock::new();
+
 #[derive(Parser, Debug)]
 #[command(author,version,about,long_about=None)]
 /// A text-based user interface for YouTube Music.
@@ -25,6 +34,23 @@ struct Arguments {
     /// Display and log additional debug information.
     #[arg(short, long, default_value_t = false)]
     debug: bool,
+    /// Most verbose level of messages written to the log file in the data directory. Takes
+    /// priority over the config file. Defaults to Debug if --debug is given.
+    #[arg(long, value_enum, global = true)]
+    log_level: Option<config::LogLevel>,
+    /// Answer queries with saved responses from the ytmapi-rs test fixtures instead of
+    /// YouTube Music, so no sign in is needed.
+    #[arg(long, default_value_t = false, global = true)]
+    demo: bool,
+    /// Print a man page for youtui, e.g for packaging.
+    #[arg(long, default_value_t = false)]
+    generate_man: bool,
+    #[command(flatten)]
+    paths: PathOverrides,
+    /// Change a setting for this run, e.g --set tick_rate_ms=100. Takes priority over the
+    /// config file and environment variables such as YOUTUI_TICK_RATE_MS.
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    config_overrides: Vec<config::ConfigOverride>,
     // What happens if given both cli and auth_cmd?
     #[command(flatten)]
     cli: Cli,
@@ -32,15 +58,67 @@ struct Arguments {
     auth_cmd: Option<AuthCmd>,
 }
 
+/// Locations to use instead of the defaults, so that separate configurations can coexist.
+#[derive(Args, Debug, Clone, Default)]
+struct PathOverrides {
+    /// Directory of the config file and credentials. Takes priority over YOUTUI_CONFIG_DIR.
+    #[arg(long, global = true)]
+    config_dir: Option<PathBuf>,
+    /// File containing the browser auth headers, instead of cookie.txt in the config directory.
+    #[arg(long, global = true)]
+    auth_headers: Option<PathBuf>,
+    /// File containing the OAuth token, instead of oauth.json in the config directory.
+    #[arg(long, global = true)]
+    oauth_token: Option<PathBuf>,
+}
+
 #[derive(Args, Debug, Clone)]
 // Probably shouldn't be public
 pub struct Cli {
-    /// Print the source output Json from YouTube Music's API instead of the processed value.
-    #[arg(short, long, default_value_t = false)]
-    show_source: bool,
+    /// How to print the output of the command.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+    /// Don't print errors. The exit code still gives the kind of error.
+    #[arg(short, long, global = true, default_value_t = false)]
+    quiet: bool,
+    /// Print errors to stderr as json, e.g {"kind": "auth", "exit_code": 3, "message": "..."}.
+    #[arg(long, global = true, default_value_t = false, conflicts_with = "quiet")]
+    errors_json: bool,
     #[command(subcommand)]
     command: Option<Commands>,
 }
+impl Cli {
+    fn get_error_output(&self) -> ErrorOutput {
+        if self.errors_json {
+            ErrorOutput::Json
+        } else if self.quiet {
+            ErrorOutput::Quiet
+        } else {
+            ErrorOutput::Text
+        }
+    }
+}
+/// How errors are printed to stderr.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ErrorOutput {
+    Text,
+    Json,
+    Quiet,
+}
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+enum OutputFormat {
+    /// The processed value, for reading.
+    #[default]
+    Plain,
+    /// The processed value as json.
+    Json,
+    /// The processed value as json, with each item of a list on its own line.
+    Jsonl,
+    /// The processed value as tab separated values, with each item of a list on its own row.
+    Tsv,
+    /// The source json from YouTube Music's API instead of the processed value.
+    Source,
+}
 #[derive(Subcommand, Debug, Clone)]
 enum AuthCmd {
     /// Generate an OAuth token.
@@ -48,13 +126,61 @@ enum AuthCmd {
         /// Optional: Write to a file.
         file_name: Option<PathBuf>,
     },
+    /// Check or refresh the saved OAuth token.
+    Oauth {
+        #[command(subcommand)]
+        command: OAuthCmd,
+    },
+    /// Find, create or check the config file.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCmd,
+    },
+    /// Check the config, credentials, connection and audio output, and how to fix any problems.
+    Doctor,
+    /// Control youtui running in another terminal.
+    Remote {
+        #[command(subcommand)]
+        command: remote::RemoteCommand,
+    },
+}
+#[derive(Subcommand, Debug, Clone)]
+enum OAuthCmd {
+    /// Print when the saved OAuth token expires, its scope and the account it belongs to.
+    Status,
+    /// Refresh the saved OAuth token, saving it over the old one.
+    Refresh,
+}
+#[derive(Subcommand, Debug, Clone)]
+enum ConfigCmd {
+    /// Print the config and data directories, and which files are in them.
+    Paths,
+    /// Write a config file with every setting at its default, commented out.
+    Init {
+        /// Overwrite an existing config file.
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+    /// Check the config file for errors.
+    Check,
 }
 #[derive(Subcommand, Debug, Clone)]
 enum Commands {
     GetSearchSuggestions { query: String },
     GetArtist { channel_id: String },
+    GetAlbum { browse_id: String },
+    GetPlaylist { playlist_id: String },
+    GetLyrics { video_id: String },
+    Play { id_or_link: String },
+    CreatePlaylist(CreatePlaylistArgs),
+    AddToPlaylist(AddToPlaylistArgs),
+    Rate(RateArgs),
+    RatePlaylist(RatePlaylistArgs),
     GetLibraryPlaylists,
     GetLibraryArtists, //TODO: Allow sorting
+    GetLibrarySubscriptions,
+    GetLibraryPodcasts,
+    History(HistoryArgs),
     Search { query: String },
     SearchArtists { query: String },
     SearchAlbums { query: String },
@@ -67,35 +193,113 @@ enum Commands {
     SearchProfiles { query: String },
     SearchPodcasts { query: String },
 }
+#[derive(Args, Debug, Clone)]
+struct CreatePlaylistArgs {
+    title: String,
+    /// File of video ids or links to add, one per line, or - to read them from stdin.
+    ids_file: PathBuf,
+}
+#[derive(Args, Debug, Clone)]
+struct AddToPlaylistArgs {
+    /// Id of or link to a playlist owned by the user.
+    playlist_id: String,
+    /// File of video ids or links to add, one per line, or - to read them from stdin.
+    ids_file: PathBuf,
+}
+#[derive(Args, Debug, Clone)]
+struct RateArgs {
+    /// Id of or link to a song, or - to read them from stdin, one per line.
+    video_id: String,
+    #[arg(value_enum)]
+    rating: Rating,
+}
+#[derive(Args, Debug, Clone)]
+struct RatePlaylistArgs {
+    /// Id of or link to a playlist or album, or - to read them from stdin, one per line.
+    playlist_id: String,
+    #[arg(value_enum)]
+    rating: Rating,
+}
+#[derive(Args, Debug, Clone)]
+struct HistoryArgs {
+    /// Remove a song from history, instead of listing it. Takes the song's video id or link.
+    #[arg(long, value_name = "VIDEO_ID")]
+    remove: Option<String>,
+    /// List the songs that would be removed, without removing them.
+    #[arg(long, requires = "remove")]
+    dry_run: bool,
+}
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum Rating {
+    Like,
+    Dislike,
+    /// Remove the rating.
+    None,
+}
 
 pub struct RuntimeInfo {
     _debug: bool,
     config: Config,
     api_key: ApiKey,
+    log_level: config::LogLevel,
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
+    let args = Arguments::parse();
+    let error_output = args.cli.get_error_output();
     // Using try block to print error using Display instead of Debug.
-    if let Err(e) = try_main().await {
-        println!("{e}");
-        // XXX: Return error code?
+    let Err(e) = try_main(args).await else {
+        return ExitCode::SUCCESS;
     };
+    match error_output {
+        ErrorOutput::Text => eprintln!("{e}"),
+        ErrorOutput::Json => eprintln!("{}", e.to_json()),
+        ErrorOutput::Quiet => (),
+    }
+    ExitCode::from(e.get_kind().exit_code())
 }
 
 // Main function is refactored here so that we can pretty print errors.
 // Regular main function returns debug errors so not as friendly.
-async fn try_main() -> Result<()> {
-    let args = Arguments::parse();
+async fn try_main(args: Arguments) -> Result<()> {
     let Arguments {
         debug,
+        log_level,
+        demo,
+        generate_man,
+        paths,
+        config_overrides,
         cli,
         auth_cmd,
     } = args;
-    // We don't need configuration to setup oauth token.
+    // Set before anything reads the config directory or credentials.
+    let _ = PATH_OVERRIDES.set(paths);
+    config::set_cli_overrides(config_overrides);
+    if generate_man {
+        return cli::write_man_page(Arguments::command(), &mut std::io::stdout());
+    }
+    // We don't need configuration to setup oauth token, or to manage the config file itself.
     if let Some(c) = auth_cmd {
         match c {
             AuthCmd::SetupOauth { file_name } => cli::get_and_output_oauth_token(file_name).await?,
+            AuthCmd::Oauth {
+                command: OAuthCmd::Status,
+            } => cli::print_oauth_status().await?,
+            AuthCmd::Oauth {
+                command: OAuthCmd::Refresh,
+            } => cli::refresh_oauth_token().await?,
+            AuthCmd::Config {
+                command: ConfigCmd::Paths,
+            } => cli::print_config_paths()?,
+            AuthCmd::Config {
+                command: ConfigCmd::Init { force },
+            } => cli::write_default_config(force).await?,
+            AuthCmd::Config {
+                command: ConfigCmd::Check,
+            } => cli::print_config_problems().await?,
+            AuthCmd::Remote { command } => cli::send_remote_command(command).await?,
+            AuthCmd::Doctor => cli::run_doctor().await?,
         };
         // Done here if we got this command. No need to go further.
         return Ok(());
@@ -103,16 +307,41 @@ async fn try_main() -> Result<()> {
     // Config and API key files will be in OS directories.
     // Create them if they don't exist.
     initialise_directories().await?;
-    let config = config::Config::new()?;
+    let mut config = config::Config::new()?;
+    // On first launch, guide the user through signing in before starting the app.
+    if !demo && cli.command.is_none() && !credentials_exist()? {
+        match setup::run_setup_wizard(&config).await? {
+            Some(auth_type) => config.save_auth_type(auth_type)?,
+            None => return Ok(()),
+        }
+    }
     // Once config has loaded, load API key to memory
     // (Which key to load depends on configuration)
     // XXX: check that this won't cause any delays.
     // TODO: Remove delay, should be handled inside app instead.
-    let api_key = load_api_key(&config).await?;
+    let api_key = if demo {
+        ApiKey::Demo
+    } else {
+        match load_api_key(&config).await {
+            // The credentials may be unreadable, so give the user the chance to sign in again.
+            Err(e) if cli.command.is_none() && e.is_auth_error() => {
+                let Some(auth_type) = setup::run_setup_wizard(&config).await? else {
+                    return Ok(());
+                };
+                config.save_auth_type(auth_type)?;
+                load_api_key(&config).await?
+            }
+            api_key => api_key?,
+        }
+    };
+    let log_level = log_level
+        .or(debug.then_some(config::LogLevel::Debug))
+        .unwrap_or(config.get_log_level());
     let rt = RuntimeInfo {
         _debug: debug,
         config,
         api_key,
+        log_level,
     };
     match cli.command {
         None => run_app(rt).await?,
@@ -121,28 +350,14 @@ async fn try_main() -> Result<()> {
     Ok(())
 }
 
-async fn get_api(config: &Config) -> Result<ytmapi_rs::YtMusic<BrowserToken>> {
-    let confdir = get_config_dir()?;
-    let api = match config.get_auth_type() {
-        config::AuthType::OAuth =>
-        // TODO: Add OAutho back in
-        {
-            unimplemented!()
-        }
-        // {
-        //     let mut oauth_loc = PathBuf::from(confdir);
-        //     oauth_loc.push(OAUTH_FILENAME);
-        //     let file = tokio::fs::read_to_string(oauth_loc).await?;
-        //     let oath_tok = serde_json::from_str(&file)?;
-        //     ytmapi_rs::YtMusic::from_oauth_token(oath_tok)
-        // }
-        config::AuthType::Browser => {
-            let mut cookies_loc = PathBuf::from(confdir);
-            cookies_loc.push(COOKIE_FILENAME);
-            ytmapi_rs::YtMusic::from_cookie_file(cookies_loc).await?
-        }
-    };
-    Ok(api)
+async fn get_api(api_key: &ApiKey) -> Result<ytmapi_rs::YtMusic<BrowserToken>> {
+    match api_key {
+        // TODO: Add OAuth back in
+        // ytmapi_rs::YtMusic::from_oauth_token(token)
+        ApiKey::OAuthToken(_) => Err(Error::OAuthNotYetSupportedByApp),
+        ApiKey::BrowserToken(cookie) => Ok(ytmapi_rs::YtMusic::from_cookie(cookie).await?),
+        ApiKey::Demo => Ok(demo::get_demo_api()),
+    }
 }
 
 pub async fn run_app(rt: RuntimeInfo) -> Result<()> {
@@ -151,7 +366,7 @@ pub async fn run_app(rt: RuntimeInfo) -> Result<()> {
     // TODO: Implement OAuth in the app.
     match &rt.api_key {
         ApiKey::OAuthToken(_) => return Err(Error::OAuthNotYetSupportedByApp),
-        ApiKey::BrowserToken(_) => (),
+        ApiKey::BrowserToken(_) | ApiKey::Demo => (),
     };
     let mut app = app::Youtui::new(rt)?;
     app.run().await?;
@@ -170,9 +385,23 @@ pub fn get_data_dir() -> Result<PathBuf> {
     Ok(directory)
 }
 
+/// Directory for files that can be downloaded again if removed, such as the music cache.
+pub fn get_cache_dir() -> Result<PathBuf> {
+    let directory = if let Ok(s) = std::env::var("YOUTUI_CACHE_DIR") {
+        PathBuf::from(s)
+    } else if let Some(proj_dirs) = ProjectDirs::from("com", "nick42", "youtui") {
+        proj_dirs.cache_dir().to_path_buf()
+    } else {
+        return Err(Error::DirectoryNameError);
+    };
+    Ok(directory)
+}
+
 pub fn get_config_dir() -> Result<PathBuf> {
     // TODO: Document that directory can be set by environment variable.
-    let directory = if let Ok(s) = std::env::var("YOUTUI_CONFIG_DIR") {
+    let directory = if let Some(dir) = PATH_OVERRIDES.get().and_then(|p| p.config_dir.clone()) {
+        dir
+    } else if let Ok(s) = std::env::var("YOUTUI_CONFIG_DIR") {
         PathBuf::from(s)
     } else if let Some(proj_dirs) = ProjectDirs::from("com", "nick42", "youtui") {
         proj_dirs.config_local_dir().to_path_buf()
@@ -182,9 +411,29 @@ pub fn get_config_dir() -> Result<PathBuf> {
     Ok(directory)
 }
 
+/// Path of the browser auth headers, from the command line or in the config directory.
+pub fn get_cookie_file_path() -> Result<PathBuf> {
+    match PATH_OVERRIDES.get().and_then(|p| p.auth_headers.clone()) {
+        Some(path) => Ok(path),
+        None => Ok(get_config_dir()?.join(COOKIE_FILENAME)),
+    }
+}
+
+/// Path of the OAuth token, from the command line or in the config directory.
+pub fn get_oauth_file_path() -> Result<PathBuf> {
+    match PATH_OVERRIDES.get().and_then(|p| p.oauth_token.clone()) {
+        Some(path) => Ok(path),
+        None => Ok(get_config_dir()?.join(OAUTH_FILENAME)),
+    }
+}
+
+/// True if a cookie or OAuth token has been saved.
+fn credentials_exist() -> Result<bool> {
+    Ok(get_cookie_file_path()?.exists() || get_oauth_file_path()?.exists())
+}
+
 async fn load_cookie_file() -> Result<String> {
-    let mut path = get_config_dir()?;
-    path.push(COOKIE_FILENAME);
+    let path = get_cookie_file_path()?;
     let file = tokio::fs::read_to_string(&path)
         .await
         .map_err(|e| Error::new_auth_token_error(config::AuthType::Browser, path, e));
@@ -192,8 +441,7 @@ async fn load_cookie_file() -> Result<String> {
 }
 
 async fn load_oauth_file() -> Result<OAuthToken> {
-    let mut path = get_config_dir()?;
-    path.push(OAUTH_FILENAME);
+    let path = get_oauth_file_path()?;
     let file = tokio::fs::read_to_string(&path)
         .await
         // TODO: Remove clone
@@ -202,21 +450,37 @@ async fn load_oauth_file() -> Result<OAuthToken> {
         .map_err(|_| Error::new_auth_token_parse_error(config::AuthType::OAuth, path))
 }
 
-/// Create the Config and Data directories for the app if they do not already exist.
+/// Create the Config, Data and Cache directories for the app if they do not already exist.
 /// Returns an error if unsuccesful.
 async fn initialise_directories() -> Result<()> {
     let config_dir = get_config_dir()?;
     let data_dir = get_data_dir()?;
+    let cache_dir = get_cache_dir()?;
     tokio::fs::create_dir_all(config_dir).await?;
     tokio::fs::create_dir_all(data_dir).await?;
+    tokio::fs::create_dir_all(cache_dir).await?;
     Ok(())
 }
 
+/// Load the credentials for the configured auth type. If they are missing or have expired,
+/// the other type is used instead if it is available.
 async fn load_api_key(cfg: &Config) -> Result<ApiKey> {
-    // TODO: Better error hanadling
     let api_key = match cfg.get_auth_type() {
-        config::AuthType::OAuth => ApiKey::OAuthToken(load_oauth_file().await?),
-        config::AuthType::Browser => ApiKey::BrowserToken(load_cookie_file().await?),
+        config::AuthType::OAuth => match load_oauth_file().await {
+            Ok(token) if token.get_expiry() > SystemTime::now() => ApiKey::OAuthToken(token),
+            preferred => match load_cookie_file().await {
+                Ok(cookie) => ApiKey::BrowserToken(cookie),
+                // An expired token can still be refreshed, so isn't an error yet.
+                Err(_) => ApiKey::OAuthToken(preferred?),
+            },
+        },
+        config::AuthType::Browser => match load_cookie_file().await {
+            Ok(cookie) => ApiKey::BrowserToken(cookie),
+            Err(e) => match load_oauth_file().await {
+                Ok(token) if token.get_expiry() > SystemTime::now() => ApiKey::OAuthToken(token),
+                _ => return Err(e),
+            },
+        },
     };
     Ok(api_key)
 }
@@ -0,0 +1,260 @@
+//! Control of a running youtui from another process, e.g `youtui remote next`.
+//! Each connection sends a single command on one line, and receives the reply as text.
+use crate::appevent::AppEvent;
+use crate::error::Error;
+use crate::Result;
+use clap::Subcommand;
+use serde::Serialize;
+use serde_json::json;
+use std::fmt::Display;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, oneshot};
+
+#[cfg(unix)]
+const REMOTE_SOCKET_FILENAME: &str = "youtui.sock";
+#[cfg(windows)]
+pub const REMOTE_PIPE_NAME: &str = r"\\.\pipe\youtui";
+pub mod web;
+
+/// Replies starting with this are errors, rather than the result of the command.
+const ERROR_REPLY_PREFIX: &str = "error: ";
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum RemoteCommand {
+    /// Pause the playing song, or resume it if paused.
+    PlayPause,
+    /// Play the next song.
+    Next,
+    /// Play the previous song.
+    Prev,
+    /// Change the volume, e.g +5 or -5.
+    Volume {
+        #[arg(allow_negative_numbers = true)]
+        step: i8,
+    },
+    /// Print what is playing and the volume.
+    Status,
+    /// Print the songs in the playlist.
+    Queue,
+}
+
+impl RemoteCommand {
+    fn parse(line: &str) -> std::result::Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let command = match (words.next(), words.next()) {
+            (Some("play-pause"), None) => RemoteCommand::PlayPause,
+            (Some("next"), None) => RemoteCommand::Next,
+            (Some("prev"), None) => RemoteCommand::Prev,
+            (Some("status"), None) => RemoteCommand::Status,
+            (Some("queue"), None) => RemoteCommand::Queue,
+            (Some("volume"), Some(step)) => RemoteCommand::Volume {
+                step: step
+                    .parse()
+                    .map_err(|_| format!("Invalid volume step <{step}>"))?,
+            },
+            _ => return Err(format!("Unknown command <{}>", line.trim())),
+        };
+        if words.next().is_some() {
+            return Err(format!("Unknown command <{}>", line.trim()));
+        }
+        Ok(command)
+    }
+    fn to_line(&self) -> String {
+        match self {
+            RemoteCommand::PlayPause => "play-pause".to_string(),
+            RemoteCommand::Next => "next".to_string(),
+            RemoteCommand::Prev => "prev".to_string(),
+            RemoteCommand::Volume { step } => format!("volume {step:+}"),
+            RemoteCommand::Status => "status".to_string(),
+            RemoteCommand::Queue => "queue".to_string(),
+        }
+    }
+}
+
+/// The app's reply to a command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteReply {
+    Done,
+    Status(RemoteStatus),
+    Queue(Vec<RemoteSong>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RemoteStatus {
+    /// E.g "Playing" or "Paused".
+    pub state: &'static str,
+    /// None if no song is playing.
+    pub song: Option<RemoteSong>,
+    pub elapsed_secs: usize,
+    pub volume: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RemoteSong {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub playing: bool,
+}
+
+impl RemoteReply {
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            RemoteReply::Done => json!({}),
+            RemoteReply::Status(status) => json!(status),
+            RemoteReply::Queue(songs) => json!(songs),
+        }
+    }
+}
+
+impl Display for RemoteSong {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} - {}", self.artists.join(", "), self.title)
+    }
+}
+
+impl Display for RemoteReply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteReply::Done => write!(f, "ok"),
+            RemoteReply::Status(RemoteStatus {
+                state,
+                song: Some(song),
+                elapsed_secs,
+                volume,
+            }) => write!(
+                f,
+                "{state}: {song} [{:02}:{:02}], volume {volume}%",
+                elapsed_secs / 60,
+                elapsed_secs % 60
+            ),
+            RemoteReply::Status(RemoteStatus { state, volume, .. }) => {
+                write!(f, "{state}, volume {volume}%")
+            }
+            RemoteReply::Queue(songs) if songs.is_empty() => write!(f, "Playlist is empty"),
+            RemoteReply::Queue(songs) => {
+                let lines = songs.iter().enumerate().map(|(idx, song)| {
+                    let marker = if song.playing { '>' } else { ' ' };
+                    format!("{marker} {}. {song}", idx + 1)
+                });
+                write!(f, "{}", lines.collect::<Vec<_>>().join("\n"))
+            }
+        }
+    }
+}
+
+/// A command received from another process, and where to send the reply once the app has
+/// handled it.
+#[derive(Debug)]
+pub struct RemoteRequest {
+    pub command: RemoteCommand,
+    pub reply_tx: oneshot::Sender<RemoteReply>,
+}
+
+#[cfg(unix)]
+pub fn get_remote_socket_path() -> Result<std::path::PathBuf> {
+    Ok(crate::get_data_dir()?.join(REMOTE_SOCKET_FILENAME))
+}
+
+/// Read a command from the connection, pass it to the app, and write back the app's reply.
+pub async fn handle_connection<S>(stream: S, tx: mpsc::Sender<AppEvent>) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut line = String::new();
+    BufReader::new(reader).read_line(&mut line).await?;
+    let reply = match RemoteCommand::parse(&line) {
+        Ok(command) => send_to_app(command, &tx).await?.to_string(),
+        Err(e) => format!("{ERROR_REPLY_PREFIX}{e}"),
+    };
+    writer.write_all(format!("{reply}\n").as_bytes()).await?;
+    writer.shutdown().await?;
+    Ok(())
+}
+
+/// Pass a command to the app, and wait for it to be handled.
+async fn send_to_app(command: RemoteCommand, tx: &mpsc::Sender<AppEvent>) -> Result<RemoteReply> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    tx.send(AppEvent::Remote(RemoteRequest { command, reply_tx }))
+        .await
+        .map_err(|_| Error::Communication)?;
+    reply_rx.await.map_err(|_| Error::Communication)
+}
+
+/// Send a command to the running app, returning its reply.
+pub async fn send_command(command: &RemoteCommand) -> Result<String> {
+    #[cfg(unix)]
+    let stream = tokio::net::UnixStream::connect(get_remote_socket_path()?).await;
+    #[cfg(windows)]
+    let stream = tokio::net::windows::named_pipe::ClientOptions::new().open(REMOTE_PIPE_NAME);
+    let mut stream = stream
+        .map_err(|e| Error::Other(format!("Unable to connect to youtui, is it running? {e}")))?;
+    stream
+        .write_all(format!("{}\n", command.to_line()).as_bytes())
+        .await?;
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).await?;
+    let reply = reply.trim_end();
+    match reply.strip_prefix(ERROR_REPLY_PREFIX) {
+        Some(e) => Err(Error::Other(e.to_string())),
+        None => Ok(reply.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RemoteCommand, RemoteReply, RemoteSong, RemoteStatus};
+
+    #[test]
+    fn test_parse_remote_command() {
+        for command in [
+            RemoteCommand::PlayPause,
+            RemoteCommand::Next,
+            RemoteCommand::Prev,
+            RemoteCommand::Volume { step: 5 },
+            RemoteCommand::Volume { step: -5 },
+            RemoteCommand::Status,
+            RemoteCommand::Queue,
+        ] {
+            assert_eq!(RemoteCommand::parse(&command.to_line()), Ok(command));
+        }
+        assert_eq!(
+            RemoteCommand::parse("volume +5\n"),
+            Ok(RemoteCommand::Volume { step: 5 })
+        );
+        assert!(RemoteCommand::parse("volume loud").is_err());
+        assert!(RemoteCommand::parse("next song").is_err());
+        assert!(RemoteCommand::parse("").is_err());
+    }
+    #[test]
+    fn test_display_remote_reply() {
+        let song = RemoteSong {
+            title: "Song".into(),
+            artists: vec!["Artist A".into(), "Artist B".into()],
+            album: "Album".into(),
+            playing: true,
+        };
+        let status = RemoteReply::Status(RemoteStatus {
+            state: "Playing",
+            song: Some(song.clone()),
+            elapsed_secs: 65,
+            volume: 50,
+        });
+        assert_eq!(
+            status.to_string(),
+            "Playing: Artist A, Artist B - Song [01:05], volume 50%"
+        );
+        let queue = RemoteReply::Queue(vec![
+            song.clone(),
+            RemoteSong {
+                playing: false,
+                ..song
+            },
+        ]);
+        assert_eq!(
+            queue.to_string(),
+            "> 1. Artist A, Artist B - Song\n  2. Artist A, Artist B - Song"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! A web remote, to control youtui from a browser, e.g on a phone once web_remote_address
+//! allows other devices. The page at "/" calls json endpoints under "/api", which require the
+//! token from config as a bearer token.
+use super::{send_to_app, RemoteCommand};
+use crate::appevent::AppEvent;
+use crate::error::Error;
+use crate::Result;
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+const WEB_REMOTE_PAGE: &str = include_str!("web/index.html");
+/// None of the endpoints take a body, so requests are never larger than their headers.
+const MAX_REQUEST_SIZE: usize = 8192;
+/// A client that doesn't finish sending its request in time is disconnected.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, PartialEq)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: Option<String>,
+    token: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Page,
+    Command(RemoteCommand),
+    BadRequest(&'static str),
+    Unauthorized,
+    NotFound,
+}
+
+/// Parse the request line and the Authorization header, ignoring anything else.
+fn parse_request(head: &str) -> Option<HttpRequest> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+    let token = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if !name.trim().eq_ignore_ascii_case("authorization") {
+            return None;
+        }
+        value.trim().strip_prefix("Bearer ").map(|t| t.to_string())
+    });
+    Some(HttpRequest {
+        method,
+        path,
+        query,
+        token,
+    })
+}
+
+fn route(request: &HttpRequest, token: &str) -> Route {
+    // The page doesn't need the token, as it asks for it.
+    if request.method == "GET" && request.path == "/" {
+        return Route::Page;
+    }
+    // Checked before anything else, so that a caller without the token learns nothing about
+    // the endpoints.
+    if !request
+        .token
+        .as_deref()
+        .is_some_and(|request_token| tokens_match(request_token, token))
+    {
+        return Route::Unauthorized;
+    }
+    let command = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/status") => RemoteCommand::Status,
+        ("GET", "/api/queue") => RemoteCommand::Queue,
+        ("POST", "/api/play-pause") => RemoteCommand::PlayPause,
+        ("POST", "/api/next") => RemoteCommand::Next,
+        ("POST", "/api/prev") => RemoteCommand::Prev,
+        ("POST", "/api/volume") => {
+            let step = request
+                .query
+                .as_deref()
+                .unwrap_or_default()
+                .split('&')
+                .find_map(|param| param.strip_prefix("step="))
+                .and_then(|step| step.parse().ok());
+            match step {
+                Some(step) => RemoteCommand::Volume { step },
+                None => return Route::BadRequest("Expected a volume step, e.g ?step=-5"),
+            }
+        }
+        _ => return Route::NotFound,
+    };
+    Route::Command(command)
+}
+
+/// Compare tokens in time that depends only on their length, so that the token can't be
+/// guessed a character at a time from how long requests take.
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn error_response(status: &str, message: &str) -> String {
+    response(
+        status,
+        "application/json",
+        &json!({ "error": message }).to_string(),
+    )
+}
+
+/// Read the request up to the end of its headers.
+async fn read_head(stream: &mut TcpStream) -> Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 || head.len() + read > MAX_REQUEST_SIZE {
+            return Err(Error::Other("Incomplete web remote request".into()));
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// Respond to a single request, then close the connection.
+pub async fn handle_connection(
+    mut stream: TcpStream,
+    token: String,
+    tx: mpsc::Sender<AppEvent>,
+) -> Result<()> {
+    let head = tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream))
+        .await
+        .map_err(|_| Error::Other("Timed out reading web remote request".into()))??;
+    let response = match parse_request(&head).map(|request| route(&request, &token)) {
+        Some(Route::Page) => response("200 OK", "text/html; charset=utf-8", WEB_REMOTE_PAGE),
+        Some(Route::Command(command)) => {
+            let reply = send_to_app(command, &tx).await?;
+            response("200 OK", "application/json", &reply.to_json().to_string())
+        }
+        Some(Route::BadRequest(message)) => error_response("400 Bad Request", message),
+        Some(Route::Unauthorized) => error_response("401 Unauthorized", "Invalid token"),
+        Some(Route::NotFound) => error_response("404 Not Found", "Not found"),
+        None => error_response("400 Bad Request", "Invalid request"),
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_request, route, tokens_match, HttpRequest, Route};
+    use crate::remote::RemoteCommand;
+
+    #[test]
+    fn test_parse_request() {
+        assert_eq!(
+            parse_request(
+                "POST /api/volume?step=-5 HTTP/1.1\r\nHost: youtui\r\nauthorization: Bearer abc\r\n\r\n"
+            ),
+            Some(HttpRequest {
+                method: "POST".into(),
+                path: "/api/volume".into(),
+                query: Some("step=-5".into()),
+                token: Some("abc".into()),
+            })
+        );
+        assert_eq!(parse_request("\r\n\r\n"), None);
+    }
+    #[test]
+    fn test_route() {
+        let request = |method: &str, target: &str, token: Option<&str>| {
+            let auth = token
+                .map(|t| format!("Authorization: Bearer {t}\r\n"))
+                .unwrap_or_default();
+            parse_request(&format!("{method} {target} HTTP/1.1\r\n{auth}\r\n")).unwrap()
+        };
+        assert_eq!(route(&request("GET", "/", None), "abc"), Route::Page);
+        assert_eq!(
+            route(&request("POST", "/api/next", Some("abc")), "abc"),
+            Route::Command(RemoteCommand::Next)
+        );
+        assert_eq!(
+            route(&request("POST", "/api/volume?step=5", Some("abc")), "abc"),
+            Route::Command(RemoteCommand::Volume { step: 5 })
+        );
+        assert_eq!(
+            route(&request("GET", "/api/status", Some("wrong")), "abc"),
+            Route::Unauthorized
+        );
+        assert_eq!(
+            route(&request("GET", "/api/status", None), "abc"),
+            Route::Unauthorized
+        );
+        assert_eq!(
+            route(&request("GET", "/api/next", Some("abc")), "abc"),
+            Route::NotFound
+        );
+        assert!(matches!(
+            route(&request("POST", "/api/volume", Some("abc")), "abc"),
+            Route::BadRequest(_)
+        ));
+        assert_eq!(
+            route(&request("POST", "/api/volume", None), "abc"),
+            Route::Unauthorized
+        );
+        assert_eq!(
+            route(&request("GET", "/api/unknown", None), "abc"),
+            Route::Unauthorized
+        );
+    }
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc", "abc"));
+        assert!(!tokens_match("abd", "abc"));
+        assert!(!tokens_match("ab", "abc"));
+        assert!(!tokens_match("", "abc"));
+    }
+}
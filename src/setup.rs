@@ -0,0 +1,391 @@
+use crate::appevent::{AppEvent, EventHandler};
+use crate::config::{AuthType, Config};
+use crate::drawutils::{self, advance_spinner};
+use crate::i18n;
+use crate::{get_cookie_file_path, get_oauth_file_path, Result};
+use crossterm::{
+    event::{DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use ytmapi_rs::auth::OAuthTokenGenerator;
+
+const EVENT_CHANNEL_SIZE: usize = 256;
+/// Auth types that can be chosen, in the order they are displayed.
+const AUTH_TYPES: [AuthType; 2] = [AuthType::Browser, AuthType::OAuth];
+
+/// Step of the setup wizard that is currently shown.
+#[derive(Clone, Debug, PartialEq)]
+enum SetupStep {
+    ChooseAuthType,
+    /// Waiting for the user to sign in at the verification url.
+    OAuthWaiting {
+        url: String,
+        user_code: String,
+    },
+    PasteCookie,
+    CheckingCookie,
+    Error(String),
+}
+
+/// Work the setup wizard needs done in response to a key press.
+#[derive(Debug, PartialEq)]
+enum SetupCommand {
+    RequestOAuthCode,
+    CheckCookie(String),
+    Quit,
+}
+
+/// Guided setup shown on first launch, when no credentials exist in the config directory.
+struct SetupWizard {
+    step: SetupStep,
+    /// Selected auth type on the first step.
+    cur: usize,
+    cookie: String,
+}
+
+impl Default for SetupWizard {
+    fn default() -> Self {
+        Self {
+            step: SetupStep::ChooseAuthType,
+            cur: 0,
+            cookie: String::new(),
+        }
+    }
+}
+
+impl SetupWizard {
+    fn handle_key(&mut self, key: KeyEvent) -> Option<SetupCommand> {
+        if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('c') {
+            return Some(SetupCommand::Quit);
+        }
+        match self.step {
+            SetupStep::ChooseAuthType => match key.code {
+                KeyCode::Up => self.cur = self.cur.saturating_sub(1),
+                KeyCode::Down => self.cur = (self.cur + 1).min(AUTH_TYPES.len() - 1),
+                KeyCode::Enter => match AUTH_TYPES[self.cur] {
+                    AuthType::Browser => self.step = SetupStep::PasteCookie,
+                    AuthType::OAuth => return Some(SetupCommand::RequestOAuthCode),
+                },
+                KeyCode::Esc => return Some(SetupCommand::Quit),
+                _ => (),
+            },
+            SetupStep::PasteCookie => match key.code {
+                KeyCode::Char(c) => self.cookie.push(c),
+                KeyCode::Backspace => {
+                    self.cookie.pop();
+                }
+                KeyCode::Enter if !self.cookie.trim().is_empty() => {
+                    self.step = SetupStep::CheckingCookie;
+                    return Some(SetupCommand::CheckCookie(self.cookie.trim().to_string()));
+                }
+                KeyCode::Esc => self.back(),
+                _ => (),
+            },
+            SetupStep::OAuthWaiting { .. } | SetupStep::CheckingCookie => {
+                if key.code == KeyCode::Esc {
+                    self.back()
+                }
+            }
+            SetupStep::Error(_) => {
+                if let KeyCode::Esc | KeyCode::Enter = key.code {
+                    self.back()
+                }
+            }
+        }
+        None
+    }
+    /// Pasted text is only accepted when entering the cookie.
+    fn handle_paste(&mut self, text: &str) {
+        if self.step == SetupStep::PasteCookie {
+            self.cookie.push_str(text.trim());
+        }
+    }
+    fn back(&mut self) {
+        self.step = SetupStep::ChooseAuthType;
+        self.cookie.clear();
+    }
+    /// True while waiting for credentials to be generated or checked.
+    fn is_waiting(&self) -> bool {
+        matches!(
+            self.step,
+            SetupStep::OAuthWaiting { .. } | SetupStep::CheckingCookie
+        )
+    }
+}
+
+/// Guide the user through signing in to YouTube Music inside the TUI, and write the credentials
+/// to the config directory.
+/// Returns the auth type that was set up, or None if the user quit.
+pub async fn run_setup_wizard(config: &Config) -> Result<Option<AuthType>> {
+    i18n::set_locale(config.get_locale());
+    drawutils::set_ascii_icons(config.get_ascii_icons());
+    drawutils::set_high_contrast(config.get_high_contrast());
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    let mut events = EventHandler::new(EVENT_CHANNEL_SIZE, config.get_tick_rate())?;
+    let result = run(&mut terminal, &mut events).await;
+    disable_raw_mode()?;
+    execute!(io::stdout(), DisableBracketedPaste, LeaveAlternateScreen)?;
+    result
+}
+
+/// Run the setup wizard in a terminal that has already been set up, e.g by the app when the
+/// credentials have expired. Bracketed paste should be enabled so that the cookie can be pasted.
+pub async fn run(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    events: &mut EventHandler,
+) -> Result<Option<AuthType>> {
+    let mut wizard = SetupWizard::default();
+    // Credentials are generated or checked in the background, so the wizard stays responsive.
+    let (result_tx, mut result_rx) = mpsc::channel::<Result<AuthType>>(1);
+    let mut task: Option<JoinHandle<()>> = None;
+    loop {
+        terminal.draw(|f| draw::draw_setup(f, &wizard))?;
+        let event = tokio::select! {
+            Some(result) = result_rx.recv() => {
+                // The user may have gone back since the task finished.
+                if wizard.is_waiting() {
+                    match result {
+                        Ok(auth_type) => return Ok(Some(auth_type)),
+                        Err(e) => wizard.step = SetupStep::Error(e.to_string()),
+                    }
+                }
+                continue;
+            }
+            event = events.next() => event,
+        };
+        let command = match event {
+            Some(AppEvent::Tick) => {
+                advance_spinner();
+                None
+            }
+            Some(AppEvent::QuitSignal) | None => return Ok(None),
+            Some(AppEvent::Crossterm(Event::Paste(text))) => {
+                wizard.handle_paste(&text);
+                None
+            }
+            Some(AppEvent::Crossterm(Event::Key(key))) => wizard.handle_key(key),
+            Some(AppEvent::Crossterm(_)) => None,
+            // Remote control is only started once the app is running.
+            Some(AppEvent::Remote(_)) => None,
+            // Setup doesn't use the config that could change, and it's short lived enough that
+            // job control can wait until the app is running.
+            Some(AppEvent::ConfigChanged | AppEvent::SuspendSignal | AppEvent::ContinueSignal) => {
+                None
+            }
+        };
+        // Going back cancels any credentials still being generated or checked.
+        if !wizard.is_waiting() {
+            if let Some(task) = task.take() {
+                task.abort();
+            }
+        }
+        let result_tx = result_tx.clone();
+        match command {
+            Some(SetupCommand::Quit) => return Ok(None),
+            Some(SetupCommand::RequestOAuthCode) => {
+                match ytmapi_rs::generate_oauth_token_generator().await {
+                    Ok(generator) => {
+                        wizard.step = SetupStep::OAuthWaiting {
+                            url: generator.verification_url.clone(),
+                            user_code: generator.user_code.clone(),
+                        };
+                        task = Some(tokio::spawn(async move {
+                            let result = get_and_write_oauth_token(generator).await;
+                            let _ = result_tx.send(result).await;
+                        }));
+                    }
+                    Err(e) => wizard.step = SetupStep::Error(e.to_string()),
+                }
+            }
+            Some(SetupCommand::CheckCookie(cookie)) => {
+                task = Some(tokio::spawn(async move {
+                    let result = check_and_write_cookie(cookie).await;
+                    let _ = result_tx.send(result).await;
+                }));
+            }
+            None => (),
+        }
+    }
+}
+
+/// Poll until the user has finished signing in, then write the OAuth token.
+async fn get_and_write_oauth_token(generator: OAuthTokenGenerator) -> Result<AuthType> {
+    let expires_at = Instant::now() + Duration::from_secs(generator.expires_in as u64);
+    let interval = Duration::from_secs(generator.interval.max(1) as u64);
+    let token = loop {
+        tokio::time::sleep(interval).await;
+        match ytmapi_rs::generate_oauth_token(generator.device_code.clone()).await {
+            Ok(token) => break token,
+            Err(e) if Instant::now() >= expires_at => return Err(e.into()),
+            // Requests fail until the user has signed in.
+            Err(_) => (),
+        }
+    };
+    let path = get_oauth_file_path()?;
+    tokio::fs::write(path, serde_json::to_string_pretty(&token)?).await?;
+    Ok(AuthType::OAuth)
+}
+
+/// Write the cookie, once it has been confirmed to work with YouTube Music.
+async fn check_and_write_cookie(cookie: String) -> Result<AuthType> {
+    ytmapi_rs::generate_browser_token(&cookie).await?;
+    let path = get_cookie_file_path()?;
+    tokio::fs::write(path, cookie).await?;
+    Ok(AuthType::Browser)
+}
+
+mod draw {
+    use super::{SetupStep, SetupWizard, AUTH_TYPES};
+    use crate::config::AuthType;
+    use crate::drawutils::{
+        apply_high_contrast, centered_rect, get_spinner, high_contrast, highlight_style,
+        SELECTED_BORDER_COLOUR, TABLE_HEADINGS_COLOUR, TEXT_COLOUR,
+    };
+    use crate::i18n::{tr, tr_format};
+    use ratatui::{
+        style::Style,
+        text::Line,
+        widgets::{Block, Borders, Clear, Paragraph, Wrap},
+        Frame,
+    };
+
+    const POPUP_WIDTH: u16 = 70;
+    const POPUP_HEIGHT: u16 = 12;
+
+    pub fn draw_setup(f: &mut Frame, w: &SetupWizard) {
+        let text_style = Style::new().fg(TEXT_COLOUR);
+        let hint_style = Style::new().fg(TABLE_HEADINGS_COLOUR);
+        let lines = match &w.step {
+            SetupStep::ChooseAuthType => {
+                let mut lines = vec![
+                    Line::styled(tr("No YouTube Music credentials were found."), text_style),
+                    Line::styled(tr("Choose how to sign in:"), text_style),
+                    Line::default(),
+                ];
+                lines.extend(AUTH_TYPES.iter().enumerate().map(|(i, auth_type)| {
+                    let label = match auth_type {
+                        AuthType::Browser => tr("Browser cookie"),
+                        AuthType::OAuth => tr("OAuth (command line only)"),
+                    };
+                    let style = if i == w.cur {
+                        highlight_style()
+                    } else {
+                        text_style
+                    };
+                    Line::styled(label, style)
+                }));
+                lines.push(Line::default());
+                lines.push(Line::styled(tr("Enter: select  Esc: quit"), hint_style));
+                lines
+            }
+            SetupStep::OAuthWaiting { url, user_code } => vec![
+                Line::styled(
+                    tr_format("Go to {} and enter the code {}", &[url, user_code]),
+                    text_style,
+                ),
+                Line::default(),
+                Line::styled(
+                    tr_format("{} Waiting for you to sign in", &[&get_spinner()]),
+                    text_style,
+                ),
+                Line::default(),
+                Line::styled(tr("Esc: back"), hint_style),
+            ],
+            SetupStep::PasteCookie => vec![
+                Line::styled(
+                    tr("Open YouTube Music in your web browser while signed in."),
+                    text_style,
+                ),
+                Line::styled(
+                    tr("Copy the Cookie header of a POST request to music.youtube.com."),
+                    text_style,
+                ),
+                Line::default(),
+                Line::styled(
+                    tr_format("Cookie: {} characters", &[&w.cookie.chars().count()]),
+                    text_style,
+                ),
+                Line::default(),
+                Line::styled(
+                    tr("Paste the cookie, then Enter: save  Esc: back"),
+                    hint_style,
+                ),
+            ],
+            SetupStep::CheckingCookie => vec![
+                Line::styled(
+                    tr_format("{} Checking cookie", &[&get_spinner()]),
+                    text_style,
+                ),
+                Line::default(),
+                Line::styled(tr("Esc: back"), hint_style),
+            ],
+            SetupStep::Error(e) => vec![
+                Line::styled(tr_format("Setup failed: {}", &[e]), text_style),
+                Line::default(),
+                Line::styled(tr("Esc: back"), hint_style),
+            ],
+        };
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(SELECTED_BORDER_COLOUR))
+                .title(tr("youtui setup")),
+        );
+        let area = centered_rect(POPUP_HEIGHT, POPUP_WIDTH, f.size());
+        f.render_widget(Clear, area);
+        f.render_widget(paragraph, area);
+        if high_contrast() {
+            apply_high_contrast(f.buffer_mut());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SetupCommand, SetupStep, SetupWizard};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+    #[test]
+    fn test_enter_cookie() {
+        let mut wizard = SetupWizard::default();
+        assert_eq!(wizard.handle_key(key(KeyCode::Enter)), None);
+        assert_eq!(wizard.step, SetupStep::PasteCookie);
+        // Enter is ignored until a cookie is entered.
+        assert_eq!(wizard.handle_key(key(KeyCode::Enter)), None);
+        wizard.handle_paste(" SID=abc \n");
+        wizard.handle_key(key(KeyCode::Char(';')));
+        assert_eq!(
+            wizard.handle_key(key(KeyCode::Enter)),
+            Some(SetupCommand::CheckCookie("SID=abc;".into()))
+        );
+        assert!(wizard.is_waiting());
+    }
+    #[test]
+    fn test_choose_oauth_and_go_back() {
+        let mut wizard = SetupWizard::default();
+        wizard.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            wizard.handle_key(key(KeyCode::Enter)),
+            Some(SetupCommand::RequestOAuthCode)
+        );
+        wizard.step = SetupStep::Error("Error".into());
+        wizard.handle_key(key(KeyCode::Esc));
+        assert_eq!(wizard.step, SetupStep::ChooseAuthType);
+        assert_eq!(
+            wizard.handle_key(key(KeyCode::Esc)),
+            Some(SetupCommand::Quit)
+        );
+    }
+}
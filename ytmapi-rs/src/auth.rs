@@ -1,6 +1,7 @@
 //! Available authorisation tokens.
 use self::private::Sealed;
-use crate::error::Result;
+use crate::common::Locale;
+use crate::error::{Error, Result};
 use crate::parse::ProcessedResult;
 use crate::{process::RawResult, query::Query};
 pub use browser::BrowserToken;
@@ -10,6 +11,13 @@ use reqwest::Client;
 pub mod browser;
 pub mod oauth;
 
+/// Hosts that requests are redirected to when Google wants cookie consent.
+const CONSENT_HOSTS: [&str; 2] = ["consent.youtube.com", "consent.google.com"];
+/// Path that requests are redirected to when Google wants a captcha to be completed.
+const CAPTCHA_PATH: &str = "google.com/sorry/";
+/// Path that YouTube Music redirects to in regions where it isn't available.
+const REGION_RESTRICTED_PATH: &str = "music.youtube.com/coming-soon";
+
 // Seal AuthToken for now, due to instability of async trait currently.
 mod private {
     pub trait Sealed {}
@@ -19,10 +27,65 @@ mod private {
 #[allow(async_fn_in_trait)]
 pub trait AuthToken: Sized + Sealed {
     // TODO: Continuations - as Stream?
+    /// Send the query. The query's locale takes priority over the locale given.
     async fn raw_query<'a, Q: Query>(
         &'a self,
         client: &Client,
         query: Q,
+        locale: &Locale,
     ) -> Result<RawResult<'a, Q, Self>>;
     fn serialize_json<Q: Query>(raw: RawResult<Q, Self>) -> Result<ProcessedResult<Q>>;
 }
+
+/// Instead of a response, YouTube may redirect to a web page that the user needs to act on, e.g
+/// a cookie consent wall. Returns an error for the page if so, given the url that was finally
+/// reached and the body received.
+fn check_for_blocking_page(url: &str, body: &str) -> Result<()> {
+    if CONSENT_HOSTS.iter().any(|host| url.contains(host))
+        || body.contains("action=\"https://consent.youtube.com/save\"")
+    {
+        return Err(Error::consent_required());
+    }
+    if url.contains(CAPTCHA_PATH) || body.contains("id=\"captcha-form\"") {
+        return Err(Error::captcha_required());
+    }
+    if url.contains(REGION_RESTRICTED_PATH) {
+        return Err(Error::region_restricted());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_for_blocking_page;
+
+    #[test]
+    fn test_check_for_blocking_page() {
+        let api_url = "https://music.youtube.com/youtubei/v1/browse?alt=json";
+        assert!(check_for_blocking_page(api_url, "{\"contents\": {}}").is_ok());
+        let consent = check_for_blocking_page(
+            "https://consent.youtube.com/m?continue=https%3A%2F%2Fmusic.youtube.com",
+            "<html></html>",
+        );
+        assert!(consent.unwrap_err().is_auth_error());
+        let consent = check_for_blocking_page(
+            api_url,
+            "<form action=\"https://consent.youtube.com/save\" method=\"POST\">",
+        );
+        assert!(consent.is_err());
+        let captcha = check_for_blocking_page(
+            "https://www.google.com/sorry/index?continue=https://music.youtube.com/",
+            "<html></html>",
+        );
+        assert_eq!(
+            captcha.unwrap_err().to_string(),
+            "YouTube asked to complete a captcha. Open YouTube Music in your browser and \
+             complete it, or wait a while before trying again"
+        );
+        let region = check_for_blocking_page("https://music.youtube.com/coming-soon/", "");
+        assert_eq!(
+            region.unwrap_err().to_string(),
+            "YouTube Music isn't available in your region"
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use super::private::Sealed;
-use super::AuthToken;
+use super::{check_for_blocking_page, AuthToken};
+use crate::common::Locale;
 use crate::crawler::JsonCrawler;
 use crate::error::{self, Error, Result};
 use crate::parse::ProcessedResult;
 use crate::process::JsonCloner;
 use crate::utils;
+use crate::OfflineResponses;
 use crate::{
     process::RawResult,
     query::Query,
@@ -20,6 +22,9 @@ pub struct BrowserToken {
     sapisid: String,
     client_version: String,
     cookies: String,
+    /// Set for a token that answers queries without using the network.
+    #[serde(skip)]
+    offline_responses: Option<OfflineResponses>,
 }
 
 impl Sealed for BrowserToken {}
@@ -28,7 +33,12 @@ impl AuthToken for BrowserToken {
         &'a self,
         client: &Client,
         query: Q,
+        locale: &Locale,
     ) -> Result<RawResult<Q, BrowserToken>> {
+        if let Some(responses) = &self.offline_responses {
+            let json = responses.respond(&query)?;
+            return Ok(RawResult::from_raw(json, query, self));
+        }
         // TODO: Functionize - used for OAuth as well.
         let url = format!("{YTM_API_URL}{}{YTM_PARAMS}{YTM_PARAMS_KEY}", query.path());
         let mut body = json!({
@@ -39,6 +49,10 @@ impl AuthToken for BrowserToken {
                 },
             },
         });
+        query
+            .locale()
+            .map_or_else(|| locale.clone(), |l| l.or(locale))
+            .apply_to_client(&mut body["context"]["client"]);
         if let Some(body) = body.as_object_mut() {
             body.append(&mut query.header());
             if let Some(q) = query.params() {
@@ -56,10 +70,10 @@ impl AuthToken for BrowserToken {
             .header("Cookie", &self.cookies)
             .json(&body)
             .send()
-            .await?
-            .text()
             .await?;
-
+        let url = result.url().to_string();
+        let result = result.text().await?;
+        check_for_blocking_page(&url, &result)?;
         let result = RawResult::from_raw(result, query, self);
         Ok(result)
     }
@@ -92,6 +106,15 @@ impl AuthToken for BrowserToken {
 }
 
 impl BrowserToken {
+    /// A token that answers queries with the offline responses, instead of sending them.
+    pub(crate) fn offline(responses: OfflineResponses) -> Self {
+        Self {
+            sapisid: String::new(),
+            client_version: String::new(),
+            cookies: String::new(),
+            offline_responses: Some(responses),
+        }
+    }
     pub async fn from_str(cookie_str: &str, client: &Client) -> Result<Self> {
         let cookies = cookie_str.trim().to_string();
         let user_agent = USER_AGENT;
@@ -100,9 +123,10 @@ impl BrowserToken {
             .header(reqwest::header::COOKIE, &cookies)
             .header(reqwest::header::USER_AGENT, user_agent)
             .send()
-            .await?
-            .text()
             .await?;
+        let url = response.url().to_string();
+        let response = response.text().await?;
+        check_for_blocking_page(&url, &response)?;
         // parse for user agent issues here.
         if response.contains("Sorry, YouTube Music is not optimised for your browser. Check for updates or try Google Chrome.") {
             return Err(Error::invalid_user_agent(user_agent));
@@ -128,6 +152,7 @@ impl BrowserToken {
             sapisid,
             client_version,
             cookies,
+            offline_responses: None,
         })
     }
     pub async fn from_cookie_file<P>(path: P, client: &Client) -> Result<Self>
@@ -1,5 +1,6 @@
 use super::private::Sealed;
-use super::AuthToken;
+use super::{check_for_blocking_page, AuthToken};
+use crate::common::Locale;
 use crate::crawler::JsonCrawler;
 use crate::error::{self, Error, Result};
 use crate::parse::ProcessedResult;
@@ -16,11 +17,10 @@ use crate::{
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // The original reason for the two different structs was that we did not save the refresh token.
 // But now we do, so consider simply making this only one struct.
-// Otherwise the only difference is not including Scope which is not super relevant.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct OAuthToken {
     token_type: String,
@@ -28,6 +28,9 @@ pub struct OAuthToken {
     refresh_token: String,
     expires_in: usize,
     request_time: SystemTime,
+    // Tokens saved before the scope was recorded don't have one.
+    #[serde(default)]
+    scope: Option<String>,
 }
 // TODO: Lock down construction of this type.
 #[derive(Clone, Deserialize)]
@@ -68,7 +71,7 @@ impl OAuthToken {
             access_token,
             expires_in,
             token_type,
-            ..
+            scope,
         } = google_token;
         Self {
             token_type,
@@ -76,6 +79,7 @@ impl OAuthToken {
             access_token,
             request_time,
             expires_in,
+            scope: Some(scope),
         }
     }
     fn from_google_token(google_token: GoogleOAuthToken, request_time: SystemTime) -> Self {
@@ -85,7 +89,7 @@ impl OAuthToken {
             expires_in,
             token_type,
             refresh_token,
-            ..
+            scope,
         } = google_token;
         Self {
             token_type,
@@ -93,8 +97,17 @@ impl OAuthToken {
             access_token,
             request_time,
             expires_in,
+            scope: Some(scope),
         }
     }
+    /// The time the access token expires, after which it needs to be refreshed.
+    pub fn get_expiry(&self) -> SystemTime {
+        self.request_time + Duration::from_secs(self.expires_in as u64)
+    }
+    /// Space separated scopes the token was granted, if known.
+    pub fn get_scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
 }
 
 impl OAuthDeviceCode {
@@ -112,6 +125,7 @@ impl AuthToken for OAuthToken {
         &self,
         client: &Client,
         query: Q,
+        locale: &Locale,
     ) -> Result<RawResult<Q, OAuthToken>> {
         // TODO: Functionize - used for Browser Auth as well.
         let url = format!("{YTM_API_URL}{}{YTM_PARAMS}{YTM_PARAMS_KEY}", query.path());
@@ -125,6 +139,10 @@ impl AuthToken for OAuthToken {
                 },
             },
         });
+        query
+            .locale()
+            .map_or_else(|| locale.clone(), |l| l.or(locale))
+            .apply_to_client(&mut body["context"]["client"]);
         if let Some(body) = body.as_object_mut() {
             body.append(&mut query.header());
             if let Some(q) = query.params() {
@@ -160,9 +178,10 @@ impl AuthToken for OAuthToken {
             .header("X-Goog-Request-Time", request_time_unix)
             .json(&body)
             .send()
-            .await?
-            .text()
             .await?;
+        let url = result.url().to_string();
+        let result = result.text().await?;
+        check_for_blocking_page(&url, &result)?;
         let result = RawResult::from_raw(result, query, self);
         Ok(result)
     }
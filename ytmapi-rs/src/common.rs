@@ -4,24 +4,25 @@
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
+use crate::utils::constants::YTM_URL;
 use crate::Error;
 
 /// A search suggestion containing a list of TextRuns.
 /// May be a history suggestion.
-#[derive(PartialEq, Debug, Clone, Deserialize)]
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
 pub struct SearchSuggestion {
     pub runs: Vec<TextRun>,
     pub suggestion_type: SuggestionType,
 }
 
-#[derive(PartialEq, Debug, Clone, Deserialize, Copy)]
+#[derive(PartialEq, Debug, Clone, Deserialize, Copy, Serialize)]
 pub enum SuggestionType {
     History,
     Prediction,
 }
 
 /// A block of text that may be boldened.
-#[derive(PartialEq, Debug, Clone, Deserialize)]
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
 pub enum TextRun {
     Bold(String),
     Normal(String),
@@ -52,7 +53,7 @@ impl SearchSuggestion {
             .iter()
             .fold(String::new(), |acc, r| acc + &r.get_text())
     }
-    pub(crate) fn new(suggestion_type: SuggestionType, runs: Vec<TextRun>) -> Self {
+    pub fn new(suggestion_type: SuggestionType, runs: Vec<TextRun>) -> Self {
         Self {
             runs,
             suggestion_type,
@@ -73,12 +74,88 @@ pub enum Explicit {
     NotExplicit,
 }
 
+/// The user's rating of a song.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LikeStatus {
+    #[serde(rename = "LIKE")]
+    Liked,
+    #[serde(rename = "DISLIKE")]
+    Disliked,
+    #[serde(rename = "INDIFFERENT")]
+    Indifferent,
+}
+
+impl LikeStatus {
+    /// Parse the like status in the format returned by InnerTube, e.g "LIKE".
+    pub fn from_raw(raw: &str) -> Option<Self> {
+        match raw {
+            "LIKE" => Some(Self::Liked),
+            "DISLIKE" => Some(Self::Disliked),
+            "INDIFFERENT" => Some(Self::Indifferent),
+            _ => None,
+        }
+    }
+}
+
+/// Language and region that results are returned in, e.g the names of charts and the songs
+/// that are in them. Unset fields use the account's default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Locale {
+    /// Language code, e.g "en" or "ja".
+    pub language: Option<String>,
+    /// Region code, e.g "US" or "JP".
+    pub region: Option<String>,
+}
+
+impl Locale {
+    pub fn with_language<S: Into<String>>(self, language: S) -> Self {
+        Locale {
+            language: Some(language.into()),
+            ..self
+        }
+    }
+    pub fn with_region<S: Into<String>>(self, region: S) -> Self {
+        Locale {
+            region: Some(region.into()),
+            ..self
+        }
+    }
+    /// This locale, with fields that are unset taken from the fallback.
+    pub(crate) fn or(&self, fallback: &Locale) -> Locale {
+        Locale {
+            language: self.language.clone().or_else(|| fallback.language.clone()),
+            region: self.region.clone().or_else(|| fallback.region.clone()),
+        }
+    }
+    /// Set the language and region on the client in an innertube request context.
+    pub(crate) fn apply_to_client(&self, client: &mut serde_json::Value) {
+        if let Some(language) = &self.language {
+            client["hl"] = language.as_str().into();
+        }
+        if let Some(region) = &self.region {
+            client["gl"] = region.as_str().into();
+        }
+    }
+}
+
+/// Who can see a playlist.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PrivacyStatus {
+    #[serde(rename = "PUBLIC")]
+    Public,
+    #[serde(rename = "UNLISTED")]
+    Unlisted,
+    #[default]
+    #[serde(rename = "PRIVATE")]
+    Private,
+}
+
 // Note, library album will also have artists field. How do we handle - are these two different
 // types?
 // Or, is Album a trait?
 // XXX: Consider if this is the same as the Album struct that uses ResultCore.
 // XXX: I think this should become a trait.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Album {
     pub title: String,
     // TODO: Use type system
@@ -176,6 +253,89 @@ impl<'a> From<&'a AlbumID<'a>> for AlbumID<'a> {
         AlbumID(core.as_ref().into())
     }
 }
+impl<'a> From<&'a PlaylistID<'a>> for PlaylistID<'a> {
+    fn from(value: &'a PlaylistID<'a>) -> Self {
+        let core = &value.0;
+        PlaylistID(core.as_ref().into())
+    }
+}
+impl<'a> From<&'a PodcastID<'a>> for PodcastID<'a> {
+    fn from(value: &'a PodcastID<'a>) -> Self {
+        let core = &value.0;
+        PodcastID(core.as_ref().into())
+    }
+}
+
+impl<'a> VideoID<'a> {
+    /// Get the YouTube Music url for the video.
+    pub fn get_url(&self) -> String {
+        format!("{YTM_URL}/watch?v={}", self.0)
+    }
+}
+impl VideoID<'static> {
+    /// Parse a VideoID from a YouTube or YouTube Music url, or from a raw id.
+    pub fn from_url_or_id(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let id = if let Some((_, rest)) = s.split_once("youtu.be/") {
+            rest.split(['?', '&', '#']).next()?
+        } else if s.contains("://") {
+            get_url_param(s, "v")?
+        } else {
+            s
+        };
+        (id.len() == 11 && is_id_str(id)).then(|| Self(id.to_string().into()))
+    }
+}
+impl<'a> PlaylistID<'a> {
+    /// Get the YouTube Music url for the playlist.
+    pub fn get_url(&self) -> String {
+        format!("{YTM_URL}/playlist?list={}", self.0)
+    }
+}
+impl PlaylistID<'static> {
+    /// Parse a PlaylistID from a YouTube or YouTube Music url, or from a raw id.
+    pub fn from_url_or_id(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let id = if s.contains("://") {
+            get_url_param(s, "list")?
+        } else {
+            s
+        };
+        // Playlist IDs are longer than video IDs, which are always 11 characters.
+        (id.len() > 11 && is_id_str(id)).then(|| Self(id.to_string().into()))
+    }
+}
+impl<'a> AlbumID<'a> {
+    /// Get the YouTube Music url for the album.
+    pub fn get_url(&self) -> String {
+        format!("{YTM_URL}/browse/{}", self.0)
+    }
+}
+impl<'a> ChannelID<'a> {
+    /// Get the YouTube Music url for the channel.
+    pub fn get_url(&self) -> String {
+        format!("{YTM_URL}/channel/{}", self.0)
+    }
+}
+impl<'a> PodcastID<'a> {
+    /// Get the YouTube Music url for the podcast.
+    pub fn get_url(&self) -> String {
+        format!("{YTM_URL}/browse/{}", self.0)
+    }
+}
+/// Get the value of a query parameter from a url.
+fn get_url_param<'a>(url: &'a str, key: &str) -> Option<&'a str> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split(['&', '#'])
+        .filter_map(|param| param.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+fn is_id_str(s: &str) -> bool {
+    s.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
 
 impl<'a> BrowseParams<'a> {
     pub fn from_raw<S>(raw_str: S) -> BrowseParams<'a>
@@ -232,7 +392,7 @@ pub mod library {
     use crate::{ChannelID, Thumbnail};
     use serde::{Deserialize, Serialize};
 
-    use super::PlaylistID;
+    use super::{AlbumID, PlaylistID, PodcastID};
 
     #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
     pub struct Playlist {
@@ -242,6 +402,16 @@ pub mod library {
         pub count: Option<usize>,
         pub description: Option<String>,
         pub author: Option<String>,
+        /// True if the user can edit the playlist, e.g because they own it.
+        pub can_edit: bool,
+    }
+    #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+    pub struct LibraryAlbum {
+        pub album_id: AlbumID<'static>,
+        pub title: String,
+        pub artist: String,
+        pub year: Option<String>,
+        pub thumbnails: Vec<Thumbnail>,
     }
     #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
     pub struct LibraryArtist {
@@ -249,15 +419,30 @@ pub mod library {
         pub artist: String,
         pub byline: String, // e.g 16 songs or 17.8k subscribers
     }
+    #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+    pub struct LibraryPodcast {
+        pub podcast_id: PodcastID<'static>,
+        pub title: String,
+        pub channel: Option<String>,
+        pub thumbnails: Vec<Thumbnail>,
+    }
 }
 
 pub mod browsing {
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
 
-    #[derive(PartialEq, Debug, Clone, Deserialize)]
+    #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
     pub struct Lyrics {
         pub lyrics: String,
         pub source: String,
+        /// Lyrics synced to the song, if available.
+        pub timed_lyrics: Option<Vec<TimedLyricsLine>>,
+    }
+    #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+    pub struct TimedLyricsLine {
+        pub text: String,
+        pub start_ms: u64,
+        pub end_ms: u64,
     }
     impl Lyrics {
         pub fn get_lyrics(&self) -> &str {
@@ -266,13 +451,33 @@ pub mod browsing {
         pub fn get_source(&self) -> &str {
             self.source.as_str()
         }
+        pub fn get_timed_lyrics(&self) -> Option<&[TimedLyricsLine]> {
+            self.timed_lyrics.as_deref()
+        }
         pub fn new(lyrics: String, source: String) -> Self {
-            Self { lyrics, source }
+            Self {
+                lyrics,
+                source,
+                timed_lyrics: None,
+            }
+        }
+        pub fn new_timed(timed_lyrics: Vec<TimedLyricsLine>, source: String) -> Self {
+            let lyrics = timed_lyrics
+                .iter()
+                .map(|l| l.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            Self {
+                lyrics,
+                source,
+                timed_lyrics: Some(timed_lyrics),
+            }
         }
     }
 }
 pub mod youtuberesult {
     use crate::{ChannelID, Thumbnail};
+    use serde::Serialize;
 
     use super::PlaylistID;
 
@@ -319,7 +524,7 @@ pub mod youtuberesult {
             &self.get_core().playlist_subtitle
         }
     }
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, Serialize)]
     pub struct ResultCore {
         // video_id: VideoID<'static>, //Note this is mandatory for Song but not some others, this is a weakness of
         //this genericised approach.
@@ -383,3 +588,67 @@ pub mod youtuberesult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AlbumID, ChannelID, Locale, PlaylistID, VideoID, YoutubeID};
+    use serde_json::json;
+
+    #[test]
+    fn test_video_id_from_url() {
+        let expected = Some(VideoID::from_raw("dQw4w9WgXcQ"));
+        for s in [
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ&list=RDAMVMdQw4w9WgXcQ",
+            "https://www.youtube.com/watch?feature=share&v=dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ?si=abc",
+            " dQw4w9WgXcQ ",
+        ] {
+            assert_eq!(VideoID::from_url_or_id(s), expected);
+        }
+        assert_eq!(VideoID::from_url_or_id("https://music.youtube.com/"), None);
+        assert_eq!(VideoID::from_url_or_id("not an id"), None);
+    }
+    #[test]
+    fn test_playlist_id_from_url() {
+        let expected = Some(PlaylistID::from_raw("PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"));
+        for s in [
+            "https://music.youtube.com/playlist?list=PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf",
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf",
+            "PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf",
+        ] {
+            assert_eq!(PlaylistID::from_url_or_id(s), expected);
+        }
+        assert_eq!(PlaylistID::from_url_or_id("dQw4w9WgXcQ"), None);
+    }
+    #[test]
+    fn test_id_to_url() {
+        assert_eq!(
+            VideoID::from_raw("dQw4w9WgXcQ").get_url(),
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+        assert_eq!(
+            ChannelID::from_raw("UCuAXFkgsw1L7xaCfnd5JJOw").get_url(),
+            "https://music.youtube.com/channel/UCuAXFkgsw1L7xaCfnd5JJOw"
+        );
+        assert_eq!(
+            AlbumID::from_raw("MPREb_4pL8gzRtw1p").get_url(),
+            "https://music.youtube.com/browse/MPREb_4pL8gzRtw1p"
+        );
+    }
+    #[test]
+    fn test_locale() {
+        let handle = Locale::default().with_language("en").with_region("US");
+        let query = Locale::default().with_region("JP");
+        let locale = query.or(&handle);
+        assert_eq!(
+            locale,
+            Locale::default().with_language("en").with_region("JP")
+        );
+        let mut client = json!({ "clientName" : "WEB_REMIX" });
+        locale.apply_to_client(&mut client);
+        assert_eq!(
+            client,
+            json!({ "clientName" : "WEB_REMIX", "hl" : "en", "gl" : "JP" })
+        );
+    }
+}
@@ -1,6 +1,12 @@
 //! Module to contain code related to errors that could be produced by the API.
 use core::fmt::{Debug, Display};
-use std::{io, sync::Arc};
+use std::{io, sync::Arc, time::Duration};
+
+/// Delay before the first retry of a query that failed with an error that may not happen again,
+/// doubled for each retry after it.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+/// Delay before the first retry when rate limited, as retrying soon is likely to be limited too.
+const RATE_LIMITED_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
 
 /// Alias for a Result with the error type ytmapi-rs::Error.
 pub type Result<T> = core::result::Result<T, Error>;
@@ -58,6 +64,14 @@ pub enum ErrorKind {
     BrowserAuthenticationFailed,
     /// OAuthToken has expired.
     OAuthTokenExpired,
+    /// InnerTube returned Google's consent page instead of a response, as consent hasn't been
+    /// given for the account, whether signed in with browser cookies or OAuth.
+    ConsentRequired,
+    /// InnerTube returned a captcha challenge instead of a response, e.g as it has received
+    /// unusual traffic.
+    CaptchaRequired,
+    /// YouTube Music isn't available in the region the request was sent from.
+    RegionRestricted,
     // This is a u64 not a usize as that is what serde_json will deserialize to.
     // TODO: Could use a library to handle these.
     /// Recieved an error code in the Json reply from InnerTube.
@@ -91,6 +105,54 @@ impl Error {
             false
         }
     }
+    /// Returns true if the error was caused by the credentials, e.g the cookie was invalid or
+    /// has expired, and so authenticating again may resolve it.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            *self.inner,
+            ErrorKind::Header
+                | ErrorKind::BrowserAuthenticationFailed
+                | ErrorKind::OAuthTokenExpired
+                | ErrorKind::ConsentRequired
+        )
+    }
+    /// Returns true if the error was caused by the connection to InnerTube, e.g the network
+    /// is unavailable, and so trying again later may resolve it.
+    pub fn is_network_error(&self) -> bool {
+        matches!(*self.inner, ErrorKind::Web(_) | ErrorKind::Io(_))
+    }
+    /// Returns true if InnerTube rejected the request as too many have been sent recently, and
+    /// so trying again after a delay may resolve it.
+    pub fn is_rate_limit_error(&self) -> bool {
+        matches!(*self.inner, ErrorKind::OtherErrorCodeInResponse(429))
+    }
+    /// Returns true if InnerTube couldn't find what was requested, e.g an id that doesn't exist.
+    pub fn is_not_found_error(&self) -> bool {
+        matches!(*self.inner, ErrorKind::OtherErrorCodeInResponse(404))
+    }
+    /// Returns true if the response from InnerTube wasn't in the expected format, e.g as the API
+    /// has changed.
+    pub fn is_parse_error(&self) -> bool {
+        matches!(
+            *self.inner,
+            ErrorKind::Parsing { .. }
+                | ErrorKind::Navigation { .. }
+                | ErrorKind::InvalidResponse { .. }
+        )
+    }
+    /// How long to wait before retrying a query that failed with this error, having already
+    /// retried it `retries` times, or None if retrying is unlikely to help. The caller decides
+    /// how many times to retry.
+    pub fn retry_delay(&self, retries: u32) -> Option<Duration> {
+        let base_delay = if self.is_rate_limit_error() {
+            RATE_LIMITED_RETRY_BASE_DELAY
+        } else if self.is_network_error() {
+            RETRY_BASE_DELAY
+        } else {
+            return None;
+        };
+        Some(base_delay * 2u32.pow(retries))
+    }
     /// If an error is a Navigation or Parsing error, return the source Json and key at the location of the error.
     pub fn get_json_and_key(&self) -> Option<(String, &String)> {
         match self.inner.as_ref() {
@@ -105,6 +167,9 @@ impl Error {
             | ErrorKind::OtherErrorCodeInResponse(_)
             | ErrorKind::OAuthTokenExpired
             | ErrorKind::BrowserAuthenticationFailed
+            | ErrorKind::ConsentRequired
+            | ErrorKind::CaptchaRequired
+            | ErrorKind::RegionRestricted
             | ErrorKind::InvalidUserAgent(_) => None,
         }
     }
@@ -123,6 +188,21 @@ impl Error {
             inner: Box::new(ErrorKind::BrowserAuthenticationFailed),
         }
     }
+    pub(crate) fn consent_required() -> Self {
+        Self {
+            inner: Box::new(ErrorKind::ConsentRequired),
+        }
+    }
+    pub(crate) fn captcha_required() -> Self {
+        Self {
+            inner: Box::new(ErrorKind::CaptchaRequired),
+        }
+    }
+    pub(crate) fn region_restricted() -> Self {
+        Self {
+            inner: Box::new(ErrorKind::RegionRestricted),
+        }
+    }
     pub(crate) fn navigation<S: Into<String>>(key: S, json: Arc<String>) -> Self {
         Self {
             inner: Box::new(ErrorKind::Navigation {
@@ -197,6 +277,20 @@ impl Display for ErrorKind {
             ErrorKind::OAuthTokenExpired => write!(f, "OAuth token has expired"),
             ErrorKind::InvalidUserAgent(u) => write!(f, "InnerTube rejected User Agent {u}"),
             ErrorKind::BrowserAuthenticationFailed => write!(f, "Browser authentication failed"),
+            ErrorKind::ConsentRequired => write!(
+                f,
+                "YouTube asked for consent. Open YouTube Music in your browser, accept the \
+                 consent prompt, then sign in again, e.g by exporting your cookies again or \
+                 setting up OAuth again"
+            ),
+            ErrorKind::CaptchaRequired => write!(
+                f,
+                "YouTube asked to complete a captcha. Open YouTube Music in your browser and \
+                 complete it, or wait a while before trying again"
+            ),
+            ErrorKind::RegionRestricted => {
+                write!(f, "YouTube Music isn't available in your region")
+            }
             ErrorKind::UnableToSerializeGoogleOAuthToken { response, err } => write!(
                 f,
                 "Unable to serialize Google auth token {}, received error {}",
@@ -232,3 +326,27 @@ impl From<io::Error> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use std::time::Duration;
+
+    #[test]
+    fn test_retry_delay() {
+        let network_error = Error::from(std::io::Error::other("Connection reset"));
+        assert_eq!(network_error.retry_delay(0), Some(Duration::from_secs(1)));
+        assert_eq!(network_error.retry_delay(2), Some(Duration::from_secs(4)));
+        let rate_limit_error = Error::other_code(429);
+        assert_eq!(
+            rate_limit_error.retry_delay(0),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            rate_limit_error.retry_delay(1),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(Error::other_code(404).retry_delay(0), None);
+        assert_eq!(Error::header().retry_delay(0), None);
+    }
+}
@@ -34,27 +34,35 @@ use auth::{
 };
 use common::{
     browsing::Lyrics,
-    library::{LibraryArtist, Playlist},
+    library::{LibraryAlbum, LibraryArtist, LibraryPodcast, Playlist},
     watch::WatchPlaylist,
-    SearchSuggestion,
+    AlbumID, PlaylistID, SearchSuggestion,
 };
-pub use common::{Album, BrowseID, ChannelID, Thumbnail, VideoID};
+pub use common::{Album, BrowseID, ChannelID, Locale, Thumbnail, VideoID};
 pub use error::{Error, Result};
+use futures::{Stream, StreamExt};
 use parse::{
-    AlbumParams, ArtistParams, Parse, SearchResultAlbum, SearchResultArtist, SearchResultEpisode,
-    SearchResultFeaturedPlaylist, SearchResultPlaylist, SearchResultPodcast, SearchResultProfile,
-    SearchResultSong, SearchResultVideo, SearchResults,
+    AccountInfo, AlbumParams, ArtistParams, Charts, Episode, HistoryItem, Parse,
+    PlaylistContinuationParams, PlaylistParams, Podcast, SearchArtistsParams, SearchResultAlbum,
+    SearchResultArtist, SearchResultEpisode, SearchResultFeaturedPlaylist, SearchResultPlaylist,
+    SearchResultPodcast, SearchResultProfile, SearchResultSong, SearchResultVideo, SearchResults,
 };
 use process::RawResult;
 use query::{
-    lyrics::GetLyricsQuery, watch::GetWatchPlaylistQuery, AlbumsFilter, ArtistsFilter, BasicSearch,
-    CommunityPlaylistsFilter, EpisodesFilter, FeaturedPlaylistsFilter, FilteredSearch,
-    GetAlbumQuery, GetArtistAlbumsQuery, GetArtistQuery, GetLibraryArtistsQuery,
-    GetLibraryPlaylistsQuery, GetSearchSuggestionsQuery, PlaylistsFilter, PodcastsFilter,
-    ProfilesFilter, Query, SearchQuery, SongsFilter, VideosFilter,
+    lyrics::GetLyricsQuery, watch::GetWatchPlaylistQuery, AddPlaylistItemsQuery, AlbumsFilter,
+    ArtistsFilter, BasicSearch, CommunityPlaylistsFilter, CreatePlaylistQuery, EditPlaylistQuery,
+    EpisodesFilter, FeaturedPlaylistsFilter, FilteredSearch, GetAccountInfoQuery, GetAlbumQuery,
+    GetArtistAlbumsQuery, GetArtistQuery, GetChartsQuery, GetHistoryQuery, GetLibraryAlbumsQuery,
+    GetLibraryArtistsQuery, GetLibraryPlaylistsQuery, GetLibraryPodcastsQuery,
+    GetLibrarySubscriptionsQuery, GetNewEpisodesQuery, GetPlaylistContinuationQuery,
+    GetPlaylistQuery, GetPodcastQuery, GetSearchSuggestionsQuery, PlaylistsFilter, PodcastsFilter,
+    ProfilesFilter, Query, RatePlaylistQuery, RateSongQuery, RemoveHistoryItemsQuery,
+    SearchArtistsContinuationQuery, SearchQuery, SongsFilter, SubscribeArtistQuery,
+    UnsubscribeArtistQuery, VideosFilter,
 };
 use reqwest::Client;
 use std::path::Path;
+use std::sync::Arc;
 
 // TODO: Confirm if auth should be pub
 pub mod auth;
@@ -71,41 +79,99 @@ pub mod query;
 #[cfg(test)]
 mod tests;
 
+/// Times an album request in `get_albums` is retried before giving up.
+const MAX_ALBUM_RETRIES: u32 = 3;
+
 #[derive(Debug, Clone)]
 // XXX: Consider wrapping auth in reference counting for cheap cloning.
 /// A handle to the YouTube Music API, wrapping a reqwest::Client.
 /// Generic over AuthToken, as different AuthTokens may allow different queries to be executed.
 pub struct YtMusic<A: AuthToken> {
-    // TODO: add language
-    // TODO: add location
     client: Client,
     token: A,
+    locale: Locale,
+}
+
+type Responder = dyn Fn(&str, &serde_json::Map<String, serde_json::Value>, Option<&str>) -> Option<String>
+    + Send
+    + Sync;
+
+#[derive(Clone)]
+/// Responses to queries that are used instead of the network, e.g saved json for testing or
+/// demos. Given a query's path, header and params, the responder returns the json response, or
+/// None if it has no response for the query.
+pub struct OfflineResponses(Arc<Responder>);
+
+impl OfflineResponses {
+    pub fn new(
+        responder: impl Fn(&str, &serde_json::Map<String, serde_json::Value>, Option<&str>) -> Option<String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self(Arc::new(responder))
+    }
+    pub(crate) fn respond<Q: Query>(&self, query: &Q) -> Result<String> {
+        (self.0)(query.path(), &query.header(), query.params().as_deref())
+            .ok_or_else(|| Error::other(format!("No offline response for {} query", query.path())))
+    }
+}
+
+impl std::fmt::Debug for OfflineResponses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OfflineResponses(/* responder */)")
+    }
 }
 
 impl YtMusic<BrowserToken> {
     /// Create a new API handle using a BrowserToken.
     pub fn from_browser_token(token: BrowserToken) -> YtMusic<BrowserToken> {
         let client = Client::new();
-        YtMusic { client, token }
+        YtMusic {
+            client,
+            token,
+            locale: Locale::default(),
+        }
     }
     /// Create a new API handle using a real browser authentication cookie saved to a file on disk.
     pub async fn from_cookie_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let client = Client::new();
         let token = BrowserToken::from_cookie_file(path, &client).await?;
-        Ok(Self { client, token })
+        Ok(Self {
+            client,
+            token,
+            locale: Locale::default(),
+        })
     }
     /// Create a new API handle using a real browser authentication cookie in a String.
     pub async fn from_cookie<S: AsRef<str>>(cookie: S) -> Result<Self> {
         let client = Client::new();
         let token = BrowserToken::from_str(cookie.as_ref(), &client).await?;
-        Ok(Self { client, token })
+        Ok(Self {
+            client,
+            token,
+            locale: Locale::default(),
+        })
+    }
+    /// Create a new API handle that never uses the network, instead answering queries with the
+    /// offline responses. Queries without a response return an error.
+    pub fn from_offline_responses(responses: OfflineResponses) -> YtMusic<BrowserToken> {
+        YtMusic {
+            client: Client::new(),
+            token: BrowserToken::offline(responses),
+            locale: Locale::default(),
+        }
     }
 }
 impl YtMusic<OAuthToken> {
     /// Create a new API handle using an OAuthToken.
     pub fn from_oauth_token(token: OAuthToken) -> YtMusic<OAuthToken> {
         let client = Client::new();
-        YtMusic { client, token }
+        YtMusic {
+            client,
+            token,
+            locale: Locale::default(),
+        }
     }
     /// Refresh the internal oauth token, and return a clone of it (for user to store locally, e.g).
     pub async fn refresh_token(&mut self) -> Result<OAuthToken> {
@@ -115,9 +181,16 @@ impl YtMusic<OAuthToken> {
     }
 }
 impl<A: AuthToken> YtMusic<A> {
+    /// Set the language and region that results are returned in, for all queries that don't
+    /// set their own.
+    pub fn with_locale(self, locale: Locale) -> Self {
+        YtMusic { locale, ..self }
+    }
     async fn raw_query<Q: Query>(&self, query: Q) -> Result<RawResult<Q, A>> {
         // TODO: Check for a response the reflects an expired Headers token
-        self.token.raw_query(&self.client, query).await
+        self.token
+            .raw_query(&self.client, query, &self.locale)
+            .await
     }
     /// Return the raw JSON returned by YouTube music for Query Q.
     pub async fn json_query<Q: Query>(&self, query: Q) -> Result<String> {
@@ -141,6 +214,28 @@ impl<A: AuthToken> YtMusic<A> {
         let query = query.into();
         self.raw_query(query).await?.process()?.parse()
     }
+    /// API Search Query for Artists only, also returning a token to get the next page of
+    /// results if there are more.
+    pub async fn search_artists_with_continuation<
+        'a,
+        Q: Into<SearchQuery<'a, FilteredSearch<ArtistsFilter>>>,
+    >(
+        &self,
+        query: Q,
+    ) -> Result<SearchArtistsParams> {
+        let query = query.into();
+        self.raw_query(query)
+            .await?
+            .process()?
+            .parse_with_continuation()
+    }
+    /// Get the next page of results from an artist search.
+    pub async fn search_artists_continuation(
+        &self,
+        query: SearchArtistsContinuationQuery,
+    ) -> Result<SearchArtistsParams> {
+        self.raw_query(query).await?.process()?.parse()
+    }
     /// API Search Query for Albums only.
     pub async fn search_albums<'a, Q: Into<SearchQuery<'a, FilteredSearch<AlbumsFilter>>>>(
         &self,
@@ -228,6 +323,61 @@ impl<A: AuthToken> YtMusic<A> {
     pub async fn get_album(&self, query: GetAlbumQuery<'_>) -> Result<AlbumParams> {
         self.raw_query(query).await?.process()?.parse()
     }
+    /// Get several albums, with at most `concurrency` requests in flight at once. Albums are
+    /// returned with their id as they are received, so may be out of order. Dropping the stream
+    /// cancels the remaining requests. As one failed request would otherwise leave a gap in the
+    /// albums, requests that fail with an error that may not happen again are retried.
+    pub fn get_albums<'a, I>(
+        &'a self,
+        ids: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = (AlbumID<'a>, Result<AlbumParams>)> + 'a
+    where
+        I: IntoIterator<Item = AlbumID<'a>>,
+    {
+        // Futures are collected up front rather than mapping the stream, as a stream holding a
+        // closure over ids can't be proven Send when awaited in a spawned task.
+        let albums: Vec<_> = ids
+            .into_iter()
+            .map(|id| async move {
+                let album = self.get_album_with_retry(&id).await;
+                (id, album)
+            })
+            .collect();
+        futures::stream::iter(albums).buffer_unordered(concurrency.max(1))
+    }
+    async fn get_album_with_retry(&self, id: &AlbumID<'_>) -> Result<AlbumParams> {
+        let mut retries = 0;
+        loop {
+            let e = match self.get_album(GetAlbumQuery::new(id)).await {
+                Ok(album) => return Ok(album),
+                Err(e) => e,
+            };
+            let Some(delay) = e
+                .retry_delay(retries)
+                .filter(|_| retries < MAX_ALBUM_RETRIES)
+            else {
+                return Err(e);
+            };
+            retries += 1;
+            tokio::time::sleep(delay).await;
+        }
+    }
+    pub async fn get_playlist(&self, query: GetPlaylistQuery<'_>) -> Result<PlaylistParams> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    pub async fn get_playlist_continuation(
+        &self,
+        query: GetPlaylistContinuationQuery,
+    ) -> Result<PlaylistContinuationParams> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    pub async fn get_charts(&self, query: GetChartsQuery<'_>) -> Result<Charts> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    pub async fn get_podcast(&self, query: GetPodcastQuery<'_>) -> Result<Podcast> {
+        self.raw_query(query).await?.process()?.parse()
+    }
     pub async fn get_lyrics(&self, query: GetLyricsQuery<'_>) -> Result<Lyrics> {
         self.raw_query(query).await?.process()?.parse()
     }
@@ -259,6 +409,76 @@ impl<A: AuthToken> YtMusic<A> {
     ) -> Result<Vec<LibraryArtist>> {
         self.raw_query(query).await?.process()?.parse()
     }
+    /// Albums saved to the user's library.
+    pub async fn get_library_albums(&self) -> Result<Vec<LibraryAlbum>> {
+        self.raw_query(GetLibraryAlbumsQuery)
+            .await?
+            .process()?
+            .parse()
+    }
+    /// Artists the user is subscribed to.
+    pub async fn get_library_subscriptions(&self) -> Result<Vec<LibraryArtist>> {
+        self.raw_query(GetLibrarySubscriptionsQuery)
+            .await?
+            .process()?
+            .parse()
+    }
+    pub async fn subscribe_artist(&self, query: SubscribeArtistQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    pub async fn unsubscribe_artist(&self, query: UnsubscribeArtistQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    /// Like, dislike, or remove the rating from a song.
+    pub async fn rate_song(&self, query: RateSongQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    /// Like, dislike, or remove the rating from a playlist or album.
+    pub async fn rate_playlist(&self, query: RatePlaylistQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    /// Add songs to a playlist owned by the user.
+    pub async fn add_playlist_items(&self, query: AddPlaylistItemsQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    /// Edit the settings of a playlist owned by the user, e.g who can see it.
+    pub async fn edit_playlist(&self, query: EditPlaylistQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    /// Create a playlist owned by the user, returning its id.
+    pub async fn create_playlist(
+        &self,
+        query: CreatePlaylistQuery<'_>,
+    ) -> Result<PlaylistID<'static>> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    /// Songs the user has played, most recent first.
+    pub async fn get_history(&self) -> Result<Vec<HistoryItem>> {
+        self.raw_query(GetHistoryQuery).await?.process()?.parse()
+    }
+    /// Remove songs from the user's history, using the feedback tokens from get_history.
+    pub async fn remove_history_items(&self, query: RemoveHistoryItemsQuery<'_>) -> Result<()> {
+        self.raw_query(query).await?.process()?.parse()
+    }
+    pub async fn get_library_podcasts(&self) -> Result<Vec<LibraryPodcast>> {
+        self.raw_query(GetLibraryPodcastsQuery)
+            .await?
+            .process()?
+            .parse()
+    }
+    /// The latest episodes of the podcasts the user is subscribed to.
+    pub async fn get_new_episodes(&self) -> Result<Vec<Episode>> {
+        self.raw_query(GetNewEpisodesQuery)
+            .await?
+            .process()?
+            .parse()
+    }
+    pub async fn get_account_info(&self) -> Result<AccountInfo> {
+        self.raw_query(GetAccountInfoQuery)
+            .await?
+            .process()?
+            .parse()
+    }
 }
 // TODO: Keep session alive after calling these methods.
 /// Generates a tuple containing fresh OAuthDeviceCode and corresponding url for you to authenticate yourself at.
@@ -270,6 +490,13 @@ pub async fn generate_oauth_code_and_url() -> Result<(OAuthDeviceCode, String)>
     Ok((code.device_code, url))
 }
 // TODO: Keep session alive after calling these methods.
+/// Generates a fresh OAuthTokenGenerator, which also says how often to poll for the token and
+/// when the code expires.
+pub async fn generate_oauth_token_generator() -> Result<OAuthTokenGenerator> {
+    let client = Client::new();
+    OAuthTokenGenerator::new(&client).await
+}
+// TODO: Keep session alive after calling these methods.
 /// Generates an OAuth Token when given an OAuthDeviceCode.
 pub async fn generate_oauth_token(code: OAuthDeviceCode) -> Result<OAuthToken> {
     let client = Client::new();
@@ -15,7 +15,7 @@ pub const TOGGLE_MENU: &str = "/toggleMenuServiceItemRenderer";
 pub const PLAY_BUTTON: &str =
     "/overlay/musicItemThumbnailOverlayRenderer/content/musicPlayButtonRenderer";
 pub const NAVIGATION_BROWSE: &str = "/navigationEndpoint/browseEndpoint";
-pub const _PAGE_TYPE: &str =
+pub const PAGE_TYPE: &str =
     "/browseEndpointContextSupportedConfigs/browseEndpointContextMusicConfig/pageType";
 pub const _WATCH_VIDEO_ID: &str = "/watchEndpoint/videoId";
 pub const NAVIGATION_WATCH_PLAYLIST_ID: &str =
@@ -39,11 +39,11 @@ pub const _TASTE_PROFILE_ARTIST: &str = "/title/runs";
 pub const _SECTION_LIST_CONTINUATION: &str = "/continuationContents/sectionListContinuation";
 pub const HEADER_DETAIL: &str = "/header/musicDetailHeaderRenderer";
 pub const DESCRIPTION_SHELF: &str = "/musicDescriptionShelfRenderer";
-pub const _CAROUSEL: &str = "/musicCarouselShelfRenderer";
+pub const CAROUSEL: &str = "/musicCarouselShelfRenderer";
 pub const _IMMERSIVE_CAROUSEL: &str = "/musicImmersiveCarouselShelfRenderer";
-pub const _FRAMEWORK_MUTATIONS: &str = "/frameworkUpdates/entityBatchUpdate/mutations";
+pub const FRAMEWORK_MUTATIONS: &str = "/frameworkUpdates/entityBatchUpdate/mutations";
 pub const TITLE_TEXT: &str = concatcp!("/title", RUN_TEXT);
-pub const _NAVIGATION_VIDEO_ID: &str = concatcp!("/navigationEndpoint", _WATCH_VIDEO_ID);
+pub const NAVIGATION_VIDEO_ID: &str = concatcp!("/navigationEndpoint", _WATCH_VIDEO_ID);
 pub const PLAYLIST_ITEM_VIDEO_ID: &str = "/playlistItemData/videoId";
 pub const SINGLE_COLUMN_TAB: &str = concatcp!(SINGLE_COLUMN, TAB_CONTENT);
 pub const SECTION_LIST_ITEM: &str = concatcp!("/sectionListRenderer", CONTENT);
@@ -74,7 +74,11 @@ pub const MENU_PLAYLIST_ID: &str = concatcp!(
     NAVIGATION_WATCH_PLAYLIST_ID
 );
 pub const DESCRIPTION: &str = concatcp!("/description", RUN_TEXT);
-pub const _CAROUSEL_CONTENTS: &str = concatcp!(_CAROUSEL, "/contents");
+pub const CAROUSEL_CONTENTS: &str = concatcp!(CAROUSEL, "/contents");
 pub const CAROUSEL_TITLE: &str = concatcp!("/header/musicCarouselShelfBasicHeaderRenderer", TITLE);
 pub const _CARD_SHELF_TITLE: &str =
     concatcp!("/header/musicCardShelfHeaderBasicRenderer", TITLE_TEXT);
+pub const ACCOUNT_INFO: &str =
+    "/actions/0/openPopupAction/popup/multiPageMenuRenderer/header/activeAccountHeaderRenderer";
+pub const ACCOUNT_NAME: &str = concatcp!(ACCOUNT_INFO, "/accountName", RUN_TEXT);
+pub const ACCOUNT_CHANNEL_HANDLE: &str = concatcp!(ACCOUNT_INFO, "/channelHandle", RUN_TEXT);
@@ -1,6 +1,6 @@
 //! Results from parsing Innertube queries.
 use crate::{
-    common::{AlbumType, Explicit, PlaylistID, PodcastID, ProfileID, Thumbnail, VideoID},
+    common::{AlbumID, AlbumType, Explicit, PlaylistID, PodcastID, ProfileID, Thumbnail, VideoID},
     crawler::{JsonCrawler, JsonCrawlerBorrowed},
     nav_consts::*,
     process::{self, process_flex_column_item},
@@ -8,15 +8,26 @@ use crate::{
     ChannelID,
 };
 use crate::{Error, Result};
+pub use account::*;
 pub use album::*;
 pub use artist::*;
+pub use charts::*;
 use const_format::concatcp;
+pub use history::*;
+pub use playlist::*;
+pub use podcasts::*;
 use serde::{Deserialize, Serialize};
 
+mod account;
 mod album;
 mod artist;
+mod charts;
 mod continuations;
+mod history;
 mod library;
+mod playlist;
+mod podcasts;
+mod rate;
 mod search;
 
 // TODO: Seal
@@ -145,7 +156,10 @@ pub struct TopResult {
     pub plays: Option<String>,
     /// Podcast publisher.
     pub publisher: Option<String>,
-    // TODO: Add endpoint id.
+    /// Set for songs and videos.
+    pub video_id: Option<VideoID<'static>>,
+    /// Set for artists, albums, playlists and podcasts. The type of id depends on the result type.
+    pub browse_id: Option<String>,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// An artist search result.
@@ -156,6 +170,13 @@ pub struct SearchResultArtist {
     pub browse_id: ChannelID<'static>,
     pub thumbnails: Vec<Thumbnail>,
 }
+/// A page of artist search results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchArtistsParams {
+    pub artists: Vec<SearchResultArtist>,
+    /// Token to get the next page of results, if there are more.
+    pub continuation: Option<String>,
+}
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// A podcast search result.
 pub struct SearchResultPodcast {
@@ -201,7 +222,7 @@ pub struct SearchResultAlbum {
     pub artist: String,
     pub year: String,
     pub explicit: Explicit,
-    pub browse_id: ChannelID<'static>,
+    pub browse_id: AlbumID<'static>,
     pub album_type: AlbumType,
     pub thumbnails: Vec<Thumbnail>,
 }
@@ -342,16 +363,24 @@ mod tests {
 mod lyrics {
     use const_format::concatcp;
 
-    use crate::common::browsing::Lyrics;
+    use crate::common::browsing::{Lyrics, TimedLyricsLine};
+    use crate::crawler::{JsonCrawler, JsonCrawlerBorrowed};
     use crate::nav_consts::{DESCRIPTION, DESCRIPTION_SHELF, RUN_TEXT, SECTION_LIST_ITEM};
     use crate::query::lyrics::GetLyricsQuery;
-    use crate::Result;
+    use crate::{Error, Result};
 
     use super::ProcessedResult;
 
+    // NOTE: Timed lyrics are currently only returned to the mobile clients.
+    const TIMED_LYRICS_DATA: &str =
+        "/contents/elementRenderer/newElement/type/componentType/model/timedLyricsModel/lyricsData";
+
     impl<'a> ProcessedResult<GetLyricsQuery<'a>> {
         pub fn parse(self) -> Result<Lyrics> {
             let ProcessedResult { json_crawler, .. } = self;
+            if json_crawler.path_exists(concatcp!(TIMED_LYRICS_DATA, "/timedLyricsData")) {
+                return parse_timed_lyrics(json_crawler);
+            }
             let mut description_shelf = json_crawler.navigate_pointer(concatcp!(
                 "/contents",
                 SECTION_LIST_ITEM,
@@ -364,6 +393,33 @@ mod lyrics {
         }
     }
 
+    fn parse_timed_lyrics(json_crawler: JsonCrawler) -> Result<Lyrics> {
+        let mut lyrics_data = json_crawler.navigate_pointer(TIMED_LYRICS_DATA)?;
+        let source = lyrics_data.take_value_pointer("/sourceMessage")?;
+        let lines = lyrics_data
+            .borrow_pointer("/timedLyricsData")?
+            .into_array_iter_mut()?
+            .map(parse_timed_lyrics_line)
+            .collect::<Result<_>>()?;
+        Ok(Lyrics::new_timed(lines, source))
+    }
+
+    fn parse_timed_lyrics_line(mut line: JsonCrawlerBorrowed) -> Result<TimedLyricsLine> {
+        // Cue times are sent as strings.
+        let mut parse_ms = |path: &str| -> Result<u64> {
+            line.take_value_pointer::<String, _>(path)?
+                .parse()
+                .map_err(|_| Error::other(format!("Unable to parse lyrics cue time at {path}")))
+        };
+        let start_ms = parse_ms("/cueRange/startTimeMilliseconds")?;
+        let end_ms = parse_ms("/cueRange/endTimeMilliseconds")?;
+        Ok(TimedLyricsLine {
+            text: line.take_value_pointer("/lyricLine")?,
+            start_ms,
+            end_ms,
+        })
+    }
+
     #[cfg(test)]
     mod tests {
         use crate::{
@@ -392,7 +448,8 @@ mod lyrics {
                 output,
                 Lyrics {
                     lyrics: "Push \r\nCome on, she almost there push, come on\r\nCome on, come on, push, it's almost there \r\nOne more time, come one\r\nCome on, push, baby, one more time \r\nHarder, harder, push it harder \r\nPush, push, come on \r\nOne more time, here it goes \r\nI see the head\r\nYeah, come on\r\nYeah, yeah\r\nYou did it, baby, yeah\r\n\r\nBut if you lose, don't ask no questions why\r\nThe only game you know is do or die\r\nAh-ha-ha\r\nHard to understand what a hell of a man\r\n\r\nHip hop the hippie the hippie\r\nTp the hip hop and you don't stop \r\nRock it out, baby bubba, to the boogie, the bang-bang\r\nThe boogie to the boogie that be\r\nNow what you hear is not a test, I'm rappin', to the beat \r\n\r\nGoddamn it, Voletta, what the fuck are you doin'?\r\nYou can't control that goddamn boy? (What?)\r\nI just saw Mr. Johnson, he told me he caught the motherfucking boy shoplifting \r\nWhat the fuck are you doing? (Kiss my black ass, motherfucker)\r\nYou can't control that god-, I don't know what the fuck to do with that boy\r\n(What the fuck do you want me to do?)\r\nIf if you can't fucking control that boy, I'ma send him\r\n(All you fucking do is bitch at me)\r\nBitch, bitch, I'ma send his motherfuckin' ass to a group home goddamnit, what?\r\nI'll smack the shit outta you bitch, what, what the fuck?\r\n(Kiss my black ass, motherfucker)\r\nYou're fuckin' up\r\n(Comin' in here smelling like sour socks you, dumb motherfucker) \r\n\r\nWhen I'm bustin' up a party I feel no guilt\r\nGizmo's cuttin' up for thee \r\nSuckers that's down with nei-\r\n\r\nWhat, nigga, you wanna rob them motherfuckin' trains, you crazy? \r\nYes, yes, motherfucker, motherfuckin' right, nigga, yes \r\nNigga, what the fuck, nigga? We gonna get-\r\nNigga, it's eighty-seven nigga, is you dead broke? \r\nYeah, nigga, but, but\r\nMotherfucker, is you broke, motherfucker? \r\nWe need to get some motherfuckin' paper, nigga \r\nNigga it's a train, ain't nobody never robbed no motherfuckin' train \r\nJust listen, man, is your mother givin' you money, nigga? \r\nMy moms don't give me shit nigga, it's time to get paid, nigga \r\nIs you with me? Motherfucker, is you with me? \r\nYeah, I'm with you, nigga, come on \r\nAlright then, nigga, lets make it happen then \r\nAll you motherfuckers get on the fuckin' floor \r\nGet on the motherfuckin' floor\r\nChill, give me all your motherfuckin' money \r\nAnd don't move, nigga\r\nI want the fuckin' jewelry \r\nGive me every fuckin' thing \r\nNigga, I'd shut the fuck up or I'ma blow your motherfuckin' brains out \r\nShut the fuck up, bitch, give me your fuckin' money, motherfucker\r\nFuck you, bitch, get up off that shit \r\nWhat the fuck you holdin' on to that shit for, bitch? \r\n\r\nI get money, money I got\r\nStunts call me honey if they feel real hot\r\n\r\nOpen C-74, Smalls \r\nMr. Smalls, let me walk you to the door \r\nSo how does it feel leavin' us? \r\nCome on, man, what kind of fuckin' question is that, man? \r\nTryin' to get the fuck up out this joint, dog \r\nYeah, yeah, you'll be back \r\nYou niggas always are \r\nGo ahead, man, what the fuck is you hollerin' about? \r\nYou won't see me up in this motherfucker no more \r\nWe'll see \r\nI got big plans nigga, big plans, hahaha".to_string(),
-                    source: "Source: LyricFind".to_string()
+                    source: "Source: LyricFind".to_string(),
+                    timed_lyrics: None,
                 }
             );
         }
@@ -442,4 +499,34 @@ mod watch {
         let path = format!("/tabs/{tab_id}/tabRenderer/endpoint/browseEndpoint/browseId");
         watch_next_renderer.borrow_pointer(path)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{
+            common::{watch::WatchPlaylist, LyricsID, PlaylistID, YoutubeID},
+            crawler::JsonCrawler,
+            parse::ProcessedResult,
+            process::JsonCloner,
+            query::watch::GetWatchPlaylistQuery,
+            VideoID,
+        };
+
+        #[test]
+        fn test_watch_playlist_dummy_json() {
+            let testfile = std::fs::read_to_string("test_json/get_watch_playlist.json").unwrap();
+            let cloner = JsonCloner::from_string(testfile).unwrap();
+            let json_crawler = JsonCrawler::from_json_cloner(cloner);
+            let query = GetWatchPlaylistQuery::new_from_video_id(VideoID::from_raw("BxXY7Dsvd-Y"));
+            let output = ProcessedResult::from_raw(json_crawler, query)
+                .parse()
+                .unwrap();
+            assert_eq!(
+                output,
+                WatchPlaylist::new(
+                    Some(PlaylistID::from_raw("RDAMVMBxXY7Dsvd-Y")),
+                    LyricsID("MPLYt_placeholder".into())
+                )
+            );
+        }
+    }
 }
@@ -0,0 +1,51 @@
+use super::ProcessedResult;
+use crate::nav_consts::{ACCOUNT_CHANNEL_HANDLE, ACCOUNT_NAME};
+use crate::query::GetAccountInfoQuery;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// The account the API is signed in to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub name: String,
+    /// E.g "@name". Accounts without a channel have no handle.
+    pub channel_handle: Option<String>,
+}
+
+impl ProcessedResult<GetAccountInfoQuery> {
+    pub fn parse(self) -> Result<AccountInfo> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let name = json_crawler.take_value_pointer(ACCOUNT_NAME)?;
+        let channel_handle = json_crawler.take_value_pointer(ACCOUNT_CHANNEL_HANDLE).ok();
+        Ok(AccountInfo {
+            name,
+            channel_handle,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccountInfo;
+    use crate::{
+        crawler::JsonCrawler, parse::ProcessedResult, process::JsonCloner,
+        query::GetAccountInfoQuery,
+    };
+
+    #[test]
+    fn test_account_info_dummy_json() {
+        let testfile = std::fs::read_to_string("test_json/get_account_info.json").unwrap();
+        let cloner = JsonCloner::from_string(testfile).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let processed = ProcessedResult::from_raw(json_crawler, GetAccountInfoQuery);
+        assert_eq!(
+            processed.parse().unwrap(),
+            AccountInfo {
+                name: "Test User".into(),
+                channel_handle: Some("@testuser".into()),
+            }
+        );
+    }
+}
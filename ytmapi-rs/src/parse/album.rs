@@ -5,16 +5,17 @@ use crate::nav_consts::*;
 use crate::query::*;
 use crate::{Error, Result};
 use const_format::concatcp;
+use serde::Serialize;
 
 use super::{parse_playlist_items, ProcessedResult, SongResult};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum AlbumLikeStatus {
     Like,
     Indifferent,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct AlbumParamsOtherVersion {
     pub title: String,
     pub year: String,
@@ -25,7 +26,7 @@ pub struct AlbumParamsOtherVersion {
 
 // Is this similar to another struct?
 // XXX: Consider correct privacy
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct AlbumParams {
     pub title: String,
     pub category: AlbumType,
@@ -217,3 +218,42 @@ impl<'a> ProcessedResult<GetAlbumQuery<'a>> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{youtuberesult::YoutubeResult, AlbumID, YoutubeID};
+    use crate::crawler::JsonCrawler;
+    use crate::parse::ProcessedResult;
+    use crate::process::JsonCloner;
+    use crate::query::GetAlbumQuery;
+
+    #[test]
+    fn test_album_dummy_json() {
+        let testfile = std::fs::read_to_string("test_json/get_album.json").unwrap();
+        let cloner = JsonCloner::from_string(testfile).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let query = GetAlbumQuery::new(AlbumID::from_raw("MPREb_C1fGfaCjvdH"));
+        let album = ProcessedResult::from_raw(json_crawler, query)
+            .parse()
+            .unwrap();
+        assert_eq!(album.title, "OK Computer");
+        assert_eq!(album.year, "1997");
+        assert_eq!(album.duration, "11 minutes");
+        assert_eq!(
+            album.audio_playlist_id.as_deref(),
+            Some("OLAK5uy_placeholder")
+        );
+        let tracks: Vec<_> = album
+            .tracks
+            .iter()
+            .map(|t| (t.get_title().as_str(), t.get_video_id().get_raw()))
+            .collect();
+        assert_eq!(
+            tracks,
+            [
+                ("Airbag", "BxXY7Dsvd-Y"),
+                ("Paranoid Android", "fHiGbolFFGw")
+            ]
+        );
+    }
+}
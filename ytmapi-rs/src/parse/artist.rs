@@ -1,10 +1,16 @@
+use super::ChartSong;
+use super::Episode;
 use super::MusicShelfContents;
 use super::ParsedSongAlbum;
+use super::PlaylistSong;
 use super::ProcessedResult;
+use super::SearchResultSong;
+use super::SearchResultVideo;
 use crate::common::youtuberesult::ResultCore;
 use crate::common::youtuberesult::YoutubeResult;
 use crate::common::AlbumID;
 use crate::common::BrowseParams;
+use crate::common::Explicit;
 use crate::common::PlaylistID;
 use crate::common::VideoID;
 use crate::common::YoutubeID;
@@ -16,8 +22,9 @@ use crate::ChannelID;
 use crate::Result;
 use crate::Thumbnail;
 use const_format::concatcp;
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ArtistParams {
     pub description: String,
     pub views: String,
@@ -114,10 +121,15 @@ impl<'a> ProcessedResult<GetArtistQuery<'a>> {
                 .ok();
             // TODO: finish other categories
             match category {
-                ArtistTopReleaseCategory::Related => (),
+                ArtistTopReleaseCategory::Related => {
+                    let mut results = Vec::new();
+                    for i in r.navigate_pointer("/contents")?.as_array_iter_mut()? {
+                        results.push(parse_related_from_mtrir(i.navigate_pointer(MTRIR)?)?);
+                    }
+                    top_releases.related = Some(GetArtistRelated { results });
+                }
                 ArtistTopReleaseCategory::Videos => (),
-                ArtistTopReleaseCategory::Singles => (),
-                ArtistTopReleaseCategory::Albums => {
+                ArtistTopReleaseCategory::Albums | ArtistTopReleaseCategory::Singles => {
                     let mut results = Vec::new();
                     for i in r.navigate_pointer("/contents")?.as_array_iter_mut()? {
                         results.push(parse_album_from_mtrir(i.navigate_pointer(MTRIR)?)?);
@@ -127,7 +139,12 @@ impl<'a> ProcessedResult<GetArtistQuery<'a>> {
                         params,
                         results,
                     };
-                    top_releases.albums = Some(albums);
+                    // Singles share a layout with albums.
+                    if let ArtistTopReleaseCategory::Singles = category {
+                        top_releases.singles = Some(albums);
+                    } else {
+                        top_releases.albums = Some(albums);
+                    }
                 }
                 ArtistTopReleaseCategory::Playlists => todo!(),
                 ArtistTopReleaseCategory::None => (),
@@ -177,7 +194,7 @@ impl<'a> ProcessedResult<GetArtistQuery<'a>> {
         })
     }
 }
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct GetArtistTopReleases {
     pub songs: Option<GetArtistSongs>,
     pub albums: Option<GetArtistAlbums>,
@@ -185,16 +202,16 @@ pub struct GetArtistTopReleases {
     pub videos: Option<GetArtistVideos>,
     pub related: Option<GetArtistRelated>,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GetArtistRelated {
     pub results: Vec<RelatedResult>,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GetArtistSongs {
     pub results: Vec<SongResult>,
     pub browse_id: PlaylistID<'static>,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GetArtistVideos {
     pub results: Vec<VideoResult>,
     pub browse_id: PlaylistID<'static>,
@@ -202,25 +219,28 @@ pub struct GetArtistVideos {
 /// The Albums section of the Browse Artist page.
 /// The browse_id and params can be used to get the full list of artist's albums.
 /// If they aren't set, and results is not empty, assuming that all albums are displayed here already.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GetArtistAlbums {
     pub results: Vec<AlbumResult>,
     // XXX: Unsure if AlbumID is correct here.
     pub browse_id: Option<AlbumID<'static>>,
     pub params: Option<BrowseParams<'static>>,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RelatedResult {
     pub browse_id: ChannelID<'static>,
     pub title: String,
     pub subscribers: String,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AlbumResult {
+    #[serde(flatten)]
     core: ResultCore,
+    pub year: Option<String>,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct VideoResult {
+    #[serde(flatten)]
     core: ResultCore,
 }
 impl YoutubeResult for VideoResult {
@@ -234,9 +254,10 @@ impl YoutubeResult for AlbumResult {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 // Could this alternatively be Result<Song>?
 pub struct SongResult {
+    #[serde(flatten)]
     core: ResultCore,
     video_id: VideoID<'static>,
     track_no: usize,
@@ -258,6 +279,118 @@ impl SongResult {
         self.track_no
     }
 }
+// Search results don't contain a track number, so this is set to 0.
+impl From<SearchResultSong> for SongResult {
+    fn from(value: SearchResultSong) -> Self {
+        let SearchResultSong {
+            title,
+            album,
+            duration,
+            explicit,
+            video_id,
+            thumbnails,
+            ..
+        } = value;
+        let core = ResultCore::new(
+            None,
+            Some(duration),
+            None,
+            None,
+            title,
+            None,
+            thumbnails,
+            true,
+            explicit == Explicit::IsExplicit,
+            None,
+            None,
+            None,
+            None,
+        );
+        Self {
+            core,
+            video_id,
+            track_no: 0,
+            album: Some(ParsedSongAlbum {
+                name: Some(album),
+                id: None,
+            }),
+        }
+    }
+}
+// Search results don't contain a track number, so this is set to 0.
+impl From<SearchResultVideo> for SongResult {
+    fn from(value: SearchResultVideo) -> Self {
+        let SearchResultVideo {
+            title,
+            video_id,
+            length,
+            thumbnails,
+            ..
+        } = value;
+        let core = ResultCore::new(
+            None,
+            Some(length),
+            None,
+            None,
+            title,
+            None,
+            thumbnails,
+            true,
+            false,
+            None,
+            None,
+            None,
+            None,
+        );
+        Self {
+            core,
+            video_id,
+            track_no: 0,
+            album: None,
+        }
+    }
+}
+// Chart results don't contain a track number or duration.
+impl From<ChartSong> for SongResult {
+    fn from(value: ChartSong) -> Self {
+        let ChartSong {
+            title,
+            video_id,
+            thumbnails,
+            ..
+        } = value;
+        let core = ResultCore::new(
+            None, None, None, None, title, None, thumbnails, true, false, None, None, None, None,
+        );
+        Self {
+            core,
+            video_id,
+            track_no: 0,
+            album: None,
+        }
+    }
+}
+impl From<Episode> for SongResult {
+    fn from(value: Episode) -> Self {
+        let Episode {
+            title,
+            duration,
+            video_id,
+            thumbnails,
+            ..
+        } = value;
+        let core = ResultCore::new(
+            None, duration, None, None, title, None, thumbnails, true, false, None, None, None,
+            None,
+        );
+        Self {
+            core,
+            video_id,
+            track_no: 0,
+            album: None,
+        }
+    }
+}
 // Should be at higher level in mod structure.
 #[derive(Debug)]
 enum ArtistTopReleaseCategory {
@@ -286,7 +419,7 @@ impl ArtistTopReleaseCategory {
 }
 pub(crate) fn parse_album_from_mtrir(mut navigator: JsonCrawlerBorrowed) -> Result<AlbumResult> {
     let title = navigator.take_value_pointer(TITLE_TEXT)?;
-    let _year: Option<String> = navigator.take_value_pointer(SUBTITLE2).ok();
+    let year = navigator.take_value_pointer(SUBTITLE2).ok();
     let browse_id: String = navigator.take_value_pointer(concatcp!(TITLE, NAVIGATION_BROWSE_ID))?;
     let thumbnails = navigator.take_value_pointer(THUMBNAIL_RENDERER)?;
     let is_explicit = navigator.path_exists(concatcp!(TITLE, SUBTITLE_BADGE_LABEL));
@@ -305,12 +438,48 @@ pub(crate) fn parse_album_from_mtrir(mut navigator: JsonCrawlerBorrowed) -> Resu
         None,
         None,
     );
-    Ok(AlbumResult { core })
+    Ok(AlbumResult { core, year })
+}
+pub(crate) fn parse_related_from_mtrir(
+    mut navigator: JsonCrawlerBorrowed,
+) -> Result<RelatedResult> {
+    let title = navigator.take_value_pointer(TITLE_TEXT)?;
+    let browse_id = navigator
+        .take_value_pointer(concatcp!(TITLE, NAVIGATION_BROWSE_ID))
+        .map(|b_id: String| ChannelID::from_raw(b_id))?;
+    // Subscriber count isn't always displayed.
+    let subscribers = navigator.take_value_pointer(SUBTITLE).unwrap_or_default();
+    Ok(RelatedResult {
+        browse_id,
+        title,
+        subscribers,
+    })
 }
 
 //TODO: Menu entries
 //TODO: Consider rename
 pub(crate) fn parse_playlist_items(music_shelf: MusicShelfContents) -> Result<Vec<SongResult>> {
+    Ok(parse_playlist_songs(music_shelf)?
+        .into_iter()
+        .map(|s| s.song)
+        .collect())
+}
+
+/// As per parse_playlist_items, but also keeps the artists for each song, as
+/// these differ between songs on a playlist.
+pub(crate) fn parse_playlist_songs(music_shelf: MusicShelfContents) -> Result<Vec<PlaylistSong>> {
+    Ok(parse_playlist_songs_with(music_shelf, |_| ())?
+        .into_iter()
+        .map(|(song, _)| song)
+        .collect())
+}
+
+/// As per parse_playlist_songs, but also parses extra data from each song's
+/// musicResponsiveListItemRenderer, such as a menu entry that only exists for that query type.
+pub(crate) fn parse_playlist_songs_with<T>(
+    music_shelf: MusicShelfContents,
+    mut parse_extra: impl FnMut(&mut JsonCrawlerBorrowed) -> T,
+) -> Result<Vec<(PlaylistSong, T)>> {
     let MusicShelfContents { json } = music_shelf;
     let mut results = Vec::new();
     // this should be set in each loop not here...
@@ -375,7 +544,7 @@ pub(crate) fn parse_playlist_items(music_shelf: MusicShelfContents) -> Result<Ve
         // It depends on the query type so consider reflecting this in the code.
         // XXX: Consider which parts of this query are mandatory as currently erroring.
         // Using OK as a crutch to avoid error.
-        let _artists = super::parse_song_artists(&mut data, 1)?;
+        let artists = super::parse_song_artists(&mut data, 1)?;
         // Album may not exist, using an Option to reflect this.
         // It depends on the query type so consider reflecting this in the code.
         let album = super::parse_song_album(&mut data, 2).ok();
@@ -410,7 +579,7 @@ pub(crate) fn parse_playlist_items(music_shelf: MusicShelfContents) -> Result<Ve
             ))
             .ok();
 
-        let result = SongResult {
+        let song = SongResult {
             core: ResultCore::new(
                 set_video_id,
                 duration,
@@ -431,12 +600,8 @@ pub(crate) fn parse_playlist_items(music_shelf: MusicShelfContents) -> Result<Ve
             // Need to add parsing for this.
             track_no: i + 1,
         };
-        // TODO: Menu_entries
-        //    if menu_entries:
-        //        for menu_entry in menu_entries:
-        //            song[menu_entry[-1]] = nav(data, MENU_ITEMS + menu_entry)
-        //
-        results.push(result);
+        let extra = parse_extra(&mut data);
+        results.push((PlaylistSong { song, artists }, extra));
     }
     Ok(results)
 }
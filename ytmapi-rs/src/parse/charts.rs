@@ -0,0 +1,164 @@
+use super::{parse_item_text, parse_song_artists, ParsedSongArtist, ProcessedResult};
+use crate::common::{Thumbnail, YoutubeID};
+use crate::crawler::JsonCrawlerBorrowed;
+use crate::nav_consts::{
+    CAROUSEL_CONTENTS, FRAMEWORK_MUTATIONS, MRLIR, MTRIR, MUSIC_SHELF, NAVIGATION_BROWSE_ID,
+    PLAY_BUTTON, SECTION_LIST, SINGLE_COLUMN_TAB, THUMBNAILS, THUMBNAIL_RENDERER, TITLE,
+    TITLE_TEXT,
+};
+use crate::query::GetChartsQuery;
+use crate::{ChannelID, Result, VideoID};
+use const_format::concatcp;
+use serde::{Deserialize, Serialize};
+
+const COUNTRY_SELECTOR: &str = concatcp!(
+    MUSIC_SHELF,
+    "/subheaders/0/musicSideAlignedItemRenderer/startItems/0/musicSortFilterButtonRenderer",
+    TITLE,
+    "/text"
+);
+const COUNTRY_OPTION: &str = "/payload/musicFormBooleanChoice/opaqueToken";
+const CHART_RANK: &str = "/customIndexColumn/musicCustomIndexColumnRenderer/text/runs/0/text";
+
+/// The top charts for a country.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Charts {
+    /// Name of the country the charts are for.
+    pub country: String,
+    /// Country codes that can be passed to GetChartsQuery::with_country.
+    pub country_options: Vec<String>,
+    /// Only returned for some countries, and if authenticated.
+    pub songs: Vec<ChartSong>,
+    pub videos: Vec<ChartSong>,
+    pub artists: Vec<ChartArtist>,
+    /// Not returned for the global charts.
+    pub trending: Vec<ChartSong>,
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartSong {
+    pub title: String,
+    pub artists: Vec<ParsedSongArtist>,
+    pub video_id: VideoID<'static>,
+    pub rank: Option<String>,
+    pub thumbnails: Vec<Thumbnail>,
+}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartArtist {
+    pub title: String,
+    pub browse_id: ChannelID<'static>,
+    pub subscribers: Option<String>,
+    pub rank: Option<String>,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+impl<'a> ProcessedResult<GetChartsQuery<'a>> {
+    pub fn parse(self) -> Result<Charts> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let mut charts = Charts::default();
+        // Country options aren't stored with the rest of the results.
+        if let Ok(mutations) = json_crawler.borrow_pointer(FRAMEWORK_MUTATIONS) {
+            charts.country_options = mutations
+                .into_array_iter_mut()?
+                .filter_map(|mut m| m.take_value_pointer(COUNTRY_OPTION).ok())
+                .collect();
+        }
+        let sections = json_crawler.borrow_pointer(concatcp!(SINGLE_COLUMN_TAB, SECTION_LIST))?;
+        for mut section in sections.into_array_iter_mut()? {
+            if let Ok(country) = section.take_value_pointer(COUNTRY_SELECTOR) {
+                charts.country = country;
+                continue;
+            }
+            let Ok(contents) = section.borrow_pointer(CAROUSEL_CONTENTS) else {
+                continue;
+            };
+            // The carousel titles are localised, so instead the type of chart is determined
+            // from its contents. Genres are returned as playlists, and are skipped.
+            let mut items = contents.into_array_iter_mut()?.peekable();
+            let Some(first) = items.peek() else {
+                continue;
+            };
+            if first.path_exists(MTRIR) {
+                if !first.path_exists(concatcp!(MTRIR, "/navigationEndpoint/watchEndpoint")) {
+                    continue;
+                }
+                charts.videos = items.map(parse_chart_video).collect::<Result<_>>()?;
+            } else if first.path_exists(concatcp!(MRLIR, NAVIGATION_BROWSE_ID)) {
+                charts.artists = items.map(parse_chart_artist).collect::<Result<_>>()?;
+            } else {
+                let songs = items.map(parse_chart_song).collect::<Result<_>>()?;
+                // Top songs are listed before trending.
+                if charts.songs.is_empty() {
+                    charts.songs = songs;
+                } else {
+                    charts.trending = songs;
+                }
+            }
+        }
+        Ok(charts)
+    }
+}
+
+fn parse_chart_song(item: JsonCrawlerBorrowed) -> Result<ChartSong> {
+    let mut data = item.navigate_pointer(MRLIR)?;
+    let title = parse_item_text(&mut data, 0, 0)?;
+    let artists = parse_song_artists(&mut data, 1)?;
+    let video_id = data
+        .take_value_pointer(concatcp!(
+            PLAY_BUTTON,
+            "/playNavigationEndpoint/watchEndpoint/videoId"
+        ))
+        .map(|v: String| VideoID::from_raw(v))?;
+    let rank = data.take_value_pointer(CHART_RANK).ok();
+    let thumbnails = data.take_value_pointer(THUMBNAILS).unwrap_or_default();
+    Ok(ChartSong {
+        title,
+        artists,
+        video_id,
+        rank,
+        thumbnails,
+    })
+}
+
+fn parse_chart_video(item: JsonCrawlerBorrowed) -> Result<ChartSong> {
+    let mut data = item.navigate_pointer(MTRIR)?;
+    let title = data.take_value_pointer(TITLE_TEXT)?;
+    let video_id = data
+        .take_value_pointer("/navigationEndpoint/watchEndpoint/videoId")
+        .map(|v: String| VideoID::from_raw(v))?;
+    // The first subtitle run is the artist, the remaining runs are the view count.
+    let artists = data
+        .take_value_pointer("/subtitle/runs/0/text")
+        .map(|name| ParsedSongArtist { name, id: None })
+        .into_iter()
+        .collect();
+    let thumbnails = data
+        .take_value_pointer(THUMBNAIL_RENDERER)
+        .unwrap_or_default();
+    Ok(ChartSong {
+        title,
+        artists,
+        video_id,
+        rank: None,
+        thumbnails,
+    })
+}
+
+fn parse_chart_artist(item: JsonCrawlerBorrowed) -> Result<ChartArtist> {
+    let mut data = item.navigate_pointer(MRLIR)?;
+    let title = parse_item_text(&mut data, 0, 0)?;
+    let browse_id = data
+        .take_value_pointer(NAVIGATION_BROWSE_ID)
+        .map(|c: String| ChannelID::from_raw(c))?;
+    let subscribers = parse_item_text(&mut data, 1, 0).ok();
+    let rank = data.take_value_pointer(CHART_RANK).ok();
+    let thumbnails = data.take_value_pointer(THUMBNAILS).unwrap_or_default();
+    Ok(ChartArtist {
+        title,
+        browse_id,
+        subscribers,
+        rank,
+        thumbnails,
+    })
+}
@@ -0,0 +1,140 @@
+use super::{parse_playlist_songs_with, MusicShelfContents, PlaylistSong, ProcessedResult};
+use crate::crawler::JsonCrawlerBorrowed;
+use crate::nav_consts::{
+    FEEDBACK_TOKEN, MENU_ITEMS, MENU_SERVICE, MUSIC_SHELF, SECTION_LIST, SINGLE_COLUMN_TAB,
+    TITLE_TEXT,
+};
+use crate::query::{GetHistoryQuery, RemoveHistoryItemsQuery};
+use crate::{Error, Result};
+use const_format::concatcp;
+use serde::Serialize;
+
+/// A song from the user's history.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryItem {
+    #[serde(flatten)]
+    pub song: PlaylistSong,
+    /// When the song was played, as grouped by YouTube Music e.g "Today" or "Last week".
+    pub played: String,
+    /// Token to remove the song from history using RemoveHistoryItemsQuery.
+    pub feedback_token: Option<String>,
+}
+
+impl ProcessedResult<GetHistoryQuery> {
+    pub fn parse(self) -> Result<Vec<HistoryItem>> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let mut results = Vec::new();
+        for mut section in json_crawler
+            .borrow_pointer(concatcp!(SINGLE_COLUMN_TAB, SECTION_LIST))?
+            .into_array_iter_mut()?
+        {
+            // Sections other than songs, such as the notice shown when history is empty, are
+            // skipped.
+            let Ok(mut shelf) = section.borrow_pointer(MUSIC_SHELF) else {
+                continue;
+            };
+            let played: String = shelf.take_value_pointer(TITLE_TEXT)?;
+            let contents = MusicShelfContents::from_crawler(shelf.borrow_pointer("/contents")?);
+            let songs = parse_playlist_songs_with(contents, take_remove_feedback_token)?;
+            results.extend(songs.into_iter().map(|(song, feedback_token)| HistoryItem {
+                song,
+                played: played.clone(),
+                feedback_token,
+            }));
+        }
+        Ok(results)
+    }
+}
+
+impl<'a> ProcessedResult<RemoveHistoryItemsQuery<'a>> {
+    pub fn parse(self) -> Result<()> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let processed: Vec<bool> = json_crawler
+            .borrow_pointer("/feedbackResponses")?
+            .into_array_iter_mut()?
+            .map(|mut response| response.take_value_pointer("/isProcessed"))
+            .collect::<Result<_>>()?;
+        if processed.iter().all(|p| *p) {
+            Ok(())
+        } else {
+            Err(Error::other("Not all songs were removed from history"))
+        }
+    }
+}
+
+// The remove from history entry is the last item of the song's menu.
+fn take_remove_feedback_token(data: &mut JsonCrawlerBorrowed) -> Option<String> {
+    data.borrow_pointer(MENU_ITEMS)
+        .ok()?
+        .into_array_iter_mut()
+        .ok()?
+        .filter_map(|mut item| {
+            item.take_value_pointer(concatcp!(MENU_SERVICE, FEEDBACK_TOKEN))
+                .ok()
+        })
+        .last()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{youtuberesult::YoutubeResult, YoutubeID},
+        crawler::JsonCrawler,
+        parse::ProcessedResult,
+        process::JsonCloner,
+        query::{GetHistoryQuery, RemoveHistoryItemsQuery},
+    };
+
+    #[test]
+    fn test_get_history_dummy_json() {
+        let testfile = std::fs::read_to_string("test_json/get_history.json").unwrap();
+        let cloner = JsonCloner::from_string(testfile).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let processed = ProcessedResult::from_raw(json_crawler, GetHistoryQuery);
+        let result = processed.parse().unwrap();
+        let summary: Vec<_> = result
+            .iter()
+            .map(|item| {
+                (
+                    item.song.song.get_title().as_str(),
+                    item.song.song.get_video_id().get_raw(),
+                    item.played.as_str(),
+                    item.feedback_token.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Wish You Were Here",
+                    "IXdNnw99-Ic",
+                    "Today",
+                    Some("AB9zfpKhistory1")
+                ),
+                ("Money", "-0kcet4aPpQ", "Today", Some("AB9zfpKhistory2")),
+                (
+                    "Paranoid Android",
+                    "fHiGbolFFGw",
+                    "Yesterday",
+                    Some("AB9zfpKhistory3")
+                ),
+            ]
+        );
+        assert_eq!(result[2].song.artists[0].name, "Radiohead");
+    }
+    #[test]
+    fn test_remove_history_items_dummy_json() {
+        let parse = |json: &str| {
+            let cloner = JsonCloner::from_string(json.to_string()).unwrap();
+            let json_crawler = JsonCrawler::from_json_cloner(cloner);
+            ProcessedResult::from_raw(json_crawler, RemoveHistoryItemsQuery::new(["token"])).parse()
+        };
+        assert!(parse(r#"{"feedbackResponses":[{"isProcessed":true}]}"#).is_ok());
+        assert!(parse(r#"{"feedbackResponses":[{"isProcessed":false}]}"#).is_err());
+    }
+}
@@ -1,15 +1,24 @@
 use super::{parse_item_text, ProcessedResult};
-use crate::common::library::{LibraryArtist, Playlist};
+use crate::common::library::{LibraryAlbum, LibraryArtist, LibraryPodcast, Playlist};
 use crate::common::PlaylistID;
-use crate::crawler::JsonCrawler;
+use crate::crawler::{JsonCrawler, JsonCrawlerBorrowed};
 use crate::nav_consts::{
     GRID, ITEM_SECTION, MRLIR, MTRIR, MUSIC_SHELF, NAVIGATION_BROWSE_ID, SECTION_LIST,
-    SECTION_LIST_ITEM, SINGLE_COLUMN_TAB, THUMBNAIL_RENDERER, TITLE, TITLE_TEXT,
+    SECTION_LIST_ITEM, SINGLE_COLUMN_TAB, SUBTITLE, SUBTITLE_RUNS, THUMBNAIL_RENDERER, TITLE,
+    TITLE_TEXT,
+};
+use crate::query::{
+    GetLibraryAlbumsQuery, GetLibraryArtistsQuery, GetLibraryPlaylistsQuery,
+    GetLibraryPodcastsQuery, GetLibrarySubscriptionsQuery, SubscribeArtistQuery,
+    UnsubscribeArtistQuery,
 };
-use crate::query::{GetLibraryArtistsQuery, GetLibraryPlaylistsQuery};
 use crate::{Result, Thumbnail};
 use const_format::concatcp;
 
+const MENU_ITEMS: &str = "/menu/menuRenderer/items";
+const EDIT_PLAYLIST_MENU_ITEM: &str =
+    "/menuNavigationItemRenderer/navigationEndpoint/playlistEditorEndpoint";
+
 impl<'a> ProcessedResult<GetLibraryArtistsQuery> {
     // TODO: Continuations
     pub fn parse(self) -> Result<Vec<LibraryArtist>> {
@@ -18,6 +27,28 @@ impl<'a> ProcessedResult<GetLibraryArtistsQuery> {
     }
 }
 
+impl ProcessedResult<GetLibrarySubscriptionsQuery> {
+    // TODO: Continuations
+    pub fn parse(self) -> Result<Vec<LibraryArtist>> {
+        let ProcessedResult { json_crawler, .. } = self;
+        parse_library_artists(json_crawler)
+    }
+}
+
+impl<'a> ProcessedResult<SubscribeArtistQuery<'a>> {
+    // The response contains no useful information, errors are already caught when processing.
+    pub fn parse(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ProcessedResult<UnsubscribeArtistQuery<'a>> {
+    // The response contains no useful information, errors are already caught when processing.
+    pub fn parse(self) -> Result<()> {
+        Ok(())
+    }
+}
+
 impl<'a> ProcessedResult<GetLibraryPlaylistsQuery> {
     // TODO: Continuations
     // TODO: Implement count and author fields
@@ -27,6 +58,34 @@ impl<'a> ProcessedResult<GetLibraryPlaylistsQuery> {
     }
 }
 
+impl ProcessedResult<GetLibraryAlbumsQuery> {
+    // TODO: Continuations
+    pub fn parse(self) -> Result<Vec<LibraryAlbum>> {
+        let ProcessedResult { json_crawler, .. } = self;
+        if let Some(contents) = process_library_contents_grid(json_crawler) {
+            contents
+                .navigate_pointer("/items")?
+                .as_array_iter_mut()?
+                .map(parse_library_album)
+                .collect()
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
+
+impl ProcessedResult<GetLibraryPodcastsQuery> {
+    // TODO: Continuations
+    pub fn parse(self) -> Result<Vec<LibraryPodcast>> {
+        let ProcessedResult { json_crawler, .. } = self;
+        if let Some(contents) = process_library_contents_grid(json_crawler) {
+            parse_content_list_podcasts(contents)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
+
 fn parse_library_artists(json_crawler: JsonCrawler) -> Result<Vec<LibraryArtist>> {
     if let Some(contents) = process_library_contents_music_shelf(json_crawler) {
         parse_content_list_artists(contents)
@@ -100,6 +159,56 @@ fn parse_content_list_artists(json_crawler: JsonCrawler) -> Result<Vec<LibraryAr
     Ok(results)
 }
 
+fn parse_content_list_podcasts(json_crawler: JsonCrawler) -> Result<Vec<LibraryPodcast>> {
+    json_crawler
+        .navigate_pointer("/items")?
+        .as_array_iter_mut()?
+        // First result is just a link to the New Episodes auto playlist.
+        .skip(1)
+        .map(parse_library_podcast)
+        .collect()
+}
+
+fn parse_library_podcast(item: JsonCrawlerBorrowed) -> Result<LibraryPodcast> {
+    let mut data = item.navigate_pointer(MTRIR)?;
+    let title = data.take_value_pointer(TITLE_TEXT)?;
+    let podcast_id = data.take_value_pointer(concatcp!(TITLE, NAVIGATION_BROWSE_ID))?;
+    let channel = data.take_value_pointer(SUBTITLE).ok();
+    let thumbnails = data.take_value_pointer(THUMBNAIL_RENDERER)?;
+    Ok(LibraryPodcast {
+        podcast_id,
+        title,
+        channel,
+        thumbnails,
+    })
+}
+
+fn parse_library_album(item: JsonCrawlerBorrowed) -> Result<LibraryAlbum> {
+    let mut data = item.navigate_pointer(MTRIR)?;
+    let title = data.take_value_pointer(TITLE_TEXT)?;
+    let album_id = data.take_value_pointer(concatcp!(TITLE, NAVIGATION_BROWSE_ID))?;
+    let thumbnails = data.take_value_pointer(THUMBNAIL_RENDERER)?;
+    // Subtitle is e.g "Album • Artist • 2020", the year is missing for some albums.
+    let subtitle: Vec<String> = data
+        .take_value_pointer::<Vec<serde_json::Value>, _>(SUBTITLE_RUNS)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|run| run.get("text")?.as_str().map(str::to_string))
+        .collect();
+    let artist = subtitle.get(2).cloned().unwrap_or_default();
+    let year = subtitle
+        .last()
+        .filter(|text| text.len() == 4 && text.chars().all(|c| c.is_ascii_digit()))
+        .cloned();
+    Ok(LibraryAlbum {
+        album_id,
+        title,
+        artist,
+        year,
+        thumbnails,
+    })
+}
+
 fn parse_content_list_playlist(json_crawler: JsonCrawler) -> Result<Vec<Playlist>> {
     // TODO: Implement count and author fields
     let mut results = Vec::new();
@@ -118,6 +227,13 @@ fn parse_content_list_playlist(json_crawler: JsonCrawler) -> Result<Vec<Playlist
             // Revisit later if we crash.
             .take_value()?;
         let thumbnails: Vec<Thumbnail> = result.take_value_pointer(THUMBNAIL_RENDERER)?;
+        // Privacy isn't listed in the library, but editable playlists have an edit menu item.
+        let can_edit = match result.borrow_pointer(MENU_ITEMS) {
+            Ok(items) => items
+                .into_array_iter_mut()?
+                .any(|item| item.path_exists(EDIT_PLAYLIST_MENU_ITEM)),
+            Err(_) => false,
+        };
         let mut description = None;
         let count = None;
         let author = None;
@@ -137,6 +253,7 @@ fn parse_content_list_playlist(json_crawler: JsonCrawler) -> Result<Vec<Playlist
             title,
             thumbnails,
             count,
+            can_edit,
         };
         results.push(playlist)
     }
@@ -146,11 +263,11 @@ fn parse_content_list_playlist(json_crawler: JsonCrawler) -> Result<Vec<Playlist
 #[cfg(test)]
 mod tests {
     use crate::{
-        common::library::{LibraryArtist, Playlist},
+        common::library::{LibraryAlbum, LibraryArtist, Playlist},
         crawler::JsonCrawler,
         parse::ProcessedResult,
         process::JsonCloner,
-        query::{GetLibraryArtistsQuery, GetLibraryPlaylistsQuery},
+        query::{GetLibraryAlbumsQuery, GetLibraryArtistsQuery, GetLibraryPlaylistsQuery},
     };
     use serde_json::json;
 
@@ -180,7 +297,8 @@ mod tests {
             ],
             "count": null,
             "description": "Auto playlist",
-            "author": null
+            "author": null,
+            "can_edit": false
           },
           {
             "playlist_id": "VLPLCZQcydUIP07hMOwAXIag92l76d3z3Thv",
@@ -199,7 +317,8 @@ mod tests {
             ],
             "count": null,
             "description": "Nick Dowsett • 20 tracks",
-            "author": null
+            "author": null,
+            "can_edit": true
           },
           {
             "playlist_id": "VLRDCLAK5uy_lRzD6ZcGWU_ef3r4y7ifNYLiGmCCX_jIk",
@@ -218,7 +337,8 @@ mod tests {
             ],
             "count": null,
             "description": "YouTube Music • 50 songs",
-            "author": null
+            "author": null,
+            "can_edit": false
           },
           {
             "playlist_id": "VLSE",
@@ -237,7 +357,8 @@ mod tests {
             ],
             "count": null,
             "description": "Episodes you save for later",
-            "author": null
+            "author": null,
+            "can_edit": false
           }
         ]);
         let expected: Vec<Playlist> = serde_json::from_value(expected).unwrap();
@@ -272,4 +393,42 @@ mod tests {
         let expected: Vec<LibraryArtist> = serde_json::from_value(expected).unwrap();
         assert_eq!(result, expected);
     }
+    #[test]
+    fn test_library_albums_dummy_json() {
+        let testfile = std::fs::read_to_string("test_json/get_library_albums.json").unwrap();
+        let cloner = JsonCloner::from_string(testfile).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let processed = ProcessedResult::from_raw(json_crawler, GetLibraryAlbumsQuery);
+        let result = processed.parse().unwrap();
+        let thumbnails = json!([{
+            "height": 226,
+            "width": 226,
+            "url": "https://lh3.googleusercontent.com/placeholder=w226-h226-l90-rj"
+        }]);
+        let expected = json!([
+            {
+                "album_id": "MPREb_C1fGfaCjvdH",
+                "title": "OK Computer",
+                "artist": "Radiohead",
+                "year": "1997",
+                "thumbnails": thumbnails
+            },
+            {
+                "album_id": "MPREb_uw1JnHXb8Ty",
+                "title": "Kid A",
+                "artist": "Radiohead",
+                "year": "2000",
+                "thumbnails": thumbnails
+            },
+            {
+                "album_id": "MPREb_8QkDeEIawvX",
+                "title": "Spaces",
+                "artist": "Nils Frahm",
+                "year": null,
+                "thumbnails": thumbnails
+            }
+        ]);
+        let expected: Vec<LibraryAlbum> = serde_json::from_value(expected).unwrap();
+        assert_eq!(result, expected);
+    }
 }
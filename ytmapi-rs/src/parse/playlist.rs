@@ -0,0 +1,308 @@
+use super::{parse_playlist_songs, MusicShelfContents, ParsedSongArtist, ProcessedResult};
+use crate::common::{PlaylistID, PrivacyStatus, YoutubeID};
+use crate::crawler::{JsonCrawler, JsonCrawlerBorrowed};
+use crate::nav_consts::{
+    HEADER_DETAIL, NAVIGATION_BROWSE, PAGE_TYPE, SECTION_LIST_ITEM, SINGLE_COLUMN_TAB, SUBTITLE2,
+    SUBTITLE_RUNS, TITLE_TEXT,
+};
+use crate::query::{
+    AddPlaylistItemsQuery, CreatePlaylistQuery, EditPlaylistQuery, GetPlaylistContinuationQuery,
+    GetPlaylistQuery,
+};
+use crate::{Error, Result};
+use const_format::concatcp;
+use serde::Serialize;
+
+use super::SongResult;
+
+const PLAYLIST_SHELF: &str = "/musicPlaylistShelfRenderer";
+const PLAYLIST_SHELF_CONTINUATION: &str = "/continuationContents/musicPlaylistShelfContinuation";
+const EDIT_PLAYLIST_SUCCEEDED: &str = "STATUS_SUCCEEDED";
+const NEXT_CONTINUATION: &str = "/continuations/0/nextContinuationData/continuation";
+// Playlists owned by the user have an editable header wrapping the regular one.
+const EDITABLE_HEADER: &str = "/header/musicEditablePlaylistDetailHeaderRenderer";
+const EDITABLE_HEADER_DETAIL: &str =
+    concatcp!(EDITABLE_HEADER, "/header/musicDetailHeaderRenderer");
+const EDITABLE_HEADER_PRIVACY: &str = concatcp!(
+    EDITABLE_HEADER,
+    "/editHeader/musicPlaylistEditHeaderRenderer/privacy"
+);
+const USER_CHANNEL_PAGE_TYPE: &str = "MUSIC_PAGE_TYPE_USER_CHANNEL";
+const SECOND_SUBTITLE_RUNS: &str = "/secondSubtitle/runs";
+
+/// A song on a playlist. Unlike an album, each song may have different artists.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaylistSong {
+    pub song: SongResult,
+    pub artists: Vec<ParsedSongArtist>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaylistParams {
+    pub title: String,
+    pub author: Option<String>,
+    /// Only known for playlists the user can edit. Other playlists may be public, or unlisted
+    /// and opened by link.
+    pub privacy_status: Option<PrivacyStatus>,
+    /// Users credited in the header, which lists every collaborator on a collaborative
+    /// playlist.
+    pub collaborators: Vec<String>,
+    /// True if the user can edit the playlist, e.g because they own it.
+    pub can_edit: bool,
+    /// Number of tracks on the whole playlist, which may be more than the tracks on the first
+    /// page.
+    pub track_count: Option<usize>,
+    pub tracks: Vec<PlaylistSong>,
+    /// Token to get the next page of tracks, if the playlist is too large to be
+    /// returned in one go.
+    pub continuation: Option<String>,
+}
+
+/// A page of tracks retrieved from a GetPlaylistContinuationQuery.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaylistContinuationParams {
+    pub tracks: Vec<PlaylistSong>,
+    pub continuation: Option<String>,
+}
+
+impl<'a> ProcessedResult<GetPlaylistQuery<'a>> {
+    pub fn parse(self) -> Result<PlaylistParams> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let can_edit = json_crawler.path_exists(EDITABLE_HEADER_DETAIL);
+        let (header_path, privacy_status) = if can_edit {
+            let privacy_status = json_crawler.take_value_pointer(EDITABLE_HEADER_PRIVACY)?;
+            (EDITABLE_HEADER_DETAIL, Some(privacy_status))
+        } else {
+            (HEADER_DETAIL, None)
+        };
+        let mut header = json_crawler.borrow_pointer(header_path)?;
+        let title = header.take_value_pointer(TITLE_TEXT)?;
+        let collaborators = match header.borrow_pointer(SUBTITLE_RUNS) {
+            Ok(runs) => parse_user_runs(runs)?,
+            Err(_) => Vec::new(),
+        };
+        // The author's name has already been taken if it links to their channel.
+        let author = match collaborators.first() {
+            Some(author) => Some(author.clone()),
+            None => header.take_value_pointer(SUBTITLE2).ok(),
+        };
+        let track_count = parse_track_count(&mut header, can_edit);
+        let shelf = json_crawler.borrow_pointer(concatcp!(
+            SINGLE_COLUMN_TAB,
+            SECTION_LIST_ITEM,
+            PLAYLIST_SHELF
+        ))?;
+        let (tracks, continuation) = parse_playlist_shelf(shelf)?;
+        Ok(PlaylistParams {
+            title,
+            author,
+            privacy_status,
+            collaborators,
+            can_edit,
+            track_count,
+            tracks,
+            continuation,
+        })
+    }
+}
+
+impl<'a> ProcessedResult<AddPlaylistItemsQuery<'a>> {
+    pub fn parse(self) -> Result<()> {
+        let ProcessedResult { json_crawler, .. } = self;
+        parse_edit_playlist_status(json_crawler)
+    }
+}
+
+impl<'a> ProcessedResult<EditPlaylistQuery<'a>> {
+    pub fn parse(self) -> Result<()> {
+        let ProcessedResult { json_crawler, .. } = self;
+        parse_edit_playlist_status(json_crawler)
+    }
+}
+
+fn parse_edit_playlist_status(mut json_crawler: JsonCrawler) -> Result<()> {
+    let status: String = json_crawler.take_value_pointer("/status")?;
+    if status == EDIT_PLAYLIST_SUCCEEDED {
+        Ok(())
+    } else {
+        Err(Error::other(format!(
+            "Editing playlist returned status {status}"
+        )))
+    }
+}
+
+/// The number of tracks listed in the header, e.g "1,234 songs".
+fn parse_track_count(header: &mut JsonCrawlerBorrowed, can_edit: bool) -> Option<usize> {
+    let runs_len = header
+        .borrow_pointer(SECOND_SUBTITLE_RUNS)
+        .ok()
+        .and_then(|runs| runs.into_array_iter_mut().ok())
+        .map(|runs| runs.len())?;
+    // Playlists the user can edit also list their views before the track count.
+    let index = if can_edit && runs_len > 3 { 2 } else { 0 };
+    let text: String = header
+        .take_value_pointer(format!("{SECOND_SUBTITLE_RUNS}/{index}/text"))
+        .ok()?;
+    let count = text.split_whitespace().next()?;
+    count.replace([',', '.'], "").parse().ok()
+}
+
+/// The names of the users linked to in a list of text runs.
+fn parse_user_runs(runs: JsonCrawlerBorrowed) -> Result<Vec<String>> {
+    let mut users = Vec::new();
+    for mut run in runs.into_array_iter_mut()? {
+        let page_type: Option<String> = run
+            .take_value_pointer(concatcp!(NAVIGATION_BROWSE, PAGE_TYPE))
+            .ok();
+        if page_type.as_deref() == Some(USER_CHANNEL_PAGE_TYPE) {
+            users.push(run.take_value_pointer("/text")?);
+        }
+    }
+    Ok(users)
+}
+
+impl<'a> ProcessedResult<CreatePlaylistQuery<'a>> {
+    /// The id of the new playlist.
+    pub fn parse(self) -> Result<PlaylistID<'static>> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let id: String = json_crawler.take_value_pointer("/playlistId")?;
+        Ok(PlaylistID::from_raw(id))
+    }
+}
+
+impl ProcessedResult<GetPlaylistContinuationQuery> {
+    // TODO: Track numbers restart from 1 on each page.
+    pub fn parse(self) -> Result<PlaylistContinuationParams> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let shelf = json_crawler.borrow_pointer(PLAYLIST_SHELF_CONTINUATION)?;
+        let (tracks, continuation) = parse_playlist_shelf(shelf)?;
+        Ok(PlaylistContinuationParams {
+            tracks,
+            continuation,
+        })
+    }
+}
+
+fn parse_playlist_shelf(
+    mut shelf: JsonCrawlerBorrowed,
+) -> Result<(Vec<PlaylistSong>, Option<String>)> {
+    let continuation = shelf.take_value_pointer(NEXT_CONTINUATION).ok();
+    // An empty playlist has no contents.
+    let tracks = match shelf.borrow_pointer("/contents") {
+        Ok(contents) => parse_playlist_songs(MusicShelfContents::from_crawler(contents))?,
+        Err(_) => Vec::new(),
+    };
+    Ok((tracks, continuation))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{PlaylistID, PrivacyStatus, YoutubeID},
+        crawler::JsonCrawler,
+        parse::ProcessedResult,
+        process::JsonCloner,
+        query::{CreatePlaylistQuery, GetPlaylistQuery},
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_create_playlist_dummy_json() {
+        let testfile = std::fs::read_to_string("test_json/create_playlist.json").unwrap();
+        let cloner = JsonCloner::from_string(testfile).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let processed = ProcessedResult::from_raw(json_crawler, CreatePlaylistQuery::new("Test"));
+        assert_eq!(
+            processed.parse().unwrap(),
+            PlaylistID::from_raw("PLQwVIlKxHM6qv-o99iX9R85og7IzF9YS_")
+        );
+    }
+    #[test]
+    fn test_get_editable_playlist_dummy_json() {
+        let user_run = |name: &str| {
+            json!({
+                "text": name,
+                "navigationEndpoint": { "browseEndpoint": {
+                    "browseId": "UCabc",
+                    "browseEndpointContextSupportedConfigs": { "browseEndpointContextMusicConfig": {
+                        "pageType": "MUSIC_PAGE_TYPE_USER_CHANNEL"
+                    }}
+                }}
+            })
+        };
+        let testfile = json!({
+            "header": { "musicEditablePlaylistDetailHeaderRenderer": {
+                "editHeader": { "musicPlaylistEditHeaderRenderer": { "privacy": "UNLISTED" } },
+                "header": { "musicDetailHeaderRenderer": {
+                    "title": { "runs": [{ "text": "Road trip" }] },
+                    "subtitle": { "runs": [
+                        { "text": "Playlist" },
+                        { "text": " • " },
+                        user_run("Alice"),
+                        { "text": " & " },
+                        user_run("Bob"),
+                        { "text": " • " },
+                        { "text": "2024" }
+                    ]},
+                    "secondSubtitle": { "runs": [
+                        { "text": "1,234 views" },
+                        { "text": " • " },
+                        { "text": "1,050 songs" },
+                        { "text": " • " },
+                        { "text": "3+ hours" }
+                    ]}
+                }}
+            }},
+            "contents": { "singleColumnBrowseResultsRenderer": { "tabs": [{ "tabRenderer": {
+                "content": { "sectionListRenderer": { "contents": [
+                    { "musicPlaylistShelfRenderer": {} }
+                ]}}
+            }}]}}
+        });
+        let cloner = JsonCloner::from_string(testfile.to_string()).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let processed = ProcessedResult::from_raw(
+            json_crawler,
+            GetPlaylistQuery::new(PlaylistID::from_raw("PLabc")),
+        );
+        let playlist = processed.parse().unwrap();
+        assert_eq!(playlist.title, "Road trip");
+        assert_eq!(playlist.privacy_status, Some(PrivacyStatus::Unlisted));
+        assert_eq!(playlist.author.as_deref(), Some("Alice"));
+        assert_eq!(playlist.collaborators, vec!["Alice", "Bob"]);
+        assert!(playlist.can_edit);
+        assert_eq!(playlist.track_count, Some(1050));
+        assert!(playlist.tracks.is_empty());
+    }
+    #[test]
+    fn test_get_playlist_unknown_privacy_dummy_json() {
+        let testfile = json!({
+            "header": { "musicDetailHeaderRenderer": {
+                "title": { "runs": [{ "text": "Shared mix" }] },
+                "secondSubtitle": { "runs": [{ "text": "12 songs" }] }
+            }},
+            "contents": { "singleColumnBrowseResultsRenderer": { "tabs": [{ "tabRenderer": {
+                "content": { "sectionListRenderer": { "contents": [
+                    { "musicPlaylistShelfRenderer": {} }
+                ]}}
+            }}]}}
+        });
+        let cloner = JsonCloner::from_string(testfile.to_string()).unwrap();
+        let json_crawler = JsonCrawler::from_json_cloner(cloner);
+        let processed = ProcessedResult::from_raw(
+            json_crawler,
+            GetPlaylistQuery::new(PlaylistID::from_raw("PLabc")),
+        );
+        let playlist = processed.parse().unwrap();
+        assert_eq!(playlist.title, "Shared mix");
+        assert_eq!(playlist.privacy_status, None);
+        assert!(!playlist.can_edit);
+        assert_eq!(playlist.track_count, Some(12));
+    }
+}
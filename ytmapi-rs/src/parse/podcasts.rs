@@ -0,0 +1,106 @@
+use super::ProcessedResult;
+use crate::common::{Thumbnail, YoutubeID};
+use crate::crawler::JsonCrawlerBorrowed;
+use crate::nav_consts::{
+    DESCRIPTION, DESCRIPTION_SHELF, MUSIC_SHELF, RUN_TEXT, SECTION_LIST_ITEM, SUBTITLE,
+    TAB_CONTENT, THUMBNAILS, TITLE_TEXT,
+};
+use crate::query::{GetNewEpisodesQuery, GetPodcastQuery};
+use crate::{Result, VideoID};
+use const_format::concatcp;
+use serde::{Deserialize, Serialize};
+
+const TWO_COLUMN: &str = "/contents/twoColumnBrowseResultsRenderer";
+const RESPONSIVE_HEADER: &str = "/musicResponsiveHeaderRenderer";
+const STRAPLINE_TEXT: &str = concatcp!("/straplineTextOne", RUN_TEXT);
+const MMRIR: &str = "/musicMultiRowListItemRenderer";
+const EPISODE_DURATION: &str =
+    "/playbackProgress/musicPlaybackProgressRenderer/durationText/runs/1/text";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Podcast {
+    pub title: String,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub episodes: Vec<Episode>,
+}
+/// A podcast episode. Episodes are played the same way as songs, using the video id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Episode {
+    pub title: String,
+    pub description: Option<String>,
+    pub date: Option<String>,
+    pub duration: Option<String>,
+    pub video_id: VideoID<'static>,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+impl<'a> ProcessedResult<GetPodcastQuery<'a>> {
+    // TODO: Continuations
+    pub fn parse(self) -> Result<Podcast> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        let mut two_column = json_crawler.borrow_pointer(TWO_COLUMN)?;
+        let mut header = two_column.borrow_pointer(concatcp!(
+            TAB_CONTENT,
+            SECTION_LIST_ITEM,
+            RESPONSIVE_HEADER
+        ))?;
+        let title = header.take_value_pointer(TITLE_TEXT)?;
+        let author = header.take_value_pointer(STRAPLINE_TEXT).ok();
+        let description = header
+            .take_value_pointer(concatcp!("/description", DESCRIPTION_SHELF, DESCRIPTION))
+            .ok();
+        let episodes = parse_episodes(two_column)?;
+        Ok(Podcast {
+            title,
+            author,
+            description,
+            episodes,
+        })
+    }
+}
+
+impl ProcessedResult<GetNewEpisodesQuery> {
+    pub fn parse(self) -> Result<Vec<Episode>> {
+        let ProcessedResult {
+            mut json_crawler, ..
+        } = self;
+        parse_episodes(json_crawler.borrow_pointer(TWO_COLUMN)?)
+    }
+}
+
+/// The episodes listed beside a podcast or episodes playlist header.
+fn parse_episodes(mut two_column: JsonCrawlerBorrowed) -> Result<Vec<Episode>> {
+    two_column
+        .borrow_pointer(concatcp!(
+            "/secondaryContents",
+            SECTION_LIST_ITEM,
+            MUSIC_SHELF,
+            "/contents"
+        ))?
+        .into_array_iter_mut()?
+        .map(parse_episode)
+        .collect()
+}
+
+fn parse_episode(item: JsonCrawlerBorrowed) -> Result<Episode> {
+    let mut data = item.navigate_pointer(MMRIR)?;
+    let title = data.take_value_pointer(TITLE_TEXT)?;
+    let video_id = data
+        .take_value_pointer("/onTap/watchEndpoint/videoId")
+        .map(|v: String| VideoID::from_raw(v))?;
+    let description = data.take_value_pointer(DESCRIPTION).ok();
+    let date = data.take_value_pointer(SUBTITLE).ok();
+    let duration = data.take_value_pointer(EPISODE_DURATION).ok();
+    let thumbnails = data.take_value_pointer(THUMBNAILS).unwrap_or_default();
+    Ok(Episode {
+        title,
+        description,
+        date,
+        duration,
+        video_id,
+        thumbnails,
+    })
+}
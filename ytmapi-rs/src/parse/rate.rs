@@ -0,0 +1,17 @@
+use super::ProcessedResult;
+use crate::query::{RatePlaylistQuery, RateSongQuery};
+use crate::Result;
+
+impl<'a> ProcessedResult<RateSongQuery<'a>> {
+    // The response contains no useful information, errors are already caught when processing.
+    pub fn parse(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ProcessedResult<RatePlaylistQuery<'a>> {
+    // The response contains no useful information, errors are already caught when processing.
+    pub fn parse(self) -> Result<()> {
+        Ok(())
+    }
+}
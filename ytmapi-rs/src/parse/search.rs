@@ -1,15 +1,15 @@
 use super::{
-    parse_item_text, Parse, ProcessedResult, SearchResultAlbum, SearchResultArtist,
-    SearchResultCommunityPlaylist, SearchResultEpisode, SearchResultFeaturedPlaylist,
-    SearchResultPlaylist, SearchResultPodcast, SearchResultProfile, SearchResultSong,
-    SearchResultType, SearchResultVideo, SearchResults, TopResult, TopResultType,
+    parse_item_text, Parse, ProcessedResult, SearchArtistsParams, SearchResultAlbum,
+    SearchResultArtist, SearchResultCommunityPlaylist, SearchResultEpisode,
+    SearchResultFeaturedPlaylist, SearchResultPlaylist, SearchResultPodcast, SearchResultProfile,
+    SearchResultSong, SearchResultType, SearchResultVideo, SearchResults, TopResult, TopResultType,
 };
 use crate::common::{AlbumType, Explicit, SearchSuggestion, SuggestionType, TextRun};
 use crate::crawler::{JsonCrawler, JsonCrawlerBorrowed};
 use crate::nav_consts::{
     BADGE_LABEL, LIVE_BADGE_LABEL, MUSIC_CARD_SHELF, MUSIC_SHELF, NAVIGATION_BROWSE_ID,
-    PLAYLIST_ITEM_VIDEO_ID, PLAY_BUTTON, SECTION_LIST, SUBTITLE, SUBTITLE2, TAB_CONTENT,
-    THUMBNAILS, TITLE_TEXT,
+    NAVIGATION_VIDEO_ID, PLAYLIST_ITEM_VIDEO_ID, PLAY_BUTTON, SECTION_LIST, SUBTITLE, SUBTITLE2,
+    TAB_CONTENT, THUMBNAILS, TITLE, TITLE_TEXT,
 };
 use crate::parse::EpisodeDate;
 use crate::{query::*, Thumbnail};
@@ -21,6 +21,8 @@ mod tests;
 // watchPlaylistEndpoint params within overlay.
 const FEATURED_PLAYLIST_ENDPOINT_PARAMS: &str = "wAEB";
 const COMMUNITY_PLAYLIST_ENDPOINT_PARAMS: &str = "wAEB8gECKAE%3D";
+const MUSIC_SHELF_CONTINUATION: &str = "/continuationContents/musicShelfContinuation";
+const NEXT_CONTINUATION: &str = "/continuations/0/nextContinuationData/continuation";
 
 // TODO: Type safety
 // TODO: Tests
@@ -174,6 +176,13 @@ fn parse_top_results_from_music_card_shelf_contents(
     let year = None;
     let plays = None;
     let thumbnails: Vec<Thumbnail> = music_shelf_contents.take_value_pointer(THUMBNAILS)?;
+    // The title links to the result.
+    let video_id = music_shelf_contents
+        .take_value_pointer(concatcp!(TITLE, NAVIGATION_VIDEO_ID))
+        .ok();
+    let browse_id = music_shelf_contents
+        .take_value_pointer(concatcp!(TITLE, NAVIGATION_BROWSE_ID))
+        .ok();
     let first_result = TopResult {
         // Assuming that in non-card case top result always has a result type.
         result_type: Some(result_type),
@@ -186,6 +195,8 @@ fn parse_top_results_from_music_card_shelf_contents(
         duration,
         year,
         plays,
+        video_id,
+        browse_id,
     };
     // End - first result parsing.
     // TODO: Improve efficiency.
@@ -249,6 +260,8 @@ fn parse_top_result_from_music_shelf_contents(
         }
     }
     let thumbnails: Vec<Thumbnail> = mrlir.take_value_pointer(THUMBNAILS)?;
+    let video_id = mrlir.take_value_pointer(PLAYLIST_ITEM_VIDEO_ID).ok();
+    let browse_id = mrlir.take_value_pointer(NAVIGATION_BROWSE_ID).ok();
     Ok(TopResult {
         result_type: result_type.ok(),
         subscribers,
@@ -260,6 +273,8 @@ fn parse_top_result_from_music_shelf_contents(
         duration,
         year,
         plays,
+        video_id,
+        browse_id,
     })
 }
 // TODO: Type safety
@@ -707,6 +722,42 @@ impl<'a> Parse for ProcessedResult<SearchQuery<'a, FilteredSearch<ArtistsFilter>
         FilteredSearchMSRContents::try_from(section_contents)?.try_into()
     }
 }
+impl<'a> ProcessedResult<SearchQuery<'a, FilteredSearch<ArtistsFilter>>> {
+    /// Parse the first page of artists, along with the token to get the next page.
+    pub fn parse_with_continuation(self) -> Result<SearchArtistsParams> {
+        let mut section_contents = SectionContentsCrawler::try_from(self)?;
+        if section_contents_is_empty(&section_contents) {
+            return Ok(SearchArtistsParams::default());
+        }
+        let continuation = section_contents
+            .0
+            .take_value_pointer(concatcp!("/musicShelfRenderer", NEXT_CONTINUATION))
+            .ok();
+        let artists: Vec<SearchResultArtist> =
+            FilteredSearchMSRContents::try_from(section_contents)?.try_into()?;
+        Ok(SearchArtistsParams {
+            artists,
+            continuation,
+        })
+    }
+}
+impl ProcessedResult<SearchArtistsContinuationQuery> {
+    pub fn parse(self) -> Result<SearchArtistsParams> {
+        let ProcessedResult { json_crawler, .. } = self;
+        let mut shelf = json_crawler.navigate_pointer(MUSIC_SHELF_CONTINUATION)?;
+        // The last page has no continuation.
+        let continuation = shelf.take_value_pointer(NEXT_CONTINUATION).ok();
+        let artists: Vec<SearchResultArtist> = if shelf.path_exists("/contents") {
+            FilteredSearchMSRContents(shelf.navigate_pointer("/contents")?).try_into()?
+        } else {
+            Vec::new()
+        };
+        Ok(SearchArtistsParams {
+            artists,
+            continuation,
+        })
+    }
+}
 impl<'a> Parse for ProcessedResult<SearchQuery<'a, FilteredSearch<ProfilesFilter>>> {
     type Output = Vec<SearchResultProfile>;
     fn parse(self) -> Result<Self::Output> {
@@ -134,6 +134,27 @@ async fn test_search_artists() {
     assert_eq!(output, expected);
 }
 #[tokio::test]
+async fn test_search_artists_with_continuation() {
+    let source_path = Path::new("./test_json/search_artists_20231226.json");
+    let expected_path = Path::new("./test_json/search_artists_20231226_output.txt");
+    let source = tokio::fs::read_to_string(source_path)
+        .await
+        .expect("Expect file read to pass during tests");
+    let expected = tokio::fs::read_to_string(expected_path)
+        .await
+        .expect("Expect file read to pass during tests");
+    let expected = expected.trim();
+    let json_clone = JsonCloner::from_string(source).unwrap();
+    // Blank query has no bearing on function
+    let query = SearchQuery::new("").with_filter(ArtistsFilter);
+    let output = ProcessedResult::from_raw(JsonCrawler::from_json_cloner(json_clone), query)
+        .parse_with_continuation()
+        .unwrap();
+    assert!(output.continuation.is_some());
+    let output = format!("{:#?}", output.artists);
+    assert_eq!(output, expected);
+}
+#[tokio::test]
 async fn test_search_albums() {
     let source_path = Path::new("./test_json/search_albums_20231226.json");
     let expected_path = Path::new("./test_json/search_albums_20231226_output.txt");
@@ -1,7 +1,14 @@
 //! Type safe queries to pass to the API.
+use crate::common::Locale;
+pub use account::*;
 pub use album::*;
 pub use artist::*;
+pub use charts::*;
+pub use history::*;
 pub use library::*;
+pub use playlist::*;
+pub use podcasts::*;
+pub use rate::*;
 pub use search::*;
 use std::borrow::Cow;
 
@@ -17,6 +24,30 @@ pub trait Query {
     fn header(&self) -> serde_json::Map<String, serde_json::Value>;
     fn params(&self) -> Option<Cow<str>>;
     fn path(&self) -> &str;
+    /// Language and region for this query, overriding those set on the API handle.
+    fn locale(&self) -> Option<&Locale> {
+        None
+    }
+}
+
+pub mod account {
+    use super::Query;
+    use std::borrow::Cow;
+
+    /// Name and handle of the signed in account.
+    /// NOTE: Requires authentication.
+    pub struct GetAccountInfoQuery;
+    impl Query for GetAccountInfoQuery {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            serde_json::Map::new()
+        }
+        fn path(&self) -> &str {
+            "account/account_menu"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
 }
 
 pub mod album {
@@ -53,6 +84,360 @@ pub mod album {
     }
 }
 
+pub mod playlist {
+    use super::Query;
+    use crate::common::{PlaylistID, PrivacyStatus, VideoID, YoutubeID};
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    pub struct GetPlaylistQuery<'a> {
+        id: PlaylistID<'a>,
+    }
+    /// Get the next page of tracks from a playlist.
+    pub struct GetPlaylistContinuationQuery {
+        continuation: String,
+    }
+    impl<'a> Query for GetPlaylistQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            // Browse id for a playlist is the playlist id prefixed with VL.
+            let id = self.id.get_raw();
+            let browse_id = if id.starts_with("VL") {
+                id.to_string()
+            } else {
+                format!("VL{id}")
+            };
+            let serde_json::Value::Object(map) = json!({
+                 "browseId" : browse_id,
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl Query for GetPlaylistContinuationQuery {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let serde_json::Value::Object(map) = json!({
+                 "continuation" : self.continuation,
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> GetPlaylistQuery<'a> {
+        pub fn new<T: Into<PlaylistID<'a>>>(id: T) -> GetPlaylistQuery<'a> {
+            GetPlaylistQuery { id: id.into() }
+        }
+    }
+    impl GetPlaylistContinuationQuery {
+        pub fn new(continuation: String) -> GetPlaylistContinuationQuery {
+            GetPlaylistContinuationQuery { continuation }
+        }
+    }
+    /// Add songs to a playlist owned by the user. Songs already on the playlist are skipped.
+    /// NOTE: Requires authentication.
+    pub struct AddPlaylistItemsQuery<'a> {
+        id: PlaylistID<'a>,
+        video_ids: Vec<VideoID<'a>>,
+    }
+    impl<'a> Query for AddPlaylistItemsQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            // Unlike browsing, editing requires the playlist id without the VL prefix.
+            let id = self.id.get_raw();
+            let id = id.strip_prefix("VL").unwrap_or(id);
+            let actions: Vec<_> = self
+                .video_ids
+                .iter()
+                .map(|v| {
+                    json!({
+                        "action" : "ACTION_ADD_VIDEO",
+                        "addedVideoId" : v.get_raw(),
+                        "dedupeOption" : "DEDUPE_OPTION_SKIP",
+                    })
+                })
+                .collect();
+            let serde_json::Value::Object(map) = json!({
+                 "playlistId" : id,
+                 "actions" : actions,
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse/edit_playlist"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    /// Create a playlist owned by the user, optionally with songs. Playlists are private
+    /// unless another privacy status is set.
+    /// NOTE: Requires authentication.
+    pub struct CreatePlaylistQuery<'a> {
+        title: String,
+        description: String,
+        privacy_status: PrivacyStatus,
+        video_ids: Vec<VideoID<'a>>,
+    }
+    impl<'a> Query for CreatePlaylistQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let mut value = json!({
+                "title" : self.title,
+                "description" : self.description,
+                "privacyStatus" : self.privacy_status,
+            });
+            if !self.video_ids.is_empty() {
+                let video_ids: Vec<_> = self.video_ids.iter().map(|v| v.get_raw()).collect();
+                value["videoIds"] = json!(video_ids);
+            }
+            let serde_json::Value::Object(map) = value else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "playlist/create"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> CreatePlaylistQuery<'a> {
+        pub fn new<S: Into<String>>(title: S) -> CreatePlaylistQuery<'a> {
+            CreatePlaylistQuery {
+                title: title.into(),
+                description: String::new(),
+                privacy_status: PrivacyStatus::default(),
+                video_ids: Vec::new(),
+            }
+        }
+        pub fn with_description<S: Into<String>>(self, description: S) -> CreatePlaylistQuery<'a> {
+            CreatePlaylistQuery {
+                description: description.into(),
+                ..self
+            }
+        }
+        pub fn with_privacy_status(self, privacy_status: PrivacyStatus) -> CreatePlaylistQuery<'a> {
+            CreatePlaylistQuery {
+                privacy_status,
+                ..self
+            }
+        }
+        pub fn with_video_ids(self, video_ids: Vec<VideoID<'a>>) -> CreatePlaylistQuery<'a> {
+            CreatePlaylistQuery { video_ids, ..self }
+        }
+    }
+    impl<'a> AddPlaylistItemsQuery<'a> {
+        pub fn new<T: Into<PlaylistID<'a>>>(
+            id: T,
+            video_ids: Vec<VideoID<'a>>,
+        ) -> AddPlaylistItemsQuery<'a> {
+            AddPlaylistItemsQuery {
+                id: id.into(),
+                video_ids,
+            }
+        }
+    }
+    /// Edit the settings of a playlist owned by the user. Settings that aren't set are left
+    /// unchanged.
+    /// NOTE: Requires authentication.
+    pub struct EditPlaylistQuery<'a> {
+        id: PlaylistID<'a>,
+        privacy_status: Option<PrivacyStatus>,
+    }
+    impl<'a> Query for EditPlaylistQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            // Unlike browsing, editing requires the playlist id without the VL prefix.
+            let id = self.id.get_raw();
+            let id = id.strip_prefix("VL").unwrap_or(id);
+            let mut actions = Vec::new();
+            if let Some(privacy_status) = self.privacy_status {
+                actions.push(json!({
+                    "action" : "ACTION_SET_PLAYLIST_PRIVACY",
+                    "playlistPrivacy" : privacy_status,
+                }));
+            }
+            let serde_json::Value::Object(map) = json!({
+                 "playlistId" : id,
+                 "actions" : actions,
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse/edit_playlist"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> EditPlaylistQuery<'a> {
+        pub fn new<T: Into<PlaylistID<'a>>>(id: T) -> EditPlaylistQuery<'a> {
+            EditPlaylistQuery {
+                id: id.into(),
+                privacy_status: None,
+            }
+        }
+        pub fn with_privacy_status(self, privacy_status: PrivacyStatus) -> EditPlaylistQuery<'a> {
+            EditPlaylistQuery {
+                privacy_status: Some(privacy_status),
+                ..self
+            }
+        }
+    }
+}
+
+pub mod charts {
+    use super::Query;
+    use crate::common::Locale;
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    /// Get the top charts. If no country is set, the global charts will be returned.
+    #[derive(Default)]
+    pub struct GetChartsQuery<'a> {
+        country: Option<Cow<'a, str>>,
+        locale: Option<Locale>,
+    }
+    impl<'a> Query for GetChartsQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let value = match &self.country {
+                Some(country) => json!({
+                    "browseId" : "FEmusic_charts",
+                    "formData" : {"selectedValues": [country]}
+                }),
+                None => json!({
+                    "browseId" : "FEmusic_charts",
+                }),
+            };
+            let serde_json::Value::Object(map) = value else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+        fn locale(&self) -> Option<&Locale> {
+            self.locale.as_ref()
+        }
+    }
+    impl<'a> GetChartsQuery<'a> {
+        pub fn new() -> GetChartsQuery<'a> {
+            Default::default()
+        }
+        /// Country should be an ISO 3166-1 Alpha-2 country code, or ZZ for global.
+        pub fn with_country<S: Into<Cow<'a, str>>>(self, country: S) -> GetChartsQuery<'a> {
+            GetChartsQuery {
+                country: Some(country.into()),
+                ..self
+            }
+        }
+        /// Set the language and region of the results, e.g for the names of the charts.
+        pub fn with_locale(self, locale: Locale) -> GetChartsQuery<'a> {
+            GetChartsQuery {
+                locale: Some(locale),
+                ..self
+            }
+        }
+    }
+}
+
+pub mod podcasts {
+    use super::Query;
+    use crate::common::{PodcastID, YoutubeID};
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    /// Get the podcasts saved to the user's library.
+    /// NOTE: Requires authentication.
+    pub struct GetLibraryPodcastsQuery;
+    pub struct GetPodcastQuery<'a> {
+        id: PodcastID<'a>,
+    }
+    /// Get the auto-generated "New Episodes" playlist, with the latest episodes of the
+    /// podcasts the user is subscribed to.
+    /// NOTE: Requires authentication.
+    pub struct GetNewEpisodesQuery;
+    impl Query for GetLibraryPodcastsQuery {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let serde_json::Value::Object(map) = json!({
+                 "browseId" : "FEmusic_library_non_music_audio_list"
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl Query for GetNewEpisodesQuery {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let serde_json::Value::Object(map) = json!({
+                 "browseId" : "VLRDPN"
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> Query for GetPodcastQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            // Browse id for a podcast is the podcast's playlist id prefixed with MPSP.
+            let id = self.id.get_raw();
+            let browse_id = if id.starts_with("MPSP") {
+                id.to_string()
+            } else {
+                format!("MPSP{id}")
+            };
+            let serde_json::Value::Object(map) = json!({
+                 "browseId" : browse_id,
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> GetPodcastQuery<'a> {
+        pub fn new<T: Into<PodcastID<'a>>>(id: T) -> GetPodcastQuery<'a> {
+            GetPodcastQuery { id: id.into() }
+        }
+    }
+}
+
 // For future use.
 pub mod continuations {
     use std::borrow::Cow;
@@ -83,6 +468,144 @@ pub mod continuations {
     }
 }
 
+pub mod rate {
+    use super::Query;
+    use crate::common::{LikeStatus, PlaylistID, YoutubeID};
+    use crate::VideoID;
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    /// Like, dislike, or remove the rating from a song.
+    /// NOTE: Requires authentication.
+    pub struct RateSongQuery<'a> {
+        video_id: VideoID<'a>,
+        rating: LikeStatus,
+    }
+    impl<'a> Query for RateSongQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let serde_json::Value::Object(map) = json!({
+                "target" : {"videoId" : self.video_id.get_raw()},
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            match self.rating {
+                LikeStatus::Liked => "like/like",
+                LikeStatus::Disliked => "like/dislike",
+                LikeStatus::Indifferent => "like/removelike",
+            }
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> RateSongQuery<'a> {
+        pub fn new(video_id: VideoID<'a>, rating: LikeStatus) -> RateSongQuery<'a> {
+            RateSongQuery { video_id, rating }
+        }
+    }
+    /// Like, dislike, or remove the rating from a playlist. Albums are rated using their audio
+    /// playlist.
+    /// NOTE: Requires authentication.
+    pub struct RatePlaylistQuery<'a> {
+        playlist_id: PlaylistID<'a>,
+        rating: LikeStatus,
+    }
+    impl<'a> Query for RatePlaylistQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            // Like editing, rating requires the playlist id without the VL prefix.
+            let id = self.playlist_id.get_raw();
+            let id = id.strip_prefix("VL").unwrap_or(id);
+            let serde_json::Value::Object(map) = json!({
+                "target" : {"playlistId" : id},
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            match self.rating {
+                LikeStatus::Liked => "like/like",
+                LikeStatus::Disliked => "like/dislike",
+                LikeStatus::Indifferent => "like/removelike",
+            }
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> RatePlaylistQuery<'a> {
+        pub fn new<T: Into<PlaylistID<'a>>>(
+            playlist_id: T,
+            rating: LikeStatus,
+        ) -> RatePlaylistQuery<'a> {
+            RatePlaylistQuery {
+                playlist_id: playlist_id.into(),
+                rating,
+            }
+        }
+    }
+}
+
+pub mod history {
+    use super::Query;
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    /// Songs the user has played, most recent first.
+    /// NOTE: Requires authentication.
+    pub struct GetHistoryQuery;
+    impl Query for GetHistoryQuery {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let serde_json::Value::Object(map) = json!({
+                 "browseId" : "FEmusic_history"
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "browse"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+
+    /// Remove songs from the user's history, using the feedback tokens from GetHistoryQuery.
+    /// NOTE: Requires authentication.
+    pub struct RemoveHistoryItemsQuery<'a> {
+        feedback_tokens: Vec<Cow<'a, str>>,
+    }
+    impl<'a> Query for RemoveHistoryItemsQuery<'a> {
+        fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+            let serde_json::Value::Object(map) = json!({
+                 "feedbackTokens" : self.feedback_tokens,
+            }) else {
+                unreachable!("Created a map");
+            };
+            map
+        }
+        fn path(&self) -> &str {
+            "feedback"
+        }
+        fn params(&self) -> Option<Cow<str>> {
+            None
+        }
+    }
+    impl<'a> RemoveHistoryItemsQuery<'a> {
+        pub fn new<T: Into<Cow<'a, str>>>(
+            feedback_tokens: impl IntoIterator<Item = T>,
+        ) -> RemoveHistoryItemsQuery<'a> {
+            RemoveHistoryItemsQuery {
+                feedback_tokens: feedback_tokens.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+}
+
 pub mod lyrics {
 
     use std::borrow::Cow;
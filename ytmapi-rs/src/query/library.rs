@@ -1,6 +1,8 @@
 // NOTE: Authentication is required to use the queries in this module.
 // Currently, all queries are implemented with authentication however in future this could be scaled back.
 use super::Query;
+use crate::common::YoutubeID;
+use crate::ChannelID;
 use serde_json::json;
 use std::borrow::Cow;
 
@@ -21,6 +23,24 @@ impl Query for GetLibraryPlaylistsQuery {
         None
     }
 }
+/// Albums saved to the user's library.
+pub struct GetLibraryAlbumsQuery;
+impl Query for GetLibraryAlbumsQuery {
+    fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+        let serde_json::Value::Object(map) = json!({
+             "browseId" : "FEmusic_liked_albums"
+        }) else {
+            unreachable!("Created a map");
+        };
+        map
+    }
+    fn path(&self) -> &str {
+        "browse"
+    }
+    fn params(&self) -> Option<Cow<str>> {
+        None
+    }
+}
 #[derive(Default)]
 pub enum LibraryArtistsSortOrder {
     NameAsc,
@@ -63,3 +83,72 @@ impl Query for GetLibraryArtistsQuery {
         }
     }
 }
+
+/// Artists the user is subscribed to.
+pub struct GetLibrarySubscriptionsQuery;
+impl Query for GetLibrarySubscriptionsQuery {
+    fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+        let serde_json::Value::Object(map) = json!({
+             "browseId" : "FEmusic_library_corpus_artists"
+        }) else {
+            unreachable!("Created a map");
+        };
+        map
+    }
+    fn path(&self) -> &str {
+        "browse"
+    }
+    fn params(&self) -> Option<Cow<str>> {
+        None
+    }
+}
+
+pub struct SubscribeArtistQuery<'a> {
+    channel_id: ChannelID<'a>,
+}
+impl<'a> SubscribeArtistQuery<'a> {
+    pub fn new(channel_id: ChannelID<'a>) -> SubscribeArtistQuery<'a> {
+        SubscribeArtistQuery { channel_id }
+    }
+}
+impl<'a> Query for SubscribeArtistQuery<'a> {
+    fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+        let serde_json::Value::Object(map) = json!({
+             "channelIds" : [self.channel_id.get_raw()]
+        }) else {
+            unreachable!("Created a map");
+        };
+        map
+    }
+    fn path(&self) -> &str {
+        "subscription/subscribe"
+    }
+    fn params(&self) -> Option<Cow<str>> {
+        None
+    }
+}
+
+pub struct UnsubscribeArtistQuery<'a> {
+    channel_id: ChannelID<'a>,
+}
+impl<'a> UnsubscribeArtistQuery<'a> {
+    pub fn new(channel_id: ChannelID<'a>) -> UnsubscribeArtistQuery<'a> {
+        UnsubscribeArtistQuery { channel_id }
+    }
+}
+impl<'a> Query for UnsubscribeArtistQuery<'a> {
+    fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+        let serde_json::Value::Object(map) = json!({
+             "channelIds" : [self.channel_id.get_raw()]
+        }) else {
+            unreachable!("Created a map");
+        };
+        map
+    }
+    fn path(&self) -> &str {
+        "subscription/unsubscribe"
+    }
+    fn params(&self) -> Option<Cow<str>> {
+        None
+    }
+}
@@ -11,6 +11,7 @@ pub struct SearchQuery<'a, S: SearchType> {
     query: Cow<'a, str>,
     spelling_mode: SpellingMode,
     searchtype: S,
+    locale: Option<Locale>,
 }
 
 // TODO Seal
@@ -206,6 +207,9 @@ impl<'a, S: SearchType> Query for SearchQuery<'a, S> {
     fn params(&self) -> Option<Cow<str>> {
         self.searchtype.specialised_params(&self.spelling_mode)
     }
+    fn locale(&self) -> Option<&Locale> {
+        self.locale.as_ref()
+    }
 }
 
 // This currently requires type annotations.
@@ -216,6 +220,7 @@ impl<'a, Q: Into<Cow<'a, str>>, S: SearchType> From<Q> for SearchQuery<'a, S> {
             query: value.into(),
             spelling_mode: SpellingMode::default(),
             searchtype: S::default(),
+            locale: None,
         }
     }
 }
@@ -227,6 +232,7 @@ impl<'a> SearchQuery<'a, BasicSearch> {
             query: q.into(),
             spelling_mode: SpellingMode::default(),
             searchtype: BasicSearch {},
+            locale: None,
         }
     }
 }
@@ -237,6 +243,11 @@ impl<'a, S: SearchType> SearchQuery<'a, S> {
         self.spelling_mode = spelling_mode;
         self
     }
+    /// Set the language and region of the results, e.g to search for songs in another market.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
     /// Chnage the set query.
     pub fn with_query<Q: Into<Cow<'a, str>>>(mut self, query: Q) -> Self {
         self.query = query.into();
@@ -253,6 +264,7 @@ impl<'a> SearchQuery<'a, BasicSearch> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: FilteredSearch { filter },
         }
     }
@@ -261,6 +273,7 @@ impl<'a> SearchQuery<'a, BasicSearch> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: UploadSearch,
         }
     }
@@ -269,6 +282,7 @@ impl<'a> SearchQuery<'a, BasicSearch> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: LibrarySearch,
         }
     }
@@ -283,6 +297,7 @@ impl<'a, F: FilteredSearchType> SearchQuery<'a, FilteredSearch<F>> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: FilteredSearch { filter },
         }
     }
@@ -291,6 +306,7 @@ impl<'a, F: FilteredSearchType> SearchQuery<'a, FilteredSearch<F>> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: BasicSearch,
         }
     }
@@ -302,6 +318,7 @@ impl<'a> SearchQuery<'a, UploadSearch> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: BasicSearch,
         }
     }
@@ -312,12 +329,37 @@ impl<'a> SearchQuery<'a, LibrarySearch> {
         SearchQuery {
             query: self.query,
             spelling_mode: self.spelling_mode,
+            locale: self.locale,
             searchtype: BasicSearch,
         }
     }
 }
 
 #[derive(PartialEq, Debug, Clone)]
+/// Get the next page of results from an artist search.
+pub struct SearchArtistsContinuationQuery {
+    continuation: String,
+}
+
+impl SearchArtistsContinuationQuery {
+    pub fn new(continuation: String) -> SearchArtistsContinuationQuery {
+        SearchArtistsContinuationQuery { continuation }
+    }
+}
+
+impl Query for SearchArtistsContinuationQuery {
+    fn header(&self) -> serde_json::Map<String, serde_json::Value> {
+        let value = self.continuation.as_str().into();
+        serde_json::Map::from_iter([("continuation".into(), value)])
+    }
+    fn path(&self) -> &str {
+        "search"
+    }
+    fn params(&self) -> Option<Cow<str>> {
+        None
+    }
+}
+
 pub struct GetSearchSuggestionsQuery<'a> {
     query: Cow<'a, str>,
 }
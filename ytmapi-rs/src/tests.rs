@@ -267,6 +267,54 @@ async fn test_get_library_artists() {
     assert!(res.len() > 0);
 }
 #[tokio::test]
+async fn test_get_playlist() {
+    let api = new_standard_api().await.unwrap();
+    let playlists = api.get_library_playlists().await.unwrap();
+    let playlist = api
+        .get_playlist(GetPlaylistQuery::new(&playlists[0].playlist_id))
+        .await
+        .unwrap();
+    assert!(!playlist.title.is_empty());
+    // Large playlists are paged, so continue to the next page if required.
+    if let Some(continuation) = playlist.continuation {
+        api.get_playlist_continuation(GetPlaylistContinuationQuery::new(continuation))
+            .await
+            .unwrap();
+    }
+}
+#[tokio::test]
+async fn test_get_charts() {
+    let api = new_standard_api().await.unwrap();
+    let res = api.get_charts(GetChartsQuery::new()).await.unwrap();
+    assert!(!res.artists.is_empty());
+    let country = res.country_options.last().unwrap().clone();
+    let res = api
+        .get_charts(GetChartsQuery::new().with_country(country))
+        .await
+        .unwrap();
+    assert!(!res.artists.is_empty());
+}
+#[tokio::test]
+async fn test_get_library_podcasts() {
+    let api = new_standard_api().await.unwrap();
+    let _res = api.get_library_podcasts().await.unwrap();
+}
+#[tokio::test]
+async fn test_get_podcast() {
+    let api = new_standard_api().await.unwrap();
+    let podcasts = api.search_podcasts("Beatles").await.unwrap();
+    let res = api
+        .get_podcast(GetPodcastQuery::new(&podcasts[0].podcast_id))
+        .await
+        .unwrap();
+    assert!(!res.episodes.is_empty());
+}
+#[tokio::test]
+async fn test_get_new_episodes() {
+    let api = new_standard_api().await.unwrap();
+    let _res = api.get_new_episodes().await.unwrap();
+}
+#[tokio::test]
 async fn test_watch_playlist() {
     // TODO: Make more generic
     let api = new_standard_api().await.unwrap();
@@ -300,6 +348,7 @@ async fn test_get_lyrics() {
     let example = Lyrics {
             lyrics: "You're my lesson I had to learn\nAnother page I'll have to turn\nI got one more message, always tryna be heard\nBut you never listen to a word\n\nHeaven knows we came so close\nBut this ain't real, it's just a dream\nWake me up, I've been fast asleep\nLetting go of fantasies\nBeen caught up in who I needed you to be\nHow foolish of me\n\nFoolish of me\nFoolish of me\nFoolish of me\nFoolish of me\n\nJust give me one second and I'll be fine\nJust let me catch my breath and come back to life\nI finally get the message, you were never meant to be mine\nCouldn't see the truth, I was blind (meant to be mine)\n\nWhoa, heaven knows we came so close\nBut this ain't real, it's just a dream\nWake me up, I've been fast asleep\nLetting go of fantasies\nBeen caught up in who I needed you to be\nHow foolish of me\n\nFoolish of me\nFoolish of me\nFoolish of me\nFoolish of me\n\nLetting go, we came so close (how foolish of me)\nOh, I'm letting go of fantasies\nBeen caught up in who I needed you to be\nHow foolish of me".into(),
             source: "Source: Musixmatch".into(),
+            timed_lyrics: None,
         };
     assert_eq!(res, example)
 }
@@ -432,3 +481,27 @@ async fn test_get_artist_album_songs() {
     let _res = res.parse().unwrap();
     println!("Process album took {} ms", now.elapsed().as_millis());
 }
+#[tokio::test]
+async fn test_offline_responses() {
+    let account_info = std::fs::read_to_string("test_json/get_account_info.json").unwrap();
+    let responses = OfflineResponses::new(move |path, _, _| {
+        (path == "account/account_menu").then(|| account_info.clone())
+    });
+    let api = YtMusic::from_offline_responses(responses);
+    api.get_account_info().await.unwrap();
+    assert!(api.get_library_playlists().await.is_err());
+}
+#[tokio::test]
+async fn test_get_albums_offline() {
+    let api = YtMusic::from_offline_responses(OfflineResponses::new(|_, _, _| None));
+    let ids: Vec<_> = (0..5)
+        .map(|i| AlbumID::from_raw(format!("MPREb_{i}")))
+        .collect();
+    let albums: Vec<_> = api.get_albums(ids.clone(), 2).collect().await;
+    assert_eq!(albums.len(), ids.len());
+    for id in ids {
+        assert!(albums
+            .iter()
+            .any(|(album_id, album)| *album_id == id && album.is_err()));
+    }
+}